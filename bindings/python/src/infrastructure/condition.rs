@@ -1,6 +1,7 @@
 use pyo3::prelude::*;
 
 use super::{error::into_pyerr, status::StatusKind};
+use crate::subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind};
 
 #[pyclass]
 #[derive(Clone)]
@@ -42,3 +43,82 @@ impl StatusCondition {
         self.0.get_trigger_value().map_err(into_pyerr)
     }
 }
+
+#[pyclass]
+#[derive(Clone)]
+pub struct ReadCondition(dust_dds::infrastructure::condition::ReadCondition);
+
+impl From<dust_dds::infrastructure::condition::ReadCondition> for ReadCondition {
+    fn from(value: dust_dds::infrastructure::condition::ReadCondition) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ReadCondition> for dust_dds::infrastructure::condition::ReadCondition {
+    fn from(value: ReadCondition) -> Self {
+        value.0
+    }
+}
+
+#[pymethods]
+impl ReadCondition {
+    pub fn get_sample_state_mask(&self) -> Vec<SampleStateKind> {
+        self.0
+            .get_sample_state_mask()
+            .iter()
+            .map(|&s| SampleStateKind::from(s))
+            .collect()
+    }
+
+    pub fn get_view_state_mask(&self) -> Vec<ViewStateKind> {
+        self.0
+            .get_view_state_mask()
+            .iter()
+            .map(|&s| ViewStateKind::from(s))
+            .collect()
+    }
+
+    pub fn get_instance_state_mask(&self) -> Vec<InstanceStateKind> {
+        self.0
+            .get_instance_state_mask()
+            .iter()
+            .map(|&s| InstanceStateKind::from(s))
+            .collect()
+    }
+
+    pub fn get_trigger_value(&self) -> PyResult<bool> {
+        self.0.get_trigger_value().map_err(into_pyerr)
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct GuardCondition(dust_dds::infrastructure::condition::GuardCondition);
+
+impl From<dust_dds::infrastructure::condition::GuardCondition> for GuardCondition {
+    fn from(value: dust_dds::infrastructure::condition::GuardCondition) -> Self {
+        Self(value)
+    }
+}
+
+impl From<GuardCondition> for dust_dds::infrastructure::condition::GuardCondition {
+    fn from(value: GuardCondition) -> Self {
+        value.0
+    }
+}
+
+#[pymethods]
+impl GuardCondition {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_trigger_value(&self, value: bool) -> PyResult<()> {
+        self.0.set_trigger_value(value).map_err(into_pyerr)
+    }
+
+    pub fn get_trigger_value(&self) -> PyResult<bool> {
+        self.0.get_trigger_value().map_err(into_pyerr)
+    }
+}
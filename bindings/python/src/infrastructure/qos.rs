@@ -70,6 +70,8 @@ impl DomainParticipantQos {
         Self(dust_dds::infrastructure::qos::DomainParticipantQos {
             user_data: user_data.clone().into(),
             entity_factory: entity_factory.into(),
+            entity_name: Default::default(),
+            property: Default::default(),
         })
     }
 
@@ -247,6 +249,7 @@ impl TopicQos {
         Self(dust_dds::infrastructure::qos::TopicQos {
             topic_data: topic_data.into(),
             durability: durability.into(),
+            durability_service: Default::default(),
             deadline: deadline.into(),
             latency_budget: latency_budget.into(),
             liveliness: liveliness.into(),
@@ -366,6 +369,7 @@ impl DataWriterQos {
     ) -> Self {
         Self(dust_dds::infrastructure::qos::DataWriterQos {
             durability: durability.into(),
+            durability_service: Default::default(),
             deadline: deadline.into(),
             latency_budget: latency_budget.into(),
             liveliness: liveliness.into(),
@@ -380,6 +384,14 @@ impl DataWriterQos {
             ownership_strength: ownership_strength.into(),
             writer_data_lifecycle: writer_data_lifecycle.into(),
             representation: representation.into(),
+            entity_name: Default::default(),
+            property: Default::default(),
+            disable_positive_acks: Default::default(),
+            history_overflow: Default::default(),
+            locator_selection: Default::default(),
+            publish_rate: Default::default(),
+            delivery_mode: Default::default(),
+            historical_data: Default::default(),
         })
     }
 
@@ -500,6 +512,9 @@ impl DataReaderQos {
             time_based_filter: time_based_filter.into(),
             reader_data_lifecycle: reader_data_lifecycle.into(),
             representation: representation.into(),
+            entity_name: Default::default(),
+            property: Default::default(),
+            disable_positive_acks: Default::default(),
         })
     }
 
@@ -1,11 +1,18 @@
 use pyo3::prelude::*;
 
-use super::{condition::StatusCondition, error::into_pyerr, time::Duration};
+use super::{
+    condition::{GuardCondition, ReadCondition, StatusCondition},
+    error::into_pyerr,
+    time::Duration,
+};
 
 #[pyclass]
 #[derive(Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum Condition {
     StatusCondition { condition: StatusCondition },
+    ReadCondition { condition: ReadCondition },
+    GuardCondition { condition: GuardCondition },
 }
 
 impl From<dust_dds::infrastructure::wait_set::Condition> for Condition {
@@ -16,6 +23,16 @@ impl From<dust_dds::infrastructure::wait_set::Condition> for Condition {
                     condition: c.into(),
                 }
             }
+            dust_dds::infrastructure::wait_set::Condition::ReadCondition(c) => {
+                Condition::ReadCondition {
+                    condition: c.into(),
+                }
+            }
+            dust_dds::infrastructure::wait_set::Condition::GuardCondition(c) => {
+                Condition::GuardCondition {
+                    condition: c.into(),
+                }
+            }
         }
     }
 }
@@ -26,6 +43,12 @@ impl From<Condition> for dust_dds::infrastructure::wait_set::Condition {
             Condition::StatusCondition { condition } => {
                 dust_dds::infrastructure::wait_set::Condition::StatusCondition(condition.into())
             }
+            Condition::ReadCondition { condition } => {
+                dust_dds::infrastructure::wait_set::Condition::ReadCondition(condition.into())
+            }
+            Condition::GuardCondition { condition } => {
+                dust_dds::infrastructure::wait_set::Condition::GuardCondition(condition.into())
+            }
         }
     }
 }
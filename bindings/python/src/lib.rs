@@ -31,6 +31,7 @@ fn dust_dds(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<infrastructure::time::DurationKind>()?;
     m.add_class::<infrastructure::status::StatusKind>()?;
     m.add_class::<infrastructure::wait_set::Condition>()?;
+    m.add_class::<infrastructure::condition::GuardCondition>()?;
     m.add_class::<infrastructure::wait_set::WaitSet>()?;
 
     // Add QosPolicy classes
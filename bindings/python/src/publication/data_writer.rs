@@ -1,7 +1,8 @@
-use pyo3::prelude::*;
+use pyo3::{exceptions::PyTypeError, prelude::*};
 
 use crate::{
     builtin_topics::SubscriptionBuiltinTopicData,
+    domain::domain_participant::DomainParticipant,
     infrastructure::{
         condition::StatusCondition,
         error::into_pyerr,
@@ -87,8 +88,15 @@ impl DataWriter {
             .map_err(into_pyerr)
     }
 
-    pub fn get_key_value(&self, _key_holder: Py<PyAny>, _handle: InstanceHandle) -> PyResult<()> {
-        unimplemented!()
+    pub fn get_key_value(&self, key_holder: Py<PyAny>, handle: InstanceHandle) -> PyResult<()> {
+        let type_ = DomainParticipant::get_type(&self.0.get_topic().get_type_name())
+            .ok_or(PyTypeError::new_err("Type information not found"))?;
+        let mut key_value =
+            PythonDdsData::from_py_object(Python::with_gil(|py| key_holder.clone_ref(py)))?;
+        self.0
+            .get_key_value(&mut key_value, handle.into())
+            .map_err(into_pyerr)?;
+        key_value.update_py_object(&key_holder, &type_)
     }
 
     pub fn lookup_instance(&self, instance: Py<PyAny>) -> PyResult<Option<InstanceHandle>> {
@@ -7,7 +7,7 @@ use crate::{
     builtin_topics::PublicationBuiltinTopicData,
     domain::domain_participant::DomainParticipant,
     infrastructure::{
-        condition::StatusCondition,
+        condition::{ReadCondition, StatusCondition},
         error::into_pyerr,
         instance::InstanceHandle,
         qos::DataReaderQos,
@@ -341,12 +341,23 @@ impl DataReader {
         }
     }
 
-    pub fn get_key_value(&self, _key_holder: Py<PyAny>, _handle: InstanceHandle) -> PyResult<()> {
-        unimplemented!()
+    pub fn get_key_value(&self, key_holder: Py<PyAny>, handle: InstanceHandle) -> PyResult<()> {
+        let type_ = DomainParticipant::get_type(&self.0.get_topicdescription().get_type_name())
+            .ok_or(PyTypeError::new_err("Type information not found"))?;
+        let mut key_value =
+            PythonDdsData::from_py_object(Python::with_gil(|py| key_holder.clone_ref(py)))?;
+        self.0
+            .get_key_value(&mut key_value, handle.into())
+            .map_err(into_pyerr)?;
+        key_value.update_py_object(&key_holder, &type_)
     }
 
-    pub fn lookup_instance(&self, _instance: Py<PyAny>) -> PyResult<Option<InstanceHandle>> {
-        unimplemented!()
+    pub fn lookup_instance(&self, instance: Py<PyAny>) -> PyResult<Option<InstanceHandle>> {
+        Ok(self
+            .0
+            .lookup_instance(&PythonDdsData::from_py_object(instance)?)
+            .map_err(into_pyerr)?
+            .map(InstanceHandle::from))
     }
 
     pub fn get_liveliness_changed_status(&self) -> PyResult<LivelinessChangedStatus> {
@@ -468,6 +479,42 @@ impl DataReader {
         self.0.set_listener(listener, &mask).map_err(into_pyerr)
     }
 
+    #[pyo3(signature = (
+        sample_states=ANY_SAMPLE_STATE.to_vec(),
+        view_states=ANY_VIEW_STATE.to_vec(),
+        instance_states=ANY_INSTANCE_STATE.to_vec(),
+    ))]
+    pub fn create_readcondition(
+        &self,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+    ) -> PyResult<ReadCondition> {
+        let sample_states: Vec<_> = sample_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::SampleStateKind::from)
+            .collect();
+        let view_states: Vec<_> = view_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::ViewStateKind::from)
+            .collect();
+        let instance_states: Vec<_> = instance_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::InstanceStateKind::from)
+            .collect();
+        Ok(self
+            .0
+            .create_readcondition(&sample_states, &view_states, &instance_states)
+            .map_err(into_pyerr)?
+            .into())
+    }
+
+    pub fn delete_readcondition(&self, a_condition: ReadCondition) -> PyResult<()> {
+        self.0
+            .delete_readcondition(a_condition.into())
+            .map_err(into_pyerr)
+    }
+
     pub fn get_statuscondition(&self) -> StatusCondition {
         self.0.get_statuscondition().into()
     }
@@ -13,7 +13,12 @@ use crate::{
 };
 
 use super::{
-    data_reader::DataReader, data_reader_listener::DataReaderListener,
+    data_reader::DataReader,
+    data_reader_listener::DataReaderListener,
+    sample_info::{
+        InstanceStateKind, SampleStateKind, ViewStateKind, ANY_INSTANCE_STATE, ANY_SAMPLE_STATE,
+        ANY_VIEW_STATE,
+    },
     subcriber_listener::SubscriberListener,
 };
 
@@ -86,6 +91,46 @@ impl Subscriber {
             .map(DataReader::from))
     }
 
+    pub fn begin_access(&self) -> PyResult<()> {
+        self.0.begin_access().map_err(into_pyerr)
+    }
+
+    pub fn end_access(&self) -> PyResult<()> {
+        self.0.end_access().map_err(into_pyerr)
+    }
+
+    #[pyo3(signature = (
+        sample_states=ANY_SAMPLE_STATE.to_vec(),
+        view_states=ANY_VIEW_STATE.to_vec(),
+        instance_states=ANY_INSTANCE_STATE.to_vec(),
+    ))]
+    pub fn get_datareaders(
+        &self,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+    ) -> PyResult<Vec<DataReader>> {
+        let sample_states: Vec<_> = sample_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::SampleStateKind::from)
+            .collect();
+        let view_states: Vec<_> = view_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::ViewStateKind::from)
+            .collect();
+        let instance_states: Vec<_> = instance_states
+            .into_iter()
+            .map(dust_dds::subscription::sample_info::InstanceStateKind::from)
+            .collect();
+        Ok(self
+            .0
+            .get_datareaders::<PythonDdsData>(&sample_states, &view_states, &instance_states)
+            .map_err(into_pyerr)?
+            .into_iter()
+            .map(DataReader::from)
+            .collect())
+    }
+
     pub fn notify_datareaders(&self) -> PyResult<()> {
         self.0.notify_datareaders().map_err(into_pyerr)
     }
@@ -448,6 +448,22 @@ impl PythonDdsData {
     }
 }
 
+impl PythonDdsData {
+    // Sets the members of an existing object instead of creating a new one
+    pub fn update_py_object(self, py_object: &Py<PyAny>, type_: &Py<PyAny>) -> PyResult<()> {
+        let value = self.into_py_object(type_)?;
+        Python::with_gil(|py| {
+            let annotations = type_.bind(py).getattr("__annotations__")?;
+            let annotation_dict = annotations.downcast::<PyDict>().map_err(PyErr::from)?;
+            for member_name in annotation_dict.keys() {
+                let member_name = member_name.downcast::<PyString>()?;
+                py_object.setattr(py, member_name, value.getattr(py, member_name)?)?;
+            }
+            Ok(())
+        })
+    }
+}
+
 impl DdsSerialize for PythonDdsData {
     fn serialize_data(&self) -> DdsResult<Vec<u8>> {
         Ok(self.data.clone())
@@ -1,24 +1,29 @@
 use crate::{
     implementation::data_representation_builtin_endpoints::{
         parameter_id_values::{
-            PID_DATA_REPRESENTATION, PID_DEADLINE, PID_DESTINATION_ORDER, PID_DURABILITY,
-            PID_ENDPOINT_GUID, PID_GROUP_DATA, PID_HISTORY, PID_LATENCY_BUDGET, PID_LIFESPAN,
+            PID_DATA_REPRESENTATION, PID_DEADLINE, PID_DESTINATION_ORDER,
+            PID_DISABLE_POSITIVE_ACKS, PID_DURABILITY, PID_DURABILITY_SERVICE, PID_ENDPOINT_GUID,
+            PID_ENTITY_NAME, PID_GROUP_DATA, PID_HISTORY, PID_LATENCY_BUDGET, PID_LIFESPAN,
             PID_LIVELINESS, PID_OWNERSHIP, PID_OWNERSHIP_STRENGTH, PID_PARTICIPANT_GUID,
-            PID_PARTITION, PID_PRESENTATION, PID_RELIABILITY, PID_RESOURCE_LIMITS,
-            PID_TIME_BASED_FILTER, PID_TOPIC_DATA, PID_TOPIC_NAME, PID_TRANSPORT_PRIORITY,
-            PID_TYPE_NAME, PID_USER_DATA,
+            PID_PARTITION, PID_PRESENTATION, PID_PROPERTY_LIST, PID_RELIABILITY,
+            PID_RESOURCE_LIMITS, PID_TIME_BASED_FILTER, PID_TOPIC_DATA, PID_TOPIC_NAME,
+            PID_TRANSPORT_PRIORITY, PID_TYPE_INFORMATION, PID_TYPE_NAME, PID_USER_DATA,
         },
         payload_serializer_deserializer::parameter_list_serializer::ParameterListCdrSerializer,
     },
+    implementation::xtypes_glue::type_information::serialize_type_information,
     infrastructure::qos_policy::{
         DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
-        DurabilityQosPolicy, GroupDataQosPolicy, HistoryQosPolicy, LatencyBudgetQosPolicy,
+        DisablePositiveAcksQosPolicy, DurabilityQosPolicy, DurabilityServiceQosPolicy,
+        EntityNameQosPolicy, GroupDataQosPolicy, HistoryQosPolicy, LatencyBudgetQosPolicy,
         LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy, OwnershipStrengthQosPolicy,
-        PartitionQosPolicy, PresentationQosPolicy, ReliabilityQosPolicy, ResourceLimitsQosPolicy,
-        TimeBasedFilterQosPolicy, TopicDataQosPolicy, TransportPriorityQosPolicy,
-        UserDataQosPolicy,
+        PartitionQosPolicy, PresentationQosPolicy, PropertyQosPolicy, ReliabilityQosPolicy,
+        ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy,
+        TransportPriorityQosPolicy, UserDataQosPolicy,
+    },
+    xtypes::{
+        deserialize::XTypesDeserialize, serialize::XTypesSerialize, type_object::TypeInformation,
     },
-    xtypes::{deserialize::XTypesDeserialize, serialize::XTypesSerialize},
 };
 
 use super::{
@@ -56,6 +61,8 @@ pub struct BuiltInTopicKey {
 pub struct ParticipantBuiltinTopicData {
     pub(crate) key: BuiltInTopicKey,
     pub(crate) user_data: UserDataQosPolicy,
+    pub(crate) entity_name: EntityNameQosPolicy,
+    pub(crate) property: PropertyQosPolicy,
 }
 
 impl ParticipantBuiltinTopicData {
@@ -68,6 +75,16 @@ impl ParticipantBuiltinTopicData {
     pub fn user_data(&self) -> &UserDataQosPolicy {
         &self.user_data
     }
+
+    /// Get the entity name QoS policy of the discovered participant.
+    pub fn entity_name(&self) -> &EntityNameQosPolicy {
+        &self.entity_name
+    }
+
+    /// Get the property QoS policy of the discovered participant.
+    pub fn property(&self) -> &PropertyQosPolicy {
+        &self.property
+    }
 }
 
 /// Structure representing a discovered [`Topic`](crate::topic_definition::topic::Topic).
@@ -77,6 +94,7 @@ pub struct TopicBuiltinTopicData {
     pub(crate) name: String,
     pub(crate) type_name: String,
     pub(crate) durability: DurabilityQosPolicy,
+    pub(crate) durability_service: DurabilityServiceQosPolicy,
     pub(crate) deadline: DeadlineQosPolicy,
     pub(crate) latency_budget: LatencyBudgetQosPolicy,
     pub(crate) liveliness: LivelinessQosPolicy,
@@ -102,6 +120,11 @@ impl DdsSerialize for TopicBuiltinTopicData {
         serializer.write(PID_TOPIC_NAME, &self.name)?;
         serializer.write(PID_TYPE_NAME, &self.type_name)?;
         serializer.write_with_default(PID_DURABILITY, &self.durability, &Default::default())?;
+        serializer.write_with_default(
+            PID_DURABILITY_SERVICE,
+            &self.durability_service,
+            &Default::default(),
+        )?;
         serializer.write_with_default(PID_DEADLINE, &self.deadline, &Default::default())?;
         serializer.write_with_default(
             PID_LATENCY_BUDGET,
@@ -165,6 +188,11 @@ impl TopicBuiltinTopicData {
         &self.durability
     }
 
+    /// Get the durability service QoS policy of the discovered topic.
+    pub fn durability_service(&self) -> &DurabilityServiceQosPolicy {
+        &self.durability_service
+    }
+
     /// Get the deadline QoS policy of the discovered topic.
     pub fn deadline(&self) -> &DeadlineQosPolicy {
         &self.deadline
@@ -234,6 +262,7 @@ pub struct PublicationBuiltinTopicData {
     pub(crate) topic_name: String,
     pub(crate) type_name: String,
     pub(crate) durability: DurabilityQosPolicy,
+    pub(crate) durability_service: DurabilityServiceQosPolicy,
     pub(crate) deadline: DeadlineQosPolicy,
     pub(crate) latency_budget: LatencyBudgetQosPolicy,
     pub(crate) liveliness: LivelinessQosPolicy,
@@ -248,6 +277,10 @@ pub struct PublicationBuiltinTopicData {
     pub(crate) topic_data: TopicDataQosPolicy,
     pub(crate) group_data: GroupDataQosPolicy,
     pub(crate) representation: DataRepresentationQosPolicy,
+    pub(crate) entity_name: EntityNameQosPolicy,
+    pub(crate) property: PropertyQosPolicy,
+    pub(crate) disable_positive_acks: DisablePositiveAcksQosPolicy,
+    pub(crate) type_information: Option<TypeInformation>,
 }
 
 impl DdsSerialize for PublicationBuiltinTopicData {
@@ -267,6 +300,11 @@ impl DdsSerialize for PublicationBuiltinTopicData {
         serializer.write(PID_TOPIC_NAME, &self.topic_name)?;
         serializer.write(PID_TYPE_NAME, &self.type_name)?;
         serializer.write_with_default(PID_DURABILITY, &self.durability, &Default::default())?;
+        serializer.write_with_default(
+            PID_DURABILITY_SERVICE,
+            &self.durability_service,
+            &Default::default(),
+        )?;
         serializer.write_with_default(PID_DEADLINE, &self.deadline, &Default::default())?;
         serializer.write_with_default(
             PID_LATENCY_BUDGET,
@@ -302,6 +340,19 @@ impl DdsSerialize for PublicationBuiltinTopicData {
             &self.representation,
             &Default::default(),
         )?;
+        serializer.write_with_default(PID_ENTITY_NAME, &self.entity_name, &Default::default())?;
+        serializer.write_with_default(PID_PROPERTY_LIST, &self.property, &Default::default())?;
+        serializer.write_with_default(
+            PID_DISABLE_POSITIVE_ACKS,
+            &self.disable_positive_acks,
+            &Default::default(),
+        )?;
+        if let Some(type_information) = &self.type_information {
+            serializer.write_bytes(
+                PID_TYPE_INFORMATION,
+                &serialize_type_information(type_information),
+            )?;
+        }
 
         serializer.write_sentinel()?;
         Ok(serializer.writer)
@@ -334,6 +385,11 @@ impl PublicationBuiltinTopicData {
         &self.durability
     }
 
+    /// Get the durability service QoS policy of the discovered publication.
+    pub fn durability_service(&self) -> &DurabilityServiceQosPolicy {
+        &self.durability_service
+    }
+
     /// Get the deadline QoS policy of the discovered writer.
     pub fn deadline(&self) -> &DeadlineQosPolicy {
         &self.deadline
@@ -403,6 +459,26 @@ impl PublicationBuiltinTopicData {
     pub fn representation(&self) -> &DataRepresentationQosPolicy {
         &self.representation
     }
+
+    /// Get the entity name QoS policy of the discovered writer.
+    pub fn entity_name(&self) -> &EntityNameQosPolicy {
+        &self.entity_name
+    }
+
+    /// Get the property QoS policy of the discovered writer.
+    pub fn property(&self) -> &PropertyQosPolicy {
+        &self.property
+    }
+
+    /// Get the disable positive acks QoS policy of the discovered writer.
+    pub fn disable_positive_acks(&self) -> &DisablePositiveAcksQosPolicy {
+        &self.disable_positive_acks
+    }
+
+    /// Get the type information announced for the type of the discovered writer, if any.
+    pub fn type_information(&self) -> Option<&TypeInformation> {
+        self.type_information.as_ref()
+    }
 }
 
 /// Structure representing a discovered [`DataReader`](crate::subscription::data_reader::DataReader).
@@ -426,6 +502,10 @@ pub struct SubscriptionBuiltinTopicData {
     pub(crate) topic_data: TopicDataQosPolicy,
     pub(crate) group_data: GroupDataQosPolicy,
     pub(crate) representation: DataRepresentationQosPolicy,
+    pub(crate) entity_name: EntityNameQosPolicy,
+    pub(crate) property: PropertyQosPolicy,
+    pub(crate) disable_positive_acks: DisablePositiveAcksQosPolicy,
+    pub(crate) type_information: Option<TypeInformation>,
 }
 
 impl DdsSerialize for SubscriptionBuiltinTopicData {
@@ -477,6 +557,19 @@ impl DdsSerialize for SubscriptionBuiltinTopicData {
             &self.representation,
             &Default::default(),
         )?;
+        serializer.write_with_default(PID_ENTITY_NAME, &self.entity_name, &Default::default())?;
+        serializer.write_with_default(PID_PROPERTY_LIST, &self.property, &Default::default())?;
+        serializer.write_with_default(
+            PID_DISABLE_POSITIVE_ACKS,
+            &self.disable_positive_acks,
+            &Default::default(),
+        )?;
+        if let Some(type_information) = &self.type_information {
+            serializer.write_bytes(
+                PID_TYPE_INFORMATION,
+                &serialize_type_information(type_information),
+            )?;
+        }
 
         serializer.write_sentinel()?;
         Ok(serializer.writer)
@@ -573,4 +666,24 @@ impl SubscriptionBuiltinTopicData {
     pub fn representation(&self) -> &DataRepresentationQosPolicy {
         &self.representation
     }
+
+    /// Get the entity name QoS policy of the discovered reader.
+    pub fn entity_name(&self) -> &EntityNameQosPolicy {
+        &self.entity_name
+    }
+
+    /// Get the property QoS policy of the discovered reader.
+    pub fn property(&self) -> &PropertyQosPolicy {
+        &self.property
+    }
+
+    /// Get the disable positive acks QoS policy of the discovered reader.
+    pub fn disable_positive_acks(&self) -> &DisablePositiveAcksQosPolicy {
+        &self.disable_positive_acks
+    }
+
+    /// Get the type information announced for the type of the discovered reader, if any.
+    pub fn type_information(&self) -> Option<&TypeInformation> {
+        self.type_information.as_ref()
+    }
 }
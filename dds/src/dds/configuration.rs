@@ -1,16 +1,327 @@
-use std::time::Duration;
+use std::{
+    fmt::Debug,
+    net::{IpAddr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
-use crate::infrastructure::error::{DdsError, DdsResult};
+use crate::{
+    infrastructure::error::{DdsError, DdsResult},
+    runtime::executor::Spawner,
+};
+
+/// Strategy used to space out the repeated retransmissions of the same changes to a reader that keeps requesting
+/// them. The first retransmission of a change is always sent as soon as it is requested.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RetransmissionBackoff {
+    /// Wait the same delay before each repeated retransmission.
+    Fixed(Duration),
+    /// Increase the delay by `increment` on each repeated retransmission up to `max`.
+    Linear {
+        /// Delay added on each repeated retransmission
+        increment: Duration,
+        /// Maximum delay between retransmissions
+        max: Duration,
+    },
+    /// Double the delay on each repeated retransmission starting from `initial` up to `max`.
+    Exponential {
+        /// Delay before the first repeated retransmission
+        initial: Duration,
+        /// Maximum delay between retransmissions
+        max: Duration,
+    },
+}
+
+impl RetransmissionBackoff {
+    /// Minimum delay since the last retransmission of a change before retransmitting it again, given the number of
+    /// times it was already retransmitted.
+    pub fn delay(&self, retransmission_count: u32) -> Duration {
+        match *self {
+            RetransmissionBackoff::Fixed(delay) => delay,
+            RetransmissionBackoff::Linear { increment, max } => increment
+                .checked_mul(retransmission_count)
+                .map_or(max, |delay| delay.min(max)),
+            RetransmissionBackoff::Exponential { initial, max } => match retransmission_count {
+                0 => Duration::ZERO,
+                n => 2u32
+                    .checked_pow(n - 1)
+                    .and_then(|factor| initial.checked_mul(factor))
+                    .map_or(max, |delay| delay.min(max)),
+            },
+        }
+    }
+}
+
+impl Default for RetransmissionBackoff {
+    fn default() -> Self {
+        RetransmissionBackoff::Fixed(Duration::ZERO)
+    }
+}
+
+/// Hard limits enforced while deserializing the received RTPS messages, so that a malformed or malicious remote
+/// participant can not cause an unbounded allocation in the receive path.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DeserializationLimits {
+    /// Maximum number of parameters in a received parameter list, such as the inline QoS of a DATA submessage.
+    /// Submessages with a longer parameter list are discarded.
+    pub max_parameter_list_length: usize,
+    /// Maximum number of fragments of a received sample. The DATA_FRAG submessages of larger samples are discarded.
+    pub max_fragments_per_sample: usize,
+    /// Maximum number of partially received fragmented samples kept for each matched writer. When a fragment of a new
+    /// sample is received with the limit reached, the pending sample with the lowest sequence number is dropped.
+    pub max_pending_fragmented_samples: usize,
+    /// Time after which a partially received fragmented sample is dropped if none of its missing fragments is
+    /// received in the meantime.
+    pub pending_fragmented_sample_timeout: Duration,
+    /// Maximum number of elements of a sequence in the data of a received sample. Reading a sample with a longer
+    /// sequence fails with [`DdsError::Error`](crate::infrastructure::error::DdsError::Error).
+    pub max_sequence_length: usize,
+}
+
+impl Default for DeserializationLimits {
+    fn default() -> Self {
+        Self {
+            max_parameter_list_length: 1024,
+            max_fragments_per_sample: 65536,
+            max_pending_fragmented_samples: 64,
+            pending_fragmented_sample_timeout: Duration::from_secs(30),
+            max_sequence_length: 1 << 24,
+        }
+    }
+}
+
+/// Batching of the samples written by the user-defined data writers. The samples are held back and then sent together,
+/// packed in as few RTPS messages as possible, once the size of their serialized data reaches `max_batch_size` or once
+/// `flush_period` has elapsed since the first of them was written. The held back samples can also be sent right away
+/// with [`DataWriter::flush`](crate::publication::data_writer::DataWriter::flush).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct WriterBatching {
+    /// Size of the serialized data of the held back samples at which they are sent.
+    pub max_batch_size: usize,
+    /// Maximum time during which a sample is held back. The elapsed time is checked every 50 milliseconds.
+    pub flush_period: Duration,
+}
+
+/// Limit of the rate at which the user-defined data writers send the serialized data of their samples. The rate is
+/// shaped with a token bucket which holds at most one second worth of bytes. The samples which exceed the rate are
+/// kept and sent once enough bytes become available again, with the writers of higher
+/// [`TransportPriorityQosPolicy`](crate::infrastructure::qos_policy::TransportPriorityQosPolicy) served first.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FlowControl {
+    /// Maximum number of bytes of serialized data sent per second.
+    pub max_bytes_per_second: usize,
+    /// Whether the rate is shared by all the data writers of a participant or applies to each of them.
+    pub scope: FlowControlScope,
+}
+
+/// Entities to which the rate of a [`FlowControl`] applies.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FlowControlScope {
+    /// All the user-defined data writers of a participant share the same rate.
+    Participant,
+    /// Each user-defined data writer is limited to the rate on its own.
+    Writer,
+}
+
+/// Entry of the mapping between the [`TransportPriorityQosPolicy`](crate::infrastructure::qos_policy::TransportPriorityQosPolicy)
+/// of the data writers and the Differentiated Services Code Point (DSCP) set on the UDPv4 datagrams they send.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TransportPriorityDscp {
+    /// Lowest transport priority to which the entry applies. A data writer uses the entry with the highest minimum
+    /// transport priority not above its own.
+    pub min_transport_priority: i32,
+    /// DSCP set on the datagrams, between 0 and 63.
+    pub dscp: u8,
+}
+
+/// Maximum transmission unit (MTU) of the network path to a destination address, used instead of the MTU discovered
+/// with [`DustDdsConfigurationBuilder::path_mtu_discovery`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DestinationMtu {
+    /// IP address of the destination, such as the address of a remote participant or a multicast group.
+    pub address: IpAddr,
+    /// Size in bytes of the largest IP packet sent to the destination without being fragmented, including the IP and
+    /// UDP headers.
+    pub mtu: usize,
+}
+
+/// Behavior of a full actor mailbox when more mail is sent to it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum MailboxOverflowPolicy {
+    /// The sender waits until the actor has processed enough mail to make room for it. The mail sent by the tasks of
+    /// Dust DDS themselves is always accepted, even above the capacity, so that the actors never wait on each other.
+    #[default]
+    Block,
+    /// The mail is dropped. The operations of the DDS API whose mail is dropped return
+    /// [`DdsError::OutOfResources`], while the dropped received messages and listener notifications are lost.
+    Drop,
+}
+
+/// Capacities of the mailboxes of the actors doing the work of each participant. [`None`] means that the mailbox is
+/// unbounded.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ActorMailboxCapacities {
+    /// Mailbox of the participant, which receives the operations of the DDS API and the discovered and received
+    /// samples to process.
+    pub participant: Option<usize>,
+    /// Mailbox of the RTPS transport of the participant, which receives the RTPS messages read from the sockets and
+    /// the changes to send.
+    pub transport: Option<usize>,
+    /// Mailbox of each listener, which receives the notifications of the status changes.
+    pub listener: Option<usize>,
+}
+
+// Peer to which the SPDP announcements are sent, parsed from a string of the form
+// `[udpv4://|udpv6://]host[:port]` where an IPv6 host with a port is written within brackets
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct InitialPeer {
+    pub(crate) udp_v4: bool,
+    pub(crate) udp_v6: bool,
+    pub(crate) host: String,
+    pub(crate) port: Option<u16>,
+}
+
+impl InitialPeer {
+    pub(crate) fn parse(peer: &str) -> DdsResult<Self> {
+        let invalid = || DdsError::Error(format!("Invalid initial peer {}", peer));
+        let (udp_v4, udp_v6, address) = if let Some(address) = peer.strip_prefix("udpv4://") {
+            (true, false, address)
+        } else if let Some(address) = peer.strip_prefix("udpv6://") {
+            (false, true, address)
+        } else if peer.contains("://") {
+            return Err(invalid());
+        } else {
+            (true, true, peer)
+        };
+        let (host, port) = if let Some(bracketed) = address.strip_prefix('[') {
+            let (host, rest) = bracketed.split_once(']').ok_or_else(invalid)?;
+            match rest {
+                "" => (host, None),
+                rest => (host, Some(rest.strip_prefix(':').ok_or_else(invalid)?)),
+            }
+        } else if address.matches(':').count() > 1 {
+            (address, None)
+        } else {
+            match address.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (address, None),
+            }
+        };
+        let port = port
+            .map(|p| p.parse::<u16>().map_err(|_| invalid()))
+            .transpose()?;
+        if host.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            udp_v4,
+            udp_v6,
+            host: host.to_string(),
+            port,
+        })
+    }
+}
+
+/// IP versions used by the participants to communicate.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum IpVersion {
+    /// Use only IPv4.
+    #[default]
+    V4,
+    /// Use only IPv6.
+    V6,
+    /// Use both IPv4 and IPv6. The participants listen and advertise locators of both versions.
+    V4AndV6,
+}
+
+impl IpVersion {
+    /// Whether IPv4 is used.
+    pub fn is_v4_enabled(&self) -> bool {
+        matches!(self, IpVersion::V4 | IpVersion::V4AndV6)
+    }
+
+    /// Whether IPv6 is used.
+    pub fn is_v6_enabled(&self) -> bool {
+        matches!(self, IpVersion::V6 | IpVersion::V4AndV6)
+    }
+}
+
+/// Role of the participants in the discovery of the other participants and their endpoints.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum DiscoveryMode {
+    /// The participants announce themselves to the multicast group and the initial peers and discover the other
+    /// participants and their endpoints directly.
+    #[default]
+    Simple,
+    /// The participants act as discovery servers. They relay the participants and the endpoints announced by their
+    /// clients to the other clients.
+    Server,
+    /// The participants announce themselves only to the discovery servers and discover the other participants and
+    /// their endpoints through them.
+    Client,
+}
+
+// Spawner given by the user for the listener callbacks. Two configurations only compare equal if they share the
+// same spawner.
+#[derive(Clone)]
+struct ListenerSpawner(Arc<dyn Spawner>);
+
+impl Debug for ListenerSpawner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ListenerSpawner")
+    }
+}
+
+impl PartialEq for ListenerSpawner {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ListenerSpawner {}
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 /// This struct specifies the high-level configuration for the DustDDS library. The configuration can be set for use by the
 /// [`DomainParticipantFactory::set_configuration`](dust_dds::domain::domain_participant_factory::DomainParticipantFactory::set_configuration) method.
 pub struct DustDdsConfiguration {
     domain_tag: String,
+    accepted_domain_tags: Vec<String>,
     interface_name: Option<String>,
     fragment_size: usize,
     udp_receive_buffer_size: Option<usize>,
     participant_announcement_interval: Duration,
+    participant_lease_duration: Duration,
+    listener_threads: usize,
+    listener_spawner: Option<ListenerSpawner>,
+    max_discovered_participants: Option<usize>,
+    max_discovered_endpoints: Option<usize>,
+    receive_busy_poll_duration: Option<Duration>,
+    receive_threads: usize,
+    max_message_size: usize,
+    path_mtu_discovery: bool,
+    destination_mtu_list: Vec<DestinationMtu>,
+    retransmission_backoff: RetransmissionBackoff,
+    samples_per_piggyback_heartbeat: usize,
+    heartbeat_response_jitter: Duration,
+    user_data_multicast_threshold: Option<usize>,
+    writer_batching: Option<WriterBatching>,
+    sedp_batching: Option<WriterBatching>,
+    flow_control: Option<FlowControl>,
+    transport_priority_dscp_mapping: Vec<TransportPriorityDscp>,
+    disable_multicast: bool,
+    source_timestamp_tolerance: Option<Duration>,
+    deserialization_limits: DeserializationLimits,
+    actor_mailbox_capacities: ActorMailboxCapacities,
+    mailbox_overflow_policy: MailboxOverflowPolicy,
+    tcp_port: Option<u16>,
+    tcp_peers: Vec<SocketAddr>,
+    tcp_max_connections: usize,
+    ip_version: IpVersion,
+    initial_peers: Vec<String>,
+    discovery_mode: DiscoveryMode,
+    discovery_servers: Vec<String>,
+    persistence_directory: Option<PathBuf>,
 }
 
 impl DustDdsConfiguration {
@@ -19,6 +330,11 @@ impl DustDdsConfiguration {
         self.domain_tag.as_ref()
     }
 
+    /// Domain tags of the remote participants accepted by the participants in addition to their own domain tag
+    pub fn accepted_domain_tags(&self) -> &[String] {
+        self.accepted_domain_tags.as_ref()
+    }
+
     /// Network interface name to use for discovery
     pub fn interface_name(&self) -> Option<&str> {
         self.interface_name.as_deref()
@@ -38,16 +354,209 @@ impl DustDdsConfiguration {
     pub fn participant_announcement_interval(&self) -> Duration {
         self.participant_announcement_interval
     }
+
+    /// Time during which the remote participants consider the participant alive after each of its announcements.
+    pub fn participant_lease_duration(&self) -> Duration {
+        self.participant_lease_duration
+    }
+
+    /// Number of threads used by each participant to invoke the listener callbacks.
+    pub fn listener_threads(&self) -> usize {
+        self.listener_threads
+    }
+
+    pub(crate) fn listener_spawner(&self) -> Option<&Arc<dyn Spawner>> {
+        self.listener_spawner.as_ref().map(|s| &s.0)
+    }
+
+    /// Maximum number of remote participants discovered by each participant. [`None`] means no limit.
+    pub fn max_discovered_participants(&self) -> Option<usize> {
+        self.max_discovered_participants
+    }
+
+    /// Maximum number of remote data writers and data readers discovered by each participant. [`None`] means no limit.
+    pub fn max_discovered_endpoints(&self) -> Option<usize> {
+        self.max_discovered_endpoints
+    }
+
+    /// Time during which the user-defined traffic socket is busy-polled before parking the receive thread. [`None`] means
+    /// that the receive thread always blocks on the socket.
+    pub fn receive_busy_poll_duration(&self) -> Option<Duration> {
+        self.receive_busy_poll_duration
+    }
+
+    /// Number of threads used by each participant to receive the user-defined traffic.
+    pub fn receive_threads(&self) -> usize {
+        self.receive_threads
+    }
+
+    /// Maximum size of the RTPS messages sent by each participant.
+    pub fn max_message_size(&self) -> usize {
+        self.max_message_size
+    }
+
+    /// Whether the size of the RTPS messages sent to each destination is limited to the MTU of the network path to it.
+    pub fn path_mtu_discovery(&self) -> bool {
+        self.path_mtu_discovery
+    }
+
+    /// MTU of the network path to the destinations for which it is not discovered.
+    pub fn destination_mtu_list(&self) -> &[DestinationMtu] {
+        self.destination_mtu_list.as_ref()
+    }
+
+    /// Strategy used to space out the repeated retransmissions of the same changes to a reader.
+    pub fn retransmission_backoff(&self) -> RetransmissionBackoff {
+        self.retransmission_backoff
+    }
+
+    /// Number of samples sent by a reliable writer to each reader between the HEARTBEAT submessages appended to the data.
+    pub fn samples_per_piggyback_heartbeat(&self) -> usize {
+        self.samples_per_piggyback_heartbeat
+    }
+
+    /// Maximum random delay added by the reliable readers before answering a HEARTBEAT with an ACKNACK.
+    pub fn heartbeat_response_jitter(&self) -> Duration {
+        self.heartbeat_response_jitter
+    }
+
+    /// Number of matched readers sharing a multicast locator above which the user data is sent once to that locator.
+    /// [`None`] means that the user data is only sent with unicast.
+    pub fn user_data_multicast_threshold(&self) -> Option<usize> {
+        self.user_data_multicast_threshold
+    }
+
+    /// Batching of the samples written by the user-defined data writers. [`None`] means that each sample is sent as
+    /// soon as it is written.
+    pub fn writer_batching(&self) -> Option<WriterBatching> {
+        self.writer_batching
+    }
+
+    /// Batching of the SEDP announcements of the local data writers, data readers and topics. [`None`] means that each
+    /// announcement is sent as soon as the entity is created, modified or deleted.
+    pub fn sedp_batching(&self) -> Option<WriterBatching> {
+        self.sedp_batching
+    }
+
+    /// Limit of the rate at which the user-defined data writers send their samples. [`None`] means that the samples
+    /// are sent as fast as they are written.
+    pub fn flow_control(&self) -> Option<FlowControl> {
+        self.flow_control
+    }
+
+    /// Mapping between the transport priority of the data writers and the DSCP set on the UDPv4 datagrams they send.
+    /// An empty mapping means that the DSCP is never set.
+    pub fn transport_priority_dscp_mapping(&self) -> &[TransportPriorityDscp] {
+        self.transport_priority_dscp_mapping.as_ref()
+    }
+
+    /// Whether the participants neither join nor advertise any multicast group and never send to a multicast locator.
+    pub fn disable_multicast(&self) -> bool {
+        self.disable_multicast
+    }
+
+    /// Maximum time by which the source timestamps supplied by the user may be ahead of the participant clock.
+    /// [`None`] means that the source timestamps are not checked.
+    pub fn source_timestamp_tolerance(&self) -> Option<Duration> {
+        self.source_timestamp_tolerance
+    }
+
+    /// Hard limits enforced while deserializing the received RTPS messages.
+    pub fn deserialization_limits(&self) -> DeserializationLimits {
+        self.deserialization_limits
+    }
+
+    /// Capacities of the mailboxes of the actors doing the work of each participant.
+    pub fn actor_mailbox_capacities(&self) -> ActorMailboxCapacities {
+        self.actor_mailbox_capacities
+    }
+
+    /// Behavior of a full actor mailbox when more mail is sent to it.
+    pub fn mailbox_overflow_policy(&self) -> MailboxOverflowPolicy {
+        self.mailbox_overflow_policy
+    }
+
+    /// Port on which the participants accept the RTPS over TCP connections. [`None`] means that TCP is not used.
+    pub fn tcp_port(&self) -> Option<u16> {
+        self.tcp_port
+    }
+
+    /// Addresses of the TCP ports of the remote participants to which the participants announce themselves.
+    pub fn tcp_peers(&self) -> &[SocketAddr] {
+        self.tcp_peers.as_ref()
+    }
+
+    /// Maximum number of TCP connections open at the same time by each participant.
+    pub fn tcp_max_connections(&self) -> usize {
+        self.tcp_max_connections
+    }
+
+    /// IP versions used by the participants to communicate.
+    pub fn ip_version(&self) -> IpVersion {
+        self.ip_version
+    }
+
+    /// Peers to which the participants send their SPDP announcements in addition to the multicast group.
+    pub fn initial_peers(&self) -> &[String] {
+        self.initial_peers.as_ref()
+    }
+
+    /// Role of the participants in the discovery.
+    pub fn discovery_mode(&self) -> DiscoveryMode {
+        self.discovery_mode
+    }
+
+    /// Discovery servers with which the participants register when they are discovery clients.
+    pub fn discovery_servers(&self) -> &[String] {
+        self.discovery_servers.as_ref()
+    }
+
+    /// Directory where the participants store the samples of the writers with PERSISTENT durability.
+    pub fn persistence_directory(&self) -> Option<&Path> {
+        self.persistence_directory.as_deref()
+    }
 }
 
 impl Default for DustDdsConfiguration {
     fn default() -> Self {
         Self {
             domain_tag: "".to_string(),
+            accepted_domain_tags: Vec::new(),
             interface_name: None,
             fragment_size: 1344,
             udp_receive_buffer_size: None,
             participant_announcement_interval: Duration::from_secs(5),
+            participant_lease_duration: Duration::from_secs(100),
+            listener_threads: 1,
+            listener_spawner: None,
+            max_discovered_participants: None,
+            max_discovered_endpoints: None,
+            receive_busy_poll_duration: None,
+            receive_threads: 1,
+            max_message_size: 65507,
+            path_mtu_discovery: false,
+            destination_mtu_list: Vec::new(),
+            retransmission_backoff: RetransmissionBackoff::default(),
+            samples_per_piggyback_heartbeat: 1,
+            heartbeat_response_jitter: Duration::ZERO,
+            user_data_multicast_threshold: None,
+            writer_batching: None,
+            sedp_batching: None,
+            flow_control: None,
+            transport_priority_dscp_mapping: Vec::new(),
+            disable_multicast: false,
+            source_timestamp_tolerance: None,
+            deserialization_limits: DeserializationLimits::default(),
+            actor_mailbox_capacities: ActorMailboxCapacities::default(),
+            mailbox_overflow_policy: MailboxOverflowPolicy::default(),
+            tcp_port: None,
+            tcp_peers: Vec::new(),
+            tcp_max_connections: 256,
+            ip_version: IpVersion::default(),
+            initial_peers: Vec::new(),
+            discovery_mode: DiscoveryMode::default(),
+            discovery_servers: Vec::new(),
+            persistence_directory: None,
         }
     }
 }
@@ -66,14 +575,183 @@ impl DustDdsConfigurationBuilder {
         }
     }
 
+    /// Construct a configuration builder with the multicast-free deployment profile, for networks such as cloud or
+    /// container platforms where multicast is not available. The participants don't join or advertise any multicast
+    /// group, so they don't send or receive the multicast SPDP announcements, and all the data is sent with unicast.
+    pub fn multicast_free() -> Self {
+        Self::new().disable_multicast(true)
+    }
+
     /// Build a new configuration
     pub fn build(self) -> DdsResult<DustDdsConfiguration> {
         let fragment_size_range = 8..=65000;
+        // From the smallest datagram every IPv4 host must accept (576) without the IP and UDP
+        // headers to the largest UDP payload
+        let max_message_size_range = 548..=65507;
         if !fragment_size_range.contains(&self.configuration.fragment_size) {
             Err(DdsError::Error(format!(
                 "Interface size out of range. Value must be between in {:?}",
                 fragment_size_range
             )))
+        } else if self.configuration.listener_threads == 0 {
+            Err(DdsError::Error(
+                "Number of listener threads must be at least 1".to_string(),
+            ))
+        } else if self.configuration.receive_threads == 0 {
+            Err(DdsError::Error(
+                "Number of receive threads must be at least 1".to_string(),
+            ))
+        } else if !max_message_size_range.contains(&self.configuration.max_message_size) {
+            Err(DdsError::Error(format!(
+                "Maximum message size out of range. Value must be in {:?}",
+                max_message_size_range
+            )))
+        } else if self
+            .configuration
+            .destination_mtu_list
+            .iter()
+            .any(|m| !(576..=65535).contains(&m.mtu))
+        {
+            Err(DdsError::Error(
+                "Destination MTU out of range. Value must be in 576..=65535".to_string(),
+            ))
+        } else if self.configuration.samples_per_piggyback_heartbeat == 0 {
+            Err(DdsError::Error(
+                "Number of samples per piggyback heartbeat must be at least 1".to_string(),
+            ))
+        } else if self.configuration.disable_multicast
+            && self.configuration.user_data_multicast_threshold.is_some()
+        {
+            Err(DdsError::Error(
+                "User data multicast threshold can not be set when multicast is disabled"
+                    .to_string(),
+            ))
+        } else if self
+            .configuration
+            .writer_batching
+            .into_iter()
+            .chain(self.configuration.sedp_batching)
+            .any(|b| b.max_batch_size == 0)
+        {
+            Err(DdsError::Error(
+                "Maximum batch size must be at least 1".to_string(),
+            ))
+        } else if self
+            .configuration
+            .flow_control
+            .is_some_and(|f| f.max_bytes_per_second == 0)
+        {
+            Err(DdsError::Error(
+                "Maximum bytes per second of the flow control must be at least 1".to_string(),
+            ))
+        } else if self
+            .configuration
+            .transport_priority_dscp_mapping
+            .iter()
+            .any(|m| m.dscp > 63)
+        {
+            Err(DdsError::Error("DSCP must be below 64".to_string()))
+        } else if self.configuration.participant_lease_duration.is_zero()
+            || self.configuration.participant_lease_duration.as_secs() > i32::MAX as u64
+        {
+            Err(DdsError::Error(format!(
+                "Participant lease duration must be above zero and below {} seconds",
+                i32::MAX
+            )))
+        } else if self
+            .configuration
+            .source_timestamp_tolerance
+            .is_some_and(|t| t.as_secs() > i32::MAX as u64)
+        {
+            Err(DdsError::Error(format!(
+                "Source timestamp tolerance must be below {} seconds",
+                i32::MAX
+            )))
+        } else if self
+            .configuration
+            .deserialization_limits
+            .max_parameter_list_length
+            == 0
+            || self
+                .configuration
+                .deserialization_limits
+                .max_fragments_per_sample
+                == 0
+            || self
+                .configuration
+                .deserialization_limits
+                .max_pending_fragmented_samples
+                == 0
+        {
+            Err(DdsError::Error(
+                "Deserialization limits must be at least 1".to_string(),
+            ))
+        } else if self
+            .configuration
+            .deserialization_limits
+            .pending_fragmented_sample_timeout
+            .is_zero()
+        {
+            Err(DdsError::Error(
+                "Pending fragmented sample timeout must be above zero".to_string(),
+            ))
+        } else if [
+            self.configuration.actor_mailbox_capacities.participant,
+            self.configuration.actor_mailbox_capacities.transport,
+            self.configuration.actor_mailbox_capacities.listener,
+        ]
+        .contains(&Some(0))
+        {
+            Err(DdsError::Error(
+                "Actor mailbox capacities must be at least 1".to_string(),
+            ))
+        } else if self.configuration.tcp_port.is_none() && !self.configuration.tcp_peers.is_empty()
+        {
+            Err(DdsError::Error(
+                "TCP peers can not be set when the TCP port is not set".to_string(),
+            ))
+        } else if self.configuration.tcp_peers.iter().any(|p| !p.is_ipv4()) {
+            Err(DdsError::Error(
+                "TCP peers must be IPv4 addresses".to_string(),
+            ))
+        } else if self.configuration.tcp_max_connections == 0 {
+            Err(DdsError::Error(
+                "TCP max connections must be at least 1".to_string(),
+            ))
+        } else if self.configuration.tcp_port.is_some()
+            && !self.configuration.ip_version.is_v4_enabled()
+        {
+            Err(DdsError::Error(
+                "TCP transport requires IPv4 to be enabled".to_string(),
+            ))
+        } else if let Some(Err(e)) = self
+            .configuration
+            .initial_peers
+            .iter()
+            .map(|p| InitialPeer::parse(p))
+            .find(|p| p.is_err())
+        {
+            Err(e)
+        } else if self.configuration.discovery_mode == DiscoveryMode::Client
+            && self.configuration.discovery_servers.is_empty()
+        {
+            Err(DdsError::Error(
+                "Discovery clients require at least one discovery server".to_string(),
+            ))
+        } else if self.configuration.discovery_mode != DiscoveryMode::Client
+            && !self.configuration.discovery_servers.is_empty()
+        {
+            Err(DdsError::Error(
+                "Discovery servers can only be set for discovery clients".to_string(),
+            ))
+        } else if let Some(Err(e)) = self
+            .configuration
+            .discovery_servers
+            .iter()
+            .map(|p| InitialPeer::parse(p))
+            .find(|p| p.is_err())
+        {
+            Err(e)
         } else {
             Ok(self.configuration)
         }
@@ -85,6 +763,14 @@ impl DustDdsConfigurationBuilder {
         self
     }
 
+    /// Set the domain tags of the remote participants accepted in addition to the own domain tag. This allows
+    /// migrating a system from one domain tag to another in stages by letting the participants with the old and the
+    /// new domain tag discover each other.
+    pub fn accepted_domain_tags(mut self, accepted_domain_tags: Vec<String>) -> Self {
+        self.configuration.accepted_domain_tags = accepted_domain_tags;
+        self
+    }
+
     /// Set the network interface name to use for discovery
     pub fn interface_name(mut self, interface_name: Option<String>) -> Self {
         self.configuration.interface_name = interface_name;
@@ -112,4 +798,286 @@ impl DustDdsConfigurationBuilder {
         self.configuration.participant_announcement_interval = participant_announcement_interval;
         self
     }
+
+    /// Set the lease duration announced by the participant. The remote participants remove the participant, and
+    /// unmatch its endpoints, when they receive no announcement from it during this time. It should be several times
+    /// the participant announcement interval so that a few lost announcements don't expire the lease.
+    pub fn participant_lease_duration(mut self, participant_lease_duration: Duration) -> Self {
+        self.configuration.participant_lease_duration = participant_lease_duration;
+        self
+    }
+
+    /// Set the number of threads used by each participant to invoke the listener callbacks. The listeners of the
+    /// different entities are distributed among these threads so that a slow callback only delays the listeners
+    /// sharing its thread. The callbacks of a single entity are always invoked sequentially and in order.
+    pub fn listener_threads(mut self, listener_threads: usize) -> Self {
+        self.configuration.listener_threads = listener_threads;
+        self
+    }
+
+    /// Set the [`Spawner`](crate::dds_async::runtime::Spawner) on which each participant invokes the listener
+    /// callbacks, so that they run on an executor or thread pool provided by the application instead of on the
+    /// listener threads, which are then not created. The callbacks of a single entity are still invoked sequentially
+    /// and in order.
+    pub fn listener_spawner(mut self, listener_spawner: impl Spawner) -> Self {
+        self.configuration.listener_spawner = Some(ListenerSpawner(Arc::new(listener_spawner)));
+        self
+    }
+
+    /// Set the maximum number of remote participants discovered by each participant. Participants announced
+    /// once the limit is reached are ignored. [`None`] means no limit.
+    pub fn max_discovered_participants(
+        mut self,
+        max_discovered_participants: Option<usize>,
+    ) -> Self {
+        self.configuration.max_discovered_participants = max_discovered_participants;
+        self
+    }
+
+    /// Set the maximum number of remote data writers and data readers discovered by each participant. Endpoints
+    /// announced once the limit is reached are ignored. [`None`] means no limit.
+    pub fn max_discovered_endpoints(mut self, max_discovered_endpoints: Option<usize>) -> Self {
+        self.configuration.max_discovered_endpoints = max_discovered_endpoints;
+        self
+    }
+
+    /// Set the time during which the user-defined traffic socket is busy-polled after each received message before
+    /// parking the receive thread on a blocking read. Busy-polling lowers the reception latency at the cost of keeping
+    /// a core spinning while data is flowing. [`None`] means that the receive thread always blocks on the socket.
+    pub fn receive_busy_poll_duration(
+        mut self,
+        receive_busy_poll_duration: Option<Duration>,
+    ) -> Self {
+        self.configuration.receive_busy_poll_duration = receive_busy_poll_duration;
+        self
+    }
+
+    /// Set the number of threads used by each participant to receive the user-defined traffic. On Linux the
+    /// user-defined traffic port is sharded among several sockets using SO_REUSEPORT so that the messages coming
+    /// from the same source are always received in order by the same thread. On other platforms a single receive
+    /// thread is used.
+    pub fn receive_threads(mut self, receive_threads: usize) -> Self {
+        self.configuration.receive_threads = receive_threads;
+        self
+    }
+
+    /// Set the maximum size of the RTPS messages sent by each participant. Submessages that do not fit together are
+    /// sent in separate messages and the data is fragmented, even if it is below the fragment size, so that each
+    /// message fits. This allows adapting the wire behavior to networks with jumbo frames or a reduced MTU.
+    pub fn max_message_size(mut self, max_message_size: usize) -> Self {
+        self.configuration.max_message_size = max_message_size;
+        self
+    }
+
+    /// Set whether the size of the RTPS messages sent to each destination is limited to the MTU of the network path
+    /// to it, so that their UDP datagrams are not fragmented at the IP level. The data sent to a destination is
+    /// fragmented accordingly. The MTU is taken from the network interface on the subnet of the destination, or else
+    /// from the interface with the lowest MTU, and is only discovered on Linux.
+    pub fn path_mtu_discovery(mut self, path_mtu_discovery: bool) -> Self {
+        self.configuration.path_mtu_discovery = path_mtu_discovery;
+        self
+    }
+
+    /// Set the MTU of the network path to some destinations, which takes precedence over the discovered one. The
+    /// messages sent to these destinations are limited to their MTU even without the path MTU discovery.
+    pub fn destination_mtu_list(mut self, destination_mtu_list: Vec<DestinationMtu>) -> Self {
+        self.configuration.destination_mtu_list = destination_mtu_list;
+        self
+    }
+
+    /// Set the strategy used to space out the repeated retransmissions of the same changes to a reader that keeps
+    /// requesting them. Requests received while a change is still within its backoff delay are ignored, which avoids
+    /// flooding a congested or broken link. By default the changes are retransmitted every time they are requested.
+    pub fn retransmission_backoff(mut self, retransmission_backoff: RetransmissionBackoff) -> Self {
+        self.configuration.retransmission_backoff = retransmission_backoff;
+        self
+    }
+
+    /// Set the number of samples sent by a reliable writer to each reader between the HEARTBEAT submessages appended
+    /// to the data. Piggybacking a HEARTBEAT only every few samples lowers the overhead of high-rate flows at the cost
+    /// of a slower detection of lost samples, which otherwise relies on the periodic heartbeats. By default a
+    /// HEARTBEAT is appended to every sample.
+    pub fn samples_per_piggyback_heartbeat(
+        mut self,
+        samples_per_piggyback_heartbeat: usize,
+    ) -> Self {
+        self.configuration.samples_per_piggyback_heartbeat = samples_per_piggyback_heartbeat;
+        self
+    }
+
+    /// Set the maximum random delay added by the reliable readers before answering a HEARTBEAT with an ACKNACK. When
+    /// many readers of the same writer detect the same loss, spreading their responses over this interval avoids a
+    /// storm of simultaneous ACKNACKs and repairs. By default the readers answer right away.
+    pub fn heartbeat_response_jitter(mut self, heartbeat_response_jitter: Duration) -> Self {
+        self.configuration.heartbeat_response_jitter = heartbeat_response_jitter;
+        self
+    }
+
+    /// Set the number of matched readers sharing a multicast locator above which a writer sends its user data once to
+    /// that locator instead of sending a copy to each reader. When set, the participants also join the user data
+    /// multicast group of the domain and advertise it to the remote participants. Repairs and heartbeats are still
+    /// sent to each reader with unicast. [`None`] means that the user data is only sent with unicast.
+    pub fn user_data_multicast_threshold(
+        mut self,
+        user_data_multicast_threshold: Option<usize>,
+    ) -> Self {
+        self.configuration.user_data_multicast_threshold = user_data_multicast_threshold;
+        self
+    }
+
+    /// Set the batching of the samples written by the user-defined data writers. Coalescing many small samples in the
+    /// same RTPS messages reduces the number of datagrams sent at the cost of delaying the samples by up to the flush
+    /// period. [`None`] means that each sample is sent as soon as it is written.
+    pub fn writer_batching(mut self, writer_batching: Option<WriterBatching>) -> Self {
+        self.configuration.writer_batching = writer_batching;
+        self
+    }
+
+    /// Set the batching of the SEDP announcements of the local data writers, data readers and topics. When an
+    /// application creates or modifies many entities at once, their announcements are coalesced in the same RTPS
+    /// messages instead of each being sent on its own, at the cost of delaying their discovery by up to the flush
+    /// period. [`None`] means that each announcement is sent as soon as the entity is created, modified or deleted.
+    pub fn sedp_batching(mut self, sedp_batching: Option<WriterBatching>) -> Self {
+        self.configuration.sedp_batching = sedp_batching;
+        self
+    }
+
+    /// Set the limit of the rate at which the user-defined data writers send their samples. Shaping the traffic
+    /// prevents a burst, such as the historical samples sent to a late-joining reader, from starving the other
+    /// writers or flooding the network. [`None`] means that the samples are sent as fast as they are written.
+    pub fn flow_control(mut self, flow_control: Option<FlowControl>) -> Self {
+        self.configuration.flow_control = flow_control;
+        self
+    }
+
+    /// Set the mapping between the transport priority of the data writers and the Differentiated Services Code Point
+    /// (DSCP) set on the UDPv4 datagrams they send, so that the networks which support it prioritize the DDS traffic.
+    /// The datagrams of each DSCP are sent from a socket of their own. The datagrams of the writers with a transport
+    /// priority below all the entries, of the built-in endpoints and over IPv6 are sent without DSCP.
+    pub fn transport_priority_dscp_mapping(
+        mut self,
+        transport_priority_dscp_mapping: Vec<TransportPriorityDscp>,
+    ) -> Self {
+        self.configuration.transport_priority_dscp_mapping = transport_priority_dscp_mapping;
+        self
+    }
+
+    /// Set whether all the multicast usage is disabled. When disabled, the participants don't join the SPDP and user
+    /// data multicast groups nor advertise any multicast locator, and the multicast locators advertised by the remote
+    /// participants are ignored.
+    pub fn disable_multicast(mut self, disable_multicast: bool) -> Self {
+        self.configuration.disable_multicast = disable_multicast;
+        self
+    }
+
+    /// Set the maximum time by which the timestamps passed to the `_w_timestamp` operations of the data writers may be
+    /// ahead of the participant clock. Operations with a timestamp further in the future fail with
+    /// [`DdsError::BadParameter`]. [`None`] means that the source timestamps are not checked.
+    pub fn source_timestamp_tolerance(
+        mut self,
+        source_timestamp_tolerance: Option<Duration>,
+    ) -> Self {
+        self.configuration.source_timestamp_tolerance = source_timestamp_tolerance;
+        self
+    }
+
+    /// Set the hard limits enforced while deserializing the received RTPS messages and samples. Parameter lists and
+    /// fragmented samples above these limits are discarded and samples with longer sequences fail to be read so that a malformed or malicious remote participant can not make the
+    /// receive path allocate an unbounded amount of memory.
+    pub fn deserialization_limits(mut self, deserialization_limits: DeserializationLimits) -> Self {
+        self.configuration.deserialization_limits = deserialization_limits;
+        self
+    }
+
+    /// Set the capacities of the mailboxes of the actors doing the work of each participant. By default the mailboxes
+    /// are unbounded, so a participant receiving or being asked for more work than it can process keeps queuing it
+    /// in memory. With bounded mailboxes the [`MailboxOverflowPolicy`] decides what happens once they are full.
+    pub fn actor_mailbox_capacities(
+        mut self,
+        actor_mailbox_capacities: ActorMailboxCapacities,
+    ) -> Self {
+        self.configuration.actor_mailbox_capacities = actor_mailbox_capacities;
+        self
+    }
+
+    /// Set the behavior of a full actor mailbox when more mail is sent to it. Blocking slows down the application
+    /// threads and the receive threads to the pace of the participant, whereas dropping keeps them running at the
+    /// cost of the dropped work.
+    pub fn mailbox_overflow_policy(
+        mut self,
+        mailbox_overflow_policy: MailboxOverflowPolicy,
+    ) -> Self {
+        self.configuration.mailbox_overflow_policy = mailbox_overflow_policy;
+        self
+    }
+
+    /// Set the port on which the participants accept the RTPS over TCP connections. When set, the participants
+    /// advertise TCP unicast locators instead of the UDP ones, so that all the unicast traffic to them goes over the
+    /// TCP connections, which allows communicating across NAT and firewalls blocking UDP. A port of 0 lets the OS
+    /// choose a different port for each participant. [`None`] means that TCP is not used.
+    pub fn tcp_port(mut self, tcp_port: Option<u16>) -> Self {
+        self.configuration.tcp_port = tcp_port;
+        self
+    }
+
+    /// Set the addresses of the TCP ports of the remote participants to which the participants announce themselves.
+    /// The connections are opened by this side and the remote participants send their data back through them, so
+    /// only the peers need to accept incoming connections. Requires the TCP port to be set.
+    pub fn tcp_peers(mut self, tcp_peers: Vec<SocketAddr>) -> Self {
+        self.configuration.tcp_peers = tcp_peers;
+        self
+    }
+
+    /// Set the maximum number of TCP connections open at the same time by each participant, counting both the
+    /// accepted connections and the ones opened to the remote participants. Each connection is read by a thread of
+    /// its own, so the connections above the limit are closed right away. The default is 256.
+    pub fn tcp_max_connections(mut self, tcp_max_connections: usize) -> Self {
+        self.configuration.tcp_max_connections = tcp_max_connections;
+        self
+    }
+
+    /// Set the IP versions used by the participants to communicate. With IPv6 the participants open their sockets on
+    /// the IPv6 addresses of the interfaces, excluding the link-local ones, advertise IPv6 locators and join the IPv6
+    /// SPDP multicast group. With both versions the participants send their messages to the locators of both versions
+    /// advertised by the remote participants. By default only IPv4 is used.
+    pub fn ip_version(mut self, ip_version: IpVersion) -> Self {
+        self.configuration.ip_version = ip_version;
+        self
+    }
+
+    /// Set the peers to which the participants send their SPDP announcements, which allows discovering the remote
+    /// participants in networks where multicast is not available, such as cloud or container platforms. Each peer is
+    /// written as `host:port`, `host` or `[ipv6]:port`, optionally prefixed with `udpv4://` or `udpv6://` to restrict it
+    /// to one IP version. The host is either an IP address or a name resolved when the participants are created. The
+    /// port is the metatraffic unicast port of the remote participant. Without a port the announcements are sent to the
+    /// well-known metatraffic unicast ports of the first participants of the domain on that host. The remote
+    /// participants answer the announcements, so only one side needs to know the other.
+    pub fn initial_peers(mut self, initial_peers: Vec<String>) -> Self {
+        self.configuration.initial_peers = initial_peers;
+        self
+    }
+
+    /// Set the role of the participants in the discovery. With [`DiscoveryMode::Client`] the participants don't send
+    /// their SPDP announcements to the multicast group but only to the discovery servers, which relay the participants
+    /// and the endpoints of all their clients. This avoids the multicast traffic and the announcements between every
+    /// pair of participants in large deployments. The user data is still exchanged directly between the participants.
+    pub fn discovery_mode(mut self, discovery_mode: DiscoveryMode) -> Self {
+        self.configuration.discovery_mode = discovery_mode;
+        self
+    }
+
+    /// Set the discovery servers with which the participants register when they are discovery clients. Each server is
+    /// written with the same syntax as the [`initial_peers`](Self::initial_peers).
+    pub fn discovery_servers(mut self, discovery_servers: Vec<String>) -> Self {
+        self.configuration.discovery_servers = discovery_servers;
+        self
+    }
+
+    /// Set the directory where the participants store the samples of the writers with PERSISTENT durability so that they
+    /// are delivered to the readers joining after the application restarted. Without a directory the samples are only
+    /// kept in memory as for the TRANSIENT durability.
+    pub fn persistence_directory(mut self, persistence_directory: Option<PathBuf>) -> Self {
+        self.configuration.persistence_directory = persistence_directory;
+        self
+    }
 }
@@ -9,7 +9,7 @@ use crate::{
     infrastructure::{
         condition::StatusCondition,
         error::DdsResult,
-        instance::InstanceHandle,
+        instance::{EntityKind, InstanceHandle},
         qos::{DomainParticipantQos, PublisherQos, QosKind, SubscriberQos, TopicQos},
         status::StatusKind,
         time::{Duration, Time},
@@ -162,7 +162,12 @@ impl DomainParticipant {
         .map(Topic::new)
     }
 
-    #[doc(hidden)]
+    /// This operation creates a [`Topic`] whose type is only known at runtime, e.g. a type constructed with the
+    /// [`DynamicTypeBuilderFactory`](crate::xtypes::dynamic_type_builder::DynamicTypeBuilderFactory).
+    /// The samples of this topic can be written and read using [`DynamicData`](crate::xtypes::dynamic_data::DynamicData)
+    /// as the type of the [`DataWriter`](crate::publication::data_writer::DataWriter) and
+    /// [`DataReader`](crate::subscription::data_reader::DataReader).
+    /// The rest of the arguments behave as in [`DomainParticipant::create_topic`].
     #[tracing::instrument(skip(self, a_listener, dynamic_type_representation))]
     pub fn create_dynamic_topic(
         &self,
@@ -325,6 +330,16 @@ impl DomainParticipant {
         block_on(self.participant_async.assert_liveliness())
     }
 
+    /// This operation immediately announces the [`DomainParticipant`] to the other participants of the domain and
+    /// sends a heartbeat from each of its builtin discovery writers, instead of waiting for the periodic announcement.
+    /// This can be used when the application knows that the network connectivity has changed, for example after
+    /// resuming from sleep, so that the remote participants and endpoints are discovered again as soon as possible.
+    /// The operation will return [`DdsError::NotEnabled`](crate::infrastructure::error::DdsError) if the participant is not enabled.
+    #[tracing::instrument(skip(self))]
+    pub fn announce(&self) -> DdsResult<()> {
+        block_on(self.participant_async.announce())
+    }
+
     /// This operation sets a default value of the Publisher QoS policies which will be used for newly created [`Publisher`] entities in the
     /// case where the QoS policies are defaulted in the [`DomainParticipant::create_publisher()`] operation.
     /// This operation will check that the resulting policies are self consistent; if they are not, the operation will have no effect and
@@ -444,6 +459,33 @@ impl DomainParticipant {
         block_on(self.participant_async.contains_entity(a_handle))
     }
 
+    /// This operation retrieves the [`InstanceHandle`] and the [`EntityKind`] of all the entities that were created from the
+    /// [`DomainParticipant`]. As with [`DomainParticipant::contains_entity()`] the containment applies recursively, so the list
+    /// includes the [`Topic`], [`Publisher`] and [`Subscriber`] entities as well as the
+    /// [`DataWriter`](crate::publication::data_writer::DataWriter) and [`DataReader`](crate::subscription::data_reader::DataReader)
+    /// entities created from them. The built-in entities are not part of the list.
+    #[tracing::instrument(skip(self))]
+    pub fn get_contained_entities(&self) -> DdsResult<Vec<(InstanceHandle, EntityKind)>> {
+        block_on(self.participant_async.get_contained_entities())
+    }
+
+    /// This operation retrieves all the [`Publisher`] entities created from the [`DomainParticipant`], including the ones
+    /// created by other parts of the application. The built-in publisher is not part of the list.
+    #[tracing::instrument(skip(self))]
+    pub fn get_publishers(&self) -> DdsResult<Vec<Publisher>> {
+        block_on(self.participant_async.get_publishers())
+            .map(|publishers| publishers.into_iter().map(Publisher::new).collect())
+    }
+
+    /// This operation retrieves all the [`Subscriber`] entities created from the [`DomainParticipant`], including the ones
+    /// created by other parts of the application. The built-in subscriber, which is obtained with
+    /// [`DomainParticipant::get_builtin_subscriber()`], is not part of the list.
+    #[tracing::instrument(skip(self))]
+    pub fn get_subscribers(&self) -> DdsResult<Vec<Subscriber>> {
+        block_on(self.participant_async.get_subscribers())
+            .map(|subscribers| subscribers.into_iter().map(Subscriber::new).collect())
+    }
+
     /// This operation returns the current value of the time that the service uses to time-stamp data-writes and to set the reception timestamp
     /// for the data-updates it receives.
     #[tracing::instrument(skip(self))]
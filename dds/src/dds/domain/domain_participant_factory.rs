@@ -3,7 +3,7 @@ use crate::{
     configuration::DustDdsConfiguration,
     dds_async::{
         domain_participant_factory::DomainParticipantFactoryAsync,
-        domain_participant_listener::DomainParticipantListenerAsync,
+        domain_participant_listener::DomainParticipantListenerAsync, runtime::Spawner,
     },
     domain::domain_participant_listener::DomainParticipantListener,
     infrastructure::{
@@ -14,20 +14,76 @@ use crate::{
     runtime::executor::block_on,
 };
 
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 use tracing::warn;
 
+#[cfg(feature = "xml")]
+use crate::infrastructure::qos_profile::QosProfile;
+#[cfg(feature = "xml")]
+use std::path::Path;
+
 /// DomainId type alias
 pub type DomainId = i32;
 
 /// The sole purpose of this class is to allow the creation and destruction of [`DomainParticipant`] objects.
 /// [`DomainParticipantFactory`] itself has no factory. It is a pre-existing singleton object that can be accessed by means of the
 /// [`DomainParticipantFactory::get_instance`] operation.
+/// Independent factories, each with its own configuration, default participant QoS and runtime, can additionally be created by
+/// means of [`DomainParticipantFactory::new`] so that libraries using Dust DDS don't share the state of the singleton.
 pub struct DomainParticipantFactory {
-    participant_factory_async: &'static DomainParticipantFactoryAsync,
+    participant_factory_async: Arc<DomainParticipantFactoryAsync>,
+}
+
+impl Default for DomainParticipantFactory {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DomainParticipantFactory {
+    /// Create a new [`DomainParticipantFactory`] independent from the singleton and from any other factory. The
+    /// participants created by the factory are deleted by means of the same factory.
+    pub fn new() -> Self {
+        Self {
+            participant_factory_async: Arc::new(DomainParticipantFactoryAsync::new()),
+        }
+    }
+
+    /// Create a new [`DomainParticipantFactory`] whose tasks, including the ones of the participants it creates, are
+    /// spawned on the given [`Tokio`](https://crates.io/crates/tokio) runtime instead of on threads created by Dust DDS.
+    /// The operations of the factory and its entities must not be called from the worker threads of that runtime since
+    /// they block until the tasks complete.
+    #[cfg(feature = "tokio")]
+    pub fn new_with_runtime(runtime: tokio::runtime::Handle) -> Self {
+        Self {
+            participant_factory_async: Arc::new(DomainParticipantFactoryAsync::new_with_runtime(
+                runtime,
+            )),
+        }
+    }
+
+    /// Create a new [`DomainParticipantFactory`] whose tasks, including the ones of the participants it creates, are
+    /// spawned by the given [`Spawner`] on an async runtime instead of on threads created by Dust DDS. The operations
+    /// of the factory and its entities must not be called from the threads of that runtime since they block until the
+    /// tasks complete.
+    pub fn new_with_spawner(spawner: impl Spawner) -> Self {
+        Self {
+            participant_factory_async: Arc::new(DomainParticipantFactoryAsync::new_with_spawner(
+                spawner,
+            )),
+        }
+    }
+
+    /// Create a new [`DomainParticipantFactory`] whose tasks, including the ones of the participants it creates, run
+    /// on a fixed pool of `number_of_threads` threads named `thread_name` instead of on a thread per participant.
+    pub fn new_with_thread_pool(number_of_threads: usize, thread_name: &str) -> Self {
+        Self {
+            participant_factory_async: Arc::new(
+                DomainParticipantFactoryAsync::new_with_thread_pool(number_of_threads, thread_name),
+            ),
+        }
+    }
+
     /// This operation creates a new [`DomainParticipant`] object. The [`DomainParticipant`] signifies that the calling application intends
     /// to join the Domain identified by the `domain_id` argument.
     /// If the specified QoS policies are not consistent, the operation will fail and no [`DomainParticipant`] will be created.
@@ -69,7 +125,7 @@ impl DomainParticipantFactory {
     pub fn get_instance() -> &'static Self {
         static PARTICIPANT_FACTORY: OnceLock<DomainParticipantFactory> = OnceLock::new();
         PARTICIPANT_FACTORY.get_or_init(|| Self {
-            participant_factory_async: DomainParticipantFactoryAsync::get_instance(),
+            participant_factory_async: DomainParticipantFactoryAsync::shared_instance().clone(),
         })
     }
 
@@ -122,10 +178,45 @@ impl DomainParticipantFactory {
     pub fn get_qos(&self) -> DdsResult<DomainParticipantFactoryQos> {
         block_on(self.participant_factory_async.get_qos())
     }
+
+    /// This operation loads the QoS profiles of an XML document in the format of the OMG DDS-XML specification, that is a `<dds>`
+    /// element containing `<qos_library>` elements whose `<qos_profile>` elements give the QoS of the entities by means of the
+    /// `<domainparticipant_qos>`, `<topic_qos>`, `<publisher_qos>`, `<subscriber_qos>`, `<datawriter_qos>` and `<datareader_qos>`
+    /// elements. A profile can derive from a profile of the same document or from a profile loaded previously by means of its
+    /// `base_name` attribute. A profile with the name of an already loaded profile replaces it.
+    /// If the document is not valid or contains an unsupported QoS policy, none of its profiles is loaded and the operation returns
+    /// [`DdsError::Error`](crate::infrastructure::error::DdsError::Error).
+    #[cfg(feature = "xml")]
+    #[tracing::instrument(skip(self, document))]
+    pub fn load_qos_profiles(&self, document: &str) -> DdsResult<()> {
+        block_on(self.participant_factory_async.load_qos_profiles(document))
+    }
+
+    /// This operation loads the QoS profiles of the XML file at `path` in the same way as [`DomainParticipantFactory::load_qos_profiles`].
+    #[cfg(feature = "xml")]
+    #[tracing::instrument(skip(self, path))]
+    pub fn load_qos_profiles_from_file(&self, path: impl AsRef<Path>) -> DdsResult<()> {
+        block_on(
+            self.participant_factory_async
+                .load_qos_profiles_from_file(path),
+        )
+    }
+
+    /// This operation retrieves the QoS of the entities given by a loaded QoS profile. The `profile_name` is made of the names of the
+    /// library and of the profile separated by `::`, for example `MyLibrary::MyProfile`. If no profile with that name was loaded the
+    /// operation returns [`DdsError::BadParameter`](crate::infrastructure::error::DdsError::BadParameter).
+    #[cfg(feature = "xml")]
+    #[tracing::instrument(skip(self))]
+    pub fn get_qos_from_profile(&self, profile_name: &str) -> DdsResult<QosProfile> {
+        block_on(
+            self.participant_factory_async
+                .get_qos_from_profile(profile_name),
+        )
+    }
 }
 
 impl DomainParticipantFactory {
-    /// Set the configuration of the [`DomainParticipantFactory`] singleton
+    /// Set the configuration of the [`DomainParticipantFactory`]
     pub fn set_configuration(&self, configuration: DustDdsConfiguration) -> DdsResult<()> {
         block_on(
             self.participant_factory_async
@@ -133,7 +224,7 @@ impl DomainParticipantFactory {
         )
     }
 
-    /// Get the current configuration of the [`DomainParticipantFactory`] singleton
+    /// Get the current configuration of the [`DomainParticipantFactory`]
     pub fn get_configuration(&self) -> DdsResult<DustDdsConfiguration> {
         block_on(self.participant_factory_async.get_configuration())
     }
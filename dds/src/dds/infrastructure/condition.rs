@@ -1,6 +1,8 @@
 use crate::{
-    dds_async::condition::StatusConditionAsync, infrastructure::error::DdsResult,
+    dds_async::condition::{GuardConditionAsync, ReadConditionAsync, StatusConditionAsync},
+    infrastructure::error::DdsResult,
     runtime::executor::block_on,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
 };
 
 use super::status::StatusKind;
@@ -54,3 +56,93 @@ impl StatusCondition {
         block_on(self.condition_async.get_trigger_value())
     }
 }
+
+/// A [`ReadCondition`] object is a specific Condition that is associated with a
+/// [`DataReader`](crate::subscription::data_reader::DataReader) and created with
+/// [`DataReader::create_readcondition`](crate::subscription::data_reader::DataReader::create_readcondition).
+/// The *trigger_value* of the [`ReadCondition`] is [`true`] when the [`DataReader`](crate::subscription::data_reader::DataReader)
+/// has at least one sample whose sample state, view state and instance state are in the masks of the [`ReadCondition`].
+#[derive(Clone)]
+pub struct ReadCondition {
+    condition_async: ReadConditionAsync,
+}
+
+impl ReadCondition {
+    pub(crate) fn new(condition_async: ReadConditionAsync) -> Self {
+        Self { condition_async }
+    }
+
+    pub(crate) fn condition_async(&self) -> &ReadConditionAsync {
+        &self.condition_async
+    }
+}
+
+impl ReadCondition {
+    /// This operation returns the set of sample states that are taken into account to determine the *trigger_value* of the [`ReadCondition`].
+    /// These are the sample states specified when the [`ReadCondition`] was created.
+    pub fn get_sample_state_mask(&self) -> &[SampleStateKind] {
+        self.condition_async.get_sample_state_mask()
+    }
+
+    /// This operation returns the set of view states that are taken into account to determine the *trigger_value* of the [`ReadCondition`].
+    /// These are the view states specified when the [`ReadCondition`] was created.
+    pub fn get_view_state_mask(&self) -> &[ViewStateKind] {
+        self.condition_async.get_view_state_mask()
+    }
+
+    /// This operation returns the set of instance states that are taken into account to determine the *trigger_value* of the [`ReadCondition`].
+    /// These are the instance states specified when the [`ReadCondition`] was created.
+    pub fn get_instance_state_mask(&self) -> &[InstanceStateKind] {
+        self.condition_async.get_instance_state_mask()
+    }
+}
+
+/// This implementation block contains the Condition operations for the [`ReadCondition`].
+impl ReadCondition {
+    /// This operation retrieves the *trigger_value* of the [`ReadCondition`].
+    pub fn get_trigger_value(&self) -> DdsResult<bool> {
+        block_on(self.condition_async.get_trigger_value())
+    }
+}
+
+/// A [`GuardCondition`] object is a specific Condition whose *trigger_value* is completely under the control of the application.
+/// It can be attached to a [`WaitSet`](crate::infrastructure::wait_set::WaitSet) to wake up a thread blocked on
+/// [`WaitSet::wait`](crate::infrastructure::wait_set::WaitSet::wait) from another thread.
+/// The *trigger_value* of a newly created [`GuardCondition`] is [`false`].
+#[derive(Clone, Default)]
+pub struct GuardCondition {
+    condition_async: GuardConditionAsync,
+}
+
+impl GuardCondition {
+    /// Create a new [`GuardCondition`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn from_async(condition_async: GuardConditionAsync) -> Self {
+        Self { condition_async }
+    }
+
+    pub(crate) fn condition_async(&self) -> &GuardConditionAsync {
+        &self.condition_async
+    }
+}
+
+impl GuardCondition {
+    /// This operation sets the *trigger_value* of the [`GuardCondition`].
+    /// [`WaitSet`](crate::infrastructure::wait_set::WaitSet) objects behavior depend on the changes of the *trigger_value* of their
+    /// attached conditions. Therefore, any [`WaitSet`](crate::infrastructure::wait_set::WaitSet) to which the [`GuardCondition`] is
+    /// attached is potentially affected by this operation.
+    pub fn set_trigger_value(&self, value: bool) -> DdsResult<()> {
+        block_on(self.condition_async.set_trigger_value(value))
+    }
+}
+
+/// This implementation block contains the Condition operations for the [`GuardCondition`].
+impl GuardCondition {
+    /// This operation retrieves the *trigger_value* of the [`GuardCondition`].
+    pub fn get_trigger_value(&self) -> DdsResult<bool> {
+        block_on(self.condition_async.get_trigger_value())
+    }
+}
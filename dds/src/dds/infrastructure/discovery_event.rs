@@ -0,0 +1,26 @@
+use super::instance::InstanceHandle;
+
+/// Change of a remote entity reported by the discovery change streams of the
+/// [`DomainParticipantAsync`](crate::dds_async::domain_participant::DomainParticipantAsync).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DiscoveryEvent<T> {
+    /// A remote entity was discovered.
+    Added {
+        /// Handle of the discovered entity.
+        handle: InstanceHandle,
+        /// Data announced by the discovered entity.
+        data: T,
+    },
+    /// A remote entity that was already discovered announced different data.
+    Updated {
+        /// Handle of the discovered entity.
+        handle: InstanceHandle,
+        /// Data newly announced by the discovered entity.
+        data: T,
+    },
+    /// A remote entity is no longer discovered.
+    Removed {
+        /// Handle of the entity which was removed.
+        handle: InstanceHandle,
+    },
+}
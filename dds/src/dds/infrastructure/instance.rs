@@ -69,6 +69,22 @@ impl Default for InstanceHandle {
     }
 }
 
+/// Enumeration of the kinds of Entity that can be contained in a
+/// [`DomainParticipant`](crate::domain::domain_participant::DomainParticipant).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum EntityKind {
+    /// The entity is a [`Topic`](crate::topic_definition::topic::Topic)
+    Topic,
+    /// The entity is a [`Publisher`](crate::publication::publisher::Publisher)
+    Publisher,
+    /// The entity is a [`Subscriber`](crate::subscription::subscriber::Subscriber)
+    Subscriber,
+    /// The entity is a [`DataWriter`](crate::publication::data_writer::DataWriter)
+    DataWriter,
+    /// The entity is a [`DataReader`](crate::subscription::data_reader::DataReader)
+    DataReader,
+}
+
 /// Special constant value representing a 'nil' [`InstanceHandle`]
 pub const HANDLE_NIL: InstanceHandle = InstanceHandle([0; 16]);
 
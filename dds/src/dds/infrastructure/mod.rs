@@ -1,6 +1,9 @@
 /// Classes related to the status conditions.
 pub mod condition;
 
+/// Classes related to the events reported by the discovery change streams.
+pub mod discovery_event;
+
 /// Classes related to the error and return codes.
 pub mod error;
 
@@ -13,9 +16,16 @@ pub mod qos;
 /// Classes related to the qos policies.
 pub mod qos_policy;
 
+/// Classes related to the QoS profiles loaded from XML documents.
+#[cfg(feature = "xml")]
+pub mod qos_profile;
+
 /// Classes related to communication statuses.
 pub mod status;
 
+/// Classes related to the diagnostic statistics of the entities.
+pub mod statistics;
+
 /// Classes related to time and duration.
 pub mod time;
 
@@ -5,13 +5,18 @@ use crate::infrastructure::{
 
 use super::{
     qos_policy::{
-        DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
-        DurabilityQosPolicy, EntityFactoryQosPolicy, GroupDataQosPolicy, HistoryQosPolicy,
-        HistoryQosPolicyKind, LatencyBudgetQosPolicy, LifespanQosPolicy, LivelinessQosPolicy,
-        OwnershipQosPolicy, OwnershipStrengthQosPolicy, PartitionQosPolicy, PresentationQosPolicy,
-        ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
-        ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy,
-        TransportPriorityQosPolicy, UserDataQosPolicy, WriterDataLifecycleQosPolicy,
+        DataRepresentationId, DataRepresentationQosPolicy, DeadlineQosPolicy,
+        DeliveryModeQosPolicy, DeliveryModeQosPolicyKind, DestinationOrderQosPolicy,
+        DisablePositiveAcksQosPolicy, DurabilityQosPolicy, DurabilityServiceQosPolicy,
+        EntityFactoryQosPolicy, EntityNameQosPolicy, GroupDataQosPolicy, HistoricalDataQosPolicy,
+        HistoryOverflowQosPolicy, HistoryOverflowQosPolicyKind, HistoryQosPolicy,
+        HistoryQosPolicyKind, LatencyBudgetQosPolicy, Length, LifespanQosPolicy,
+        LivelinessQosPolicy, LocatorSelectionQosPolicy, OwnershipQosPolicy,
+        OwnershipStrengthQosPolicy, PartitionQosPolicy, PresentationQosPolicy, PropertyQosPolicy,
+        PublishRateQosPolicy, ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy,
+        ReliabilityQosPolicyKind, ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy,
+        TopicDataQosPolicy, TransportPriorityQosPolicy, UserDataQosPolicy,
+        WriterDataLifecycleQosPolicy, XCDR_DATA_REPRESENTATION,
     },
     time::DurationKind,
 };
@@ -39,6 +44,10 @@ pub struct DomainParticipantQos {
     pub user_data: UserDataQosPolicy,
     /// Value of the entity factory QoS policy.
     pub entity_factory: EntityFactoryQosPolicy,
+    /// Value of the entity name QoS policy.
+    pub entity_name: EntityNameQosPolicy,
+    /// Value of the property QoS policy.
+    pub property: PropertyQosPolicy,
 }
 
 /// QoS policies applicable to the [`Publisher`](crate::publication::publisher::Publisher)
@@ -54,11 +63,23 @@ pub struct PublisherQos {
     pub entity_factory: EntityFactoryQosPolicy,
 }
 
+impl PublisherQos {
+    pub(crate) fn check_immutability(&self, other: &Self) -> DdsResult<()> {
+        if self.presentation != other.presentation {
+            Err(DdsError::ImmutablePolicy)
+        } else {
+            Ok(())
+        }
+    }
+}
+
 /// QoS policies applicable to the [`DataWriter`](crate::publication::data_writer::DataWriter)
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct DataWriterQos {
     /// Value of the durability QoS policy.
     pub durability: DurabilityQosPolicy,
+    /// Value of the durability service QoS policy.
+    pub durability_service: DurabilityServiceQosPolicy,
     /// Value of the deadline QoS policy.
     pub deadline: DeadlineQosPolicy,
     /// Value of the latency budget QoS policy.
@@ -87,6 +108,22 @@ pub struct DataWriterQos {
     pub writer_data_lifecycle: WriterDataLifecycleQosPolicy,
     /// Value of the data representation QoS policy.
     pub representation: DataRepresentationQosPolicy,
+    /// Value of the entity name QoS policy.
+    pub entity_name: EntityNameQosPolicy,
+    /// Value of the property QoS policy.
+    pub property: PropertyQosPolicy,
+    /// Value of the disable positive acks QoS policy.
+    pub disable_positive_acks: DisablePositiveAcksQosPolicy,
+    /// Value of the history overflow QoS policy.
+    pub history_overflow: HistoryOverflowQosPolicy,
+    /// Value of the locator selection QoS policy.
+    pub locator_selection: LocatorSelectionQosPolicy,
+    /// Value of the publish rate QoS policy.
+    pub publish_rate: PublishRateQosPolicy,
+    /// Value of the delivery mode QoS policy.
+    pub delivery_mode: DeliveryModeQosPolicy,
+    /// Value of the historical data QoS policy.
+    pub historical_data: HistoricalDataQosPolicy,
 }
 
 impl Default for DataWriterQos {
@@ -100,6 +137,7 @@ impl Default for DataWriterQos {
                 )),
             },
             durability: DurabilityQosPolicy::default(),
+            durability_service: DurabilityServiceQosPolicy::default(),
             deadline: DeadlineQosPolicy::default(),
             latency_budget: LatencyBudgetQosPolicy::default(),
             liveliness: LivelinessQosPolicy::default(),
@@ -113,6 +151,14 @@ impl Default for DataWriterQos {
             transport_priority: TransportPriorityQosPolicy::default(),
             writer_data_lifecycle: WriterDataLifecycleQosPolicy::default(),
             representation: DataRepresentationQosPolicy::default(),
+            entity_name: EntityNameQosPolicy::default(),
+            property: PropertyQosPolicy::default(),
+            disable_positive_acks: DisablePositiveAcksQosPolicy::default(),
+            history_overflow: HistoryOverflowQosPolicy::default(),
+            locator_selection: LocatorSelectionQosPolicy::default(),
+            publish_rate: PublishRateQosPolicy::default(),
+            delivery_mode: DeliveryModeQosPolicy::default(),
+            historical_data: HistoricalDataQosPolicy::default(),
         }
     }
 }
@@ -130,6 +176,32 @@ impl DataWriterQos {
             return Err(DdsError::InconsistentPolicy);
         }
 
+        // Dropping the oldest samples is only allowed for best-effort writers keeping the last samples
+        if self.history_overflow.kind == HistoryOverflowQosPolicyKind::DropOldest
+            && (self.reliability.kind != ReliabilityQosPolicyKind::BestEffort
+                || self.history.kind == HistoryQosPolicyKind::KeepAll)
+        {
+            return Err(DdsError::InconsistentPolicy);
+        }
+
+        if !self.durability_service.is_consistent() {
+            return Err(DdsError::InconsistentPolicy);
+        }
+
+        // Only reliable readers request the samples of a writer in pull mode
+        if self.delivery_mode.kind == DeliveryModeQosPolicyKind::Pull
+            && self.reliability.kind != ReliabilityQosPolicyKind::Reliable
+        {
+            return Err(DdsError::InconsistentPolicy);
+        }
+
+        // A publish rate of zero would never allow any sample to be written
+        if self.publish_rate.max_samples_per_second == Length::Limited(0)
+            || self.publish_rate.max_bytes_per_second == Length::Limited(0)
+        {
+            return Err(DdsError::InconsistentPolicy);
+        }
+
         // The setting of RESOURCE_LIMITS max_samples_per_instance must be consistent with the HISTORY depth. For these two
         // QoS to be consistent, they must verify that *depth <= max_samples_per_instance.*
         match self.history.kind {
@@ -146,18 +218,33 @@ impl DataWriterQos {
 
     pub(crate) fn check_immutability(&self, other: &Self) -> DdsResult<()> {
         if self.durability != other.durability
+            || self.durability_service != other.durability_service
             || self.liveliness != other.liveliness
             || self.reliability != other.reliability
             || self.destination_order != other.destination_order
             || self.history != other.history
             || self.resource_limits != other.resource_limits
             || self.ownership != other.ownership
+            || self.disable_positive_acks != other.disable_positive_acks
+            || self.locator_selection != other.locator_selection
+            || self.delivery_mode != other.delivery_mode
+            || self.historical_data != other.historical_data
+            || self.representation != other.representation
         {
             Err(DdsError::ImmutablePolicy)
         } else {
             Ok(())
         }
     }
+
+    // The representation used to serialize the samples, which is XCDR if none is given
+    pub(crate) fn data_representation(&self) -> DataRepresentationId {
+        self.representation
+            .value
+            .first()
+            .copied()
+            .unwrap_or(XCDR_DATA_REPRESENTATION)
+    }
 }
 
 /// QoS policies applicable to the [`Subscriber`](crate::subscription::subscriber::Subscriber)
@@ -212,6 +299,12 @@ pub struct DataReaderQos {
     pub reader_data_lifecycle: ReaderDataLifecycleQosPolicy,
     /// Value of the data representation QoS policy.
     pub representation: DataRepresentationQosPolicy,
+    /// Value of the entity name QoS policy.
+    pub entity_name: EntityNameQosPolicy,
+    /// Value of the property QoS policy.
+    pub property: PropertyQosPolicy,
+    /// Value of the disable positive acks QoS policy.
+    pub disable_positive_acks: DisablePositiveAcksQosPolicy,
 }
 
 impl Default for DataReaderQos {
@@ -236,6 +329,9 @@ impl Default for DataReaderQos {
             time_based_filter: TimeBasedFilterQosPolicy::default(),
             reader_data_lifecycle: ReaderDataLifecycleQosPolicy::default(),
             representation: DataRepresentationQosPolicy::default(),
+            entity_name: EntityNameQosPolicy::default(),
+            property: PropertyQosPolicy::default(),
+            disable_positive_acks: DisablePositiveAcksQosPolicy::default(),
         }
     }
 }
@@ -276,6 +372,8 @@ impl DataReaderQos {
             || self.history != other.history
             || self.resource_limits != other.resource_limits
             || self.ownership != other.ownership
+            || self.disable_positive_acks != other.disable_positive_acks
+            || self.representation != other.representation
         {
             Err(DdsError::ImmutablePolicy)
         } else {
@@ -291,6 +389,8 @@ pub struct TopicQos {
     pub topic_data: TopicDataQosPolicy,
     /// Value of the durability QoS policy.
     pub durability: DurabilityQosPolicy,
+    /// Value of the durability service QoS policy.
+    pub durability_service: DurabilityServiceQosPolicy,
     /// Value of the deadline QoS policy.
     pub deadline: DeadlineQosPolicy,
     /// Value of the latency budget QoS policy.
@@ -327,6 +427,7 @@ impl Default for TopicQos {
             },
             topic_data: TopicDataQosPolicy::default(),
             durability: DurabilityQosPolicy::default(),
+            durability_service: DurabilityServiceQosPolicy::default(),
             deadline: DeadlineQosPolicy::default(),
             latency_budget: LatencyBudgetQosPolicy::default(),
             liveliness: LivelinessQosPolicy::default(),
@@ -349,6 +450,10 @@ impl TopicQos {
             return Err(DdsError::InconsistentPolicy);
         }
 
+        if !self.durability_service.is_consistent() {
+            return Err(DdsError::InconsistentPolicy);
+        }
+
         // The setting of RESOURCE_LIMITS max_samples_per_instance must be consistent with the HISTORY depth. For these two
         // QoS to be consistent, they must verify that *depth <= max_samples_per_instance.*
         match self.history.kind {
@@ -119,6 +119,7 @@ pub trait QosPolicy {
 
 const USERDATA_QOS_POLICY_NAME: &str = "UserData";
 const DURABILITY_QOS_POLICY_NAME: &str = "Durability";
+const DURABILITYSERVICE_QOS_POLICY_NAME: &str = "DurabilityService";
 const PRESENTATION_QOS_POLICY_NAME: &str = "Presentation";
 const DEADLINE_QOS_POLICY_NAME: &str = "Deadline";
 const LATENCYBUDGET_QOS_POLICY_NAME: &str = "LatencyBudget";
@@ -139,6 +140,14 @@ const TRANSPORTPRIORITY_QOS_POLICY_NAME: &str = "TransportPriority";
 const GROUPDATA_QOS_POLICY_NAME: &str = "GroupData";
 const LIFESPAN_QOS_POLICY_NAME: &str = "Lifespan";
 const DATA_REPRESENTATION_QOS_POLICY_NAME: &str = "DataRepresentation";
+const ENTITYNAME_QOS_POLICY_NAME: &str = "EntityName";
+const PROPERTY_QOS_POLICY_NAME: &str = "Property";
+const DISABLEPOSITIVEACKS_QOS_POLICY_NAME: &str = "DisablePositiveAcks";
+const HISTORYOVERFLOW_QOS_POLICY_NAME: &str = "HistoryOverflow";
+const LOCATORSELECTION_QOS_POLICY_NAME: &str = "LocatorSelection";
+const PUBLISHRATE_QOS_POLICY_NAME: &str = "PublishRate";
+const DELIVERYMODE_QOS_POLICY_NAME: &str = "DeliveryMode";
+const HISTORICALDATA_QOS_POLICY_NAME: &str = "HistoricalData";
 
 /// QosPolicy Id representing an invalid QoS policy
 pub const INVALID_QOS_POLICY_ID: QosPolicyId = 0;
@@ -405,6 +414,66 @@ impl Default for DurabilityQosPolicy {
     }
 }
 
+/// This policy configures the durability service which keeps the historical samples of the [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// objects whose [`DurabilityQosPolicy`] is [`DurabilityQosPolicyKind::Transient`] or [`DurabilityQosPolicyKind::Persistent`].
+///
+/// The samples written by those writers are stored by the durability service of their participant so that they remain available after the
+/// writer that wrote them is deleted. The samples of [`DurabilityQosPolicyKind::Transient`] writers are kept in memory while the samples of
+/// [`DurabilityQosPolicyKind::Persistent`] writers are stored in the persistence directory of the
+/// [`DustDdsConfiguration`](crate::configuration::DustDdsConfiguration), if any, so that they also survive the application.
+/// A writer created for a topic with stored samples delivers them to the late-joining readers as its own historical data.
+/// The [`DurabilityServiceQosPolicy::history_kind`] and the resource limits control how many samples are stored for each topic, in the same way as the
+/// [`HistoryQosPolicy`] and [`ResourceLimitsQosPolicy`] do for the writer history.
+/// The [`DurabilityServiceQosPolicy::service_cleanup_delay`] controls for how long the samples of a disposed instance are kept by the
+/// durability service.
+#[derive(Debug, PartialEq, Eq, Clone, XTypesSerialize, XTypesDeserialize)]
+pub struct DurabilityServiceQosPolicy {
+    /// Time after which the samples of a disposed instance are removed from the durability service
+    pub service_cleanup_delay: DurationKind,
+    /// Kind of history used by the durability service to keep the samples of each instance
+    pub history_kind: HistoryQosPolicyKind,
+    /// Maximum number of samples stored for a topic
+    pub max_samples: Length,
+    /// Maximum number of instances stored for a topic
+    pub max_instances: Length,
+    /// Maximum number of samples of each instance stored for a topic
+    pub max_samples_per_instance: Length,
+}
+
+impl QosPolicy for DurabilityServiceQosPolicy {
+    fn name(&self) -> &str {
+        DURABILITYSERVICE_QOS_POLICY_NAME
+    }
+}
+
+impl DurabilityServiceQosPolicy {
+    // The limits must be consistent in the same way as the HISTORY and RESOURCE_LIMITS policies
+    pub(crate) fn is_consistent(&self) -> bool {
+        let is_depth_consistent = match self.history_kind {
+            HistoryQosPolicyKind::KeepLast(depth) => {
+                depth as usize <= self.max_samples_per_instance
+            }
+            HistoryQosPolicyKind::KeepAll => true,
+        };
+        self.max_samples >= self.max_samples_per_instance && is_depth_consistent
+    }
+}
+
+impl Default for DurabilityServiceQosPolicy {
+    fn default() -> Self {
+        Self {
+            service_cleanup_delay: DurationKind::Finite(Duration::new(
+                DURATION_ZERO_SEC,
+                DURATION_ZERO_NSEC,
+            )),
+            history_kind: HistoryQosPolicyKind::KeepLast(1),
+            max_samples: Length::Unlimited,
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Unlimited,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, XTypesSerialize, XTypesDeserialize)]
 /// Enumeration representing the different types of Presentation QoS policy access scope.
 pub enum PresentationQosPolicyAccessScopeKind {
@@ -412,20 +481,20 @@ pub enum PresentationQosPolicyAccessScopeKind {
     Instance,
     /// Access scope per topic
     Topic,
+    /// Access scope per publisher or subscriber
+    Group,
 }
 
 impl PartialOrd for PresentationQosPolicyAccessScopeKind {
     fn partial_cmp(&self, other: &PresentationQosPolicyAccessScopeKind) -> Option<Ordering> {
-        match self {
-            PresentationQosPolicyAccessScopeKind::Instance => match other {
-                PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Equal),
-                PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Less),
-            },
-            PresentationQosPolicyAccessScopeKind::Topic => match other {
-                PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Greater),
-                PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Equal),
-            },
+        fn rank(access_scope: &PresentationQosPolicyAccessScopeKind) -> u8 {
+            match access_scope {
+                PresentationQosPolicyAccessScopeKind::Instance => 0,
+                PresentationQosPolicyAccessScopeKind::Topic => 1,
+                PresentationQosPolicyAccessScopeKind::Group => 2,
+            }
         }
+        rank(self).partial_cmp(&rank(other))
     }
 }
 
@@ -446,6 +515,10 @@ impl PartialOrd for PresentationQosPolicyAccessScopeKind {
 ///   independently. That is, changes made to instances within each individual DataWriter will be available as coherent with
 ///   respect to other changes to instances in that same DataWriter, but will not be grouped with changes made to instances
 ///   belonging to a different DataWriter.
+/// - If access_scope is set to GROUP, then coherent changes made to instances through DataWriter entities attached to a
+///   common Publisher are made available as a unit to remote subscribers. On the subscribing side the changes received while
+///   the application accesses the data between `Subscriber::begin_access` and `Subscriber::end_access` are only made available
+///   once the access ends, so that all the DataReader entities of the Subscriber present a coherent view of the data.
 ///   If ordered_access is set, then the access_scope controls the maximum extent for which order will be preserved by the Service.
 /// - If access_scope is set to INSTANCE (the lowest level), then changes to each instance are considered unordered relative
 ///   to changes to any other instance. That means that changes (creations, deletions, modifications) made to two instances
@@ -455,6 +528,10 @@ impl PartialOrd for PresentationQosPolicyAccessScopeKind {
 ///   available to subscribers in the same order they occur. Changes made to instances through different DataWriter entities
 ///   are not necessarily seen in the order they occur. This is the case, even if the changes are made by a single application
 ///   thread using DataWriter objects attached to the same Publisher.
+/// - If access_scope is set to GROUP, changes made to instances via DataWriter entities attached to the same Publisher are
+///   made available to subscribers in the same order they occur. The subscribing application retrieves this order across the
+///   DataReader entities of the Subscriber by means of `Subscriber::get_datareaders` between `Subscriber::begin_access` and
+///   `Subscriber::end_access`.
 ///
 /// Note that this QoS policy controls the scope at which related changes are made available to the subscriber. This means the
 /// subscriber can access the changes in a coherent manner and in the proper order; however, it does not necessarily imply that the
@@ -1183,7 +1260,8 @@ impl Default for ReaderDataLifecycleQosPolicy {
 
 /*******  DDS X-TYPES Extension **********/
 
-type DataRepresentationId = u16;
+/// Identifier of a data representation
+pub type DataRepresentationId = u16;
 /// XCDR data representation
 pub const XCDR_DATA_REPRESENTATION: DataRepresentationId = 0;
 /// XML data representation
@@ -1206,9 +1284,360 @@ impl QosPolicy for DataRepresentationQosPolicy {
     }
 }
 
+/// This policy is a vendor-neutral extension which allows the application to assign a human-readable name to the
+/// [`DomainParticipant`](crate::domain::domain_participant::DomainParticipant), [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// and [`DataReader`](crate::subscription::data_reader::DataReader) entities.
+///
+/// The name is propagated by means of the built-in topics so that remote applications and tools can display it
+/// instead of the raw GUID. An empty name means that no name is assigned to the entity.
+#[derive(Debug, PartialEq, Eq, Clone, Default, XTypesSerialize, XTypesDeserialize)]
+pub struct EntityNameQosPolicy {
+    /// Entity name value
+    pub name: String,
+}
+
+impl QosPolicy for EntityNameQosPolicy {
+    fn name(&self) -> &str {
+        ENTITYNAME_QOS_POLICY_NAME
+    }
+}
+
+/// Structure representing a name/value pair of the [`PropertyQosPolicy`].
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Property {
+    /// Name of the property
+    pub name: String,
+    /// Value of the property
+    pub value: String,
+    /// Indicates whether the property is sent to the remote applications by means of the built-in topics
+    pub propagate: bool,
+}
+impl XTypesSerialize for Property {
+    fn serialize(&self, serializer: impl XTypesSerializer) -> Result<(), XTypesError> {
+        let mut s = serializer.serialize_final_struct()?;
+        s.serialize_field(&self.name, "name")?;
+        s.serialize_field(&self.value, "value")
+    }
+}
+impl<'de> XTypesDeserialize<'de> for Property {
+    fn deserialize(deserializer: impl XTypesDeserializer<'de>) -> Result<Self, XTypesError> {
+        let mut d = deserializer.deserialize_final_struct()?;
+        Ok(Self {
+            name: d.deserialize_field("name")?,
+            value: d.deserialize_field("value")?,
+            // Only propagated properties are ever received
+            propagate: true,
+        })
+    }
+}
+
+/// Structure representing a name/binary value pair of the [`PropertyQosPolicy`].
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct BinaryProperty {
+    /// Name of the property
+    pub name: String,
+    /// Binary value of the property
+    pub value: Vec<u8>,
+    /// Indicates whether the property is sent to the remote applications by means of the built-in topics
+    pub propagate: bool,
+}
+impl XTypesSerialize for BinaryProperty {
+    fn serialize(&self, serializer: impl XTypesSerializer) -> Result<(), XTypesError> {
+        let mut s = serializer.serialize_final_struct()?;
+        s.serialize_field(&self.name, "name")?;
+        s.serialize_field(&Bytes(self.value.as_slice()), "value")
+    }
+}
+impl<'de> XTypesDeserialize<'de> for BinaryProperty {
+    fn deserialize(deserializer: impl XTypesDeserializer<'de>) -> Result<Self, XTypesError> {
+        let mut d = deserializer.deserialize_final_struct()?;
+        Ok(Self {
+            name: d.deserialize_field("name")?,
+            value: d.deserialize_field::<ByteBuf>("value")?.0,
+            // Only propagated properties are ever received
+            propagate: true,
+        })
+    }
+}
+
+/// This policy is a DDS-Security extension which allows the application to attach a list of name/value pairs to the
+/// [`DomainParticipant`](crate::domain::domain_participant::DomainParticipant), [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// and [`DataReader`](crate::subscription::data_reader::DataReader) entities.
+///
+/// The properties are used to configure plugins of the Service and to attach deployment metadata to the entities. Only the
+/// properties with the `propagate` flag set are sent to the remote applications by means of the built-in topics.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct PropertyQosPolicy {
+    /// List of string properties
+    pub value: Vec<Property>,
+    /// List of binary properties
+    pub binary_value: Vec<BinaryProperty>,
+}
+
+impl PropertyQosPolicy {
+    pub(crate) fn propagated(&self) -> Self {
+        Self {
+            value: self.value.iter().filter(|p| p.propagate).cloned().collect(),
+            binary_value: self
+                .binary_value
+                .iter()
+                .filter(|p| p.propagate)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl XTypesSerialize for PropertyQosPolicy {
+    fn serialize(&self, serializer: impl XTypesSerializer) -> Result<(), XTypesError> {
+        let mut s = serializer.serialize_final_struct()?;
+        s.serialize_field(&self.value, "value")?;
+        s.serialize_field(&self.binary_value, "binary_value")
+    }
+}
+impl<'de> XTypesDeserialize<'de> for PropertyQosPolicy {
+    fn deserialize(deserializer: impl XTypesDeserializer<'de>) -> Result<Self, XTypesError> {
+        let mut d = deserializer.deserialize_final_struct()?;
+        Ok(Self {
+            value: d.deserialize_field("value")?,
+            // Some implementations omit the binary properties. An absent list is treated as
+            // empty for interoperability reasons:
+            binary_value: d.deserialize_trailing_field("binary_value")?,
+        })
+    }
+}
+
+impl QosPolicy for PropertyQosPolicy {
+    fn name(&self) -> &str {
+        PROPERTY_QOS_POLICY_NAME
+    }
+}
+
+/// This policy is a vendor extension which allows reliable [`DataReader`](crate::subscription::data_reader::DataReader) entities
+/// to only send negative acknowledgments for the missing samples instead of acknowledging every received sample.
+///
+/// Positive acknowledgments are only disabled between a [`DataWriter`](crate::publication::data_writer::DataWriter) and a
+/// [`DataReader`](crate::subscription::data_reader::DataReader) which both enable this policy. In that case the writer considers
+/// a sample acknowledged by the reader once it has been kept for the configured `duration` after being sent, which reduces the
+/// acknowledgment traffic of reliable topics with a large number of readers. The `duration` is only used by the writer and it
+/// is not sent to the remote applications.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DisablePositiveAcksQosPolicy {
+    /// Indicates whether positive acknowledgments are disabled
+    pub enabled: bool,
+    /// Time during which the writer keeps the sent samples available for repair before considering them acknowledged
+    pub duration: DurationKind,
+}
+
+impl Default for DisablePositiveAcksQosPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            duration: DurationKind::Infinite,
+        }
+    }
+}
+
+impl XTypesSerialize for DisablePositiveAcksQosPolicy {
+    fn serialize(&self, serializer: impl XTypesSerializer) -> Result<(), XTypesError> {
+        let mut s = serializer.serialize_final_struct()?;
+        s.serialize_field(&self.enabled, "enabled")
+    }
+}
+impl<'de> XTypesDeserialize<'de> for DisablePositiveAcksQosPolicy {
+    fn deserialize(deserializer: impl XTypesDeserializer<'de>) -> Result<Self, XTypesError> {
+        let mut d = deserializer.deserialize_final_struct()?;
+        Ok(Self {
+            enabled: d.deserialize_field("enabled")?,
+            // The keep duration is local to the writer and never received
+            duration: DurationKind::Infinite,
+        })
+    }
+}
+
+impl QosPolicy for DisablePositiveAcksQosPolicy {
+    fn name(&self) -> &str {
+        DISABLEPOSITIVEACKS_QOS_POLICY_NAME
+    }
+}
+
+/// Enumeration representing the different types of [`HistoryOverflowQosPolicy`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HistoryOverflowQosPolicyKind {
+    /// Writing a sample above the resource limits fails with [`DdsError::OutOfResources`](crate::infrastructure::error::DdsError::OutOfResources).
+    Reject,
+    /// The oldest sample in the writer history is dropped to make room for the new sample.
+    DropOldest,
+}
+
+/// This policy is a vendor extension which controls how a best-effort [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// with a [`HistoryQosPolicyKind::KeepLast`] history handles a write that exceeds the `max_samples` of its [`ResourceLimitsQosPolicy`].
+///
+/// With [`HistoryOverflowQosPolicyKind::DropOldest`] the writer silently drops its oldest sample instead of failing, so that bursts
+/// of writes never stall the application. The number of dropped samples is available through
+/// [`DataWriter::get_dropped_sample_count`](crate::publication::data_writer::DataWriter::get_dropped_sample_count). The policy is
+/// local to the writer and it is not sent to the remote applications.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HistoryOverflowQosPolicy {
+    /// Behavior of the writer when its history is full
+    pub kind: HistoryOverflowQosPolicyKind,
+}
+
+impl Default for HistoryOverflowQosPolicy {
+    fn default() -> Self {
+        Self {
+            kind: HistoryOverflowQosPolicyKind::Reject,
+        }
+    }
+}
+
+impl QosPolicy for HistoryOverflowQosPolicy {
+    fn name(&self) -> &str {
+        HISTORYOVERFLOW_QOS_POLICY_NAME
+    }
+}
+
+/// Enumeration representing the different types of [`LocatorSelectionQosPolicy`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LocatorSelectionQosPolicyKind {
+    /// The samples are sent to the unicast locators of the matched readers.
+    Unicast,
+    /// The samples are sent to the multicast locators of the matched readers which advertise any, and to the unicast
+    /// locators of the other readers.
+    PreferMulticast,
+}
+
+/// This policy is a vendor extension which selects the locators used by a [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// to send its samples to a matched [`DataReader`](crate::subscription::data_reader::DataReader) advertising both unicast and
+/// multicast locators.
+///
+/// Sending to the multicast locators allows the network to replicate the data for topics with a large number of readers. The
+/// policy is local to the writer and it is not sent to the remote applications.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LocatorSelectionQosPolicy {
+    /// Kind of locators preferred by the writer
+    pub kind: LocatorSelectionQosPolicyKind,
+}
+
+impl Default for LocatorSelectionQosPolicy {
+    fn default() -> Self {
+        Self {
+            kind: LocatorSelectionQosPolicyKind::Unicast,
+        }
+    }
+}
+
+impl QosPolicy for LocatorSelectionQosPolicy {
+    fn name(&self) -> &str {
+        LOCATORSELECTION_QOS_POLICY_NAME
+    }
+}
+
+/// This policy is a vendor extension which limits the rate at which a [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// publishes its samples, protecting the network from a runaway producer.
+///
+/// The rate is measured over the last second of writes. A write which would exceed any of the limits blocks a reliable writer
+/// until the rate allows it, up to the `max_blocking_time` of its [`ReliabilityQosPolicy`], after which it fails with
+/// [`DdsError::Timeout`](crate::infrastructure::error::DdsError::Timeout). A best-effort writer drops the sample instead and
+/// counts it in [`DataWriter::get_dropped_sample_count`](crate::publication::data_writer::DataWriter::get_dropped_sample_count).
+/// The policy is local to the writer and it is not sent to the remote applications.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PublishRateQosPolicy {
+    /// Maximum number of samples written per second
+    pub max_samples_per_second: Length,
+    /// Maximum number of serialized bytes written per second
+    pub max_bytes_per_second: Length,
+}
+
+impl Default for PublishRateQosPolicy {
+    fn default() -> Self {
+        Self {
+            max_samples_per_second: Length::Unlimited,
+            max_bytes_per_second: Length::Unlimited,
+        }
+    }
+}
+
+impl QosPolicy for PublishRateQosPolicy {
+    fn name(&self) -> &str {
+        PUBLISHRATE_QOS_POLICY_NAME
+    }
+}
+
+/// Enumeration representing the different types of [`DeliveryModeQosPolicy`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DeliveryModeQosPolicyKind {
+    /// The samples are sent to the matched readers as soon as they are written.
+    Push,
+    /// The samples are only announced with heartbeats and sent when the matched readers request them.
+    Pull,
+}
+
+/// This policy is a vendor extension which selects whether a reliable [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// operates in the push or the pull mode of the RTPS protocol.
+///
+/// In pull mode the writer never sends its samples on its own initiative to the matched reliable readers. The heartbeats let the
+/// readers know which samples are available and each of them is only sent in response to an acknack requesting it, which suits
+/// very large histories served on demand. Best-effort readers can not request samples so they are always served in push mode.
+/// The policy is local to the writer and it is not sent to the remote applications.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DeliveryModeQosPolicy {
+    /// Mode used by the writer to deliver its samples
+    pub kind: DeliveryModeQosPolicyKind,
+}
+
+impl Default for DeliveryModeQosPolicy {
+    fn default() -> Self {
+        Self {
+            kind: DeliveryModeQosPolicyKind::Push,
+        }
+    }
+}
+
+impl QosPolicy for DeliveryModeQosPolicy {
+    fn name(&self) -> &str {
+        DELIVERYMODE_QOS_POLICY_NAME
+    }
+}
+
+/// This policy is a vendor extension which limits the historical samples that a [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// delivers to each late-joining reader whose [`DurabilityQosPolicy`] is not [`DurabilityQosPolicyKind::Volatile`].
+///
+/// When a reader is matched only the most recent samples of the writer history that satisfy all the limits are delivered to it,
+/// the older ones are announced as no longer relevant. The limits are independent of the [`HistoryQosPolicy`] of the writer which
+/// keeps storing its samples as usual, so that for example a writer keeping a long history for its current readers can hand only
+/// the last sample of each instance to a new reader. The samples written after the reader is matched are never limited.
+/// The policy is local to the writer and it is not sent to the remote applications.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HistoricalDataQosPolicy {
+    /// Maximum number of historical samples of each instance delivered to a late-joining reader
+    pub depth: Length,
+    /// Maximum number of historical samples delivered to a late-joining reader over all the instances
+    pub max_samples: Length,
+    /// Maximum age, with respect to its source timestamp, of a historical sample delivered to a late-joining reader
+    pub max_age: DurationKind,
+}
+
+impl Default for HistoricalDataQosPolicy {
+    fn default() -> Self {
+        Self {
+            depth: Length::Unlimited,
+            max_samples: Length::Unlimited,
+            max_age: DurationKind::Infinite,
+        }
+    }
+}
+
+impl QosPolicy for HistoricalDataQosPolicy {
+    fn name(&self) -> &str {
+        HISTORICALDATA_QOS_POLICY_NAME
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::xtypes::xcdr_deserializer::Xcdr1LeDeserializer;
 
     #[test]
     fn durability_qos_policy_kind_ordering() {
@@ -1329,4 +1758,27 @@ mod tests {
         assert!(Length::Limited(10) == 10usize);
         assert!(10usize == Length::Limited(10));
     }
+
+    #[test]
+    fn property_qos_policy_without_binary_properties() {
+        let data = [
+            0, 0, 0, 0, // value length
+        ];
+        assert_eq!(
+            PropertyQosPolicy::deserialize(&mut Xcdr1LeDeserializer::new(&data)),
+            Ok(PropertyQosPolicy::default())
+        );
+    }
+
+    #[test]
+    fn property_qos_policy_with_malformed_binary_properties() {
+        let data = [
+            0, 0, 0, 0, // value length
+            1, 0, 0, 0, // binary_value length without any element
+        ];
+        assert_eq!(
+            PropertyQosPolicy::deserialize(&mut Xcdr1LeDeserializer::new(&data)),
+            Err(XTypesError::InvalidData)
+        );
+    }
 }
@@ -0,0 +1,739 @@
+use super::{
+    error::{DdsError, DdsResult},
+    qos::{
+        DataReaderQos, DataWriterQos, DomainParticipantQos, PublisherQos, SubscriberQos, TopicQos,
+    },
+    qos_policy::{
+        DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
+        DestinationOrderQosPolicyKind, DurabilityQosPolicy, DurabilityQosPolicyKind,
+        DurabilityServiceQosPolicy, EntityFactoryQosPolicy, HistoryQosPolicy, HistoryQosPolicyKind,
+        LatencyBudgetQosPolicy, Length, LifespanQosPolicy, LivelinessQosPolicy,
+        LivelinessQosPolicyKind, OwnershipQosPolicy, OwnershipQosPolicyKind,
+        OwnershipStrengthQosPolicy, PartitionQosPolicy, PresentationQosPolicy,
+        PresentationQosPolicyAccessScopeKind, ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy,
+        ReliabilityQosPolicyKind, ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy,
+        TransportPriorityQosPolicy, WriterDataLifecycleQosPolicy, XCDR2_DATA_REPRESENTATION,
+        XCDR_DATA_REPRESENTATION, XML_DATA_REPRESENTATION,
+    },
+    time::{Duration, DurationKind},
+};
+use roxmltree::{Document, Node};
+use std::{collections::HashMap, str::FromStr};
+
+/// QoS policies of the entities given by a QoS profile. The policies which are not set by the profile, or by the profiles it
+/// derives from, have their default value.
+/// The QoS profiles are loaded by means of
+/// [`DomainParticipantFactory::load_qos_profiles`](crate::domain::domain_participant_factory::DomainParticipantFactory::load_qos_profiles)
+/// and retrieved by means of
+/// [`DomainParticipantFactory::get_qos_from_profile`](crate::domain::domain_participant_factory::DomainParticipantFactory::get_qos_from_profile).
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct QosProfile {
+    /// QoS policies of the [`DomainParticipant`](crate::domain::domain_participant::DomainParticipant).
+    pub domain_participant_qos: DomainParticipantQos,
+    /// QoS policies of the [`Topic`](crate::topic_definition::topic::Topic).
+    pub topic_qos: TopicQos,
+    /// QoS policies of the [`Publisher`](crate::publication::publisher::Publisher).
+    pub publisher_qos: PublisherQos,
+    /// QoS policies of the [`Subscriber`](crate::subscription::subscriber::Subscriber).
+    pub subscriber_qos: SubscriberQos,
+    /// QoS policies of the [`DataWriter`](crate::publication::data_writer::DataWriter).
+    pub data_writer_qos: DataWriterQos,
+    /// QoS policies of the [`DataReader`](crate::subscription::data_reader::DataReader).
+    pub data_reader_qos: DataReaderQos,
+}
+
+fn qos_profile_error(reason: impl core::fmt::Display) -> DdsError {
+    DdsError::Error(format!("QoS profile: {}", reason))
+}
+
+fn unsupported_element(node: Node) -> DdsError {
+    qos_profile_error(format!(
+        "unsupported element <{}> in <{}>",
+        node.tag_name().name(),
+        node.parent_element()
+            .map(|p| p.tag_name().name())
+            .unwrap_or_default()
+    ))
+}
+
+fn invalid_value(node: Node) -> DdsError {
+    qos_profile_error(format!(
+        "invalid value {} of <{}>",
+        text(node),
+        node.tag_name().name()
+    ))
+}
+
+fn elements<'a, 'input: 'a>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(|n| n.is_element())
+}
+
+fn text<'a>(node: Node<'a, '_>) -> &'a str {
+    node.text().unwrap_or_default().trim()
+}
+
+fn parse_number<T: FromStr>(node: Node) -> DdsResult<T> {
+    text(node).parse().map_err(|_| invalid_value(node))
+}
+
+fn parse_bool(node: Node) -> DdsResult<bool> {
+    match text(node) {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(invalid_value(node)),
+    }
+}
+
+fn parse_length(node: Node) -> DdsResult<Length> {
+    match text(node) {
+        "LENGTH_UNLIMITED" | "-1" => Ok(Length::Unlimited),
+        _ => parse_number(node).map(Length::Limited),
+    }
+}
+
+// The durations are given by their sec and nanosec elements, any of which can take the special
+// values of the infinite duration
+fn parse_duration(node: Node) -> DdsResult<DurationKind> {
+    let mut sec = 0;
+    let mut nanosec = 0;
+    for field in elements(node) {
+        match (field.tag_name().name(), text(field)) {
+            ("sec", "DURATION_INFINITY" | "DURATION_INFINITE_SEC")
+            | ("nanosec", "DURATION_INFINITY" | "DURATION_INFINITE_NSEC") => {
+                return Ok(DurationKind::Infinite)
+            }
+            ("sec", _) => sec = parse_number(field)?,
+            ("nanosec", _) => nanosec = parse_number(field)?,
+            _ => return Err(unsupported_element(field)),
+        }
+    }
+    Ok(DurationKind::Finite(Duration::new(sec, nanosec)))
+}
+
+// Policies given by a single field
+fn parse_field<T>(
+    node: Node,
+    name: &str,
+    value: &mut T,
+    parse: impl Fn(Node) -> DdsResult<T>,
+) -> DdsResult<()> {
+    for field in elements(node) {
+        if field.has_tag_name(name) {
+            *value = parse(field)?;
+        } else {
+            return Err(unsupported_element(field));
+        }
+    }
+    Ok(())
+}
+
+fn parse_durability(node: Node, policy: &mut DurabilityQosPolicy) -> DdsResult<()> {
+    parse_field(node, "kind", &mut policy.kind, |n| match text(n) {
+        "VOLATILE_DURABILITY_QOS" => Ok(DurabilityQosPolicyKind::Volatile),
+        "TRANSIENT_LOCAL_DURABILITY_QOS" => Ok(DurabilityQosPolicyKind::TransientLocal),
+        "TRANSIENT_DURABILITY_QOS" => Ok(DurabilityQosPolicyKind::Transient),
+        "PERSISTENT_DURABILITY_QOS" => Ok(DurabilityQosPolicyKind::Persistent),
+        _ => Err(invalid_value(n)),
+    })
+}
+
+fn parse_history_kind(node: Node, depth: u32) -> DdsResult<HistoryQosPolicyKind> {
+    match text(node) {
+        "KEEP_LAST_HISTORY_QOS" => Ok(HistoryQosPolicyKind::KeepLast(depth)),
+        "KEEP_ALL_HISTORY_QOS" => Ok(HistoryQosPolicyKind::KeepAll),
+        _ => Err(invalid_value(node)),
+    }
+}
+
+// The depth is only meaningful for the keep last history and it is kept separately so that it
+// can be given in any order with respect to the kind
+fn parse_history(
+    node: Node,
+    kind: &mut HistoryQosPolicyKind,
+    kind_name: &str,
+    depth_name: &str,
+) -> DdsResult<()> {
+    let mut depth = match *kind {
+        HistoryQosPolicyKind::KeepLast(depth) => depth,
+        HistoryQosPolicyKind::KeepAll => 1,
+    };
+    let mut kind_node = None;
+    for field in elements(node) {
+        if field.has_tag_name(kind_name) {
+            kind_node = Some(field);
+        } else if field.has_tag_name(depth_name) {
+            depth = parse_number(field)?;
+        }
+    }
+    *kind = match kind_node {
+        Some(n) => parse_history_kind(n, depth)?,
+        None => match *kind {
+            HistoryQosPolicyKind::KeepLast(_) => HistoryQosPolicyKind::KeepLast(depth),
+            HistoryQosPolicyKind::KeepAll => HistoryQosPolicyKind::KeepAll,
+        },
+    };
+    Ok(())
+}
+
+fn parse_durability_service(node: Node, policy: &mut DurabilityServiceQosPolicy) -> DdsResult<()> {
+    parse_history(
+        node,
+        &mut policy.history_kind,
+        "history_kind",
+        "history_depth",
+    )?;
+    for field in elements(node) {
+        match field.tag_name().name() {
+            "service_cleanup_delay" => policy.service_cleanup_delay = parse_duration(field)?,
+            "history_kind" | "history_depth" => (),
+            "max_samples" => policy.max_samples = parse_length(field)?,
+            "max_instances" => policy.max_instances = parse_length(field)?,
+            "max_samples_per_instance" => policy.max_samples_per_instance = parse_length(field)?,
+            _ => return Err(unsupported_element(field)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_presentation(node: Node, policy: &mut PresentationQosPolicy) -> DdsResult<()> {
+    for field in elements(node) {
+        match field.tag_name().name() {
+            "access_scope" => {
+                policy.access_scope = match text(field) {
+                    "INSTANCE_PRESENTATION_QOS" => PresentationQosPolicyAccessScopeKind::Instance,
+                    "TOPIC_PRESENTATION_QOS" => PresentationQosPolicyAccessScopeKind::Topic,
+                    "GROUP_PRESENTATION_QOS" => PresentationQosPolicyAccessScopeKind::Group,
+                    _ => return Err(invalid_value(field)),
+                }
+            }
+            "coherent_access" => policy.coherent_access = parse_bool(field)?,
+            "ordered_access" => policy.ordered_access = parse_bool(field)?,
+            _ => return Err(unsupported_element(field)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_deadline(node: Node, policy: &mut DeadlineQosPolicy) -> DdsResult<()> {
+    parse_field(node, "period", &mut policy.period, parse_duration)
+}
+
+fn parse_latency_budget(node: Node, policy: &mut LatencyBudgetQosPolicy) -> DdsResult<()> {
+    parse_field(node, "duration", &mut policy.duration, parse_duration)
+}
+
+fn parse_ownership(node: Node, policy: &mut OwnershipQosPolicy) -> DdsResult<()> {
+    parse_field(node, "kind", &mut policy.kind, |n| match text(n) {
+        "SHARED_OWNERSHIP_QOS" => Ok(OwnershipQosPolicyKind::Shared),
+        "EXCLUSIVE_OWNERSHIP_QOS" => Ok(OwnershipQosPolicyKind::Exclusive),
+        _ => Err(invalid_value(n)),
+    })
+}
+
+fn parse_ownership_strength(node: Node, policy: &mut OwnershipStrengthQosPolicy) -> DdsResult<()> {
+    parse_field(node, "value", &mut policy.value, parse_number)
+}
+
+fn parse_liveliness(node: Node, policy: &mut LivelinessQosPolicy) -> DdsResult<()> {
+    for field in elements(node) {
+        match field.tag_name().name() {
+            "kind" => {
+                policy.kind = match text(field) {
+                    "AUTOMATIC_LIVELINESS_QOS" => LivelinessQosPolicyKind::Automatic,
+                    "MANUAL_BY_PARTICIPANT_LIVELINESS_QOS" => {
+                        LivelinessQosPolicyKind::ManualByParticipant
+                    }
+                    "MANUAL_BY_TOPIC_LIVELINESS_QOS" => LivelinessQosPolicyKind::ManualByTopic,
+                    _ => return Err(invalid_value(field)),
+                }
+            }
+            "lease_duration" => policy.lease_duration = parse_duration(field)?,
+            _ => return Err(unsupported_element(field)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_time_based_filter(node: Node, policy: &mut TimeBasedFilterQosPolicy) -> DdsResult<()> {
+    parse_field(
+        node,
+        "minimum_separation",
+        &mut policy.minimum_separation,
+        parse_duration,
+    )
+}
+
+fn parse_partition(node: Node, policy: &mut PartitionQosPolicy) -> DdsResult<()> {
+    parse_field(node, "name", &mut policy.name, |n| {
+        elements(n)
+            .map(|element| {
+                if element.has_tag_name("element") {
+                    Ok(text(element).to_string())
+                } else {
+                    Err(unsupported_element(element))
+                }
+            })
+            .collect()
+    })
+}
+
+fn parse_reliability(node: Node, policy: &mut ReliabilityQosPolicy) -> DdsResult<()> {
+    for field in elements(node) {
+        match field.tag_name().name() {
+            "kind" => {
+                policy.kind = match text(field) {
+                    "BEST_EFFORT_RELIABILITY_QOS" => ReliabilityQosPolicyKind::BestEffort,
+                    "RELIABLE_RELIABILITY_QOS" => ReliabilityQosPolicyKind::Reliable,
+                    _ => return Err(invalid_value(field)),
+                }
+            }
+            "max_blocking_time" => policy.max_blocking_time = parse_duration(field)?,
+            _ => return Err(unsupported_element(field)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_transport_priority(node: Node, policy: &mut TransportPriorityQosPolicy) -> DdsResult<()> {
+    parse_field(node, "value", &mut policy.value, parse_number)
+}
+
+fn parse_lifespan(node: Node, policy: &mut LifespanQosPolicy) -> DdsResult<()> {
+    parse_field(node, "duration", &mut policy.duration, parse_duration)
+}
+
+fn parse_destination_order(node: Node, policy: &mut DestinationOrderQosPolicy) -> DdsResult<()> {
+    parse_field(node, "kind", &mut policy.kind, |n| match text(n) {
+        "BY_RECEPTION_TIMESTAMP_DESTINATIONORDER_QOS" => {
+            Ok(DestinationOrderQosPolicyKind::ByReceptionTimestamp)
+        }
+        "BY_SOURCE_TIMESTAMP_DESTINATIONORDER_QOS" => {
+            Ok(DestinationOrderQosPolicyKind::BySourceTimestamp)
+        }
+        _ => Err(invalid_value(n)),
+    })
+}
+
+fn parse_history_policy(node: Node, policy: &mut HistoryQosPolicy) -> DdsResult<()> {
+    if let Some(field) =
+        elements(node).find(|n| !n.has_tag_name("kind") && !n.has_tag_name("depth"))
+    {
+        return Err(unsupported_element(field));
+    }
+    parse_history(node, &mut policy.kind, "kind", "depth")
+}
+
+fn parse_resource_limits(node: Node, policy: &mut ResourceLimitsQosPolicy) -> DdsResult<()> {
+    for field in elements(node) {
+        match field.tag_name().name() {
+            "max_samples" => policy.max_samples = parse_length(field)?,
+            "max_instances" => policy.max_instances = parse_length(field)?,
+            "max_samples_per_instance" => policy.max_samples_per_instance = parse_length(field)?,
+            _ => return Err(unsupported_element(field)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_entity_factory(node: Node, policy: &mut EntityFactoryQosPolicy) -> DdsResult<()> {
+    parse_field(
+        node,
+        "autoenable_created_entities",
+        &mut policy.autoenable_created_entities,
+        parse_bool,
+    )
+}
+
+fn parse_writer_data_lifecycle(
+    node: Node,
+    policy: &mut WriterDataLifecycleQosPolicy,
+) -> DdsResult<()> {
+    parse_field(
+        node,
+        "autodispose_unregistered_instances",
+        &mut policy.autodispose_unregistered_instances,
+        parse_bool,
+    )
+}
+
+fn parse_reader_data_lifecycle(
+    node: Node,
+    policy: &mut ReaderDataLifecycleQosPolicy,
+) -> DdsResult<()> {
+    for field in elements(node) {
+        match field.tag_name().name() {
+            "autopurge_nowriter_samples_delay" => {
+                policy.autopurge_nowriter_samples_delay = parse_duration(field)?
+            }
+            "autopurge_disposed_samples_delay" => {
+                policy.autopurge_disposed_samples_delay = parse_duration(field)?
+            }
+            _ => return Err(unsupported_element(field)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_representation(node: Node, policy: &mut DataRepresentationQosPolicy) -> DdsResult<()> {
+    parse_field(node, "value", &mut policy.value, |n| {
+        elements(n)
+            .map(|element| match (element.tag_name().name(), text(element)) {
+                ("element", "XCDR_DATA_REPRESENTATION") => Ok(XCDR_DATA_REPRESENTATION),
+                ("element", "XML_DATA_REPRESENTATION") => Ok(XML_DATA_REPRESENTATION),
+                ("element", "XCDR2_DATA_REPRESENTATION") => Ok(XCDR2_DATA_REPRESENTATION),
+                ("element", _) => Err(invalid_value(element)),
+                _ => Err(unsupported_element(element)),
+            })
+            .collect()
+    })
+}
+
+fn parse_domain_participant_qos(node: Node, qos: &mut DomainParticipantQos) -> DdsResult<()> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "entity_factory" => parse_entity_factory(policy, &mut qos.entity_factory)?,
+            _ => return Err(unsupported_element(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_topic_qos(node: Node, qos: &mut TopicQos) -> DdsResult<()> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "durability" => parse_durability(policy, &mut qos.durability)?,
+            "durability_service" => parse_durability_service(policy, &mut qos.durability_service)?,
+            "deadline" => parse_deadline(policy, &mut qos.deadline)?,
+            "latency_budget" => parse_latency_budget(policy, &mut qos.latency_budget)?,
+            "liveliness" => parse_liveliness(policy, &mut qos.liveliness)?,
+            "reliability" => parse_reliability(policy, &mut qos.reliability)?,
+            "destination_order" => parse_destination_order(policy, &mut qos.destination_order)?,
+            "history" => parse_history_policy(policy, &mut qos.history)?,
+            "resource_limits" => parse_resource_limits(policy, &mut qos.resource_limits)?,
+            "transport_priority" => parse_transport_priority(policy, &mut qos.transport_priority)?,
+            "lifespan" => parse_lifespan(policy, &mut qos.lifespan)?,
+            "ownership" => parse_ownership(policy, &mut qos.ownership)?,
+            "representation" => parse_representation(policy, &mut qos.representation)?,
+            _ => return Err(unsupported_element(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_publisher_qos(node: Node, qos: &mut PublisherQos) -> DdsResult<()> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "presentation" => parse_presentation(policy, &mut qos.presentation)?,
+            "partition" => parse_partition(policy, &mut qos.partition)?,
+            "entity_factory" => parse_entity_factory(policy, &mut qos.entity_factory)?,
+            _ => return Err(unsupported_element(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_subscriber_qos(node: Node, qos: &mut SubscriberQos) -> DdsResult<()> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "presentation" => parse_presentation(policy, &mut qos.presentation)?,
+            "partition" => parse_partition(policy, &mut qos.partition)?,
+            "entity_factory" => parse_entity_factory(policy, &mut qos.entity_factory)?,
+            _ => return Err(unsupported_element(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_data_writer_qos(node: Node, qos: &mut DataWriterQos) -> DdsResult<()> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "durability" => parse_durability(policy, &mut qos.durability)?,
+            "durability_service" => parse_durability_service(policy, &mut qos.durability_service)?,
+            "deadline" => parse_deadline(policy, &mut qos.deadline)?,
+            "latency_budget" => parse_latency_budget(policy, &mut qos.latency_budget)?,
+            "liveliness" => parse_liveliness(policy, &mut qos.liveliness)?,
+            "reliability" => parse_reliability(policy, &mut qos.reliability)?,
+            "destination_order" => parse_destination_order(policy, &mut qos.destination_order)?,
+            "history" => parse_history_policy(policy, &mut qos.history)?,
+            "resource_limits" => parse_resource_limits(policy, &mut qos.resource_limits)?,
+            "transport_priority" => parse_transport_priority(policy, &mut qos.transport_priority)?,
+            "lifespan" => parse_lifespan(policy, &mut qos.lifespan)?,
+            "ownership" => parse_ownership(policy, &mut qos.ownership)?,
+            "ownership_strength" => parse_ownership_strength(policy, &mut qos.ownership_strength)?,
+            "writer_data_lifecycle" => {
+                parse_writer_data_lifecycle(policy, &mut qos.writer_data_lifecycle)?
+            }
+            "representation" => parse_representation(policy, &mut qos.representation)?,
+            _ => return Err(unsupported_element(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_data_reader_qos(node: Node, qos: &mut DataReaderQos) -> DdsResult<()> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "durability" => parse_durability(policy, &mut qos.durability)?,
+            "deadline" => parse_deadline(policy, &mut qos.deadline)?,
+            "latency_budget" => parse_latency_budget(policy, &mut qos.latency_budget)?,
+            "liveliness" => parse_liveliness(policy, &mut qos.liveliness)?,
+            "reliability" => parse_reliability(policy, &mut qos.reliability)?,
+            "destination_order" => parse_destination_order(policy, &mut qos.destination_order)?,
+            "history" => parse_history_policy(policy, &mut qos.history)?,
+            "resource_limits" => parse_resource_limits(policy, &mut qos.resource_limits)?,
+            "ownership" => parse_ownership(policy, &mut qos.ownership)?,
+            "time_based_filter" => parse_time_based_filter(policy, &mut qos.time_based_filter)?,
+            "reader_data_lifecycle" => {
+                parse_reader_data_lifecycle(policy, &mut qos.reader_data_lifecycle)?
+            }
+            "representation" => parse_representation(policy, &mut qos.representation)?,
+            _ => return Err(unsupported_element(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_qos_profile(node: Node, qos_profile: &mut QosProfile) -> DdsResult<()> {
+    for qos in elements(node) {
+        match qos.tag_name().name() {
+            "domainparticipant_qos" => {
+                parse_domain_participant_qos(qos, &mut qos_profile.domain_participant_qos)?
+            }
+            "topic_qos" => parse_topic_qos(qos, &mut qos_profile.topic_qos)?,
+            "publisher_qos" => parse_publisher_qos(qos, &mut qos_profile.publisher_qos)?,
+            "subscriber_qos" => parse_subscriber_qos(qos, &mut qos_profile.subscriber_qos)?,
+            "datawriter_qos" => parse_data_writer_qos(qos, &mut qos_profile.data_writer_qos)?,
+            "datareader_qos" => parse_data_reader_qos(qos, &mut qos_profile.data_reader_qos)?,
+            _ => return Err(unsupported_element(qos)),
+        }
+    }
+    Ok(())
+}
+
+// Parses the QoS profiles of a document in the format of the OMG DDS-XML specification. The
+// profiles are named "<library name>::<profile name>" and they can derive from a profile of the
+// same document or from one of the already loaded profiles given as argument.
+pub(crate) fn parse_qos_profiles(
+    document: &str,
+    loaded_qos_profile_list: &HashMap<String, QosProfile>,
+) -> DdsResult<Vec<(String, QosProfile)>> {
+    let document = Document::parse(document).map_err(qos_profile_error)?;
+    let root = document.root_element();
+    if !root.has_tag_name("dds") {
+        return Err(unsupported_element(root));
+    }
+
+    let mut qos_profile_list: Vec<(String, QosProfile)> = Vec::new();
+    for library in elements(root) {
+        if !library.has_tag_name("qos_library") {
+            return Err(unsupported_element(library));
+        }
+        let library_name = library
+            .attribute("name")
+            .ok_or_else(|| qos_profile_error("QoS library without name"))?;
+        for profile in elements(library) {
+            if !profile.has_tag_name("qos_profile") {
+                return Err(unsupported_element(profile));
+            }
+            let profile_name = profile
+                .attribute("name")
+                .ok_or_else(|| qos_profile_error("QoS profile without name"))?;
+            let mut qos_profile = match profile.attribute("base_name") {
+                Some(base_name) => {
+                    // A base name without library refers to a profile of the same library
+                    let base_name = if base_name.contains("::") {
+                        base_name.to_string()
+                    } else {
+                        format!("{}::{}", library_name, base_name)
+                    };
+                    qos_profile_list
+                        .iter()
+                        .rev()
+                        .find(|(name, _)| name == &base_name)
+                        .map(|(_, p)| p)
+                        .or_else(|| loaded_qos_profile_list.get(&base_name))
+                        .cloned()
+                        .ok_or_else(|| {
+                            qos_profile_error(format!("unknown base profile {}", base_name))
+                        })?
+                }
+                None => QosProfile::default(),
+            };
+            parse_qos_profile(profile, &mut qos_profile)?;
+            qos_profile_list.push((format!("{}::{}", library_name, profile_name), qos_profile));
+        }
+    }
+    Ok(qos_profile_list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_qos_profiles_with_base_profile() {
+        let document = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <dds xmlns="http://www.omg.org/dds/">
+                <qos_library name="Library">
+                    <qos_profile name="Base">
+                        <datawriter_qos>
+                            <reliability>
+                                <kind>BEST_EFFORT_RELIABILITY_QOS</kind>
+                            </reliability>
+                            <history>
+                                <kind>KEEP_LAST_HISTORY_QOS</kind>
+                                <depth>10</depth>
+                            </history>
+                        </datawriter_qos>
+                        <publisher_qos>
+                            <partition>
+                                <name>
+                                    <element>A</element>
+                                    <element>B</element>
+                                </name>
+                            </partition>
+                        </publisher_qos>
+                    </qos_profile>
+                    <qos_profile name="Derived" base_name="Base">
+                        <datawriter_qos>
+                            <deadline>
+                                <period>
+                                    <sec>1</sec>
+                                    <nanosec>500</nanosec>
+                                </period>
+                            </deadline>
+                            <resource_limits>
+                                <max_samples>LENGTH_UNLIMITED</max_samples>
+                                <max_instances>20</max_instances>
+                            </resource_limits>
+                        </datawriter_qos>
+                        <datareader_qos>
+                            <reliability>
+                                <kind>RELIABLE_RELIABILITY_QOS</kind>
+                                <max_blocking_time>
+                                    <sec>DURATION_INFINITY</sec>
+                                </max_blocking_time>
+                            </reliability>
+                            <representation>
+                                <value>
+                                    <element>XCDR2_DATA_REPRESENTATION</element>
+                                </value>
+                            </representation>
+                        </datareader_qos>
+                    </qos_profile>
+                </qos_library>
+            </dds>"#;
+
+        let qos_profile_list = parse_qos_profiles(document, &HashMap::new()).unwrap();
+
+        assert_eq!(qos_profile_list.len(), 2);
+        assert_eq!(qos_profile_list[0].0, "Library::Base");
+        let (name, derived) = &qos_profile_list[1];
+        assert_eq!(name, "Library::Derived");
+        assert_eq!(
+            derived.data_writer_qos.reliability.kind,
+            ReliabilityQosPolicyKind::BestEffort
+        );
+        assert_eq!(
+            derived.data_writer_qos.history.kind,
+            HistoryQosPolicyKind::KeepLast(10)
+        );
+        assert_eq!(
+            derived.data_writer_qos.deadline.period,
+            DurationKind::Finite(Duration::new(1, 500))
+        );
+        assert_eq!(
+            derived.data_writer_qos.resource_limits,
+            ResourceLimitsQosPolicy {
+                max_samples: Length::Unlimited,
+                max_instances: Length::Limited(20),
+                max_samples_per_instance: Length::Unlimited,
+            }
+        );
+        assert_eq!(
+            derived.publisher_qos.partition.name,
+            vec!["A".to_string(), "B".to_string()]
+        );
+        assert_eq!(
+            derived.data_reader_qos.reliability,
+            ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: DurationKind::Infinite,
+            }
+        );
+        assert_eq!(
+            derived.data_reader_qos.representation.value,
+            vec![XCDR2_DATA_REPRESENTATION]
+        );
+        assert_eq!(derived.topic_qos, TopicQos::default());
+    }
+
+    #[test]
+    fn base_profile_is_taken_from_loaded_profiles() {
+        let loaded_document = r#"<dds>
+                <qos_library name="Loaded">
+                    <qos_profile name="Base">
+                        <topic_qos>
+                            <durability>
+                                <kind>TRANSIENT_LOCAL_DURABILITY_QOS</kind>
+                            </durability>
+                        </topic_qos>
+                    </qos_profile>
+                </qos_library>
+            </dds>"#;
+        let loaded_qos_profile_list = parse_qos_profiles(loaded_document, &HashMap::new())
+            .unwrap()
+            .into_iter()
+            .collect();
+        let document = r#"<dds>
+                <qos_library name="Library">
+                    <qos_profile name="Derived" base_name="Loaded::Base"/>
+                </qos_library>
+            </dds>"#;
+
+        let qos_profile_list = parse_qos_profiles(document, &loaded_qos_profile_list).unwrap();
+
+        assert_eq!(
+            qos_profile_list[0].1.topic_qos.durability.kind,
+            DurabilityQosPolicyKind::TransientLocal
+        );
+    }
+
+    #[test]
+    fn invalid_qos_profiles_are_rejected() {
+        let unknown_base = r#"<dds>
+                <qos_library name="Library">
+                    <qos_profile name="Derived" base_name="Unknown"/>
+                </qos_library>
+            </dds>"#;
+        let unsupported_policy = r#"<dds>
+                <qos_library name="Library">
+                    <qos_profile name="Profile">
+                        <datareader_qos>
+                            <ownership_strength>
+                                <value>1</value>
+                            </ownership_strength>
+                        </datareader_qos>
+                    </qos_profile>
+                </qos_library>
+            </dds>"#;
+        let invalid_kind = r#"<dds>
+                <qos_library name="Library">
+                    <qos_profile name="Profile">
+                        <topic_qos>
+                            <reliability>
+                                <kind>RELIABLE</kind>
+                            </reliability>
+                        </topic_qos>
+                    </qos_profile>
+                </qos_library>
+            </dds>"#;
+
+        for document in [unknown_base, unsupported_policy, invalid_kind, "<dds>"] {
+            assert!(matches!(
+                parse_qos_profiles(document, &HashMap::new()),
+                Err(DdsError::Error(_))
+            ));
+        }
+    }
+}
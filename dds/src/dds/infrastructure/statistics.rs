@@ -0,0 +1,48 @@
+use super::{instance::InstanceHandle, qos_policy::Length};
+
+/// Structure holding the number of samples stored in the history of an entity for one instance.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InstanceSampleCount {
+    /// Handle of the instance
+    pub instance_handle: InstanceHandle,
+    /// Number of samples of the instance stored in the history
+    pub sample_count: usize,
+}
+
+/// Structure holding the diagnostic counters of a [`DataWriter`](crate::publication::data_writer::DataWriter).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DataWriterStatistics {
+    /// Number of instances known by the writer, that is registered at least once.
+    pub instance_count: usize,
+    /// Number of instances whose last operation was a write.
+    pub alive_instance_count: usize,
+    /// Number of instances whose last operation was a dispose.
+    pub disposed_instance_count: usize,
+    /// Number of instances whose last operation was an unregister.
+    pub unregistered_instance_count: usize,
+    /// Number of samples stored in the history for each instance with at least one sample.
+    pub samples_per_instance: Vec<InstanceSampleCount>,
+    /// Total number of samples stored in the history.
+    pub history_sample_count: usize,
+    /// Maximum number of samples that the history can store as given by the resource limits.
+    pub history_max_samples: Length,
+}
+
+/// Structure holding the diagnostic counters of a [`DataReader`](crate::subscription::data_reader::DataReader).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DataReaderStatistics {
+    /// Number of instances known by the reader.
+    pub instance_count: usize,
+    /// Number of instances in the [`InstanceStateKind::Alive`](crate::subscription::sample_info::InstanceStateKind::Alive) state.
+    pub alive_instance_count: usize,
+    /// Number of instances in the [`InstanceStateKind::NotAliveDisposed`](crate::subscription::sample_info::InstanceStateKind::NotAliveDisposed) state.
+    pub disposed_instance_count: usize,
+    /// Number of instances in the [`InstanceStateKind::NotAliveNoWriters`](crate::subscription::sample_info::InstanceStateKind::NotAliveNoWriters) state.
+    pub no_writers_instance_count: usize,
+    /// Number of samples stored in the history for each instance with at least one sample.
+    pub samples_per_instance: Vec<InstanceSampleCount>,
+    /// Total number of samples stored in the history.
+    pub history_sample_count: usize,
+    /// Maximum number of samples that the history can store as given by the resource limits.
+    pub history_max_samples: Length,
+}
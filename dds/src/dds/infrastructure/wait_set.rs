@@ -4,13 +4,17 @@ use crate::{
     runtime::executor::block_on,
 };
 
-use super::condition::StatusCondition;
+use super::condition::{GuardCondition, ReadCondition, StatusCondition};
 
 /// Enumeration of the different Condition objects that can be associated with a [`WaitSet`].
 #[derive(Clone)]
 pub enum Condition {
     /// Status condition variant
     StatusCondition(StatusCondition),
+    /// Read condition variant
+    ReadCondition(ReadCondition),
+    /// Guard condition variant
+    GuardCondition(GuardCondition),
 }
 impl Condition {
     #[tracing::instrument(skip(self))]
@@ -18,6 +22,8 @@ impl Condition {
     pub fn get_trigger_value(&self) -> DdsResult<bool> {
         match self {
             Condition::StatusCondition(c) => c.get_trigger_value(),
+            Condition::ReadCondition(c) => c.get_trigger_value(),
+            Condition::GuardCondition(c) => c.get_trigger_value(),
         }
     }
 }
@@ -54,6 +60,12 @@ impl WaitSet {
                 ConditionAsync::StatusCondition(sc) => {
                     Condition::StatusCondition(StatusCondition::new(sc))
                 }
+                ConditionAsync::ReadCondition(rc) => {
+                    Condition::ReadCondition(ReadCondition::new(rc))
+                }
+                ConditionAsync::GuardCondition(gc) => {
+                    Condition::GuardCondition(GuardCondition::from_async(gc))
+                }
             })
             .collect())
     }
@@ -68,6 +80,14 @@ impl WaitSet {
             Condition::StatusCondition(sc) => block_on(self.waitset_async.attach_condition(
                 ConditionAsync::StatusCondition(sc.condition_async().clone()),
             )),
+            Condition::ReadCondition(rc) => block_on(
+                self.waitset_async
+                    .attach_condition(ConditionAsync::ReadCondition(rc.condition_async().clone())),
+            ),
+            Condition::GuardCondition(gc) => block_on(
+                self.waitset_async
+                    .attach_condition(ConditionAsync::GuardCondition(gc.condition_async().clone())),
+            ),
         }
     }
 
@@ -87,6 +107,12 @@ impl WaitSet {
                 ConditionAsync::StatusCondition(sc) => {
                     Condition::StatusCondition(StatusCondition::new(sc))
                 }
+                ConditionAsync::ReadCondition(rc) => {
+                    Condition::ReadCondition(ReadCondition::new(rc))
+                }
+                ConditionAsync::GuardCondition(gc) => {
+                    Condition::GuardCondition(GuardCondition::from_async(gc))
+                }
             })
             .collect())
     }
@@ -6,6 +6,7 @@ use crate::{
         error::DdsResult,
         instance::InstanceHandle,
         qos::{DataWriterQos, QosKind},
+        statistics::DataWriterStatistics,
         status::{
             LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
             PublicationMatchedStatus, StatusKind,
@@ -14,7 +15,10 @@ use crate::{
     },
     publication::{data_writer_listener::DataWriterListener, publisher::Publisher},
     runtime::executor::block_on,
-    topic_definition::{topic::Topic, type_support::DdsSerialize},
+    topic_definition::{
+        topic::Topic,
+        type_support::{DdsDeserialize, DdsSerialize},
+    },
 };
 
 /// The [`DataWriter`] allows the application to set the value of the
@@ -140,7 +144,10 @@ where
     /// This operation returns [`DdsError::BadParameter`](crate::infrastructure::error::DdsError) if the `handle` does not
     /// correspond to an existing data object known to the [`DataWriter`].
     #[tracing::instrument(skip(self, key_holder))]
-    pub fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()> {
+    pub fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()>
+    where
+        Foo: for<'de> DdsDeserialize<'de>,
+    {
         block_on(self.writer_async.get_key_value(key_holder, handle))
     }
 
@@ -206,6 +213,29 @@ where
         block_on(self.writer_async.write_w_timestamp(data, handle, timestamp))
     }
 
+    /// This operation modifies the value of several data instances at once. It performs the same function as calling
+    /// [`DataWriter::write`] for each element of `data_list`, in order, but the samples are stored in the history with a
+    /// single request to the middleware and sent out together, which increases the throughput of applications
+    /// producing samples in bursts. All the samples of the batch share the same source timestamp.
+    /// The identity of the instance of each sample is automatically deduced from the data (by means of the key).
+    /// If writing one of the samples fails, the samples preceding it in `data_list` remain written, the remaining
+    /// ones are discarded and the error is returned.
+    #[tracing::instrument(skip(self, data_list))]
+    pub fn write_batch(&self, data_list: &[Foo]) -> DdsResult<()> {
+        block_on(self.writer_async.write_batch(data_list))
+    }
+
+    /// This operation performs the same function and returns the same values as [`DataWriter::write_batch`] and can
+    /// be used instead of [`DataWriter::write_batch`] in the cases where the application desires to specify the
+    /// value for the `source_timestamp` shared by all the samples of the batch.
+    #[tracing::instrument(skip(self, data_list))]
+    pub fn write_batch_w_timestamp(&self, data_list: &[Foo], timestamp: Time) -> DdsResult<()> {
+        block_on(
+            self.writer_async
+                .write_batch_w_timestamp(data_list, timestamp),
+        )
+    }
+
     /// This operation requests the middleware to delete the data (the actual deletion is postponed until there is no more use for that
     /// data in the whole system). In general, applications are made aware of the deletion by means of operations on the
     /// [`DataReader`](crate::subscription::data_reader::DataReader) objects that already knew the instance.
@@ -243,6 +273,54 @@ where
 }
 
 impl<Foo> DataWriter<Foo> {
+    /// This operation performs the same function as [`DataWriter::unregister_instance`] but identifies the instance
+    /// only by its `handle`, so the application does not need to provide a `Foo` value to hold the key.
+    /// The key sent to the [`DataReader`](crate::subscription::data_reader::DataReader) entities is the one stored when the
+    /// instance was registered. If the `handle` does not correspond to an instance registered with this [`DataWriter`] the
+    /// operation fails and returns [`DdsError::BadParameter`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self))]
+    pub fn unregister_instance_w_handle(&self, handle: InstanceHandle) -> DdsResult<()> {
+        block_on(self.writer_async.unregister_instance_w_handle(handle))
+    }
+
+    /// This operation performs the same function and returns the same values as [`DataWriter::unregister_instance_w_handle`]
+    /// and can be used instead in the cases where the application desires to specify the value for the `source_timestamp`.
+    #[tracing::instrument(skip(self))]
+    pub fn unregister_instance_w_handle_w_timestamp(
+        &self,
+        handle: InstanceHandle,
+        timestamp: Time,
+    ) -> DdsResult<()> {
+        block_on(
+            self.writer_async
+                .unregister_instance_w_handle_w_timestamp(handle, timestamp),
+        )
+    }
+
+    /// This operation performs the same function as [`DataWriter::dispose`] but identifies the instance
+    /// only by its `handle`, so the application does not need to provide a `Foo` value to hold the key.
+    /// The key sent to the [`DataReader`](crate::subscription::data_reader::DataReader) entities is the one stored when the
+    /// instance was registered. If the `handle` does not correspond to an instance registered with this [`DataWriter`] the
+    /// operation fails and returns [`DdsError::BadParameter`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self))]
+    pub fn dispose_w_handle(&self, handle: InstanceHandle) -> DdsResult<()> {
+        block_on(self.writer_async.dispose_w_handle(handle))
+    }
+
+    /// This operation performs the same function and returns the same values as [`DataWriter::dispose_w_handle`]
+    /// and can be used instead in the cases where the application desires to specify the value for the `source_timestamp`.
+    #[tracing::instrument(skip(self))]
+    pub fn dispose_w_handle_w_timestamp(
+        &self,
+        handle: InstanceHandle,
+        timestamp: Time,
+    ) -> DdsResult<()> {
+        block_on(
+            self.writer_async
+                .dispose_w_handle_w_timestamp(handle, timestamp),
+        )
+    }
+
     /// This operation blocks the calling thread until either all data written by the [`DataWriter`] is acknowledged by all
     /// matched [`DataReader`](crate::subscription::data_reader::DataReader) entities that have
     /// [`ReliabilityQosPolicyKind::Reliable`](crate::infrastructure::qos_policy::ReliabilityQosPolicyKind), or else the duration
@@ -280,6 +358,23 @@ impl<Foo> DataWriter<Foo> {
         block_on(self.writer_async.get_publication_matched_status())
     }
 
+    /// This operation returns the total number of samples dropped by the [`DataWriter`] to make room for new samples
+    /// when its history is full and it uses [`HistoryOverflowQosPolicyKind::DropOldest`](crate::infrastructure::qos_policy::HistoryOverflowQosPolicyKind::DropOldest),
+    /// together with the samples of a best-effort [`DataWriter`] dropped for exceeding its [`PublishRateQosPolicy`](crate::infrastructure::qos_policy::PublishRateQosPolicy).
+    /// This operation is a vendor extension.
+    #[tracing::instrument(skip(self))]
+    pub fn get_dropped_sample_count(&self) -> DdsResult<u64> {
+        block_on(self.writer_async.get_dropped_sample_count())
+    }
+
+    /// This operation returns the [`DataWriterStatistics`] with the number of instances known by the [`DataWriter`]
+    /// in each state and the occupancy of its history. It is meant for the capacity monitoring of keyed topics.
+    /// This operation is a vendor extension.
+    #[tracing::instrument(skip(self))]
+    pub fn get_statistics(&self) -> DdsResult<DataWriterStatistics> {
+        block_on(self.writer_async.get_statistics())
+    }
+
     /// This operation returns the [`Topic`] associated with the [`DataWriter`]. This is the same [`Topic`] that was used to create the [`DataWriter`].
     #[tracing::instrument(skip(self))]
     pub fn get_topic(&self) -> Topic {
@@ -305,6 +400,15 @@ impl<Foo> DataWriter<Foo> {
         block_on(self.writer_async.assert_liveliness())
     }
 
+    /// This operation sends right away the samples held back by the writer batching configured with
+    /// [`DustDdsConfigurationBuilder::writer_batching`](crate::configuration::DustDdsConfigurationBuilder::writer_batching)
+    /// instead of waiting for the batch to fill up or for the flush period to elapse. Without batching the samples are
+    /// always sent as soon as they are written and this operation has no effect.
+    #[tracing::instrument(skip(self))]
+    pub fn flush(&self) -> DdsResult<()> {
+        block_on(self.writer_async.flush())
+    }
+
     /// This operation retrieves information on a subscription that is currently *associated* with the [`DataWriter`]; that is, a subscription
     /// with a matching [`Topic`] and compatible QoS that the application has not indicated should be ignored by means of the
     /// [`DomainParticipant::ignore_subscription`](crate::domain::domain_participant::DomainParticipant) operation.
@@ -82,7 +82,7 @@ impl Publisher {
     /// different [`Publisher`], the operation will have no effect and it will return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
     /// The deletion of the [`DataWriter`] will automatically unregister all instances. Depending on the settings of the
     /// [`WriterDataLifecycleQosPolicy`](crate::infrastructure::qos_policy::WriterDataLifecycleQosPolicy), the deletion of the
-    /// [`DataWriter`].
+    /// [`DataWriter`] may also dispose all instances.
     #[tracing::instrument(skip(self, a_datawriter))]
     pub fn delete_datawriter<Foo>(&self, a_datawriter: &DataWriter<Foo>) -> DdsResult<()> {
         block_on(
@@ -208,10 +208,13 @@ impl Publisher {
     #[tracing::instrument(skip(self))]
     pub fn copy_from_topic_qos(
         &self,
-        _a_datawriter_qos: &mut DataWriterQos,
-        _a_topic_qos: &TopicQos,
+        a_datawriter_qos: &mut DataWriterQos,
+        a_topic_qos: &TopicQos,
     ) -> DdsResult<()> {
-        todo!()
+        block_on(
+            self.publisher_async
+                .copy_from_topic_qos(a_datawriter_qos, a_topic_qos),
+        )
     }
 }
 
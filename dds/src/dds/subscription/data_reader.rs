@@ -1,11 +1,15 @@
 use crate::{
     builtin_topics::PublicationBuiltinTopicData,
-    dds_async::{data_reader::DataReaderAsync, data_reader_listener::DataReaderListenerAsync},
+    dds_async::{
+        data_reader::{DataReaderAsync, OrderedInstanceIterAsync},
+        data_reader_listener::DataReaderListenerAsync,
+    },
     infrastructure::{
-        condition::StatusCondition,
+        condition::{ReadCondition, StatusCondition},
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind},
+        statistics::DataReaderStatistics,
         status::{
             LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
             SampleLostStatus, SampleRejectedStatus, StatusKind, SubscriptionMatchedStatus,
@@ -14,7 +18,14 @@ use crate::{
     },
     runtime::executor::block_on,
     subscription::data_reader_listener::DataReaderListener,
-    topic_definition::{topic::Topic, type_support::DdsDeserialize},
+    topic_definition::{
+        topic::Topic,
+        type_support::{
+            deserialize_rtps_encapsulated_dynamic_data_w_max_sequence_length, DdsDeserialize,
+            DdsSerialize,
+        },
+    },
+    xtypes::{dynamic_data::DynamicData, dynamic_type::DynamicType},
 };
 
 use std::{marker::PhantomData, sync::Arc};
@@ -31,14 +42,21 @@ pub struct Sample<Foo> {
     data: Option<Arc<[u8]>>,
     /// Information of the sample received by the [`DataReader`].
     sample_info: SampleInfo,
+    /// Maximum number of elements of the sequences in the data as configured for the participant.
+    max_sequence_length: usize,
     phantom: PhantomData<Foo>,
 }
 
 impl<Foo> Sample<Foo> {
-    pub(crate) fn new(data: Option<Arc<[u8]>>, sample_info: SampleInfo) -> Self {
+    pub(crate) fn new(
+        data: Option<Arc<[u8]>>,
+        sample_info: SampleInfo,
+        max_sequence_length: usize,
+    ) -> Self {
         Self {
             data,
             sample_info,
+            max_sequence_length,
             phantom: PhantomData,
         }
     }
@@ -51,7 +69,25 @@ where
     /// Get the Foo value associated with this sample.
     pub fn data(&'de self) -> DdsResult<Foo> {
         match self.data.as_ref() {
-            Some(data) => Ok(Foo::deserialize_data(data.as_ref())?),
+            Some(data) => Ok(Foo::deserialize_data_w_max_sequence_length(
+                data.as_ref(),
+                self.max_sequence_length,
+            )?),
+            None => Err(DdsError::NoData),
+        }
+    }
+}
+
+impl Sample<DynamicData> {
+    /// Get the [`DynamicData`] value associated with this sample interpreted as the given type,
+    /// typically the type returned by [`Topic::get_type_support`].
+    pub fn dynamic_data(&self, dynamic_type: &dyn DynamicType) -> DdsResult<DynamicData> {
+        match self.data.as_ref() {
+            Some(data) => deserialize_rtps_encapsulated_dynamic_data_w_max_sequence_length(
+                data.as_ref(),
+                dynamic_type,
+                self.max_sequence_length,
+            ),
             None => Err(DdsError::NoData),
         }
     }
@@ -64,6 +100,20 @@ impl<Foo> Sample<Foo> {
     }
 }
 
+/// Iterator over the instances of a [`DataReader`] and their samples created by
+/// [`DataReader::iter_instances_ordered`]. Each item contains the [`InstanceHandle`] of the instance and its samples.
+pub struct OrderedInstanceIter<'a, Foo> {
+    iter_async: OrderedInstanceIterAsync<'a, Foo>,
+}
+
+impl<Foo> Iterator for OrderedInstanceIter<'_, Foo> {
+    type Item = DdsResult<(InstanceHandle, Vec<Sample<Foo>>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        block_on(self.iter_async.next())
+    }
+}
+
 /// A [`DataReader`] allows the application (1) to declare the data it wishes to receive (i.e., make a subscription) and (2) to access the
 /// data received by the attached [`Subscriber`].
 ///
@@ -163,6 +213,31 @@ impl<Foo> DataReader<Foo> {
         )
     }
 
+    /// This operation accesses a collection of [`Sample`] from the [`DataReader`] in the same way as [`DataReader::take`]
+    /// but, if no samples meet the constraints, it blocks the calling thread until such samples are received or the
+    /// duration specified by the `max_wait` parameter elapses, whichever happens first.
+    /// If `max_wait` elapses before any sample meeting the constraints is available, the return value will be
+    /// [`DdsError::NoData`](crate::infrastructure::error::DdsError).
+    /// This operation provides a simplified API for blocking consumption avoiding the need for the application to
+    /// attach a [`StatusCondition`] to a [`WaitSet`](crate::infrastructure::wait_set::WaitSet).
+    #[tracing::instrument(skip(self))]
+    pub fn take_timeout(
+        &self,
+        max_samples: i32,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        max_wait: Duration,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        block_on(self.reader_async.take_timeout(
+            max_samples,
+            sample_states,
+            view_states,
+            instance_states,
+            max_wait,
+        ))
+    }
+
     /// This operation reads the next, non-previously accessed [`Sample`] value from the [`DataReader`].
     /// The implied order among the samples stored in the [`DataReader`] is the same as for the [`DataReader::read`]
     /// operation. This operation is semantically equivalent to the read operation where the input Data sequence has
@@ -301,12 +376,40 @@ impl<Foo> DataReader<Foo> {
         ))
     }
 
+    /// This operation returns an iterator over the instances of the [`DataReader`] which have samples matching the
+    /// `sample_states`, `view_states` and `instance_states`. The instances are yielded one at a time together with their
+    /// samples in increasing order of their [`InstanceHandle`], following the same iteration as
+    /// [`DataReader::read_next_instance`]. The samples of each instance are ordered as required by the
+    /// [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy) with the
+    /// [`PresentationQosPolicyAccessScopeKind::Instance`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind)
+    /// access scope, that is consistently with the
+    /// [`DestinationOrderQosPolicy`](crate::infrastructure::qos_policy::DestinationOrderQosPolicy).
+    /// The samples are read, so they remain accessible via subsequent 'read' or 'take' operations.
+    /// Instances which receive samples during the iteration are yielded if their handle is after the last yielded one.
+    pub fn iter_instances_ordered(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> OrderedInstanceIter<'_, Foo> {
+        OrderedInstanceIter {
+            iter_async: self.reader_async.iter_instances_ordered(
+                sample_states,
+                view_states,
+                instance_states,
+            ),
+        }
+    }
+
     /// This operation can be used to retrieve the instance key that corresponds to an `handle`.
     /// The operation will only fill the fields that form the key inside the `key_holder` instance.
     /// This operation may return [`DdsError::BadParameter`](crate::infrastructure::error::DdsError)
     /// if the [`InstanceHandle`] `handle` does not correspond to an existing data object known to the [`DataReader`].
     #[tracing::instrument(skip(self, key_holder))]
-    pub fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()> {
+    pub fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()>
+    where
+        Foo: DdsSerialize + for<'de> DdsDeserialize<'de>,
+    {
         block_on(self.reader_async.get_key_value(key_holder, handle))
     }
 
@@ -317,9 +420,42 @@ impl<Foo> DataReader<Foo> {
     /// been previously registered, or if for any other reason the Service is unable to provide
     /// an instance handle, the operation will succeed and return [`None`].
     #[tracing::instrument(skip(self, instance))]
-    pub fn lookup_instance(&self, instance: &Foo) -> DdsResult<Option<InstanceHandle>> {
+    pub fn lookup_instance(&self, instance: &Foo) -> DdsResult<Option<InstanceHandle>>
+    where
+        Foo: DdsSerialize,
+    {
         block_on(self.reader_async.lookup_instance(instance))
     }
+
+    /// This operation creates a [`ReadCondition`]. The returned [`ReadCondition`] will be attached and belong to the [`DataReader`].
+    /// The *trigger_value* of the [`ReadCondition`] is [`true`] whenever the [`DataReader`] has samples matching the
+    /// `sample_states`, `view_states` and `instance_states`, so it can be attached to a
+    /// [`WaitSet`](crate::infrastructure::wait_set::WaitSet) to wait for the arrival of data.
+    #[tracing::instrument(skip(self))]
+    pub fn create_readcondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<ReadCondition> {
+        block_on(self.reader_async.create_readcondition(
+            sample_states,
+            view_states,
+            instance_states,
+        ))
+        .map(ReadCondition::new)
+    }
+
+    /// This operation deletes a [`ReadCondition`] attached to the [`DataReader`].
+    /// If the [`ReadCondition`] is not attached to the [`DataReader`], the operation will return the error
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn delete_readcondition(&self, a_condition: ReadCondition) -> DdsResult<()> {
+        block_on(
+            self.reader_async
+                .delete_readcondition(a_condition.condition_async().clone()),
+        )
+    }
 }
 
 impl<Foo> DataReader<Foo> {
@@ -361,6 +497,14 @@ impl<Foo> DataReader<Foo> {
         block_on(self.reader_async.get_subscription_matched_status())
     }
 
+    /// This operation returns the [`DataReaderStatistics`] with the number of instances known by the [`DataReader`]
+    /// in each state and the occupancy of its history. It is meant for the capacity monitoring of keyed topics.
+    /// This operation is a vendor extension.
+    #[tracing::instrument(skip(self))]
+    pub fn get_statistics(&self) -> DdsResult<DataReaderStatistics> {
+        block_on(self.reader_async.get_statistics())
+    }
+
     /// This operation returns the [`Topic`] associated with the [`DataReader`]. This is the same [`Topic`]
     /// that was used to create the [`DataReader`].
     #[tracing::instrument(skip(self))]
@@ -12,6 +12,7 @@ use crate::{
         status::{SampleLostStatus, StatusKind},
     },
     runtime::executor::block_on,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
     topic_definition::topic::Topic,
 };
 
@@ -104,6 +105,56 @@ impl Subscriber {
         )
     }
 
+    /// This operation indicates that the application is about to access the data samples in any of the [`DataReader`] objects
+    /// attached to the [`Subscriber`]. The application is required to use this operation only if the
+    /// [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy) of the [`Subscriber`] has the
+    /// `access_scope` set to [`PresentationQosPolicyAccessScopeKind::Group`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind).
+    /// In this case, the operation must be called prior to calling any of the sample-accessing operations, namely
+    /// [`Subscriber::get_datareaders`] on the [`Subscriber`] and read or take on any [`DataReader`].
+    /// With `coherent_access` set, the samples received during the access are only made available to the [`DataReader`] objects
+    /// once the access ends so that the data read from the different [`DataReader`] objects is consistent.
+    /// Calls to [`Subscriber::begin_access`] can be nested and must be matched with corresponding calls to [`Subscriber::end_access`].
+    #[tracing::instrument(skip(self))]
+    pub fn begin_access(&self) -> DdsResult<()> {
+        block_on(self.subscriber_async.begin_access())
+    }
+
+    /// This operation indicates that the application has finished accessing the data samples in [`DataReader`] objects managed by
+    /// the [`Subscriber`]. This operation must be used to close a corresponding [`Subscriber::begin_access`], otherwise it returns
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self))]
+    pub fn end_access(&self) -> DdsResult<()> {
+        block_on(self.subscriber_async.end_access())
+    }
+
+    /// This operation allows the application to access the [`DataReader`] objects that contain samples with the specified
+    /// `sample_states`, `view_states`, and `instance_states`.
+    /// If the [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy) of the [`Subscriber`] has the
+    /// `access_scope` set to [`PresentationQosPolicyAccessScopeKind::Group`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind)
+    /// and `ordered_access` set to [`true`], this operation must be called between [`Subscriber::begin_access`] and
+    /// [`Subscriber::end_access`], otherwise it returns [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    /// In this case the returned collection contains a [`DataReader`] once per sample, possibly several times, in the order in which
+    /// the samples have to be accessed: reading or taking one sample from each [`DataReader`] in the order of the collection returns
+    /// the samples in the order in which they were written. Otherwise each [`DataReader`] appears at most once and in no specific order.
+    /// The [`DataReader`] objects are returned with the type `Foo`, so the application must only use each of them to access the data
+    /// of its [`Topic`], as given by [`DataReader::get_topicdescription`].
+    #[tracing::instrument(skip(self))]
+    pub fn get_datareaders<Foo>(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<Vec<DataReader<Foo>>> {
+        Ok(block_on(self.subscriber_async.get_datareaders(
+            sample_states,
+            view_states,
+            instance_states,
+        ))?
+        .into_iter()
+        .map(DataReader::new)
+        .collect())
+    }
+
     /// This operation invokes the operation [`DataReaderListener::on_data_available`] on the listener objects attached to contained [`DataReader`]
     /// entities with a [`StatusKind::DataAvailable`] that is considered changed.
     /// This operation is typically invoked from the [`SubscriberListener::on_data_on_readers`] operation. That way the
@@ -119,7 +170,9 @@ impl Subscriber {
         DomainParticipant::new(self.subscriber_async.get_participant())
     }
 
-    /// This operation allows access to the [`SampleLostStatus`].
+    /// This operation allows access to the [`SampleLostStatus`]. The status of the [`Subscriber`] aggregates the samples lost
+    /// by all the [`DataReader`] objects it contains, including the ones already deleted. The `total_count_change` counts
+    /// the samples lost since the last time this operation was called on the [`Subscriber`].
     #[tracing::instrument(skip(self))]
     pub fn get_sample_lost_status(&self) -> DdsResult<SampleLostStatus> {
         block_on(self.subscriber_async.get_sample_lost_status())
@@ -164,10 +217,13 @@ impl Subscriber {
     /// may not be the final one, as the application can still modify some policies prior to applying the policies to the [`DataReader`].
     #[tracing::instrument]
     pub fn copy_from_topic_qos(
-        _a_datareader_qos: &mut DataReaderQos,
-        _a_topic_qos: &TopicQos,
+        a_datareader_qos: &mut DataReaderQos,
+        a_topic_qos: &TopicQos,
     ) -> DdsResult<()> {
-        todo!()
+        block_on(SubscriberAsync::copy_from_topic_qos(
+            a_datareader_qos,
+            a_topic_qos,
+        ))
     }
 
     /// This operation is used to set the QoS policies of the Entity and replacing the values of any policies previously set.
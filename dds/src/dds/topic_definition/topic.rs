@@ -76,6 +76,10 @@ impl Topic {
     /// The parameter `qos` can be set to [`QosKind::Default`] to indicate that the QoS of the Entity should be changed to match the current default QoS set in the Entity's factory.
     /// The operation [`Self::set_qos()`] cannot modify the immutable QoS so a successful return of the operation indicates that the mutable QoS for the Entity has been
     /// modified to match the current default for the Entity's factory.
+    /// When the [`Topic`] is enabled, the updated QoS is announced to the remote participants. The data writers and data readers already
+    /// created keep their QoS. The updated QoS can be applied to the ones created later with
+    /// [`Publisher::copy_from_topic_qos`](crate::publication::publisher::Publisher::copy_from_topic_qos) and
+    /// [`Subscriber::copy_from_topic_qos`](crate::subscription::subscriber::Subscriber::copy_from_topic_qos).
     #[tracing::instrument(skip(self))]
     pub fn set_qos(&self, qos: QosKind<TopicQos>) -> DdsResult<()> {
         block_on(self.topic_async.set_qos(qos))
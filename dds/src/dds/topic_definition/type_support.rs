@@ -1,8 +1,12 @@
 use crate::{
-    infrastructure::error::DdsResult,
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        qos_policy::{DataRepresentationId, XCDR2_DATA_REPRESENTATION, XCDR_DATA_REPRESENTATION},
+    },
     xtypes::{
-        dynamic_type::DynamicType,
-        xcdr_deserializer::{Xcdr2BeDeserializer, Xcdr2LeDeserializer},
+        dynamic_data::DynamicData,
+        dynamic_type::{DynamicType, ExtensibilityKind},
+        xcdr_deserializer::{delimited_body_v2, Xcdr2BeDeserializer, Xcdr2LeDeserializer},
     },
 };
 pub use dust_dds_derive::{DdsDeserialize, DdsSerialize};
@@ -28,6 +32,16 @@ pub trait TypeSupport {
 pub trait DdsSerialize {
     /// Method to serialize the instance of the type into the provided writer.
     fn serialize_data(&self) -> DdsResult<Vec<u8>>;
+
+    /// Method to serialize the instance of the type with the data representation offered by the
+    /// [`DataRepresentationQosPolicy`](crate::infrastructure::qos_policy::DataRepresentationQosPolicy)
+    /// of the writer. By default the representation is ignored and [`DdsSerialize::serialize_data`] is used.
+    fn serialize_data_w_representation(
+        &self,
+        _representation: DataRepresentationId,
+    ) -> DdsResult<Vec<u8>> {
+        self.serialize_data()
+    }
 }
 
 /// This trait describes how the bytes can be deserialize to construct the data structure.
@@ -41,6 +55,17 @@ pub trait DdsSerialize {
 pub trait DdsDeserialize<'de>: Sized {
     /// Method to deserialize the bytes into an instance of the type.
     fn deserialize_data(serialized_data: &'de [u8]) -> DdsResult<Self>;
+
+    /// Method to deserialize the bytes of a received sample into an instance of the type, rejecting the sequences
+    /// with more than `max_sequence_length` elements as configured by the
+    /// [`DeserializationLimits`](crate::configuration::DeserializationLimits). By default the limit is ignored and
+    /// [`DdsDeserialize::deserialize_data`] is used.
+    fn deserialize_data_w_max_sequence_length(
+        serialized_data: &'de [u8],
+        _max_sequence_length: usize,
+    ) -> DdsResult<Self> {
+        Self::deserialize_data(serialized_data)
+    }
 }
 
 use crate::xtypes::{
@@ -48,7 +73,7 @@ use crate::xtypes::{
     error::XTypesError,
     serialize::XTypesSerialize,
     xcdr_deserializer::{Xcdr1BeDeserializer, Xcdr1LeDeserializer},
-    xcdr_serializer::{Xcdr1BeSerializer, Xcdr1LeSerializer},
+    xcdr_serializer::{Xcdr1BeSerializer, Xcdr1LeSerializer, Xcdr2BeSerializer, Xcdr2LeSerializer},
 };
 /// This is a convenience derive to allow the user to easily derive all the different traits needed for a type to be used for
 /// communication with DustDDS. If the individual traits are manually derived then this derive should not be used.
@@ -83,6 +108,25 @@ use crate::xtypes::{
 ///     }
 /// ```
 ///
+/// The extensibility of the type is `final` unless specified with `#[dust_dds(extensibility = "appendable")]`
+/// or `#[dust_dds(extensibility = "mutable")]`, in which case every field of a mutable type must have an `id`.
+/// Fields of type [`Option`] marked `#[dust_dds(optional)]` are serialized as optional members which may be absent:
+///
+/// ```rust
+///     use dust_dds::topic_definition::type_support::DdsType;
+///
+///     #[derive(DdsType)]
+///     #[dust_dds(extensibility = "mutable")]
+///     struct EvolvingData {
+///         #[dust_dds(key, id = 10)]
+///         id: u8,
+///         #[dust_dds(id = 11)]
+///         value: u32,
+///         #[dust_dds(id = 12, optional)]
+///         unit: Option<String>,
+///     }
+/// ```
+///
 pub use dust_dds_derive::DdsType;
 
 type RepresentationIdentifier = [u8; 2];
@@ -92,13 +136,16 @@ const CDR_BE: RepresentationIdentifier = [0x00, 0x00];
 const CDR_LE: RepresentationIdentifier = [0x00, 0x01];
 const CDR2_BE: RepresentationIdentifier = [0x00, 0x06];
 const CDR2_LE: RepresentationIdentifier = [0x00, 0x07];
-const _D_CDR2_BE: RepresentationIdentifier = [0x00, 0x08];
-const _D_CDR2_LE: RepresentationIdentifier = [0x00, 0x09];
-const _PL_CDR_BE: RepresentationIdentifier = [0x00, 0x02];
-const _PL_CDR_LE: RepresentationIdentifier = [0x00, 0x03];
+const D_CDR2_BE: RepresentationIdentifier = [0x00, 0x08];
+const D_CDR2_LE: RepresentationIdentifier = [0x00, 0x09];
+const PL_CDR_BE: RepresentationIdentifier = [0x00, 0x02];
+const PL_CDR_LE: RepresentationIdentifier = [0x00, 0x03];
+const PL_CDR2_BE: RepresentationIdentifier = [0x00, 0x0a];
+const PL_CDR2_LE: RepresentationIdentifier = [0x00, 0x0b];
 const REPRESENTATION_OPTIONS: RepresentationOptions = [0x00, 0x00];
 
 /// This is a helper function to serialize a type implementing [`XTypesSerialize`] using the XTypes defined XCDR1 representation with LittleEndian endianness.
+/// The representation identifier is PL_CDR if the type is mutable and PLAIN_CDR otherwise.
 pub fn serialize_rtps_xtypes_xcdr1_le(value: &impl XTypesSerialize) -> DdsResult<Vec<u8>> {
     let padded_length = (Xcdr1LeSerializer::bytes_len(value)? + 3) & !3;
     let mut writer = Vec::with_capacity(padded_length + 4);
@@ -106,11 +153,15 @@ pub fn serialize_rtps_xtypes_xcdr1_le(value: &impl XTypesSerialize) -> DdsResult
     writer.write_all(&REPRESENTATION_OPTIONS)?;
     let mut serializer = Xcdr1LeSerializer::new(&mut writer);
     XTypesSerialize::serialize(value, &mut serializer)?;
+    if serializer.extensibility() == Some(ExtensibilityKind::Mutable) {
+        writer[..2].copy_from_slice(&PL_CDR_LE);
+    }
     pad(&mut writer)?;
     Ok(writer)
 }
 
 /// This is a helper function to serialize a type implementing [`XTypesSerialize`] using the XTypes defined XCDR1 representation with BigEndian endianness.
+/// The representation identifier is PL_CDR if the type is mutable and PLAIN_CDR otherwise.
 pub fn serialize_rtps_xtypes_xcdr1_be(value: &impl XTypesSerialize) -> DdsResult<Vec<u8>> {
     let padded_length = (Xcdr1BeSerializer::bytes_len(value)? + 3) & !3;
     let mut writer = Vec::with_capacity(padded_length + 4);
@@ -118,10 +169,62 @@ pub fn serialize_rtps_xtypes_xcdr1_be(value: &impl XTypesSerialize) -> DdsResult
     writer.write_all(&REPRESENTATION_OPTIONS)?;
     let mut serializer = Xcdr1BeSerializer::new(&mut writer);
     XTypesSerialize::serialize(value, &mut serializer)?;
+    if serializer.extensibility() == Some(ExtensibilityKind::Mutable) {
+        writer[..2].copy_from_slice(&PL_CDR_BE);
+    }
+    pad(&mut writer)?;
+    Ok(writer)
+}
+
+/// This is a helper function to serialize a type implementing [`XTypesSerialize`] using the XTypes defined XCDR2 representation with LittleEndian endianness.
+/// The representation identifier is PLAIN_CDR2, DELIMITED_CDR2 or PL_CDR2 depending on the extensibility of the type.
+pub fn serialize_rtps_xtypes_xcdr2_le(value: &impl XTypesSerialize) -> DdsResult<Vec<u8>> {
+    let padded_length = (Xcdr2LeSerializer::bytes_len(value)? + 3) & !3;
+    let mut writer = Vec::with_capacity(padded_length + 4);
+    writer.write_all(&CDR2_LE)?;
+    writer.write_all(&REPRESENTATION_OPTIONS)?;
+    let mut serializer = Xcdr2LeSerializer::new(&mut writer);
+    XTypesSerialize::serialize(value, &mut serializer)?;
+    match serializer.extensibility() {
+        Some(ExtensibilityKind::Appendable) => writer[..2].copy_from_slice(&D_CDR2_LE),
+        Some(ExtensibilityKind::Mutable) => writer[..2].copy_from_slice(&PL_CDR2_LE),
+        Some(ExtensibilityKind::Final) | None => (),
+    }
+    pad(&mut writer)?;
+    Ok(writer)
+}
+
+/// This is a helper function to serialize a type implementing [`XTypesSerialize`] using the XTypes defined XCDR2 representation with BigEndian endianness.
+/// The representation identifier is PLAIN_CDR2, DELIMITED_CDR2 or PL_CDR2 depending on the extensibility of the type.
+pub fn serialize_rtps_xtypes_xcdr2_be(value: &impl XTypesSerialize) -> DdsResult<Vec<u8>> {
+    let padded_length = (Xcdr2BeSerializer::bytes_len(value)? + 3) & !3;
+    let mut writer = Vec::with_capacity(padded_length + 4);
+    writer.write_all(&CDR2_BE)?;
+    writer.write_all(&REPRESENTATION_OPTIONS)?;
+    let mut serializer = Xcdr2BeSerializer::new(&mut writer);
+    XTypesSerialize::serialize(value, &mut serializer)?;
+    match serializer.extensibility() {
+        Some(ExtensibilityKind::Appendable) => writer[..2].copy_from_slice(&D_CDR2_BE),
+        Some(ExtensibilityKind::Mutable) => writer[..2].copy_from_slice(&PL_CDR2_BE),
+        Some(ExtensibilityKind::Final) | None => (),
+    }
     pad(&mut writer)?;
     Ok(writer)
 }
 
+/// This is a helper function to serialize a type implementing [`XTypesSerialize`] with LittleEndian endianness using the
+/// XCDR1 or XCDR2 representation as given by the [`DataRepresentationQosPolicy`](crate::infrastructure::qos_policy::DataRepresentationQosPolicy).
+pub fn serialize_rtps_xtypes_le(
+    value: &impl XTypesSerialize,
+    representation: DataRepresentationId,
+) -> DdsResult<Vec<u8>> {
+    match representation {
+        XCDR_DATA_REPRESENTATION => serialize_rtps_xtypes_xcdr1_le(value),
+        XCDR2_DATA_REPRESENTATION => serialize_rtps_xtypes_xcdr2_le(value),
+        _ => Err(DdsError::Unsupported),
+    }
+}
+
 fn pad(writer: &mut Vec<u8>) -> std::io::Result<()> {
     let padding = match writer.len() % 4 {
         1 => &[0, 0, 0][..],
@@ -137,6 +240,18 @@ fn pad(writer: &mut Vec<u8>) -> std::io::Result<()> {
 /// This is a helper function to deserialize a type implementing [`CdrDeserialize`] using the RTPS classic CDR representation.
 /// The representation endianness to be used is automatically determined from the representation identifier and options
 pub fn deserialize_rtps_encapsulated_data<'de, T>(serialized_data: &mut &'de [u8]) -> DdsResult<T>
+where
+    T: XTypesDeserialize<'de>,
+{
+    deserialize_rtps_encapsulated_data_w_max_sequence_length(serialized_data, usize::MAX)
+}
+
+/// This is a helper function to deserialize a type implementing [`XTypesDeserialize`] like
+/// [`deserialize_rtps_encapsulated_data`] which rejects the sequences with more than `max_sequence_length` elements.
+pub fn deserialize_rtps_encapsulated_data_w_max_sequence_length<'de, T>(
+    serialized_data: &mut &'de [u8],
+    max_sequence_length: usize,
+) -> DdsResult<T>
 where
     T: XTypesDeserialize<'de>,
 {
@@ -146,11 +261,141 @@ where
     serialized_data.read_exact(&mut representation_option)?;
 
     let value = match representation_identifier {
-        CDR_BE => XTypesDeserialize::deserialize(&mut Xcdr1BeDeserializer::new(serialized_data)),
-        CDR_LE => XTypesDeserialize::deserialize(&mut Xcdr1LeDeserializer::new(serialized_data)),
-        CDR2_BE => XTypesDeserialize::deserialize(&mut Xcdr2BeDeserializer::new(serialized_data)),
-        CDR2_LE => XTypesDeserialize::deserialize(&mut Xcdr2LeDeserializer::new(serialized_data)),
+        CDR_BE | PL_CDR_BE => {
+            XTypesDeserialize::deserialize(&mut Xcdr1BeDeserializer::with_max_sequence_length(
+                serialized_data,
+                max_sequence_length,
+            ))
+        }
+        CDR_LE | PL_CDR_LE => {
+            XTypesDeserialize::deserialize(&mut Xcdr1LeDeserializer::with_max_sequence_length(
+                serialized_data,
+                max_sequence_length,
+            ))
+        }
+        CDR2_BE | D_CDR2_BE | PL_CDR2_BE => {
+            XTypesDeserialize::deserialize(&mut Xcdr2BeDeserializer::with_max_sequence_length(
+                serialized_data,
+                max_sequence_length,
+            ))
+        }
+        CDR2_LE | D_CDR2_LE | PL_CDR2_LE => {
+            XTypesDeserialize::deserialize(&mut Xcdr2LeDeserializer::with_max_sequence_length(
+                serialized_data,
+                max_sequence_length,
+            ))
+        }
         _ => Err(XTypesError::InvalidData),
     }?;
     Ok(value)
 }
+
+/// This is a helper function to deserialize the data of a sample with a type known only at runtime.
+/// Mutable types are expected to be encoded as a parameter list. With the XCDR2 representations only the
+/// top level type may be appendable or mutable.
+pub fn deserialize_rtps_encapsulated_dynamic_data(
+    serialized_data: &[u8],
+    dynamic_type: &dyn DynamicType,
+) -> DdsResult<DynamicData> {
+    deserialize_rtps_encapsulated_dynamic_data_w_max_sequence_length(
+        serialized_data,
+        dynamic_type,
+        usize::MAX,
+    )
+}
+
+/// This is a helper function to deserialize the data of a sample with a type known only at runtime like
+/// [`deserialize_rtps_encapsulated_dynamic_data`] which rejects the sequences with more than `max_sequence_length`
+/// elements.
+pub fn deserialize_rtps_encapsulated_dynamic_data_w_max_sequence_length(
+    mut serialized_data: &[u8],
+    dynamic_type: &dyn DynamicType,
+    max_sequence_length: usize,
+) -> DdsResult<DynamicData> {
+    let mut representation_identifier = [0u8, 0];
+    serialized_data.read_exact(&mut representation_identifier)?;
+    let mut representation_option = [0u8, 0];
+    serialized_data.read_exact(&mut representation_option)?;
+
+    let is_mutable =
+        dynamic_type.get_descriptor()?.extensibility_kind == ExtensibilityKind::Mutable;
+    let value = match representation_identifier {
+        CDR_BE | PL_CDR_BE if is_mutable => DynamicData::deserialize_parameter_list_be(
+            dynamic_type,
+            serialized_data,
+            max_sequence_length,
+        ),
+        CDR_LE | PL_CDR_LE if is_mutable => DynamicData::deserialize_parameter_list_le(
+            dynamic_type,
+            serialized_data,
+            max_sequence_length,
+        ),
+        CDR_BE => DynamicData::deserialize(
+            dynamic_type,
+            &mut Xcdr1BeDeserializer::with_max_sequence_length(
+                serialized_data,
+                max_sequence_length,
+            ),
+        ),
+        CDR_LE => DynamicData::deserialize(
+            dynamic_type,
+            &mut Xcdr1LeDeserializer::with_max_sequence_length(
+                serialized_data,
+                max_sequence_length,
+            ),
+        ),
+        CDR2_BE => DynamicData::deserialize(
+            dynamic_type,
+            &mut Xcdr2BeDeserializer::with_max_sequence_length(
+                serialized_data,
+                max_sequence_length,
+            ),
+        ),
+        CDR2_LE => DynamicData::deserialize(
+            dynamic_type,
+            &mut Xcdr2LeDeserializer::with_max_sequence_length(
+                serialized_data,
+                max_sequence_length,
+            ),
+        ),
+        D_CDR2_BE => DynamicData::deserialize(
+            dynamic_type,
+            &mut Xcdr2BeDeserializer::with_max_sequence_length(
+                delimited_body_v2(serialized_data, u32::from_be_bytes)?,
+                max_sequence_length,
+            ),
+        ),
+        D_CDR2_LE => DynamicData::deserialize(
+            dynamic_type,
+            &mut Xcdr2LeDeserializer::with_max_sequence_length(
+                delimited_body_v2(serialized_data, u32::from_le_bytes)?,
+                max_sequence_length,
+            ),
+        ),
+        PL_CDR2_BE => DynamicData::deserialize_member_list_be(
+            dynamic_type,
+            delimited_body_v2(serialized_data, u32::from_be_bytes)?,
+            max_sequence_length,
+        ),
+        PL_CDR2_LE => DynamicData::deserialize_member_list_le(
+            dynamic_type,
+            delimited_body_v2(serialized_data, u32::from_le_bytes)?,
+            max_sequence_length,
+        ),
+        _ => Err(XTypesError::InvalidData),
+    }?;
+    Ok(value)
+}
+
+impl DdsSerialize for DynamicData {
+    fn serialize_data(&self) -> DdsResult<Vec<u8>> {
+        serialize_rtps_xtypes_xcdr1_le(self)
+    }
+
+    fn serialize_data_w_representation(
+        &self,
+        representation: DataRepresentationId,
+    ) -> DdsResult<Vec<u8>> {
+        serialize_rtps_xtypes_le(self, representation)
+    }
+}
@@ -1,7 +1,17 @@
 use crate::{
-    implementation::status_condition::status_condition_actor::{self, StatusConditionActor},
-    infrastructure::{error::DdsResult, status::StatusKind},
+    implementation::{
+        domain_participant_backend::{
+            domain_participant_actor::DomainParticipantActor, services::data_reader_service,
+        },
+        status_condition::status_condition_actor::{self, StatusConditionActor},
+    },
+    infrastructure::{error::DdsResult, instance::InstanceHandle, status::StatusKind},
     runtime::actor::ActorAddress,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
+};
+use std::sync::{
+    atomic::{self, AtomicBool},
+    Arc,
 };
 
 /// Async version of [`StatusCondition`](crate::infrastructure::condition::StatusCondition).
@@ -58,3 +68,100 @@ impl StatusConditionAsync {
             .await)
     }
 }
+
+/// Async version of [`ReadCondition`](crate::infrastructure::condition::ReadCondition).
+#[derive(Clone)]
+pub struct ReadConditionAsync {
+    participant_address: ActorAddress<DomainParticipantActor>,
+    subscriber_handle: InstanceHandle,
+    data_reader_handle: InstanceHandle,
+    sample_states: Vec<SampleStateKind>,
+    view_states: Vec<ViewStateKind>,
+    instance_states: Vec<InstanceStateKind>,
+}
+
+impl ReadConditionAsync {
+    pub(crate) fn new(
+        participant_address: ActorAddress<DomainParticipantActor>,
+        subscriber_handle: InstanceHandle,
+        data_reader_handle: InstanceHandle,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+    ) -> Self {
+        Self {
+            participant_address,
+            subscriber_handle,
+            data_reader_handle,
+            sample_states,
+            view_states,
+            instance_states,
+        }
+    }
+
+    pub(crate) fn data_reader_handle(&self) -> InstanceHandle {
+        self.data_reader_handle
+    }
+}
+
+impl ReadConditionAsync {
+    /// Async version of [`get_sample_state_mask`](crate::infrastructure::condition::ReadCondition::get_sample_state_mask).
+    pub fn get_sample_state_mask(&self) -> &[SampleStateKind] {
+        &self.sample_states
+    }
+
+    /// Async version of [`get_view_state_mask`](crate::infrastructure::condition::ReadCondition::get_view_state_mask).
+    pub fn get_view_state_mask(&self) -> &[ViewStateKind] {
+        &self.view_states
+    }
+
+    /// Async version of [`get_instance_state_mask`](crate::infrastructure::condition::ReadCondition::get_instance_state_mask).
+    pub fn get_instance_state_mask(&self) -> &[InstanceStateKind] {
+        &self.instance_states
+    }
+}
+
+impl ReadConditionAsync {
+    /// Async version of [`get_trigger_value`](crate::infrastructure::condition::ReadCondition::get_trigger_value).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_trigger_value(&self) -> DdsResult<bool> {
+        self.participant_address
+            .send_actor_mail(data_reader_service::GetReadConditionTriggerValue {
+                subscriber_handle: self.subscriber_handle,
+                data_reader_handle: self.data_reader_handle,
+                sample_states: self.sample_states.clone(),
+                view_states: self.view_states.clone(),
+                instance_states: self.instance_states.clone(),
+            })?
+            .receive_reply()
+            .await
+    }
+}
+
+/// Async version of [`GuardCondition`](crate::infrastructure::condition::GuardCondition).
+#[derive(Clone, Default)]
+pub struct GuardConditionAsync {
+    trigger_value: Arc<AtomicBool>,
+}
+
+impl GuardConditionAsync {
+    /// Create a new [`GuardConditionAsync`] with a *trigger_value* of [`false`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Async version of [`set_trigger_value`](crate::infrastructure::condition::GuardCondition::set_trigger_value).
+    #[tracing::instrument(skip(self))]
+    pub async fn set_trigger_value(&self, value: bool) -> DdsResult<()> {
+        self.trigger_value.store(value, atomic::Ordering::Release);
+        Ok(())
+    }
+}
+
+impl GuardConditionAsync {
+    /// Async version of [`get_trigger_value`](crate::infrastructure::condition::GuardCondition::get_trigger_value).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_trigger_value(&self) -> DdsResult<bool> {
+        Ok(self.trigger_value.load(atomic::Ordering::Acquire))
+    }
+}
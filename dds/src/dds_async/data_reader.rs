@@ -1,8 +1,11 @@
 use tracing::warn;
 
 use super::{
-    condition::StatusConditionAsync, data_reader_listener::DataReaderListenerAsync,
-    subscriber::SubscriberAsync, topic::TopicAsync,
+    condition::{ReadConditionAsync, StatusConditionAsync},
+    data_reader_listener::DataReaderListenerAsync,
+    data_reader_stream::DataReaderStream,
+    subscriber::SubscriberAsync,
+    topic::TopicAsync,
 };
 use crate::{
     builtin_topics::PublicationBuiltinTopicData,
@@ -11,12 +14,13 @@ use crate::{
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor, services::data_reader_service,
         },
-        status_condition::status_condition_actor::StatusConditionActor,
+        status_condition::status_condition_actor::{self, StatusConditionActor},
     },
     infrastructure::{
-        error::DdsResult,
+        error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind},
+        statistics::DataReaderStatistics,
         status::{
             LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
             SampleLostStatus, SampleRejectedStatus, StatusKind, SubscriptionMatchedStatus,
@@ -30,6 +34,7 @@ use crate::{
             InstanceStateKind, SampleStateKind, ViewStateKind, ANY_INSTANCE_STATE, ANY_VIEW_STATE,
         },
     },
+    topic_definition::type_support::{DdsDeserialize, DdsSerialize},
 };
 use std::marker::PhantomData;
 
@@ -62,6 +67,10 @@ impl<Foo> DataReaderAsync<Foo> {
         self.subscriber.participant_address()
     }
 
+    pub(crate) fn status_condition_address(&self) -> &ActorAddress<StatusConditionActor> {
+        &self.status_condition_address
+    }
+
     pub(crate) fn change_foo_type<T>(self) -> DataReaderAsync<T> {
         DataReaderAsync {
             handle: self.handle,
@@ -111,7 +120,9 @@ impl<Foo> DataReaderAsync<Foo> {
 
         Ok(samples
             .into_iter()
-            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .map(|(data, sample_info)| {
+                Sample::new(data, sample_info, self.subscriber.max_sequence_length())
+            })
             .collect())
     }
 
@@ -140,7 +151,63 @@ impl<Foo> DataReaderAsync<Foo> {
 
         Ok(samples
             .into_iter()
-            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .map(|(data, sample_info)| {
+                Sample::new(data, sample_info, self.subscriber.max_sequence_length())
+            })
+            .collect())
+    }
+
+    /// Async version of [`take_timeout`](crate::subscription::data_reader::DataReader::take_timeout).
+    #[tracing::instrument(skip(self))]
+    pub async fn take_timeout(
+        &self,
+        max_samples: i32,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        max_wait: Duration,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        let participant_address = self.participant_address().clone();
+        let subscriber_handle = self.subscriber.get_instance_handle().await;
+        let data_reader_handle = self.handle;
+        let sample_states = sample_states.to_vec();
+        let view_states = view_states.to_vec();
+        let instance_states = instance_states.to_vec();
+        let samples = self
+            .subscriber
+            .get_participant()
+            .timer_handle()
+            .timeout(
+                max_wait.into(),
+                Box::pin(async move {
+                    loop {
+                        match participant_address
+                            .send_actor_mail(data_reader_service::Take {
+                                subscriber_handle,
+                                data_reader_handle,
+                                max_samples,
+                                sample_states: sample_states.clone(),
+                                view_states: view_states.clone(),
+                                instance_states: instance_states.clone(),
+                                specific_instance_handle: None,
+                            })?
+                            .receive_reply()
+                            .await
+                        {
+                            Err(DdsError::NoData) => (),
+                            r => return r,
+                        }
+                    }
+                }),
+            )
+            .await
+            .map_err(|_| DdsError::NoData)??;
+
+        Ok(samples
+            .into_iter()
+            .map(|(data, sample_info)| {
+                Sample::new(data, sample_info, self.subscriber.max_sequence_length())
+            })
             .collect())
     }
 
@@ -161,7 +228,11 @@ impl<Foo> DataReaderAsync<Foo> {
             .receive_reply()
             .await?;
         let (data, sample_info) = samples.pop().expect("Would return NoData if empty");
-        Ok(Sample::new(data, sample_info))
+        Ok(Sample::new(
+            data,
+            sample_info,
+            self.subscriber.max_sequence_length(),
+        ))
     }
 
     /// Async version of [`take_next_sample`](crate::subscription::data_reader::DataReader::take_next_sample).
@@ -181,7 +252,11 @@ impl<Foo> DataReaderAsync<Foo> {
             .receive_reply()
             .await?;
         let (data, sample_info) = samples.pop().expect("Would return NoData if empty");
-        Ok(Sample::new(data, sample_info))
+        Ok(Sample::new(
+            data,
+            sample_info,
+            self.subscriber.max_sequence_length(),
+        ))
     }
 
     /// Async version of [`read_instance`](crate::subscription::data_reader::DataReader::read_instance).
@@ -209,7 +284,9 @@ impl<Foo> DataReaderAsync<Foo> {
             .await?;
         Ok(samples
             .into_iter()
-            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .map(|(data, sample_info)| {
+                Sample::new(data, sample_info, self.subscriber.max_sequence_length())
+            })
             .collect())
     }
 
@@ -239,7 +316,9 @@ impl<Foo> DataReaderAsync<Foo> {
 
         Ok(samples
             .into_iter()
-            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .map(|(data, sample_info)| {
+                Sample::new(data, sample_info, self.subscriber.max_sequence_length())
+            })
             .collect())
     }
 
@@ -268,7 +347,9 @@ impl<Foo> DataReaderAsync<Foo> {
             .await?;
         Ok(samples
             .into_iter()
-            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .map(|(data, sample_info)| {
+                Sample::new(data, sample_info, self.subscriber.max_sequence_length())
+            })
             .collect())
     }
 
@@ -297,24 +378,94 @@ impl<Foo> DataReaderAsync<Foo> {
             .await?;
         Ok(samples
             .into_iter()
-            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .map(|(data, sample_info)| {
+                Sample::new(data, sample_info, self.subscriber.max_sequence_length())
+            })
             .collect())
     }
 
-    /// Async version of [`get_key_value`](crate::subscription::data_reader::DataReader::get_key_value).
-    #[tracing::instrument(skip(self, _key_holder))]
-    pub async fn get_key_value(
+    /// Async version of [`iter_instances_ordered`](crate::subscription::data_reader::DataReader::iter_instances_ordered).
+    pub fn iter_instances_ordered(
         &self,
-        _key_holder: &mut Foo,
-        _handle: InstanceHandle,
-    ) -> DdsResult<()> {
-        todo!()
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> OrderedInstanceIterAsync<'_, Foo> {
+        OrderedInstanceIterAsync {
+            reader: self,
+            sample_states: sample_states.to_vec(),
+            view_states: view_states.to_vec(),
+            instance_states: instance_states.to_vec(),
+            previous_handle: None,
+            finished: false,
+        }
+    }
+
+    /// Async version of [`get_key_value`](crate::subscription::data_reader::DataReader::get_key_value).
+    #[tracing::instrument(skip(self, key_holder))]
+    pub async fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()>
+    where
+        Foo: DdsSerialize + for<'de> DdsDeserialize<'de>,
+    {
+        let serialized_key_holder = key_holder.serialize_data()?;
+        let serialized_data = self
+            .participant_address()
+            .send_actor_mail(data_reader_service::GetKeyValue {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+                serialized_key_holder,
+                instance_handle: handle,
+            })?
+            .receive_reply()
+            .await?;
+        *key_holder = Foo::deserialize_data(&serialized_data)?;
+        Ok(())
     }
 
     /// Async version of [`lookup_instance`](crate::subscription::data_reader::DataReader::lookup_instance).
-    #[tracing::instrument(skip(self, _instance))]
-    pub async fn lookup_instance(&self, _instance: &Foo) -> DdsResult<Option<InstanceHandle>> {
-        todo!()
+    #[tracing::instrument(skip(self, instance))]
+    pub async fn lookup_instance(&self, instance: &Foo) -> DdsResult<Option<InstanceHandle>>
+    where
+        Foo: DdsSerialize,
+    {
+        let serialized_data = instance.serialize_data()?;
+        self.participant_address()
+            .send_actor_mail(data_reader_service::LookupInstance {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+                serialized_data,
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`create_readcondition`](crate::subscription::data_reader::DataReader::create_readcondition).
+    #[tracing::instrument(skip(self))]
+    pub async fn create_readcondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<ReadConditionAsync> {
+        Ok(ReadConditionAsync::new(
+            self.participant_address().clone(),
+            self.subscriber.get_instance_handle().await,
+            self.handle,
+            sample_states.to_vec(),
+            view_states.to_vec(),
+            instance_states.to_vec(),
+        ))
+    }
+
+    /// Async version of [`delete_readcondition`](crate::subscription::data_reader::DataReader::delete_readcondition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn delete_readcondition(&self, a_condition: ReadConditionAsync) -> DdsResult<()> {
+        if a_condition.data_reader_handle() != self.handle {
+            return Err(DdsError::PreconditionNotMet(
+                "ReadCondition not created by this DataReader".to_string(),
+            ));
+        }
+        Ok(())
     }
 }
 
@@ -322,7 +473,13 @@ impl<Foo> DataReaderAsync<Foo> {
     /// Async version of [`get_liveliness_changed_status`](crate::subscription::data_reader::DataReader::get_liveliness_changed_status).
     #[tracing::instrument(skip(self))]
     pub async fn get_liveliness_changed_status(&self) -> DdsResult<LivelinessChangedStatus> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_reader_service::GetLivelinessChangedStatus {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_requested_deadline_missed_status`](crate::subscription::data_reader::DataReader::get_requested_deadline_missed_status).
@@ -330,7 +487,13 @@ impl<Foo> DataReaderAsync<Foo> {
     pub async fn get_requested_deadline_missed_status(
         &self,
     ) -> DdsResult<RequestedDeadlineMissedStatus> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_reader_service::GetRequestedDeadlineMissedStatus {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_requested_incompatible_qos_status`](crate::subscription::data_reader::DataReader::get_requested_incompatible_qos_status).
@@ -338,19 +501,37 @@ impl<Foo> DataReaderAsync<Foo> {
     pub async fn get_requested_incompatible_qos_status(
         &self,
     ) -> DdsResult<RequestedIncompatibleQosStatus> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_reader_service::GetRequestedIncompatibleQosStatus {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_sample_lost_status`](crate::subscription::data_reader::DataReader::get_sample_lost_status).
     #[tracing::instrument(skip(self))]
     pub async fn get_sample_lost_status(&self) -> DdsResult<SampleLostStatus> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_reader_service::GetSampleLostStatus {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_sample_rejected_status`](crate::subscription::data_reader::DataReader::get_sample_rejected_status).
     #[tracing::instrument(skip(self))]
     pub async fn get_sample_rejected_status(&self) -> DdsResult<SampleRejectedStatus> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_reader_service::GetSampleRejectedStatus {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_subscription_matched_status`](crate::subscription::data_reader::DataReader::get_subscription_matched_status).
@@ -365,6 +546,18 @@ impl<Foo> DataReaderAsync<Foo> {
             .await
     }
 
+    /// Async version of [`get_statistics`](crate::subscription::data_reader::DataReader::get_statistics).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_statistics(&self) -> DdsResult<DataReaderStatistics> {
+        self.participant_address()
+            .send_actor_mail(data_reader_service::GetStatistics {
+                subscriber_handle: self.subscriber.get_instance_handle().await,
+                data_reader_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
+    }
+
     /// Async version of [`get_topicdescription`](crate::subscription::data_reader::DataReader::get_topicdescription).
     #[tracing::instrument(skip(self))]
     pub fn get_topicdescription(&self) -> TopicAsync {
@@ -456,7 +649,11 @@ impl<Foo> DataReaderAsync<Foo> {
     /// Async version of [`get_status_changes`](crate::subscription::data_reader::DataReader::get_status_changes).
     #[tracing::instrument(skip(self))]
     pub async fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
+        Ok(self
+            .status_condition_address
+            .send_actor_mail(status_condition_actor::GetStatusChanges)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`enable`](crate::subscription::data_reader::DataReader::enable).
@@ -479,6 +676,17 @@ impl<Foo> DataReaderAsync<Foo> {
     }
 }
 
+impl<Foo> DataReaderAsync<Foo>
+where
+    Foo: for<'de> DdsDeserialize<'de> + Send + Sync + 'static,
+{
+    /// Convert the reader into a [`DataReaderStream`] which takes the received samples one by one as they
+    /// become available. The stream ends once the reader is deleted.
+    pub fn into_stream(self) -> DataReaderStream<Foo> {
+        DataReaderStream::new(self)
+    }
+}
+
 impl<'a, Foo> DataReaderAsync<Foo>
 where
     Foo: 'a,
@@ -502,3 +710,48 @@ where
             .await
     }
 }
+
+/// Async version of [`OrderedInstanceIter`](crate::subscription::data_reader::OrderedInstanceIter).
+pub struct OrderedInstanceIterAsync<'a, Foo> {
+    reader: &'a DataReaderAsync<Foo>,
+    sample_states: Vec<SampleStateKind>,
+    view_states: Vec<ViewStateKind>,
+    instance_states: Vec<InstanceStateKind>,
+    previous_handle: Option<InstanceHandle>,
+    finished: bool,
+}
+
+impl<Foo> OrderedInstanceIterAsync<'_, Foo> {
+    /// Read the samples of the next instance in the instance handle order. This operation returns [`None`]
+    /// once there are no more instances with samples matching the requested states.
+    pub async fn next(&mut self) -> Option<DdsResult<(InstanceHandle, Vec<Sample<Foo>>)>> {
+        if self.finished {
+            return None;
+        }
+        match self
+            .reader
+            .read_next_instance(
+                i32::MAX,
+                self.previous_handle,
+                &self.sample_states,
+                &self.view_states,
+                &self.instance_states,
+            )
+            .await
+        {
+            Ok(samples) => {
+                let handle = samples[0].sample_info().instance_handle;
+                self.previous_handle = Some(handle);
+                Some(Ok((handle, samples)))
+            }
+            Err(DdsError::NoData) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
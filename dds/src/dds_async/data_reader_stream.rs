@@ -0,0 +1,91 @@
+use super::data_reader::DataReaderAsync;
+use crate::{
+    implementation::status_condition::status_condition_actor,
+    infrastructure::{error::DdsError, status::StatusKind},
+    subscription::sample_info::SampleInfo,
+    topic_definition::type_support::DdsDeserialize,
+};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_core::Stream;
+
+type NextSampleFuture<Foo> =
+    Pin<Box<dyn Future<Output = Option<((Foo, SampleInfo), DataReaderAsync<Foo>)>> + Send>>;
+
+/// Stream of the samples received by a [`DataReaderAsync`] created by
+/// [`DataReaderAsync::into_stream`]. Each sample is taken from the reader in the order in which
+/// it would be returned by [`DataReaderAsync::take_next_sample`] and the samples which contain
+/// no valid data are skipped.
+pub struct DataReaderStream<Foo> {
+    next_sample: Option<NextSampleFuture<Foo>>,
+}
+
+impl<Foo> DataReaderStream<Foo>
+where
+    Foo: for<'de> DdsDeserialize<'de> + Send + Sync + 'static,
+{
+    pub(crate) fn new(reader: DataReaderAsync<Foo>) -> Self {
+        Self {
+            next_sample: Some(Box::pin(take_next_valid_sample(reader))),
+        }
+    }
+}
+
+impl<Foo> Stream for DataReaderStream<Foo>
+where
+    Foo: for<'de> DdsDeserialize<'de> + Send + Sync + 'static,
+{
+    type Item = (Foo, SampleInfo);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(next_sample) = self.next_sample.as_mut() else {
+            return Poll::Ready(None);
+        };
+        match next_sample.as_mut().poll(cx) {
+            Poll::Ready(Some((sample, reader))) => {
+                self.next_sample = Some(Box::pin(take_next_valid_sample(reader)));
+                Poll::Ready(Some(sample))
+            }
+            Poll::Ready(None) => {
+                self.next_sample = None;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+// Takes the samples one by one and, when there are none left, waits until the status condition
+// of the reader reports new data. The stream ends once the reader is deleted
+async fn take_next_valid_sample<Foo>(
+    reader: DataReaderAsync<Foo>,
+) -> Option<((Foo, SampleInfo), DataReaderAsync<Foo>)>
+where
+    Foo: for<'de> DdsDeserialize<'de>,
+{
+    loop {
+        match reader.take_next_sample().await {
+            Ok(sample) => {
+                if let Ok(data) = sample.data() {
+                    return Some(((data, sample.sample_info()), reader));
+                }
+            }
+            Err(DdsError::NoData) => {
+                reader
+                    .status_condition_address()
+                    .send_actor_mail(status_condition_actor::WaitForCommunicationState {
+                        state: StatusKind::DataAvailable,
+                    })
+                    .ok()?
+                    .receive_reply()
+                    .await
+                    .await
+                    .ok()?;
+            }
+            Err(_) => return None,
+        }
+    }
+}
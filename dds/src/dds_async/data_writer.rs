@@ -11,22 +11,30 @@ use crate::{
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor, services::data_writer_service,
         },
-        status_condition::status_condition_actor::StatusConditionActor,
+        status_condition::status_condition_actor::{self, StatusConditionActor},
     },
     infrastructure::{
-        error::DdsResult,
+        error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, QosKind},
+        qos_policy::DataRepresentationId,
+        statistics::DataWriterStatistics,
         status::{
             LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
             PublicationMatchedStatus, StatusKind,
         },
-        time::{Duration, Time},
+        time::{Duration, DurationKind, Time},
     },
     runtime::actor::ActorAddress,
-    topic_definition::type_support::DdsSerialize,
+    topic_definition::type_support::{DdsDeserialize, DdsSerialize},
+};
+use std::{
+    marker::PhantomData,
+    sync::{
+        atomic::{self, AtomicU16},
+        Arc,
+    },
 };
-use std::marker::PhantomData;
 
 /// Async version of [`DataWriter`](crate::publication::data_writer::DataWriter).
 pub struct DataWriterAsync<Foo> {
@@ -34,6 +42,8 @@ pub struct DataWriterAsync<Foo> {
     status_condition_address: ActorAddress<StatusConditionActor>,
     publisher: PublisherAsync,
     topic: TopicAsync,
+    // Shared with the writer in the backend, which sets it once the writer is enabled
+    data_representation: Arc<AtomicU16>,
     phantom: PhantomData<Foo>,
 }
 
@@ -44,6 +54,7 @@ impl<Foo> Clone for DataWriterAsync<Foo> {
             status_condition_address: self.status_condition_address.clone(),
             publisher: self.publisher.clone(),
             topic: self.topic.clone(),
+            data_representation: self.data_representation.clone(),
             phantom: self.phantom,
         }
     }
@@ -55,12 +66,14 @@ impl<Foo> DataWriterAsync<Foo> {
         status_condition_address: ActorAddress<StatusConditionActor>,
         publisher: PublisherAsync,
         topic: TopicAsync,
+        data_representation: Arc<AtomicU16>,
     ) -> Self {
         Self {
             handle,
             status_condition_address,
             publisher,
             topic,
+            data_representation,
             phantom: PhantomData,
         }
     }
@@ -69,12 +82,38 @@ impl<Foo> DataWriterAsync<Foo> {
         self.publisher.participant_address()
     }
 
+    fn data_representation(&self) -> DataRepresentationId {
+        self.data_representation.load(atomic::Ordering::Acquire)
+    }
+
+    async fn wait_for_publish_rate(
+        &self,
+        delay: Duration,
+        blocked_time: &mut Duration,
+    ) -> DdsResult<()> {
+        if let DurationKind::Finite(max_blocking_time) =
+            self.get_qos().await?.reliability.max_blocking_time
+        {
+            if *blocked_time + delay > max_blocking_time {
+                return Err(DdsError::Timeout);
+            }
+        }
+        *blocked_time = *blocked_time + delay;
+        self.publisher
+            .get_participant()
+            .timer_handle()
+            .sleep(delay.into())
+            .await;
+        Ok(())
+    }
+
     pub(crate) fn change_foo_type<T>(self) -> DataWriterAsync<T> {
         DataWriterAsync {
             handle: self.handle,
             status_condition_address: self.status_condition_address,
             publisher: self.publisher,
             topic: self.topic,
+            data_representation: self.data_representation,
             phantom: PhantomData,
         }
     }
@@ -97,13 +136,23 @@ where
     }
 
     /// Async version of [`register_instance_w_timestamp`](crate::publication::data_writer::DataWriter::register_instance_w_timestamp).
-    #[tracing::instrument(skip(self, _instance))]
+    #[tracing::instrument(skip(self, instance))]
     pub async fn register_instance_w_timestamp(
         &self,
-        _instance: &Foo,
+        instance: &Foo,
         timestamp: Time,
     ) -> DdsResult<Option<InstanceHandle>> {
-        todo!()
+        let serialized_data =
+            instance.serialize_data_w_representation(self.data_representation())?;
+        self.participant_address()
+            .send_actor_mail(data_writer_service::RegisterInstance {
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+                serialized_data,
+                timestamp,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`unregister_instance`](crate::publication::data_writer::DataWriter::unregister_instance).
@@ -130,12 +179,14 @@ where
         handle: Option<InstanceHandle>,
         timestamp: Time,
     ) -> DdsResult<()> {
-        let serialized_data = instance.serialize_data()?;
+        let serialized_data =
+            instance.serialize_data_w_representation(self.data_representation())?;
         self.participant_address()
             .send_actor_mail(data_writer_service::UnregisterInstance {
                 publisher_handle: self.publisher.get_instance_handle().await,
                 data_writer_handle: self.handle,
                 serialized_data,
+                handle,
                 timestamp,
             })?
             .receive_reply()
@@ -143,19 +194,32 @@ where
     }
 
     /// Async version of [`get_key_value`](crate::publication::data_writer::DataWriter::get_key_value).
-    #[tracing::instrument(skip(self, _key_holder))]
-    pub async fn get_key_value(
-        &self,
-        _key_holder: &mut Foo,
-        _handle: InstanceHandle,
-    ) -> DdsResult<()> {
-        todo!()
+    #[tracing::instrument(skip(self, key_holder))]
+    pub async fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()>
+    where
+        Foo: for<'de> DdsDeserialize<'de>,
+    {
+        let serialized_key_holder =
+            key_holder.serialize_data_w_representation(self.data_representation())?;
+        let serialized_data = self
+            .participant_address()
+            .send_actor_mail(data_writer_service::GetKeyValue {
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+                serialized_key_holder,
+                instance_handle: handle,
+            })?
+            .receive_reply()
+            .await?;
+        *key_holder = Foo::deserialize_data(&serialized_data)?;
+        Ok(())
     }
 
     /// Async version of [`lookup_instance`](crate::publication::data_writer::DataWriter::lookup_instance).
     #[tracing::instrument(skip(self, instance))]
     pub async fn lookup_instance(&self, instance: &Foo) -> DdsResult<Option<InstanceHandle>> {
-        let serialized_data = instance.serialize_data()?;
+        let serialized_data =
+            instance.serialize_data_w_representation(self.data_representation())?;
         self.participant_address()
             .send_actor_mail(data_writer_service::LookupInstance {
                 publisher_handle: self.publisher.get_instance_handle().await,
@@ -185,8 +249,11 @@ where
         handle: Option<InstanceHandle>,
         timestamp: Time,
     ) -> DdsResult<()> {
-        let serialized_data = data.serialize_data()?;
-        self.participant_address()
+        let mut serialized_data =
+            data.serialize_data_w_representation(self.data_representation())?;
+        let mut blocked_time = Duration::new(0, 0);
+        while let Some(publish_rate_exceeded) = self
+            .participant_address()
             .send_actor_mail(data_writer_service::WriteWTimestamp {
                 participant_address: self.participant_address().clone(),
                 publisher_handle: self.publisher.get_instance_handle().await,
@@ -195,7 +262,55 @@ where
                 timestamp,
             })?
             .receive_reply()
-            .await
+            .await?
+        {
+            serialized_data = publish_rate_exceeded.data;
+            self.wait_for_publish_rate(publish_rate_exceeded.delay, &mut blocked_time)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Async version of [`write_batch`](crate::publication::data_writer::DataWriter::write_batch).
+    #[tracing::instrument(skip(self, data_list))]
+    pub async fn write_batch(&self, data_list: &[Foo]) -> DdsResult<()> {
+        let timestamp = self
+            .get_publisher()
+            .get_participant()
+            .get_current_time()
+            .await?;
+        self.write_batch_w_timestamp(data_list, timestamp).await
+    }
+
+    /// Async version of [`write_batch_w_timestamp`](crate::publication::data_writer::DataWriter::write_batch_w_timestamp).
+    #[tracing::instrument(skip(self, data_list))]
+    pub async fn write_batch_w_timestamp(
+        &self,
+        data_list: &[Foo],
+        timestamp: Time,
+    ) -> DdsResult<()> {
+        let mut serialized_data_list = data_list
+            .iter()
+            .map(|data| data.serialize_data_w_representation(self.data_representation()))
+            .collect::<DdsResult<Vec<_>>>()?;
+        let mut blocked_time = Duration::new(0, 0);
+        while let Some(publish_rate_exceeded) = self
+            .participant_address()
+            .send_actor_mail(data_writer_service::WriteBatchWTimestamp {
+                participant_address: self.participant_address().clone(),
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+                serialized_data_list,
+                timestamp,
+            })?
+            .receive_reply()
+            .await?
+        {
+            serialized_data_list = publish_rate_exceeded.data;
+            self.wait_for_publish_rate(publish_rate_exceeded.delay, &mut blocked_time)
+                .await?;
+        }
+        Ok(())
     }
 
     /// Async version of [`dispose`](crate::publication::data_writer::DataWriter::dispose).
@@ -217,9 +332,10 @@ where
         handle: Option<InstanceHandle>,
         timestamp: Time,
     ) -> DdsResult<()> {
-        let serialized_data = data.serialize_data()?;
+        let serialized_data = data.serialize_data_w_representation(self.data_representation())?;
         self.participant_address()
             .send_actor_mail(data_writer_service::DisposeWTimestamp {
+                participant_address: self.participant_address().clone(),
                 publisher_handle: self.publisher.get_instance_handle().await,
                 data_writer_handle: self.handle,
                 serialized_data,
@@ -231,12 +347,71 @@ where
 }
 
 impl<Foo> DataWriterAsync<Foo> {
+    /// Async version of [`unregister_instance_w_handle`](crate::publication::data_writer::DataWriter::unregister_instance_w_handle).
+    #[tracing::instrument(skip(self))]
+    pub async fn unregister_instance_w_handle(&self, handle: InstanceHandle) -> DdsResult<()> {
+        let timestamp = self
+            .get_publisher()
+            .get_participant()
+            .get_current_time()
+            .await?;
+        self.unregister_instance_w_handle_w_timestamp(handle, timestamp)
+            .await
+    }
+
+    /// Async version of [`unregister_instance_w_handle_w_timestamp`](crate::publication::data_writer::DataWriter::unregister_instance_w_handle_w_timestamp).
+    #[tracing::instrument(skip(self))]
+    pub async fn unregister_instance_w_handle_w_timestamp(
+        &self,
+        handle: InstanceHandle,
+        timestamp: Time,
+    ) -> DdsResult<()> {
+        self.participant_address()
+            .send_actor_mail(data_writer_service::UnregisterInstanceWHandleWTimestamp {
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+                instance_handle: handle,
+                timestamp,
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`dispose_w_handle`](crate::publication::data_writer::DataWriter::dispose_w_handle).
+    #[tracing::instrument(skip(self))]
+    pub async fn dispose_w_handle(&self, handle: InstanceHandle) -> DdsResult<()> {
+        let timestamp = self
+            .get_publisher()
+            .get_participant()
+            .get_current_time()
+            .await?;
+        self.dispose_w_handle_w_timestamp(handle, timestamp).await
+    }
+
+    /// Async version of [`dispose_w_handle_w_timestamp`](crate::publication::data_writer::DataWriter::dispose_w_handle_w_timestamp).
+    #[tracing::instrument(skip(self))]
+    pub async fn dispose_w_handle_w_timestamp(
+        &self,
+        handle: InstanceHandle,
+        timestamp: Time,
+    ) -> DdsResult<()> {
+        self.participant_address()
+            .send_actor_mail(data_writer_service::DisposeWHandleWTimestamp {
+                participant_address: self.participant_address().clone(),
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+                instance_handle: handle,
+                timestamp,
+            })?
+            .receive_reply()
+            .await
+    }
+
     /// Async version of [`wait_for_acknowledgments`](crate::publication::data_writer::DataWriter::wait_for_acknowledgments).
     #[tracing::instrument(skip(self))]
     pub async fn wait_for_acknowledgments(&self, max_wait: Duration) -> DdsResult<()> {
         self.participant_address()
             .send_actor_mail(data_writer_service::WaitForAcknowledgments {
-                participant_address: self.participant_address().clone(),
                 publisher_handle: self.publisher.get_instance_handle().await,
                 data_writer_handle: self.handle,
                 timeout: max_wait,
@@ -249,7 +424,13 @@ impl<Foo> DataWriterAsync<Foo> {
     /// Async version of [`get_liveliness_lost_status`](crate::publication::data_writer::DataWriter::get_liveliness_lost_status).
     #[tracing::instrument(skip(self))]
     pub async fn get_liveliness_lost_status(&self) -> DdsResult<LivelinessLostStatus> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_writer_service::GetLivelinessLostStatus {
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_offered_deadline_missed_status`](crate::publication::data_writer::DataWriter::get_offered_deadline_missed_status).
@@ -271,7 +452,13 @@ impl<Foo> DataWriterAsync<Foo> {
     pub async fn get_offered_incompatible_qos_status(
         &self,
     ) -> DdsResult<OfferedIncompatibleQosStatus> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_writer_service::GetOfferedIncompatibleQosStatus {
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_publication_matched_status`](crate::publication::data_writer::DataWriter::get_publication_matched_status).
@@ -286,6 +473,30 @@ impl<Foo> DataWriterAsync<Foo> {
             .await
     }
 
+    /// Async version of [`get_dropped_sample_count`](crate::publication::data_writer::DataWriter::get_dropped_sample_count).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_dropped_sample_count(&self) -> DdsResult<u64> {
+        self.participant_address()
+            .send_actor_mail(data_writer_service::GetDroppedSampleCount {
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`get_statistics`](crate::publication::data_writer::DataWriter::get_statistics).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_statistics(&self) -> DdsResult<DataWriterStatistics> {
+        self.participant_address()
+            .send_actor_mail(data_writer_service::GetStatistics {
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
+    }
+
     /// Async version of [`get_topic`](crate::publication::data_writer::DataWriter::get_topic).
     #[tracing::instrument(skip(self))]
     pub fn get_topic(&self) -> TopicAsync {
@@ -301,7 +512,26 @@ impl<Foo> DataWriterAsync<Foo> {
     /// Async version of [`assert_liveliness`](crate::publication::data_writer::DataWriter::assert_liveliness).
     #[tracing::instrument(skip(self))]
     pub async fn assert_liveliness(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(data_writer_service::AssertLiveliness {
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+                participant_address: self.participant_address().clone(),
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`flush`](crate::publication::data_writer::DataWriter::flush).
+    #[tracing::instrument(skip(self))]
+    pub async fn flush(&self) -> DdsResult<()> {
+        self.participant_address()
+            .send_actor_mail(data_writer_service::Flush {
+                publisher_handle: self.publisher.get_instance_handle().await,
+                data_writer_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_matched_subscription_data`](crate::publication::data_writer::DataWriter::get_matched_subscription_data).
@@ -369,7 +599,11 @@ impl<Foo> DataWriterAsync<Foo> {
     /// Async version of [`get_status_changes`](crate::publication::data_writer::DataWriter::get_status_changes).
     #[tracing::instrument(skip(self))]
     pub async fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
+        Ok(self
+            .status_condition_address
+            .send_actor_mail(status_condition_actor::GetStatusChanges)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`enable`](crate::publication::data_writer::DataWriter::enable).
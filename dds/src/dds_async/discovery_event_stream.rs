@@ -0,0 +1,26 @@
+use crate::{infrastructure::discovery_event::DiscoveryEvent, runtime::mpsc::MpscReceiver};
+
+/// Stream of the [`DiscoveryEvent`] of the remote entities of one kind discovered by a
+/// [`DomainParticipantAsync`](crate::dds_async::domain_participant::DomainParticipantAsync).
+pub struct DiscoveryEventStream<T> {
+    event_receiver: MpscReceiver<DiscoveryEvent<T>>,
+}
+
+impl<T> DiscoveryEventStream<T> {
+    pub(crate) fn new(event_receiver: MpscReceiver<DiscoveryEvent<T>>) -> Self {
+        Self { event_receiver }
+    }
+
+    /// Wait for the next discovery event. This operation returns [`None`] once the
+    /// participant that created the stream has been deleted.
+    pub async fn next(&mut self) -> Option<DiscoveryEvent<T>> {
+        self.event_receiver.recv().await
+    }
+}
+
+impl<T> Drop for DiscoveryEventStream<T> {
+    fn drop(&mut self) {
+        // Closing the channel lets the participant stop sending events to this stream
+        self.event_receiver.close();
+    }
+}
@@ -1,7 +1,8 @@
 use super::{
-    condition::StatusConditionAsync, domain_participant_listener::DomainParticipantListenerAsync,
-    publisher::PublisherAsync, publisher_listener::PublisherListenerAsync,
-    subscriber::SubscriberAsync, subscriber_listener::SubscriberListenerAsync, topic::TopicAsync,
+    condition::StatusConditionAsync, discovery_event_stream::DiscoveryEventStream,
+    domain_participant_listener::DomainParticipantListenerAsync, publisher::PublisherAsync,
+    publisher_listener::PublisherListenerAsync, subscriber::SubscriberAsync,
+    subscriber_listener::SubscriberListenerAsync, topic::TopicAsync,
     topic_listener::TopicListenerAsync,
 };
 use crate::{
@@ -9,18 +10,19 @@ use crate::{
     domain::domain_participant_factory::DomainId,
     implementation::{
         domain_participant_backend::{
-            domain_participant_actor::DomainParticipantActor, services::domain_participant_service,
+            domain_participant_actor::DomainParticipantActor,
+            services::{discovery_service, domain_participant_service},
         },
-        status_condition::status_condition_actor::StatusConditionActor,
+        status_condition::status_condition_actor::{self, StatusConditionActor},
     },
     infrastructure::{
         error::{DdsError, DdsResult},
-        instance::InstanceHandle,
+        instance::{EntityKind, InstanceHandle},
         qos::{DomainParticipantQos, PublisherQos, QosKind, SubscriberQos, TopicQos},
         status::StatusKind,
         time::{Duration, Time},
     },
-    runtime::{actor::ActorAddress, timer::TimerHandle},
+    runtime::{actor::ActorAddress, mpsc::mpsc_channel, timer::TimerHandle},
     topic_definition::type_support::TypeSupport,
     xtypes::dynamic_type::DynamicType,
 };
@@ -35,6 +37,7 @@ pub struct DomainParticipantAsync {
     domain_id: DomainId,
     handle: InstanceHandle,
     timer_handle: TimerHandle,
+    max_sequence_length: usize,
 }
 
 impl DomainParticipantAsync {
@@ -45,6 +48,7 @@ impl DomainParticipantAsync {
         domain_id: DomainId,
         handle: InstanceHandle,
         timer_handle: TimerHandle,
+        max_sequence_length: usize,
     ) -> Self {
         Self {
             participant_address,
@@ -53,12 +57,21 @@ impl DomainParticipantAsync {
             domain_id,
             handle,
             timer_handle,
+            max_sequence_length,
         }
     }
 
     pub(crate) fn participant_address(&self) -> &ActorAddress<DomainParticipantActor> {
         &self.participant_address
     }
+
+    pub(crate) fn timer_handle(&self) -> &TimerHandle {
+        &self.timer_handle
+    }
+
+    pub(crate) fn max_sequence_length(&self) -> usize {
+        self.max_sequence_length
+    }
 }
 
 impl DomainParticipantAsync {
@@ -150,7 +163,7 @@ impl DomainParticipantAsync {
             .await
     }
 
-    #[doc(hidden)]
+    /// Async version of [`create_dynamic_topic`](crate::domain::domain_participant::DomainParticipant::create_dynamic_topic).
     #[tracing::instrument(skip(self, a_listener, dynamic_type_representation))]
     pub async fn create_dynamic_topic(
         &self,
@@ -284,7 +297,13 @@ impl DomainParticipantAsync {
     /// Async version of [`ignore_topic`](crate::domain::domain_participant::DomainParticipant::ignore_topic).
     #[tracing::instrument(skip(self))]
     pub async fn ignore_topic(&self, handle: InstanceHandle) -> DdsResult<()> {
-        todo!()
+        self.participant_address
+            .send_actor_mail(domain_participant_service::IgnoreTopic {
+                handle,
+                participant_address: self.participant_address.clone(),
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`ignore_publication`](crate::domain::domain_participant::DomainParticipant::ignore_publication).
@@ -325,7 +344,21 @@ impl DomainParticipantAsync {
     /// Async version of [`assert_liveliness`](crate::domain::domain_participant::DomainParticipant::assert_liveliness).
     #[tracing::instrument(skip(self))]
     pub async fn assert_liveliness(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address
+            .send_actor_mail(domain_participant_service::AssertLiveliness {
+                participant_address: self.participant_address.clone(),
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`announce`](crate::domain::domain_participant::DomainParticipant::announce).
+    #[tracing::instrument(skip(self))]
+    pub async fn announce(&self) -> DdsResult<()> {
+        self.participant_address
+            .send_actor_mail(discovery_service::AnnounceParticipantOnDemand)?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`set_default_publisher_qos`](crate::domain::domain_participant::DomainParticipant::set_default_publisher_qos).
@@ -405,6 +438,25 @@ impl DomainParticipantAsync {
             .await
     }
 
+    /// This operation returns a stream of the changes of the participants discovered by this
+    /// participant. The participants already discovered when the stream is created are first
+    /// reported as [`DiscoveryEvent::Added`](crate::infrastructure::discovery_event::DiscoveryEvent::Added),
+    /// followed by the participants which are added, updated or removed afterwards. This allows
+    /// tracking the remote participants without polling [`Self::get_discovered_participants`].
+    #[tracing::instrument(skip(self))]
+    pub async fn discovered_participants_stream(
+        &self,
+    ) -> DdsResult<DiscoveryEventStream<ParticipantBuiltinTopicData>> {
+        let (event_sender, event_receiver) = mpsc_channel();
+        self.participant_address
+            .send_actor_mail(
+                domain_participant_service::AddDiscoveredParticipantEventSender { event_sender },
+            )?
+            .receive_reply()
+            .await;
+        Ok(DiscoveryEventStream::new(event_receiver))
+    }
+
     /// Async version of [`get_discovered_topics`](crate::domain::domain_participant::DomainParticipant::get_discovered_topics).
     #[tracing::instrument(skip(self))]
     pub async fn get_discovered_topics(&self) -> DdsResult<Vec<InstanceHandle>> {
@@ -426,10 +478,71 @@ impl DomainParticipantAsync {
             .await
     }
 
+    /// This operation returns a stream of the changes of the topics discovered by this
+    /// participant. The topics already discovered when the stream is created are first
+    /// reported as [`DiscoveryEvent::Added`](crate::infrastructure::discovery_event::DiscoveryEvent::Added),
+    /// followed by the topics which are added, updated or removed afterwards. This allows
+    /// tracking the remote topics without polling [`Self::get_discovered_topics`].
+    #[tracing::instrument(skip(self))]
+    pub async fn discovered_topics_stream(
+        &self,
+    ) -> DdsResult<DiscoveryEventStream<TopicBuiltinTopicData>> {
+        let (event_sender, event_receiver) = mpsc_channel();
+        self.participant_address
+            .send_actor_mail(domain_participant_service::AddDiscoveredTopicEventSender {
+                event_sender,
+            })?
+            .receive_reply()
+            .await;
+        Ok(DiscoveryEventStream::new(event_receiver))
+    }
+
     /// Async version of [`contains_entity`](crate::domain::domain_participant::DomainParticipant::contains_entity).
     #[tracing::instrument(skip(self))]
-    pub async fn contains_entity(&self, _a_handle: InstanceHandle) -> DdsResult<bool> {
-        todo!()
+    pub async fn contains_entity(&self, a_handle: InstanceHandle) -> DdsResult<bool> {
+        self.participant_address
+            .send_actor_mail(domain_participant_service::ContainsEntity { a_handle })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`get_contained_entities`](crate::domain::domain_participant::DomainParticipant::get_contained_entities).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_contained_entities(&self) -> DdsResult<Vec<(InstanceHandle, EntityKind)>> {
+        self.participant_address
+            .send_actor_mail(domain_participant_service::GetContainedEntities)?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`get_publishers`](crate::domain::domain_participant::DomainParticipant::get_publishers).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_publishers(&self) -> DdsResult<Vec<PublisherAsync>> {
+        Ok(self
+            .participant_address
+            .send_actor_mail(domain_participant_service::GetPublishers)?
+            .receive_reply()
+            .await?
+            .into_iter()
+            .map(|(handle, status_condition_address)| {
+                PublisherAsync::new(handle, status_condition_address, self.clone())
+            })
+            .collect())
+    }
+
+    /// Async version of [`get_subscribers`](crate::domain::domain_participant::DomainParticipant::get_subscribers).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_subscribers(&self) -> DdsResult<Vec<SubscriberAsync>> {
+        Ok(self
+            .participant_address
+            .send_actor_mail(domain_participant_service::GetSubscribers)?
+            .receive_reply()
+            .await?
+            .into_iter()
+            .map(|(handle, status_condition_address)| {
+                SubscriberAsync::new(handle, status_condition_address, self.clone())
+            })
+            .collect())
     }
 
     /// Async version of [`get_current_time`](crate::domain::domain_participant::DomainParticipant::get_current_time).
@@ -490,7 +603,11 @@ impl DomainParticipantAsync {
     /// Async version of [`get_status_changes`](crate::domain::domain_participant::DomainParticipant::get_status_changes).
     #[tracing::instrument(skip(self))]
     pub async fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
+        Ok(self
+            .status_condition_address
+            .send_actor_mail(status_condition_actor::GetStatusChanges)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`enable`](crate::domain::domain_participant::DomainParticipant::enable).
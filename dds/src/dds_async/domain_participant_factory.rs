@@ -1,4 +1,4 @@
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 use super::{
     domain_participant::DomainParticipantAsync,
@@ -18,20 +18,82 @@ use crate::{
         qos::{DomainParticipantFactoryQos, DomainParticipantQos, QosKind},
         status::StatusKind,
     },
-    runtime::{actor::Actor, executor::Executor, timer::TimerDriver},
+    runtime::{
+        actor::Actor,
+        executor::{Executor, ExecutorPool, RuntimeHandle, Spawner},
+        timer::TimerDriver,
+    },
 };
 
+#[cfg(feature = "xml")]
+use crate::infrastructure::qos_profile::QosProfile;
+#[cfg(feature = "xml")]
+use std::path::Path;
+
 /// Async version of [`DomainParticipantFactory`](crate::domain::domain_participant_factory::DomainParticipantFactory).
-/// Unlike the sync version, the [`DomainParticipantFactoryAsync`] is not a singleton and can be created by means of
-/// a constructor by passing a handle to a [`Tokio`](https://crates.io/crates/tokio) runtime. This allows the factory
-/// to spin tasks on an existing runtime which can be shared with other things outside Dust DDS.
+/// Besides the singleton returned by [`DomainParticipantFactoryAsync::get_instance`], independent factories can be
+/// created with [`DomainParticipantFactoryAsync::new`]. Each factory has its own configuration, default participant QoS
+/// and runtime.
 pub struct DomainParticipantFactoryAsync {
     _executor: Executor,
     timer_driver: TimerDriver,
     domain_participant_factory_actor: Actor<DomainParticipantFactoryActor>,
 }
 
+impl Default for DomainParticipantFactoryAsync {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DomainParticipantFactoryAsync {
+    /// Create a new [`DomainParticipantFactoryAsync`] independent from the singleton and from any other factory. The
+    /// participants created by the factory are deleted by means of the same factory.
+    pub fn new() -> Self {
+        Self::new_with_runtime_handle(RuntimeHandle::Threads)
+    }
+
+    /// Create a new [`DomainParticipantFactoryAsync`] whose tasks, including the ones of the participants it creates,
+    /// are spawned on the given [`Tokio`](https://crates.io/crates/tokio) runtime instead of on threads created by
+    /// Dust DDS. This lets the application control the number of threads and the shutdown order. The listeners and
+    /// the network receive threads still run on their own threads.
+    #[cfg(feature = "tokio")]
+    pub fn new_with_runtime(runtime: tokio::runtime::Handle) -> Self {
+        Self::new_with_spawner(runtime)
+    }
+
+    /// Create a new [`DomainParticipantFactoryAsync`] whose tasks, including the ones of the participants it creates,
+    /// are spawned by the given [`Spawner`] on an async runtime such as [smol](https://crates.io/crates/smol) or
+    /// [async-std](https://crates.io/crates/async-std) instead of on threads created by Dust DDS. The listeners and
+    /// the network receive threads still run on their own threads.
+    pub fn new_with_spawner(spawner: impl Spawner) -> Self {
+        Self::new_with_runtime_handle(RuntimeHandle::Spawner(Arc::new(spawner)))
+    }
+
+    /// Create a new [`DomainParticipantFactoryAsync`] whose tasks, including the ones of the participants it creates,
+    /// run on a fixed pool of `number_of_threads` threads named `thread_name` instead of on a thread per participant.
+    /// This bounds the number of threads used by Dust DDS independently of the number of participants. A value of
+    /// zero is treated as one thread. The listeners and the network receive threads still run on their own threads.
+    pub fn new_with_thread_pool(number_of_threads: usize, thread_name: &str) -> Self {
+        Self::new_with_runtime_handle(RuntimeHandle::ThreadPool(Arc::new(
+            ExecutorPool::with_thread_name(number_of_threads, thread_name),
+        )))
+    }
+
+    fn new_with_runtime_handle(runtime: RuntimeHandle) -> Self {
+        let executor = runtime.executor();
+        let timer_driver = TimerDriver::new();
+        let domain_participant_factory_actor = Actor::spawn(
+            DomainParticipantFactoryActor::new(runtime),
+            &executor.handle(),
+        );
+        Self {
+            _executor: executor,
+            domain_participant_factory_actor,
+            timer_driver,
+        }
+    }
+
     /// Async version of [`create_participant`](crate::domain::domain_participant_factory::DomainParticipantFactory::create_participant).
     pub async fn create_participant(
         &self,
@@ -46,6 +108,7 @@ impl DomainParticipantFactoryAsync {
             participant_handle,
             participant_status_condition_address,
             builtin_subscriber_status_condition_address,
+            max_sequence_length,
         ) = self
             .domain_participant_factory_actor
             .send_actor_mail(domain_participant_factory_actor::CreateParticipant {
@@ -53,7 +116,7 @@ impl DomainParticipantFactoryAsync {
                 qos,
                 listener: a_listener,
                 status_kind,
-            })
+            })?
             .receive_reply()
             .await?;
 
@@ -64,6 +127,7 @@ impl DomainParticipantFactoryAsync {
             domain_id,
             participant_handle,
             self.timer_driver.handle(),
+            max_sequence_length,
         );
 
         Ok(domain_participant)
@@ -81,11 +145,11 @@ impl DomainParticipantFactoryAsync {
 
             let deleted_participant = self
                 .domain_participant_factory_actor
-                .send_actor_mail(domain_participant_factory_actor::DeleteParticipant { handle })
+                .send_actor_mail(domain_participant_factory_actor::DeleteParticipant { handle })?
                 .receive_reply()
                 .await?;
             deleted_participant
-                .send_actor_mail(discovery_service::AnnounceDeletedParticipant)
+                .send_actor_mail(discovery_service::AnnounceDeletedParticipant)?
                 .receive_reply()
                 .await?;
             deleted_participant.stop().await;
@@ -101,18 +165,14 @@ impl DomainParticipantFactoryAsync {
     /// times without side-effects and it will return the same [`DomainParticipantFactoryAsync`] instance.
     #[tracing::instrument]
     pub fn get_instance() -> &'static Self {
-        static PARTICIPANT_FACTORY_ASYNC: OnceLock<DomainParticipantFactoryAsync> = OnceLock::new();
-        PARTICIPANT_FACTORY_ASYNC.get_or_init(|| {
-            let executor = Executor::new();
-            let timer_driver = TimerDriver::new();
-            let domain_participant_factory_actor =
-                Actor::spawn(DomainParticipantFactoryActor::new(), &executor.handle());
-            Self {
-                _executor: executor,
-                domain_participant_factory_actor,
-                timer_driver,
-            }
-        })
+        Self::shared_instance()
+    }
+
+    // The singleton is shared with the sync DomainParticipantFactory singleton
+    pub(crate) fn shared_instance() -> &'static Arc<Self> {
+        static PARTICIPANT_FACTORY_ASYNC: OnceLock<Arc<DomainParticipantFactoryAsync>> =
+            OnceLock::new();
+        PARTICIPANT_FACTORY_ASYNC.get_or_init(|| Arc::new(Self::new()))
     }
 
     /// Async version of [`lookup_participant`](crate::domain::domain_participant_factory::DomainParticipantFactory::lookup_participant).
@@ -129,7 +189,7 @@ impl DomainParticipantFactoryAsync {
         qos: QosKind<DomainParticipantQos>,
     ) -> DdsResult<()> {
         self.domain_participant_factory_actor
-            .send_actor_mail(domain_participant_factory_actor::SetDefaultParticipantQos { qos })
+            .send_actor_mail(domain_participant_factory_actor::SetDefaultParticipantQos { qos })?
             .receive_reply()
             .await
     }
@@ -138,7 +198,7 @@ impl DomainParticipantFactoryAsync {
     pub async fn get_default_participant_qos(&self) -> DdsResult<DomainParticipantQos> {
         Ok(self
             .domain_participant_factory_actor
-            .send_actor_mail(domain_participant_factory_actor::GetDefaultParticipantQos)
+            .send_actor_mail(domain_participant_factory_actor::GetDefaultParticipantQos)?
             .receive_reply()
             .await)
     }
@@ -146,7 +206,7 @@ impl DomainParticipantFactoryAsync {
     /// Async version of [`set_qos`](crate::domain::domain_participant_factory::DomainParticipantFactory::set_qos).
     pub async fn set_qos(&self, qos: QosKind<DomainParticipantFactoryQos>) -> DdsResult<()> {
         self.domain_participant_factory_actor
-            .send_actor_mail(domain_participant_factory_actor::SetQos { qos })
+            .send_actor_mail(domain_participant_factory_actor::SetQos { qos })?
             .receive_reply()
             .await
     }
@@ -155,15 +215,44 @@ impl DomainParticipantFactoryAsync {
     pub async fn get_qos(&self) -> DdsResult<DomainParticipantFactoryQos> {
         Ok(self
             .domain_participant_factory_actor
-            .send_actor_mail(domain_participant_factory_actor::GetQos)
+            .send_actor_mail(domain_participant_factory_actor::GetQos)?
             .receive_reply()
             .await)
     }
 
+    /// Async version of [`load_qos_profiles`](crate::domain::domain_participant_factory::DomainParticipantFactory::load_qos_profiles).
+    #[cfg(feature = "xml")]
+    pub async fn load_qos_profiles(&self, document: &str) -> DdsResult<()> {
+        self.domain_participant_factory_actor
+            .send_actor_mail(domain_participant_factory_actor::LoadQosProfiles {
+                document: document.to_string(),
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`load_qos_profiles_from_file`](crate::domain::domain_participant_factory::DomainParticipantFactory::load_qos_profiles_from_file).
+    #[cfg(feature = "xml")]
+    pub async fn load_qos_profiles_from_file(&self, path: impl AsRef<Path>) -> DdsResult<()> {
+        let document = std::fs::read_to_string(path)?;
+        self.load_qos_profiles(&document).await
+    }
+
+    /// Async version of [`get_qos_from_profile`](crate::domain::domain_participant_factory::DomainParticipantFactory::get_qos_from_profile).
+    #[cfg(feature = "xml")]
+    pub async fn get_qos_from_profile(&self, profile_name: &str) -> DdsResult<QosProfile> {
+        self.domain_participant_factory_actor
+            .send_actor_mail(domain_participant_factory_actor::GetQosFromProfile {
+                profile_name: profile_name.to_string(),
+            })?
+            .receive_reply()
+            .await
+    }
+
     /// Async version of [`set_configuration`](crate::domain::domain_participant_factory::DomainParticipantFactory::set_configuration).
     pub async fn set_configuration(&self, configuration: DustDdsConfiguration) -> DdsResult<()> {
         self.domain_participant_factory_actor
-            .send_actor_mail(domain_participant_factory_actor::SetConfiguration { configuration })
+            .send_actor_mail(domain_participant_factory_actor::SetConfiguration { configuration })?
             .receive_reply()
             .await;
         Ok(())
@@ -173,7 +262,7 @@ impl DomainParticipantFactoryAsync {
     pub async fn get_configuration(&self) -> DdsResult<DustDdsConfiguration> {
         Ok(self
             .domain_participant_factory_actor
-            .send_actor_mail(domain_participant_factory_actor::GetConfiguration)
+            .send_actor_mail(domain_participant_factory_actor::GetConfiguration)?
             .receive_reply()
             .await)
     }
@@ -4,10 +4,14 @@ pub mod condition;
 pub mod data_reader;
 /// Classes related to the async data reader listener.
 pub mod data_reader_listener;
+/// Classes related to the async data reader sample stream.
+pub mod data_reader_stream;
 /// Classes related to the async data writer.
 pub mod data_writer;
 /// Classes related to the async data writer listener.
 pub mod data_writer_listener;
+/// Classes related to the async discovery change streams.
+pub mod discovery_event_stream;
 /// Classes related to the async domain participant.
 pub mod domain_participant;
 /// Classes related to the async domain participant factory.
@@ -18,6 +22,8 @@ pub mod domain_participant_listener;
 pub mod publisher;
 /// Classes related to the async publisher listener.
 pub mod publisher_listener;
+/// Classes related to the async runtime on which the tasks of the entities run.
+pub mod runtime;
 /// Classes related to the async subscriber.
 pub mod subscriber;
 /// Classes related to the async subscriber listener.
@@ -7,16 +7,17 @@ use crate::{
     implementation::{
         any_data_writer_listener::AnyDataWriterListener,
         domain_participant_backend::{
-            domain_participant_actor::DomainParticipantActor, services::publisher_service,
+            domain_participant_actor::DomainParticipantActor,
+            services::{data_writer_service, publisher_service},
         },
-        status_condition::status_condition_actor::StatusConditionActor,
+        status_condition::status_condition_actor::{self, StatusConditionActor},
     },
     infrastructure::{
         error::DdsResult,
         instance::InstanceHandle,
         qos::{DataWriterQos, PublisherQos, QosKind, TopicQos},
         status::StatusKind,
-        time::Duration,
+        time::{Duration, DurationKind},
     },
     runtime::actor::ActorAddress,
 };
@@ -62,7 +63,7 @@ impl PublisherAsync {
     {
         let topic_name = a_topic.get_name();
         let listener = a_listener.map::<Box<dyn AnyDataWriterListener + Send>, _>(|b| Box::new(b));
-        let (guid, writer_status_condition_address) = self
+        let (guid, writer_status_condition_address, data_representation) = self
             .participant_address()
             .send_actor_mail(publisher_service::CreateDataWriter {
                 publisher_handle: self.handle,
@@ -80,6 +81,7 @@ impl PublisherAsync {
             writer_status_condition_address,
             self.clone(),
             a_topic.clone(),
+            data_representation,
         ))
     }
 
@@ -89,10 +91,29 @@ impl PublisherAsync {
         &self,
         a_datawriter: &DataWriterAsync<Foo>,
     ) -> DdsResult<()> {
+        let datawriter_handle = a_datawriter.get_instance_handle().await;
+        // The instances are unregistered while the writer still exists so that the unregistrations
+        // can be repaired until the readers acknowledge them or the max blocking time elapses
+        self.participant_address()
+            .send_actor_mail(data_writer_service::UnregisterAllInstances {
+                publisher_handle: self.handle,
+                data_writer_handle: datawriter_handle,
+            })?
+            .receive_reply()
+            .await?;
+        if let DurationKind::Finite(max_blocking_time) =
+            a_datawriter.get_qos().await?.reliability.max_blocking_time
+        {
+            a_datawriter
+                .wait_for_acknowledgments(max_blocking_time)
+                .await
+                .ok();
+        }
+
         self.participant_address()
             .send_actor_mail(publisher_service::DeleteDataWriter {
                 publisher_handle: self.handle,
-                datawriter_handle: a_datawriter.get_instance_handle().await,
+                datawriter_handle,
                 participant_address: self.participant_address().clone(),
             })?
             .receive_reply()
@@ -177,10 +198,23 @@ impl PublisherAsync {
     #[tracing::instrument(skip(self))]
     pub async fn copy_from_topic_qos(
         &self,
-        _a_datawriter_qos: &mut DataWriterQos,
-        _a_topic_qos: &TopicQos,
+        a_datawriter_qos: &mut DataWriterQos,
+        a_topic_qos: &TopicQos,
     ) -> DdsResult<()> {
-        todo!()
+        a_datawriter_qos.durability = a_topic_qos.durability.clone();
+        a_datawriter_qos.durability_service = a_topic_qos.durability_service.clone();
+        a_datawriter_qos.deadline = a_topic_qos.deadline.clone();
+        a_datawriter_qos.latency_budget = a_topic_qos.latency_budget.clone();
+        a_datawriter_qos.liveliness = a_topic_qos.liveliness.clone();
+        a_datawriter_qos.reliability = a_topic_qos.reliability.clone();
+        a_datawriter_qos.destination_order = a_topic_qos.destination_order.clone();
+        a_datawriter_qos.history = a_topic_qos.history.clone();
+        a_datawriter_qos.resource_limits = a_topic_qos.resource_limits.clone();
+        a_datawriter_qos.transport_priority = a_topic_qos.transport_priority.clone();
+        a_datawriter_qos.lifespan = a_topic_qos.lifespan.clone();
+        a_datawriter_qos.ownership = a_topic_qos.ownership.clone();
+        a_datawriter_qos.representation = a_topic_qos.representation.clone();
+        Ok(())
     }
 }
 
@@ -192,6 +226,7 @@ impl PublisherAsync {
             .send_actor_mail(publisher_service::SetQos {
                 publisher_handle: self.handle,
                 qos,
+                participant_address: self.participant_address().clone(),
             })?
             .receive_reply()
             .await
@@ -234,13 +269,23 @@ impl PublisherAsync {
     /// Async version of [`get_status_changes`](crate::publication::publisher::Publisher::get_status_changes).
     #[tracing::instrument(skip(self))]
     pub async fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
+        Ok(self
+            .status_condition_address
+            .send_actor_mail(status_condition_actor::GetStatusChanges)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`enable`](crate::publication::publisher::Publisher::enable).
     #[tracing::instrument(skip(self))]
     pub async fn enable(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(publisher_service::Enable {
+                publisher_handle: self.handle,
+                participant_address: self.participant_address().clone(),
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_instance_handle`](crate::publication::publisher::Publisher::get_instance_handle).
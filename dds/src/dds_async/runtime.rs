@@ -0,0 +1,5 @@
+#[cfg(feature = "async-std")]
+pub use crate::runtime::executor::AsyncStdSpawner;
+#[cfg(feature = "smol")]
+pub use crate::runtime::executor::SmolSpawner;
+pub use crate::runtime::executor::Spawner;
@@ -9,7 +9,7 @@ use crate::{
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor, services::subscriber_service,
         },
-        status_condition::status_condition_actor::StatusConditionActor,
+        status_condition::status_condition_actor::{self, StatusConditionActor},
     },
     infrastructure::{
         error::{DdsError, DdsResult},
@@ -18,6 +18,7 @@ use crate::{
         status::{SampleLostStatus, StatusKind},
     },
     runtime::actor::ActorAddress,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
 };
 
 /// Async version of [`Subscriber`](crate::subscription::subscriber::Subscriber).
@@ -44,6 +45,10 @@ impl SubscriberAsync {
     pub(crate) fn participant_address(&self) -> &ActorAddress<DomainParticipantActor> {
         self.participant.participant_address()
     }
+
+    pub(crate) fn max_sequence_length(&self) -> usize {
+        self.participant.max_sequence_length()
+    }
 }
 
 impl SubscriberAsync {
@@ -127,10 +132,74 @@ impl SubscriberAsync {
         }
     }
 
+    /// Async version of [`begin_access`](crate::subscription::subscriber::Subscriber::begin_access).
+    #[tracing::instrument(skip(self))]
+    pub async fn begin_access(&self) -> DdsResult<()> {
+        self.participant_address()
+            .send_actor_mail(subscriber_service::BeginAccess {
+                subscriber_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`end_access`](crate::subscription::subscriber::Subscriber::end_access).
+    #[tracing::instrument(skip(self))]
+    pub async fn end_access(&self) -> DdsResult<()> {
+        self.participant_address()
+            .send_actor_mail(subscriber_service::EndAccess {
+                subscriber_handle: self.handle,
+                participant_address: self.participant_address().clone(),
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`get_datareaders`](crate::subscription::subscriber::Subscriber::get_datareaders).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_datareaders<Foo>(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<Vec<DataReaderAsync<Foo>>> {
+        let data_reader_list = self
+            .participant_address()
+            .send_actor_mail(subscriber_service::GetDataReaders {
+                subscriber_handle: self.handle,
+                sample_states: sample_states.to_vec(),
+                view_states: view_states.to_vec(),
+                instance_states: instance_states.to_vec(),
+            })?
+            .receive_reply()
+            .await?;
+        let mut data_reader_async_list = Vec::with_capacity(data_reader_list.len());
+        for (reader_handle, reader_status_condition_address, topic_name) in data_reader_list {
+            let topic = self
+                .participant
+                .lookup_topicdescription(&topic_name)
+                .await?
+                .ok_or(DdsError::AlreadyDeleted)?;
+            data_reader_async_list.push(DataReaderAsync::new(
+                reader_handle,
+                reader_status_condition_address,
+                self.clone(),
+                topic,
+            ));
+        }
+        Ok(data_reader_async_list)
+    }
+
     /// Async version of [`notify_datareaders`](crate::subscription::subscriber::Subscriber::notify_datareaders).
     #[tracing::instrument(skip(self))]
     pub async fn notify_datareaders(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(subscriber_service::NotifyDataReaders {
+                subscriber_handle: self.handle,
+                participant_address: self.participant_address().clone(),
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_participant`](crate::subscription::subscriber::Subscriber::get_participant).
@@ -142,7 +211,12 @@ impl SubscriberAsync {
     /// Async version of [`get_sample_lost_status`](crate::subscription::subscriber::Subscriber::get_sample_lost_status).
     #[tracing::instrument(skip(self))]
     pub async fn get_sample_lost_status(&self) -> DdsResult<SampleLostStatus> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(subscriber_service::GetSampleLostStatus {
+                subscriber_handle: self.handle,
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`delete_contained_entities`](crate::subscription::subscriber::Subscriber::delete_contained_entities).
@@ -177,10 +251,20 @@ impl SubscriberAsync {
     /// Async version of [`copy_from_topic_qos`](crate::subscription::subscriber::Subscriber::copy_from_topic_qos).
     #[tracing::instrument]
     pub async fn copy_from_topic_qos(
-        _a_datareader_qos: &mut DataReaderQos,
-        _a_topic_qos: &TopicQos,
+        a_datareader_qos: &mut DataReaderQos,
+        a_topic_qos: &TopicQos,
     ) -> DdsResult<()> {
-        todo!()
+        a_datareader_qos.durability = a_topic_qos.durability.clone();
+        a_datareader_qos.deadline = a_topic_qos.deadline.clone();
+        a_datareader_qos.latency_budget = a_topic_qos.latency_budget.clone();
+        a_datareader_qos.liveliness = a_topic_qos.liveliness.clone();
+        a_datareader_qos.reliability = a_topic_qos.reliability.clone();
+        a_datareader_qos.destination_order = a_topic_qos.destination_order.clone();
+        a_datareader_qos.history = a_topic_qos.history.clone();
+        a_datareader_qos.resource_limits = a_topic_qos.resource_limits.clone();
+        a_datareader_qos.ownership = a_topic_qos.ownership.clone();
+        a_datareader_qos.representation = a_topic_qos.representation.clone();
+        Ok(())
     }
 
     /// Async version of [`set_qos`](crate::subscription::subscriber::Subscriber::set_qos).
@@ -190,6 +274,7 @@ impl SubscriberAsync {
             .send_actor_mail(subscriber_service::SetQos {
                 subscriber_handle: self.handle,
                 qos,
+                participant_address: self.participant_address().clone(),
             })?
             .receive_reply()
             .await
@@ -232,13 +317,23 @@ impl SubscriberAsync {
     /// Async version of [`get_status_changes`](crate::subscription::subscriber::Subscriber::get_status_changes).
     #[tracing::instrument(skip(self))]
     pub async fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
+        Ok(self
+            .status_condition_address
+            .send_actor_mail(status_condition_actor::GetStatusChanges)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`enable`](crate::subscription::subscriber::Subscriber::enable).
     #[tracing::instrument(skip(self))]
     pub async fn enable(&self) -> DdsResult<()> {
-        todo!()
+        self.participant_address()
+            .send_actor_mail(subscriber_service::Enable {
+                subscriber_handle: self.handle,
+                participant_address: self.participant_address().clone(),
+            })?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`get_instance_handle`](crate::subscription::subscriber::Subscriber::get_instance_handle).
@@ -5,7 +5,7 @@ use super::{
 use crate::{
     implementation::{
         domain_participant_backend::services::topic_service,
-        status_condition::status_condition_actor::StatusConditionActor,
+        status_condition::status_condition_actor::{self, StatusConditionActor},
     },
     infrastructure::{
         error::DdsResult,
@@ -89,6 +89,7 @@ impl TopicAsync {
             .send_actor_mail(topic_service::SetQos {
                 topic_name: self.topic_name.clone(),
                 topic_qos: qos,
+                participant_address: self.participant.participant_address().clone(),
             })?
             .receive_reply()
             .await
@@ -115,7 +116,11 @@ impl TopicAsync {
     /// Async version of [`get_status_changes`](crate::topic_definition::topic::Topic::get_status_changes).
     #[tracing::instrument(skip(self))]
     pub async fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
+        Ok(self
+            .status_condition_address
+            .send_actor_mail(status_condition_actor::GetStatusChanges)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`enable`](crate::topic_definition::topic::Topic::enable).
@@ -3,13 +3,17 @@ use crate::infrastructure::{
     time::Duration,
 };
 
-use super::condition::StatusConditionAsync;
+use super::condition::{GuardConditionAsync, ReadConditionAsync, StatusConditionAsync};
 
 /// Async version of [`Condition`](crate::infrastructure::wait_set::Condition).
 #[derive(Clone)]
 pub enum ConditionAsync {
     /// Status condition variant
     StatusCondition(StatusConditionAsync),
+    /// Read condition variant
+    ReadCondition(ReadConditionAsync),
+    /// Guard condition variant
+    GuardCondition(GuardConditionAsync),
 }
 
 impl ConditionAsync {
@@ -18,6 +22,8 @@ impl ConditionAsync {
     pub async fn get_trigger_value(&self) -> DdsResult<bool> {
         match self {
             ConditionAsync::StatusCondition(c) => c.get_trigger_value().await,
+            ConditionAsync::ReadCondition(c) => c.get_trigger_value().await,
+            ConditionAsync::GuardCondition(c) => c.get_trigger_value().await,
         }
     }
 }
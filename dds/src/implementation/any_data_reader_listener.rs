@@ -1,8 +1,8 @@
 use crate::{
     dds_async::{data_reader::DataReaderAsync, data_reader_listener::DataReaderListenerAsync},
     infrastructure::status::{
-        RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleRejectedStatus,
-        SubscriptionMatchedStatus,
+        LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
+        SampleLostStatus, SampleRejectedStatus, SubscriptionMatchedStatus,
     },
     runtime::executor::block_on,
 };
@@ -20,6 +20,14 @@ pub trait AnyDataReaderListener: Send {
         status: SampleRejectedStatus,
     );
 
+    fn trigger_on_sample_lost(&mut self, the_reader: DataReaderAsync<()>, status: SampleLostStatus);
+
+    fn trigger_on_liveliness_changed(
+        &mut self,
+        the_reader: DataReaderAsync<()>,
+        status: LivelinessChangedStatus,
+    );
+
     fn trigger_on_subscription_matched(
         &mut self,
         the_reader: DataReaderAsync<()>,
@@ -56,6 +64,22 @@ where
         block_on(self.on_sample_rejected(the_reader.change_foo_type(), status))
     }
 
+    fn trigger_on_sample_lost(
+        &mut self,
+        the_reader: DataReaderAsync<()>,
+        status: SampleLostStatus,
+    ) {
+        block_on(self.on_sample_lost(the_reader.change_foo_type(), status))
+    }
+
+    fn trigger_on_liveliness_changed(
+        &mut self,
+        the_reader: DataReaderAsync<()>,
+        status: LivelinessChangedStatus,
+    ) {
+        block_on(self.on_liveliness_changed(the_reader.change_foo_type(), status))
+    }
+
     fn trigger_on_subscription_matched(
         &mut self,
         the_reader: DataReaderAsync<()>,
@@ -1,11 +1,12 @@
 use super::{
     parameter_id_values::{
-        DEFAULT_EXPECTS_INLINE_QOS, PID_DATA_REPRESENTATION, PID_DEADLINE, PID_DESTINATION_ORDER,
-        PID_DURABILITY, PID_ENDPOINT_GUID, PID_EXPECTS_INLINE_QOS, PID_GROUP_DATA,
+        DEFAULT_EXPECTS_INLINE_QOS, PID_CONTENT_FILTER_PROPERTY, PID_DATA_REPRESENTATION,
+        PID_DEADLINE, PID_DESTINATION_ORDER, PID_DISABLE_POSITIVE_ACKS, PID_DURABILITY,
+        PID_ENDPOINT_GUID, PID_ENTITY_NAME, PID_EXPECTS_INLINE_QOS, PID_GROUP_DATA,
         PID_GROUP_ENTITYID, PID_LATENCY_BUDGET, PID_LIVELINESS, PID_MULTICAST_LOCATOR,
-        PID_OWNERSHIP, PID_PARTICIPANT_GUID, PID_PARTITION, PID_PRESENTATION, PID_RELIABILITY,
-        PID_TIME_BASED_FILTER, PID_TOPIC_DATA, PID_TOPIC_NAME, PID_TYPE_NAME, PID_UNICAST_LOCATOR,
-        PID_USER_DATA,
+        PID_OWNERSHIP, PID_PARTICIPANT_GUID, PID_PARTITION, PID_PRESENTATION, PID_PROPERTY_LIST,
+        PID_RELIABILITY, PID_TIME_BASED_FILTER, PID_TOPIC_DATA, PID_TOPIC_NAME,
+        PID_TYPE_INFORMATION, PID_TYPE_NAME, PID_UNICAST_LOCATOR, PID_USER_DATA,
     },
     payload_serializer_deserializer::{
         parameter_list_deserializer::ParameterListCdrDeserializer,
@@ -14,13 +15,26 @@ use super::{
 };
 use crate::{
     builtin_topics::SubscriptionBuiltinTopicData,
+    implementation::xtypes_glue::type_information::{
+        deserialize_type_information, serialize_type_information,
+    },
     infrastructure::{
         error::DdsResult, qos_policy::DEFAULT_RELIABILITY_QOS_POLICY_DATA_READER_AND_TOPICS,
     },
     topic_definition::type_support::{DdsDeserialize, DdsSerialize, TypeSupport},
     transport::types::{EntityId, Guid, Locator},
+    xtypes::{deserialize::XTypesDeserialize, serialize::XTypesSerialize},
 };
 
+#[derive(Debug, PartialEq, Eq, Clone, XTypesSerialize, XTypesDeserialize)]
+pub struct ContentFilterProperty {
+    pub content_filtered_topic_name: String,
+    pub related_topic_name: String,
+    pub filter_class_name: String,
+    pub filter_expression: String,
+    pub expression_parameters: Vec<String>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ReaderProxy {
     pub remote_reader_guid: Guid,
@@ -34,6 +48,7 @@ pub struct ReaderProxy {
 pub struct DiscoveredReaderData {
     pub(crate) dds_subscription_data: SubscriptionBuiltinTopicData,
     pub(crate) reader_proxy: ReaderProxy,
+    pub(crate) content_filter: Option<ContentFilterProperty>,
 }
 impl TypeSupport for DiscoveredReaderData {
     fn get_type_name() -> &'static str {
@@ -180,6 +195,27 @@ impl DdsSerialize for DiscoveredReaderData {
             &self.dds_subscription_data.representation,
             &Default::default(),
         )?;
+        serializer.write_with_default(
+            PID_ENTITY_NAME,
+            &self.dds_subscription_data.entity_name,
+            &Default::default(),
+        )?;
+        serializer.write_with_default(
+            PID_PROPERTY_LIST,
+            &self.dds_subscription_data.property,
+            &Default::default(),
+        )?;
+        serializer.write_with_default(
+            PID_DISABLE_POSITIVE_ACKS,
+            &self.dds_subscription_data.disable_positive_acks,
+            &Default::default(),
+        )?;
+        if let Some(type_information) = &self.dds_subscription_data.type_information {
+            serializer.write_bytes(
+                PID_TYPE_INFORMATION,
+                &serialize_type_information(type_information),
+            )?;
+        }
 
         // reader_proxy: ReaderProxy
 
@@ -202,6 +238,10 @@ impl DdsSerialize for DiscoveredReaderData {
             &DEFAULT_EXPECTS_INLINE_QOS,
         )?;
 
+        if let Some(content_filter) = &self.content_filter {
+            serializer.write(PID_CONTENT_FILTER_PROPERTY, content_filter)?;
+        }
+
         serializer.write_sentinel()?;
         Ok(serializer.writer)
     }
@@ -240,6 +280,14 @@ impl<'de> DdsDeserialize<'de> for SubscriptionBuiltinTopicData {
             group_data: pl_deserializer.read_with_default(PID_GROUP_DATA, Default::default())?,
             representation: pl_deserializer
                 .read_with_default(PID_DATA_REPRESENTATION, Default::default())?,
+            entity_name: pl_deserializer.read_with_default(PID_ENTITY_NAME, Default::default())?,
+            property: pl_deserializer.read_with_default(PID_PROPERTY_LIST, Default::default())?,
+            disable_positive_acks: pl_deserializer
+                .read_with_default(PID_DISABLE_POSITIVE_ACKS, Default::default())?,
+            // A type information which can not be interpreted is handled as not announced
+            type_information: pl_deserializer
+                .read_bytes(PID_TYPE_INFORMATION)?
+                .and_then(|data| deserialize_type_information(data).ok()),
         })
     }
 }
@@ -259,6 +307,7 @@ impl<'de> DdsDeserialize<'de> for DiscoveredReaderData {
                 expects_inline_qos: pl_deserializer
                     .read_with_default(PID_EXPECTS_INLINE_QOS, DEFAULT_EXPECTS_INLINE_QOS)?,
             },
+            content_filter: pl_deserializer.read(PID_CONTENT_FILTER_PROPERTY).ok(),
         })
     }
 }
@@ -300,6 +349,10 @@ mod tests {
                 topic_data: Default::default(),
                 group_data: Default::default(),
                 representation: Default::default(),
+                entity_name: Default::default(),
+                property: Default::default(),
+                disable_positive_acks: Default::default(),
+                type_information: None,
             },
             reader_proxy: ReaderProxy {
                 remote_reader_guid: Guid::new(
@@ -311,6 +364,7 @@ mod tests {
                 multicast_locator_list: vec![],
                 expects_inline_qos: false,
             },
+            content_filter: None,
         };
 
         let expected = vec![
@@ -354,6 +408,7 @@ mod tests {
                 multicast_locator_list: vec![],
                 expects_inline_qos: false,
             },
+            content_filter: None,
             dds_subscription_data: SubscriptionBuiltinTopicData {
                 key: BuiltInTopicKey {
                     value: [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0],
@@ -377,6 +432,10 @@ mod tests {
                 topic_data: Default::default(),
                 group_data: Default::default(),
                 representation: Default::default(),
+                entity_name: Default::default(),
+                property: Default::default(),
+                disable_positive_acks: Default::default(),
+                type_information: None,
             },
         };
 
@@ -405,4 +464,50 @@ mod tests {
         let result = DiscoveredReaderData::deserialize_data(&mut data).unwrap();
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn serialize_and_deserialize_content_filter() {
+        let data = &[
+            0x00, 0x03, 0x00, 0x00, // PL_CDR_LE
+            0x5a, 0x00, 16, 0, //PID_ENDPOINT_GUID, length
+            1, 0, 0, 0, // ,
+            2, 0, 0, 0, // ,
+            3, 0, 0, 0, // ,
+            4, 0, 0, 0, // ,
+            0x05, 0x00, 0x08, 0x00, // PID_TOPIC_NAME, Length: 8
+            3, 0x00, 0x00, 0x00, // string length (incl. terminator)
+            b'a', b'b', 0, 0x00, // string + padding (1 byte)
+            0x07, 0x00, 0x08, 0x00, // PID_TYPE_NAME, Length: 8
+            3, 0x00, 0x00, 0x00, // string length (incl. terminator)
+            b'c', b'd', 0, 0x00, // string + padding (1 byte)
+            0x35, 0x00, 44, 0x00, // PID_CONTENT_FILTER_PROPERTY, Length: 44
+            2, 0x00, 0x00, 0x00, // string length (incl. terminator)
+            b'f', 0, 0x00, 0x00, // string + padding (2 bytes)
+            3, 0x00, 0x00, 0x00, // string length (incl. terminator)
+            b'a', b'b', 0, 0x00, // string + padding (1 byte)
+            7, 0x00, 0x00, 0x00, // string length (incl. terminator)
+            b'D', b'D', b'S', b'S', // string
+            b'Q', b'L', 0, 0x00, // string + padding (1 byte)
+            6, 0x00, 0x00, 0x00, // string length (incl. terminator)
+            b'x', b' ', b'>', b' ', // string
+            b'1', 0, 0x00, 0x00, // string + padding (2 bytes)
+            0, 0x00, 0x00, 0x00, // expression_parameters length
+            0x01, 0x00, 0x00, 0x00, // PID_SENTINEL, length
+        ][..];
+        let result = DiscoveredReaderData::deserialize_data(data).unwrap();
+        assert_eq!(
+            result.content_filter,
+            Some(ContentFilterProperty {
+                content_filtered_topic_name: "f".to_string(),
+                related_topic_name: "ab".to_string(),
+                filter_class_name: "DDSSQL".to_string(),
+                filter_expression: "x > 1".to_string(),
+                expression_parameters: vec![],
+            })
+        );
+        assert_eq!(
+            DiscoveredReaderData::deserialize_data(&result.serialize_data().unwrap()).unwrap(),
+            result
+        );
+    }
 }
@@ -1,9 +1,9 @@
 use super::{
     parameter_id_values::{
         PID_DATA_REPRESENTATION, PID_DEADLINE, PID_DESTINATION_ORDER, PID_DURABILITY,
-        PID_ENDPOINT_GUID, PID_HISTORY, PID_LATENCY_BUDGET, PID_LIFESPAN, PID_LIVELINESS,
-        PID_OWNERSHIP, PID_RELIABILITY, PID_RESOURCE_LIMITS, PID_TOPIC_DATA, PID_TOPIC_NAME,
-        PID_TRANSPORT_PRIORITY, PID_TYPE_NAME,
+        PID_DURABILITY_SERVICE, PID_ENDPOINT_GUID, PID_HISTORY, PID_LATENCY_BUDGET, PID_LIFESPAN,
+        PID_LIVELINESS, PID_OWNERSHIP, PID_RELIABILITY, PID_RESOURCE_LIMITS, PID_TOPIC_DATA,
+        PID_TOPIC_NAME, PID_TRANSPORT_PRIORITY, PID_TYPE_NAME,
     },
     payload_serializer_deserializer::parameter_list_serializer::ParameterListCdrSerializer,
 };
@@ -95,6 +95,11 @@ impl DdsSerialize for DiscoveredTopicData {
             &self.topic_builtin_topic_data.durability,
             &Default::default(),
         )?;
+        serializer.write_with_default(
+            PID_DURABILITY_SERVICE,
+            &self.topic_builtin_topic_data.durability_service,
+            &Default::default(),
+        )?;
         serializer.write_with_default(
             PID_DEADLINE,
             &self.topic_builtin_topic_data.deadline,
@@ -185,6 +190,7 @@ mod tests {
                 name: "ab".to_string(),
                 type_name: "cd".to_string(),
                 durability: topic_qos.durability,
+                durability_service: topic_qos.durability_service,
                 deadline: topic_qos.deadline,
                 latency_budget: topic_qos.latency_budget,
                 liveliness: topic_qos.liveliness,
@@ -230,6 +236,7 @@ mod tests {
                 name: "ab".to_string(),
                 type_name: "cd".to_string(),
                 durability: topic_qos.durability,
+                durability_service: topic_qos.durability_service,
                 deadline: topic_qos.deadline,
                 latency_budget: topic_qos.latency_budget,
                 liveliness: topic_qos.liveliness,
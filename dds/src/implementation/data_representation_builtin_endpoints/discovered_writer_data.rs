@@ -1,10 +1,12 @@
 use super::{
     parameter_id_values::{
         PID_DATA_MAX_SIZE_SERIALIZED, PID_DATA_REPRESENTATION, PID_DEADLINE, PID_DESTINATION_ORDER,
-        PID_DURABILITY, PID_ENDPOINT_GUID, PID_GROUP_DATA, PID_GROUP_ENTITYID, PID_LATENCY_BUDGET,
-        PID_LIFESPAN, PID_LIVELINESS, PID_MULTICAST_LOCATOR, PID_OWNERSHIP, PID_OWNERSHIP_STRENGTH,
-        PID_PARTICIPANT_GUID, PID_PARTITION, PID_PRESENTATION, PID_RELIABILITY, PID_TOPIC_DATA,
-        PID_TOPIC_NAME, PID_TYPE_NAME, PID_UNICAST_LOCATOR, PID_USER_DATA,
+        PID_DISABLE_POSITIVE_ACKS, PID_DURABILITY, PID_DURABILITY_SERVICE, PID_ENDPOINT_GUID,
+        PID_ENTITY_NAME, PID_GROUP_DATA, PID_GROUP_ENTITYID, PID_LATENCY_BUDGET, PID_LIFESPAN,
+        PID_LIVELINESS, PID_MULTICAST_LOCATOR, PID_OWNERSHIP, PID_OWNERSHIP_STRENGTH,
+        PID_PARTICIPANT_GUID, PID_PARTITION, PID_PRESENTATION, PID_PROPERTY_LIST, PID_RELIABILITY,
+        PID_TOPIC_DATA, PID_TOPIC_NAME, PID_TYPE_INFORMATION, PID_TYPE_NAME, PID_UNICAST_LOCATOR,
+        PID_USER_DATA,
     },
     payload_serializer_deserializer::{
         parameter_list_deserializer::ParameterListCdrDeserializer,
@@ -13,6 +15,9 @@ use super::{
 };
 use crate::{
     builtin_topics::PublicationBuiltinTopicData,
+    implementation::xtypes_glue::type_information::{
+        deserialize_type_information, serialize_type_information,
+    },
     infrastructure::{error::DdsResult, qos_policy::DEFAULT_RELIABILITY_QOS_POLICY_DATA_WRITER},
     topic_definition::type_support::{DdsDeserialize, DdsSerialize, TypeSupport},
     transport::types::{EntityId, Guid, Locator},
@@ -113,6 +118,11 @@ impl DdsSerialize for DiscoveredWriterData {
             &self.dds_publication_data.durability,
             &Default::default(),
         )?;
+        serializer.write_with_default(
+            PID_DURABILITY_SERVICE,
+            &self.dds_publication_data.durability_service,
+            &Default::default(),
+        )?;
         serializer.write_with_default(
             PID_DEADLINE,
             &self.dds_publication_data.deadline,
@@ -184,6 +194,27 @@ impl DdsSerialize for DiscoveredWriterData {
             &self.dds_publication_data.representation,
             &Default::default(),
         )?;
+        serializer.write_with_default(
+            PID_ENTITY_NAME,
+            &self.dds_publication_data.entity_name,
+            &Default::default(),
+        )?;
+        serializer.write_with_default(
+            PID_PROPERTY_LIST,
+            &self.dds_publication_data.property,
+            &Default::default(),
+        )?;
+        serializer.write_with_default(
+            PID_DISABLE_POSITIVE_ACKS,
+            &self.dds_publication_data.disable_positive_acks,
+            &Default::default(),
+        )?;
+        if let Some(type_information) = &self.dds_publication_data.type_information {
+            serializer.write_bytes(
+                PID_TYPE_INFORMATION,
+                &serialize_type_information(type_information),
+            )?;
+        }
 
         // writer_proxy: WriterProxy:
 
@@ -223,6 +254,8 @@ impl<'de> DdsDeserialize<'de> for PublicationBuiltinTopicData {
             topic_name: pl_deserializer.read(PID_TOPIC_NAME)?,
             type_name: pl_deserializer.read(PID_TYPE_NAME)?,
             durability: pl_deserializer.read_with_default(PID_DURABILITY, Default::default())?,
+            durability_service: pl_deserializer
+                .read_with_default(PID_DURABILITY_SERVICE, Default::default())?,
             deadline: pl_deserializer.read_with_default(PID_DEADLINE, Default::default())?,
             latency_budget: pl_deserializer
                 .read_with_default(PID_LATENCY_BUDGET, Default::default())?,
@@ -244,6 +277,14 @@ impl<'de> DdsDeserialize<'de> for PublicationBuiltinTopicData {
 
             representation: pl_deserializer
                 .read_with_default(PID_DATA_REPRESENTATION, Default::default())?,
+            entity_name: pl_deserializer.read_with_default(PID_ENTITY_NAME, Default::default())?,
+            property: pl_deserializer.read_with_default(PID_PROPERTY_LIST, Default::default())?,
+            disable_positive_acks: pl_deserializer
+                .read_with_default(PID_DISABLE_POSITIVE_ACKS, Default::default())?,
+            // A type information which can not be interpreted is handled as not announced
+            type_information: pl_deserializer
+                .read_bytes(PID_TYPE_INFORMATION)?
+                .and_then(|data| deserialize_type_information(data).ok()),
         })
     }
 }
@@ -291,6 +332,7 @@ mod tests {
                 topic_name: "ab".to_string(),
                 type_name: "cd".to_string(),
                 durability: Default::default(),
+                durability_service: Default::default(),
                 deadline: Default::default(),
                 latency_budget: Default::default(),
                 liveliness: Default::default(),
@@ -305,6 +347,10 @@ mod tests {
                 topic_data: Default::default(),
                 group_data: Default::default(),
                 representation: Default::default(),
+                entity_name: Default::default(),
+                property: Default::default(),
+                disable_positive_acks: Default::default(),
+                type_information: None,
             },
             writer_proxy: WriterProxy {
                 remote_writer_guid: Guid::new(
@@ -358,6 +404,7 @@ mod tests {
                 topic_name: "ab".to_string(),
                 type_name: "cd".to_string(),
                 durability: Default::default(),
+                durability_service: Default::default(),
                 deadline: Default::default(),
                 latency_budget: Default::default(),
                 liveliness: Default::default(),
@@ -372,6 +419,10 @@ mod tests {
                 topic_data: Default::default(),
                 group_data: Default::default(),
                 representation: Default::default(),
+                entity_name: Default::default(),
+                property: Default::default(),
+                disable_positive_acks: Default::default(),
+                type_information: None,
             },
             writer_proxy: WriterProxy {
                 // must correspond to publication_builtin_topic_data.key
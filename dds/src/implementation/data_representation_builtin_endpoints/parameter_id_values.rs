@@ -14,7 +14,7 @@ pub const PID_TYPE_NAME: ParameterId = 0x0007;
 pub const PID_GROUP_DATA: ParameterId = 0x002d;
 pub const PID_TOPIC_DATA: ParameterId = 0x002e;
 pub const PID_DURABILITY: ParameterId = 0x001d;
-pub const _PID_DURABILITY_SERVICE: ParameterId = 0x001e;
+pub const PID_DURABILITY_SERVICE: ParameterId = 0x001e;
 pub const PID_DEADLINE: ParameterId = 0x0023;
 pub const PID_LATENCY_BUDGET: ParameterId = 0x0027;
 pub const PID_LIVELINESS: ParameterId = 0x001b;
@@ -42,14 +42,14 @@ pub const PID_METATRAFFIC_MULTICAST_LOCATOR: ParameterId = 0x0033;
 pub const PID_EXPECTS_INLINE_QOS: ParameterId = 0x0043;
 pub const PID_PARTICIPANT_MANUAL_LIVELINESS_COUNT: ParameterId = 0x0034;
 pub const PID_PARTICIPANT_LEASE_DURATION: ParameterId = 0x0002;
-pub const _PID_CONTENT_FILTER_PROPERTY: ParameterId = 0x0035;
+pub const PID_CONTENT_FILTER_PROPERTY: ParameterId = 0x0035;
 pub const PID_PARTICIPANT_GUID: ParameterId = 0x0050;
 pub const _PID_GROUP_GUID: ParameterId = 0x0052;
 pub const PID_BUILTIN_ENDPOINT_SET: ParameterId = 0x0058;
 pub const PID_BUILTIN_ENDPOINT_QOS: ParameterId = 0x0077;
-pub const _PID_PROPERTY_LIST: ParameterId = 0x0059;
+pub const PID_PROPERTY_LIST: ParameterId = 0x0059;
 pub const PID_TYPE_MAX_SIZE_SERIALIZED: ParameterId = 0x0060;
-pub const _PID_ENTITY_NAME: ParameterId = 0x0062;
+pub const PID_ENTITY_NAME: ParameterId = 0x0062;
 pub const PID_ENDPOINT_GUID: ParameterId = 0x005a;
 // Following PID is not defined in standard
 // (but its listed in "Table 9.14 - ParameterId mapping and default values")
@@ -59,6 +59,18 @@ pub const PID_DATA_MAX_SIZE_SERIALIZED: ParameterId = PID_TYPE_MAX_SIZE_SERIALIZ
 pub const PID_GROUP_ENTITYID: ParameterId = 0x0053;
 pub const PID_DATA_REPRESENTATION: ParameterId = 0x0073;
 
+// ParameterId of the TypeInformation of the endpoints defined by the DDS-XTypes specification
+pub const PID_TYPE_INFORMATION: ParameterId = 0x0075;
+
+// Constant values from DDS Security Table 10 - ParameterId values used to represent
+// security information
+pub const PID_IDENTITY_TOKEN: ParameterId = 0x1001;
+pub const PID_PARTICIPANT_SECURITY_INFO: ParameterId = 0x1005;
+
+// Vendor specific PID used by other implementations to announce that positive
+// acknowledgments are disabled on the endpoint
+#[allow(overflowing_literals)]
+pub const PID_DISABLE_POSITIVE_ACKS: ParameterId = 0x8005;
 #[allow(overflowing_literals)]
 pub const _PID_TYPE_REPRESENTATION: ParameterId = 0x8010;
 #[allow(overflowing_literals)]
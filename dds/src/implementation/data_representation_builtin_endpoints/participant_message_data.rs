@@ -0,0 +1,101 @@
+use crate::{
+    infrastructure::error::{DdsError, DdsResult},
+    transport::types::GuidPrefix,
+    xtypes::{
+        deserialize::XTypesDeserialize,
+        serialize::XTypesSerialize,
+        xcdr_deserializer::{Xcdr1BeDeserializer, Xcdr1LeDeserializer},
+        xcdr_serializer::Xcdr1LeSerializer,
+    },
+};
+
+const CDR_BE: [u8; 4] = [0x00, 0x00, 0x00, 0x00];
+const CDR_LE: [u8; 4] = [0x00, 0x01, 0x00, 0x00];
+
+pub type ParticipantMessageKind = [u8; 4];
+
+pub const PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE: ParticipantMessageKind =
+    [0x00, 0x00, 0x00, 0x01];
+pub const PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE: ParticipantMessageKind =
+    [0x00, 0x00, 0x00, 0x02];
+
+// Data of the DCPSParticipantMessage builtin topic as defined in RTPS 9.6.2.1 used to assert
+// the liveliness of the writers of a participant
+#[derive(Debug, PartialEq, Eq, Clone, XTypesSerialize, XTypesDeserialize)]
+pub struct ParticipantMessageData {
+    pub participant_guid_prefix: GuidPrefix,
+    pub kind: ParticipantMessageKind,
+    pub data: Vec<u8>,
+}
+
+impl ParticipantMessageData {
+    // The key of the topic is the participant GUID prefix together with the kind
+    pub fn key(&self) -> [u8; 16] {
+        let mut key = [0; 16];
+        key[..12].copy_from_slice(&self.participant_guid_prefix);
+        key[12..].copy_from_slice(&self.kind);
+        key
+    }
+
+    pub fn serialize_data(&self) -> DdsResult<Vec<u8>> {
+        let mut data = CDR_LE.to_vec();
+        self.serialize(&mut Xcdr1LeSerializer::new(&mut data))?;
+        Ok(data)
+    }
+
+    pub fn deserialize_data(serialized_data: &[u8]) -> DdsResult<Self> {
+        let body = serialized_data
+            .get(4..)
+            .ok_or(DdsError::Error("Participant message too short".to_string()))?;
+        Ok(match [serialized_data[0], serialized_data[1], 0, 0] {
+            CDR_BE => Self::deserialize(&mut Xcdr1BeDeserializer::new(body))?,
+            CDR_LE => Self::deserialize(&mut Xcdr1LeDeserializer::new(body))?,
+            _ => {
+                return Err(DdsError::Error(
+                    "Unsupported participant message representation".to_string(),
+                ))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_participant_message_data() {
+        let data = ParticipantMessageData {
+            participant_guid_prefix: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+            kind: PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE,
+            data: vec![7],
+        };
+        let expected = vec![
+            0x00, 0x01, 0x00, 0x00, // CDR_LE
+            1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, // participant_guid_prefix
+            0, 0, 0, 2, // kind
+            1, 0, 0, 0, // data length
+            7, // data
+        ];
+        assert_eq!(data.serialize_data().unwrap(), expected);
+    }
+
+    #[test]
+    fn deserialize_participant_message_data() {
+        let expected = ParticipantMessageData {
+            participant_guid_prefix: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+            kind: PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE,
+            data: vec![],
+        };
+        let serialized_data = [
+            0x00, 0x00, 0x00, 0x00, // CDR_BE
+            1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, // participant_guid_prefix
+            0, 0, 0, 1, // kind
+            0, 0, 0, 0, // data length
+        ];
+        assert_eq!(
+            ParticipantMessageData::deserialize_data(&serialized_data).unwrap(),
+            expected
+        );
+    }
+}
@@ -141,6 +141,17 @@ impl<'de> ParameterListCdrDeserializer<'de> {
         Ok(collection)
     }
 
+    // Returns the value of the parameter without deserializing it
+    pub fn read_bytes(&self, pid: ParameterId) -> Result<Option<&'de [u8]>, RtpsError> {
+        let mut iterator = self.iter();
+        while let Some(parameter) = iterator.next()? {
+            if parameter.pid == pid {
+                return Ok(Some(parameter.data));
+            }
+        }
+        Ok(None)
+    }
+
     pub fn read_with_default<T>(&self, pid: ParameterId, default: T) -> Result<T, RtpsError>
     where
         T: XTypesDeserialize<'de>,
@@ -46,6 +46,23 @@ impl ParameterListCdrSerializer {
         Ok(())
     }
 
+    // Writes a parameter whose value is already serialized, e.g. with the XCDR version 2
+    // representation used by the XTypes parameters
+    pub fn write_bytes(&mut self, id: ParameterId, value: &[u8]) -> Result<(), RtpsError> {
+        let padded_length = (value.len() + 3) & !3;
+        if padded_length > u16::MAX as usize {
+            return Err(RtpsError::new(RtpsErrorKind::InvalidData, format!("Serialized parameter ID {} with serialized size {} exceeds maximum parameter size of {}", id, padded_length, u16::MAX)));
+        }
+        self.writer.write_all(&id.to_le_bytes())?;
+        self.writer
+            .write_all(&(padded_length as u16).to_le_bytes())?;
+        self.writer.write_all(value)?;
+        const ZEROS: [u8; 4] = [0; 4];
+        self.writer
+            .write_all(&ZEROS[..padded_length - value.len()])?;
+        Ok(())
+    }
+
     pub fn write_with_default<T>(
         &mut self,
         id: ParameterId,
@@ -4,12 +4,13 @@ use super::{
         PID_BUILTIN_ENDPOINT_QOS, PID_BUILTIN_ENDPOINT_SET, PID_DATA_REPRESENTATION, PID_DEADLINE,
         PID_DEFAULT_MULTICAST_LOCATOR, PID_DEFAULT_UNICAST_LOCATOR, PID_DESTINATION_ORDER,
         PID_DISCOVERED_PARTICIPANT, PID_DOMAIN_ID, PID_DOMAIN_TAG, PID_DURABILITY,
-        PID_ENDPOINT_GUID, PID_EXPECTS_INLINE_QOS, PID_HISTORY, PID_LATENCY_BUDGET, PID_LIFESPAN,
-        PID_LIVELINESS, PID_METATRAFFIC_MULTICAST_LOCATOR, PID_METATRAFFIC_UNICAST_LOCATOR,
-        PID_OWNERSHIP, PID_PARTICIPANT_GUID, PID_PARTICIPANT_LEASE_DURATION,
-        PID_PARTICIPANT_MANUAL_LIVELINESS_COUNT, PID_PROTOCOL_VERSION, PID_RELIABILITY,
-        PID_RESOURCE_LIMITS, PID_TOPIC_DATA, PID_TOPIC_NAME, PID_TRANSPORT_PRIORITY, PID_TYPE_NAME,
-        PID_USER_DATA, PID_VENDORID,
+        PID_DURABILITY_SERVICE, PID_ENDPOINT_GUID, PID_ENTITY_NAME, PID_EXPECTS_INLINE_QOS,
+        PID_HISTORY, PID_IDENTITY_TOKEN, PID_LATENCY_BUDGET, PID_LIFESPAN, PID_LIVELINESS,
+        PID_METATRAFFIC_MULTICAST_LOCATOR, PID_METATRAFFIC_UNICAST_LOCATOR, PID_OWNERSHIP,
+        PID_PARTICIPANT_GUID, PID_PARTICIPANT_LEASE_DURATION,
+        PID_PARTICIPANT_MANUAL_LIVELINESS_COUNT, PID_PARTICIPANT_SECURITY_INFO, PID_PROPERTY_LIST,
+        PID_PROTOCOL_VERSION, PID_RELIABILITY, PID_RESOURCE_LIMITS, PID_TOPIC_DATA, PID_TOPIC_NAME,
+        PID_TRANSPORT_PRIORITY, PID_TYPE_NAME, PID_USER_DATA, PID_VENDORID,
     },
     payload_serializer_deserializer::{
         parameter_list_deserializer::ParameterListCdrDeserializer,
@@ -23,6 +24,7 @@ use crate::{
         error::DdsResult, instance::InstanceHandle,
         qos_policy::DEFAULT_RELIABILITY_QOS_POLICY_DATA_READER_AND_TOPICS, time::Duration,
     },
+    security::types::{DataHolder, ParticipantSecurityInfo},
     topic_definition::type_support::{DdsDeserialize, DdsSerialize, TypeSupport},
     transport::types::{GuidPrefix, Locator, Long, ProtocolVersion, VendorId},
     xtypes::{deserialize::XTypesDeserialize, serialize::XTypesSerialize},
@@ -68,8 +70,8 @@ impl BuiltinEndpointSet {
     @position(9) DISC_BUILTIN_ENDPOINT_PARTICIPANT_STATE_DETECTOR,
     */
 
-    pub const _BUILTIN_ENDPOINT_PARTICIPANT_MESSAGE_DATA_WRITER: u32 = 1 << 10;
-    pub const _BUILTIN_ENDPOINT_PARTICIPANT_MESSAGE_DATA_READER: u32 = 1 << 11;
+    pub const BUILTIN_ENDPOINT_PARTICIPANT_MESSAGE_DATA_WRITER: u32 = 1 << 10;
+    pub const BUILTIN_ENDPOINT_PARTICIPANT_MESSAGE_DATA_READER: u32 = 1 << 11;
 
     /*
     Bits 12-15 have been reserved by the DDS-Xtypes 1.2 Specification
@@ -78,6 +80,14 @@ impl BuiltinEndpointSet {
     and future revisions thereof.
     */
 
+    pub const BUILTIN_TYPE_LOOKUP_SERVICE_REQUEST_DATA_WRITER: u32 = 1 << 12;
+    pub const BUILTIN_TYPE_LOOKUP_SERVICE_REQUEST_DATA_READER: u32 = 1 << 13;
+    pub const BUILTIN_TYPE_LOOKUP_SERVICE_REPLY_DATA_WRITER: u32 = 1 << 14;
+    pub const BUILTIN_TYPE_LOOKUP_SERVICE_REPLY_DATA_READER: u32 = 1 << 15;
+
+    pub const BUILTIN_PARTICIPANT_STATELESS_MESSAGE_WRITER: u32 = 1 << 22;
+    pub const BUILTIN_PARTICIPANT_STATELESS_MESSAGE_READER: u32 = 1 << 23;
+
     pub const BUILTIN_ENDPOINT_TOPICS_ANNOUNCER: u32 = 1 << 28;
     pub const BUILTIN_ENDPOINT_TOPICS_DETECTOR: u32 = 1 << 29;
 
@@ -132,6 +142,8 @@ pub struct SpdpDiscoveredParticipantData {
     pub(crate) participant_proxy: ParticipantProxy,
     pub(crate) lease_duration: Duration,
     pub(crate) discovered_participant_list: Vec<InstanceHandle>,
+    pub(crate) identity_token: Option<DataHolder>,
+    pub(crate) participant_security_info: Option<ParticipantSecurityInfo>,
 }
 impl TypeSupport for SpdpDiscoveredParticipantData {
     fn get_type_name() -> &'static str {
@@ -203,6 +215,8 @@ impl DdsSerialize for ParticipantBuiltinTopicData {
         // dds_participant_data: ParticipantBuiltinTopicData :
         serializer.write(PID_PARTICIPANT_GUID, &self.key)?;
         serializer.write_with_default(PID_USER_DATA, &self.user_data, &Default::default())?;
+        serializer.write_with_default(PID_ENTITY_NAME, &self.entity_name, &Default::default())?;
+        serializer.write_with_default(PID_PROPERTY_LIST, &self.property, &Default::default())?;
         serializer.write_sentinel()?;
         Ok(serializer.writer)
     }
@@ -214,6 +228,8 @@ impl<'de> DdsDeserialize<'de> for ParticipantBuiltinTopicData {
         Ok(Self {
             key: pl_deserializer.read(PID_PARTICIPANT_GUID)?,
             user_data: pl_deserializer.read_with_default(PID_USER_DATA, Default::default())?,
+            entity_name: pl_deserializer.read_with_default(PID_ENTITY_NAME, Default::default())?,
+            property: pl_deserializer.read_with_default(PID_PROPERTY_LIST, Default::default())?,
         })
     }
 }
@@ -226,6 +242,8 @@ impl<'de> DdsDeserialize<'de> for TopicBuiltinTopicData {
             name: pl_deserializer.read(PID_TOPIC_NAME)?,
             type_name: pl_deserializer.read(PID_TYPE_NAME)?,
             durability: pl_deserializer.read_with_default(PID_DURABILITY, Default::default())?,
+            durability_service: pl_deserializer
+                .read_with_default(PID_DURABILITY_SERVICE, Default::default())?,
             deadline: pl_deserializer.read_with_default(PID_DEADLINE, Default::default())?,
             latency_budget: pl_deserializer
                 .read_with_default(PID_LATENCY_BUDGET, Default::default())?,
@@ -262,6 +280,16 @@ impl DdsSerialize for SpdpDiscoveredParticipantData {
             &self.dds_participant_data.user_data,
             &Default::default(),
         )?;
+        serializer.write_with_default(
+            PID_ENTITY_NAME,
+            &self.dds_participant_data.entity_name,
+            &Default::default(),
+        )?;
+        serializer.write_with_default(
+            PID_PROPERTY_LIST,
+            &self.dds_participant_data.property,
+            &Default::default(),
+        )?;
 
         // participant_proxy: ParticipantProxy :
         if let Some(domain_id) = &self.participant_proxy.domain_id {
@@ -321,6 +349,13 @@ impl DdsSerialize for SpdpDiscoveredParticipantData {
             &self.discovered_participant_list,
         )?;
 
+        if let Some(identity_token) = &self.identity_token {
+            serializer.write(PID_IDENTITY_TOKEN, identity_token)?;
+        }
+        if let Some(participant_security_info) = &self.participant_security_info {
+            serializer.write(PID_PARTICIPANT_SECURITY_INFO, participant_security_info)?;
+        }
+
         serializer.write_sentinel()?;
         Ok(serializer.writer)
     }
@@ -368,6 +403,8 @@ impl<'de> DdsDeserialize<'de> for SpdpDiscoveredParticipantData {
             )?,
             discovered_participant_list: pl_deserializer
                 .read_collection(PID_DISCOVERED_PARTICIPANT)?,
+            identity_token: pl_deserializer.read(PID_IDENTITY_TOKEN).ok(),
+            participant_security_info: pl_deserializer.read(PID_PARTICIPANT_SECURITY_INFO).ok(),
         })
     }
 }
@@ -406,6 +443,8 @@ mod tests {
                     value: [8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 0, 0, 1, 0xc1],
                 },
                 user_data: UserDataQosPolicy { value: vec![] },
+                entity_name: Default::default(),
+                property: Default::default(),
             },
             participant_proxy: ParticipantProxy {
                 domain_id,
@@ -424,6 +463,8 @@ mod tests {
             },
             lease_duration,
             discovered_participant_list: vec![],
+            identity_token: None,
+            participant_security_info: None,
         };
 
         let expected = vec![
@@ -523,6 +564,8 @@ mod tests {
                     value: [8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 0, 0, 1, 0xc1],
                 },
                 user_data: UserDataQosPolicy { value: vec![] },
+                entity_name: Default::default(),
+                property: Default::default(),
             },
             participant_proxy: ParticipantProxy {
                 domain_id: Some(domain_id),
@@ -541,6 +584,8 @@ mod tests {
             },
             lease_duration,
             discovered_participant_list: vec![],
+            identity_token: None,
+            participant_security_info: None,
         };
 
         let mut data = &[
@@ -1,48 +1,80 @@
-use super::{entities::domain_participant::DomainParticipantEntity, handle::InstanceHandleCounter};
+use super::{
+    durability_service::DurabilityService, entities::domain_participant::DomainParticipantEntity,
+    handle::InstanceHandleCounter, participant_message::ParticipantMessage,
+};
 use crate::{
     dds_async::{
         data_reader::DataReaderAsync, data_writer::DataWriterAsync,
         domain_participant::DomainParticipantAsync, publisher::PublisherAsync,
         subscriber::SubscriberAsync, topic::TopicAsync,
     },
+    implementation::xtypes_glue::type_lookup::TypeLookup,
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
     },
-    runtime::{actor::ActorAddress, executor::Executor, timer::TimerDriver},
+    runtime::{
+        actor::{ActorAddress, Mailbox},
+        executor::{Executor, ExecutorPool},
+        timer::TimerDriver,
+    },
     transport::participant::TransportParticipant,
 };
 
+#[cfg(feature = "security")]
+use crate::security::participant_security::ParticipantSecurity;
+
 pub struct DomainParticipantActor {
     pub transport: Box<dyn TransportParticipant>,
     pub instance_handle_counter: InstanceHandleCounter,
     pub entity_counter: u16,
     pub domain_participant: DomainParticipantEntity,
-    pub backend_executor: Executor,
-    pub listener_executor: Executor,
+    _backend_executor: Executor,
+    pub listener_executor: ExecutorPool,
+    pub listener_mailbox: Mailbox,
     pub timer_driver: TimerDriver,
     pub fragment_size: usize,
+    pub max_sequence_length: usize,
+    pub type_lookup: TypeLookup,
+    pub participant_message: ParticipantMessage,
+    pub durability_service: DurabilityService,
+    #[cfg(feature = "security")]
+    pub security: Option<ParticipantSecurity>,
 }
 
 impl DomainParticipantActor {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         domain_participant: DomainParticipantEntity,
         transport: Box<dyn TransportParticipant>,
         backend_executor: Executor,
-        listener_executor: Executor,
+        listener_executor: ExecutorPool,
+        listener_mailbox: Mailbox,
         timer_driver: TimerDriver,
         instance_handle_counter: InstanceHandleCounter,
         fragment_size: usize,
+        max_sequence_length: usize,
+        type_lookup: TypeLookup,
+        participant_message: ParticipantMessage,
+        durability_service: DurabilityService,
+        #[cfg(feature = "security")] security: Option<ParticipantSecurity>,
     ) -> Self {
         Self {
             transport,
             instance_handle_counter,
             entity_counter: 0,
             domain_participant,
-            backend_executor,
+            _backend_executor: backend_executor,
             listener_executor,
+            listener_mailbox,
             timer_driver,
             fragment_size,
+            max_sequence_length,
+            type_lookup,
+            participant_message,
+            durability_service,
+            #[cfg(feature = "security")]
+            security,
         }
     }
 
@@ -60,6 +92,7 @@ impl DomainParticipantActor {
             self.domain_participant.domain_id(),
             self.domain_participant.instance_handle(),
             self.timer_driver.handle(),
+            self.max_sequence_length,
         )
     }
 
@@ -134,6 +167,7 @@ impl DomainParticipantActor {
             data_writer.status_condition().address(),
             self.get_publisher_async(participant_address.clone(), publisher_handle)?,
             self.get_topic_async(participant_address, data_writer.topic_name().to_owned())?,
+            data_writer.data_representation(),
         ))
     }
 
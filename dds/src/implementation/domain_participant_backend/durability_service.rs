@@ -0,0 +1,248 @@
+use crate::infrastructure::{
+    instance::InstanceHandle,
+    qos_policy::{
+        DurabilityQosPolicyKind, DurabilityServiceQosPolicy, HistoryQosPolicyKind, Length,
+    },
+    time::Time,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DurableSample {
+    pub instance_handle: InstanceHandle,
+    pub serialized_data: Vec<u8>,
+    pub timestamp: Time,
+}
+
+// Backend where the durability service keeps the samples of each topic. The samples are
+// always handed over in the order in which they were written.
+pub trait DurabilityStorage: Send {
+    fn load(&mut self, topic_name: &str) -> Vec<DurableSample>;
+
+    fn store(&mut self, topic_name: &str, sample_list: Vec<DurableSample>);
+}
+
+#[derive(Default)]
+pub struct InMemoryDurabilityStorage {
+    topic_list: HashMap<String, Vec<DurableSample>>,
+}
+
+impl DurabilityStorage for InMemoryDurabilityStorage {
+    fn load(&mut self, topic_name: &str) -> Vec<DurableSample> {
+        self.topic_list.get(topic_name).cloned().unwrap_or_default()
+    }
+
+    fn store(&mut self, topic_name: &str, sample_list: Vec<DurableSample>) {
+        if sample_list.is_empty() {
+            self.topic_list.remove(topic_name);
+        } else {
+            self.topic_list.insert(topic_name.to_owned(), sample_list);
+        }
+    }
+}
+
+// Keeps the samples of each topic in its own file of the persistence directory. The file name
+// is the hexadecimal representation of the topic name so that any topic name can be stored.
+pub struct FileDurabilityStorage {
+    directory: PathBuf,
+}
+
+impl FileDurabilityStorage {
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    fn topic_file_path(&self, topic_name: &str) -> PathBuf {
+        let file_name: String = topic_name.bytes().map(|b| format!("{b:02x}")).collect();
+        self.directory.join(file_name + ".dds")
+    }
+
+    fn read_sample_list(path: &Path) -> io::Result<Vec<DurableSample>> {
+        let mut reader = io::BufReader::new(std::fs::File::open(path)?);
+        let mut sample_list = Vec::new();
+        let mut instance_handle = [0; 16];
+        loop {
+            match reader.read_exact(&mut instance_handle) {
+                Ok(()) => (),
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+            let mut header = [0; 12];
+            reader.read_exact(&mut header)?;
+            let sec = i32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+            let nanosec = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+            let length = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+            let mut serialized_data = vec![0; length as usize];
+            reader.read_exact(&mut serialized_data)?;
+            sample_list.push(DurableSample {
+                instance_handle: InstanceHandle::new(instance_handle),
+                serialized_data,
+                timestamp: Time::new(sec, nanosec),
+            });
+        }
+        Ok(sample_list)
+    }
+
+    fn write_sample_list(&self, path: &Path, sample_list: &[DurableSample]) -> io::Result<()> {
+        std::fs::create_dir_all(&self.directory)?;
+        let mut buffer = Vec::new();
+        for sample in sample_list {
+            buffer.extend_from_slice(sample.instance_handle.as_ref());
+            buffer.extend_from_slice(&sample.timestamp.sec().to_le_bytes());
+            buffer.extend_from_slice(&sample.timestamp.nanosec().to_le_bytes());
+            buffer.extend_from_slice(&(sample.serialized_data.len() as u32).to_le_bytes());
+            buffer.extend_from_slice(&sample.serialized_data);
+        }
+        // The file is replaced at once so that an interrupted write doesn't corrupt the samples
+        // stored before
+        let temporary_path = path.with_extension("tmp");
+        std::fs::write(&temporary_path, buffer)?;
+        std::fs::rename(temporary_path, path)
+    }
+}
+
+impl DurabilityStorage for FileDurabilityStorage {
+    fn load(&mut self, topic_name: &str) -> Vec<DurableSample> {
+        let path = self.topic_file_path(topic_name);
+        if !path.exists() {
+            return Vec::new();
+        }
+        Self::read_sample_list(&path).unwrap_or_else(|e| {
+            tracing::warn!(?path, error = %e, "Failed to read the persistent samples");
+            Vec::new()
+        })
+    }
+
+    fn store(&mut self, topic_name: &str, sample_list: Vec<DurableSample>) {
+        let path = self.topic_file_path(topic_name);
+        let result = if sample_list.is_empty() {
+            match std::fs::remove_file(&path) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+                r => r,
+            }
+        } else {
+            self.write_sample_list(&path, &sample_list)
+        };
+        if let Err(e) = result {
+            tracing::warn!(?path, error = %e, "Failed to store the persistent samples");
+        }
+    }
+}
+
+// Keeps the samples written by the TRANSIENT and PERSISTENT writers so that they outlive the
+// writers and can be delivered to late-joining readers by the writers created afterwards
+pub struct DurabilityService {
+    transient_storage: Box<dyn DurabilityStorage>,
+    persistent_storage: Box<dyn DurabilityStorage>,
+}
+
+impl DurabilityService {
+    // Without a persistence directory the PERSISTENT samples are only kept in memory as the
+    // TRANSIENT ones
+    pub fn new(persistence_directory: Option<PathBuf>) -> Self {
+        let persistent_storage: Box<dyn DurabilityStorage> = match persistence_directory {
+            Some(directory) => Box::new(FileDurabilityStorage::new(directory)),
+            None => Box::new(InMemoryDurabilityStorage::default()),
+        };
+        Self {
+            transient_storage: Box::new(InMemoryDurabilityStorage::default()),
+            persistent_storage,
+        }
+    }
+
+    fn storage(&mut self, kind: DurabilityQosPolicyKind) -> Option<&mut dyn DurabilityStorage> {
+        match kind {
+            DurabilityQosPolicyKind::Transient => Some(self.transient_storage.as_mut()),
+            DurabilityQosPolicyKind::Persistent => Some(self.persistent_storage.as_mut()),
+            DurabilityQosPolicyKind::Volatile | DurabilityQosPolicyKind::TransientLocal => None,
+        }
+    }
+
+    pub fn sample_list(
+        &mut self,
+        kind: DurabilityQosPolicyKind,
+        topic_name: &str,
+    ) -> Vec<DurableSample> {
+        self.storage(kind)
+            .map(|s| s.load(topic_name))
+            .unwrap_or_default()
+    }
+
+    pub fn add_sample(
+        &mut self,
+        kind: DurabilityQosPolicyKind,
+        topic_name: &str,
+        qos: &DurabilityServiceQosPolicy,
+        sample: DurableSample,
+    ) {
+        let Some(storage) = self.storage(kind) else {
+            return;
+        };
+        let mut sample_list = storage.load(topic_name);
+
+        let instance_list: HashSet<_> = sample_list.iter().map(|s| s.instance_handle).collect();
+        if !instance_list.contains(&sample.instance_handle)
+            && instance_list.len() >= qos.max_instances
+        {
+            return;
+        }
+
+        let depth = match qos.history_kind {
+            HistoryQosPolicyKind::KeepLast(depth) => depth as usize,
+            HistoryQosPolicyKind::KeepAll => usize::MAX,
+        };
+        let max_samples_per_instance = match qos.max_samples_per_instance {
+            Length::Limited(max_samples_per_instance) => {
+                depth.min(max_samples_per_instance as usize)
+            }
+            Length::Unlimited => depth,
+        };
+        // The oldest samples are discarded to make room for the new one
+        while sample_list
+            .iter()
+            .filter(|s| s.instance_handle == sample.instance_handle)
+            .count()
+            >= max_samples_per_instance
+        {
+            let Some(oldest_sample_index) = sample_list
+                .iter()
+                .position(|s| s.instance_handle == sample.instance_handle)
+            else {
+                break;
+            };
+            sample_list.remove(oldest_sample_index);
+        }
+        while !sample_list.is_empty() && sample_list.len() >= qos.max_samples {
+            sample_list.remove(0);
+        }
+        if max_samples_per_instance > 0 && qos.max_samples != 0 {
+            sample_list.push(sample);
+        }
+
+        storage.store(topic_name, sample_list);
+    }
+
+    // Removes the samples of the instance written up to the given time, which keeps the samples
+    // written after the instance was disposed
+    pub fn remove_instance(
+        &mut self,
+        kind: DurabilityQosPolicyKind,
+        topic_name: &str,
+        instance_handle: InstanceHandle,
+        timestamp: Time,
+    ) {
+        let Some(storage) = self.storage(kind) else {
+            return;
+        };
+        let mut sample_list = storage.load(topic_name);
+        let sample_count = sample_list.len();
+        sample_list.retain(|s| s.instance_handle != instance_handle || s.timestamp > timestamp);
+        if sample_list.len() != sample_count {
+            storage.store(topic_name, sample_list);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Bound,
     sync::Arc,
 };
 
@@ -10,6 +11,7 @@ use crate::{
         status_condition::status_condition_actor::{self, StatusConditionActor},
         xtypes_glue::key_and_instance_handle::{
             get_instance_handle_from_serialized_foo, get_instance_handle_from_serialized_key,
+            get_serialized_key_from_serialized_foo, set_key_in_serialized_foo,
         },
     },
     infrastructure::{
@@ -20,14 +22,15 @@ use crate::{
             DestinationOrderQosPolicyKind, HistoryQosPolicyKind, OwnershipQosPolicyKind,
             QosPolicyId,
         },
+        statistics::{DataReaderStatistics, InstanceSampleCount},
         status::{
             LivelinessChangedStatus, QosPolicyCount, RequestedDeadlineMissedStatus,
             RequestedIncompatibleQosStatus, SampleLostStatus, SampleRejectedStatus,
             SampleRejectedStatusKind, StatusKind, SubscriptionMatchedStatus,
         },
-        time::{DurationKind, Time},
+        time::{Duration, DurationKind, Time},
     },
-    runtime::{actor::Actor, executor::TaskHandle},
+    runtime::{actor::Actor, timer::TimerTaskHandle},
     subscription::sample_info::{InstanceStateKind, SampleInfo, SampleStateKind, ViewStateKind},
     transport::{
         history_cache::CacheChange,
@@ -50,6 +53,13 @@ struct InstanceState {
     instance_state: InstanceStateKind,
     most_recent_disposed_generation_count: i32,
     most_recent_no_writers_generation_count: i32,
+    last_sample_timestamp: Option<Time>,
+    // Timestamp of the most recent change of the instance, either with data or changing its state
+    latest_change_timestamp: Option<Time>,
+    sample_count: usize,
+    read_sample_count: usize,
+    // Data of a sample of the instance from which its key is retrieved
+    sample_data: Option<Arc<[u8]>>,
 }
 
 impl InstanceState {
@@ -59,9 +69,31 @@ impl InstanceState {
             instance_state: InstanceStateKind::Alive,
             most_recent_disposed_generation_count: 0,
             most_recent_no_writers_generation_count: 0,
+            last_sample_timestamp: None,
+            latest_change_timestamp: None,
+            sample_count: 0,
+            read_sample_count: 0,
+            sample_data: None,
         }
     }
 
+    fn has_sample_in_states(&self, sample_states: &[SampleStateKind]) -> bool {
+        (sample_states.contains(&SampleStateKind::Read) && self.read_sample_count > 0)
+            || (sample_states.contains(&SampleStateKind::NotRead)
+                && self.sample_count > self.read_sample_count)
+    }
+
+    fn is_in_states(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> bool {
+        view_states.contains(&self.view_state)
+            && instance_states.contains(&self.instance_state)
+            && self.has_sample_in_states(sample_states)
+    }
+
     fn update_state(&mut self, change_kind: ChangeKind) {
         match self.instance_state {
             InstanceStateKind::Alive => {
@@ -115,6 +147,16 @@ pub struct ReaderSample {
     pub disposed_generation_count: i32,
     pub no_writers_generation_count: i32,
     pub reception_timestamp: Time,
+    pub lifespan: DurationKind,
+}
+
+impl ReaderSample {
+    // The lifespan is counted from the source timestamp and, if the writer did not send it, from
+    // the reception timestamp
+    fn is_expired(&self, now: Time) -> bool {
+        let timestamp = self.source_timestamp.unwrap_or(self.reception_timestamp);
+        DurationKind::Finite(now - timestamp) >= self.lifespan
+    }
 }
 
 pub struct IndexedSample {
@@ -143,21 +185,28 @@ pub struct DataReaderEntity {
     topic_name: String,
     type_name: String,
     type_support: Arc<dyn DynamicType + Send + Sync>,
-    _liveliness_changed_status: LivelinessChangedStatus,
+    liveliness_changed_status: LivelinessChangedStatus,
     requested_deadline_missed_status: RequestedDeadlineMissedStatus,
     requested_incompatible_qos_status: RequestedIncompatibleQosStatus,
-    _sample_lost_status: SampleLostStatus,
+    sample_lost_status: SampleLostStatus,
     sample_rejected_status: SampleRejectedStatus,
     subscription_matched_status: SubscriptionMatchedStatus,
     matched_publication_list: HashMap<InstanceHandle, PublicationBuiltinTopicData>,
+    alive_publication_list: HashSet<InstanceHandle>,
+    not_alive_publication_list: HashSet<InstanceHandle>,
+    publication_lease_list: HashMap<InstanceHandle, (u64, TimerTaskHandle)>,
+    publication_lease_counter: u64,
     enabled: bool,
     data_available_status_changed_flag: bool,
     incompatible_writer_list: HashSet<InstanceHandle>,
     status_condition: Actor<StatusConditionActor>,
     listener: Option<Actor<DataReaderListenerActor>>,
     listener_mask: Vec<StatusKind>,
-    instances: HashMap<InstanceHandle, InstanceState>,
-    instance_deadline_missed_task: HashMap<InstanceHandle, TaskHandle>,
+    // Ordered by handle and keeping the number of samples of each instance in the sample list so
+    // that the instances with samples can be found without going through the samples
+    instances: BTreeMap<InstanceHandle, InstanceState>,
+    instance_deadline_missed_task: HashMap<InstanceHandle, TimerTaskHandle>,
+    instance_autopurge_task: HashMap<InstanceHandle, TimerTaskHandle>,
     instance_ownership: HashMap<InstanceHandle, [u8; 16]>,
     transport_reader: TransportReaderKind,
 }
@@ -182,21 +231,26 @@ impl DataReaderEntity {
             topic_name,
             type_name,
             type_support,
-            _liveliness_changed_status: LivelinessChangedStatus::default(),
+            liveliness_changed_status: LivelinessChangedStatus::default(),
             requested_deadline_missed_status: RequestedDeadlineMissedStatus::default(),
             requested_incompatible_qos_status: RequestedIncompatibleQosStatus::default(),
-            _sample_lost_status: SampleLostStatus::default(),
+            sample_lost_status: SampleLostStatus::default(),
             sample_rejected_status: SampleRejectedStatus::default(),
             subscription_matched_status: SubscriptionMatchedStatus::default(),
             matched_publication_list: HashMap::new(),
+            alive_publication_list: HashSet::new(),
+            not_alive_publication_list: HashSet::new(),
+            publication_lease_list: HashMap::new(),
+            publication_lease_counter: 0,
             enabled: false,
             data_available_status_changed_flag: false,
             incompatible_writer_list: HashSet::new(),
             status_condition,
             listener,
             listener_mask,
-            instances: HashMap::new(),
+            instances: BTreeMap::new(),
             instance_deadline_missed_task: HashMap::new(),
+            instance_autopurge_task: HashMap::new(),
             instance_ownership: HashMap::new(),
             transport_reader,
         }
@@ -217,7 +271,9 @@ impl DataReaderEntity {
         self.status_condition
             .send_actor_mail(status_condition_actor::RemoveCommunicationState {
                 state: StatusKind::DataAvailable,
-            });
+            })
+            .ok();
+        self.data_available_status_changed_flag = false;
 
         let indexed_sample_list = self.create_indexed_sample_collection(
             max_samples,
@@ -236,7 +292,7 @@ impl DataReaderEntity {
             .unzip();
 
         for index in change_index_list {
-            self.sample_list[index].sample_state = SampleStateKind::Read;
+            self.mark_sample_read(index);
         }
 
         Ok(samples)
@@ -265,7 +321,9 @@ impl DataReaderEntity {
         self.status_condition
             .send_actor_mail(status_condition_actor::RemoveCommunicationState {
                 state: StatusKind::DataAvailable,
-            });
+            })
+            .ok();
+        self.data_available_status_changed_flag = false;
 
         let mut change_index_list: Vec<usize>;
         let samples;
@@ -276,12 +334,80 @@ impl DataReaderEntity {
             .unzip();
 
         while let Some(index) = change_index_list.pop() {
-            self.sample_list.remove(index);
+            self.remove_sample(index);
         }
 
         Ok(samples)
     }
 
+    pub fn has_sample_in_states(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> bool {
+        self.instances
+            .values()
+            .any(|i| i.is_in_states(sample_states, view_states, instance_states))
+    }
+
+    fn push_sample(&mut self, sample: ReaderSample) {
+        let instance = self
+            .instances
+            .get_mut(&sample.instance_handle)
+            .expect("Instance must exist before its samples are added");
+        instance.sample_count += 1;
+        if sample.sample_state == SampleStateKind::Read {
+            instance.read_sample_count += 1;
+        }
+        self.sample_list.push(sample);
+    }
+
+    fn remove_sample(&mut self, index: usize) -> ReaderSample {
+        let sample = self.sample_list.remove(index);
+        if let Some(instance) = self.instances.get_mut(&sample.instance_handle) {
+            instance.sample_count -= 1;
+            if sample.sample_state == SampleStateKind::Read {
+                instance.read_sample_count -= 1;
+            }
+        }
+        sample
+    }
+
+    fn mark_sample_read(&mut self, index: usize) {
+        let sample = &mut self.sample_list[index];
+        if sample.sample_state == SampleStateKind::NotRead {
+            sample.sample_state = SampleStateKind::Read;
+            if let Some(instance) = self.instances.get_mut(&sample.instance_handle) {
+                instance.read_sample_count += 1;
+            }
+        }
+    }
+
+    // Time ordering the samples matching the states among the samples of the other readers of the
+    // subscriber, listed in the order in which the samples are read
+    pub fn sample_order_timestamp_list(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> Vec<Time> {
+        self.sample_list
+            .iter()
+            .filter(|cc| {
+                sample_states.contains(&cc.sample_state)
+                    && view_states.contains(&self.instances[&cc.instance_handle].view_state)
+                    && instance_states.contains(&self.instances[&cc.instance_handle].instance_state)
+            })
+            .map(|cc| match self.qos.destination_order.kind {
+                DestinationOrderQosPolicyKind::BySourceTimestamp => {
+                    cc.source_timestamp.unwrap_or(cc.reception_timestamp)
+                }
+                DestinationOrderQosPolicyKind::ByReceptionTimestamp => cc.reception_timestamp,
+            })
+            .collect()
+    }
+
     fn create_indexed_sample_collection(
         &mut self,
         max_samples: i32,
@@ -291,8 +417,13 @@ impl DataReaderEntity {
         specific_instance_handle: Option<InstanceHandle>,
     ) -> DdsResult<Vec<IndexedSample>> {
         if let Some(h) = specific_instance_handle {
-            if !self.instances.contains_key(&h) {
-                return Err(DdsError::BadParameter);
+            match self.instances.get(&h) {
+                Some(instance) => {
+                    if !instance.is_in_states(sample_states, view_states, instance_states) {
+                        return Err(DdsError::NoData);
+                    }
+                }
+                None => return Err(DdsError::BadParameter),
             }
         };
 
@@ -424,11 +555,58 @@ impl DataReaderEntity {
         }
     }
 
-    fn next_instance(&mut self, previous_handle: Option<InstanceHandle>) -> Option<InstanceHandle> {
-        match previous_handle {
-            Some(p) => self.instances.keys().filter(|&h| h > &p).min().cloned(),
-            None => self.instances.keys().min().cloned(),
+    pub fn lookup_instance(&self, serialized_foo: &[u8]) -> DdsResult<Option<InstanceHandle>> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        let instance_handle =
+            get_instance_handle_from_serialized_foo(serialized_foo, self.type_support.as_ref())?;
+        Ok(self
+            .instances
+            .contains_key(&instance_handle)
+            .then_some(instance_handle))
+    }
+
+    pub fn get_key_value(
+        &self,
+        serialized_key_holder: &[u8],
+        instance_handle: InstanceHandle,
+    ) -> DdsResult<Vec<u8>> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
         }
+
+        let sample_data = self
+            .instances
+            .get(&instance_handle)
+            .and_then(|i| i.sample_data.as_ref())
+            .ok_or(DdsError::BadParameter)?;
+        let serialized_key =
+            get_serialized_key_from_serialized_foo(sample_data, self.type_support.as_ref())?;
+        set_key_in_serialized_foo(
+            serialized_key_holder,
+            &serialized_key,
+            self.type_support.as_ref(),
+        )
+    }
+
+    // Smallest instance handle after the previous one among the instances with samples matching the states
+    fn next_instance(
+        &self,
+        previous_handle: Option<InstanceHandle>,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> Option<InstanceHandle> {
+        let lower_bound = match previous_handle {
+            Some(p) => Bound::Excluded(p),
+            None => Bound::Unbounded,
+        };
+        self.instances
+            .range((lower_bound, Bound::Unbounded))
+            .find(|(_, i)| i.is_in_states(sample_states, view_states, instance_states))
+            .map(|(handle, _)| *handle)
     }
 
     pub fn take_next_instance(
@@ -443,7 +621,12 @@ impl DataReaderEntity {
             return Err(DdsError::NotEnabled);
         }
 
-        match self.next_instance(previous_handle) {
+        match self.next_instance(
+            previous_handle,
+            &sample_states,
+            &view_states,
+            &instance_states,
+        ) {
             Some(next_handle) => self.take(
                 max_samples,
                 sample_states,
@@ -467,7 +650,7 @@ impl DataReaderEntity {
             return Err(DdsError::NotEnabled);
         }
 
-        match self.next_instance(previous_handle) {
+        match self.next_instance(previous_handle, sample_states, view_states, instance_states) {
             Some(next_handle) => self.read(
                 max_samples,
                 sample_states,
@@ -479,12 +662,15 @@ impl DataReaderEntity {
         }
     }
     fn convert_cache_change_to_sample(
-        &mut self,
+        &self,
         cache_change: CacheChange,
         reception_timestamp: Time,
     ) -> DdsResult<ReaderSample> {
-        let instance_handle = {
-            match cache_change.kind {
+        // The key hash sent by the writer identifies the instance directly so the key only
+        // needs to be deserialized when it is missing
+        let instance_handle = match cache_change.instance_handle {
+            Some(key_hash) => InstanceHandle::new(key_hash),
+            None => match cache_change.kind {
                 ChangeKind::Alive | ChangeKind::AliveFiltered => {
                     get_instance_handle_from_serialized_foo(
                         cache_change.data_value.as_ref(),
@@ -493,40 +679,46 @@ impl DataReaderEntity {
                 }
                 ChangeKind::NotAliveDisposed
                 | ChangeKind::NotAliveUnregistered
-                | ChangeKind::NotAliveDisposedUnregistered => match cache_change.instance_handle {
-                    Some(i) => InstanceHandle::new(i),
-                    None => get_instance_handle_from_serialized_key(
+                | ChangeKind::NotAliveDisposedUnregistered => {
+                    get_instance_handle_from_serialized_key(
                         cache_change.data_value.as_ref(),
                         self.type_support.as_ref(),
-                    )?,
-                },
-            }
+                    )?
+                }
+            },
         };
 
-        // Update the state of the instance before creating since this has direct impact on
-        // the information that is store on the sample
-        match cache_change.kind {
-            ChangeKind::Alive | ChangeKind::AliveFiltered => {
-                self.instances
-                    .entry(instance_handle)
-                    .or_insert_with(InstanceState::new)
-                    .update_state(cache_change.kind);
-                Ok(())
-            }
-            ChangeKind::NotAliveDisposed
-            | ChangeKind::NotAliveUnregistered
-            | ChangeKind::NotAliveDisposedUnregistered => {
-                match self.instances.get_mut(&instance_handle) {
-                    Some(instance) => {
-                        instance.update_state(cache_change.kind);
-                        Ok(())
+        // The generation counts of the sample are the ones the instance has once the sample is
+        // added. The instance itself is only updated when the sample is accepted
+        let (disposed_generation_count, no_writers_generation_count) =
+            match (self.instances.get(&instance_handle), cache_change.kind) {
+                (Some(instance), ChangeKind::Alive | ChangeKind::AliveFiltered) => {
+                    match instance.instance_state {
+                        InstanceStateKind::Alive => (
+                            instance.most_recent_disposed_generation_count,
+                            instance.most_recent_no_writers_generation_count,
+                        ),
+                        InstanceStateKind::NotAliveDisposed => (
+                            instance.most_recent_disposed_generation_count + 1,
+                            instance.most_recent_no_writers_generation_count,
+                        ),
+                        InstanceStateKind::NotAliveNoWriters => (
+                            instance.most_recent_disposed_generation_count,
+                            instance.most_recent_no_writers_generation_count + 1,
+                        ),
                     }
-                    None => Err(DdsError::Error(
+                }
+                (Some(instance), _) => (
+                    instance.most_recent_disposed_generation_count,
+                    instance.most_recent_no_writers_generation_count,
+                ),
+                (None, ChangeKind::Alive | ChangeKind::AliveFiltered) => (0, 0),
+                (None, _) => {
+                    return Err(DdsError::Error(
                         "Received message changing state of unknown instance".to_string(),
-                    )),
+                    ))
                 }
-            }
-        }?;
+            };
 
         Ok(ReaderSample {
             kind: cache_change.kind,
@@ -535,11 +727,13 @@ impl DataReaderEntity {
             source_timestamp: cache_change.source_timestamp.map(Into::into),
             data_value: cache_change.data_value.clone(),
             sample_state: SampleStateKind::NotRead,
-            disposed_generation_count: self.instances[&instance_handle]
-                .most_recent_disposed_generation_count,
-            no_writers_generation_count: self.instances[&instance_handle]
-                .most_recent_no_writers_generation_count,
+            disposed_generation_count,
+            no_writers_generation_count,
             reception_timestamp,
+            lifespan: self
+                .matched_publication_list
+                .get(&InstanceHandle::new(cache_change.writer_guid.into()))
+                .map_or(DurationKind::Infinite, |p| p.lifespan().duration),
         })
     }
 
@@ -550,6 +744,23 @@ impl DataReaderEntity {
     ) -> DdsResult<AddChangeResult> {
         let sample = self.convert_cache_change_to_sample(cache_change, reception_timestamp)?;
         let change_instance_handle = sample.instance_handle;
+        if sample.is_expired(reception_timestamp) {
+            return Ok(AddChangeResult::NotAdded);
+        }
+        // Ordering by source timestamp, a change older than the latest one of the instance is
+        // discarded so that all the readers end up with the same final value
+        let sample_timestamp = sample
+            .source_timestamp
+            .unwrap_or(sample.reception_timestamp);
+        if self.qos.destination_order.kind == DestinationOrderQosPolicyKind::BySourceTimestamp
+            && self
+                .instances
+                .get(&sample.instance_handle)
+                .and_then(|i| i.latest_change_timestamp)
+                .is_some_and(|t| sample_timestamp < t)
+        {
+            return Ok(AddChangeResult::NotAdded);
+        }
         // data_reader exclusive access if the writer is not the allowed to write the sample do an early return
         if self.qos.ownership.kind == OwnershipQosPolicyKind::Exclusive {
             // Get the InstanceHandle of the data writer owning this instance
@@ -583,106 +794,109 @@ impl DataReaderEntity {
             self.instance_ownership.remove(&sample.instance_handle);
         }
 
-        let is_sample_of_interest_based_on_time = {
-            let closest_timestamp_before_received_sample = self
-                .sample_list
-                .iter()
-                .filter(|cc| cc.instance_handle == sample.instance_handle)
-                .filter(|cc| cc.source_timestamp <= sample.source_timestamp)
-                .map(|cc| cc.source_timestamp)
-                .max();
-
-            if let Some(Some(t)) = closest_timestamp_before_received_sample {
-                if let Some(sample_source_time) = sample.source_timestamp {
-                    let sample_separation = sample_source_time - t;
-                    DurationKind::Finite(sample_separation)
-                        >= self.qos.time_based_filter.minimum_separation
-                } else {
-                    true
-                }
-            } else {
-                true
+        // The timestamp of the last accepted sample is kept in the instance so that the filter
+        // keeps applying after the previous samples are taken
+        let minimum_separation = self.qos.time_based_filter.minimum_separation;
+        let is_sample_of_interest_based_on_time = match self
+            .instances
+            .get(&sample.instance_handle)
+            .and_then(|i| i.last_sample_timestamp)
+        {
+            Some(last_sample_timestamp)
+                if sample.kind == ChangeKind::Alive
+                    && minimum_separation > DurationKind::Finite(Duration::new(0, 0)) =>
+            {
+                DurationKind::Finite(sample_timestamp - last_sample_timestamp) >= minimum_separation
             }
+            _ => true,
         };
 
         if !is_sample_of_interest_based_on_time {
             return Ok(AddChangeResult::NotAdded);
         }
 
-        let is_max_samples_limit_reached = {
-            let total_samples = self
+        let num_alive_samples_of_instance = self
+            .sample_list
+            .iter()
+            .filter(|cc| {
+                cc.instance_handle == sample.instance_handle && cc.kind == ChangeKind::Alive
+            })
+            .count();
+        // With KEEP_LAST the oldest sample of the instance makes room for the new one once the
+        // history depth is reached so the resource limits are not exceeded
+        let is_oldest_sample_replaced = matches!(
+            self.qos.history.kind,
+            HistoryQosPolicyKind::KeepLast(depth) if depth as usize == num_alive_samples_of_instance
+        );
+
+        // Only the samples with data take up the resources of the reader. The instances which are
+        // not alive and have no samples left are not counted since they can be reclaimed
+        if sample.kind == ChangeKind::Alive && !is_oldest_sample_replaced {
+            let num_alive_samples = self
                 .sample_list
                 .iter()
                 .filter(|cc| cc.kind == ChangeKind::Alive)
                 .count();
+            let is_instance_in_use = |i: &InstanceState| {
+                i.instance_state == InstanceStateKind::Alive || i.sample_count > 0
+            };
+            let is_new_instance = !self
+                .instances
+                .get(&sample.instance_handle)
+                .is_some_and(is_instance_in_use);
+            let num_instances = self
+                .instances
+                .values()
+                .filter(|i| is_instance_in_use(i))
+                .count();
 
-            total_samples == self.qos.resource_limits.max_samples
-        };
-        let is_max_instances_limit_reached = {
-            let instance_handle_list: HashSet<_> = self
-                .sample_list
-                .iter()
-                .map(|cc| cc.instance_handle)
-                .collect();
-
-            if instance_handle_list.contains(&sample.instance_handle) {
-                false
+            let rejected_status_kind = if num_alive_samples >= self.qos.resource_limits.max_samples
+            {
+                Some(SampleRejectedStatusKind::RejectedBySamplesLimit)
+            } else if is_new_instance && num_instances >= self.qos.resource_limits.max_instances {
+                Some(SampleRejectedStatusKind::RejectedByInstancesLimit)
+            } else if num_alive_samples_of_instance
+                >= self.qos.resource_limits.max_samples_per_instance
+            {
+                Some(SampleRejectedStatusKind::RejectedBySamplesPerInstanceLimit)
             } else {
-                instance_handle_list.len() == self.qos.resource_limits.max_instances
+                None
+            };
+            if let Some(rejected_status_kind) = rejected_status_kind {
+                return Ok(AddChangeResult::Rejected(
+                    sample.instance_handle,
+                    rejected_status_kind,
+                ));
             }
-        };
-        let is_max_samples_per_instance_limit_reached = {
-            let total_samples_of_instance = self
+        }
+
+        if is_oldest_sample_replaced {
+            let index_sample_to_remove = self
                 .sample_list
                 .iter()
-                .filter(|cc| cc.instance_handle == sample.instance_handle)
-                .count();
-
-            total_samples_of_instance == self.qos.resource_limits.max_samples_per_instance
-        };
-        if is_max_samples_limit_reached {
-            return Ok(AddChangeResult::Rejected(
-                sample.instance_handle,
-                SampleRejectedStatusKind::RejectedBySamplesLimit,
-            ));
-        } else if is_max_instances_limit_reached {
-            return Ok(AddChangeResult::Rejected(
-                sample.instance_handle,
-                SampleRejectedStatusKind::RejectedByInstancesLimit,
-            ));
-        } else if is_max_samples_per_instance_limit_reached {
-            return Ok(AddChangeResult::Rejected(
-                sample.instance_handle,
-                SampleRejectedStatusKind::RejectedBySamplesPerInstanceLimit,
-            ));
-        }
-        let num_alive_samples_of_instance = self
-            .sample_list
-            .iter()
-            .filter(|cc| {
-                cc.instance_handle == sample.instance_handle && cc.kind == ChangeKind::Alive
-            })
-            .count() as u32;
-
-        if let HistoryQosPolicyKind::KeepLast(depth) = self.qos.history.kind {
-            if depth == num_alive_samples_of_instance {
-                let index_sample_to_remove = self
-                    .sample_list
-                    .iter()
-                    .position(|cc| {
-                        cc.instance_handle == sample.instance_handle && cc.kind == ChangeKind::Alive
-                    })
-                    .expect("Samples must exist");
-                self.sample_list.remove(index_sample_to_remove);
-            }
+                .position(|cc| {
+                    cc.instance_handle == sample.instance_handle && cc.kind == ChangeKind::Alive
+                })
+                .expect("Samples must exist");
+            self.remove_sample(index_sample_to_remove);
         }
 
         match sample.kind {
             ChangeKind::Alive | ChangeKind::AliveFiltered => {
-                self.instances
+                let instance = self
+                    .instances
                     .entry(sample.instance_handle)
-                    .or_insert_with(InstanceState::new)
-                    .update_state(sample.kind);
+                    .or_insert_with(InstanceState::new);
+                instance.update_state(sample.kind);
+                instance.last_sample_timestamp = Some(sample_timestamp);
+                instance.latest_change_timestamp = Some(sample_timestamp);
+                instance
+                    .sample_data
+                    .get_or_insert_with(|| sample.data_value.clone());
+                // An instance which becomes alive again is no longer purged
+                if let Some(t) = self.instance_autopurge_task.remove(&sample.instance_handle) {
+                    t.abort();
+                }
                 Ok(())
             }
             ChangeKind::NotAliveDisposed
@@ -691,6 +905,7 @@ impl DataReaderEntity {
                 match self.instances.get_mut(&sample.instance_handle) {
                     Some(instance) => {
                         instance.update_state(sample.kind);
+                        instance.latest_change_timestamp = Some(sample_timestamp);
                         Ok(())
                     }
                     None => Err(DdsError::Error(
@@ -701,22 +916,14 @@ impl DataReaderEntity {
         }?;
 
         tracing::debug!(cache_change = ?sample, "Adding change to data reader history cache");
-        self.sample_list.push(sample);
+        self.push_sample(sample);
         self.data_available_status_changed_flag = true;
 
         match self.qos.destination_order.kind {
-            DestinationOrderQosPolicyKind::BySourceTimestamp => {
-                self.sample_list.sort_by(|a, b| {
-                    a.source_timestamp
-                        .as_ref()
-                        .expect("Missing source timestamp")
-                        .cmp(
-                            b.source_timestamp
-                                .as_ref()
-                                .expect("Missing source timestamp"),
-                        )
-                });
-            }
+            // Samples received without a source timestamp are ordered by their reception time
+            DestinationOrderQosPolicyKind::BySourceTimestamp => self
+                .sample_list
+                .sort_by_key(|s| s.source_timestamp.unwrap_or(s.reception_timestamp)),
             DestinationOrderQosPolicyKind::ByReceptionTimestamp => self
                 .sample_list
                 .sort_by(|a, b| a.reception_timestamp.cmp(&b.reception_timestamp)),
@@ -732,6 +939,67 @@ impl DataReaderEntity {
         Ok(AddChangeResult::Added(change_instance_handle))
     }
 
+    pub fn remove_expired_samples(&mut self, now: Time) {
+        while let Some(index) = self.sample_list.iter().position(|s| s.is_expired(now)) {
+            self.remove_sample(index);
+        }
+    }
+
+    /// Returns the delay after which the instance must be purged according to the reader data
+    /// lifecycle QoS if it is not alive and its purge is not already scheduled
+    pub fn instance_autopurge_delay(&self, instance_handle: &InstanceHandle) -> Option<Duration> {
+        if self.instance_autopurge_task.contains_key(instance_handle) {
+            return None;
+        }
+        let delay = match self.instances.get(instance_handle)?.instance_state {
+            InstanceStateKind::Alive => return None,
+            InstanceStateKind::NotAliveDisposed => {
+                self.qos
+                    .reader_data_lifecycle
+                    .autopurge_disposed_samples_delay
+            }
+            InstanceStateKind::NotAliveNoWriters => {
+                self.qos
+                    .reader_data_lifecycle
+                    .autopurge_nowriter_samples_delay
+            }
+        };
+        match delay {
+            DurationKind::Finite(delay) => Some(delay),
+            DurationKind::Infinite => None,
+        }
+    }
+
+    pub fn insert_instance_autopurge_task(
+        &mut self,
+        instance_handle: InstanceHandle,
+        task: TimerTaskHandle,
+    ) {
+        if let Some(previous_task) = self.instance_autopurge_task.insert(instance_handle, task) {
+            previous_task.abort();
+        }
+    }
+
+    /// Removes the samples and all the information kept about the instance unless it became
+    /// alive again
+    pub fn purge_instance(&mut self, instance_handle: &InstanceHandle) {
+        self.instance_autopurge_task.remove(instance_handle);
+        if self
+            .instances
+            .get(instance_handle)
+            .is_none_or(|i| i.instance_state == InstanceStateKind::Alive)
+        {
+            return;
+        }
+        self.sample_list
+            .retain(|s| &s.instance_handle != instance_handle);
+        self.instances.remove(instance_handle);
+        self.instance_ownership.remove(instance_handle);
+        if let Some(t) = self.instance_deadline_missed_task.remove(instance_handle) {
+            t.abort();
+        }
+    }
+
     pub fn instance_handle(&self) -> InstanceHandle {
         self.instance_handle
     }
@@ -771,25 +1039,133 @@ impl DataReaderEntity {
         &mut self,
         publication_builtin_topic_data: PublicationBuiltinTopicData,
     ) {
-        self.matched_publication_list.insert(
-            InstanceHandle::new(publication_builtin_topic_data.key.value),
-            publication_builtin_topic_data,
-        );
-        self.subscription_matched_status.current_count +=
-            self.matched_publication_list.len() as i32;
-        self.subscription_matched_status.current_count_change += 1;
-        self.subscription_matched_status.total_count += 1;
-        self.subscription_matched_status.total_count_change += 1;
+        // A publication announced again, as when its QoS changes, only has its data updated
+        if self
+            .matched_publication_list
+            .insert(
+                InstanceHandle::new(publication_builtin_topic_data.key.value),
+                publication_builtin_topic_data,
+            )
+            .is_none()
+        {
+            self.subscription_matched_status.current_count =
+                self.matched_publication_list.len() as i32;
+            self.subscription_matched_status.current_count_change += 1;
+            self.subscription_matched_status.total_count += 1;
+            self.subscription_matched_status.total_count_change += 1;
+        }
     }
 
-    pub fn remove_matched_publication(&mut self, publication_handle: &InstanceHandle) {
+    /// Returns whether the liveliness of the reader changed because the publication was alive
+    pub fn remove_matched_publication(&mut self, publication_handle: &InstanceHandle) -> bool {
         self.matched_publication_list.remove(publication_handle);
         self.subscription_matched_status.current_count = self.matched_publication_list.len() as i32;
         self.subscription_matched_status.current_count_change -= 1;
         self.status_condition
             .send_actor_mail(status_condition_actor::AddCommunicationState {
                 state: StatusKind::SubscriptionMatched,
-            });
+            })
+            .ok();
+
+        if let Some((_, lease_task)) = self.publication_lease_list.remove(publication_handle) {
+            lease_task.abort();
+        }
+        if self.alive_publication_list.remove(publication_handle) {
+            self.liveliness_changed_status.alive_count -= 1;
+            self.liveliness_changed_status.alive_count_change -= 1;
+        } else if self.not_alive_publication_list.remove(publication_handle) {
+            self.liveliness_changed_status.not_alive_count -= 1;
+            self.liveliness_changed_status.not_alive_count_change -= 1;
+        } else {
+            return false;
+        }
+        self.liveliness_changed_status.last_publication_handle = *publication_handle;
+        self.status_condition
+            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                state: StatusKind::LivelinessChanged,
+            })
+            .ok();
+        true
+    }
+
+    /// Returns whether the liveliness of the reader changed because the publication was not alive
+    pub fn assert_matched_publication_liveliness(
+        &mut self,
+        publication_handle: InstanceHandle,
+    ) -> bool {
+        if !self
+            .matched_publication_list
+            .contains_key(&publication_handle)
+            || !self.alive_publication_list.insert(publication_handle)
+        {
+            return false;
+        }
+        self.liveliness_changed_status.alive_count += 1;
+        self.liveliness_changed_status.alive_count_change += 1;
+        if self.not_alive_publication_list.remove(&publication_handle) {
+            self.liveliness_changed_status.not_alive_count -= 1;
+            self.liveliness_changed_status.not_alive_count_change -= 1;
+        }
+        self.liveliness_changed_status.last_publication_handle = publication_handle;
+        self.status_condition
+            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                state: StatusKind::LivelinessChanged,
+            })
+            .ok();
+        true
+    }
+
+    /// Returns whether the liveliness of the reader changed because the publication was alive
+    pub fn set_matched_publication_not_alive(
+        &mut self,
+        publication_handle: InstanceHandle,
+    ) -> bool {
+        if !self.alive_publication_list.remove(&publication_handle) {
+            return false;
+        }
+        self.not_alive_publication_list.insert(publication_handle);
+        self.liveliness_changed_status.alive_count -= 1;
+        self.liveliness_changed_status.alive_count_change -= 1;
+        self.liveliness_changed_status.not_alive_count += 1;
+        self.liveliness_changed_status.not_alive_count_change += 1;
+        self.liveliness_changed_status.last_publication_handle = publication_handle;
+        self.status_condition
+            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                state: StatusKind::LivelinessChanged,
+            })
+            .ok();
+        true
+    }
+
+    pub fn generate_publication_lease_id(&mut self) -> u64 {
+        self.publication_lease_counter += 1;
+        self.publication_lease_counter
+    }
+
+    /// Replace the liveliness lease of the matched publication, cancelling the timer of the
+    /// previous one
+    pub fn renew_publication_lease(
+        &mut self,
+        publication_handle: InstanceHandle,
+        lease_id: u64,
+        lease_task: TimerTaskHandle,
+    ) {
+        if let Some((_, previous_lease_task)) = self
+            .publication_lease_list
+            .insert(publication_handle, (lease_id, lease_task))
+        {
+            previous_lease_task.abort();
+        }
+    }
+
+    pub fn is_publication_lease_current(
+        &self,
+        publication_handle: &InstanceHandle,
+        lease_id: u64,
+    ) -> bool {
+        self.publication_lease_list
+            .get(publication_handle)
+            .is_some_and(|(id, _)| *id == lease_id)
     }
 
     pub fn increment_requested_deadline_missed_status(&mut self, instance_handle: InstanceHandle) {
@@ -866,6 +1242,57 @@ impl DataReaderEntity {
         status
     }
 
+    pub fn increment_sample_lost_status(&mut self, lost_changes_count: i32) {
+        self.sample_lost_status.total_count += lost_changes_count;
+        self.sample_lost_status.total_count_change += lost_changes_count;
+    }
+
+    pub fn sample_lost_total_count(&self) -> i32 {
+        self.sample_lost_status.total_count
+    }
+
+    pub fn get_sample_lost_status(&mut self) -> SampleLostStatus {
+        let status = self.sample_lost_status.clone();
+        self.sample_lost_status.total_count_change = 0;
+
+        status
+    }
+
+    pub fn get_liveliness_changed_status(&mut self) -> LivelinessChangedStatus {
+        let status = self.liveliness_changed_status.clone();
+        self.liveliness_changed_status.alive_count_change = 0;
+        self.liveliness_changed_status.not_alive_count_change = 0;
+
+        status
+    }
+
+    pub fn get_statistics(&self) -> DataReaderStatistics {
+        let count_instances = |kind: InstanceStateKind| {
+            self.instances
+                .values()
+                .filter(|i| i.instance_state == kind)
+                .count()
+        };
+        let samples_per_instance = self
+            .instances
+            .iter()
+            .filter(|(_, i)| i.sample_count > 0)
+            .map(|(instance_handle, i)| InstanceSampleCount {
+                instance_handle: *instance_handle,
+                sample_count: i.sample_count,
+            })
+            .collect();
+        DataReaderStatistics {
+            instance_count: self.instances.len(),
+            alive_instance_count: count_instances(InstanceStateKind::Alive),
+            disposed_instance_count: count_instances(InstanceStateKind::NotAliveDisposed),
+            no_writers_instance_count: count_instances(InstanceStateKind::NotAliveNoWriters),
+            samples_per_instance,
+            history_sample_count: self.sample_list.len(),
+            history_max_samples: self.qos.resource_limits.max_samples,
+        }
+    }
+
     pub fn get_subscription_matched_status(&mut self) -> SubscriptionMatchedStatus {
         let status = self.subscription_matched_status.clone();
 
@@ -897,16 +1324,53 @@ impl DataReaderEntity {
     pub fn insert_instance_deadline_missed_task(
         &mut self,
         instance_handle: InstanceHandle,
-        task: TaskHandle,
+        task: TimerTaskHandle,
     ) {
+        if let Some(previous_task) = self
+            .instance_deadline_missed_task
+            .insert(instance_handle, task)
+        {
+            previous_task.abort();
+        }
+    }
+
+    /// Cancels the deadline timers of all the instances and returns the instances which had one
+    pub fn remove_instance_deadline_missed_tasks(&mut self) -> Vec<InstanceHandle> {
         self.instance_deadline_missed_task
-            .insert(instance_handle, task);
+            .drain()
+            .map(|(instance_handle, task)| {
+                task.abort();
+                instance_handle
+            })
+            .collect()
+    }
+
+    /// Ends the sample streams waiting for data once the reader is deleted
+    pub fn cancel_communication_state_waiters(&self) {
+        self.status_condition
+            .send_actor_mail(status_condition_actor::CancelCommunicationStateWaiters)
+            .ok();
+    }
+
+    /// Cancels all the timers of the reader so that none of them fires after it is deleted
+    pub fn cancel_timer_tasks(&mut self) {
+        self.remove_instance_deadline_missed_tasks();
+        for (_, autopurge_task) in self.instance_autopurge_task.drain() {
+            autopurge_task.abort();
+        }
+        for (_, (_, lease_task)) in self.publication_lease_list.drain() {
+            lease_task.abort();
+        }
     }
 
     pub fn listener(&self) -> Option<&Actor<DataReaderListenerActor>> {
         self.listener.as_ref()
     }
 
+    pub fn is_data_available_status_changed(&self) -> bool {
+        self.data_available_status_changed_flag
+    }
+
     pub fn listener_mask(&self) -> &[StatusKind] {
         &self.listener_mask
     }
@@ -5,20 +5,25 @@ use crate::{
         status_condition::status_condition_actor::{self, StatusConditionActor},
         xtypes_glue::key_and_instance_handle::{
             get_instance_handle_from_serialized_foo, get_instance_handle_from_serialized_key,
+            get_serialized_key_from_serialized_foo, set_key_in_serialized_foo,
         },
     },
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::DataWriterQos,
-        qos_policy::{HistoryQosPolicyKind, Length, QosPolicyId, ReliabilityQosPolicyKind},
+        qos_policy::{
+            HistoryOverflowQosPolicyKind, HistoryQosPolicyKind, Length, PublishRateQosPolicy,
+            QosPolicyId, ReliabilityQosPolicyKind,
+        },
+        statistics::{DataWriterStatistics, InstanceSampleCount},
         status::{
-            OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus, PublicationMatchedStatus,
-            QosPolicyCount, StatusKind,
+            LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
+            PublicationMatchedStatus, QosPolicyCount, StatusKind,
         },
-        time::{DurationKind, Time},
+        time::{Duration, DurationKind, Time},
     },
-    runtime::{actor::Actor, executor::TaskHandle},
+    runtime::{actor::Actor, timer::TimerTaskHandle},
     transport::{
         history_cache::{CacheChange, HistoryCache},
         types::{ChangeKind, Guid},
@@ -28,7 +33,10 @@ use crate::{
 };
 use std::{
     collections::{HashMap, HashSet, VecDeque},
-    sync::Arc,
+    sync::{
+        atomic::{self, AtomicU16},
+        Arc,
+    },
 };
 
 pub enum TransportWriterKind {
@@ -61,6 +69,9 @@ pub struct DataWriterEntity {
     publication_matched_status: PublicationMatchedStatus,
     incompatible_subscription_list: HashSet<InstanceHandle>,
     offered_incompatible_qos_status: OfferedIncompatibleQosStatus,
+    liveliness_lost_status: LivelinessLostStatus,
+    liveliness_lease: Option<(u64, TimerTaskHandle)>,
+    liveliness_lease_counter: u64,
     enabled: bool,
     status_condition: Actor<StatusConditionActor>,
     listener: Option<Actor<DataWriterListenerActor>>,
@@ -68,10 +79,24 @@ pub struct DataWriterEntity {
     max_seq_num: Option<i64>,
     last_change_sequence_number: i64,
     qos: DataWriterQos,
-    registered_instance_list: HashSet<InstanceHandle>,
+    // Shared with all the handles of the writer, which serialize the samples with it
+    data_representation: Arc<AtomicU16>,
+    registered_instance_list: HashMap<InstanceHandle, Vec<u8>>,
+    // The key of an unregistered instance is kept until the instance is registered again so that
+    // it is still counted in the statistics
+    unregistered_instance_list: HashSet<InstanceHandle>,
     offered_deadline_missed_status: OfferedDeadlineMissedStatus,
-    instance_deadline_missed_task: HashMap<InstanceHandle, TaskHandle>,
+    instance_deadline_missed_task: HashMap<InstanceHandle, TimerTaskHandle>,
     instance_samples: HashMap<InstanceHandle, VecDeque<i64>>,
+    dropped_sample_count: u64,
+    instance_last_change_kind: HashMap<InstanceHandle, ChangeKind>,
+    published_sample_window: VecDeque<PublishedSamples>,
+}
+
+struct PublishedSamples {
+    timestamp: Time,
+    sample_count: usize,
+    byte_count: usize,
 }
 
 impl DataWriterEntity {
@@ -97,17 +122,25 @@ impl DataWriterEntity {
             publication_matched_status: PublicationMatchedStatus::default(),
             incompatible_subscription_list: HashSet::new(),
             offered_incompatible_qos_status: OfferedIncompatibleQosStatus::default(),
+            liveliness_lost_status: LivelinessLostStatus::default(),
+            liveliness_lease: None,
+            liveliness_lease_counter: 0,
             enabled: false,
             status_condition,
             listener,
             listener_mask,
             max_seq_num: None,
             last_change_sequence_number: 0,
+            data_representation: Arc::new(AtomicU16::new(qos.data_representation())),
             qos,
-            registered_instance_list: HashSet::new(),
+            registered_instance_list: HashMap::new(),
+            unregistered_instance_list: HashSet::new(),
             offered_deadline_missed_status: OfferedDeadlineMissedStatus::default(),
             instance_deadline_missed_task: HashMap::new(),
             instance_samples: HashMap::new(),
+            dropped_sample_count: 0,
+            instance_last_change_kind: HashMap::new(),
+            published_sample_window: VecDeque::new(),
         }
     }
 
@@ -137,6 +170,13 @@ impl DataWriterEntity {
 
     pub fn enable(&mut self) {
         self.enabled = true;
+        // The representation can not change anymore once the writer is enabled
+        self.data_representation
+            .store(self.qos.data_representation(), atomic::Ordering::Release);
+    }
+
+    pub fn data_representation(&self) -> Arc<AtomicU16> {
+        self.data_representation.clone()
     }
 
     pub fn qos(&self) -> &DataWriterQos {
@@ -153,13 +193,120 @@ impl DataWriterEntity {
     }
 
     pub fn contains_instance(&mut self, instance_handle: &InstanceHandle) -> bool {
-        self.registered_instance_list.contains(instance_handle)
+        self.registered_instance_list.contains_key(instance_handle)
+    }
+
+    fn is_registered(&self, instance_handle: &InstanceHandle) -> bool {
+        self.registered_instance_list.contains_key(instance_handle)
+            && !self.unregistered_instance_list.contains(instance_handle)
+    }
+
+    // Only the instances which are currently registered count towards the max_instances limit
+    fn register_instance(
+        &mut self,
+        instance_handle: InstanceHandle,
+        serialized_data: &[u8],
+    ) -> DdsResult<()> {
+        if self.is_registered(&instance_handle) {
+            return Ok(());
+        }
+        let registered_instance_count =
+            self.registered_instance_list.len() - self.unregistered_instance_list.len();
+        if registered_instance_count < self.qos.resource_limits.max_instances {
+            if !self.registered_instance_list.contains_key(&instance_handle) {
+                let serialized_key = get_serialized_key_from_serialized_foo(
+                    serialized_data,
+                    self.type_support.as_ref(),
+                )?;
+                self.registered_instance_list
+                    .insert(instance_handle, serialized_key);
+            }
+            self.unregistered_instance_list.remove(&instance_handle);
+            Ok(())
+        } else {
+            Err(DdsError::OutOfResources)
+        }
+    }
+
+    pub fn register_instance_w_timestamp(
+        &mut self,
+        serialized_data: &[u8],
+    ) -> DdsResult<Option<InstanceHandle>> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        let instance_handle =
+            get_instance_handle_from_serialized_foo(serialized_data, self.type_support.as_ref())?;
+        self.register_instance(instance_handle, serialized_data)?;
+        Ok(Some(instance_handle))
+    }
+
+    pub fn get_key_value(
+        &self,
+        serialized_key_holder: &[u8],
+        instance_handle: InstanceHandle,
+    ) -> DdsResult<Vec<u8>> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        let serialized_key = self
+            .registered_instance_list
+            .get(&instance_handle)
+            .ok_or(DdsError::BadParameter)?;
+        set_key_in_serialized_foo(
+            serialized_key_holder,
+            serialized_key,
+            self.type_support.as_ref(),
+        )
     }
 
     pub fn write_w_timestamp(
         &mut self,
         serialized_data: Vec<u8>,
         timestamp: Time,
+    ) -> DdsResult<i64> {
+        let mut change_list = Vec::new();
+        let sequence_number = self.insert_change(serialized_data, timestamp, &mut change_list)?;
+        for change in change_list {
+            self.transport_writer.history_cache().add_change(change);
+        }
+        Ok(sequence_number)
+    }
+
+    pub fn write_batch_w_timestamp(
+        &mut self,
+        serialized_data_list: Vec<Vec<u8>>,
+        timestamp: Time,
+    ) -> DdsResult<Vec<i64>> {
+        // All the changes of the batch are handed over to the transport at once so that
+        // they are sent in a single pass. If a sample fails, the ones before it are still written.
+        let mut change_list = Vec::new();
+        let mut sequence_number_list = Vec::with_capacity(serialized_data_list.len());
+        let mut result = Ok(());
+        for serialized_data in serialized_data_list {
+            match self.insert_change(serialized_data, timestamp, &mut change_list) {
+                Ok(sequence_number) => sequence_number_list.push(sequence_number),
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        if !change_list.is_empty() {
+            self.transport_writer
+                .history_cache()
+                .add_change_list(change_list);
+        }
+        result.map(|_| sequence_number_list)
+    }
+
+    fn insert_change(
+        &mut self,
+        serialized_data: Vec<u8>,
+        timestamp: Time,
+        pending_change_list: &mut Vec<CacheChange>,
     ) -> DdsResult<i64> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
@@ -170,21 +317,7 @@ impl DataWriterEntity {
         let instance_handle =
             get_instance_handle_from_serialized_foo(&serialized_data, self.type_support.as_ref())?;
 
-        if !self.registered_instance_list.contains(&instance_handle) {
-            if self.registered_instance_list.len() < self.qos.resource_limits.max_instances {
-                self.registered_instance_list.insert(instance_handle);
-            } else {
-                return Err(DdsError::OutOfResources);
-            }
-        }
-
-        if let Length::Limited(max_instances) = self.qos.resource_limits.max_instances {
-            if !self.instance_samples.contains_key(&instance_handle)
-                && self.instance_samples.len() == max_instances as usize
-            {
-                return Err(DdsError::OutOfResources);
-            }
-        }
+        self.register_instance(instance_handle, &serialized_data)?;
 
         if let Length::Limited(max_samples_per_instance) =
             self.qos.resource_limits.max_samples_per_instance
@@ -211,7 +344,11 @@ impl DataWriterEntity {
                 .fold(0, |acc, (_, x)| acc + x.len());
 
             if total_samples >= max_samples as usize {
-                return Err(DdsError::OutOfResources);
+                if self.qos.history_overflow.kind == HistoryOverflowQosPolicyKind::DropOldest {
+                    self.drop_oldest_change(pending_change_list);
+                } else {
+                    return Err(DdsError::OutOfResources);
+                }
             }
         }
 
@@ -227,7 +364,12 @@ impl DataWriterEntity {
             if let Some(s) = self.instance_samples.get_mut(&instance_handle) {
                 if s.len() == depth as usize {
                     if let Some(&smallest_seq_num_instance) = s.front() {
-                        if self.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable {
+                        let is_pending = pending_change_list
+                            .iter()
+                            .any(|cc| cc.sequence_number == smallest_seq_num_instance);
+                        if !is_pending
+                            && self.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable
+                        {
                             let start_time = std::time::Instant::now();
                             while let TransportWriterKind::Stateful(w) = &self.transport_writer {
                                 if w.is_change_acknowledged(smallest_seq_num_instance) {
@@ -245,9 +387,16 @@ impl DataWriterEntity {
                         }
                     }
                     if let Some(smallest_seq_num_instance) = s.pop_front() {
-                        self.transport_writer
-                            .history_cache()
-                            .remove_change(smallest_seq_num_instance);
+                        if let Some(index) = pending_change_list
+                            .iter()
+                            .position(|cc| cc.sequence_number == smallest_seq_num_instance)
+                        {
+                            pending_change_list.remove(index);
+                        } else {
+                            self.transport_writer
+                                .history_cache()
+                                .remove_change(smallest_seq_num_instance);
+                        }
                     }
                 }
             }
@@ -271,10 +420,38 @@ impl DataWriterEntity {
             .entry(instance_handle)
             .or_default()
             .push_back(change.sequence_number);
-        self.transport_writer.history_cache().add_change(change);
+        self.instance_last_change_kind
+            .insert(instance_handle, ChangeKind::Alive);
+        pending_change_list.push(change);
         Ok(self.last_change_sequence_number)
     }
 
+    fn drop_oldest_change(&mut self, pending_change_list: &mut Vec<CacheChange>) {
+        let oldest_instance_samples = self
+            .instance_samples
+            .values_mut()
+            .filter(|s| !s.is_empty())
+            .min_by_key(|s| s.front().copied());
+        if let Some(oldest_seq_num) = oldest_instance_samples.and_then(|s| s.pop_front()) {
+            if let Some(index) = pending_change_list
+                .iter()
+                .position(|cc| cc.sequence_number == oldest_seq_num)
+            {
+                pending_change_list.remove(index);
+            } else {
+                self.transport_writer
+                    .history_cache()
+                    .remove_change(oldest_seq_num);
+            }
+            self.dropped_sample_count += 1;
+            tracing::debug!(
+                sequence_number = oldest_seq_num,
+                total_dropped_sample_count = self.dropped_sample_count,
+                "Dropped oldest sample of full writer history"
+            );
+        }
+    }
+
     pub fn dispose_w_timestamp(
         &mut self,
         serialized_key: Vec<u8>,
@@ -305,7 +482,7 @@ impl DataWriterEntity {
 
         let instance_handle =
             get_instance_handle_from_serialized_key(&serialized_key, self.type_support.as_ref())?;
-        if !self.registered_instance_list.contains(&instance_handle) {
+        if !self.registered_instance_list.contains_key(&instance_handle) {
             return Err(DdsError::BadParameter);
         }
 
@@ -326,6 +503,8 @@ impl DataWriterEntity {
         self.transport_writer
             .history_cache()
             .add_change(cache_change);
+        self.instance_last_change_kind
+            .insert(instance_handle, ChangeKind::NotAliveDisposed);
 
         Ok(())
     }
@@ -360,7 +539,7 @@ impl DataWriterEntity {
 
         let instance_handle =
             get_instance_handle_from_serialized_key(&serialized_key, self.type_support.as_ref())?;
-        if !self.registered_instance_list.contains(&instance_handle) {
+        if !self.is_registered(&instance_handle) {
             return Err(DdsError::BadParameter);
         }
 
@@ -370,8 +549,17 @@ impl DataWriterEntity {
 
         self.last_change_sequence_number += 1;
 
+        let kind = if self
+            .qos
+            .writer_data_lifecycle
+            .autodispose_unregistered_instances
+        {
+            ChangeKind::NotAliveDisposedUnregistered
+        } else {
+            ChangeKind::NotAliveUnregistered
+        };
         let cache_change = CacheChange {
-            kind: ChangeKind::NotAliveDisposed,
+            kind,
             writer_guid: self.transport_writer().guid(),
             sequence_number: self.last_change_sequence_number,
             source_timestamp: Some(timestamp.into()),
@@ -381,9 +569,52 @@ impl DataWriterEntity {
         self.transport_writer
             .history_cache()
             .add_change(cache_change);
+        self.instance_last_change_kind.insert(instance_handle, kind);
+        self.unregistered_instance_list.insert(instance_handle);
         Ok(())
     }
 
+    pub fn dispose_w_handle_w_timestamp(
+        &mut self,
+        instance_handle: InstanceHandle,
+        timestamp: Time,
+    ) -> DdsResult<()> {
+        let serialized_key = self
+            .registered_instance_list
+            .get(&instance_handle)
+            .ok_or(DdsError::BadParameter)?
+            .clone();
+        self.dispose_w_timestamp(serialized_key, timestamp)
+    }
+
+    pub fn unregister_w_handle_w_timestamp(
+        &mut self,
+        instance_handle: InstanceHandle,
+        timestamp: Time,
+    ) -> DdsResult<()> {
+        let serialized_key = self
+            .registered_instance_list
+            .get(&instance_handle)
+            .ok_or(DdsError::BadParameter)?
+            .clone();
+        self.unregister_w_timestamp(serialized_key, timestamp)
+    }
+
+    // Unregisters all the instances still registered by the writer which, depending on the
+    // writer data lifecycle QoS, also disposes them
+    pub fn unregister_all_instances(&mut self, timestamp: Time) {
+        let instance_handle_list: Vec<_> = self
+            .registered_instance_list
+            .keys()
+            .filter(|h| !self.unregistered_instance_list.contains(h))
+            .cloned()
+            .collect();
+        for instance_handle in instance_handle_list {
+            self.unregister_w_handle_w_timestamp(instance_handle, timestamp)
+                .ok();
+        }
+    }
+
     pub fn remove_change(&mut self, sequence_number: i64) {
         self.transport_writer
             .history_cache()
@@ -395,12 +626,18 @@ impl DataWriterEntity {
         subscription_builtin_topic_data: SubscriptionBuiltinTopicData,
     ) {
         let handle = InstanceHandle::new(subscription_builtin_topic_data.key().value);
-        self.matched_subscription_list
-            .insert(handle, subscription_builtin_topic_data);
-        self.publication_matched_status.current_count = self.matched_subscription_list.len() as i32;
-        self.publication_matched_status.current_count_change += 1;
-        self.publication_matched_status.total_count += 1;
-        self.publication_matched_status.total_count_change += 1;
+        // A subscription announced again, as when its QoS changes, only has its data updated
+        if self
+            .matched_subscription_list
+            .insert(handle, subscription_builtin_topic_data)
+            .is_none()
+        {
+            self.publication_matched_status.current_count =
+                self.matched_subscription_list.len() as i32;
+            self.publication_matched_status.current_count_change += 1;
+            self.publication_matched_status.total_count += 1;
+            self.publication_matched_status.total_count_change += 1;
+        }
     }
 
     pub fn remove_matched_subscription(&mut self, subscription_handle: &InstanceHandle) {
@@ -446,6 +683,102 @@ impl DataWriterEntity {
         status
     }
 
+    pub fn dropped_sample_count(&self) -> u64 {
+        self.dropped_sample_count
+    }
+
+    /// Returns the time to wait before the samples can be written without exceeding the
+    /// publish rate, or `None` if they can be written now.
+    pub fn publish_rate_delay(
+        &mut self,
+        now: Time,
+        sample_count: usize,
+        byte_count: usize,
+    ) -> Option<Duration> {
+        let rate_period = Duration::new(1, 0);
+        while let Some(published_samples) = self.published_sample_window.front() {
+            if now - published_samples.timestamp >= rate_period {
+                self.published_sample_window.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        // Samples above the limits on their own are written when nothing else was published
+        // in the last period, otherwise they could never be written
+        let oldest_published_samples = self.published_sample_window.front()?;
+        let (window_sample_count, window_byte_count) = self
+            .published_sample_window
+            .iter()
+            .fold((0, 0), |(samples, bytes), x| {
+                (samples + x.sample_count, bytes + x.byte_count)
+            });
+        let publish_rate = &self.qos.publish_rate;
+        if publish_rate.max_samples_per_second < window_sample_count + sample_count
+            || publish_rate.max_bytes_per_second < window_byte_count + byte_count
+        {
+            Some(rate_period - (now - oldest_published_samples.timestamp))
+        } else {
+            None
+        }
+    }
+
+    pub fn add_published_samples(&mut self, now: Time, sample_count: usize, byte_count: usize) {
+        if self.qos.publish_rate != PublishRateQosPolicy::default() {
+            self.published_sample_window.push_back(PublishedSamples {
+                timestamp: now,
+                sample_count,
+                byte_count,
+            });
+        }
+    }
+
+    pub fn drop_rate_limited_samples(&mut self, sample_count: usize) {
+        self.dropped_sample_count += sample_count as u64;
+        tracing::debug!(
+            sample_count,
+            total_dropped_sample_count = self.dropped_sample_count,
+            "Dropped samples exceeding the writer publish rate"
+        );
+    }
+
+    pub fn get_statistics(&self) -> DataWriterStatistics {
+        let count_instances = |kinds: &[ChangeKind]| {
+            self.instance_last_change_kind
+                .values()
+                .filter(|k| kinds.contains(k))
+                .count()
+        };
+        let mut samples_per_instance: Vec<_> = self
+            .instance_samples
+            .iter()
+            .filter(|(_, s)| !s.is_empty())
+            .map(|(&instance_handle, s)| InstanceSampleCount {
+                instance_handle,
+                sample_count: s.len(),
+            })
+            .collect();
+        samples_per_instance.sort_by_key(|s| s.instance_handle);
+        DataWriterStatistics {
+            instance_count: self.registered_instance_list.len(),
+            alive_instance_count: count_instances(&[ChangeKind::Alive]),
+            disposed_instance_count: count_instances(&[ChangeKind::NotAliveDisposed]),
+            unregistered_instance_count: count_instances(&[
+                ChangeKind::NotAliveUnregistered,
+                ChangeKind::NotAliveDisposedUnregistered,
+            ]),
+            history_sample_count: samples_per_instance.iter().map(|s| s.sample_count).sum(),
+            samples_per_instance,
+            history_max_samples: self.qos.resource_limits.max_samples,
+        }
+    }
+
+    pub fn get_liveliness_lost_status(&mut self) -> LivelinessLostStatus {
+        let status = self.liveliness_lost_status.clone();
+        self.liveliness_lost_status.total_count_change = 0;
+        status
+    }
+
     pub fn get_matched_subscriptions(&self) -> Vec<InstanceHandle> {
         self.matched_subscription_list.keys().cloned().collect()
     }
@@ -463,7 +796,8 @@ impl DataWriterEntity {
         self.status_condition
             .send_actor_mail(status_condition_actor::RemoveCommunicationState {
                 state: StatusKind::OfferedDeadlineMissed,
-            });
+            })
+            .ok();
         status
     }
 
@@ -475,23 +809,71 @@ impl DataWriterEntity {
         status
     }
 
+    pub fn increment_liveliness_lost_status(&mut self) {
+        self.liveliness_lost_status.total_count += 1;
+        self.liveliness_lost_status.total_count_change += 1;
+    }
+
+    pub fn generate_liveliness_lease_id(&mut self) -> u64 {
+        self.liveliness_lease_counter += 1;
+        self.liveliness_lease_counter
+    }
+
+    /// Replace the liveliness lease of the writer, cancelling the timer of the previous one
+    pub fn renew_liveliness_lease(&mut self, lease_id: u64, lease_task: TimerTaskHandle) {
+        if let Some((_, previous_lease_task)) =
+            self.liveliness_lease.replace((lease_id, lease_task))
+        {
+            previous_lease_task.abort();
+        }
+    }
+
+    pub fn is_liveliness_lease_current(&self, lease_id: u64) -> bool {
+        self.liveliness_lease
+            .as_ref()
+            .is_some_and(|(id, _)| *id == lease_id)
+    }
+
     pub fn increment_offered_deadline_missed_status(&mut self, instance_handle: InstanceHandle) {
         self.offered_deadline_missed_status.last_instance_handle = instance_handle;
         self.offered_deadline_missed_status.total_count += 1;
         self.offered_deadline_missed_status.total_count_change += 1;
     }
 
-    pub fn type_support(&self) -> &(dyn DynamicType + Send + Sync) {
-        self.type_support.as_ref()
+    pub fn type_support(&self) -> &Arc<dyn DynamicType + Send + Sync> {
+        &self.type_support
     }
 
     pub fn insert_instance_deadline_missed_task(
         &mut self,
         instance_handle: InstanceHandle,
-        task: TaskHandle,
+        task: TimerTaskHandle,
     ) {
+        if let Some(previous_task) = self
+            .instance_deadline_missed_task
+            .insert(instance_handle, task)
+        {
+            previous_task.abort();
+        }
+    }
+
+    /// Cancels the deadline timers of all the instances and returns the instances which had one
+    pub fn remove_instance_deadline_missed_tasks(&mut self) -> Vec<InstanceHandle> {
         self.instance_deadline_missed_task
-            .insert(instance_handle, task);
+            .drain()
+            .map(|(instance_handle, task)| {
+                task.abort();
+                instance_handle
+            })
+            .collect()
+    }
+
+    /// Cancels all the timers of the writer so that none of them fires after it is deleted
+    pub fn cancel_timer_tasks(&mut self) {
+        self.remove_instance_deadline_missed_tasks();
+        if let Some((_, lease_task)) = self.liveliness_lease.take() {
+            lease_task.abort();
+        }
     }
 
     pub fn status_condition(&self) -> &Actor<StatusConditionActor> {
@@ -515,12 +897,12 @@ impl DataWriterEntity {
         &self.listener_mask
     }
 
-    pub fn are_all_changes_acknowledged(&self) -> bool {
-        match &self.transport_writer {
+    pub fn on_all_changes_acknowledged(&mut self, callback: Box<dyn FnOnce() + Send>) {
+        match &mut self.transport_writer {
             TransportWriterKind::Stateful(w) => {
-                w.is_change_acknowledged(self.last_change_sequence_number)
+                w.on_change_acknowledged(self.last_change_sequence_number, callback)
             }
-            TransportWriterKind::Stateless(_) => true,
+            TransportWriterKind::Stateless(_) => callback(),
         }
     }
 }
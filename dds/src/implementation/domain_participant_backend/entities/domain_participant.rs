@@ -4,7 +4,7 @@ use std::{
 };
 
 use crate::{
-    builtin_topics::TopicBuiltinTopicData,
+    builtin_topics::{ParticipantBuiltinTopicData, TopicBuiltinTopicData},
     domain::domain_participant_factory::DomainId,
     implementation::{
         data_representation_builtin_endpoints::{
@@ -17,13 +17,15 @@ use crate::{
         status_condition::status_condition_actor::StatusConditionActor,
     },
     infrastructure::{
-        error::DdsResult,
-        instance::InstanceHandle,
+        discovery_event::DiscoveryEvent,
+        error::{DdsError, DdsResult},
+        instance::{EntityKind, InstanceHandle},
         qos::{DomainParticipantQos, PublisherQos, SubscriberQos, TopicQos},
         status::StatusKind,
-        time::Time,
+        time::{Duration, Time},
     },
-    runtime::actor::Actor,
+    runtime::{actor::Actor, mpsc::MpscSender, timer::TimerTaskHandle},
+    transport::types::GuidPrefix,
 };
 
 use super::{publisher::PublisherEntity, subscriber::SubscriberEntity, topic::TopicEntity};
@@ -31,6 +33,7 @@ use super::{publisher::PublisherEntity, subscriber::SubscriberEntity, topic::Top
 pub struct DomainParticipantEntity {
     domain_id: DomainId,
     domain_tag: String,
+    accepted_domain_tags: Vec<String>,
     instance_handle: InstanceHandle,
     qos: DomainParticipantQos,
     builtin_subscriber: SubscriberEntity,
@@ -45,11 +48,25 @@ pub struct DomainParticipantEntity {
     discovered_topic_list: HashMap<InstanceHandle, TopicBuiltinTopicData>,
     discovered_reader_list: HashMap<InstanceHandle, DiscoveredReaderData>,
     discovered_writer_list: HashMap<InstanceHandle, DiscoveredWriterData>,
+    discovered_participant_guid_prefix_index: HashMap<GuidPrefix, InstanceHandle>,
+    discovered_reader_topic_index: HashMap<String, HashSet<InstanceHandle>>,
+    discovered_writer_topic_index: HashMap<String, HashSet<InstanceHandle>>,
+    discovered_participant_event_sender_list:
+        Vec<MpscSender<DiscoveryEvent<ParticipantBuiltinTopicData>>>,
+    discovered_topic_event_sender_list: Vec<MpscSender<DiscoveryEvent<TopicBuiltinTopicData>>>,
+    max_discovered_participants: Option<usize>,
+    max_discovered_endpoints: Option<usize>,
+    source_timestamp_tolerance: Option<Duration>,
+    is_discovery_server: bool,
+    relayed_participant_list: HashSet<InstanceHandle>,
+    participant_lease_duration: Duration,
+    participant_lease_list: HashMap<InstanceHandle, (u64, TimerTaskHandle)>,
+    participant_lease_counter: u64,
     enabled: bool,
     ignored_participants: HashSet<InstanceHandle>,
     ignored_publications: HashSet<InstanceHandle>,
     ignored_subcriptions: HashSet<InstanceHandle>,
-    _ignored_topic_list: HashSet<InstanceHandle>,
+    ignored_topic_list: HashSet<InstanceHandle>,
     listener: Option<Actor<DomainParticipantListenerActor>>,
     listener_mask: Vec<StatusKind>,
     status_condition: Actor<StatusConditionActor>,
@@ -68,6 +85,12 @@ impl DomainParticipantEntity {
         builtin_subscriber: SubscriberEntity,
         topic_list: HashMap<String, TopicEntity>,
         domain_tag: String,
+        accepted_domain_tags: Vec<String>,
+        max_discovered_participants: Option<usize>,
+        max_discovered_endpoints: Option<usize>,
+        source_timestamp_tolerance: Option<Duration>,
+        is_discovery_server: bool,
+        participant_lease_duration: Duration,
     ) -> Self {
         Self {
             domain_id,
@@ -85,15 +108,29 @@ impl DomainParticipantEntity {
             discovered_topic_list: HashMap::new(),
             discovered_reader_list: HashMap::new(),
             discovered_writer_list: HashMap::new(),
+            discovered_participant_guid_prefix_index: HashMap::new(),
+            discovered_reader_topic_index: HashMap::new(),
+            discovered_writer_topic_index: HashMap::new(),
+            discovered_participant_event_sender_list: Vec::new(),
+            discovered_topic_event_sender_list: Vec::new(),
+            max_discovered_participants,
+            max_discovered_endpoints,
+            source_timestamp_tolerance,
+            is_discovery_server,
+            relayed_participant_list: HashSet::new(),
+            participant_lease_duration,
+            participant_lease_list: HashMap::new(),
+            participant_lease_counter: 0,
             enabled: false,
             ignored_participants: HashSet::new(),
             ignored_publications: HashSet::new(),
             ignored_subcriptions: HashSet::new(),
-            _ignored_topic_list: HashSet::new(),
+            ignored_topic_list: HashSet::new(),
             listener,
             listener_mask,
             status_condition,
             domain_tag,
+            accepted_domain_tags,
         }
     }
 
@@ -105,6 +142,16 @@ impl DomainParticipantEntity {
         Time::new(unix_time.as_secs() as i32, unix_time.subsec_nanos())
     }
 
+    pub fn check_source_timestamp(&self, timestamp: Time) -> DdsResult<()> {
+        let now = self.get_current_time();
+        match self.source_timestamp_tolerance {
+            Some(tolerance) if timestamp > now && timestamp - now > tolerance => {
+                Err(DdsError::BadParameter)
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub fn enable(&mut self) {
         self.enabled = true;
     }
@@ -122,14 +169,52 @@ impl DomainParticipantEntity {
     }
 
     pub fn add_discovered_topic(&mut self, topic_builtin_topic_data: TopicBuiltinTopicData) {
-        self.discovered_topic_list.insert(
-            InstanceHandle::new(topic_builtin_topic_data.key().value),
-            topic_builtin_topic_data,
-        );
+        let handle = InstanceHandle::new(topic_builtin_topic_data.key().value);
+        let event = match self.discovered_topic_list.get(&handle) {
+            None => Some(DiscoveryEvent::Added {
+                handle,
+                data: topic_builtin_topic_data.clone(),
+            }),
+            Some(previous_data) if previous_data != &topic_builtin_topic_data => {
+                Some(DiscoveryEvent::Updated {
+                    handle,
+                    data: topic_builtin_topic_data.clone(),
+                })
+            }
+            Some(_) => None,
+        };
+        self.discovered_topic_list
+            .insert(handle, topic_builtin_topic_data);
+        if let Some(event) = event {
+            send_discovery_event(&mut self.discovered_topic_event_sender_list, event);
+        }
     }
 
-    pub fn remove_discovered_writer(&mut self, discovered_writer_handle: &InstanceHandle) {
-        self.discovered_writer_list.remove(discovered_writer_handle);
+    pub fn remove_discovered_topic(&mut self, topic_handle: &InstanceHandle) {
+        if self.discovered_topic_list.remove(topic_handle).is_some() {
+            send_discovery_event(
+                &mut self.discovered_topic_event_sender_list,
+                DiscoveryEvent::Removed {
+                    handle: *topic_handle,
+                },
+            );
+        }
+    }
+
+    pub fn add_discovered_topic_event_sender(
+        &mut self,
+        event_sender: MpscSender<DiscoveryEvent<TopicBuiltinTopicData>>,
+    ) {
+        // Report the topics discovered so far so that the stream does not miss any topic
+        for (handle, data) in &self.discovered_topic_list {
+            event_sender
+                .send(DiscoveryEvent::Added {
+                    handle: *handle,
+                    data: data.clone(),
+                })
+                .ok();
+        }
+        self.discovered_topic_event_sender_list.push(event_sender);
     }
 
     pub fn qos(&self) -> &DomainParticipantQos {
@@ -156,6 +241,24 @@ impl DomainParticipantEntity {
         self.ignored_participants.insert(handle);
     }
 
+    pub fn ignore_topic(&mut self, handle: InstanceHandle) {
+        self.ignored_topic_list.insert(handle);
+    }
+
+    pub fn is_topic_handle_ignored(&self, handle: &InstanceHandle) -> bool {
+        self.ignored_topic_list.contains(handle)
+    }
+
+    // The endpoints refer to their topic by name so a topic is ignored if any of the discovered
+    // topics with that name is ignored
+    pub fn is_topic_ignored(&self, topic_name: &str) -> bool {
+        self.ignored_topic_list.iter().any(|handle| {
+            self.discovered_topic_list
+                .get(handle)
+                .is_some_and(|t| t.name() == topic_name)
+        })
+    }
+
     pub fn ignore_subscription(&mut self, handle: InstanceHandle) {
         self.ignored_subcriptions.insert(handle);
     }
@@ -186,7 +289,11 @@ impl DomainParticipantEntity {
     }
 
     pub fn get_discovered_topics(&self) -> Vec<InstanceHandle> {
-        self.discovered_topic_list.keys().cloned().collect()
+        self.discovered_topic_list
+            .keys()
+            .filter(|handle| !self.ignored_topic_list.contains(handle))
+            .cloned()
+            .collect()
     }
 
     pub fn get_discovered_topic_data(
@@ -205,47 +312,279 @@ impl DomainParticipantEntity {
     pub fn add_discovered_participant(
         &mut self,
         discovered_participant_data: SpdpDiscoveredParticipantData,
-    ) {
-        self.discovered_participant_list.insert(
-            InstanceHandle::new(discovered_participant_data.dds_participant_data.key().value),
-            discovered_participant_data,
+    ) -> DdsResult<()> {
+        let handle =
+            InstanceHandle::new(discovered_participant_data.dds_participant_data.key().value);
+        if !self.discovered_participant_list.contains_key(&handle)
+            && self
+                .max_discovered_participants
+                .is_some_and(|max| self.discovered_participant_list.len() >= max)
+        {
+            return Err(DdsError::OutOfResources);
+        }
+        let event = match self.discovered_participant_list.get(&handle) {
+            None => Some(DiscoveryEvent::Added {
+                handle,
+                data: discovered_participant_data.dds_participant_data.clone(),
+            }),
+            Some(previous_data)
+                if previous_data.dds_participant_data
+                    != discovered_participant_data.dds_participant_data =>
+            {
+                Some(DiscoveryEvent::Updated {
+                    handle,
+                    data: discovered_participant_data.dds_participant_data.clone(),
+                })
+            }
+            Some(_) => None,
+        };
+        self.discovered_participant_guid_prefix_index.insert(
+            discovered_participant_data.participant_proxy.guid_prefix,
+            handle,
         );
+        self.discovered_participant_list
+            .insert(handle, discovered_participant_data);
+        if let Some(event) = event {
+            send_discovery_event(&mut self.discovered_participant_event_sender_list, event);
+        }
+        Ok(())
+    }
+
+    pub fn is_discovery_server(&self) -> bool {
+        self.is_discovery_server
+    }
+
+    /// Record whether the discovered participant is only known through a discovery server, in
+    /// which case the builtin endpoints are not matched with it
+    pub fn set_participant_relayed(&mut self, participant_handle: InstanceHandle, relayed: bool) {
+        if relayed {
+            self.relayed_participant_list.insert(participant_handle);
+        } else {
+            self.relayed_participant_list.remove(&participant_handle);
+        }
+    }
+
+    pub fn is_participant_relayed(&self, participant_handle: &InstanceHandle) -> bool {
+        self.relayed_participant_list.contains(participant_handle)
+    }
+
+    pub fn participant_lease_duration(&self) -> Duration {
+        self.participant_lease_duration
+    }
+
+    pub fn generate_participant_lease_id(&mut self) -> u64 {
+        self.participant_lease_counter += 1;
+        self.participant_lease_counter
+    }
+
+    /// Replace the lease of the discovered participant, cancelling the timer of the previous one
+    pub fn renew_participant_lease(
+        &mut self,
+        participant_handle: InstanceHandle,
+        lease_id: u64,
+        lease_task: TimerTaskHandle,
+    ) {
+        if let Some((_, previous_lease_task)) = self
+            .participant_lease_list
+            .insert(participant_handle, (lease_id, lease_task))
+        {
+            previous_lease_task.abort();
+        }
+    }
+
+    pub fn is_participant_lease_current(
+        &self,
+        participant_handle: &InstanceHandle,
+        lease_id: u64,
+    ) -> bool {
+        self.participant_lease_list
+            .get(participant_handle)
+            .is_some_and(|(id, _)| *id == lease_id)
     }
 
     pub fn remove_discovered_participant(
         &mut self,
         discovered_participant_handle: &InstanceHandle,
     ) {
-        self.discovered_participant_list
+        self.relayed_participant_list
             .remove(discovered_participant_handle);
+        if let Some((_, lease_task)) = self
+            .participant_lease_list
+            .remove(discovered_participant_handle)
+        {
+            lease_task.abort();
+        }
+        if let Some(discovered_participant_data) = self
+            .discovered_participant_list
+            .remove(discovered_participant_handle)
+        {
+            self.discovered_participant_guid_prefix_index
+                .remove(&discovered_participant_data.participant_proxy.guid_prefix);
+            send_discovery_event(
+                &mut self.discovered_participant_event_sender_list,
+                DiscoveryEvent::Removed {
+                    handle: *discovered_participant_handle,
+                },
+            );
+        }
     }
 
-    pub fn add_discovered_reader(&mut self, discovered_reader_data: DiscoveredReaderData) {
-        self.discovered_reader_list.insert(
-            InstanceHandle::new(discovered_reader_data.dds_subscription_data.key().value),
-            discovered_reader_data,
-        );
+    pub fn add_discovered_participant_event_sender(
+        &mut self,
+        event_sender: MpscSender<DiscoveryEvent<ParticipantBuiltinTopicData>>,
+    ) {
+        // Report the participants discovered so far so that the stream does not miss any participant
+        for (handle, data) in &self.discovered_participant_list {
+            event_sender
+                .send(DiscoveryEvent::Added {
+                    handle: *handle,
+                    data: data.dds_participant_data.clone(),
+                })
+                .ok();
+        }
+        self.discovered_participant_event_sender_list
+            .push(event_sender);
+    }
+
+    pub fn get_discovered_participant_data_by_guid_prefix(
+        &self,
+        guid_prefix: &GuidPrefix,
+    ) -> Option<&SpdpDiscoveredParticipantData> {
+        self.discovered_participant_guid_prefix_index
+            .get(guid_prefix)
+            .and_then(|handle| self.discovered_participant_list.get(handle))
     }
 
-    pub fn remove_discovered_reader(&mut self, discovered_reader_handle: &InstanceHandle) {
-        self.discovered_reader_list.remove(discovered_reader_handle);
+    fn is_discovered_endpoint_limit_reached(&self) -> bool {
+        self.max_discovered_endpoints.is_some_and(|max| {
+            self.discovered_reader_list.len() + self.discovered_writer_list.len() >= max
+        })
     }
 
-    pub fn discovered_reader_data_list(&self) -> impl Iterator<Item = &DiscoveredReaderData> {
-        self.discovered_reader_list.values()
+    pub fn add_discovered_reader(
+        &mut self,
+        discovered_reader_data: DiscoveredReaderData,
+    ) -> DdsResult<()> {
+        let handle = InstanceHandle::new(discovered_reader_data.dds_subscription_data.key().value);
+        if !self.discovered_reader_list.contains_key(&handle)
+            && self.is_discovered_endpoint_limit_reached()
+        {
+            return Err(DdsError::OutOfResources);
+        }
+        self.discovered_reader_topic_index
+            .entry(
+                discovered_reader_data
+                    .dds_subscription_data
+                    .topic_name()
+                    .to_owned(),
+            )
+            .or_default()
+            .insert(handle);
+        self.discovered_reader_list
+            .insert(handle, discovered_reader_data);
+        Ok(())
     }
 
-    pub fn add_discovered_writer(&mut self, discovered_writer_data: DiscoveredWriterData) {
-        self.discovered_writer_list.insert(
-            InstanceHandle::new(discovered_writer_data.dds_publication_data.key().value),
-            discovered_writer_data,
-        );
+    pub fn remove_discovered_reader(
+        &mut self,
+        discovered_reader_handle: &InstanceHandle,
+    ) -> Option<DiscoveredReaderData> {
+        let discovered_reader_data = self
+            .discovered_reader_list
+            .remove(discovered_reader_handle)?;
+        let topic_name = discovered_reader_data.dds_subscription_data.topic_name();
+        if let Some(topic_readers) = self.discovered_reader_topic_index.get_mut(topic_name) {
+            topic_readers.remove(discovered_reader_handle);
+            if topic_readers.is_empty() {
+                self.discovered_reader_topic_index.remove(topic_name);
+            }
+        }
+        Some(discovered_reader_data)
     }
 
-    pub fn publication_builtin_topic_data_list(
+    pub fn discovered_reader_handle_list_by_guid_prefix(
         &self,
-    ) -> impl Iterator<Item = &DiscoveredWriterData> {
-        self.discovered_writer_list.values()
+        guid_prefix: &GuidPrefix,
+    ) -> Vec<InstanceHandle> {
+        self.discovered_reader_list
+            .iter()
+            .filter(|(_, r)| r.reader_proxy.remote_reader_guid.prefix() == *guid_prefix)
+            .map(|(handle, _)| *handle)
+            .collect()
+    }
+
+    pub fn discovered_reader_data_list_by_topic<'a>(
+        &'a self,
+        topic_name: &str,
+    ) -> impl Iterator<Item = &'a DiscoveredReaderData> {
+        self.discovered_reader_topic_index
+            .get(topic_name)
+            .into_iter()
+            .flatten()
+            .filter_map(|handle| self.discovered_reader_list.get(handle))
+    }
+
+    pub fn add_discovered_writer(
+        &mut self,
+        discovered_writer_data: DiscoveredWriterData,
+    ) -> DdsResult<()> {
+        let handle = InstanceHandle::new(discovered_writer_data.dds_publication_data.key().value);
+        if !self.discovered_writer_list.contains_key(&handle)
+            && self.is_discovered_endpoint_limit_reached()
+        {
+            return Err(DdsError::OutOfResources);
+        }
+        self.discovered_writer_topic_index
+            .entry(
+                discovered_writer_data
+                    .dds_publication_data
+                    .topic_name()
+                    .to_owned(),
+            )
+            .or_default()
+            .insert(handle);
+        self.discovered_writer_list
+            .insert(handle, discovered_writer_data);
+        Ok(())
+    }
+
+    pub fn remove_discovered_writer(
+        &mut self,
+        discovered_writer_handle: &InstanceHandle,
+    ) -> Option<DiscoveredWriterData> {
+        let discovered_writer_data = self
+            .discovered_writer_list
+            .remove(discovered_writer_handle)?;
+        let topic_name = discovered_writer_data.dds_publication_data.topic_name();
+        if let Some(topic_writers) = self.discovered_writer_topic_index.get_mut(topic_name) {
+            topic_writers.remove(discovered_writer_handle);
+            if topic_writers.is_empty() {
+                self.discovered_writer_topic_index.remove(topic_name);
+            }
+        }
+        Some(discovered_writer_data)
+    }
+
+    pub fn discovered_writer_handle_list_by_guid_prefix(
+        &self,
+        guid_prefix: &GuidPrefix,
+    ) -> Vec<InstanceHandle> {
+        self.discovered_writer_list
+            .iter()
+            .filter(|(_, w)| w.writer_proxy.remote_writer_guid.prefix() == *guid_prefix)
+            .map(|(handle, _)| *handle)
+            .collect()
+    }
+
+    pub fn discovered_writer_data_list_by_topic<'a>(
+        &'a self,
+        topic_name: &str,
+    ) -> impl Iterator<Item = &'a DiscoveredWriterData> {
+        self.discovered_writer_topic_index
+            .get(topic_name)
+            .into_iter()
+            .flatten()
+            .filter_map(|handle| self.discovered_writer_list.get(handle))
     }
 
     pub fn default_subscriber_qos(&self) -> &SubscriberQos {
@@ -372,6 +711,30 @@ impl DomainParticipantEntity {
             && no_user_defined_topics
     }
 
+    pub fn get_contained_entities(&self) -> Vec<(InstanceHandle, EntityKind)> {
+        let mut contained_entities = Vec::new();
+        for topic in self
+            .topic_list
+            .values()
+            .filter(|t| !BUILT_IN_TOPIC_NAME_LIST.contains(&t.topic_name()))
+        {
+            contained_entities.push((topic.instance_handle(), EntityKind::Topic));
+        }
+        for publisher in &self.user_defined_publisher_list {
+            contained_entities.push((publisher.instance_handle(), EntityKind::Publisher));
+            for data_writer in publisher.data_writer_list() {
+                contained_entities.push((data_writer.instance_handle(), EntityKind::DataWriter));
+            }
+        }
+        for subscriber in &self.user_defined_subscriber_list {
+            contained_entities.push((subscriber.instance_handle(), EntityKind::Subscriber));
+            for data_reader in subscriber.data_reader_list() {
+                contained_entities.push((data_reader.instance_handle(), EntityKind::DataReader));
+            }
+        }
+        contained_entities
+    }
+
     pub fn listener_mask(&self) -> &[StatusKind] {
         &self.listener_mask
     }
@@ -397,9 +760,27 @@ impl DomainParticipantEntity {
         &self.domain_tag
     }
 
-    pub fn discovered_participant_list(
-        &self,
-    ) -> impl Iterator<Item = &SpdpDiscoveredParticipantData> {
-        self.discovered_participant_list.values()
+    pub fn is_domain_tag_accepted(&self, domain_tag: &str) -> bool {
+        domain_tag == self.domain_tag || self.accepted_domain_tags.iter().any(|t| t == domain_tag)
     }
 }
+
+impl Drop for DomainParticipantEntity {
+    fn drop(&mut self) {
+        // End the discovery change streams once the participant is gone
+        for event_sender in &self.discovered_participant_event_sender_list {
+            event_sender.close();
+        }
+        for event_sender in &self.discovered_topic_event_sender_list {
+            event_sender.close();
+        }
+    }
+}
+
+fn send_discovery_event<T: Clone>(
+    event_sender_list: &mut Vec<MpscSender<DiscoveryEvent<T>>>,
+    event: DiscoveryEvent<T>,
+) {
+    // Senders whose stream was dropped are closed and no longer needed
+    event_sender_list.retain(|event_sender| event_sender.send(event.clone()).is_ok());
+}
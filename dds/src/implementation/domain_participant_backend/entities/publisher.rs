@@ -117,7 +117,9 @@ impl PublisherEntity {
     }
 
     pub fn set_qos(&mut self, qos: PublisherQos) -> DdsResult<()> {
-        
+        if self.enabled {
+            self.qos.check_immutability(&qos)?;
+        }
         self.qos = qos;
         Ok(())
     }
@@ -4,12 +4,14 @@ use crate::{
         status_condition::status_condition_actor::StatusConditionActor,
     },
     infrastructure::{
-        error::DdsResult,
+        error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, SubscriberQos},
-        status::StatusKind,
+        qos_policy::PresentationQosPolicyAccessScopeKind,
+        status::{SampleLostStatus, StatusKind},
     },
     runtime::actor::Actor,
+    transport::history_cache::CacheChange,
 };
 
 use super::data_reader::DataReaderEntity;
@@ -23,6 +25,10 @@ pub struct SubscriberEntity {
     status_condition: Actor<StatusConditionActor>,
     listener: Option<Actor<SubscriberListenerActor>>,
     listener_mask: Vec<StatusKind>,
+    deleted_data_readers_sample_lost_count: i32,
+    sample_lost_total_count_read: i32,
+    access_count: usize,
+    deferred_cache_change_list: Vec<(InstanceHandle, CacheChange)>,
 }
 
 impl SubscriberEntity {
@@ -42,6 +48,10 @@ impl SubscriberEntity {
             status_condition,
             listener,
             listener_mask,
+            deleted_data_readers_sample_lost_count: 0,
+            sample_lost_total_count_read: 0,
+            access_count: 0,
+            deferred_cache_change_list: Vec::new(),
         }
     }
 
@@ -66,7 +76,10 @@ impl SubscriberEntity {
             .data_reader_list
             .iter()
             .position(|x| x.instance_handle() == handle)?;
-        Some(self.data_reader_list.remove(index))
+        let data_reader = self.data_reader_list.remove(index);
+        // The samples lost by deleted readers remain part of the subscriber total
+        self.deleted_data_readers_sample_lost_count += data_reader.sample_lost_total_count();
+        Some(data_reader)
     }
 
     pub fn get_data_reader(&self, handle: InstanceHandle) -> Option<&DataReaderEntity> {
@@ -106,6 +119,22 @@ impl SubscriberEntity {
         Ok(())
     }
 
+    pub fn get_sample_lost_status(&mut self) -> SampleLostStatus {
+        let total_count = self.deleted_data_readers_sample_lost_count
+            + self
+                .data_reader_list
+                .iter()
+                .map(|dr| dr.sample_lost_total_count())
+                .sum::<i32>();
+        let total_count_change = total_count - self.sample_lost_total_count_read;
+        self.sample_lost_total_count_read = total_count;
+
+        SampleLostStatus {
+            total_count,
+            total_count_change,
+        }
+    }
+
     pub fn qos(&self) -> &SubscriberQos {
         &self.qos
     }
@@ -138,4 +167,45 @@ impl SubscriberEntity {
     pub fn listener_mask(&self) -> &[StatusKind] {
         &self.listener_mask
     }
+
+    pub fn begin_access(&mut self) {
+        self.access_count += 1;
+    }
+
+    // Returns the changes received during the access which have to be added to the readers once
+    // the outermost access ends
+    pub fn end_access(&mut self) -> DdsResult<Vec<(InstanceHandle, CacheChange)>> {
+        if self.access_count == 0 {
+            return Err(DdsError::PreconditionNotMet(
+                "end_access called without a matching begin_access".to_string(),
+            ));
+        }
+        self.access_count -= 1;
+        if self.access_count == 0 {
+            Ok(core::mem::take(&mut self.deferred_cache_change_list))
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    pub fn is_access_in_progress(&self) -> bool {
+        self.access_count > 0
+    }
+
+    // With a coherent access to the group the readers don't change while the application accesses
+    // them so that the data read from the different readers is consistent
+    pub fn is_coherent_access_in_progress(&self) -> bool {
+        self.is_access_in_progress()
+            && self.qos.presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group
+            && self.qos.presentation.coherent_access
+    }
+
+    pub fn defer_cache_change(
+        &mut self,
+        data_reader_handle: InstanceHandle,
+        cache_change: CacheChange,
+    ) {
+        self.deferred_cache_change_list
+            .push((data_reader_handle, cache_change));
+    }
 }
@@ -58,7 +58,7 @@ impl TopicEntity {
         self.enabled = true;
     }
 
-    pub fn enabled(&mut self) -> bool {
+    pub fn enabled(&self) -> bool {
         self.enabled
     }
 
@@ -89,13 +89,13 @@ impl TopicEntity {
     pub fn set_qos(&mut self, qos: TopicQos) -> DdsResult<()> {
         qos.is_consistent()?;
 
-        if self.enabled && (self.qos.durability != qos.durability
-                || self.qos.liveliness != qos.liveliness
-                || self.qos.reliability != qos.reliability
-                || self.qos.destination_order != qos.destination_order
-                || self.qos.history != qos.history
-                || self.qos.resource_limits != qos.resource_limits || self.qos.ownership != qos.ownership) {
-            return Err(DdsError::ImmutablePolicy);
+        if self.enabled && (self.qos.durability != qos.durability
+                || self.qos.liveliness != qos.liveliness
+                || self.qos.reliability != qos.reliability
+                || self.qos.destination_order != qos.destination_order
+                || self.qos.history != qos.history
+                || self.qos.resource_limits != qos.resource_limits || self.qos.ownership != qos.ownership) {
+            return Err(DdsError::ImmutablePolicy);
         }
 
         self.qos = qos;
@@ -108,7 +108,8 @@ impl TopicEntity {
         self.status_condition
             .send_actor_mail(status_condition_actor::RemoveCommunicationState {
                 state: StatusKind::InconsistentTopic,
-            });
+            })
+            .ok();
         status
     }
 
@@ -118,6 +119,7 @@ impl TopicEntity {
         self.status_condition
             .send_actor_mail(status_condition_actor::AddCommunicationState {
                 state: StatusKind::InconsistentTopic,
-            });
+            })
+            .ok();
     }
 }
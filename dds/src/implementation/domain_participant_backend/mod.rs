@@ -1,4 +1,6 @@
 pub mod domain_participant_actor;
+pub mod durability_service;
 pub mod entities;
 pub mod handle;
+pub mod participant_message;
 pub mod services;
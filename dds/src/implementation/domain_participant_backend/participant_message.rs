@@ -0,0 +1,132 @@
+use crate::{
+    implementation::data_representation_builtin_endpoints::{
+        participant_message_data::{ParticipantMessageData, ParticipantMessageKind},
+        spdp_discovered_participant_data::{BuiltinEndpointSet, ParticipantProxy},
+    },
+    infrastructure::error::DdsResult,
+    transport::{
+        history_cache::CacheChange,
+        reader::{TransportStatefulReader, WriterProxy},
+        types::{
+            ChangeKind, DurabilityKind, EntityId, Guid, GuidPrefix, ReliabilityKind,
+            BUILT_IN_READER_WITH_KEY, BUILT_IN_WRITER_WITH_KEY, ENTITYID_UNKNOWN,
+        },
+        writer::{ReaderProxy, TransportStatefulWriter},
+    },
+};
+use std::collections::HashMap;
+
+pub const ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER: EntityId =
+    EntityId::new([0x00, 0x02, 0x00], BUILT_IN_WRITER_WITH_KEY);
+
+pub const ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER: EntityId =
+    EntityId::new([0x00, 0x02, 0x00], BUILT_IN_READER_WITH_KEY);
+
+// Builtin endpoints of the DCPSParticipantMessage topic as defined in RTPS 8.4.13 "Writer
+// Liveliness Protocol". Only the last message of each kind is kept in the writer history.
+pub struct ParticipantMessage {
+    writer: Box<dyn TransportStatefulWriter>,
+    reader: Box<dyn TransportStatefulReader>,
+    last_sequence_number: i64,
+    last_change_by_kind: HashMap<ParticipantMessageKind, i64>,
+}
+
+impl ParticipantMessage {
+    pub fn new(
+        writer: Box<dyn TransportStatefulWriter>,
+        reader: Box<dyn TransportStatefulReader>,
+    ) -> Self {
+        Self {
+            writer,
+            reader,
+            last_sequence_number: 0,
+            last_change_by_kind: HashMap::new(),
+        }
+    }
+
+    pub fn write(&mut self, kind: ParticipantMessageKind) -> DdsResult<()> {
+        let participant_message_data = ParticipantMessageData {
+            participant_guid_prefix: self.writer.guid().prefix(),
+            kind,
+            data: Vec::new(),
+        };
+        self.last_sequence_number += 1;
+        let change = CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid: self.writer.guid(),
+            sequence_number: self.last_sequence_number,
+            source_timestamp: None,
+            instance_handle: Some(participant_message_data.key()),
+            data_value: participant_message_data.serialize_data()?.into(),
+        };
+        if let Some(previous_sequence_number) = self
+            .last_change_by_kind
+            .insert(kind, self.last_sequence_number)
+        {
+            self.writer
+                .history_cache()
+                .remove_change(previous_sequence_number);
+        }
+        self.writer.history_cache().add_change(change);
+        Ok(())
+    }
+
+    pub fn add_matched_participant(&mut self, participant_proxy: &ParticipantProxy) {
+        let available_builtin_endpoints = &participant_proxy.available_builtin_endpoints;
+        let guid_prefix = participant_proxy.guid_prefix;
+
+        if available_builtin_endpoints
+            .has(BuiltinEndpointSet::BUILTIN_ENDPOINT_PARTICIPANT_MESSAGE_DATA_READER)
+        {
+            self.writer.add_matched_reader(ReaderProxy {
+                remote_reader_guid: Guid::new(
+                    guid_prefix,
+                    ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+                ),
+                remote_group_entity_id: ENTITYID_UNKNOWN,
+                reliability_kind: ReliabilityKind::Reliable,
+                durability_kind: DurabilityKind::TransientLocal,
+                unicast_locator_list: participant_proxy.metatraffic_unicast_locator_list.clone(),
+                multicast_locator_list: participant_proxy
+                    .metatraffic_multicast_locator_list
+                    .clone(),
+                expects_inline_qos: false,
+                disable_positive_acks_keep_duration: None,
+                prefer_multicast: false,
+                push_mode: true,
+                historical_data_limit: Default::default(),
+                content_filter: None,
+            });
+        }
+        if available_builtin_endpoints
+            .has(BuiltinEndpointSet::BUILTIN_ENDPOINT_PARTICIPANT_MESSAGE_DATA_WRITER)
+        {
+            self.reader.add_matched_writer(WriterProxy {
+                remote_writer_guid: Guid::new(
+                    guid_prefix,
+                    ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+                ),
+                remote_group_entity_id: ENTITYID_UNKNOWN,
+                reliability_kind: ReliabilityKind::Reliable,
+                durability_kind: DurabilityKind::TransientLocal,
+                unicast_locator_list: participant_proxy.metatraffic_unicast_locator_list.clone(),
+                multicast_locator_list: participant_proxy
+                    .metatraffic_multicast_locator_list
+                    .clone(),
+                data_max_size_serialized: Default::default(),
+                disable_positive_acks: false,
+            });
+        }
+    }
+
+    pub fn remove_matched_participant(&mut self, guid_prefix: GuidPrefix) {
+        self.writer.remove_matched_reader(Guid::new(
+            guid_prefix,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+        ));
+        self.reader.remove_matched_writer(Guid::new(
+            guid_prefix,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+        ));
+    }
+}
@@ -16,14 +16,18 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind},
-        status::{StatusKind, SubscriptionMatchedStatus},
+        statistics::DataReaderStatistics,
+        status::{
+            LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
+            SampleLostStatus, SampleRejectedStatus, StatusKind, SubscriptionMatchedStatus,
+        },
         time::Duration,
     },
     runtime::actor::{Actor, ActorAddress, Mail, MailHandler},
     subscription::sample_info::{InstanceStateKind, SampleInfo, SampleStateKind, ViewStateKind},
 };
 
-use super::discovery_service;
+use super::{discovery_service, message_service};
 
 pub struct Read {
     pub subscriber_handle: InstanceHandle,
@@ -47,6 +51,12 @@ impl MailHandler<Read> for DomainParticipantActor {
                     .get_mut_subscriber(message.subscriber_handle)
             }
             .ok_or(DdsError::AlreadyDeleted)?;
+        subscriber
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::DataOnReaders,
+            })
+            .ok();
 
         let data_reader = subscriber
             .get_mut_data_reader(message.data_reader_handle)
@@ -80,6 +90,12 @@ impl MailHandler<Take> for DomainParticipantActor {
             .domain_participant
             .get_mut_subscriber(message.subscriber_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        subscriber
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::DataOnReaders,
+            })
+            .ok();
         let data_reader = subscriber
             .get_mut_data_reader(message.data_reader_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
@@ -111,6 +127,12 @@ impl MailHandler<ReadNextInstance> for DomainParticipantActor {
             .domain_participant
             .get_mut_subscriber(message.subscriber_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        subscriber
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::DataOnReaders,
+            })
+            .ok();
         let data_reader = subscriber
             .get_mut_data_reader(message.data_reader_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
@@ -142,6 +164,12 @@ impl MailHandler<TakeNextInstance> for DomainParticipantActor {
             .domain_participant
             .get_mut_subscriber(message.subscriber_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        subscriber
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::DataOnReaders,
+            })
+            .ok();
         let data_reader = subscriber
             .get_mut_data_reader(message.data_reader_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
@@ -155,6 +183,241 @@ impl MailHandler<TakeNextInstance> for DomainParticipantActor {
     }
 }
 
+pub struct LookupInstance {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub serialized_data: Vec<u8>,
+}
+impl Mail for LookupInstance {
+    type Result = DdsResult<Option<InstanceHandle>>;
+}
+impl MailHandler<LookupInstance> for DomainParticipantActor {
+    fn handle(&mut self, message: LookupInstance) -> <LookupInstance as Mail>::Result {
+        let subscriber =
+            if message.subscriber_handle == self.domain_participant.instance_handle() {
+                Some(self.domain_participant.builtin_subscriber())
+            } else {
+                self.domain_participant
+                    .get_subscriber(message.subscriber_handle)
+            }
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        subscriber
+            .get_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .lookup_instance(&message.serialized_data)
+    }
+}
+
+pub struct GetKeyValue {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub serialized_key_holder: Vec<u8>,
+    pub instance_handle: InstanceHandle,
+}
+impl Mail for GetKeyValue {
+    type Result = DdsResult<Vec<u8>>;
+}
+impl MailHandler<GetKeyValue> for DomainParticipantActor {
+    fn handle(&mut self, message: GetKeyValue) -> <GetKeyValue as Mail>::Result {
+        let subscriber =
+            if message.subscriber_handle == self.domain_participant.instance_handle() {
+                Some(self.domain_participant.builtin_subscriber())
+            } else {
+                self.domain_participant
+                    .get_subscriber(message.subscriber_handle)
+            }
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        subscriber
+            .get_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_key_value(&message.serialized_key_holder, message.instance_handle)
+    }
+}
+
+pub struct GetReadConditionTriggerValue {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub sample_states: Vec<SampleStateKind>,
+    pub view_states: Vec<ViewStateKind>,
+    pub instance_states: Vec<InstanceStateKind>,
+}
+impl Mail for GetReadConditionTriggerValue {
+    type Result = DdsResult<bool>;
+}
+impl MailHandler<GetReadConditionTriggerValue> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetReadConditionTriggerValue,
+    ) -> <GetReadConditionTriggerValue as Mail>::Result {
+        let subscriber =
+            if message.subscriber_handle == self.domain_participant.instance_handle() {
+                Some(self.domain_participant.builtin_subscriber_mut())
+            } else {
+                self.domain_participant
+                    .get_mut_subscriber(message.subscriber_handle)
+            }
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        Ok(data_reader.has_sample_in_states(
+            &message.sample_states,
+            &message.view_states,
+            &message.instance_states,
+        ))
+    }
+}
+
+pub struct GetLivelinessChangedStatus {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+}
+impl Mail for GetLivelinessChangedStatus {
+    type Result = DdsResult<LivelinessChangedStatus>;
+}
+impl MailHandler<GetLivelinessChangedStatus> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetLivelinessChangedStatus,
+    ) -> <GetLivelinessChangedStatus as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let status = data_reader.get_liveliness_changed_status();
+        data_reader
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::LivelinessChanged,
+            })
+            .ok();
+        Ok(status)
+    }
+}
+
+pub struct GetRequestedDeadlineMissedStatus {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+}
+impl Mail for GetRequestedDeadlineMissedStatus {
+    type Result = DdsResult<RequestedDeadlineMissedStatus>;
+}
+impl MailHandler<GetRequestedDeadlineMissedStatus> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetRequestedDeadlineMissedStatus,
+    ) -> <GetRequestedDeadlineMissedStatus as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let status = data_reader.get_requested_deadline_missed_status();
+        data_reader
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::RequestedDeadlineMissed,
+            })
+            .ok();
+        Ok(status)
+    }
+}
+
+pub struct GetRequestedIncompatibleQosStatus {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+}
+impl Mail for GetRequestedIncompatibleQosStatus {
+    type Result = DdsResult<RequestedIncompatibleQosStatus>;
+}
+impl MailHandler<GetRequestedIncompatibleQosStatus> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetRequestedIncompatibleQosStatus,
+    ) -> <GetRequestedIncompatibleQosStatus as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let status = data_reader.get_requested_incompatible_qos_status();
+        data_reader
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::RequestedIncompatibleQos,
+            })
+            .ok();
+        Ok(status)
+    }
+}
+
+pub struct GetSampleLostStatus {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+}
+impl Mail for GetSampleLostStatus {
+    type Result = DdsResult<SampleLostStatus>;
+}
+impl MailHandler<GetSampleLostStatus> for DomainParticipantActor {
+    fn handle(&mut self, message: GetSampleLostStatus) -> <GetSampleLostStatus as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let status = data_reader.get_sample_lost_status();
+        data_reader
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::SampleLost,
+            })
+            .ok();
+        Ok(status)
+    }
+}
+
+pub struct GetSampleRejectedStatus {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+}
+impl Mail for GetSampleRejectedStatus {
+    type Result = DdsResult<SampleRejectedStatus>;
+}
+impl MailHandler<GetSampleRejectedStatus> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetSampleRejectedStatus,
+    ) -> <GetSampleRejectedStatus as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let status = data_reader.get_sample_rejected_status();
+        data_reader
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::SampleRejected,
+            })
+            .ok();
+        Ok(status)
+    }
+}
+
 pub struct GetSubscriptionMatchedStatus {
     pub subscriber_handle: InstanceHandle,
     pub data_reader_handle: InstanceHandle,
@@ -175,15 +438,35 @@ impl MailHandler<GetSubscriptionMatchedStatus> for DomainParticipantActor {
             .get_mut_data_reader(message.data_reader_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
         let status = data_reader.get_subscription_matched_status();
-        data_reader.status_condition().send_actor_mail(
-            status_condition_actor::RemoveCommunicationState {
+        data_reader
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
                 state: StatusKind::SubscriptionMatched,
-            },
-        );
+            })
+            .ok();
         Ok(status)
     }
 }
 
+pub struct GetStatistics {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+}
+impl Mail for GetStatistics {
+    type Result = DdsResult<DataReaderStatistics>;
+}
+impl MailHandler<GetStatistics> for DomainParticipantActor {
+    fn handle(&mut self, message: GetStatistics) -> <GetStatistics as Mail>::Result {
+        Ok(self
+            .domain_participant
+            .get_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_statistics())
+    }
+}
+
 pub struct WaitForHistoricalData {
     pub participant_address: ActorAddress<DomainParticipantActor>,
     pub subscriber_handle: InstanceHandle,
@@ -302,9 +585,28 @@ impl MailHandler<SetQos> for DomainParticipantActor {
             .get_mut_data_reader(message.data_reader_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
 
+        let deadline_changed = data_reader.qos().deadline != qos.deadline;
         data_reader.set_qos(qos)?;
-
+        if deadline_changed {
+            // The instances keep being monitored but with the new deadline period
+            for instance_handle in data_reader.remove_instance_deadline_missed_tasks() {
+                message_service::schedule_requested_deadline_missed_task(
+                    self,
+                    message.subscriber_handle,
+                    message.data_reader_handle,
+                    instance_handle,
+                    message.participant_address.clone(),
+                )?;
+            }
+        }
+        let data_reader = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
         if data_reader.enabled() {
+            let topic_name = data_reader.topic_name().to_owned();
             message
                 .participant_address
                 .send_actor_mail(discovery_service::AnnounceDataReader {
@@ -312,6 +614,13 @@ impl MailHandler<SetQos> for DomainParticipantActor {
                     data_reader_handle: message.data_reader_handle,
                 })
                 .ok();
+            discovery_service::rematch_data_reader(
+                self,
+                message.subscriber_handle,
+                message.data_reader_handle,
+                &topic_name,
+                &message.participant_address,
+            );
         }
 
         Ok(())
@@ -352,15 +661,21 @@ impl MailHandler<Enable> for DomainParticipantActor {
             .domain_participant
             .get_mut_subscriber(message.subscriber_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        if !subscriber.enabled() {
+            return Err(DdsError::PreconditionNotMet(
+                "Data reader can only be enabled once its parent subscriber is enabled".to_string(),
+            ));
+        }
         let data_reader = subscriber
             .get_mut_data_reader(message.data_reader_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
         if !data_reader.enabled() {
             data_reader.enable();
+            let topic_name = data_reader.topic_name().to_owned();
 
             for discovered_writer_data in self
                 .domain_participant
-                .publication_builtin_topic_data_list()
+                .discovered_writer_data_list_by_topic(&topic_name)
                 .cloned()
             {
                 message
@@ -398,8 +713,9 @@ impl Mail for SetListener {
 impl MailHandler<SetListener> for DomainParticipantActor {
     fn handle(&mut self, message: SetListener) -> <SetListener as Mail>::Result {
         let listener = message.listener.map(|l| {
-            Actor::spawn(
+            Actor::spawn_with_mailbox(
                 DataReaderListenerActor::new(l),
+                self.listener_mailbox,
                 &self.listener_executor.handle(),
             )
         });
@@ -4,9 +4,10 @@ use crate::{
     builtin_topics::SubscriptionBuiltinTopicData,
     implementation::{
         any_data_writer_listener::AnyDataWriterListener,
+        data_representation_builtin_endpoints::participant_message_data::PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE,
         domain_participant_backend::{
-            domain_participant_actor::DomainParticipantActor,
-            services::message_service::AreAllChangesAcknowledged,
+            domain_participant_actor::DomainParticipantActor, durability_service::DurableSample,
+            entities::data_writer::TransportWriterKind,
         },
         listeners::data_writer_listener::DataWriterListenerActor,
         status_condition::status_condition_actor,
@@ -18,18 +19,49 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, QosKind},
-        status::{OfferedDeadlineMissedStatus, PublicationMatchedStatus, StatusKind},
+        qos_policy::{DurabilityQosPolicyKind, LivelinessQosPolicyKind, ReliabilityQosPolicyKind},
+        statistics::DataWriterStatistics,
+        status::{
+            LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
+            PublicationMatchedStatus, StatusKind,
+        },
         time::{Duration, DurationKind, Time},
     },
-    runtime::actor::{Actor, ActorAddress, Mail, MailHandler},
+    runtime::{
+        actor::{Actor, ActorAddress, Mail, MailHandler},
+        oneshot::oneshot,
+    },
 };
 
 use super::{discovery_service, event_service, message_service};
 
+pub struct RegisterInstance {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+    pub serialized_data: Vec<u8>,
+    pub timestamp: Time,
+}
+impl Mail for RegisterInstance {
+    type Result = DdsResult<Option<InstanceHandle>>;
+}
+impl MailHandler<RegisterInstance> for DomainParticipantActor {
+    fn handle(&mut self, message: RegisterInstance) -> <RegisterInstance as Mail>::Result {
+        self.domain_participant
+            .check_source_timestamp(message.timestamp)?;
+        self.domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .register_instance_w_timestamp(&message.serialized_data)
+    }
+}
+
 pub struct UnregisterInstance {
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
     pub serialized_data: Vec<u8>,
+    pub handle: Option<InstanceHandle>,
     pub timestamp: Time,
 }
 impl Mail for UnregisterInstance {
@@ -37,6 +69,8 @@ impl Mail for UnregisterInstance {
 }
 impl MailHandler<UnregisterInstance> for DomainParticipantActor {
     fn handle(&mut self, message: UnregisterInstance) -> <UnregisterInstance as Mail>::Result {
+        self.domain_participant
+            .check_source_timestamp(message.timestamp)?;
         let publisher = self
             .domain_participant
             .get_mut_publisher(message.publisher_handle)
@@ -45,9 +79,24 @@ impl MailHandler<UnregisterInstance> for DomainParticipantActor {
             .data_writer_list_mut()
             .find(|x| x.instance_handle() == message.data_writer_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        if let Some(handle) = message.handle {
+            let instance_handle = get_instance_handle_from_serialized_foo(
+                &message.serialized_data,
+                data_writer.type_support().as_ref(),
+            )?;
+            if handle != instance_handle {
+                return if data_writer.contains_instance(&handle) {
+                    Err(DdsError::PreconditionNotMet(
+                        "Handle does not correspond to the instance".to_string(),
+                    ))
+                } else {
+                    Err(DdsError::BadParameter)
+                };
+            }
+        }
         let serialized_key = get_serialized_key_from_serialized_foo(
             &message.serialized_data,
-            data_writer.type_support(),
+            data_writer.type_support().as_ref(),
         )?;
         data_writer.unregister_w_timestamp(serialized_key, message.timestamp)?;
 
@@ -79,7 +128,7 @@ impl MailHandler<LookupInstance> for DomainParticipantActor {
 
         let instance_handle = get_instance_handle_from_serialized_foo(
             &message.serialized_data,
-            data_writer.type_support(),
+            data_writer.type_support().as_ref(),
         )?;
 
         Ok(data_writer
@@ -88,6 +137,33 @@ impl MailHandler<LookupInstance> for DomainParticipantActor {
     }
 }
 
+pub struct GetKeyValue {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+    pub serialized_key_holder: Vec<u8>,
+    pub instance_handle: InstanceHandle,
+}
+impl Mail for GetKeyValue {
+    type Result = DdsResult<Vec<u8>>;
+}
+impl MailHandler<GetKeyValue> for DomainParticipantActor {
+    fn handle(&mut self, message: GetKeyValue) -> <GetKeyValue as Mail>::Result {
+        self.domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .data_writer_list_mut()
+            .find(|x| x.instance_handle() == message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_key_value(&message.serialized_key_holder, message.instance_handle)
+    }
+}
+
+/// Data which a reliable writer can only write after `delay` without exceeding its publish rate
+pub struct PublishRateExceeded<T> {
+    pub data: T,
+    pub delay: Duration,
+}
+
 pub struct WriteWTimestamp {
     pub participant_address: ActorAddress<DomainParticipantActor>,
     pub publisher_handle: InstanceHandle,
@@ -96,10 +172,12 @@ pub struct WriteWTimestamp {
     pub timestamp: Time,
 }
 impl Mail for WriteWTimestamp {
-    type Result = DdsResult<()>;
+    type Result = DdsResult<Option<PublishRateExceeded<Vec<u8>>>>;
 }
 impl MailHandler<WriteWTimestamp> for DomainParticipantActor {
     fn handle(&mut self, message: WriteWTimestamp) -> <WriteWTimestamp as Mail>::Result {
+        self.domain_participant
+            .check_source_timestamp(message.timestamp)?;
         let now = self.domain_participant.get_current_time();
         let publisher = self
             .domain_participant
@@ -110,61 +188,196 @@ impl MailHandler<WriteWTimestamp> for DomainParticipantActor {
             .ok_or(DdsError::AlreadyDeleted)?;
         let instance_handle = get_instance_handle_from_serialized_foo(
             &message.serialized_data,
-            data_writer.type_support(),
+            data_writer.type_support().as_ref(),
         )?;
 
+        let byte_count = message.serialized_data.len();
+        if let Some(delay) = data_writer.publish_rate_delay(now, 1, byte_count) {
+            if data_writer.qos().reliability.kind == ReliabilityQosPolicyKind::Reliable {
+                return Ok(Some(PublishRateExceeded {
+                    data: message.serialized_data,
+                    delay,
+                }));
+            }
+            data_writer.drop_rate_limited_samples(1);
+            return Ok(None);
+        }
+
+        let durable_sample = is_durable(data_writer.qos().durability.kind).then(|| DurableSample {
+            instance_handle,
+            serialized_data: message.serialized_data.clone(),
+            timestamp: message.timestamp,
+        });
         match data_writer.qos().lifespan.duration {
             DurationKind::Finite(lifespan_duration) => {
-                let timer_handle = self.timer_driver.handle();
                 let sleep_duration = message.timestamp - now + lifespan_duration;
                 if sleep_duration > Duration::new(0, 0) {
                     let sequence_number = data_writer
                         .write_w_timestamp(message.serialized_data, message.timestamp)?;
                     let participant_address = message.participant_address.clone();
-                    self.backend_executor.handle().spawn(async move {
-                        timer_handle.sleep(sleep_duration.into()).await;
-                        participant_address
-                            .send_actor_mail(message_service::RemoveWriterChange {
-                                publisher_handle: message.publisher_handle,
-                                data_writer_handle: message.data_writer_handle,
-                                sequence_number,
-                            })
-                            .ok();
-                    });
+                    self.timer_driver
+                        .handle()
+                        .schedule(sleep_duration.into(), move || {
+                            participant_address
+                                .send_actor_mail(message_service::RemoveWriterChange {
+                                    publisher_handle: message.publisher_handle,
+                                    data_writer_handle: message.data_writer_handle,
+                                    sequence_number,
+                                })
+                                .ok();
+                        });
                 }
             }
             DurationKind::Infinite => {
                 data_writer.write_w_timestamp(message.serialized_data, message.timestamp)?;
             }
         }
+        data_writer.add_published_samples(now, 1, byte_count);
+        if let Some(durable_sample) = durable_sample {
+            self.durability_service.add_sample(
+                data_writer.qos().durability.kind,
+                data_writer.topic_name(),
+                &data_writer.qos().durability_service,
+                durable_sample,
+            );
+        }
 
-        if let DurationKind::Finite(deadline_missed_period) = data_writer.qos().deadline.period {
-            let timer_handle = self.timer_driver.handle();
-            let offered_deadline_missed_task = self.backend_executor.handle().spawn(async move {
-                loop {
-                    timer_handle.sleep(deadline_missed_period.into()).await;
-                    message
-                        .participant_address
-                        .send_actor_mail(event_service::OfferedDeadlineMissed {
-                            publisher_handle: message.publisher_handle,
-                            data_writer_handle: message.data_writer_handle,
-                            change_instance_handle: instance_handle,
-                            participant_address: message.participant_address.clone(),
-                        })
-                        .ok();
+        schedule_offered_deadline_missed_task(
+            self,
+            message.publisher_handle,
+            message.data_writer_handle,
+            instance_handle,
+            message.participant_address.clone(),
+        )?;
+        renew_writer_liveliness(
+            self,
+            message.publisher_handle,
+            message.data_writer_handle,
+            message.participant_address,
+        )?;
+
+        Ok(None)
+    }
+}
+
+pub struct WriteBatchWTimestamp {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+    pub serialized_data_list: Vec<Vec<u8>>,
+    pub timestamp: Time,
+}
+impl Mail for WriteBatchWTimestamp {
+    type Result = DdsResult<Option<PublishRateExceeded<Vec<Vec<u8>>>>>;
+}
+impl MailHandler<WriteBatchWTimestamp> for DomainParticipantActor {
+    fn handle(&mut self, message: WriteBatchWTimestamp) -> <WriteBatchWTimestamp as Mail>::Result {
+        self.domain_participant
+            .check_source_timestamp(message.timestamp)?;
+        let now = self.domain_participant.get_current_time();
+        let publisher = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_writer = publisher
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let instance_handle_list = message
+            .serialized_data_list
+            .iter()
+            .map(|serialized_data| {
+                get_instance_handle_from_serialized_foo(
+                    serialized_data,
+                    data_writer.type_support().as_ref(),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let sample_count = message.serialized_data_list.len();
+        let byte_count = message.serialized_data_list.iter().map(Vec::len).sum();
+        if let Some(delay) = data_writer.publish_rate_delay(now, sample_count, byte_count) {
+            if data_writer.qos().reliability.kind == ReliabilityQosPolicyKind::Reliable {
+                return Ok(Some(PublishRateExceeded {
+                    data: message.serialized_data_list,
+                    delay,
+                }));
+            }
+            data_writer.drop_rate_limited_samples(sample_count);
+            return Ok(None);
+        }
+
+        let durable_sample_list = if is_durable(data_writer.qos().durability.kind) {
+            instance_handle_list
+                .iter()
+                .zip(&message.serialized_data_list)
+                .map(|(&instance_handle, serialized_data)| DurableSample {
+                    instance_handle,
+                    serialized_data: serialized_data.clone(),
+                    timestamp: message.timestamp,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        match data_writer.qos().lifespan.duration {
+            DurationKind::Finite(lifespan_duration) => {
+                let sleep_duration = message.timestamp - now + lifespan_duration;
+                if sleep_duration > Duration::new(0, 0) {
+                    let sequence_number_list = data_writer
+                        .write_batch_w_timestamp(message.serialized_data_list, message.timestamp)?;
+                    let participant_address = message.participant_address.clone();
+                    self.timer_driver
+                        .handle()
+                        .schedule(sleep_duration.into(), move || {
+                            for sequence_number in sequence_number_list {
+                                participant_address
+                                    .send_actor_mail(message_service::RemoveWriterChange {
+                                        publisher_handle: message.publisher_handle,
+                                        data_writer_handle: message.data_writer_handle,
+                                        sequence_number,
+                                    })
+                                    .ok();
+                            }
+                        });
                 }
-            });
-            data_writer.insert_instance_deadline_missed_task(
-                instance_handle,
-                offered_deadline_missed_task,
+            }
+            DurationKind::Infinite => {
+                data_writer
+                    .write_batch_w_timestamp(message.serialized_data_list, message.timestamp)?;
+            }
+        }
+        data_writer.add_published_samples(now, sample_count, byte_count);
+        for durable_sample in durable_sample_list {
+            self.durability_service.add_sample(
+                data_writer.qos().durability.kind,
+                data_writer.topic_name(),
+                &data_writer.qos().durability_service,
+                durable_sample,
             );
         }
 
-        Ok(())
+        for instance_handle in instance_handle_list {
+            schedule_offered_deadline_missed_task(
+                self,
+                message.publisher_handle,
+                message.data_writer_handle,
+                instance_handle,
+                message.participant_address.clone(),
+            )?;
+        }
+        renew_writer_liveliness(
+            self,
+            message.publisher_handle,
+            message.data_writer_handle,
+            message.participant_address,
+        )?;
+
+        Ok(None)
     }
 }
 
 pub struct DisposeWTimestamp {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
     pub serialized_data: Vec<u8>,
@@ -175,6 +388,8 @@ impl Mail for DisposeWTimestamp {
 }
 impl MailHandler<DisposeWTimestamp> for DomainParticipantActor {
     fn handle(&mut self, message: DisposeWTimestamp) -> <DisposeWTimestamp as Mail>::Result {
+        self.domain_participant
+            .check_source_timestamp(message.timestamp)?;
         let publisher = self
             .domain_participant
             .get_mut_publisher(message.publisher_handle)
@@ -182,16 +397,109 @@ impl MailHandler<DisposeWTimestamp> for DomainParticipantActor {
         let data_writer = publisher
             .get_mut_data_writer(message.data_writer_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        let instance_handle = get_instance_handle_from_serialized_foo(
+            &message.serialized_data,
+            data_writer.type_support().as_ref(),
+        )?;
         let serialized_key = get_serialized_key_from_serialized_foo(
             &message.serialized_data,
-            data_writer.type_support(),
+            data_writer.type_support().as_ref(),
         )?;
-        data_writer.dispose_w_timestamp(serialized_key, message.timestamp)
+        data_writer.dispose_w_timestamp(serialized_key, message.timestamp)?;
+        cleanup_durable_instance(
+            self,
+            message.publisher_handle,
+            message.data_writer_handle,
+            instance_handle,
+            message.timestamp,
+            message.participant_address,
+        )
     }
 }
 
-pub struct WaitForAcknowledgments {
+pub struct DisposeWHandleWTimestamp {
     pub participant_address: ActorAddress<DomainParticipantActor>,
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+    pub instance_handle: InstanceHandle,
+    pub timestamp: Time,
+}
+impl Mail for DisposeWHandleWTimestamp {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<DisposeWHandleWTimestamp> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: DisposeWHandleWTimestamp,
+    ) -> <DisposeWHandleWTimestamp as Mail>::Result {
+        self.domain_participant
+            .check_source_timestamp(message.timestamp)?;
+        self.domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .dispose_w_handle_w_timestamp(message.instance_handle, message.timestamp)?;
+        cleanup_durable_instance(
+            self,
+            message.publisher_handle,
+            message.data_writer_handle,
+            message.instance_handle,
+            message.timestamp,
+            message.participant_address,
+        )
+    }
+}
+
+pub struct UnregisterInstanceWHandleWTimestamp {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+    pub instance_handle: InstanceHandle,
+    pub timestamp: Time,
+}
+impl Mail for UnregisterInstanceWHandleWTimestamp {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<UnregisterInstanceWHandleWTimestamp> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: UnregisterInstanceWHandleWTimestamp,
+    ) -> <UnregisterInstanceWHandleWTimestamp as Mail>::Result {
+        self.domain_participant
+            .check_source_timestamp(message.timestamp)?;
+        self.domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .unregister_w_handle_w_timestamp(message.instance_handle, message.timestamp)
+    }
+}
+
+pub struct UnregisterAllInstances {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+}
+impl Mail for UnregisterAllInstances {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<UnregisterAllInstances> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: UnregisterAllInstances,
+    ) -> <UnregisterAllInstances as Mail>::Result {
+        let timestamp = self.domain_participant.get_current_time();
+        self.domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .unregister_all_instances(timestamp);
+        Ok(())
+    }
+}
+
+pub struct WaitForAcknowledgments {
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
     pub timeout: Duration,
@@ -204,33 +512,278 @@ impl MailHandler<WaitForAcknowledgments> for DomainParticipantActor {
         &mut self,
         message: WaitForAcknowledgments,
     ) -> <WaitForAcknowledgments as Mail>::Result {
+        let Some(data_writer) = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .and_then(|p| p.get_mut_data_writer(message.data_writer_handle))
+        else {
+            return Box::pin(async { Err(DdsError::AlreadyDeleted) });
+        };
+        // The transport notifies when the changes are acknowledged and drops the callback
+        // if the writer is deleted before
+        let (acknowledgment_sender, acknowledgment_receiver) = oneshot();
+        data_writer.on_all_changes_acknowledged(Box::new(move || acknowledgment_sender.send(())));
         let timer_handle = self.timer_driver.handle();
         Box::pin(async move {
             timer_handle
-                .timeout(
-                    message.timeout.into(),
-                    Box::pin(async move {
-                        loop {
-                            let all_changes_ack = message
-                                .participant_address
-                                .send_actor_mail(AreAllChangesAcknowledged {
-                                    publisher_handle: message.publisher_handle,
-                                    data_writer_handle: message.data_writer_handle,
-                                })?
-                                .receive_reply()
-                                .await?;
-                            if all_changes_ack {
-                                return Ok(());
-                            }
-                        }
-                    }),
-                )
+                .timeout(message.timeout.into(), Box::pin(acknowledgment_receiver))
                 .await
                 .map_err(|_| DdsError::Timeout)?
+                .map_err(|_| DdsError::AlreadyDeleted)
         })
     }
 }
 
+pub struct Flush {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+}
+impl Mail for Flush {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<Flush> for DomainParticipantActor {
+    fn handle(&mut self, message: Flush) -> <Flush as Mail>::Result {
+        let data_writer = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        if !data_writer.enabled() {
+            return Err(DdsError::NotEnabled);
+        }
+
+        if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
+            w.flush();
+        }
+        Ok(())
+    }
+}
+
+pub struct AssertLiveliness {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for AssertLiveliness {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AssertLiveliness> for DomainParticipantActor {
+    fn handle(&mut self, message: AssertLiveliness) -> <AssertLiveliness as Mail>::Result {
+        let data_writer = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        if !data_writer.enabled() {
+            return Err(DdsError::NotEnabled);
+        }
+
+        match data_writer.qos().liveliness.kind {
+            LivelinessQosPolicyKind::Automatic => (),
+            LivelinessQosPolicyKind::ManualByTopic => {
+                if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
+                    w.assert_liveliness();
+                }
+            }
+            LivelinessQosPolicyKind::ManualByParticipant => self
+                .participant_message
+                .write(PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE)?,
+        }
+        renew_writer_liveliness(
+            self,
+            message.publisher_handle,
+            message.data_writer_handle,
+            message.participant_address,
+        )
+    }
+}
+
+// Restarts the periodic deadline timer of the instance after which the offered deadline missed
+// status is signalled every deadline period until the instance is written again
+fn is_durable(kind: DurabilityQosPolicyKind) -> bool {
+    matches!(
+        kind,
+        DurabilityQosPolicyKind::Transient | DurabilityQosPolicyKind::Persistent
+    )
+}
+
+// The samples of a disposed instance are kept by the durability service for the
+// service_cleanup_delay so that they still reach the readers joining meanwhile
+fn cleanup_durable_instance(
+    domain_participant_actor: &mut DomainParticipantActor,
+    publisher_handle: InstanceHandle,
+    data_writer_handle: InstanceHandle,
+    instance_handle: InstanceHandle,
+    timestamp: Time,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) -> DdsResult<()> {
+    let data_writer = domain_participant_actor
+        .domain_participant
+        .get_publisher(publisher_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .get_data_writer(data_writer_handle)
+        .ok_or(DdsError::AlreadyDeleted)?;
+    let durability_kind = data_writer.qos().durability.kind;
+    if !is_durable(durability_kind) {
+        return Ok(());
+    }
+    let topic_name = data_writer.topic_name().to_owned();
+    match data_writer.qos().durability_service.service_cleanup_delay {
+        DurationKind::Finite(service_cleanup_delay)
+            if service_cleanup_delay > Duration::new(0, 0) =>
+        {
+            domain_participant_actor.timer_driver.handle().schedule(
+                service_cleanup_delay.into(),
+                move || {
+                    participant_address
+                        .send_actor_mail(message_service::RemoveDurableInstance {
+                            durability_kind,
+                            topic_name,
+                            instance_handle,
+                            timestamp,
+                        })
+                        .ok();
+                },
+            );
+        }
+        DurationKind::Finite(_) => domain_participant_actor.durability_service.remove_instance(
+            durability_kind,
+            &topic_name,
+            instance_handle,
+            timestamp,
+        ),
+        DurationKind::Infinite => (),
+    }
+    Ok(())
+}
+
+pub fn schedule_offered_deadline_missed_task(
+    domain_participant_actor: &mut DomainParticipantActor,
+    publisher_handle: InstanceHandle,
+    data_writer_handle: InstanceHandle,
+    instance_handle: InstanceHandle,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) -> DdsResult<()> {
+    let data_writer = domain_participant_actor
+        .domain_participant
+        .get_mut_publisher(publisher_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .get_mut_data_writer(data_writer_handle)
+        .ok_or(DdsError::AlreadyDeleted)?;
+    if let DurationKind::Finite(deadline_missed_period) = data_writer.qos().deadline.period {
+        let offered_deadline_missed_task = domain_participant_actor
+            .timer_driver
+            .handle()
+            .schedule_periodic(deadline_missed_period.into(), move || {
+                participant_address
+                    .send_actor_mail(event_service::OfferedDeadlineMissed {
+                        publisher_handle,
+                        data_writer_handle,
+                        change_instance_handle: instance_handle,
+                        participant_address: participant_address.clone(),
+                    })
+                    .ok();
+            });
+        data_writer
+            .insert_instance_deadline_missed_task(instance_handle, offered_deadline_missed_task);
+    }
+    Ok(())
+}
+
+// Writing or asserting the liveliness of a writer with MANUAL_BY_TOPIC liveliness renews its
+// own lease, whereas for MANUAL_BY_PARTICIPANT it renews the lease of all the writers of the
+// participant with that kind. Writers with AUTOMATIC liveliness never lose it.
+pub fn renew_writer_liveliness(
+    domain_participant_actor: &mut DomainParticipantActor,
+    publisher_handle: InstanceHandle,
+    data_writer_handle: InstanceHandle,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) -> DdsResult<()> {
+    let liveliness_kind = domain_participant_actor
+        .domain_participant
+        .get_mut_publisher(publisher_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .get_mut_data_writer(data_writer_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .qos()
+        .liveliness
+        .kind;
+    match liveliness_kind {
+        LivelinessQosPolicyKind::Automatic => Ok(()),
+        LivelinessQosPolicyKind::ManualByTopic => renew_liveliness_lease(
+            domain_participant_actor,
+            publisher_handle,
+            data_writer_handle,
+            participant_address,
+        ),
+        LivelinessQosPolicyKind::ManualByParticipant => {
+            renew_participant_liveliness(domain_participant_actor, participant_address);
+            Ok(())
+        }
+    }
+}
+
+pub fn renew_participant_liveliness(
+    domain_participant_actor: &mut DomainParticipantActor,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) {
+    let mut data_writer_list = Vec::new();
+    for publisher in domain_participant_actor.domain_participant.publisher_list() {
+        for data_writer in publisher.data_writer_list() {
+            if data_writer.enabled()
+                && data_writer.qos().liveliness.kind == LivelinessQosPolicyKind::ManualByParticipant
+            {
+                data_writer_list.push((publisher.instance_handle(), data_writer.instance_handle()));
+            }
+        }
+    }
+    for (publisher_handle, data_writer_handle) in data_writer_list {
+        renew_liveliness_lease(
+            domain_participant_actor,
+            publisher_handle,
+            data_writer_handle,
+            participant_address.clone(),
+        )
+        .ok();
+    }
+}
+
+fn renew_liveliness_lease(
+    domain_participant_actor: &mut DomainParticipantActor,
+    publisher_handle: InstanceHandle,
+    data_writer_handle: InstanceHandle,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) -> DdsResult<()> {
+    let data_writer = domain_participant_actor
+        .domain_participant
+        .get_mut_publisher(publisher_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .get_mut_data_writer(data_writer_handle)
+        .ok_or(DdsError::AlreadyDeleted)?;
+    if let DurationKind::Finite(lease_duration) = data_writer.qos().liveliness.lease_duration {
+        let lease_id = data_writer.generate_liveliness_lease_id();
+        let lease_task = domain_participant_actor.timer_driver.handle().schedule(
+            lease_duration.into(),
+            move || {
+                participant_address
+                    .send_actor_mail(event_service::LivelinessLost {
+                        publisher_handle,
+                        data_writer_handle,
+                        lease_id,
+                        participant_address: participant_address.clone(),
+                    })
+                    .ok();
+            },
+        );
+        data_writer.renew_liveliness_lease(lease_id, lease_task);
+    }
+    Ok(())
+}
+
 pub struct GetOfferedDeadlineMissedStatus {
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
@@ -255,6 +808,111 @@ impl MailHandler<GetOfferedDeadlineMissedStatus> for DomainParticipantActor {
     }
 }
 
+pub struct GetLivelinessLostStatus {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+}
+impl Mail for GetLivelinessLostStatus {
+    type Result = DdsResult<LivelinessLostStatus>;
+}
+impl MailHandler<GetLivelinessLostStatus> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetLivelinessLostStatus,
+    ) -> <GetLivelinessLostStatus as Mail>::Result {
+        let publisher = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_writer = publisher
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        let status = data_writer.get_liveliness_lost_status();
+
+        data_writer
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::LivelinessLost,
+            })
+            .ok();
+        Ok(status)
+    }
+}
+
+pub struct GetOfferedIncompatibleQosStatus {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+}
+impl Mail for GetOfferedIncompatibleQosStatus {
+    type Result = DdsResult<OfferedIncompatibleQosStatus>;
+}
+impl MailHandler<GetOfferedIncompatibleQosStatus> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetOfferedIncompatibleQosStatus,
+    ) -> <GetOfferedIncompatibleQosStatus as Mail>::Result {
+        let publisher = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_writer = publisher
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        let status = data_writer.get_offered_incompatible_qos_status();
+
+        data_writer
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::OfferedIncompatibleQos,
+            })
+            .ok();
+        Ok(status)
+    }
+}
+
+pub struct GetDroppedSampleCount {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+}
+impl Mail for GetDroppedSampleCount {
+    type Result = DdsResult<u64>;
+}
+impl MailHandler<GetDroppedSampleCount> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: GetDroppedSampleCount,
+    ) -> <GetDroppedSampleCount as Mail>::Result {
+        Ok(self
+            .domain_participant
+            .get_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .dropped_sample_count())
+    }
+}
+
+pub struct GetStatistics {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+}
+impl Mail for GetStatistics {
+    type Result = DdsResult<DataWriterStatistics>;
+}
+impl MailHandler<GetStatistics> for DomainParticipantActor {
+    fn handle(&mut self, message: GetStatistics) -> <GetStatistics as Mail>::Result {
+        Ok(self
+            .domain_participant
+            .get_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_statistics())
+    }
+}
+
 pub struct GetPublicationMatchedStatus {
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
@@ -277,11 +935,12 @@ impl MailHandler<GetPublicationMatchedStatus> for DomainParticipantActor {
 
         let status = data_writer.get_publication_matched_status();
 
-        data_writer.status_condition().send_actor_mail(
-            status_condition_actor::RemoveCommunicationState {
+        data_writer
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
                 state: StatusKind::PublicationMatched,
-            },
-        );
+            })
+            .ok();
         Ok(status)
     }
 }
@@ -355,8 +1014,32 @@ impl MailHandler<SetDataWriterQos> for DomainParticipantActor {
             .get_mut_data_writer(message.data_writer_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
 
+        let deadline_changed = data_writer.qos().deadline != qos.deadline;
+        let transport_priority = qos.transport_priority.value;
         data_writer.set_qos(qos)?;
+        if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
+            w.set_transport_priority(transport_priority);
+        }
+        if deadline_changed {
+            // The instances keep being monitored but with the new deadline period
+            for instance_handle in data_writer.remove_instance_deadline_missed_tasks() {
+                schedule_offered_deadline_missed_task(
+                    self,
+                    message.publisher_handle,
+                    message.data_writer_handle,
+                    instance_handle,
+                    message.participant_address.clone(),
+                )?;
+            }
+        }
+        let data_writer = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
         if data_writer.enabled() {
+            let topic_name = data_writer.topic_name().to_owned();
             message
                 .participant_address
                 .send_actor_mail(discovery_service::AnnounceDataWriter {
@@ -364,6 +1047,13 @@ impl MailHandler<SetDataWriterQos> for DomainParticipantActor {
                     data_writer_handle: message.data_writer_handle,
                 })
                 .ok();
+            discovery_service::rematch_data_writer(
+                self,
+                message.publisher_handle,
+                message.data_writer_handle,
+                &topic_name,
+                &message.participant_address,
+            );
         }
 
         Ok(())
@@ -404,15 +1094,40 @@ impl MailHandler<Enable> for DomainParticipantActor {
             .domain_participant
             .get_mut_publisher(message.publisher_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        if !publisher.enabled() {
+            return Err(DdsError::PreconditionNotMet(
+                "Data writer can only be enabled once its parent publisher is enabled".to_string(),
+            ));
+        }
         let data_writer = publisher
             .get_mut_data_writer(message.data_writer_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
         if !data_writer.enabled() {
             data_writer.enable();
+            let topic_name = data_writer.topic_name().to_owned();
+            let durability_kind = data_writer.qos().durability.kind;
+            let liveliness_kind = data_writer.qos().liveliness.kind;
+            if is_durable(durability_kind) {
+                load_durable_samples(
+                    self,
+                    message.publisher_handle,
+                    message.data_writer_handle,
+                    durability_kind,
+                    &topic_name,
+                )?;
+            }
+            if liveliness_kind != LivelinessQosPolicyKind::Automatic {
+                renew_liveliness_lease(
+                    self,
+                    message.publisher_handle,
+                    message.data_writer_handle,
+                    message.participant_address.clone(),
+                )?;
+            }
 
             for discovered_reader_data in self
                 .domain_participant
-                .discovered_reader_data_list()
+                .discovered_reader_data_list_by_topic(&topic_name)
                 .cloned()
             {
                 message
@@ -438,6 +1153,56 @@ impl MailHandler<Enable> for DomainParticipantActor {
     }
 }
 
+// A writer created for a topic of which no other durable writer is enabled in the participant
+// takes over the samples kept by the durability service to deliver them to the late-joining readers
+fn load_durable_samples(
+    domain_participant_actor: &mut DomainParticipantActor,
+    publisher_handle: InstanceHandle,
+    data_writer_handle: InstanceHandle,
+    durability_kind: DurabilityQosPolicyKind,
+    topic_name: &str,
+) -> DdsResult<()> {
+    let is_other_durable_writer_enabled = domain_participant_actor
+        .domain_participant
+        .publisher_list()
+        .flat_map(|p| p.data_writer_list())
+        .any(|dw| {
+            dw.instance_handle() != data_writer_handle
+                && dw.enabled()
+                && dw.topic_name() == topic_name
+                && dw.qos().durability.kind == durability_kind
+        });
+    if is_other_durable_writer_enabled {
+        return Ok(());
+    }
+
+    let now = domain_participant_actor
+        .domain_participant
+        .get_current_time();
+    let durable_sample_list = domain_participant_actor
+        .durability_service
+        .sample_list(durability_kind, topic_name);
+    let data_writer = domain_participant_actor
+        .domain_participant
+        .get_mut_publisher(publisher_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .get_mut_data_writer(data_writer_handle)
+        .ok_or(DdsError::AlreadyDeleted)?;
+    for durable_sample in durable_sample_list {
+        let is_expired = match data_writer.qos().lifespan.duration {
+            DurationKind::Finite(lifespan_duration) => {
+                now - durable_sample.timestamp > lifespan_duration
+            }
+            DurationKind::Infinite => false,
+        };
+        if !is_expired {
+            data_writer
+                .write_w_timestamp(durable_sample.serialized_data, durable_sample.timestamp)?;
+        }
+    }
+    Ok(())
+}
+
 pub struct SetListener {
     pub publisher_handle: InstanceHandle,
     pub data_writer_handle: InstanceHandle,
@@ -450,8 +1215,9 @@ impl Mail for SetListener {
 impl MailHandler<SetListener> for DomainParticipantActor {
     fn handle(&mut self, message: SetListener) -> <SetListener as Mail>::Result {
         let listener = message.listener.map(|l| {
-            Actor::spawn(
+            Actor::spawn_with_mailbox(
                 DataWriterListenerActor::new(l),
+                self.listener_mailbox,
                 &self.listener_executor.handle(),
             )
         });
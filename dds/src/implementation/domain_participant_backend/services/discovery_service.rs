@@ -8,7 +8,7 @@ use crate::{
     },
     implementation::{
         data_representation_builtin_endpoints::{
-            discovered_reader_data::{DiscoveredReaderData, ReaderProxy},
+            discovered_reader_data::{ContentFilterProperty, DiscoveredReaderData, ReaderProxy},
             discovered_writer_data::{DiscoveredWriterData, WriterProxy},
             spdp_discovered_participant_data::{
                 BuiltinEndpointQos, BuiltinEndpointSet, ParticipantProxy,
@@ -34,28 +34,36 @@ use crate::{
             publisher_listener, subscriber_listener,
         },
         status_condition::status_condition_actor,
+        xtypes_glue::{sql_filter::SqlFilter, type_information::type_information},
     },
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, PublisherQos, SubscriberQos, TopicQos},
         qos_policy::{
-            DurabilityQosPolicyKind, QosPolicyId, ReliabilityQosPolicyKind,
-            DATA_REPRESENTATION_QOS_POLICY_ID, DEADLINE_QOS_POLICY_ID,
-            DESTINATIONORDER_QOS_POLICY_ID, DURABILITY_QOS_POLICY_ID, LATENCYBUDGET_QOS_POLICY_ID,
-            LIVELINESS_QOS_POLICY_ID, OWNERSHIP_QOS_POLICY_ID, PRESENTATION_QOS_POLICY_ID,
-            RELIABILITY_QOS_POLICY_ID, XCDR_DATA_REPRESENTATION,
+            DataRepresentationQosPolicy, DeadlineQosPolicy, DeliveryModeQosPolicyKind,
+            DestinationOrderQosPolicy, DurabilityQosPolicy, DurabilityQosPolicyKind,
+            HistoricalDataQosPolicy, LatencyBudgetQosPolicy, Length, LivelinessQosPolicy,
+            LocatorSelectionQosPolicyKind, OwnershipQosPolicy, PresentationQosPolicy, QosPolicyId,
+            ReliabilityQosPolicy, ReliabilityQosPolicyKind, DATA_REPRESENTATION_QOS_POLICY_ID,
+            DEADLINE_QOS_POLICY_ID, DESTINATIONORDER_QOS_POLICY_ID, DURABILITY_QOS_POLICY_ID,
+            LATENCYBUDGET_QOS_POLICY_ID, LIVELINESS_QOS_POLICY_ID, OWNERSHIP_QOS_POLICY_ID,
+            PRESENTATION_QOS_POLICY_ID, RELIABILITY_QOS_POLICY_ID, XCDR_DATA_REPRESENTATION,
         },
         status::StatusKind,
-        time::Duration,
+        time::DurationKind,
     },
     runtime::actor::{ActorAddress, Mail, MailHandler},
     topic_definition::type_support::DdsSerialize,
     transport::{
         self,
-        types::{DurabilityKind, Guid, ReliabilityKind, ENTITYID_UNKNOWN},
+        types::{ChangeKind, DurabilityKind, Guid, GuidPrefix, ReliabilityKind, ENTITYID_UNKNOWN},
     },
+    xtypes::dynamic_type::DynamicType,
 };
+use std::sync::Arc;
+
+use super::{event_service, participant_message_service, type_lookup_service};
 
 pub struct AnnounceParticipant;
 impl Mail for AnnounceParticipant {
@@ -69,7 +77,32 @@ impl MailHandler<AnnounceParticipant> for DomainParticipantActor {
                     value: self.transport.guid().into(),
                 },
                 user_data: self.domain_participant.qos().user_data.clone(),
+                entity_name: self.domain_participant.qos().entity_name.clone(),
+                property: self.domain_participant.qos().property.propagated(),
+            };
+            #[cfg(feature = "security")]
+            let (identity_token, participant_security_info) = match &self.security {
+                Some(s) => (
+                    Some(s.authentication.identity_token()),
+                    s.participant_security_info(),
+                ),
+                None => (None, None),
             };
+            #[cfg(not(feature = "security"))]
+            let (identity_token, participant_security_info) = (None, None);
+            let mut available_builtin_endpoints = BuiltinEndpointSet::default();
+            available_builtin_endpoints.0 |=
+                BuiltinEndpointSet::BUILTIN_ENDPOINT_PARTICIPANT_MESSAGE_DATA_WRITER
+                    | BuiltinEndpointSet::BUILTIN_ENDPOINT_PARTICIPANT_MESSAGE_DATA_READER
+                    | BuiltinEndpointSet::BUILTIN_TYPE_LOOKUP_SERVICE_REQUEST_DATA_WRITER
+                    | BuiltinEndpointSet::BUILTIN_TYPE_LOOKUP_SERVICE_REQUEST_DATA_READER
+                    | BuiltinEndpointSet::BUILTIN_TYPE_LOOKUP_SERVICE_REPLY_DATA_WRITER
+                    | BuiltinEndpointSet::BUILTIN_TYPE_LOOKUP_SERVICE_REPLY_DATA_READER;
+            if identity_token.is_some() {
+                available_builtin_endpoints.0 |=
+                    BuiltinEndpointSet::BUILTIN_PARTICIPANT_STATELESS_MESSAGE_WRITER
+                        | BuiltinEndpointSet::BUILTIN_PARTICIPANT_STATELESS_MESSAGE_READER;
+            }
             let participant_proxy = ParticipantProxy {
                 domain_id: Some(self.domain_participant.domain_id()),
                 domain_tag: self.domain_participant.domain_tag().to_owned(),
@@ -93,15 +126,17 @@ impl MailHandler<AnnounceParticipant> for DomainParticipantActor {
                     .transport
                     .default_multicast_locator_list()
                     .to_vec(),
-                available_builtin_endpoints: BuiltinEndpointSet::default(),
+                available_builtin_endpoints,
                 manual_liveliness_count: 0,
                 builtin_endpoint_qos: BuiltinEndpointQos::default(),
             };
             let spdp_discovered_participant_data = SpdpDiscoveredParticipantData {
                 dds_participant_data: participant_builtin_topic_data,
                 participant_proxy,
-                lease_duration: Duration::new(100, 0),
+                lease_duration: self.domain_participant.participant_lease_duration(),
                 discovered_participant_list: self.domain_participant.get_discovered_participants(),
+                identity_token,
+                participant_security_info,
             };
             let timestamp = self.domain_participant.get_current_time();
 
@@ -121,6 +156,36 @@ impl MailHandler<AnnounceParticipant> for DomainParticipantActor {
     }
 }
 
+pub struct AnnounceParticipantOnDemand;
+impl Mail for AnnounceParticipantOnDemand {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AnnounceParticipantOnDemand> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        _: AnnounceParticipantOnDemand,
+    ) -> <AnnounceParticipantOnDemand as Mail>::Result {
+        if !self.domain_participant.enabled() {
+            return Err(DdsError::NotEnabled);
+        }
+
+        MailHandler::handle(self, AnnounceParticipant)?;
+
+        // The heartbeats of the SEDP writers make the remote readers request any missed endpoint
+        for data_writer in self
+            .domain_participant
+            .builtin_publisher_mut()
+            .data_writer_list_mut()
+        {
+            if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
+                w.send_heartbeat();
+            }
+        }
+
+        Ok(())
+    }
+}
+
 pub struct AnnounceDeletedParticipant;
 impl Mail for AnnounceDeletedParticipant {
     type Result = DdsResult<()>;
@@ -141,6 +206,13 @@ impl MailHandler<AnnounceDeletedParticipant> for DomainParticipantActor {
                 dw.dispose_w_timestamp(key.serialize_data()?, timestamp)?;
             }
         }
+        for data_reader in self
+            .domain_participant
+            .builtin_subscriber_mut()
+            .data_reader_list_mut()
+        {
+            data_reader.cancel_communication_state_waiters();
+        }
 
         Ok(())
     }
@@ -162,15 +234,12 @@ impl MailHandler<AnnounceDataWriter> for DomainParticipantActor {
         let data_writer = publisher
             .get_data_writer(message.data_writer_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
-        let topic_data = self
+        let topic = self
             .domain_participant
             .get_topic(data_writer.topic_name())
             .ok_or(DdsError::Error(
                 "Internal error. Data writer exists without associated topic".to_owned(),
-            ))?
-            .qos()
-            .topic_data
-            .clone();
+            ))?;
 
         let dds_publication_data = PublicationBuiltinTopicData {
             key: BuiltInTopicKey {
@@ -180,6 +249,7 @@ impl MailHandler<AnnounceDataWriter> for DomainParticipantActor {
             topic_name: data_writer.topic_name().to_owned(),
             type_name: data_writer.type_name().to_owned(),
             durability: data_writer.qos().durability.clone(),
+            durability_service: data_writer.qos().durability_service.clone(),
             deadline: data_writer.qos().deadline.clone(),
             latency_budget: data_writer.qos().latency_budget.clone(),
             liveliness: data_writer.qos().liveliness.clone(),
@@ -191,9 +261,14 @@ impl MailHandler<AnnounceDataWriter> for DomainParticipantActor {
             destination_order: data_writer.qos().destination_order.clone(),
             presentation: publisher.qos().presentation.clone(),
             partition: publisher.qos().partition.clone(),
-            topic_data,
+            topic_data: topic.qos().topic_data.clone(),
             group_data: publisher.qos().group_data.clone(),
             representation: data_writer.qos().representation.clone(),
+            entity_name: data_writer.qos().entity_name.clone(),
+            property: data_writer.qos().property.propagated(),
+            disable_positive_acks: data_writer.qos().disable_positive_acks.clone(),
+            // Types which can not be described with a TypeObject are announced without it
+            type_information: type_information(topic.type_support().as_ref()).ok(),
         };
         let writer_proxy = WriterProxy {
             remote_writer_guid: data_writer.transport_writer().guid(),
@@ -285,6 +360,10 @@ impl MailHandler<AnnounceDataReader> for DomainParticipantActor {
             topic_data: topic.qos().topic_data.clone(),
             group_data: subscriber.qos().group_data.clone(),
             representation: data_reader.qos().representation.clone(),
+            entity_name: data_reader.qos().entity_name.clone(),
+            property: data_reader.qos().property.propagated(),
+            disable_positive_acks: data_reader.qos().disable_positive_acks.clone(),
+            type_information: type_information(topic.type_support().as_ref()).ok(),
         };
         let reader_proxy = ReaderProxy {
             remote_reader_guid: data_reader.transport_reader().guid(),
@@ -296,6 +375,7 @@ impl MailHandler<AnnounceDataReader> for DomainParticipantActor {
         let discovered_reader_data = DiscoveredReaderData {
             dds_subscription_data,
             reader_proxy,
+            content_filter: None,
         };
         let timestamp = self.domain_participant.get_current_time();
         if let Some(dw) = self
@@ -329,6 +409,7 @@ impl MailHandler<AnnounceTopic> for DomainParticipantActor {
             name: topic.topic_name().to_owned(),
             type_name: topic.type_name().to_owned(),
             durability: topic.qos().durability.clone(),
+            durability_service: topic.qos().durability_service.clone(),
             deadline: topic.qos().deadline.clone(),
             latency_budget: topic.qos().latency_budget.clone(),
             liveliness: topic.qos().liveliness.clone(),
@@ -405,6 +486,7 @@ impl MailHandler<AddDiscoveredTopic> for DomainParticipantActor {
 
 pub struct AddDiscoveredParticipant {
     pub discovered_participant_data: SpdpDiscoveredParticipantData,
+    pub is_relayed: bool,
 }
 impl Mail for AddDiscoveredParticipant {
     type Result = ();
@@ -422,8 +504,9 @@ impl MailHandler<AddDiscoveredParticipant> for DomainParticipantActor {
         // If it is not equal then there the local endpoints are not configured to
         // communicate with the discovered participant.
         // AND
-        // Check that the domainTag of the discovered participant equals the local one.
-        // If it is not equal then there the local endpoints are not configured to
+        // Check that the domainTag of the discovered participant equals the local one
+        // or is one of the additionally accepted domain tags.
+        // If it is not accepted then there the local endpoints are not configured to
         // communicate with the discovered participant.
         // IN CASE no domain id was transmitted the a local domain id is assumed
         // (as specified in Table 9.19 - ParameterId mapping and default values)
@@ -435,39 +518,126 @@ impl MailHandler<AddDiscoveredParticipant> for DomainParticipantActor {
             Some(id) => id == self.domain_participant.domain_id(),
             None => true,
         };
-        let is_domain_tag_matching = message
-            .discovered_participant_data
-            .participant_proxy
-            .domain_tag
-            == self.domain_participant.domain_tag();
+        let is_domain_tag_matching = self.domain_participant.is_domain_tag_accepted(
+            &message
+                .discovered_participant_data
+                .participant_proxy
+                .domain_tag,
+        );
 
+        let participant_handle = InstanceHandle::new(
+            message
+                .discovered_participant_data
+                .dds_participant_data
+                .key
+                .value,
+        );
         let is_participant_discovered = self
             .domain_participant
-            .get_discovered_participant_data(&InstanceHandle::new(
-                message
-                    .discovered_participant_data
-                    .dds_participant_data
-                    .key
-                    .value,
-            ))
+            .get_discovered_participant_data(&participant_handle)
             .is_some();
+        let is_participant_relayed = self
+            .domain_participant
+            .is_participant_relayed(&participant_handle);
 
-        if is_domain_id_matching && is_domain_tag_matching && !is_participant_discovered {
+        // A participant announcing a GuidPrefix which is already in use with different
+        // locators indicates a GuidPrefix collision (e.g. after cloning a container). Its
+        // announcement is ignored to prevent the silent cross-talk between the participants.
+        let participant_proxy = &message.discovered_participant_data.participant_proxy;
+        let is_guid_prefix_colliding =
+            if participant_proxy.guid_prefix == self.transport.guid().prefix() {
+                participant_proxy.metatraffic_unicast_locator_list
+                    != self.transport.metatraffic_unicast_locator_list()
+            } else {
+                self.domain_participant
+                    .get_discovered_participant_data_by_guid_prefix(&participant_proxy.guid_prefix)
+                    .is_some_and(|p| {
+                        p.participant_proxy.metatraffic_unicast_locator_list
+                            != participant_proxy.metatraffic_unicast_locator_list
+                    })
+            };
+        if is_guid_prefix_colliding {
+            tracing::error!(
+                guid_prefix = ?participant_proxy.guid_prefix,
+                "Discovered participant ignored because its GuidPrefix collides with another participant"
+            );
+            return;
+        }
+
+        // The participants relayed by a discovery server are only recorded. The builtin endpoints
+        // are not matched with them since their endpoints are relayed by the server as well.
+        if message.is_relayed {
+            if (!is_participant_discovered || is_participant_relayed)
+                && self
+                    .domain_participant
+                    .add_discovered_participant(message.discovered_participant_data)
+                    .is_ok()
+            {
+                self.domain_participant
+                    .set_participant_relayed(participant_handle, true);
+            }
+            return;
+        }
+
+        #[cfg(feature = "security")]
+        if !super::security_service::authenticate_discovered_participant(
+            self,
+            &message.discovered_participant_data,
+        ) {
+            return;
+        }
+
+        if self
+            .domain_participant
+            .add_discovered_participant(message.discovered_participant_data.clone())
+            .is_err()
+        {
+            tracing::warn!(
+                "Discovered participant ignored because the maximum number of discovered participants is reached"
+            );
+            return;
+        }
+        self.domain_participant
+            .set_participant_relayed(participant_handle, false);
+
+        if is_domain_id_matching
+            && is_domain_tag_matching
+            && (!is_participant_discovered || is_participant_relayed)
+        {
+            add_participant_announcement_locators(self, &message.discovered_participant_data);
             add_matched_publications_detector(self, &message.discovered_participant_data);
             add_matched_publications_announcer(self, &message.discovered_participant_data);
             add_matched_subscriptions_detector(self, &message.discovered_participant_data);
             add_matched_subscriptions_announcer(self, &message.discovered_participant_data);
             add_matched_topics_detector(self, &message.discovered_participant_data);
             add_matched_topics_announcer(self, &message.discovered_participant_data);
+            type_lookup_service::add_matched_participant(
+                self,
+                &message.discovered_participant_data,
+            );
+            participant_message_service::add_matched_participant(
+                self,
+                &message.discovered_participant_data,
+            );
         }
 
-        self.domain_participant
-            .add_discovered_participant(message.discovered_participant_data);
+        if is_domain_id_matching
+            && is_domain_tag_matching
+            && self.domain_participant.is_discovery_server()
+            && message
+                .discovered_participant_data
+                .participant_proxy
+                .guid_prefix
+                != self.transport.guid().prefix()
+        {
+            relay_discovered_participant(self, &message.discovered_participant_data).ok();
+        }
     }
 }
 
 pub struct RemoveDiscoveredParticipant {
     pub discovered_participant: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for RemoveDiscoveredParticipant {
     type Result = ();
@@ -477,11 +647,256 @@ impl MailHandler<RemoveDiscoveredParticipant> for DomainParticipantActor {
         &mut self,
         message: RemoveDiscoveredParticipant,
     ) -> <RemoveDiscoveredParticipant as Mail>::Result {
+        #[cfg(feature = "security")]
+        super::security_service::remove_discovered_participant(
+            self,
+            &message.discovered_participant,
+        );
+
+        if let Some(discovered_participant_data) = self
+            .domain_participant
+            .get_discovered_participant_data(&message.discovered_participant)
+            .cloned()
+        {
+            if self.domain_participant.is_discovery_server()
+                && !self
+                    .domain_participant
+                    .is_participant_relayed(&message.discovered_participant)
+            {
+                relay_removed_instance(self, DCPS_PARTICIPANT, message.discovered_participant).ok();
+            }
+            remove_participant_announcement_locators(self, &discovered_participant_data);
+            type_lookup_service::remove_matched_participant(self, &discovered_participant_data);
+            participant_message_service::remove_matched_participant(
+                self,
+                &discovered_participant_data,
+            );
+            if discovered_participant_data.participant_proxy.guid_prefix
+                != self.transport.guid().prefix()
+            {
+                remove_participant_endpoints(
+                    self,
+                    &discovered_participant_data.participant_proxy.guid_prefix,
+                    &message.participant_address,
+                );
+            }
+        }
         self.domain_participant
             .remove_discovered_participant(&message.discovered_participant);
     }
 }
 
+/// Renew the lease of a discovered participant on each of its announcements. The participant is
+/// removed when its lease expires without any new announcement.
+pub fn renew_participant_lease(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) {
+    if discovered_participant_data.participant_proxy.guid_prefix
+        == domain_participant_actor.transport.guid().prefix()
+    {
+        return;
+    }
+    let participant_handle =
+        InstanceHandle::new(discovered_participant_data.dds_participant_data.key.value);
+    let lease_id = domain_participant_actor
+        .domain_participant
+        .generate_participant_lease_id();
+    let lease_task = domain_participant_actor.timer_driver.handle().schedule(
+        discovered_participant_data.lease_duration.into(),
+        move || {
+            participant_address
+                .send_actor_mail(event_service::ParticipantLeaseExpired {
+                    participant_handle,
+                    lease_id,
+                    participant_address: participant_address.clone(),
+                })
+                .ok();
+        },
+    );
+    domain_participant_actor
+        .domain_participant
+        .renew_participant_lease(participant_handle, lease_id, lease_task);
+}
+
+/// Unmatch the local endpoints from the endpoints of a participant which is no longer alive
+fn remove_participant_endpoints(
+    domain_participant_actor: &mut DomainParticipantActor,
+    guid_prefix: &GuidPrefix,
+    participant_address: &ActorAddress<DomainParticipantActor>,
+) {
+    let publication_handle_list = domain_participant_actor
+        .domain_participant
+        .discovered_writer_handle_list_by_guid_prefix(guid_prefix);
+    let subscription_handle_list = domain_participant_actor
+        .domain_participant
+        .discovered_reader_handle_list_by_guid_prefix(guid_prefix);
+    remove_discovered_endpoints(
+        domain_participant_actor,
+        publication_handle_list,
+        subscription_handle_list,
+        participant_address,
+    );
+}
+
+/// Unmatch the local endpoints from the remote endpoints of an ignored topic
+pub fn remove_topic_endpoints(
+    domain_participant_actor: &mut DomainParticipantActor,
+    topic_name: &str,
+    participant_address: &ActorAddress<DomainParticipantActor>,
+) {
+    let local_guid_prefix = domain_participant_actor.transport.guid().prefix();
+    let publication_handle_list = domain_participant_actor
+        .domain_participant
+        .discovered_writer_data_list_by_topic(topic_name)
+        .filter(|w| w.writer_proxy.remote_writer_guid.prefix() != local_guid_prefix)
+        .map(|w| InstanceHandle::new(w.dds_publication_data.key().value))
+        .collect();
+    let subscription_handle_list = domain_participant_actor
+        .domain_participant
+        .discovered_reader_data_list_by_topic(topic_name)
+        .filter(|r| r.reader_proxy.remote_reader_guid.prefix() != local_guid_prefix)
+        .map(|r| InstanceHandle::new(r.dds_subscription_data.key().value))
+        .collect();
+    remove_discovered_endpoints(
+        domain_participant_actor,
+        publication_handle_list,
+        subscription_handle_list,
+        participant_address,
+    );
+}
+
+/// Match the local writer again with the discovered readers of its topic after a change of its QoS
+/// or of the QoS of its publisher
+pub fn rematch_data_writer(
+    domain_participant_actor: &DomainParticipantActor,
+    publisher_handle: InstanceHandle,
+    data_writer_handle: InstanceHandle,
+    topic_name: &str,
+    participant_address: &ActorAddress<DomainParticipantActor>,
+) {
+    for discovered_reader_data in domain_participant_actor
+        .domain_participant
+        .discovered_reader_data_list_by_topic(topic_name)
+        .cloned()
+    {
+        participant_address
+            .send_actor_mail(AddDiscoveredReader {
+                discovered_reader_data,
+                publisher_handle,
+                data_writer_handle,
+                participant_address: participant_address.clone(),
+            })
+            .ok();
+    }
+}
+
+/// Match the local reader again with the discovered writers of its topic after a change of its QoS
+/// or of the QoS of its subscriber
+pub fn rematch_data_reader(
+    domain_participant_actor: &DomainParticipantActor,
+    subscriber_handle: InstanceHandle,
+    data_reader_handle: InstanceHandle,
+    topic_name: &str,
+    participant_address: &ActorAddress<DomainParticipantActor>,
+) {
+    for discovered_writer_data in domain_participant_actor
+        .domain_participant
+        .discovered_writer_data_list_by_topic(topic_name)
+        .cloned()
+    {
+        participant_address
+            .send_actor_mail(AddDiscoveredWriter {
+                discovered_writer_data,
+                subscriber_handle,
+                data_reader_handle,
+                participant_address: participant_address.clone(),
+            })
+            .ok();
+    }
+}
+
+/// Whether the announcement of a remote endpoint must be dropped because its topic is ignored
+pub fn is_endpoint_topic_ignored(
+    domain_participant_actor: &DomainParticipantActor,
+    endpoint_guid_prefix: GuidPrefix,
+    topic_name: &str,
+) -> bool {
+    endpoint_guid_prefix != domain_participant_actor.transport.guid().prefix()
+        && domain_participant_actor
+            .domain_participant
+            .is_topic_ignored(topic_name)
+}
+
+fn remove_discovered_endpoints(
+    domain_participant_actor: &mut DomainParticipantActor,
+    publication_handle_list: Vec<InstanceHandle>,
+    subscription_handle_list: Vec<InstanceHandle>,
+    participant_address: &ActorAddress<DomainParticipantActor>,
+) {
+    for publication_handle in publication_handle_list {
+        let Some(discovered_writer_data) = domain_participant_actor
+            .domain_participant
+            .remove_discovered_writer(&publication_handle)
+        else {
+            continue;
+        };
+        let mut data_reader_list = Vec::new();
+        for subscriber in domain_participant_actor
+            .domain_participant
+            .subscriber_list()
+        {
+            for data_reader in subscriber.data_reader_list().filter(|dr| {
+                dr.topic_name() == discovered_writer_data.dds_publication_data.topic_name()
+            }) {
+                data_reader_list
+                    .push((subscriber.instance_handle(), data_reader.instance_handle()));
+            }
+        }
+        for (subscriber_handle, data_reader_handle) in data_reader_list {
+            MailHandler::handle(
+                domain_participant_actor,
+                RemoveDiscoveredWriter {
+                    publication_handle,
+                    subscriber_handle,
+                    data_reader_handle,
+                    participant_address: participant_address.clone(),
+                },
+            )
+            .ok();
+        }
+    }
+
+    for subscription_handle in subscription_handle_list {
+        let Some(discovered_reader_data) = domain_participant_actor
+            .domain_participant
+            .remove_discovered_reader(&subscription_handle)
+        else {
+            continue;
+        };
+        let mut data_writer_list = Vec::new();
+        for publisher in domain_participant_actor.domain_participant.publisher_list() {
+            for data_writer in publisher.data_writer_list().filter(|dw| {
+                dw.topic_name() == discovered_reader_data.dds_subscription_data.topic_name()
+            }) {
+                data_writer_list.push((publisher.instance_handle(), data_writer.instance_handle()));
+            }
+        }
+        for (publisher_handle, data_writer_handle) in data_writer_list {
+            MailHandler::handle(
+                domain_participant_actor,
+                RemoveDiscoveredReader {
+                    subscription_handle,
+                    publisher_handle,
+                    data_writer_handle,
+                },
+            )
+            .ok();
+        }
+    }
+}
+
 pub struct AddDiscoveredReader {
     pub discovered_reader_data: DiscoveredReaderData,
     pub publisher_handle: InstanceHandle,
@@ -493,35 +908,49 @@ impl Mail for AddDiscoveredReader {
 }
 impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
     fn handle(&mut self, message: AddDiscoveredReader) -> <AddDiscoveredReader as Mail>::Result {
-        let default_unicast_locator_list = if let Some(p) = self
+        #[cfg(feature = "security")]
+        if !super::security_service::check_remote_datareader(self, &message.discovered_reader_data)
+        {
+            return Ok(());
+        }
+
+        let subscription_handle = InstanceHandle::new(
+            message
+                .discovered_reader_data
+                .dds_subscription_data
+                .key()
+                .value,
+        );
+        let data_writer = self
             .domain_participant
-            .discovered_participant_list()
-            .find(|p| {
-                p.participant_proxy.guid_prefix
-                    == message
-                        .discovered_reader_data
-                        .reader_proxy
-                        .remote_reader_guid
-                        .prefix()
-            }) {
-            p.participant_proxy.default_unicast_locator_list.clone()
-        } else {
-            vec![]
-        };
-        let default_multicast_locator_list = if let Some(p) = self
+            .get_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        // A data writer is only matched once it is enabled, which matches it with all the
+        // readers discovered until then
+        if !data_writer.enabled() {
+            return Ok(());
+        }
+        let was_matched = data_writer
+            .get_matched_subscription_data(&subscription_handle)
+            .is_some();
+
+        let (default_unicast_locator_list, default_multicast_locator_list) = if let Some(p) = self
             .domain_participant
-            .discovered_participant_list()
-            .find(|p| {
-                p.participant_proxy.guid_prefix
-                    == message
-                        .discovered_reader_data
-                        .reader_proxy
-                        .remote_reader_guid
-                        .prefix()
-            }) {
-            p.participant_proxy.default_multicast_locator_list.clone()
+            .get_discovered_participant_data_by_guid_prefix(
+                &message
+                    .discovered_reader_data
+                    .reader_proxy
+                    .remote_reader_guid
+                    .prefix(),
+            ) {
+            (
+                p.participant_proxy.default_unicast_locator_list.clone(),
+                p.participant_proxy.default_multicast_locator_list.clone(),
+            )
         } else {
-            vec![]
+            (vec![], vec![])
         };
         let publisher = self
             .domain_participant
@@ -604,6 +1033,17 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                     data_writer.add_matched_subscription(
                         message.discovered_reader_data.dds_subscription_data.clone(),
                     );
+                    // The reader announced again after a change of its QoS stays matched and
+                    // only the change of its data is signaled
+                    if was_matched {
+                        data_writer
+                            .status_condition()
+                            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                                state: StatusKind::PublicationMatched,
+                            })
+                            .ok();
+                        return Ok(());
+                    }
 
                     let unicast_locator_list = if message
                         .discovered_reader_data
@@ -652,6 +1092,22 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                         DurabilityQosPolicyKind::Persistent => DurabilityKind::Persistent,
                     };
 
+                    // Positive acknowledgments are only omitted if both endpoints agree on it
+                    let disable_positive_acks_keep_duration =
+                        if data_writer.qos().disable_positive_acks.enabled
+                            && message
+                                .discovered_reader_data
+                                .dds_subscription_data
+                                .disable_positive_acks
+                                .enabled
+                        {
+                            match data_writer.qos().disable_positive_acks.duration {
+                                DurationKind::Finite(d) => Some(d.into()),
+                                DurationKind::Infinite => Some(std::time::Duration::MAX),
+                            }
+                        } else {
+                            None
+                        };
                     let reader_proxy = transport::writer::ReaderProxy {
                         remote_reader_guid: message
                             .discovered_reader_data
@@ -666,6 +1122,21 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                         unicast_locator_list,
                         multicast_locator_list,
                         expects_inline_qos: false,
+                        disable_positive_acks_keep_duration,
+                        prefer_multicast: data_writer.qos().locator_selection.kind
+                            == LocatorSelectionQosPolicyKind::PreferMulticast,
+                        push_mode: data_writer.qos().delivery_mode.kind
+                            == DeliveryModeQosPolicyKind::Push,
+                        historical_data_limit: historical_data_limit(
+                            &data_writer.qos().historical_data,
+                        ),
+                        content_filter: message
+                            .discovered_reader_data
+                            .content_filter
+                            .as_ref()
+                            .and_then(|content_filter| {
+                                change_filter(content_filter, data_writer.type_support())
+                            }),
                     };
                     if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
                         w.add_matched_reader(reader_proxy);
@@ -692,7 +1163,8 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                             l.send_actor_mail(data_writer_listener::TriggerPublicationMatched {
                                 the_writer,
                                 status,
-                            });
+                            })
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -722,7 +1194,8 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                             l.send_actor_mail(publisher_listener::TriggerOnPublicationMatched {
                                 the_writer,
                                 status,
-                            });
+                            })
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -747,7 +1220,8 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                                     the_writer,
                                     status,
                                 },
-                            );
+                            )
+                            .ok();
                         }
                     }
 
@@ -759,16 +1233,11 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                         .status_condition()
                         .send_actor_mail(status_condition_actor::AddCommunicationState {
                             state: StatusKind::PublicationMatched,
-                        });
+                        })
+                        .ok();
                 } else {
                     data_writer.add_incompatible_subscription(
-                        InstanceHandle::new(
-                            message
-                                .discovered_reader_data
-                                .dds_subscription_data
-                                .key()
-                                .value,
-                        ),
+                        subscription_handle,
                         incompatible_qos_policy_list,
                     );
 
@@ -795,7 +1264,8 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                                     the_writer,
                                     status,
                                 },
-                            );
+                            )
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -825,7 +1295,8 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                             l.send_actor_mail(publisher_listener::TriggerOfferedIncompatibleQos {
                                 the_writer,
                                 status,
-                            });
+                            })
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -850,7 +1321,8 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                                     the_writer,
                                     status,
                                 },
-                            );
+                            )
+                            .ok();
                         }
                     }
 
@@ -862,10 +1334,23 @@ impl MailHandler<AddDiscoveredReader> for DomainParticipantActor {
                         .status_condition()
                         .send_actor_mail(status_condition_actor::AddCommunicationState {
                             state: StatusKind::OfferedIncompatibleQos,
-                        });
+                        })
+                        .ok();
                 }
             }
         }
+
+        // A matched reader whose QoS or partition changed such that it no longer matches
+        if was_matched {
+            MailHandler::handle(
+                self,
+                RemoveDiscoveredReader {
+                    subscription_handle,
+                    publisher_handle: message.publisher_handle,
+                    data_writer_handle: message.data_writer_handle,
+                },
+            )?;
+        }
         Ok(())
     }
 }
@@ -895,12 +1380,16 @@ impl MailHandler<RemoveDiscoveredReader> for DomainParticipantActor {
             .is_some()
         {
             data_writer.remove_matched_subscription(&message.subscription_handle);
+            if let TransportWriterKind::Stateful(w) = data_writer.transport_writer_mut() {
+                w.remove_matched_reader(Guid::from(<[u8; 16]>::from(message.subscription_handle)));
+            }
 
-            data_writer.status_condition().send_actor_mail(
-                status_condition_actor::AddCommunicationState {
+            data_writer
+                .status_condition()
+                .send_actor_mail(status_condition_actor::AddCommunicationState {
                     state: StatusKind::PublicationMatched,
-                },
-            );
+                })
+                .ok();
         }
         Ok(())
     }
@@ -917,35 +1406,50 @@ impl Mail for AddDiscoveredWriter {
 }
 impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
     fn handle(&mut self, message: AddDiscoveredWriter) -> <AddDiscoveredWriter as Mail>::Result {
-        let default_unicast_locator_list = if let Some(p) = self
+        #[cfg(feature = "security")]
+        if !super::security_service::check_remote_datawriter(self, &message.discovered_writer_data)
+        {
+            return Ok(());
+        }
+
+        let publication_handle = InstanceHandle::new(
+            message
+                .discovered_writer_data
+                .dds_publication_data
+                .key()
+                .value,
+        );
+        let data_reader = self
             .domain_participant
-            .discovered_participant_list()
-            .find(|p| {
-                p.participant_proxy.guid_prefix
-                    == message
-                        .discovered_writer_data
-                        .writer_proxy
-                        .remote_writer_guid
-                        .prefix()
-            }) {
-            p.participant_proxy.default_unicast_locator_list.clone()
-        } else {
-            vec![]
-        };
-        let default_multicast_locator_list = if let Some(p) = self
+            .get_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        // A data reader is only matched once it is enabled, which matches it with all the
+        // writers discovered until then
+        if !data_reader.enabled() {
+            return Ok(());
+        }
+        let was_matched = data_reader
+            .get_matched_publication_data(&publication_handle)
+            .is_some();
+        let participant_address = message.participant_address.clone();
+
+        let (default_unicast_locator_list, default_multicast_locator_list) = if let Some(p) = self
             .domain_participant
-            .discovered_participant_list()
-            .find(|p| {
-                p.participant_proxy.guid_prefix
-                    == message
-                        .discovered_writer_data
-                        .writer_proxy
-                        .remote_writer_guid
-                        .prefix()
-            }) {
-            p.participant_proxy.default_multicast_locator_list.clone()
+            .get_discovered_participant_data_by_guid_prefix(
+                &message
+                    .discovered_writer_data
+                    .writer_proxy
+                    .remote_writer_guid
+                    .prefix(),
+            ) {
+            (
+                p.participant_proxy.default_unicast_locator_list.clone(),
+                p.participant_proxy.default_multicast_locator_list.clone(),
+            )
         } else {
-            vec![]
+            (vec![], vec![])
         };
         let subscriber = self
             .domain_participant
@@ -1026,6 +1530,17 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                     data_reader.add_matched_publication(
                         message.discovered_writer_data.dds_publication_data.clone(),
                     );
+                    // The writer announced again after a change of its QoS stays matched and
+                    // only the change of its data is signaled
+                    if was_matched {
+                        data_reader
+                            .status_condition()
+                            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                                state: StatusKind::SubscriptionMatched,
+                            })
+                            .ok();
+                        return Ok(());
+                    }
                     let unicast_locator_list = if message
                         .discovered_writer_data
                         .writer_proxy
@@ -1089,6 +1604,12 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                             .data_max_size_serialized,
                         reliability_kind,
                         durability_kind,
+                        disable_positive_acks: data_reader.qos().disable_positive_acks.enabled
+                            && message
+                                .discovered_writer_data
+                                .dds_publication_data
+                                .disable_positive_acks
+                                .enabled,
                     };
                     if let TransportReaderKind::Stateful(r) = data_reader.transport_reader_mut() {
                         r.add_matched_writer(writer_proxy);
@@ -1115,7 +1636,8 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                             l.send_actor_mail(data_reader_listener::TriggerSubscriptionMatched {
                                 the_reader,
                                 status,
-                            });
+                            })
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -1145,7 +1667,8 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                             l.send_actor_mail(subscriber_listener::TriggerSubscriptionMatched {
                                 the_reader,
                                 status,
-                            });
+                            })
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -1170,7 +1693,8 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                                     the_reader,
                                     status,
                                 },
-                            );
+                            )
+                            .ok();
                         }
                     }
 
@@ -1182,16 +1706,11 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                         .status_condition()
                         .send_actor_mail(status_condition_actor::AddCommunicationState {
                             state: StatusKind::SubscriptionMatched,
-                        });
+                        })
+                        .ok();
                 } else {
                     data_reader.add_requested_incompatible_qos(
-                        InstanceHandle::new(
-                            message
-                                .discovered_writer_data
-                                .dds_publication_data
-                                .key()
-                                .value,
-                        ),
+                        publication_handle,
                         incompatible_qos_policy_list,
                     );
 
@@ -1218,7 +1737,8 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                                     the_reader,
                                     status,
                                 },
-                            );
+                            )
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -1250,7 +1770,8 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                                     the_reader,
                                     status,
                                 },
-                            );
+                            )
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -1275,7 +1796,8 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                                     the_reader,
                                     status,
                                 },
-                            );
+                            )
+                            .ok();
                         }
                     }
 
@@ -1287,10 +1809,24 @@ impl MailHandler<AddDiscoveredWriter> for DomainParticipantActor {
                         .status_condition()
                         .send_actor_mail(status_condition_actor::AddCommunicationState {
                             state: StatusKind::RequestedIncompatibleQos,
-                        });
+                        })
+                        .ok();
                 }
             }
         }
+
+        // A matched writer whose QoS or partition changed such that it no longer matches
+        if was_matched {
+            MailHandler::handle(
+                self,
+                RemoveDiscoveredWriter {
+                    publication_handle,
+                    subscriber_handle: message.subscriber_handle,
+                    data_reader_handle: message.data_reader_handle,
+                    participant_address,
+                },
+            )?;
+        }
         Ok(())
     }
 }
@@ -1299,6 +1835,7 @@ pub struct RemoveDiscoveredWriter {
     pub publication_handle: InstanceHandle,
     pub subscriber_handle: InstanceHandle,
     pub data_reader_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for RemoveDiscoveredWriter {
     type Result = DdsResult<()>;
@@ -1319,7 +1856,19 @@ impl MailHandler<RemoveDiscoveredWriter> for DomainParticipantActor {
             .get_matched_publication_data(&message.publication_handle)
             .is_some()
         {
-            data_reader.remove_matched_publication(&message.publication_handle);
+            if let TransportReaderKind::Stateful(r) = data_reader.transport_reader_mut() {
+                r.remove_matched_writer(Guid::from(<[u8; 16]>::from(message.publication_handle)));
+            }
+            if data_reader.remove_matched_publication(&message.publication_handle) {
+                MailHandler::handle(
+                    self,
+                    event_service::LivelinessChanged {
+                        subscriber_handle: message.subscriber_handle,
+                        data_reader_handle: message.data_reader_handle,
+                        participant_address: message.participant_address,
+                    },
+                )?;
+            }
         }
         Ok(())
     }
@@ -1330,53 +1879,30 @@ fn get_discovered_reader_incompatible_qos_policy_list(
     discovered_reader_data: &SubscriptionBuiltinTopicData,
     publisher_qos: &PublisherQos,
 ) -> Vec<QosPolicyId> {
-    let mut incompatible_qos_policy_list = Vec::new();
-    if &writer_qos.durability < discovered_reader_data.durability() {
-        incompatible_qos_policy_list.push(DURABILITY_QOS_POLICY_ID);
-    }
-    if publisher_qos.presentation.access_scope < discovered_reader_data.presentation().access_scope
-        || publisher_qos.presentation.coherent_access
-            != discovered_reader_data.presentation().coherent_access
-        || publisher_qos.presentation.ordered_access
-            != discovered_reader_data.presentation().ordered_access
-    {
-        incompatible_qos_policy_list.push(PRESENTATION_QOS_POLICY_ID);
-    }
-    if &writer_qos.deadline > discovered_reader_data.deadline() {
-        incompatible_qos_policy_list.push(DEADLINE_QOS_POLICY_ID);
-    }
-    if &writer_qos.latency_budget < discovered_reader_data.latency_budget() {
-        incompatible_qos_policy_list.push(LATENCYBUDGET_QOS_POLICY_ID);
-    }
-    if &writer_qos.liveliness < discovered_reader_data.liveliness() {
-        incompatible_qos_policy_list.push(LIVELINESS_QOS_POLICY_ID);
-    }
-    if writer_qos.reliability.kind < discovered_reader_data.reliability().kind {
-        incompatible_qos_policy_list.push(RELIABILITY_QOS_POLICY_ID);
-    }
-    if &writer_qos.destination_order < discovered_reader_data.destination_order() {
-        incompatible_qos_policy_list.push(DESTINATIONORDER_QOS_POLICY_ID);
-    }
-    if writer_qos.ownership.kind != discovered_reader_data.ownership().kind {
-        incompatible_qos_policy_list.push(OWNERSHIP_QOS_POLICY_ID);
-    }
-
-    let writer_offered_representation = writer_qos
-        .representation
-        .value
-        .first()
-        .unwrap_or(&XCDR_DATA_REPRESENTATION);
-    if !(discovered_reader_data
-        .representation()
-        .value
-        .contains(writer_offered_representation)
-        || (writer_offered_representation == &XCDR_DATA_REPRESENTATION
-            && discovered_reader_data.representation().value.is_empty()))
-    {
-        incompatible_qos_policy_list.push(DATA_REPRESENTATION_QOS_POLICY_ID);
-    }
-
-    incompatible_qos_policy_list
+    get_incompatible_qos_policy_list(
+        &RequestedOfferedQos {
+            durability: &writer_qos.durability,
+            presentation: &publisher_qos.presentation,
+            deadline: &writer_qos.deadline,
+            latency_budget: &writer_qos.latency_budget,
+            liveliness: &writer_qos.liveliness,
+            reliability: &writer_qos.reliability,
+            destination_order: &writer_qos.destination_order,
+            ownership: &writer_qos.ownership,
+            representation: &writer_qos.representation,
+        },
+        &RequestedOfferedQos {
+            durability: discovered_reader_data.durability(),
+            presentation: discovered_reader_data.presentation(),
+            deadline: discovered_reader_data.deadline(),
+            latency_budget: discovered_reader_data.latency_budget(),
+            liveliness: discovered_reader_data.liveliness(),
+            reliability: discovered_reader_data.reliability(),
+            destination_order: discovered_reader_data.destination_order(),
+            ownership: discovered_reader_data.ownership(),
+            representation: discovered_reader_data.representation(),
+        },
+    )
 }
 
 fn get_discovered_writer_incompatible_qos_policy_list(
@@ -1384,58 +1910,96 @@ fn get_discovered_writer_incompatible_qos_policy_list(
     publication_builtin_topic_data: &PublicationBuiltinTopicData,
     subscriber_qos: &SubscriberQos,
 ) -> Vec<QosPolicyId> {
-    let mut incompatible_qos_policy_list = Vec::new();
+    get_incompatible_qos_policy_list(
+        &RequestedOfferedQos {
+            durability: publication_builtin_topic_data.durability(),
+            presentation: publication_builtin_topic_data.presentation(),
+            deadline: publication_builtin_topic_data.deadline(),
+            latency_budget: publication_builtin_topic_data.latency_budget(),
+            liveliness: publication_builtin_topic_data.liveliness(),
+            reliability: publication_builtin_topic_data.reliability(),
+            destination_order: publication_builtin_topic_data.destination_order(),
+            ownership: publication_builtin_topic_data.ownership(),
+            representation: publication_builtin_topic_data.representation(),
+        },
+        &RequestedOfferedQos {
+            durability: &data_reader.qos().durability,
+            presentation: &subscriber_qos.presentation,
+            deadline: &data_reader.qos().deadline,
+            latency_budget: &data_reader.qos().latency_budget,
+            liveliness: &data_reader.qos().liveliness,
+            reliability: &data_reader.qos().reliability,
+            destination_order: &data_reader.qos().destination_order,
+            ownership: &data_reader.qos().ownership,
+            representation: &data_reader.qos().representation,
+        },
+    )
+}
 
-    if subscriber_qos.presentation.access_scope
-        > publication_builtin_topic_data.presentation().access_scope
-        || subscriber_qos.presentation.coherent_access
-            != publication_builtin_topic_data
-                .presentation()
-                .coherent_access
-        || subscriber_qos.presentation.ordered_access
-            != publication_builtin_topic_data.presentation().ordered_access
+// The policies that follow the requested/offered (RxO) pattern, gathered from the entity and
+// its publisher or subscriber so that both sides of a match are checked with the same rules
+struct RequestedOfferedQos<'a> {
+    durability: &'a DurabilityQosPolicy,
+    presentation: &'a PresentationQosPolicy,
+    deadline: &'a DeadlineQosPolicy,
+    latency_budget: &'a LatencyBudgetQosPolicy,
+    liveliness: &'a LivelinessQosPolicy,
+    reliability: &'a ReliabilityQosPolicy,
+    destination_order: &'a DestinationOrderQosPolicy,
+    ownership: &'a OwnershipQosPolicy,
+    representation: &'a DataRepresentationQosPolicy,
+}
+
+fn get_incompatible_qos_policy_list(
+    offered: &RequestedOfferedQos,
+    requested: &RequestedOfferedQos,
+) -> Vec<QosPolicyId> {
+    let mut incompatible_qos_policy_list = Vec::new();
+    if offered.durability.kind < requested.durability.kind {
+        incompatible_qos_policy_list.push(DURABILITY_QOS_POLICY_ID);
+    }
+    if offered.presentation.access_scope < requested.presentation.access_scope
+        || (requested.presentation.coherent_access && !offered.presentation.coherent_access)
+        || (requested.presentation.ordered_access && !offered.presentation.ordered_access)
     {
         incompatible_qos_policy_list.push(PRESENTATION_QOS_POLICY_ID);
     }
-    if &data_reader.qos().durability > publication_builtin_topic_data.durability() {
-        incompatible_qos_policy_list.push(DURABILITY_QOS_POLICY_ID);
-    }
-    if &data_reader.qos().deadline < publication_builtin_topic_data.deadline() {
+    if offered.deadline.period > requested.deadline.period {
         incompatible_qos_policy_list.push(DEADLINE_QOS_POLICY_ID);
     }
-    if &data_reader.qos().latency_budget > publication_builtin_topic_data.latency_budget() {
+    if offered.latency_budget.duration > requested.latency_budget.duration {
         incompatible_qos_policy_list.push(LATENCYBUDGET_QOS_POLICY_ID);
     }
-    if &data_reader.qos().liveliness > publication_builtin_topic_data.liveliness() {
+    if offered.liveliness.kind < requested.liveliness.kind
+        || offered.liveliness.lease_duration > requested.liveliness.lease_duration
+    {
         incompatible_qos_policy_list.push(LIVELINESS_QOS_POLICY_ID);
     }
-    if data_reader.qos().reliability.kind > publication_builtin_topic_data.reliability().kind {
+    if offered.reliability.kind < requested.reliability.kind {
         incompatible_qos_policy_list.push(RELIABILITY_QOS_POLICY_ID);
     }
-    if &data_reader.qos().destination_order > publication_builtin_topic_data.destination_order() {
+    if offered.destination_order.kind < requested.destination_order.kind {
         incompatible_qos_policy_list.push(DESTINATIONORDER_QOS_POLICY_ID);
     }
-    if data_reader.qos().ownership.kind != publication_builtin_topic_data.ownership().kind {
+    if offered.ownership.kind != requested.ownership.kind {
         incompatible_qos_policy_list.push(OWNERSHIP_QOS_POLICY_ID);
     }
 
-    let writer_offered_representation = publication_builtin_topic_data
-        .representation()
+    // The writer offers its first representation and an empty list is interpreted as
+    // containing XCDR_DATA_REPRESENTATION
+    let offered_representation = offered
+        .representation
         .value
         .first()
         .unwrap_or(&XCDR_DATA_REPRESENTATION);
-    if !data_reader
-        .qos()
+    if !(requested
         .representation
         .value
-        .contains(writer_offered_representation)
+        .contains(offered_representation)
+        || (offered_representation == &XCDR_DATA_REPRESENTATION
+            && requested.representation.value.is_empty()))
     {
-        // Empty list is interpreted as containing XCDR_DATA_REPRESENTATION
-        if !(writer_offered_representation == &XCDR_DATA_REPRESENTATION
-            && data_reader.qos().representation.value.is_empty())
-        {
-            incompatible_qos_policy_list.push(DATA_REPRESENTATION_QOS_POLICY_ID)
-        }
+        incompatible_qos_policy_list.push(DATA_REPRESENTATION_QOS_POLICY_ID);
     }
 
     incompatible_qos_policy_list
@@ -1459,6 +2023,225 @@ fn is_discovered_topic_consistent(
         && &topic_qos.ownership == topic_builtin_topic_data.ownership()
 }
 
+fn historical_data_limit(
+    historical_data: &HistoricalDataQosPolicy,
+) -> transport::writer::HistoricalDataLimit {
+    let length_limit = |length: &Length| match length {
+        Length::Unlimited => None,
+        Length::Limited(l) => Some(*l as usize),
+    };
+    transport::writer::HistoricalDataLimit {
+        depth: length_limit(&historical_data.depth),
+        max_samples: length_limit(&historical_data.max_samples),
+        max_age: match historical_data.max_age {
+            DurationKind::Finite(max_age) => Some(max_age.into()),
+            DurationKind::Infinite => None,
+        },
+    }
+}
+
+// Only the filters of the DDSSQL class are evaluated by the writer. Readers with any other filter
+// or with a filter which fails to parse receive all the samples and filter them themselves.
+fn change_filter(
+    content_filter: &ContentFilterProperty,
+    type_support: &Arc<dyn DynamicType + Send + Sync>,
+) -> Option<transport::writer::ChangeFilter> {
+    if content_filter.filter_class_name != "DDSSQL" {
+        return None;
+    }
+    let sql_filter = SqlFilter::new(
+        &content_filter.filter_expression,
+        &content_filter.expression_parameters,
+    )
+    .ok()?;
+    let type_support = type_support.clone();
+    Some(transport::writer::ChangeFilter::new(move |change| {
+        change.kind != ChangeKind::Alive
+            || sql_filter
+                .evaluate(&change.data_value, type_support.as_ref())
+                .unwrap_or(true)
+    }))
+}
+
+/// Whether the endpoint announced by the sender on behalf of another participant is ignored. The
+/// endpoints relayed by a discovery server back to their own participant, or back to the server
+/// itself, are already known from their original announcement.
+pub fn is_relayed_endpoint_ignored(
+    domain_participant_actor: &DomainParticipantActor,
+    sender_guid_prefix: GuidPrefix,
+    endpoint_guid_prefix: GuidPrefix,
+) -> bool {
+    let local_guid_prefix = domain_participant_actor.transport.guid().prefix();
+    sender_guid_prefix != endpoint_guid_prefix
+        && (sender_guid_prefix == local_guid_prefix || endpoint_guid_prefix == local_guid_prefix)
+}
+
+/// Whether the endpoint is relayed to the clients, which is the case for the endpoints announced
+/// directly by the remote participants to a discovery server.
+pub fn is_endpoint_relayed(
+    domain_participant_actor: &DomainParticipantActor,
+    sender_guid_prefix: GuidPrefix,
+    endpoint_guid_prefix: GuidPrefix,
+) -> bool {
+    domain_participant_actor
+        .domain_participant
+        .is_discovery_server()
+        && sender_guid_prefix == endpoint_guid_prefix
+        && sender_guid_prefix != domain_participant_actor.transport.guid().prefix()
+}
+
+fn relay_discovered_participant(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+) -> DdsResult<()> {
+    let timestamp = domain_participant_actor
+        .domain_participant
+        .get_current_time();
+    if let Some(dw) = domain_participant_actor
+        .domain_participant
+        .builtin_publisher_mut()
+        .lookup_datawriter_mut(DCPS_PARTICIPANT)
+    {
+        dw.write_w_timestamp(discovered_participant_data.serialize_data()?, timestamp)?;
+    }
+    Ok(())
+}
+
+/// The endpoints are relayed with the locators of their participant so that the clients can
+/// communicate with them regardless of the order in which the participant is relayed.
+pub fn relay_discovered_writer(
+    domain_participant_actor: &mut DomainParticipantActor,
+    mut discovered_writer_data: DiscoveredWriterData,
+) -> DdsResult<()> {
+    let writer_proxy = &mut discovered_writer_data.writer_proxy;
+    if writer_proxy.unicast_locator_list.is_empty()
+        && writer_proxy.multicast_locator_list.is_empty()
+    {
+        if let Some(p) = domain_participant_actor
+            .domain_participant
+            .get_discovered_participant_data_by_guid_prefix(
+                &writer_proxy.remote_writer_guid.prefix(),
+            )
+        {
+            writer_proxy.unicast_locator_list =
+                p.participant_proxy.default_unicast_locator_list.clone();
+            writer_proxy.multicast_locator_list =
+                p.participant_proxy.default_multicast_locator_list.clone();
+        }
+    }
+    let timestamp = domain_participant_actor
+        .domain_participant
+        .get_current_time();
+    if let Some(dw) = domain_participant_actor
+        .domain_participant
+        .builtin_publisher_mut()
+        .lookup_datawriter_mut(DCPS_PUBLICATION)
+    {
+        dw.write_w_timestamp(discovered_writer_data.serialize_data()?, timestamp)?;
+    }
+    Ok(())
+}
+
+pub fn relay_discovered_reader(
+    domain_participant_actor: &mut DomainParticipantActor,
+    mut discovered_reader_data: DiscoveredReaderData,
+) -> DdsResult<()> {
+    let reader_proxy = &mut discovered_reader_data.reader_proxy;
+    if reader_proxy.unicast_locator_list.is_empty()
+        && reader_proxy.multicast_locator_list.is_empty()
+    {
+        if let Some(p) = domain_participant_actor
+            .domain_participant
+            .get_discovered_participant_data_by_guid_prefix(
+                &reader_proxy.remote_reader_guid.prefix(),
+            )
+        {
+            reader_proxy.unicast_locator_list =
+                p.participant_proxy.default_unicast_locator_list.clone();
+            reader_proxy.multicast_locator_list =
+                p.participant_proxy.default_multicast_locator_list.clone();
+        }
+    }
+    let timestamp = domain_participant_actor
+        .domain_participant
+        .get_current_time();
+    if let Some(dw) = domain_participant_actor
+        .domain_participant
+        .builtin_publisher_mut()
+        .lookup_datawriter_mut(DCPS_SUBSCRIPTION)
+    {
+        dw.write_w_timestamp(discovered_reader_data.serialize_data()?, timestamp)?;
+    }
+    Ok(())
+}
+
+/// Dispose the relayed instance of a removed participant or endpoint on the builtin writer of
+/// the topic.
+pub fn relay_removed_instance(
+    domain_participant_actor: &mut DomainParticipantActor,
+    topic_name: &str,
+    handle: InstanceHandle,
+) -> DdsResult<()> {
+    let timestamp = domain_participant_actor
+        .domain_participant
+        .get_current_time();
+    if let Some(dw) = domain_participant_actor
+        .domain_participant
+        .builtin_publisher_mut()
+        .lookup_datawriter_mut(topic_name)
+    {
+        dw.dispose_w_timestamp(handle.serialize_data()?, timestamp)?;
+    }
+    Ok(())
+}
+
+fn add_participant_announcement_locators(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+) {
+    // The participant data is also sent unicast to the discovered participants so that a newly
+    // discovered participant receives it right away instead of waiting for the next announcement
+    if discovered_participant_data.participant_proxy.guid_prefix
+        == domain_participant_actor.transport.guid().prefix()
+    {
+        return;
+    }
+    if let Some(dw) = domain_participant_actor
+        .domain_participant
+        .builtin_publisher_mut()
+        .lookup_datawriter_mut(DCPS_PARTICIPANT)
+    {
+        if let TransportWriterKind::Stateless(w) = dw.transport_writer_mut() {
+            for &locator in &discovered_participant_data
+                .participant_proxy
+                .metatraffic_unicast_locator_list
+            {
+                w.add_reader_locator(locator);
+            }
+        }
+    }
+}
+
+fn remove_participant_announcement_locators(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+) {
+    if let Some(dw) = domain_participant_actor
+        .domain_participant
+        .builtin_publisher_mut()
+        .lookup_datawriter_mut(DCPS_PARTICIPANT)
+    {
+        if let TransportWriterKind::Stateless(w) = dw.transport_writer_mut() {
+            for locator in &discovered_participant_data
+                .participant_proxy
+                .metatraffic_unicast_locator_list
+            {
+                w.remove_reader_locator(locator);
+            }
+        }
+    }
+}
+
 fn add_matched_publications_detector(
     domain_participant_actor: &mut DomainParticipantActor,
     discovered_participant_data: &SpdpDiscoveredParticipantData,
@@ -1488,6 +2271,11 @@ fn add_matched_publications_detector(
                 .metatraffic_multicast_locator_list
                 .to_vec(),
             expects_inline_qos,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
         };
         if let Some(dw) = domain_participant_actor
             .domain_participant
@@ -1536,6 +2324,7 @@ fn add_matched_publications_announcer(
             reliability_kind: ReliabilityKind::Reliable,
             durability_kind: DurabilityKind::TransientLocal,
             data_max_size_serialized,
+            disable_positive_acks: false,
         };
         if let Some(dr) = domain_participant_actor
             .domain_participant
@@ -1583,6 +2372,11 @@ fn add_matched_subscriptions_detector(
                 .metatraffic_multicast_locator_list
                 .to_vec(),
             expects_inline_qos,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
         };
         if let Some(dw) = domain_participant_actor
             .domain_participant
@@ -1631,6 +2425,7 @@ fn add_matched_subscriptions_announcer(
                 .metatraffic_multicast_locator_list
                 .to_vec(),
             data_max_size_serialized,
+            disable_positive_acks: false,
         };
         if let Some(dr) = domain_participant_actor
             .domain_participant
@@ -1678,6 +2473,11 @@ fn add_matched_topics_detector(
                 .metatraffic_multicast_locator_list
                 .to_vec(),
             expects_inline_qos,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
         };
         if let Some(dw) = domain_participant_actor
             .domain_participant
@@ -1725,6 +2525,7 @@ fn add_matched_topics_announcer(
                 .metatraffic_multicast_locator_list
                 .to_vec(),
             data_max_size_serialized,
+            disable_positive_acks: false,
         };
         if let Some(dr) = domain_participant_actor
             .domain_participant
@@ -11,6 +11,10 @@ use crate::{
         topic_listener::TopicListenerAsync,
     },
     implementation::{
+        data_representation_builtin_endpoints::participant_message_data::{
+            PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE,
+            PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE,
+        },
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor,
             entities::{
@@ -25,17 +29,29 @@ use crate::{
         status_condition::status_condition_actor::StatusConditionActor,
     },
     infrastructure::{
+        discovery_event::DiscoveryEvent,
         error::{DdsError, DdsResult},
-        instance::InstanceHandle,
+        instance::{EntityKind, InstanceHandle},
         qos::{DomainParticipantQos, PublisherQos, QosKind, SubscriberQos, TopicQos},
+        qos_policy::LivelinessQosPolicyKind,
         status::StatusKind,
-        time::Time,
+        time::{Duration, DurationKind, Time},
+    },
+    runtime::{
+        actor::{Actor, ActorAddress, Mail, MailHandler},
+        mpsc::MpscSender,
     },
-    runtime::actor::{Actor, ActorAddress, Mail, MailHandler},
     xtypes::dynamic_type::DynamicType,
 };
 
-use super::{discovery_service, topic_service};
+use super::{
+    data_writer_service, discovery_service, publisher_service, subscriber_service, topic_service,
+};
+
+pub const AUTOMATIC_LIVELINESS_IDLE_PERIOD: std::time::Duration = std::time::Duration::from_secs(1);
+// The resolution of the timers. A shorter period would make the assertion task busy-loop for a
+// zero or tiny lease duration.
+const AUTOMATIC_LIVELINESS_MIN_PERIOD: std::time::Duration = std::time::Duration::from_millis(1);
 
 pub const BUILT_IN_TOPIC_NAME_LIST: [&str; 4] = [
     DCPS_PARTICIPANT,
@@ -69,8 +85,9 @@ impl MailHandler<CreateUserDefinedPublisher> for DomainParticipantActor {
         );
         let publisher_status_condition_address = status_condition.address();
         let listener = message.a_listener.map(|l| {
-            Actor::spawn(
+            Actor::spawn_with_mailbox(
                 PublisherListenerActor::new(l),
+                self.listener_mailbox,
                 &self.listener_executor.handle(),
             )
         });
@@ -155,8 +172,9 @@ impl MailHandler<CreateUserDefinedSubscriber> for DomainParticipantActor {
         };
         let subscriber_handle = self.instance_handle_counter.generate_new_instance_handle();
         let listener = message.a_listener.map(|l| {
-            Actor::spawn(
+            Actor::spawn_with_mailbox(
                 SubscriberListenerActor::new(l),
+                self.listener_mailbox,
                 &self.listener_executor.handle(),
             )
         });
@@ -269,9 +287,13 @@ impl MailHandler<CreateTopic> for DomainParticipantActor {
             &self.listener_executor.handle(),
         );
         let topic_status_condition_address = status_condition.address();
-        let topic_listener = message
-            .a_listener
-            .map(|l| Actor::spawn(TopicListenerActor::new(l), &self.listener_executor.handle()));
+        let topic_listener = message.a_listener.map(|l| {
+            Actor::spawn_with_mailbox(
+                TopicListenerActor::new(l),
+                self.listener_mailbox,
+                &self.listener_executor.handle(),
+            )
+        });
         let topic = TopicEntity::new(
             qos,
             message.type_name,
@@ -369,6 +391,7 @@ impl MailHandler<FindTopic> for DomainParticipantActor {
                 let qos = TopicQos {
                     topic_data: discovered_topic_data.topic_data().clone(),
                     durability: discovered_topic_data.durability().clone(),
+                    durability_service: discovered_topic_data.durability_service().clone(),
                     deadline: discovered_topic_data.deadline().clone(),
                     latency_budget: discovered_topic_data.latency_budget().clone(),
                     liveliness: discovered_topic_data.liveliness().clone(),
@@ -451,6 +474,35 @@ impl MailHandler<IgnoreParticipant> for DomainParticipantActor {
     }
 }
 
+pub struct IgnoreTopic {
+    pub handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for IgnoreTopic {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<IgnoreTopic> for DomainParticipantActor {
+    fn handle(&mut self, message: IgnoreTopic) -> <IgnoreTopic as Mail>::Result {
+        if self.domain_participant.enabled() {
+            self.domain_participant.ignore_topic(message.handle);
+            if let Some(topic_name) = self
+                .domain_participant
+                .get_discovered_topic_data(&message.handle)
+                .map(|t| t.name().to_owned())
+            {
+                discovery_service::remove_topic_endpoints(
+                    self,
+                    &topic_name,
+                    &message.participant_address,
+                );
+            }
+            Ok(())
+        } else {
+            Err(DdsError::NotEnabled)
+        }
+    }
+}
+
 pub struct IgnoreSubscription {
     pub handle: InstanceHandle,
 }
@@ -499,7 +551,9 @@ impl MailHandler<DeleteContainedEntities> for DomainParticipantActor {
         let deleted_publisher_list: Vec<PublisherEntity> =
             self.domain_participant.drain_publisher_list().collect();
         for mut publisher in deleted_publisher_list {
-            for data_writer in publisher.drain_data_writer_list() {
+            for mut data_writer in publisher.drain_data_writer_list() {
+                data_writer.unregister_all_instances(self.domain_participant.get_current_time());
+                data_writer.cancel_timer_tasks();
                 message
                     .participant_address
                     .send_actor_mail(discovery_service::AnnounceDeletedDataWriter { data_writer })
@@ -510,7 +564,9 @@ impl MailHandler<DeleteContainedEntities> for DomainParticipantActor {
         let deleted_subscriber_list: Vec<SubscriberEntity> =
             self.domain_participant.drain_subscriber_list().collect();
         for mut subscriber in deleted_subscriber_list {
-            for data_reader in subscriber.drain_data_reader_list() {
+            for mut data_reader in subscriber.drain_data_reader_list() {
+                data_reader.cancel_timer_tasks();
+                data_reader.cancel_communication_state_waiters();
                 message
                     .participant_address
                     .send_actor_mail(discovery_service::AnnounceDeletedDataReader { data_reader })
@@ -650,6 +706,22 @@ impl MailHandler<GetDiscoveredParticipantData> for DomainParticipantActor {
     }
 }
 
+pub struct AddDiscoveredParticipantEventSender {
+    pub event_sender: MpscSender<DiscoveryEvent<ParticipantBuiltinTopicData>>,
+}
+impl Mail for AddDiscoveredParticipantEventSender {
+    type Result = ();
+}
+impl MailHandler<AddDiscoveredParticipantEventSender> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: AddDiscoveredParticipantEventSender,
+    ) -> <AddDiscoveredParticipantEventSender as Mail>::Result {
+        self.domain_participant
+            .add_discovered_participant_event_sender(message.event_sender)
+    }
+}
+
 pub struct GetDiscoveredTopics;
 impl Mail for GetDiscoveredTopics {
     type Result = DdsResult<Vec<InstanceHandle>>;
@@ -660,6 +732,76 @@ impl MailHandler<GetDiscoveredTopics> for DomainParticipantActor {
     }
 }
 
+pub struct AddDiscoveredTopicEventSender {
+    pub event_sender: MpscSender<DiscoveryEvent<TopicBuiltinTopicData>>,
+}
+impl Mail for AddDiscoveredTopicEventSender {
+    type Result = ();
+}
+impl MailHandler<AddDiscoveredTopicEventSender> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: AddDiscoveredTopicEventSender,
+    ) -> <AddDiscoveredTopicEventSender as Mail>::Result {
+        self.domain_participant
+            .add_discovered_topic_event_sender(message.event_sender)
+    }
+}
+
+pub struct GetContainedEntities;
+impl Mail for GetContainedEntities {
+    type Result = DdsResult<Vec<(InstanceHandle, EntityKind)>>;
+}
+impl MailHandler<GetContainedEntities> for DomainParticipantActor {
+    fn handle(&mut self, _: GetContainedEntities) -> <GetContainedEntities as Mail>::Result {
+        Ok(self.domain_participant.get_contained_entities())
+    }
+}
+
+pub struct ContainsEntity {
+    pub a_handle: InstanceHandle,
+}
+impl Mail for ContainsEntity {
+    type Result = DdsResult<bool>;
+}
+impl MailHandler<ContainsEntity> for DomainParticipantActor {
+    fn handle(&mut self, message: ContainsEntity) -> <ContainsEntity as Mail>::Result {
+        Ok(self
+            .domain_participant
+            .get_contained_entities()
+            .iter()
+            .any(|(handle, _)| handle == &message.a_handle))
+    }
+}
+
+pub struct GetPublishers;
+impl Mail for GetPublishers {
+    type Result = DdsResult<Vec<(InstanceHandle, ActorAddress<StatusConditionActor>)>>;
+}
+impl MailHandler<GetPublishers> for DomainParticipantActor {
+    fn handle(&mut self, _: GetPublishers) -> <GetPublishers as Mail>::Result {
+        Ok(self
+            .domain_participant
+            .publisher_list()
+            .map(|p| (p.instance_handle(), p.status_condition().address()))
+            .collect())
+    }
+}
+
+pub struct GetSubscribers;
+impl Mail for GetSubscribers {
+    type Result = DdsResult<Vec<(InstanceHandle, ActorAddress<StatusConditionActor>)>>;
+}
+impl MailHandler<GetSubscribers> for DomainParticipantActor {
+    fn handle(&mut self, _: GetSubscribers) -> <GetSubscribers as Mail>::Result {
+        Ok(self
+            .domain_participant
+            .subscriber_list()
+            .map(|s| (s.instance_handle(), s.status_condition().address()))
+            .collect())
+    }
+}
+
 pub struct GetDiscoveredTopicData {
     pub topic_handle: InstanceHandle,
 }
@@ -738,8 +880,9 @@ impl Mail for SetListener {
 impl MailHandler<SetListener> for DomainParticipantActor {
     fn handle(&mut self, message: SetListener) -> <SetListener as Mail>::Result {
         let participant_listener = message.listener.map(|l| {
-            Actor::spawn(
+            Actor::spawn_with_mailbox(
                 DomainParticipantListenerActor::new(l),
+                self.listener_mailbox,
                 &self.listener_executor.handle(),
             )
         });
@@ -764,6 +907,56 @@ impl MailHandler<Enable> for DomainParticipantActor {
                 .domain_participant_address
                 .send_actor_mail(discovery_service::AnnounceParticipant)
                 .ok();
+
+            if self
+                .domain_participant
+                .qos()
+                .entity_factory
+                .autoenable_created_entities
+            {
+                let topic_name_list: Vec<_> = self
+                    .domain_participant
+                    .topic_list()
+                    .map(|t| t.topic_name().to_owned())
+                    .collect();
+                for topic_name in topic_name_list {
+                    MailHandler::handle(
+                        self,
+                        topic_service::Enable {
+                            topic_name,
+                            participant_address: message.domain_participant_address.clone(),
+                        },
+                    )?;
+                }
+                let publisher_handle_list: Vec<_> = self
+                    .domain_participant
+                    .publisher_list()
+                    .map(|p| p.instance_handle())
+                    .collect();
+                for publisher_handle in publisher_handle_list {
+                    MailHandler::handle(
+                        self,
+                        publisher_service::Enable {
+                            publisher_handle,
+                            participant_address: message.domain_participant_address.clone(),
+                        },
+                    )?;
+                }
+                let subscriber_handle_list: Vec<_> = self
+                    .domain_participant
+                    .subscriber_list()
+                    .map(|s| s.instance_handle())
+                    .collect();
+                for subscriber_handle in subscriber_handle_list {
+                    MailHandler::handle(
+                        self,
+                        subscriber_service::Enable {
+                            subscriber_handle,
+                            participant_address: message.domain_participant_address.clone(),
+                        },
+                    )?;
+                }
+            }
         }
         Ok(())
     }
@@ -778,3 +971,85 @@ impl MailHandler<IsEmpty> for DomainParticipantActor {
         self.domain_participant.is_empty()
     }
 }
+
+pub struct AssertLiveliness {
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for AssertLiveliness {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AssertLiveliness> for DomainParticipantActor {
+    fn handle(&mut self, message: AssertLiveliness) -> <AssertLiveliness as Mail>::Result {
+        if !self.domain_participant.enabled() {
+            return Err(DdsError::NotEnabled);
+        }
+        self.participant_message
+            .write(PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE)?;
+        data_writer_service::renew_participant_liveliness(self, message.participant_address);
+        Ok(())
+    }
+}
+
+pub struct AssertAutomaticLiveliness;
+impl Mail for AssertAutomaticLiveliness {
+    type Result = std::time::Duration;
+}
+impl MailHandler<AssertAutomaticLiveliness> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        _: AssertAutomaticLiveliness,
+    ) -> <AssertAutomaticLiveliness as Mail>::Result {
+        // The liveliness of the writers is asserted with a participant message several times
+        // per lease duration so that a single lost message does not make the writers be
+        // considered not alive. The returned value is the time until the next assertion is due.
+        let mut assertion_period = AUTOMATIC_LIVELINESS_IDLE_PERIOD;
+        let mut is_assertion_needed = false;
+        for publisher in self.domain_participant.publisher_list() {
+            for data_writer in publisher.data_writer_list() {
+                if !data_writer.enabled()
+                    || data_writer.qos().liveliness.kind != LivelinessQosPolicyKind::Automatic
+                {
+                    continue;
+                }
+                if let DurationKind::Finite(lease_duration) =
+                    data_writer.qos().liveliness.lease_duration
+                {
+                    assertion_period =
+                        assertion_period.min(automatic_liveliness_assertion_period(lease_duration));
+                    is_assertion_needed = true;
+                }
+            }
+        }
+        if is_assertion_needed {
+            self.participant_message
+                .write(PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE)
+                .ok();
+        }
+        assertion_period
+    }
+}
+
+fn automatic_liveliness_assertion_period(lease_duration: Duration) -> std::time::Duration {
+    (std::time::Duration::from(lease_duration) / 3).max(AUTOMATIC_LIVELINESS_MIN_PERIOD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn automatic_liveliness_assertion_period_is_bounded_for_zero_lease_duration() {
+        assert_eq!(
+            automatic_liveliness_assertion_period(Duration::new(0, 0)),
+            AUTOMATIC_LIVELINESS_MIN_PERIOD
+        );
+        assert_eq!(
+            automatic_liveliness_assertion_period(Duration::new(0, 1)),
+            AUTOMATIC_LIVELINESS_MIN_PERIOD
+        );
+        assert_eq!(
+            automatic_liveliness_assertion_period(Duration::new(3, 0)),
+            std::time::Duration::from_secs(1)
+        );
+    }
+}
@@ -15,6 +15,8 @@ use crate::{
     runtime::actor::{ActorAddress, Mail, MailHandler},
 };
 
+use super::discovery_service;
+
 pub struct RequestedDeadlineMissed {
     pub subscriber_handle: InstanceHandle,
     pub data_reader_handle: InstanceHandle,
@@ -60,7 +62,8 @@ impl MailHandler<RequestedDeadlineMissed> for DomainParticipantActor {
                 l.send_actor_mail(data_reader_listener::TriggerRequestedDeadlineMissed {
                     the_reader,
                     status,
-                });
+                })
+                .ok();
             }
         } else if self
             .domain_participant
@@ -91,7 +94,8 @@ impl MailHandler<RequestedDeadlineMissed> for DomainParticipantActor {
                 l.send_actor_mail(subscriber_listener::TriggerRequestedDeadlineMissed {
                     status,
                     the_reader,
-                });
+                })
+                .ok();
             }
         } else if self
             .domain_participant
@@ -117,7 +121,8 @@ impl MailHandler<RequestedDeadlineMissed> for DomainParticipantActor {
                         status,
                         the_reader,
                     },
-                );
+                )
+                .ok();
             }
         }
 
@@ -129,7 +134,130 @@ impl MailHandler<RequestedDeadlineMissed> for DomainParticipantActor {
             .status_condition()
             .send_actor_mail(status_condition_actor::AddCommunicationState {
                 state: StatusKind::RequestedDeadlineMissed,
-            });
+            })
+            .ok();
+
+        Ok(())
+    }
+}
+
+pub struct SampleLost {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub lost_changes_count: i64,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for SampleLost {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<SampleLost> for DomainParticipantActor {
+    fn handle(&mut self, message: SampleLost) -> <SampleLost as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        data_reader.increment_sample_lost_status(
+            i32::try_from(message.lost_changes_count).unwrap_or(i32::MAX),
+        );
+
+        if data_reader
+            .listener_mask()
+            .contains(&StatusKind::SampleLost)
+        {
+            let status = data_reader.get_sample_lost_status();
+            let the_reader = self.get_data_reader_async(
+                message.participant_address,
+                message.subscriber_handle,
+                message.data_reader_handle,
+            )?;
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_subscriber(message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(message.data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(data_reader_listener::TriggerSampleLost { the_reader, status })
+                    .ok();
+            }
+        } else if self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .listener_mask()
+            .contains(&StatusKind::SampleLost)
+        {
+            let the_reader = self.get_data_reader_async(
+                message.participant_address,
+                message.subscriber_handle,
+                message.data_reader_handle,
+            )?;
+
+            let status = self
+                .domain_participant
+                .get_mut_subscriber(message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(message.data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_sample_lost_status();
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_subscriber(message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(subscriber_listener::TriggerSampleLost { status, the_reader })
+                    .ok();
+            }
+        } else if self
+            .domain_participant
+            .listener_mask()
+            .contains(&StatusKind::SampleLost)
+        {
+            let the_reader = self.get_data_reader_async(
+                message.participant_address,
+                message.subscriber_handle,
+                message.data_reader_handle,
+            )?;
+
+            let status = self
+                .domain_participant
+                .get_mut_subscriber(message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(message.data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_sample_lost_status();
+            if let Some(l) = self.domain_participant.listener() {
+                l.send_actor_mail(domain_participant_listener::TriggerSampleLost {
+                    status,
+                    the_reader,
+                })
+                .ok();
+            }
+        }
+
+        self.domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .status_condition()
+            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                state: StatusKind::SampleLost,
+            })
+            .ok();
+        self.domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .status_condition()
+            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                state: StatusKind::SampleLost,
+            })
+            .ok();
 
         Ok(())
     }
@@ -180,7 +308,8 @@ impl MailHandler<OfferedDeadlineMissed> for DomainParticipantActor {
                 l.send_actor_mail(data_writer_listener::TriggerOfferedDeadlineMissed {
                     the_writer,
                     status,
-                });
+                })
+                .ok();
             }
         } else if self
             .domain_participant
@@ -210,7 +339,8 @@ impl MailHandler<OfferedDeadlineMissed> for DomainParticipantActor {
                 l.send_actor_mail(publisher_listener::TriggerOfferedDeadlineMissed {
                     the_writer,
                     status,
-                });
+                })
+                .ok();
             }
         } else if self
             .domain_participant
@@ -233,7 +363,8 @@ impl MailHandler<OfferedDeadlineMissed> for DomainParticipantActor {
                 l.send_actor_mail(domain_participant_listener::TriggerOfferedDeadlineMissed {
                     the_writer,
                     status,
-                });
+                })
+                .ok();
             }
         }
 
@@ -245,7 +376,303 @@ impl MailHandler<OfferedDeadlineMissed> for DomainParticipantActor {
             .status_condition()
             .send_actor_mail(status_condition_actor::AddCommunicationState {
                 state: StatusKind::OfferedDeadlineMissed,
-            });
+            })
+            .ok();
+
+        Ok(())
+    }
+}
+
+pub struct LivelinessChanged {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for LivelinessChanged {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<LivelinessChanged> for DomainParticipantActor {
+    fn handle(&mut self, message: LivelinessChanged) -> <LivelinessChanged as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_reader = subscriber
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        if data_reader
+            .listener_mask()
+            .contains(&StatusKind::LivelinessChanged)
+        {
+            let status = data_reader.get_liveliness_changed_status();
+            let the_reader = self.get_data_reader_async(
+                message.participant_address,
+                message.subscriber_handle,
+                message.data_reader_handle,
+            )?;
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_subscriber(message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(message.data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(data_reader_listener::TriggerLivelinessChanged {
+                    the_reader,
+                    status,
+                })
+                .ok();
+            }
+        } else if self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .listener_mask()
+            .contains(&StatusKind::LivelinessChanged)
+        {
+            let the_reader = self.get_data_reader_async(
+                message.participant_address,
+                message.subscriber_handle,
+                message.data_reader_handle,
+            )?;
+
+            let status = self
+                .domain_participant
+                .get_mut_subscriber(message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(message.data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_liveliness_changed_status();
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_subscriber(message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(subscriber_listener::TriggerLivelinessChanged {
+                    status,
+                    the_reader,
+                })
+                .ok();
+            }
+        } else if self
+            .domain_participant
+            .listener_mask()
+            .contains(&StatusKind::LivelinessChanged)
+        {
+            let the_reader = self.get_data_reader_async(
+                message.participant_address,
+                message.subscriber_handle,
+                message.data_reader_handle,
+            )?;
+
+            let status = self
+                .domain_participant
+                .get_mut_subscriber(message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_reader(message.data_reader_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_liveliness_changed_status();
+            if let Some(l) = self.domain_participant.listener() {
+                l.send_actor_mail(domain_participant_listener::TriggerLivelinessChanged {
+                    status,
+                    the_reader,
+                })
+                .ok();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct ParticipantLeaseExpired {
+    pub participant_handle: InstanceHandle,
+    pub lease_id: u64,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for ParticipantLeaseExpired {
+    type Result = ();
+}
+impl MailHandler<ParticipantLeaseExpired> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: ParticipantLeaseExpired,
+    ) -> <ParticipantLeaseExpired as Mail>::Result {
+        // The lease may have been renewed by an announcement received after the timer fired
+        if !self
+            .domain_participant
+            .is_participant_lease_current(&message.participant_handle, message.lease_id)
+        {
+            return;
+        }
+        tracing::info!(
+            participant = ?message.participant_handle,
+            "Discovered participant removed because its lease expired"
+        );
+        MailHandler::handle(
+            self,
+            discovery_service::RemoveDiscoveredParticipant {
+                discovered_participant: message.participant_handle,
+                participant_address: message.participant_address,
+            },
+        );
+    }
+}
+
+pub struct PublicationLeaseExpired {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub publication_handle: InstanceHandle,
+    pub lease_id: u64,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for PublicationLeaseExpired {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<PublicationLeaseExpired> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: PublicationLeaseExpired,
+    ) -> <PublicationLeaseExpired as Mail>::Result {
+        let data_reader = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_reader(message.data_reader_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        // The liveliness may have been asserted again after the timer fired
+        if data_reader.is_publication_lease_current(&message.publication_handle, message.lease_id)
+            && data_reader.set_matched_publication_not_alive(message.publication_handle)
+        {
+            MailHandler::handle(
+                self,
+                LivelinessChanged {
+                    subscriber_handle: message.subscriber_handle,
+                    data_reader_handle: message.data_reader_handle,
+                    participant_address: message.participant_address,
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct LivelinessLost {
+    pub publisher_handle: InstanceHandle,
+    pub data_writer_handle: InstanceHandle,
+    pub lease_id: u64,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for LivelinessLost {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<LivelinessLost> for DomainParticipantActor {
+    fn handle(&mut self, message: LivelinessLost) -> <LivelinessLost as Mail>::Result {
+        let publisher = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let data_writer = publisher
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+
+        // The liveliness may have been asserted again after the timer fired
+        if !data_writer.is_liveliness_lease_current(message.lease_id) {
+            return Ok(());
+        }
+        data_writer.increment_liveliness_lost_status();
+
+        if data_writer
+            .listener_mask()
+            .contains(&StatusKind::LivelinessLost)
+        {
+            let status = data_writer.get_liveliness_lost_status();
+            let the_writer = self.get_data_writer_async(
+                message.participant_address,
+                message.publisher_handle,
+                message.data_writer_handle,
+            )?;
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_publisher(message.publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_writer(message.data_writer_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(data_writer_listener::TriggerLivelinessLost {
+                    the_writer,
+                    status,
+                })
+                .ok();
+            }
+        } else if self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .listener_mask()
+            .contains(&StatusKind::LivelinessLost)
+        {
+            let the_writer = self.get_data_writer_async(
+                message.participant_address,
+                message.publisher_handle,
+                message.data_writer_handle,
+            )?;
+            let status = self
+                .domain_participant
+                .get_mut_publisher(message.publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_writer(message.data_writer_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_liveliness_lost_status();
+            if let Some(l) = self
+                .domain_participant
+                .get_mut_publisher(message.publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .listener()
+            {
+                l.send_actor_mail(publisher_listener::TriggerLivelinessLost { the_writer, status })
+                    .ok();
+            }
+        } else if self
+            .domain_participant
+            .listener_mask()
+            .contains(&StatusKind::LivelinessLost)
+        {
+            let the_writer = self.get_data_writer_async(
+                message.participant_address,
+                message.publisher_handle,
+                message.data_writer_handle,
+            )?;
+            let status = self
+                .domain_participant
+                .get_mut_publisher(message.publisher_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_mut_data_writer(message.data_writer_handle)
+                .ok_or(DdsError::AlreadyDeleted)?
+                .get_liveliness_lost_status();
+            if let Some(l) = self.domain_participant.listener() {
+                l.send_actor_mail(domain_participant_listener::TriggerLivelinessLost {
+                    the_writer,
+                    status,
+                })
+                .ok();
+            }
+        }
+
+        self.domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .get_mut_data_writer(message.data_writer_handle)
+            .ok_or(DdsError::AlreadyDeleted)?
+            .status_condition()
+            .send_actor_mail(status_condition_actor::AddCommunicationState {
+                state: StatusKind::LivelinessLost,
+            })
+            .ok();
 
         Ok(())
     }
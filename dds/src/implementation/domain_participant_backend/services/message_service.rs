@@ -12,7 +12,7 @@ use crate::{
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor,
             entities::data_reader::{AddChangeResult, TransportReaderKind},
-            services::discovery_service,
+            services::{discovery_service, participant_message_service, type_lookup_service},
         },
         listeners::{data_reader_listener, domain_participant_listener, subscriber_listener},
         status_condition::status_condition_actor,
@@ -21,17 +21,17 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos_policy::{
-            DurabilityQosPolicyKind, HistoryQosPolicy, LifespanQosPolicy, ResourceLimitsQosPolicy,
-            TransportPriorityQosPolicy,
+            DurabilityQosPolicyKind, HistoryQosPolicy, LifespanQosPolicy, LivelinessQosPolicyKind,
+            ResourceLimitsQosPolicy, TransportPriorityQosPolicy,
         },
         status::StatusKind,
-        time::DurationKind,
+        time::{DurationKind, Time},
     },
     runtime::actor::{ActorAddress, Mail, MailHandler},
     topic_definition::type_support::DdsDeserialize,
     transport::{
         history_cache::CacheChange,
-        types::{ChangeKind, SequenceNumber},
+        types::{ChangeKind, Guid, SequenceNumber},
     },
 };
 
@@ -48,7 +48,22 @@ impl Mail for AddCacheChange {
 }
 impl MailHandler<AddCacheChange> for DomainParticipantActor {
     fn handle(&mut self, message: AddCacheChange) -> <AddCacheChange as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if subscriber.is_coherent_access_in_progress() {
+            subscriber.defer_cache_change(message.data_reader_handle, message.cache_change);
+            return Ok(());
+        }
         let reception_timestamp = self.domain_participant.get_current_time();
+        assert_writer_liveliness(
+            self,
+            message.subscriber_handle,
+            message.data_reader_handle,
+            message.cache_change.writer_guid,
+            message.participant_address.clone(),
+        )?;
         let subscriber = self
             .domain_participant
             .get_mut_subscriber(message.subscriber_handle)
@@ -59,37 +74,48 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
             .ok_or(DdsError::AlreadyDeleted)?;
         let writer_instance_handle = InstanceHandle::new(message.cache_change.writer_guid.into());
 
-        if data_reader
-            .get_matched_publication_data(&writer_instance_handle)
-            .is_some()
+        if let Some(publication_data) =
+            data_reader.get_matched_publication_data(&writer_instance_handle)
         {
+            let lifespan_remaining = match publication_data.lifespan().duration {
+                DurationKind::Finite(lifespan_duration) => {
+                    let source_timestamp = message
+                        .cache_change
+                        .source_timestamp
+                        .map_or(reception_timestamp, Into::into);
+                    Some(source_timestamp - reception_timestamp + lifespan_duration)
+                }
+                DurationKind::Infinite => None,
+            };
             match data_reader.add_reader_change(message.cache_change, reception_timestamp)? {
                 AddChangeResult::Added(change_instance_handle) => {
-                    if let DurationKind::Finite(deadline_missed_period) =
-                        data_reader.qos().deadline.period
-                    {
-                        let timer_handle = self.timer_driver.handle();
+                    if let Some(sleep_duration) = lifespan_remaining {
                         let participant_address = message.participant_address.clone();
-                        let requested_deadline_missed_task =
-                            self.backend_executor.handle().spawn(async move {
-                                loop {
-                                    timer_handle.sleep(deadline_missed_period.into()).await;
-                                    participant_address
-                                        .send_actor_mail(event_service::RequestedDeadlineMissed {
-                                            subscriber_handle: message.subscriber_handle,
-                                            data_reader_handle: message.data_reader_handle,
-                                            change_instance_handle,
-                                            participant_address: participant_address.clone(),
-                                        })
-                                        .ok();
-                                }
+                        self.timer_driver
+                            .handle()
+                            .schedule(sleep_duration.into(), move || {
+                                participant_address
+                                    .send_actor_mail(RemoveExpiredReaderChanges {
+                                        subscriber_handle: message.subscriber_handle,
+                                        data_reader_handle: message.data_reader_handle,
+                                    })
+                                    .ok();
                             });
-
-                        data_reader.insert_instance_deadline_missed_task(
-                            change_instance_handle,
-                            requested_deadline_missed_task,
-                        );
                     }
+                    schedule_requested_deadline_missed_task(
+                        self,
+                        message.subscriber_handle,
+                        message.data_reader_handle,
+                        change_instance_handle,
+                        message.participant_address.clone(),
+                    )?;
+                    schedule_instance_autopurge_task(
+                        self,
+                        message.subscriber_handle,
+                        message.data_reader_handle,
+                        change_instance_handle,
+                        message.participant_address.clone(),
+                    )?;
 
                     if self
                         .domain_participant
@@ -110,7 +136,8 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
                         {
                             l.send_actor_mail(subscriber_listener::TriggerDataOnReaders {
                                 the_subscriber,
-                            });
+                            })
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -136,7 +163,8 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
                         {
                             l.send_actor_mail(data_reader_listener::TriggerDataAvailable {
                                 the_reader,
-                            });
+                            })
+                            .ok();
                         }
                     }
 
@@ -146,7 +174,8 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
                         .status_condition()
                         .send_actor_mail(status_condition_actor::AddCommunicationState {
                             state: StatusKind::DataOnReaders,
-                        });
+                        })
+                        .ok();
 
                     self.domain_participant
                         .get_mut_subscriber(message.subscriber_handle)
@@ -156,7 +185,8 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
                         .status_condition()
                         .send_actor_mail(status_condition_actor::AddCommunicationState {
                             state: StatusKind::DataAvailable,
-                        });
+                        })
+                        .ok();
                 }
                 AddChangeResult::NotAdded => (), // Do nothing
                 AddChangeResult::Rejected(instance_handle, sample_rejected_status_kind) => {
@@ -186,7 +216,8 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
                             l.send_actor_mail(data_reader_listener::TriggerSampleRejected {
                                 the_reader,
                                 status,
-                            });
+                            })
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -216,7 +247,8 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
                             l.send_actor_mail(subscriber_listener::TriggerSampleRejected {
                                 status,
                                 the_reader,
-                            });
+                            })
+                            .ok();
                         }
                     } else if self
                         .domain_participant
@@ -239,7 +271,8 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
                             l.send_actor_mail(domain_participant_listener::TriggerSampleRejected {
                                 status,
                                 the_reader,
-                            });
+                            })
+                            .ok();
                         }
                     }
 
@@ -251,7 +284,8 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
                         .status_condition()
                         .send_actor_mail(status_condition_actor::AddCommunicationState {
                             state: StatusKind::SampleRejected,
-                        });
+                        })
+                        .ok();
                 }
             }
         }
@@ -259,6 +293,193 @@ impl MailHandler<AddCacheChange> for DomainParticipantActor {
     }
 }
 
+pub struct AssertWriterLiveliness {
+    pub writer_guid: Guid,
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for AssertWriterLiveliness {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<AssertWriterLiveliness> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: AssertWriterLiveliness,
+    ) -> <AssertWriterLiveliness as Mail>::Result {
+        assert_writer_liveliness(
+            self,
+            message.subscriber_handle,
+            message.data_reader_handle,
+            message.writer_guid,
+            message.participant_address,
+        )
+    }
+}
+
+// The data of a writer with MANUAL_BY_PARTICIPANT liveliness asserts the liveliness of all the
+// writers of its participant with that kind, as the participant message would
+fn assert_writer_liveliness(
+    domain_participant_actor: &mut DomainParticipantActor,
+    subscriber_handle: InstanceHandle,
+    data_reader_handle: InstanceHandle,
+    writer_guid: Guid,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) -> DdsResult<()> {
+    let publication_handle = InstanceHandle::new(writer_guid.into());
+    let Some(publication_data) = domain_participant_actor
+        .domain_participant
+        .get_mut_subscriber(subscriber_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .get_mut_data_reader(data_reader_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .get_matched_publication_data(&publication_handle)
+    else {
+        return Ok(());
+    };
+
+    if publication_data.liveliness().kind == LivelinessQosPolicyKind::ManualByParticipant {
+        participant_message_service::assert_remote_participant_liveliness(
+            domain_participant_actor,
+            writer_guid.prefix(),
+            LivelinessQosPolicyKind::ManualByParticipant,
+            participant_address,
+        );
+        Ok(())
+    } else {
+        assert_publication_liveliness(
+            domain_participant_actor,
+            subscriber_handle,
+            data_reader_handle,
+            publication_handle,
+            participant_address,
+        )
+    }
+}
+
+// Restarts the periodic deadline timer of the instance after which the requested deadline
+// missed status is signalled every deadline period until a new sample of the instance is received
+pub fn schedule_requested_deadline_missed_task(
+    domain_participant_actor: &mut DomainParticipantActor,
+    subscriber_handle: InstanceHandle,
+    data_reader_handle: InstanceHandle,
+    instance_handle: InstanceHandle,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) -> DdsResult<()> {
+    let data_reader = domain_participant_actor
+        .domain_participant
+        .get_mut_subscriber(subscriber_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .get_mut_data_reader(data_reader_handle)
+        .ok_or(DdsError::AlreadyDeleted)?;
+    if let DurationKind::Finite(deadline_missed_period) = data_reader.qos().deadline.period {
+        let requested_deadline_missed_task = domain_participant_actor
+            .timer_driver
+            .handle()
+            .schedule_periodic(deadline_missed_period.into(), move || {
+                participant_address
+                    .send_actor_mail(event_service::RequestedDeadlineMissed {
+                        subscriber_handle,
+                        data_reader_handle,
+                        change_instance_handle: instance_handle,
+                        participant_address: participant_address.clone(),
+                    })
+                    .ok();
+            });
+        data_reader
+            .insert_instance_deadline_missed_task(instance_handle, requested_deadline_missed_task);
+    }
+    Ok(())
+}
+
+// Schedules the purge of the instance once it stops being alive for the delay set by the reader
+// data lifecycle QoS
+pub fn schedule_instance_autopurge_task(
+    domain_participant_actor: &mut DomainParticipantActor,
+    subscriber_handle: InstanceHandle,
+    data_reader_handle: InstanceHandle,
+    instance_handle: InstanceHandle,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) -> DdsResult<()> {
+    let data_reader = domain_participant_actor
+        .domain_participant
+        .get_mut_subscriber(subscriber_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .get_mut_data_reader(data_reader_handle)
+        .ok_or(DdsError::AlreadyDeleted)?;
+    if let Some(autopurge_delay) = data_reader.instance_autopurge_delay(&instance_handle) {
+        let autopurge_task = domain_participant_actor.timer_driver.handle().schedule(
+            autopurge_delay.into(),
+            move || {
+                participant_address
+                    .send_actor_mail(PurgeReaderInstance {
+                        subscriber_handle,
+                        data_reader_handle,
+                        instance_handle,
+                    })
+                    .ok();
+            },
+        );
+        data_reader.insert_instance_autopurge_task(instance_handle, autopurge_task);
+    }
+    Ok(())
+}
+
+// Asserting the liveliness of a matched publication restarts the timer which considers the
+// writer not alive when its lease duration elapses without a new assertion
+pub fn assert_publication_liveliness(
+    domain_participant_actor: &mut DomainParticipantActor,
+    subscriber_handle: InstanceHandle,
+    data_reader_handle: InstanceHandle,
+    publication_handle: InstanceHandle,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) -> DdsResult<()> {
+    let data_reader = domain_participant_actor
+        .domain_participant
+        .get_mut_subscriber(subscriber_handle)
+        .ok_or(DdsError::AlreadyDeleted)?
+        .get_mut_data_reader(data_reader_handle)
+        .ok_or(DdsError::AlreadyDeleted)?;
+    let Some(lease_duration) = data_reader
+        .get_matched_publication_data(&publication_handle)
+        .map(|p| p.liveliness().lease_duration)
+    else {
+        return Ok(());
+    };
+
+    if let DurationKind::Finite(lease_duration) = lease_duration {
+        let lease_id = data_reader.generate_publication_lease_id();
+        let lease_participant_address = participant_address.clone();
+        let lease_task = domain_participant_actor.timer_driver.handle().schedule(
+            lease_duration.into(),
+            move || {
+                lease_participant_address
+                    .send_actor_mail(event_service::PublicationLeaseExpired {
+                        subscriber_handle,
+                        data_reader_handle,
+                        publication_handle,
+                        lease_id,
+                        participant_address: lease_participant_address.clone(),
+                    })
+                    .ok();
+            },
+        );
+        data_reader.renew_publication_lease(publication_handle, lease_id, lease_task);
+    }
+
+    if data_reader.assert_matched_publication_liveliness(publication_handle) {
+        MailHandler::handle(
+            domain_participant_actor,
+            event_service::LivelinessChanged {
+                subscriber_handle,
+                data_reader_handle,
+                participant_address,
+            },
+        )?;
+    }
+    Ok(())
+}
+
 pub struct AddBuiltinParticipantsDetectorCacheChange {
     pub participant_address: ActorAddress<DomainParticipantActor>,
     pub cache_change: CacheChange,
@@ -278,29 +499,38 @@ impl MailHandler<AddBuiltinParticipantsDetectorCacheChange> for DomainParticipan
                         message.cache_change.data_value.as_ref(),
                     )
                 {
+                    // The participants announced by a discovery server on behalf of its clients
+                    // are sent with the GuidPrefix of the server
+                    let is_relayed = message.cache_change.writer_guid.prefix()
+                        != discovered_participant_data.participant_proxy.guid_prefix;
+                    discovery_service::renew_participant_lease(
+                        self,
+                        &discovered_participant_data,
+                        message.participant_address.clone(),
+                    );
                     message
                         .participant_address
                         .send_actor_mail(discovery_service::AddDiscoveredParticipant {
                             discovered_participant_data,
+                            is_relayed,
                         })
                         .ok();
                 }
             }
-            ChangeKind::NotAliveDisposed => {
+            ChangeKind::NotAliveDisposed | ChangeKind::NotAliveDisposedUnregistered => {
                 if let Ok(discovered_participant_handle) =
-                    InstanceHandle::deserialize_data(message.cache_change.data_value.as_ref())
+                    get_instance_handle_from_key_only_change(&message.cache_change)
                 {
                     message
                         .participant_address
                         .send_actor_mail(discovery_service::RemoveDiscoveredParticipant {
                             discovered_participant: discovered_participant_handle,
+                            participant_address: message.participant_address.clone(),
                         })
                         .ok();
                 }
             }
-            ChangeKind::AliveFiltered
-            | ChangeKind::NotAliveUnregistered
-            | ChangeKind::NotAliveDisposedUnregistered => (), // Do nothing,
+            ChangeKind::AliveFiltered | ChangeKind::NotAliveUnregistered => (), // Do nothing,
         }
 
         let reception_timestamp = self.domain_participant.get_current_time();
@@ -349,6 +579,14 @@ impl MailHandler<AddBuiltinTopicsDetectorCacheChange> for DomainParticipantActor
                 if let Ok(topic_builtin_topic_data) = TopicBuiltinTopicData::deserialize_data(
                     message.cache_change.data_value.as_ref(),
                 ) {
+                    if self
+                        .domain_participant
+                        .is_topic_handle_ignored(&InstanceHandle::new(
+                            topic_builtin_topic_data.key().value,
+                        ))
+                    {
+                        return;
+                    }
                     self.domain_participant
                         .add_discovered_topic(topic_builtin_topic_data.clone());
                     for topic in self.domain_participant.topic_list() {
@@ -362,10 +600,21 @@ impl MailHandler<AddBuiltinTopicsDetectorCacheChange> for DomainParticipantActor
                     }
                 }
             }
-            ChangeKind::NotAliveDisposed
-            | ChangeKind::AliveFiltered
-            | ChangeKind::NotAliveUnregistered
-            | ChangeKind::NotAliveDisposedUnregistered => (),
+            ChangeKind::NotAliveDisposed | ChangeKind::NotAliveDisposedUnregistered => {
+                if let Ok(discovered_topic_handle) =
+                    get_instance_handle_from_key_only_change(&message.cache_change)
+                {
+                    if self
+                        .domain_participant
+                        .is_topic_handle_ignored(&discovered_topic_handle)
+                    {
+                        return;
+                    }
+                    self.domain_participant
+                        .remove_discovered_topic(&discovered_topic_handle);
+                }
+            }
+            ChangeKind::AliveFiltered | ChangeKind::NotAliveUnregistered => (),
         }
 
         let reception_timestamp = self.domain_participant.get_current_time();
@@ -409,11 +658,59 @@ impl MailHandler<AddBuiltinPublicationsDetectorCacheChange> for DomainParticipan
         &mut self,
         message: AddBuiltinPublicationsDetectorCacheChange,
     ) -> <AddBuiltinPublicationsDetectorCacheChange as Mail>::Result {
+        let sender_guid_prefix = message.cache_change.writer_guid.prefix();
         match message.cache_change.kind {
             ChangeKind::Alive => {
                 if let Ok(discovered_writer_data) =
                     DiscoveredWriterData::deserialize_data(message.cache_change.data_value.as_ref())
                 {
+                    let writer_guid_prefix = discovered_writer_data
+                        .writer_proxy
+                        .remote_writer_guid
+                        .prefix();
+                    if discovery_service::is_relayed_endpoint_ignored(
+                        self,
+                        sender_guid_prefix,
+                        writer_guid_prefix,
+                    ) {
+                        return;
+                    }
+                    if discovery_service::is_endpoint_relayed(
+                        self,
+                        sender_guid_prefix,
+                        writer_guid_prefix,
+                    ) {
+                        discovery_service::relay_discovered_writer(
+                            self,
+                            discovered_writer_data.clone(),
+                        )
+                        .ok();
+                    }
+                    if discovery_service::is_endpoint_topic_ignored(
+                        self,
+                        writer_guid_prefix,
+                        discovered_writer_data.dds_publication_data.topic_name(),
+                    ) {
+                        return;
+                    }
+                    if self
+                        .domain_participant
+                        .add_discovered_writer(discovered_writer_data.clone())
+                        .is_err()
+                    {
+                        tracing::warn!(
+                            "Discovered writer ignored because the maximum number of discovered endpoints is reached"
+                        );
+                        return;
+                    }
+                    type_lookup_service::request_type_objects(
+                        self,
+                        message.cache_change.writer_guid.prefix(),
+                        discovered_writer_data
+                            .dds_publication_data
+                            .type_information(),
+                    );
+
                     let publication_builtin_topic_data =
                         &discovered_writer_data.dds_publication_data;
                     if self
@@ -426,6 +723,9 @@ impl MailHandler<AddBuiltinPublicationsDetectorCacheChange> for DomainParticipan
                             name: publication_builtin_topic_data.topic_name().to_owned(),
                             type_name: publication_builtin_topic_data.get_type_name().to_owned(),
                             durability: publication_builtin_topic_data.durability().clone(),
+                            durability_service: publication_builtin_topic_data
+                                .durability_service()
+                                .clone(),
                             deadline: publication_builtin_topic_data.deadline().clone(),
                             latency_budget: publication_builtin_topic_data.latency_budget().clone(),
                             liveliness: publication_builtin_topic_data.liveliness().clone(),
@@ -444,10 +744,11 @@ impl MailHandler<AddBuiltinPublicationsDetectorCacheChange> for DomainParticipan
                         self.domain_participant.add_discovered_topic(writer_topic);
                     }
 
-                    self.domain_participant
-                        .add_discovered_writer(discovered_writer_data.clone());
                     for subscriber in self.domain_participant.subscriber_list() {
-                        for data_reader in subscriber.data_reader_list() {
+                        for data_reader in subscriber.data_reader_list().filter(|dr| {
+                            dr.topic_name()
+                                == discovered_writer_data.dds_publication_data.topic_name()
+                        }) {
                             message
                                 .participant_address
                                 .send_actor_mail(discovery_service::AddDiscoveredWriter {
@@ -461,27 +762,59 @@ impl MailHandler<AddBuiltinPublicationsDetectorCacheChange> for DomainParticipan
                     }
                 }
             }
-            ChangeKind::NotAliveDisposed | ChangeKind::NotAliveDisposedUnregistered => {
+            // An endpoint which is unregistered without being disposed has no writer left announcing
+            // it, as when its participant is gone, so it is removed as well
+            ChangeKind::NotAliveDisposed
+            | ChangeKind::NotAliveDisposedUnregistered
+            | ChangeKind::NotAliveUnregistered => {
                 if let Ok(discovered_writer_handle) =
-                    InstanceHandle::deserialize_data(message.cache_change.data_value.as_ref())
+                    get_instance_handle_from_key_only_change(&message.cache_change)
                 {
-                    self.domain_participant
-                        .remove_discovered_writer(&discovered_writer_handle);
-                    for subscriber in self.domain_participant.subscriber_list() {
-                        for data_reader in subscriber.data_reader_list() {
-                            message
-                                .participant_address
-                                .send_actor_mail(discovery_service::RemoveDiscoveredWriter {
-                                    publication_handle: discovered_writer_handle,
-                                    subscriber_handle: subscriber.instance_handle(),
-                                    data_reader_handle: data_reader.instance_handle(),
-                                })
-                                .ok();
+                    let writer_guid_prefix =
+                        Guid::from(<[u8; 16]>::from(discovered_writer_handle)).prefix();
+                    if discovery_service::is_relayed_endpoint_ignored(
+                        self,
+                        sender_guid_prefix,
+                        writer_guid_prefix,
+                    ) {
+                        return;
+                    }
+                    if discovery_service::is_endpoint_relayed(
+                        self,
+                        sender_guid_prefix,
+                        writer_guid_prefix,
+                    ) {
+                        discovery_service::relay_removed_instance(
+                            self,
+                            DCPS_PUBLICATION,
+                            discovered_writer_handle,
+                        )
+                        .ok();
+                    }
+                    if let Some(discovered_writer_data) = self
+                        .domain_participant
+                        .remove_discovered_writer(&discovered_writer_handle)
+                    {
+                        for subscriber in self.domain_participant.subscriber_list() {
+                            for data_reader in subscriber.data_reader_list().filter(|dr| {
+                                dr.topic_name()
+                                    == discovered_writer_data.dds_publication_data.topic_name()
+                            }) {
+                                message
+                                    .participant_address
+                                    .send_actor_mail(discovery_service::RemoveDiscoveredWriter {
+                                        publication_handle: discovered_writer_handle,
+                                        subscriber_handle: subscriber.instance_handle(),
+                                        data_reader_handle: data_reader.instance_handle(),
+                                        participant_address: message.participant_address.clone(),
+                                    })
+                                    .ok();
+                            }
                         }
                     }
                 }
             }
-            ChangeKind::AliveFiltered | ChangeKind::NotAliveUnregistered => (),
+            ChangeKind::AliveFiltered => (),
         }
 
         let reception_timestamp = self.domain_participant.get_current_time();
@@ -525,11 +858,59 @@ impl MailHandler<AddBuiltinSubscriptionsDetectorCacheChange> for DomainParticipa
         &mut self,
         message: AddBuiltinSubscriptionsDetectorCacheChange,
     ) -> <AddBuiltinSubscriptionsDetectorCacheChange as Mail>::Result {
+        let sender_guid_prefix = message.cache_change.writer_guid.prefix();
         match message.cache_change.kind {
             ChangeKind::Alive => {
                 if let Ok(discovered_reader_data) =
                     DiscoveredReaderData::deserialize_data(message.cache_change.data_value.as_ref())
                 {
+                    let reader_guid_prefix = discovered_reader_data
+                        .reader_proxy
+                        .remote_reader_guid
+                        .prefix();
+                    if discovery_service::is_relayed_endpoint_ignored(
+                        self,
+                        sender_guid_prefix,
+                        reader_guid_prefix,
+                    ) {
+                        return;
+                    }
+                    if discovery_service::is_endpoint_relayed(
+                        self,
+                        sender_guid_prefix,
+                        reader_guid_prefix,
+                    ) {
+                        discovery_service::relay_discovered_reader(
+                            self,
+                            discovered_reader_data.clone(),
+                        )
+                        .ok();
+                    }
+                    if discovery_service::is_endpoint_topic_ignored(
+                        self,
+                        reader_guid_prefix,
+                        discovered_reader_data.dds_subscription_data.topic_name(),
+                    ) {
+                        return;
+                    }
+                    if self
+                        .domain_participant
+                        .add_discovered_reader(discovered_reader_data.clone())
+                        .is_err()
+                    {
+                        tracing::warn!(
+                            "Discovered reader ignored because the maximum number of discovered endpoints is reached"
+                        );
+                        return;
+                    }
+                    type_lookup_service::request_type_objects(
+                        self,
+                        message.cache_change.writer_guid.prefix(),
+                        discovered_reader_data
+                            .dds_subscription_data
+                            .type_information(),
+                    );
+
                     if self
                         .domain_participant
                         .find_topic(&discovered_reader_data.dds_subscription_data.topic_name)
@@ -554,6 +935,7 @@ impl MailHandler<AddBuiltinSubscriptionsDetectorCacheChange> for DomainParticipa
                                 .dds_subscription_data
                                 .durability()
                                 .clone(),
+                            durability_service: Default::default(),
                             deadline: discovered_reader_data
                                 .dds_subscription_data
                                 .deadline()
@@ -590,10 +972,11 @@ impl MailHandler<AddBuiltinSubscriptionsDetectorCacheChange> for DomainParticipa
                         self.domain_participant.add_discovered_topic(reader_topic);
                     }
 
-                    self.domain_participant
-                        .add_discovered_reader(discovered_reader_data.clone());
                     for publisher in self.domain_participant.publisher_list() {
-                        for data_writer in publisher.data_writer_list() {
+                        for data_writer in publisher.data_writer_list().filter(|dw| {
+                            dw.topic_name()
+                                == discovered_reader_data.dds_subscription_data.topic_name()
+                        }) {
                             message
                                 .participant_address
                                 .send_actor_mail(discovery_service::AddDiscoveredReader {
@@ -607,27 +990,58 @@ impl MailHandler<AddBuiltinSubscriptionsDetectorCacheChange> for DomainParticipa
                     }
                 }
             }
-            ChangeKind::NotAliveDisposed | ChangeKind::NotAliveDisposedUnregistered => {
+            // An endpoint which is unregistered without being disposed has no writer left announcing
+            // it, as when its participant is gone, so it is removed as well
+            ChangeKind::NotAliveDisposed
+            | ChangeKind::NotAliveDisposedUnregistered
+            | ChangeKind::NotAliveUnregistered => {
                 if let Ok(discovered_reader_handle) =
-                    InstanceHandle::deserialize_data(message.cache_change.data_value.as_ref())
+                    get_instance_handle_from_key_only_change(&message.cache_change)
                 {
-                    self.domain_participant
-                        .remove_discovered_reader(&discovered_reader_handle);
-                    for publisher in self.domain_participant.publisher_list_mut() {
-                        for data_writer in publisher.data_writer_list() {
-                            message
-                                .participant_address
-                                .send_actor_mail(discovery_service::RemoveDiscoveredReader {
-                                    subscription_handle: discovered_reader_handle,
-                                    publisher_handle: publisher.instance_handle(),
-                                    data_writer_handle: data_writer.instance_handle(),
-                                })
-                                .ok();
+                    let reader_guid_prefix =
+                        Guid::from(<[u8; 16]>::from(discovered_reader_handle)).prefix();
+                    if discovery_service::is_relayed_endpoint_ignored(
+                        self,
+                        sender_guid_prefix,
+                        reader_guid_prefix,
+                    ) {
+                        return;
+                    }
+                    if discovery_service::is_endpoint_relayed(
+                        self,
+                        sender_guid_prefix,
+                        reader_guid_prefix,
+                    ) {
+                        discovery_service::relay_removed_instance(
+                            self,
+                            DCPS_SUBSCRIPTION,
+                            discovered_reader_handle,
+                        )
+                        .ok();
+                    }
+                    if let Some(discovered_reader_data) = self
+                        .domain_participant
+                        .remove_discovered_reader(&discovered_reader_handle)
+                    {
+                        for publisher in self.domain_participant.publisher_list_mut() {
+                            for data_writer in publisher.data_writer_list().filter(|dw| {
+                                dw.topic_name()
+                                    == discovered_reader_data.dds_subscription_data.topic_name()
+                            }) {
+                                message
+                                    .participant_address
+                                    .send_actor_mail(discovery_service::RemoveDiscoveredReader {
+                                        subscription_handle: discovered_reader_handle,
+                                        publisher_handle: publisher.instance_handle(),
+                                        data_writer_handle: data_writer.instance_handle(),
+                                    })
+                                    .ok();
+                            }
                         }
                     }
                 }
             }
-            ChangeKind::AliveFiltered | ChangeKind::NotAliveUnregistered => (),
+            ChangeKind::AliveFiltered => (),
         }
 
         let reception_timestamp = self.domain_participant.get_current_time();
@@ -659,28 +1073,6 @@ impl MailHandler<RemoveBuiltinSubscriptionsDetectorCacheChange> for DomainPartic
     }
 }
 
-pub struct AreAllChangesAcknowledged {
-    pub publisher_handle: InstanceHandle,
-    pub data_writer_handle: InstanceHandle,
-}
-impl Mail for AreAllChangesAcknowledged {
-    type Result = DdsResult<bool>;
-}
-impl MailHandler<AreAllChangesAcknowledged> for DomainParticipantActor {
-    fn handle(
-        &mut self,
-        message: AreAllChangesAcknowledged,
-    ) -> <AreAllChangesAcknowledged as Mail>::Result {
-        Ok(self
-            .domain_participant
-            .get_publisher(message.publisher_handle)
-            .ok_or(DdsError::AlreadyDeleted)?
-            .get_data_writer(message.data_writer_handle)
-            .ok_or(DdsError::AlreadyDeleted)?
-            .are_all_changes_acknowledged())
-    }
-}
-
 pub struct IsHistoricalDataReceived {
     pub subscriber_handle: InstanceHandle,
     pub data_reader_handle: InstanceHandle,
@@ -739,3 +1131,81 @@ impl MailHandler<RemoveWriterChange> for DomainParticipantActor {
         }
     }
 }
+
+pub struct RemoveExpiredReaderChanges {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+}
+impl Mail for RemoveExpiredReaderChanges {
+    type Result = ();
+}
+impl MailHandler<RemoveExpiredReaderChanges> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: RemoveExpiredReaderChanges,
+    ) -> <RemoveExpiredReaderChanges as Mail>::Result {
+        let now = self.domain_participant.get_current_time();
+        if let Some(s) = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+        {
+            if let Some(dr) = s.get_mut_data_reader(message.data_reader_handle) {
+                dr.remove_expired_samples(now);
+            }
+        }
+    }
+}
+
+pub struct PurgeReaderInstance {
+    pub subscriber_handle: InstanceHandle,
+    pub data_reader_handle: InstanceHandle,
+    pub instance_handle: InstanceHandle,
+}
+impl Mail for PurgeReaderInstance {
+    type Result = ();
+}
+impl MailHandler<PurgeReaderInstance> for DomainParticipantActor {
+    fn handle(&mut self, message: PurgeReaderInstance) -> <PurgeReaderInstance as Mail>::Result {
+        if let Some(dr) = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .and_then(|s| s.get_mut_data_reader(message.data_reader_handle))
+        {
+            dr.purge_instance(&message.instance_handle);
+        }
+    }
+}
+
+pub struct RemoveDurableInstance {
+    pub durability_kind: DurabilityQosPolicyKind,
+    pub topic_name: String,
+    pub instance_handle: InstanceHandle,
+    pub timestamp: Time,
+}
+impl Mail for RemoveDurableInstance {
+    type Result = ();
+}
+impl MailHandler<RemoveDurableInstance> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: RemoveDurableInstance,
+    ) -> <RemoveDurableInstance as Mail>::Result {
+        self.durability_service.remove_instance(
+            message.durability_kind,
+            &message.topic_name,
+            message.instance_handle,
+            message.timestamp,
+        );
+    }
+}
+
+// The payload of a disposed or unregistered change only carries the serialized key and some
+// vendors omit it altogether, so the key hash is used to identify the instance when available.
+fn get_instance_handle_from_key_only_change(
+    cache_change: &CacheChange,
+) -> DdsResult<InstanceHandle> {
+    match cache_change.instance_handle {
+        Some(key_hash) => Ok(InstanceHandle::new(key_hash)),
+        None => InstanceHandle::deserialize_data(cache_change.data_value.as_ref()),
+    }
+}
@@ -4,6 +4,10 @@ pub mod discovery_service;
 pub mod domain_participant_service;
 pub mod event_service;
 pub mod message_service;
+pub mod participant_message_service;
 pub mod publisher_service;
+#[cfg(feature = "security")]
+pub mod security_service;
 pub mod subscriber_service;
 pub mod topic_service;
+pub mod type_lookup_service;
@@ -0,0 +1,115 @@
+use crate::{
+    implementation::{
+        data_representation_builtin_endpoints::{
+            participant_message_data::{
+                ParticipantMessageData, PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE,
+                PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE,
+            },
+            spdp_discovered_participant_data::SpdpDiscoveredParticipantData,
+        },
+        domain_participant_backend::domain_participant_actor::DomainParticipantActor,
+    },
+    infrastructure::qos_policy::LivelinessQosPolicyKind,
+    runtime::actor::{ActorAddress, Mail, MailHandler},
+    transport::{history_cache::CacheChange, types::GuidPrefix},
+};
+
+use super::message_service;
+
+pub struct AddParticipantMessage {
+    pub cache_change: CacheChange,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for AddParticipantMessage {
+    type Result = ();
+}
+impl MailHandler<AddParticipantMessage> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: AddParticipantMessage,
+    ) -> <AddParticipantMessage as Mail>::Result {
+        let Ok(participant_message_data) =
+            ParticipantMessageData::deserialize_data(message.cache_change.data_value.as_ref())
+        else {
+            tracing::warn!("Invalid participant message ignored");
+            return;
+        };
+        // Messages of kinds specific to other vendors are ignored
+        let liveliness_kind = match participant_message_data.kind {
+            PARTICIPANT_MESSAGE_DATA_KIND_AUTOMATIC_LIVELINESS_UPDATE => {
+                LivelinessQosPolicyKind::Automatic
+            }
+            PARTICIPANT_MESSAGE_DATA_KIND_MANUAL_LIVELINESS_UPDATE => {
+                LivelinessQosPolicyKind::ManualByParticipant
+            }
+            _ => return,
+        };
+        assert_remote_participant_liveliness(
+            self,
+            participant_message_data.participant_guid_prefix,
+            liveliness_kind,
+            message.participant_address,
+        );
+    }
+}
+
+// Asserts the liveliness of the publications of the remote participant with the given kind
+// matched by any of the local readers
+pub fn assert_remote_participant_liveliness(
+    domain_participant_actor: &mut DomainParticipantActor,
+    guid_prefix: GuidPrefix,
+    liveliness_kind: LivelinessQosPolicyKind,
+    participant_address: ActorAddress<DomainParticipantActor>,
+) {
+    let mut publication_list = Vec::new();
+    for subscriber in domain_participant_actor
+        .domain_participant
+        .subscriber_list()
+    {
+        for data_reader in subscriber.data_reader_list() {
+            for publication_handle in data_reader.get_matched_publications() {
+                if data_reader
+                    .get_matched_publication_data(&publication_handle)
+                    .is_some_and(|p| {
+                        p.key().value[..12] == guid_prefix && p.liveliness().kind == liveliness_kind
+                    })
+                {
+                    publication_list.push((
+                        subscriber.instance_handle(),
+                        data_reader.instance_handle(),
+                        publication_handle,
+                    ));
+                }
+            }
+        }
+    }
+
+    for (subscriber_handle, data_reader_handle, publication_handle) in publication_list {
+        message_service::assert_publication_liveliness(
+            domain_participant_actor,
+            subscriber_handle,
+            data_reader_handle,
+            publication_handle,
+            participant_address.clone(),
+        )
+        .ok();
+    }
+}
+
+pub fn add_matched_participant(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+) {
+    domain_participant_actor
+        .participant_message
+        .add_matched_participant(&discovered_participant_data.participant_proxy);
+}
+
+pub fn remove_matched_participant(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+) {
+    domain_participant_actor
+        .participant_message
+        .remove_matched_participant(discovered_participant_data.participant_proxy.guid_prefix);
+}
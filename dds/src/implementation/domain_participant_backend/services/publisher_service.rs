@@ -1,3 +1,5 @@
+use std::sync::{atomic::AtomicU16, Arc};
+
 use crate::{
     dds_async::publisher_listener::PublisherListenerAsync,
     implementation::{
@@ -38,7 +40,11 @@ pub struct CreateDataWriter {
     pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for CreateDataWriter {
-    type Result = DdsResult<(InstanceHandle, ActorAddress<StatusConditionActor>)>;
+    type Result = DdsResult<(
+        InstanceHandle,
+        ActorAddress<StatusConditionActor>,
+        Arc<AtomicU16>,
+    )>;
 }
 impl MailHandler<CreateDataWriter> for DomainParticipantActor {
     fn handle(&mut self, message: CreateDataWriter) -> <CreateDataWriter as Mail>::Result {
@@ -81,9 +87,14 @@ impl MailHandler<CreateDataWriter> for DomainParticipantActor {
             ReliabilityQosPolicyKind::BestEffort => ReliabilityKind::BestEffort,
             ReliabilityQosPolicyKind::Reliable => ReliabilityKind::Reliable,
         };
-        let transport_writer =
+        #[cfg(feature = "security")]
+        if let Some(security) = &self.security {
+            security.register_local_endpoint(entity_id, &message.topic_name);
+        }
+        let mut transport_writer =
             self.transport
                 .create_stateful_writer(entity_id, reliablity_kind, self.fragment_size);
+        transport_writer.set_transport_priority(qos.transport_priority.value);
 
         let topic_name = message.topic_name;
 
@@ -93,8 +104,9 @@ impl MailHandler<CreateDataWriter> for DomainParticipantActor {
         );
         let writer_status_condition_address = status_condition.address();
         let listener = message.a_listener.map(|l| {
-            Actor::spawn(
+            Actor::spawn_with_mailbox(
                 DataWriterListenerActor::new(l),
+                self.listener_mailbox,
                 &self.listener_executor.handle(),
             )
         });
@@ -110,6 +122,7 @@ impl MailHandler<CreateDataWriter> for DomainParticipantActor {
             qos,
         );
         let data_writer_handle = data_writer.instance_handle();
+        let data_representation = data_writer.data_representation();
 
         publisher.insert_data_writer(data_writer);
 
@@ -124,7 +137,11 @@ impl MailHandler<CreateDataWriter> for DomainParticipantActor {
                 .ok();
         }
 
-        Ok((data_writer_handle, writer_status_condition_address))
+        Ok((
+            data_writer_handle,
+            writer_status_condition_address,
+            data_representation,
+        ))
     }
 }
 
@@ -143,9 +160,11 @@ impl MailHandler<DeleteDataWriter> for DomainParticipantActor {
             .get_mut_publisher(message.publisher_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
 
-        let data_writer = publisher
+        let mut data_writer = publisher
             .remove_data_writer(message.datawriter_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        data_writer.unregister_all_instances(self.domain_participant.get_current_time());
+        data_writer.cancel_timer_tasks();
         message
             .participant_address
             .send_actor_mail(discovery_service::AnnounceDeletedDataWriter { data_writer })
@@ -201,6 +220,7 @@ impl MailHandler<GetDefaultDataWriterQos> for DomainParticipantActor {
 pub struct SetQos {
     pub publisher_handle: InstanceHandle,
     pub qos: QosKind<PublisherQos>,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for SetQos {
     type Result = DdsResult<()>;
@@ -216,7 +236,31 @@ impl MailHandler<SetQos> for DomainParticipantActor {
             .get_mut_publisher(message.publisher_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
 
-        publisher.set_qos(qos)
+        publisher.set_qos(qos)?;
+        // The partition and the group data of the publisher are announced with each of its writers
+        let data_writer_list: Vec<_> = publisher
+            .data_writer_list()
+            .filter(|dw| dw.enabled())
+            .map(|dw| (dw.instance_handle(), dw.topic_name().to_owned()))
+            .collect();
+        for (data_writer_handle, topic_name) in data_writer_list {
+            message
+                .participant_address
+                .send_actor_mail(discovery_service::AnnounceDataWriter {
+                    publisher_handle: message.publisher_handle,
+                    data_writer_handle,
+                })
+                .ok();
+            discovery_service::rematch_data_writer(
+                self,
+                message.publisher_handle,
+                data_writer_handle,
+                &topic_name,
+                &message.participant_address,
+            );
+        }
+
+        Ok(())
     }
 }
 
@@ -252,8 +296,9 @@ impl MailHandler<SetListener> for DomainParticipantActor {
             .ok_or(DdsError::AlreadyDeleted)?
             .set_listener(
                 message.a_listener.map(|l| {
-                    Actor::spawn(
+                    Actor::spawn_with_mailbox(
                         PublisherListenerActor::new(l),
+                        self.listener_mailbox,
                         &self.listener_executor.handle(),
                     )
                 }),
@@ -264,6 +309,47 @@ impl MailHandler<SetListener> for DomainParticipantActor {
     }
 }
 
+pub struct Enable {
+    pub publisher_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for Enable {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<Enable> for DomainParticipantActor {
+    fn handle(&mut self, message: Enable) -> <Enable as Mail>::Result {
+        if !self.domain_participant.enabled() {
+            return Err(DdsError::PreconditionNotMet(
+                "Publisher can only be enabled once its parent participant is enabled".to_string(),
+            ));
+        }
+        let publisher = self
+            .domain_participant
+            .get_mut_publisher(message.publisher_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if !publisher.enabled() {
+            publisher.enable();
+            if publisher.qos().entity_factory.autoenable_created_entities {
+                let data_writer_handle_list: Vec<_> = publisher
+                    .data_writer_list()
+                    .map(|dw| dw.instance_handle())
+                    .collect();
+                for data_writer_handle in data_writer_handle_list {
+                    MailHandler::handle(
+                        self,
+                        data_writer_service::Enable {
+                            publisher_handle: message.publisher_handle,
+                            data_writer_handle,
+                            participant_address: message.participant_address.clone(),
+                        },
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 fn get_topic_kind(type_support: &dyn DynamicType) -> TopicKind {
     for index in 0..type_support.get_member_count() {
         if let Ok(m) = type_support.get_member_by_index(index) {
@@ -0,0 +1,351 @@
+use super::discovery_service;
+use crate::{
+    implementation::{
+        data_representation_builtin_endpoints::{
+            discovered_reader_data::DiscoveredReaderData,
+            discovered_writer_data::DiscoveredWriterData,
+            spdp_discovered_participant_data::SpdpDiscoveredParticipantData,
+        },
+        domain_participant_backend::domain_participant_actor::DomainParticipantActor,
+    },
+    infrastructure::instance::InstanceHandle,
+    runtime::actor::{Mail, MailHandler},
+    security::{
+        authentication::RemoteIdentityStatus,
+        types::{
+            DataHolder, ParticipantGenericMessage, GMCLASSID_SECURITY_AUTH_HANDSHAKE,
+            GMCLASSID_SECURITY_PARTICIPANT_CRYPTO_TOKENS,
+        },
+    },
+    topic_definition::type_support::deserialize_rtps_encapsulated_data,
+    transport::{
+        history_cache::CacheChange,
+        types::{Guid, ENTITYID_PARTICIPANT},
+    },
+};
+
+pub struct AddParticipantStatelessMessage {
+    pub cache_change: CacheChange,
+}
+impl Mail for AddParticipantStatelessMessage {
+    type Result = ();
+}
+impl MailHandler<AddParticipantStatelessMessage> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: AddParticipantStatelessMessage,
+    ) -> <AddParticipantStatelessMessage as Mail>::Result {
+        let Ok(stateless_message) = deserialize_rtps_encapsulated_data::<ParticipantGenericMessage>(
+            &mut message.cache_change.data_value.as_ref(),
+        ) else {
+            return;
+        };
+        if stateless_message.destination_participant_guid != self.transport.guid() {
+            return;
+        }
+        let Some(message_data) = stateless_message.message_data.first() else {
+            return;
+        };
+        let remote_participant_guid = Guid::new(
+            message.cache_change.writer_guid.prefix(),
+            ENTITYID_PARTICIPANT,
+        );
+        match stateless_message.message_class_id.as_str() {
+            GMCLASSID_SECURITY_AUTH_HANDSHAKE => {
+                process_handshake_message(self, remote_participant_guid, message_data)
+            }
+            GMCLASSID_SECURITY_PARTICIPANT_CRYPTO_TOKENS => {
+                process_participant_crypto_tokens(self, remote_participant_guid, message_data)
+            }
+            _ => (),
+        }
+    }
+}
+
+fn process_handshake_message(
+    domain_participant_actor: &mut DomainParticipantActor,
+    remote_participant_guid: Guid,
+    handshake_message: &DataHolder,
+) {
+    let Some(security) = &mut domain_participant_actor.security else {
+        return;
+    };
+    match security
+        .authentication
+        .process_handshake(remote_participant_guid, handshake_message)
+    {
+        Ok(handshake_result) => {
+            if let Some(handshake_message) = handshake_result.message {
+                security
+                    .send_stateless_message(
+                        remote_participant_guid,
+                        GMCLASSID_SECURITY_AUTH_HANDSHAKE,
+                        handshake_message,
+                    )
+                    .ok();
+            }
+            if handshake_result.completed {
+                // The permissions received in the handshake are validated before
+                // accepting the participant
+                if let (Some(access_control), Some(remote_identity)) = (
+                    &mut security.access_control,
+                    security
+                        .authentication
+                        .remote_identity(&remote_participant_guid.prefix()),
+                ) {
+                    if let Err(e) = access_control.validate_remote_permissions(
+                        remote_participant_guid.prefix(),
+                        &remote_identity.subject_name,
+                        &remote_identity.permissions_document,
+                    ) {
+                        tracing::warn!(
+                            guid_prefix = ?remote_participant_guid.prefix(),
+                            "Permissions of the participant rejected: {:?}", e
+                        );
+                    }
+                }
+                security
+                    .send_participant_crypto_tokens(remote_participant_guid.prefix())
+                    .ok();
+                // The participant discovered while the handshake was ongoing is now added
+                if let Some(discovered_participant_data) = security
+                    .pending_participant_list
+                    .remove(&remote_participant_guid.prefix())
+                {
+                    MailHandler::handle(
+                        domain_participant_actor,
+                        discovery_service::AddDiscoveredParticipant {
+                            discovered_participant_data,
+                            is_relayed: false,
+                        },
+                    );
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!(
+                guid_prefix = ?remote_participant_guid.prefix(),
+                "Handshake message discarded: {:?}", e
+            );
+        }
+    }
+}
+
+// The crypto tokens are answered until the remote participant confirms having received the
+// local key material
+fn process_participant_crypto_tokens(
+    domain_participant_actor: &mut DomainParticipantActor,
+    remote_participant_guid: Guid,
+    crypto_tokens: &DataHolder,
+) {
+    let Some(security) = &mut domain_participant_actor.security else {
+        return;
+    };
+    let remote_guid_prefix = remote_participant_guid.prefix();
+    let (Some(cryptography), Some(shared_secret)) = (
+        &security.cryptography,
+        security.authentication.shared_secret(&remote_guid_prefix),
+    ) else {
+        return;
+    };
+    match cryptography.set_remote_participant_crypto_tokens(
+        remote_guid_prefix,
+        shared_secret,
+        crypto_tokens,
+    ) {
+        Ok(true) => {
+            security
+                .send_participant_crypto_tokens(remote_guid_prefix)
+                .ok();
+        }
+        Ok(false) => (),
+        Err(e) => {
+            tracing::warn!(
+                guid_prefix = ?remote_guid_prefix,
+                "Crypto tokens discarded: {:?}", e
+            );
+        }
+    }
+}
+
+// Returns whether the discovered participant is authenticated. The handshake with the
+// participants which are not authenticated yet is started or continued and their data is kept
+// until the handshake is completed.
+pub fn authenticate_discovered_participant(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+) -> bool {
+    let participant_proxy = &discovered_participant_data.participant_proxy;
+    if participant_proxy.guid_prefix == domain_participant_actor.transport.guid().prefix() {
+        return true;
+    }
+    let Some(security) = &mut domain_participant_actor.security else {
+        return true;
+    };
+    // Participants with different protection of the builtin endpoints cannot communicate
+    if discovered_participant_data.identity_token.is_some()
+        && discovered_participant_data.participant_security_info
+            != security.participant_security_info()
+    {
+        tracing::warn!(
+            guid_prefix = ?participant_proxy.guid_prefix,
+            "Discovered participant ignored because its security attributes do not match"
+        );
+        return false;
+    }
+    match security.authentication.validate_remote_identity(
+        participant_proxy.guid_prefix,
+        discovered_participant_data.identity_token.as_ref(),
+    ) {
+        RemoteIdentityStatus::Authenticated => {
+            // The crypto tokens are sent again while the key material of the remote participant
+            // is missing since the stateless messages can be lost
+            if security.cryptography.as_ref().is_some_and(|c| {
+                !c.has_remote_participant_key_material(&participant_proxy.guid_prefix)
+            }) {
+                security
+                    .send_participant_crypto_tokens(participant_proxy.guid_prefix)
+                    .ok();
+            }
+            security
+                .access_control
+                .as_ref()
+                .is_none_or(|a| a.is_remote_participant_allowed(&participant_proxy.guid_prefix))
+        }
+        RemoteIdentityStatus::PendingHandshake(handshake_message) => {
+            if !security
+                .pending_participant_list
+                .contains_key(&participant_proxy.guid_prefix)
+            {
+                security.add_remote_participant_locators(
+                    &participant_proxy.metatraffic_unicast_locator_list,
+                );
+            }
+            security.pending_participant_list.insert(
+                participant_proxy.guid_prefix,
+                discovered_participant_data.clone(),
+            );
+            if let Some(handshake_message) = handshake_message {
+                security
+                    .send_stateless_message(
+                        Guid::new(participant_proxy.guid_prefix, ENTITYID_PARTICIPANT),
+                        GMCLASSID_SECURITY_AUTH_HANDSHAKE,
+                        handshake_message,
+                    )
+                    .ok();
+            }
+            false
+        }
+        RemoteIdentityStatus::Rejected => {
+            if let Some(access_control) = &mut security.access_control {
+                if access_control.allows_unauthenticated_participants() {
+                    access_control.add_unauthenticated_participant(participant_proxy.guid_prefix);
+                    return true;
+                }
+            }
+            tracing::warn!(
+                guid_prefix = ?participant_proxy.guid_prefix,
+                "Discovered participant ignored because it could not be authenticated"
+            );
+            false
+        }
+    }
+}
+
+pub fn remove_discovered_participant(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant: &InstanceHandle,
+) {
+    let discovered_participant_data = domain_participant_actor
+        .domain_participant
+        .get_discovered_participant_data(discovered_participant)
+        .cloned();
+    let Some(security) = &mut domain_participant_actor.security else {
+        return;
+    };
+    let guid_prefix = Guid::from(<[u8; 16]>::from(*discovered_participant)).prefix();
+    if let Some(participant_data) = security
+        .pending_participant_list
+        .remove(&guid_prefix)
+        .or(discovered_participant_data)
+    {
+        security.remove_remote_participant_locators(
+            &participant_data
+                .participant_proxy
+                .metatraffic_unicast_locator_list,
+        );
+    }
+    security.authentication.remove_remote_identity(&guid_prefix);
+    if let Some(access_control) = &mut security.access_control {
+        access_control.remove_remote_participant(&guid_prefix);
+    }
+    if let Some(cryptography) = &security.cryptography {
+        cryptography.remove_remote_participant(&guid_prefix);
+    }
+}
+
+// Returns whether the permissions of the remote participant allow it to publish in the topic
+// of the discovered writer
+pub fn check_remote_datawriter(
+    domain_participant_actor: &DomainParticipantActor,
+    discovered_writer_data: &DiscoveredWriterData,
+) -> bool {
+    let guid_prefix = discovered_writer_data
+        .writer_proxy
+        .remote_writer_guid
+        .prefix();
+    let publication_data = &discovered_writer_data.dds_publication_data;
+    let is_allowed = guid_prefix == domain_participant_actor.transport.guid().prefix()
+        || domain_participant_actor
+            .security
+            .as_ref()
+            .and_then(|s| s.access_control.as_ref())
+            .is_none_or(|a| {
+                a.check_remote_datawriter(
+                    &guid_prefix,
+                    publication_data.topic_name(),
+                    &publication_data.partition.name,
+                )
+            });
+    if !is_allowed {
+        tracing::warn!(
+            ?guid_prefix,
+            topic_name = publication_data.topic_name(),
+            "Discovered writer ignored because it is not allowed to publish"
+        );
+    }
+    is_allowed
+}
+
+// Returns whether the permissions of the remote participant allow it to subscribe to the topic
+// of the discovered reader
+pub fn check_remote_datareader(
+    domain_participant_actor: &DomainParticipantActor,
+    discovered_reader_data: &DiscoveredReaderData,
+) -> bool {
+    let guid_prefix = discovered_reader_data
+        .reader_proxy
+        .remote_reader_guid
+        .prefix();
+    let subscription_data = &discovered_reader_data.dds_subscription_data;
+    let is_allowed = guid_prefix == domain_participant_actor.transport.guid().prefix()
+        || domain_participant_actor
+            .security
+            .as_ref()
+            .and_then(|s| s.access_control.as_ref())
+            .is_none_or(|a| {
+                a.check_remote_datareader(
+                    &guid_prefix,
+                    subscription_data.topic_name(),
+                    &subscription_data.partition.name,
+                )
+            });
+    if !is_allowed {
+        tracing::warn!(
+            ?guid_prefix,
+            topic_name = subscription_data.topic_name(),
+            "Discovered reader ignored because it is not allowed to subscribe"
+        );
+    }
+    is_allowed
+}
@@ -1,30 +1,38 @@
+use core::cmp::Ordering;
+
 use crate::{
     dds_async::subscriber_listener::SubscriberListenerAsync,
     implementation::{
         any_data_reader_listener::AnyDataReaderListener,
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor,
-            entities::data_reader::{DataReaderEntity, TransportReaderKind},
-            services::{data_reader_service, discovery_service, message_service},
+            entities::{
+                data_reader::{DataReaderEntity, TransportReaderKind},
+                domain_participant::DomainParticipantEntity,
+                subscriber::SubscriberEntity,
+            },
+            services::{data_reader_service, discovery_service, event_service, message_service},
         },
         listeners::{
-            data_reader_listener::DataReaderListenerActor,
+            data_reader_listener::{self, DataReaderListenerActor},
             subscriber_listener::SubscriberListenerActor,
         },
-        status_condition::status_condition_actor::StatusConditionActor,
+        status_condition::status_condition_actor::{self, StatusConditionActor},
     },
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind, SubscriberQos},
-        qos_policy::ReliabilityQosPolicyKind,
-        status::StatusKind,
+        qos_policy::{PresentationQosPolicyAccessScopeKind, ReliabilityQosPolicyKind},
+        status::{SampleLostStatus, StatusKind},
+        time::Time,
     },
     runtime::actor::{Actor, ActorAddress, Mail, MailHandler},
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
     transport::{
         history_cache::{CacheChange, HistoryCache},
         types::{
-            EntityId, ReliabilityKind, TopicKind, USER_DEFINED_READER_NO_KEY,
+            EntityId, Guid, ReliabilityKind, TopicKind, USER_DEFINED_READER_NO_KEY,
             USER_DEFINED_READER_WITH_KEY,
         },
     },
@@ -65,6 +73,28 @@ impl MailHandler<CreateDataReader> for DomainParticipantActor {
             fn remove_change(&mut self, _sequence_number: i64) {
                 todo!()
             }
+
+            fn assert_writer_liveliness(&mut self, writer_guid: Guid) {
+                self.domain_participant_address
+                    .send_actor_mail(message_service::AssertWriterLiveliness {
+                        writer_guid,
+                        subscriber_handle: self.subscriber_handle,
+                        data_reader_handle: self.data_reader_handle,
+                        participant_address: self.domain_participant_address.clone(),
+                    })
+                    .ok();
+            }
+
+            fn report_lost_changes(&mut self, _writer_guid: Guid, lost_changes_count: i64) {
+                self.domain_participant_address
+                    .send_actor_mail(event_service::SampleLost {
+                        subscriber_handle: self.subscriber_handle,
+                        data_reader_handle: self.data_reader_handle,
+                        lost_changes_count,
+                        participant_address: self.domain_participant_address.clone(),
+                    })
+                    .ok();
+            }
         }
 
         let topic = self
@@ -107,6 +137,10 @@ impl MailHandler<CreateDataReader> for DomainParticipantActor {
             ReliabilityQosPolicyKind::BestEffort => ReliabilityKind::BestEffort,
             ReliabilityQosPolicyKind::Reliable => ReliabilityKind::Reliable,
         };
+        #[cfg(feature = "security")]
+        if let Some(security) = &self.security {
+            security.register_local_endpoint(entity_id, &topic_name);
+        }
         let transport_reader =
             TransportReaderKind::Stateful(self.transport.create_stateful_reader(
                 entity_id,
@@ -124,8 +158,9 @@ impl MailHandler<CreateDataReader> for DomainParticipantActor {
             &self.listener_executor.handle(),
         );
         let listener = message.a_listener.map(|l| {
-            Actor::spawn(
+            Actor::spawn_with_mailbox(
                 DataReaderListenerActor::new(l),
+                self.listener_mailbox,
                 &self.listener_executor.handle(),
             )
         });
@@ -173,9 +208,11 @@ impl MailHandler<DeleteDataReader> for DomainParticipantActor {
             .domain_participant
             .get_mut_subscriber(message.subscriber_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
-        let data_reader = subscriber
+        let mut data_reader = subscriber
             .remove_data_reader(message.datareader_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
+        data_reader.cancel_timer_tasks();
+        data_reader.cancel_communication_state_waiters();
         message
             .participant_address
             .send_actor_mail(discovery_service::AnnounceDeletedDataReader { data_reader })
@@ -223,6 +260,188 @@ impl MailHandler<LookupDataReader> for DomainParticipantActor {
     }
 }
 
+// Built-in subscriber is identified by the handle of the participant itself
+fn get_mut_subscriber_or_builtin(
+    domain_participant: &mut DomainParticipantEntity,
+    subscriber_handle: InstanceHandle,
+) -> Option<&mut SubscriberEntity> {
+    if domain_participant.instance_handle() == subscriber_handle {
+        Some(domain_participant.builtin_subscriber_mut())
+    } else {
+        domain_participant.get_mut_subscriber(subscriber_handle)
+    }
+}
+
+pub struct BeginAccess {
+    pub subscriber_handle: InstanceHandle,
+}
+impl Mail for BeginAccess {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<BeginAccess> for DomainParticipantActor {
+    fn handle(&mut self, message: BeginAccess) -> <BeginAccess as Mail>::Result {
+        let subscriber =
+            get_mut_subscriber_or_builtin(&mut self.domain_participant, message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?;
+        if !subscriber.enabled() {
+            return Err(DdsError::NotEnabled);
+        }
+        subscriber.begin_access();
+        Ok(())
+    }
+}
+
+pub struct EndAccess {
+    pub subscriber_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for EndAccess {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<EndAccess> for DomainParticipantActor {
+    fn handle(&mut self, message: EndAccess) -> <EndAccess as Mail>::Result {
+        let subscriber =
+            get_mut_subscriber_or_builtin(&mut self.domain_participant, message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?;
+        for (data_reader_handle, cache_change) in subscriber.end_access()? {
+            message
+                .participant_address
+                .send_actor_mail(message_service::AddCacheChange {
+                    participant_address: message.participant_address.clone(),
+                    cache_change,
+                    subscriber_handle: message.subscriber_handle,
+                    data_reader_handle,
+                })
+                .ok();
+        }
+        Ok(())
+    }
+}
+
+pub struct GetDataReaders {
+    pub subscriber_handle: InstanceHandle,
+    pub sample_states: Vec<SampleStateKind>,
+    pub view_states: Vec<ViewStateKind>,
+    pub instance_states: Vec<InstanceStateKind>,
+}
+impl Mail for GetDataReaders {
+    type Result = DdsResult<Vec<(InstanceHandle, ActorAddress<StatusConditionActor>, String)>>;
+}
+impl MailHandler<GetDataReaders> for DomainParticipantActor {
+    fn handle(&mut self, message: GetDataReaders) -> <GetDataReaders as Mail>::Result {
+        let subscriber =
+            get_mut_subscriber_or_builtin(&mut self.domain_participant, message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?;
+        if !subscriber.enabled() {
+            return Err(DdsError::NotEnabled);
+        }
+        let reader_info = |dr: &DataReaderEntity| {
+            (
+                dr.instance_handle(),
+                dr.status_condition().address(),
+                dr.topic_name().to_owned(),
+            )
+        };
+
+        let presentation = &subscriber.qos().presentation;
+        if presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group
+            && presentation.ordered_access
+        {
+            if !subscriber.is_access_in_progress() {
+                return Err(DdsError::PreconditionNotMet(
+                    "get_datareaders with ordered group access must be called between begin_access and end_access".to_string(),
+                ));
+            }
+            // Each reader is listed once per sample so that reading one sample from each reader
+            // in the order of the list returns the samples in the order in which they were written
+            let mut reader_sample_list: Vec<(&DataReaderEntity, std::vec::IntoIter<Time>)> =
+                subscriber
+                    .data_reader_list()
+                    .map(|dr| {
+                        (
+                            dr,
+                            dr.sample_order_timestamp_list(
+                                &message.sample_states,
+                                &message.view_states,
+                                &message.instance_states,
+                            )
+                            .into_iter(),
+                        )
+                    })
+                    .collect();
+            let mut next_sample_list: Vec<Option<Time>> = reader_sample_list
+                .iter_mut()
+                .map(|(_, sample_list)| sample_list.next())
+                .collect();
+            let mut data_reader_list = Vec::new();
+            while let Some(index) = next_sample_list
+                .iter()
+                .enumerate()
+                .filter_map(|(index, timestamp)| timestamp.map(|t| (index, t)))
+                .min_by(|(_, t1), (_, t2)| t1.partial_cmp(t2).unwrap_or(Ordering::Equal))
+                .map(|(index, _)| index)
+            {
+                data_reader_list.push(reader_info(reader_sample_list[index].0));
+                next_sample_list[index] = reader_sample_list[index].1.next();
+            }
+            Ok(data_reader_list)
+        } else {
+            Ok(subscriber
+                .data_reader_list()
+                .filter(|dr| {
+                    dr.has_sample_in_states(
+                        &message.sample_states,
+                        &message.view_states,
+                        &message.instance_states,
+                    )
+                })
+                .map(reader_info)
+                .collect())
+        }
+    }
+}
+
+pub struct NotifyDataReaders {
+    pub subscriber_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for NotifyDataReaders {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<NotifyDataReaders> for DomainParticipantActor {
+    fn handle(&mut self, message: NotifyDataReaders) -> <NotifyDataReaders as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        if !subscriber.enabled() {
+            return Err(DdsError::NotEnabled);
+        }
+        let data_reader_handle_list: Vec<InstanceHandle> = subscriber
+            .data_reader_list()
+            .filter(|dr| dr.listener().is_some() && dr.is_data_available_status_changed())
+            .map(|dr| dr.instance_handle())
+            .collect();
+        for data_reader_handle in data_reader_handle_list {
+            let the_reader = self.get_data_reader_async(
+                message.participant_address.clone(),
+                message.subscriber_handle,
+                data_reader_handle,
+            )?;
+            if let Some(l) = self
+                .domain_participant
+                .get_subscriber(message.subscriber_handle)
+                .and_then(|s| s.get_data_reader(data_reader_handle))
+                .and_then(|dr| dr.listener())
+            {
+                l.send_actor_mail(data_reader_listener::TriggerDataAvailable { the_reader })
+                    .ok();
+            }
+        }
+        Ok(())
+    }
+}
+
 pub struct SetDefaultDataReaderQos {
     pub subscriber_handle: InstanceHandle,
     pub qos: QosKind<DataReaderQos>,
@@ -270,6 +489,7 @@ impl MailHandler<GetDefaultDataReaderQos> for DomainParticipantActor {
 pub struct SetQos {
     pub subscriber_handle: InstanceHandle,
     pub qos: QosKind<SubscriberQos>,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for SetQos {
     type Result = DdsResult<()>;
@@ -285,7 +505,31 @@ impl MailHandler<SetQos> for DomainParticipantActor {
             .get_mut_subscriber(message.subscriber_handle)
             .ok_or(DdsError::AlreadyDeleted)?;
 
-        subscriber.set_qos(qos)
+        subscriber.set_qos(qos)?;
+        // The partition and the group data of the subscriber are announced with each of its readers
+        let data_reader_list: Vec<_> = subscriber
+            .data_reader_list()
+            .filter(|dr| dr.enabled())
+            .map(|dr| (dr.instance_handle(), dr.topic_name().to_owned()))
+            .collect();
+        for (data_reader_handle, topic_name) in data_reader_list {
+            message
+                .participant_address
+                .send_actor_mail(discovery_service::AnnounceDataReader {
+                    subscriber_handle: message.subscriber_handle,
+                    data_reader_handle,
+                })
+                .ok();
+            discovery_service::rematch_data_reader(
+                self,
+                message.subscriber_handle,
+                data_reader_handle,
+                &topic_name,
+                &message.participant_address,
+            );
+        }
+
+        Ok(())
     }
 }
 
@@ -306,6 +550,29 @@ impl MailHandler<GetSubscriberQos> for DomainParticipantActor {
     }
 }
 
+pub struct GetSampleLostStatus {
+    pub subscriber_handle: InstanceHandle,
+}
+impl Mail for GetSampleLostStatus {
+    type Result = DdsResult<SampleLostStatus>;
+}
+impl MailHandler<GetSampleLostStatus> for DomainParticipantActor {
+    fn handle(&mut self, message: GetSampleLostStatus) -> <GetSampleLostStatus as Mail>::Result {
+        let subscriber = self
+            .domain_participant
+            .get_mut_subscriber(message.subscriber_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let status = subscriber.get_sample_lost_status();
+        subscriber
+            .status_condition()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::SampleLost,
+            })
+            .ok();
+        Ok(status)
+    }
+}
+
 pub struct SetListener {
     pub subscriber_handle: InstanceHandle,
     pub a_listener: Option<Box<dyn SubscriberListenerAsync + Send>>,
@@ -317,8 +584,9 @@ impl Mail for SetListener {
 impl MailHandler<SetListener> for DomainParticipantActor {
     fn handle(&mut self, message: SetListener) -> <SetListener as Mail>::Result {
         let listener = message.a_listener.map(|l| {
-            Actor::spawn(
+            Actor::spawn_with_mailbox(
                 SubscriberListenerActor::new(l),
+                self.listener_mailbox,
                 &self.listener_executor.handle(),
             )
         });
@@ -330,6 +598,46 @@ impl MailHandler<SetListener> for DomainParticipantActor {
     }
 }
 
+pub struct Enable {
+    pub subscriber_handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
+}
+impl Mail for Enable {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<Enable> for DomainParticipantActor {
+    fn handle(&mut self, message: Enable) -> <Enable as Mail>::Result {
+        if !self.domain_participant.enabled() {
+            return Err(DdsError::PreconditionNotMet(
+                "Subscriber can only be enabled once its parent participant is enabled".to_string(),
+            ));
+        }
+        let subscriber =
+            get_mut_subscriber_or_builtin(&mut self.domain_participant, message.subscriber_handle)
+                .ok_or(DdsError::AlreadyDeleted)?;
+        if !subscriber.enabled() {
+            subscriber.enable();
+            if subscriber.qos().entity_factory.autoenable_created_entities {
+                let data_reader_handle_list: Vec<_> = subscriber
+                    .data_reader_list()
+                    .map(|dr| dr.instance_handle())
+                    .collect();
+                for data_reader_handle in data_reader_handle_list {
+                    MailHandler::handle(
+                        self,
+                        data_reader_service::Enable {
+                            subscriber_handle: message.subscriber_handle,
+                            data_reader_handle,
+                            participant_address: message.participant_address.clone(),
+                        },
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 fn get_topic_kind(type_support: &dyn DynamicType) -> TopicKind {
     for index in 0..type_support.get_member_count() {
         if let Ok(m) = type_support.get_member_by_index(index) {
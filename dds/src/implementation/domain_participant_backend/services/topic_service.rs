@@ -35,6 +35,7 @@ impl MailHandler<GetInconsistentTopicStatus> for DomainParticipantActor {
 pub struct SetQos {
     pub topic_name: String,
     pub topic_qos: QosKind<TopicQos>,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for SetQos {
     type Result = DdsResult<()>;
@@ -51,7 +52,17 @@ impl MailHandler<SetQos> for DomainParticipantActor {
             .get_mut_topic(&message.topic_name)
             .ok_or(DdsError::AlreadyDeleted)?;
 
-        topic.set_qos(qos)
+        topic.set_qos(qos)?;
+        if topic.enabled() {
+            message
+                .participant_address
+                .send_actor_mail(discovery_service::AnnounceTopic {
+                    topic_name: message.topic_name,
+                })
+                .ok();
+        }
+
+        Ok(())
     }
 }
 
@@ -81,6 +92,11 @@ impl Mail for Enable {
 }
 impl MailHandler<Enable> for DomainParticipantActor {
     fn handle(&mut self, message: Enable) -> <Enable as Mail>::Result {
+        if !self.domain_participant.enabled() {
+            return Err(DdsError::PreconditionNotMet(
+                "Topic can only be enabled once its parent participant is enabled".to_string(),
+            ));
+        }
         let topic = self
             .domain_participant
             .get_mut_topic(&message.topic_name)
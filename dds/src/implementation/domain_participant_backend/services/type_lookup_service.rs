@@ -0,0 +1,87 @@
+use crate::{
+    implementation::{
+        data_representation_builtin_endpoints::spdp_discovered_participant_data::SpdpDiscoveredParticipantData,
+        domain_participant_backend::domain_participant_actor::DomainParticipantActor,
+    },
+    runtime::actor::{Mail, MailHandler},
+    transport::{history_cache::CacheChange, types::GuidPrefix},
+    xtypes::{dynamic_type::DynamicType, type_object::TypeInformation},
+};
+use std::sync::Arc;
+
+pub struct AddTypeLookupRequest {
+    pub cache_change: CacheChange,
+}
+impl Mail for AddTypeLookupRequest {
+    type Result = ();
+}
+impl MailHandler<AddTypeLookupRequest> for DomainParticipantActor {
+    fn handle(&mut self, message: AddTypeLookupRequest) -> <AddTypeLookupRequest as Mail>::Result {
+        let local_types: Vec<Arc<dyn DynamicType + Send + Sync>> = self
+            .domain_participant
+            .topic_list()
+            .map(|t| t.type_support().clone())
+            .collect();
+        if self
+            .type_lookup
+            .process_request(
+                self.transport.guid().prefix(),
+                message.cache_change.data_value.as_ref(),
+                &local_types,
+            )
+            .is_err()
+        {
+            tracing::warn!("Invalid TypeLookup request ignored");
+        }
+    }
+}
+
+pub struct AddTypeLookupReply {
+    pub cache_change: CacheChange,
+}
+impl Mail for AddTypeLookupReply {
+    type Result = ();
+}
+impl MailHandler<AddTypeLookupReply> for DomainParticipantActor {
+    fn handle(&mut self, message: AddTypeLookupReply) -> <AddTypeLookupReply as Mail>::Result {
+        if self
+            .type_lookup
+            .process_reply(message.cache_change.data_value.as_ref())
+            .is_err()
+        {
+            tracing::warn!("Invalid TypeLookup reply ignored");
+        }
+    }
+}
+
+pub fn add_matched_participant(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+) {
+    domain_participant_actor
+        .type_lookup
+        .add_matched_participant(&discovered_participant_data.participant_proxy);
+}
+
+pub fn remove_matched_participant(
+    domain_participant_actor: &mut DomainParticipantActor,
+    discovered_participant_data: &SpdpDiscoveredParticipantData,
+) {
+    domain_participant_actor
+        .type_lookup
+        .remove_matched_participant(discovered_participant_data.participant_proxy.guid_prefix);
+}
+
+// The TypeObjects of the discovered endpoints are requested from the participant which
+// announced them
+pub fn request_type_objects(
+    domain_participant_actor: &mut DomainParticipantActor,
+    remote_guid_prefix: GuidPrefix,
+    type_information: Option<&TypeInformation>,
+) {
+    if let Some(type_information) = type_information {
+        domain_participant_actor
+            .type_lookup
+            .request_type_objects(remote_guid_prefix, type_information);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     builtin_topics::{DCPS_PARTICIPANT, DCPS_PUBLICATION, DCPS_SUBSCRIPTION, DCPS_TOPIC},
-    configuration::DustDdsConfiguration,
+    configuration::{DiscoveryMode, DustDdsConfiguration, InitialPeer, IpVersion},
     dds_async::domain_participant_listener::DomainParticipantListenerAsync,
     domain::domain_participant_factory::DomainId,
     implementation::{
@@ -12,6 +12,7 @@ use crate::{
         },
         domain_participant_backend::{
             domain_participant_actor::DomainParticipantActor,
+            durability_service::DurabilityService,
             entities::{
                 data_reader::{DataReaderEntity, TransportReaderKind},
                 data_writer::{DataWriterEntity, TransportWriterKind},
@@ -21,10 +22,21 @@ use crate::{
                 topic::TopicEntity,
             },
             handle::InstanceHandleCounter,
-            services::{discovery_service, domain_participant_service, message_service},
+            participant_message::{
+                ParticipantMessage, ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+                ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+            },
+            services::{
+                discovery_service, domain_participant_service, message_service,
+                participant_message_service, type_lookup_service,
+            },
         },
         listeners::domain_participant_listener::DomainParticipantListenerActor,
         status_condition::status_condition_actor::StatusConditionActor,
+        xtypes_glue::type_lookup::{
+            TypeLookup, ENTITYID_TL_SVC_REPLY_READER, ENTITYID_TL_SVC_REPLY_WRITER,
+            ENTITYID_TL_SVC_REQ_READER, ENTITYID_TL_SVC_REQ_WRITER,
+        },
     },
     infrastructure::{
         error::{DdsError, DdsResult},
@@ -40,10 +52,13 @@ use crate::{
         status::StatusKind,
         time::{Duration, DurationKind},
     },
-    rtps::transport::RtpsTransport,
+    rtps::{
+        tcp_transport::{logical_port_metatraffic, tcp_locator},
+        transport::{port_builtin_unicast, RtpsTransport},
+    },
     runtime::{
-        actor::{Actor, ActorAddress, ActorBuilder, Mail, MailHandler},
-        executor::Executor,
+        actor::{Actor, ActorAddress, ActorBuilder, Mail, MailHandler, Mailbox},
+        executor::{ExecutorPool, RuntimeHandle},
         timer::TimerDriver,
     },
     topic_definition::type_support::TypeSupport,
@@ -51,15 +66,15 @@ use crate::{
         history_cache::{CacheChange, HistoryCache},
         participant::TransportParticipant,
         types::{
-            EntityId, GuidPrefix, ReliabilityKind, BUILT_IN_READER_WITH_KEY,
-            BUILT_IN_WRITER_WITH_KEY,
+            EntityId, GuidPrefix, Locator, ReliabilityKind, BUILT_IN_READER_WITH_KEY,
+            BUILT_IN_WRITER_WITH_KEY, LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6,
         },
     },
 };
 use network_interface::{Addr, NetworkInterface, NetworkInterfaceConfig};
 use std::{
     collections::HashMap,
-    net::IpAddr,
+    net::{IpAddr, SocketAddr, ToSocketAddrs},
     sync::{
         atomic::{AtomicU32, Ordering},
         Arc, OnceLock,
@@ -67,6 +82,24 @@ use std::{
 };
 use tracing::{error, warn};
 
+#[cfg(feature = "xml")]
+use crate::infrastructure::qos_profile::{parse_qos_profiles, QosProfile};
+
+#[cfg(feature = "security")]
+use crate::{
+    implementation::domain_participant_backend::services::security_service,
+    security::{
+        access_control::AccessControl,
+        authentication::{Authentication, IdentityCredentials},
+        cryptography::Cryptography,
+        participant_security::ParticipantSecurity,
+        types::{
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_STATELESS_READER,
+            ENTITYID_P2P_BUILTIN_PARTICIPANT_STATELESS_WRITER,
+        },
+    },
+};
+
 pub const ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER: EntityId =
     EntityId::new([0x00, 0x01, 0x00], BUILT_IN_WRITER_WITH_KEY);
 
@@ -96,12 +129,67 @@ pub struct DomainParticipantFactoryActor {
     domain_participant_list: HashMap<InstanceHandle, Actor<DomainParticipantActor>>,
     qos: DomainParticipantFactoryQos,
     default_participant_qos: DomainParticipantQos,
+    #[cfg(feature = "xml")]
+    qos_profile_list: HashMap<String, QosProfile>,
     configuration: DustDdsConfiguration,
+    runtime: RuntimeHandle,
+}
+
+// Participant identifiers whose well-known metatraffic unicast ports receive the announcements for
+// the initial peers given without a port
+const INITIAL_PEER_PARTICIPANT_ID_COUNT: i32 = 5;
+
+fn initial_peer_locator_list(
+    peer: &str,
+    domain_id: DomainId,
+    ip_version: IpVersion,
+) -> Vec<Locator> {
+    let Ok(initial_peer) = InitialPeer::parse(peer) else {
+        return Vec::new();
+    };
+    let ip_list: Vec<IpAddr> = match initial_peer.host.parse() {
+        Ok(ip) => vec![ip],
+        Err(_) => match (initial_peer.host.as_str(), 0).to_socket_addrs() {
+            Ok(address_list) => address_list.map(|a| a.ip()).collect(),
+            Err(e) => {
+                warn!("Failed to resolve initial peer {} with error {}", peer, e);
+                Vec::new()
+            }
+        },
+    };
+    let port_list: Vec<u16> = match initial_peer.port {
+        Some(port) => vec![port],
+        None => (0..INITIAL_PEER_PARTICIPANT_ID_COUNT)
+            .filter_map(|participant_id| port_builtin_unicast(domain_id, participant_id))
+            .collect(),
+    };
+    ip_list
+        .into_iter()
+        .filter_map(|ip| match ip {
+            IpAddr::V4(a) if initial_peer.udp_v4 && ip_version.is_v4_enabled() => {
+                let mut address = [0; 16];
+                address[12..].copy_from_slice(&a.octets());
+                Some((LOCATOR_KIND_UDP_V4, address))
+            }
+            IpAddr::V6(a) if initial_peer.udp_v6 && ip_version.is_v6_enabled() => {
+                Some((LOCATOR_KIND_UDP_V6, a.octets()))
+            }
+            _ => None,
+        })
+        .flat_map(|(kind, address)| {
+            port_list
+                .iter()
+                .map(move |&port| Locator::new(kind, port.into(), address))
+        })
+        .collect()
 }
 
 impl DomainParticipantFactoryActor {
-    pub fn new() -> Self {
-        Default::default()
+    pub fn new(runtime: RuntimeHandle) -> Self {
+        Self {
+            runtime,
+            ..Default::default()
+        }
     }
 
     fn get_unique_participant_id(&mut self) -> u32 {
@@ -111,7 +199,9 @@ impl DomainParticipantFactoryActor {
     }
 
     fn create_new_guid_prefix(&mut self) -> GuidPrefix {
-        let interface_address = NetworkInterface::show()
+        // The host ID is taken from an IPv4 address when available, otherwise from the last
+        // octets of an IPv6 address, which hold the interface identifier
+        let interface_address_list: Vec<_> = NetworkInterface::show()
             .expect("Could not scan interfaces")
             .into_iter()
             .filter(|x| {
@@ -121,16 +211,23 @@ impl DomainParticipantFactoryActor {
                     true
                 }
             })
-            .flat_map(|i| {
-                i.addr
-                    .into_iter()
-                    .filter(|a| matches!(a, Addr::V4(v4) if !v4.ip.is_loopback()))
-            })
-            .next();
+            .flat_map(|i| i.addr.into_iter().filter(|a| !a.ip().is_loopback()))
+            .collect();
+        let interface_address = interface_address_list
+            .iter()
+            .find(|a| matches!(a, Addr::V4(_)))
+            .or_else(|| {
+                interface_address_list
+                    .iter()
+                    .find(|a| self.configuration.ip_version().is_v6_enabled() && a.ip().is_ipv6())
+            });
         let host_id = if let Some(interface) = interface_address {
             match interface.ip() {
                 IpAddr::V4(a) => a.octets(),
-                IpAddr::V6(_) => unimplemented!("IPv6 not yet implemented"),
+                IpAddr::V6(a) => {
+                    let octets = a.octets();
+                    [octets[12], octets[13], octets[14], octets[15]]
+                }
             }
         } else {
             warn!("Failed to get Host ID from IP address, use 0 instead");
@@ -169,14 +266,20 @@ impl Mail for CreateParticipant {
         InstanceHandle,
         ActorAddress<StatusConditionActor>,
         ActorAddress<StatusConditionActor>,
+        usize,
     )>;
 }
 impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
     fn handle(&mut self, message: CreateParticipant) -> <CreateParticipant as Mail>::Result {
-        let backend_executor = Executor::new();
+        // The listeners and the transport keep their own threads since they block waiting for
+        // other tasks
+        let backend_executor = self.runtime.executor();
         let backend_executor_handle = backend_executor.handle();
 
-        let listener_executor = Executor::new();
+        let listener_executor = match self.configuration.listener_spawner() {
+            Some(spawner) => ExecutorPool::with_spawner(spawner.clone()),
+            None => ExecutorPool::new(self.configuration.listener_threads()),
+        };
 
         let timer_driver = TimerDriver::new();
         let timer_handle = timer_driver.handle();
@@ -187,15 +290,113 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
         };
 
         let guid_prefix = self.create_new_guid_prefix();
-        let participant_actor_builder = ActorBuilder::new();
+        let actor_mailbox_capacities = self.configuration.actor_mailbox_capacities();
+        let mailbox = |capacity| Mailbox {
+            capacity,
+            overflow_policy: self.configuration.mailbox_overflow_policy(),
+        };
+        let participant_actor_builder =
+            ActorBuilder::with_mailbox(mailbox(actor_mailbox_capacities.participant));
 
         let mut transport = Box::new(RtpsTransport::new(
             guid_prefix,
             message.domain_id,
             self.configuration.interface_name(),
             self.configuration.udp_receive_buffer_size(),
+            self.configuration.receive_busy_poll_duration(),
+            self.configuration.receive_threads(),
+            self.configuration.max_message_size(),
+            self.configuration.path_mtu_discovery(),
+            self.configuration.destination_mtu_list().to_vec(),
+            self.configuration.retransmission_backoff(),
+            self.configuration.samples_per_piggyback_heartbeat(),
+            self.configuration.heartbeat_response_jitter(),
+            self.configuration.user_data_multicast_threshold(),
+            self.configuration.writer_batching(),
+            self.configuration.sedp_batching(),
+            self.configuration.flow_control(),
+            self.configuration
+                .transport_priority_dscp_mapping()
+                .to_vec(),
+            self.configuration.disable_multicast(),
+            self.configuration.deserialization_limits(),
+            self.configuration.tcp_port(),
+            self.configuration.tcp_max_connections(),
+            self.configuration.ip_version(),
+            mailbox(actor_mailbox_capacities.transport),
+            timer_handle.clone(),
         )?);
 
+        // The participants configured with an identity only communicate with the remote
+        // participants authenticated through the handshake over the ParticipantStatelessMessage
+        #[cfg(feature = "security")]
+        let security =
+            match IdentityCredentials::from_property_qos(&domain_participant_qos.property)? {
+                Some(credentials) => {
+                    let access_control = AccessControl::from_property_qos(
+                        &domain_participant_qos.property,
+                        message.domain_id,
+                        &credentials.subject_name(),
+                    )?;
+                    let permissions_document = access_control
+                        .as_ref()
+                        .map(|a| a.permissions_document().as_bytes().to_vec())
+                        .unwrap_or_default();
+                    let stateless_message_writer = transport.create_stateless_writer(
+                        ENTITYID_P2P_BUILTIN_PARTICIPANT_STATELESS_WRITER,
+                        self.configuration.fragment_size(),
+                    );
+                    let stateless_message_reader = transport.create_stateless_reader(
+                        ENTITYID_P2P_BUILTIN_PARTICIPANT_STATELESS_READER,
+                        Box::new(ParticipantStatelessMessageReaderHistoryCache {
+                            participant_address: participant_actor_builder.address(),
+                        }),
+                    );
+                    // The submessages of the builtin endpoints are protected as required by
+                    // the governance so the transport must encode them before the endpoints
+                    // are created
+                    let cryptography = access_control.as_ref().map(|access_control| {
+                        let cryptography = Arc::new(Cryptography::new());
+                        for entity_id in [
+                            ENTITYID_SEDP_BUILTIN_TOPICS_ANNOUNCER,
+                            ENTITYID_SEDP_BUILTIN_TOPICS_DETECTOR,
+                            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+                            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+                            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+                            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
+                        ] {
+                            cryptography.register_local_endpoint(
+                                entity_id,
+                                access_control.discovery_protection_kind(),
+                            );
+                        }
+                        transport.set_submessage_crypto(cryptography.clone());
+                        cryptography
+                    });
+                    Some(ParticipantSecurity::new(
+                        Authentication::new(credentials, transport.guid(), permissions_document),
+                        access_control,
+                        cryptography,
+                        stateless_message_writer,
+                        stateless_message_reader,
+                    ))
+                }
+                None => {
+                    if domain_participant_qos
+                        .property
+                        .value
+                        .iter()
+                        .any(|p| p.name.starts_with("dds.sec.access."))
+                    {
+                        return Err(DdsError::PreconditionNotMet(
+                            "Access control requires the authentication to be configured"
+                                .to_string(),
+                        ));
+                    }
+                    None
+                }
+            };
+
         let mut instance_handle_counter = InstanceHandleCounter::default();
         fn sedp_data_reader_qos() -> DataReaderQos {
             DataReaderQos {
@@ -410,8 +611,44 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
             self.configuration.fragment_size(),
         );
-        for &discovery_locator in transport.metatraffic_multicast_locator_list() {
-            dcps_participant_transport_writer.add_reader_locator(discovery_locator);
+        // The discovery clients only announce themselves to the discovery servers
+        let is_discovery_client = self.configuration.discovery_mode() == DiscoveryMode::Client;
+        if !is_discovery_client {
+            for &discovery_locator in transport.metatraffic_multicast_locator_list() {
+                dcps_participant_transport_writer.add_reader_locator(discovery_locator);
+            }
+        }
+        // Without the multicast loopback the participant must announce itself over unicast to
+        // discover its own endpoints
+        if self.configuration.disable_multicast() || is_discovery_client {
+            for &discovery_locator in transport.metatraffic_unicast_locator_list() {
+                dcps_participant_transport_writer.add_reader_locator(discovery_locator);
+            }
+        }
+        // The participant is announced to the initial peers and the discovery servers so that it
+        // is discovered without multicast. The peers answer with their own announcement once they
+        // discover it.
+        for peer in self
+            .configuration
+            .initial_peers()
+            .iter()
+            .chain(self.configuration.discovery_servers())
+        {
+            for discovery_locator in
+                initial_peer_locator_list(peer, message.domain_id, self.configuration.ip_version())
+            {
+                dcps_participant_transport_writer.add_reader_locator(discovery_locator);
+            }
+        }
+        // The participant is announced to the TCP peers over the connections it opens to them
+        for &peer in self.configuration.tcp_peers() {
+            if let SocketAddr::V4(peer) = peer {
+                dcps_participant_transport_writer.add_reader_locator(tcp_locator(
+                    *peer.ip(),
+                    peer.port(),
+                    logical_port_metatraffic(message.domain_id),
+                ));
+            }
         }
         let mut dcps_participant_writer = DataWriterEntity::new(
             instance_handle_counter.generate_new_instance_handle(),
@@ -490,13 +727,54 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
         builtin_publisher.insert_data_writer(dcps_topics_writer);
         builtin_publisher.insert_data_writer(dcps_publications_writer);
         builtin_publisher.insert_data_writer(dcps_subscriptions_writer);
+        let type_lookup = TypeLookup::new(
+            transport.create_stateful_writer(
+                ENTITYID_TL_SVC_REQ_WRITER,
+                ReliabilityKind::Reliable,
+                self.configuration.fragment_size(),
+            ),
+            transport.create_stateful_reader(
+                ENTITYID_TL_SVC_REQ_READER,
+                ReliabilityKind::Reliable,
+                Box::new(TypeLookupRequestReaderHistoryCache {
+                    participant_address: participant_actor_builder.address(),
+                }),
+            ),
+            transport.create_stateful_writer(
+                ENTITYID_TL_SVC_REPLY_WRITER,
+                ReliabilityKind::Reliable,
+                self.configuration.fragment_size(),
+            ),
+            transport.create_stateful_reader(
+                ENTITYID_TL_SVC_REPLY_READER,
+                ReliabilityKind::Reliable,
+                Box::new(TypeLookupReplyReaderHistoryCache {
+                    participant_address: participant_actor_builder.address(),
+                }),
+            ),
+        );
+        let participant_message = ParticipantMessage::new(
+            transport.create_stateful_writer(
+                ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_WRITER,
+                ReliabilityKind::Reliable,
+                self.configuration.fragment_size(),
+            ),
+            transport.create_stateful_reader(
+                ENTITYID_P2P_BUILTIN_PARTICIPANT_MESSAGE_READER,
+                ReliabilityKind::Reliable,
+                Box::new(ParticipantMessageReaderHistoryCache {
+                    participant_address: participant_actor_builder.address(),
+                }),
+            ),
+        );
         let instance_handle = InstanceHandle::new(transport.guid().into());
 
         let status_condition =
             Actor::spawn(StatusConditionActor::default(), &listener_executor.handle());
         let listener = message.listener.map(|l| {
-            Actor::spawn(
+            Actor::spawn_with_mailbox(
                 DomainParticipantListenerActor::new(l),
+                mailbox(actor_mailbox_capacities.listener),
                 &listener_executor.handle(),
             )
         });
@@ -511,6 +789,14 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             builtin_subscriber,
             topic_list,
             self.configuration.domain_tag().to_owned(),
+            self.configuration.accepted_domain_tags().to_vec(),
+            self.configuration.max_discovered_participants(),
+            self.configuration.max_discovered_endpoints(),
+            self.configuration
+                .source_timestamp_tolerance()
+                .map(Into::into),
+            self.configuration.discovery_mode() == DiscoveryMode::Server,
+            self.configuration.participant_lease_duration().into(),
         );
 
         let domain_participant_actor = DomainParticipantActor::new(
@@ -518,9 +804,22 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             transport,
             backend_executor,
             listener_executor,
+            mailbox(actor_mailbox_capacities.listener),
             timer_driver,
             instance_handle_counter,
             self.configuration.fragment_size(),
+            self.configuration
+                .deserialization_limits()
+                .max_sequence_length,
+            type_lookup,
+            participant_message,
+            DurabilityService::new(
+                self.configuration
+                    .persistence_directory()
+                    .map(|d| d.to_path_buf()),
+            ),
+            #[cfg(feature = "security")]
+            security,
         );
         let participant_handle = domain_participant_actor
             .domain_participant
@@ -545,22 +844,48 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
         let participant_address = participant_actor.address();
         let participant_announcement_interval =
             self.configuration.participant_announcement_interval();
+        let announcement_timer_handle = timer_handle.clone();
+        backend_executor_handle.spawn(async move {
+            loop {
+                match participant_address.send_actor_mail(discovery_service::AnnounceParticipant) {
+                    Ok(r) => {
+                        if let Err(announce_result) = r.receive_reply().await {
+                            error!("Error announcing participant: {:?}", announce_result);
+                        }
+                    }
+                    Err(DdsError::AlreadyDeleted) => break,
+                    // A full mailbox only skips this announcement
+                    Err(_) => (),
+                }
+                announcement_timer_handle
+                    .sleep(participant_announcement_interval)
+                    .await;
+            }
+        });
 
+        // Start the task asserting the liveliness of the writers with automatic liveliness
+        let participant_address = participant_actor.address();
         backend_executor_handle.spawn(async move {
-            while let Ok(r) =
-                participant_address.send_actor_mail(discovery_service::AnnounceParticipant)
-            {
-                if let Err(announce_result) = r.receive_reply().await {
-                    error!("Error announcing participant: {:?}", announce_result);
+            // With a full mailbox the assertion is retried after the last known period
+            let mut assertion_period = domain_participant_service::AUTOMATIC_LIVELINESS_IDLE_PERIOD;
+            loop {
+                match participant_address
+                    .send_actor_mail(domain_participant_service::AssertAutomaticLiveliness)
+                {
+                    Ok(r) => assertion_period = r.receive_reply().await,
+                    Err(DdsError::AlreadyDeleted) => break,
+                    Err(_) => (),
                 }
-                timer_handle.sleep(participant_announcement_interval).await;
+                timer_handle.sleep(assertion_period).await;
             }
         });
 
         if self.qos.entity_factory.autoenable_created_entities {
-            participant_actor.send_actor_mail(domain_participant_service::Enable {
-                domain_participant_address: participant_actor.address(),
-            });
+            participant_actor
+                .send_actor_mail(domain_participant_service::Enable {
+                    domain_participant_address: participant_actor.address(),
+                })
+                .ok();
         }
 
         let participant_address = participant_actor.address();
@@ -572,6 +897,9 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             participant_handle,
             participant_status_condition_address,
             builtin_subscriber_status_condition_address,
+            self.configuration
+                .deserialization_limits()
+                .max_sequence_length,
         ))
     }
 }
@@ -670,6 +998,42 @@ impl MailHandler<GetQos> for DomainParticipantFactoryActor {
     }
 }
 
+#[cfg(feature = "xml")]
+pub struct LoadQosProfiles {
+    pub document: String,
+}
+#[cfg(feature = "xml")]
+impl Mail for LoadQosProfiles {
+    type Result = DdsResult<()>;
+}
+#[cfg(feature = "xml")]
+impl MailHandler<LoadQosProfiles> for DomainParticipantFactoryActor {
+    fn handle(&mut self, message: LoadQosProfiles) -> <LoadQosProfiles as Mail>::Result {
+        // The profiles of the document are only added if all of them are valid
+        let qos_profile_list = parse_qos_profiles(&message.document, &self.qos_profile_list)?;
+        self.qos_profile_list.extend(qos_profile_list);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "xml")]
+pub struct GetQosFromProfile {
+    pub profile_name: String,
+}
+#[cfg(feature = "xml")]
+impl Mail for GetQosFromProfile {
+    type Result = DdsResult<QosProfile>;
+}
+#[cfg(feature = "xml")]
+impl MailHandler<GetQosFromProfile> for DomainParticipantFactoryActor {
+    fn handle(&mut self, message: GetQosFromProfile) -> <GetQosFromProfile as Mail>::Result {
+        self.qos_profile_list
+            .get(&message.profile_name)
+            .cloned()
+            .ok_or(DdsError::BadParameter)
+    }
+}
+
 pub struct SetConfiguration {
     pub configuration: DustDdsConfiguration,
 }
@@ -698,6 +1062,11 @@ struct DcpsParticipantReaderHistoryCache {
 
 impl HistoryCache for DcpsParticipantReaderHistoryCache {
     fn add_change(&mut self, cache_change: CacheChange) {
+        // The stateless readers receive the changes of all the stateless writers of the remote
+        // participants so only the participant announcements are taken
+        if cache_change.writer_guid.entity_id() != ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER {
+            return;
+        }
         self.participant_address
             .send_actor_mail(message_service::AddBuiltinParticipantsDetectorCacheChange {
                 participant_address: self.participant_address.clone(),
@@ -791,3 +1160,68 @@ impl HistoryCache for DcpsPublicationsReaderHistoryCache {
             .ok();
     }
 }
+
+struct TypeLookupRequestReaderHistoryCache {
+    participant_address: ActorAddress<DomainParticipantActor>,
+}
+
+impl HistoryCache for TypeLookupRequestReaderHistoryCache {
+    fn add_change(&mut self, cache_change: CacheChange) {
+        self.participant_address
+            .send_actor_mail(type_lookup_service::AddTypeLookupRequest { cache_change })
+            .ok();
+    }
+
+    fn remove_change(&mut self, _sequence_number: i64) {}
+}
+
+struct TypeLookupReplyReaderHistoryCache {
+    participant_address: ActorAddress<DomainParticipantActor>,
+}
+
+impl HistoryCache for TypeLookupReplyReaderHistoryCache {
+    fn add_change(&mut self, cache_change: CacheChange) {
+        self.participant_address
+            .send_actor_mail(type_lookup_service::AddTypeLookupReply { cache_change })
+            .ok();
+    }
+
+    fn remove_change(&mut self, _sequence_number: i64) {}
+}
+
+struct ParticipantMessageReaderHistoryCache {
+    participant_address: ActorAddress<DomainParticipantActor>,
+}
+
+impl HistoryCache for ParticipantMessageReaderHistoryCache {
+    fn add_change(&mut self, cache_change: CacheChange) {
+        self.participant_address
+            .send_actor_mail(participant_message_service::AddParticipantMessage {
+                cache_change,
+                participant_address: self.participant_address.clone(),
+            })
+            .ok();
+    }
+
+    fn remove_change(&mut self, _sequence_number: i64) {}
+}
+
+#[cfg(feature = "security")]
+struct ParticipantStatelessMessageReaderHistoryCache {
+    participant_address: ActorAddress<DomainParticipantActor>,
+}
+
+#[cfg(feature = "security")]
+impl HistoryCache for ParticipantStatelessMessageReaderHistoryCache {
+    fn add_change(&mut self, cache_change: CacheChange) {
+        if cache_change.writer_guid.entity_id() != ENTITYID_P2P_BUILTIN_PARTICIPANT_STATELESS_WRITER
+        {
+            return;
+        }
+        self.participant_address
+            .send_actor_mail(security_service::AddParticipantStatelessMessage { cache_change })
+            .ok();
+    }
+
+    fn remove_change(&mut self, _sequence_number: i64) {}
+}
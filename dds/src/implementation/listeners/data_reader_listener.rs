@@ -2,8 +2,8 @@ use crate::{
     dds_async::data_reader::DataReaderAsync,
     implementation::any_data_reader_listener::AnyDataReaderListener,
     infrastructure::status::{
-        RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleRejectedStatus,
-        SubscriptionMatchedStatus,
+        LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
+        SampleLostStatus, SampleRejectedStatus, SubscriptionMatchedStatus,
     },
     runtime::actor::{Mail, MailHandler},
 };
@@ -63,6 +63,37 @@ impl MailHandler<TriggerSampleRejected> for DataReaderListenerActor {
     }
 }
 
+pub struct TriggerSampleLost {
+    pub the_reader: DataReaderAsync<()>,
+    pub status: SampleLostStatus,
+}
+impl Mail for TriggerSampleLost {
+    type Result = ();
+}
+impl MailHandler<TriggerSampleLost> for DataReaderListenerActor {
+    fn handle(&mut self, message: TriggerSampleLost) -> <TriggerSampleLost as Mail>::Result {
+        self.listener
+            .trigger_on_sample_lost(message.the_reader, message.status);
+    }
+}
+
+pub struct TriggerLivelinessChanged {
+    pub the_reader: DataReaderAsync<()>,
+    pub status: LivelinessChangedStatus,
+}
+impl Mail for TriggerLivelinessChanged {
+    type Result = ();
+}
+impl MailHandler<TriggerLivelinessChanged> for DataReaderListenerActor {
+    fn handle(
+        &mut self,
+        message: TriggerLivelinessChanged,
+    ) -> <TriggerLivelinessChanged as Mail>::Result {
+        self.listener
+            .trigger_on_liveliness_changed(message.the_reader, message.status);
+    }
+}
+
 pub struct TriggerSubscriptionMatched {
     pub the_reader: DataReaderAsync<()>,
     pub status: SubscriptionMatchedStatus,
@@ -2,7 +2,8 @@ use crate::{
     dds_async::data_writer::DataWriterAsync,
     implementation::any_data_writer_listener::AnyDataWriterListener,
     infrastructure::status::{
-        OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus, PublicationMatchedStatus,
+        LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
+        PublicationMatchedStatus,
     },
     runtime::actor::{Mail, MailHandler},
 };
@@ -67,3 +68,20 @@ impl MailHandler<TriggerOfferedDeadlineMissed> for DataWriterListenerActor {
             .trigger_on_offered_deadline_missed(message.the_writer, message.status);
     }
 }
+
+pub struct TriggerLivelinessLost {
+    pub the_writer: DataWriterAsync<()>,
+    pub status: LivelinessLostStatus,
+}
+impl Mail for TriggerLivelinessLost {
+    type Result = ();
+}
+impl MailHandler<TriggerLivelinessLost> for DataWriterListenerActor {
+    fn handle(
+        &mut self,
+        message: TriggerLivelinessLost,
+    ) -> <TriggerLivelinessLost as Mail>::Result {
+        self.listener
+            .trigger_on_liveliness_lost(message.the_writer, message.status);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
     dds_async::{data_writer::DataWriterAsync, publisher_listener::PublisherListenerAsync},
     infrastructure::status::{
-        OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus, PublicationMatchedStatus,
+        LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
+        PublicationMatchedStatus,
     },
     runtime::{
         actor::{Mail, MailHandler},
@@ -74,3 +75,22 @@ impl MailHandler<TriggerOfferedDeadlineMissed> for PublisherListenerActor {
         )
     }
 }
+
+pub struct TriggerLivelinessLost {
+    pub the_writer: DataWriterAsync<()>,
+    pub status: LivelinessLostStatus,
+}
+impl Mail for TriggerLivelinessLost {
+    type Result = ();
+}
+impl MailHandler<TriggerLivelinessLost> for PublisherListenerActor {
+    fn handle(
+        &mut self,
+        message: TriggerLivelinessLost,
+    ) -> <TriggerLivelinessLost as Mail>::Result {
+        block_on(
+            self.listener
+                .on_liveliness_lost(message.the_writer, message.status),
+        )
+    }
+}
@@ -4,8 +4,8 @@ use crate::{
         subscriber_listener::SubscriberListenerAsync,
     },
     infrastructure::status::{
-        RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleRejectedStatus,
-        SubscriptionMatchedStatus,
+        LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
+        SampleLostStatus, SampleRejectedStatus, SubscriptionMatchedStatus,
     },
     runtime::{
         actor::{Mail, MailHandler},
@@ -73,6 +73,41 @@ impl MailHandler<TriggerSampleRejected> for SubscriberListenerActor {
     }
 }
 
+pub struct TriggerSampleLost {
+    pub the_reader: DataReaderAsync<()>,
+    pub status: SampleLostStatus,
+}
+impl Mail for TriggerSampleLost {
+    type Result = ();
+}
+impl MailHandler<TriggerSampleLost> for SubscriberListenerActor {
+    fn handle(&mut self, message: TriggerSampleLost) -> <TriggerSampleLost as Mail>::Result {
+        block_on(
+            self.listener
+                .on_sample_lost(message.the_reader, message.status),
+        );
+    }
+}
+
+pub struct TriggerLivelinessChanged {
+    pub the_reader: DataReaderAsync<()>,
+    pub status: LivelinessChangedStatus,
+}
+impl Mail for TriggerLivelinessChanged {
+    type Result = ();
+}
+impl MailHandler<TriggerLivelinessChanged> for SubscriberListenerActor {
+    fn handle(
+        &mut self,
+        message: TriggerLivelinessChanged,
+    ) -> <TriggerLivelinessChanged as Mail>::Result {
+        block_on(
+            self.listener
+                .on_liveliness_changed(message.the_reader.change_foo_type(), message.status),
+        );
+    }
+}
+
 pub struct TriggerSubscriptionMatched {
     pub the_reader: DataReaderAsync<()>,
     pub status: SubscriptionMatchedStatus,
@@ -1,12 +1,17 @@
 use crate::{
     infrastructure::status::StatusKind,
-    runtime::actor::{Mail, MailHandler},
+    runtime::{
+        actor::{Mail, MailHandler},
+        oneshot::{oneshot, OneshotReceiver, OneshotSender},
+    },
 };
 
 #[derive(Debug)]
 pub struct StatusConditionActor {
     enabled_statuses: Vec<StatusKind>,
     status_changes: Vec<StatusKind>,
+    communication_state_waiter_list: Vec<(StatusKind, OneshotSender<()>)>,
+    communication_state_waiters_cancelled: bool,
 }
 
 impl Default for StatusConditionActor {
@@ -28,13 +33,45 @@ impl Default for StatusConditionActor {
                 StatusKind::SubscriptionMatched,
             ],
             status_changes: Vec::new(),
+            communication_state_waiter_list: Vec::new(),
+            communication_state_waiters_cancelled: false,
         }
     }
 }
 
 impl StatusConditionActor {
     pub fn add_communication_state(&mut self, state: StatusKind) {
-        self.status_changes.push(state);
+        if !self.status_changes.contains(&state) {
+            self.status_changes.push(state);
+        }
+        let (notified_waiter_list, waiter_list) = self
+            .communication_state_waiter_list
+            .drain(..)
+            .partition(|(s, _)| s == &state);
+        self.communication_state_waiter_list = waiter_list;
+        for (_, waiter) in notified_waiter_list {
+            waiter.send(());
+        }
+    }
+
+    // The receiver completes once the state is added, or right away if it is already there. It
+    // fails if the waiters are cancelled because the entity is deleted, also for the waiters
+    // added afterwards
+    pub fn wait_for_communication_state(&mut self, state: StatusKind) -> OneshotReceiver<()> {
+        let (waiter, receiver) = oneshot();
+        if self.communication_state_waiters_cancelled {
+            drop(waiter);
+        } else if self.status_changes.contains(&state) {
+            waiter.send(());
+        } else {
+            self.communication_state_waiter_list.push((state, waiter));
+        }
+        receiver
+    }
+
+    pub fn cancel_communication_state_waiters(&mut self) {
+        self.communication_state_waiters_cancelled = true;
+        self.communication_state_waiter_list.clear();
     }
 
     pub fn remove_communication_state(&mut self, state: StatusKind) {
@@ -49,6 +86,10 @@ impl StatusConditionActor {
         self.enabled_statuses = mask;
     }
 
+    pub fn get_status_changes(&self) -> Vec<StatusKind> {
+        self.status_changes.clone()
+    }
+
     pub fn get_trigger_value(&self) -> bool {
         for status in &self.status_changes {
             if self.enabled_statuses.contains(status) {
@@ -100,6 +141,16 @@ impl MailHandler<GetStatusConditionTriggerValue> for StatusConditionActor {
     }
 }
 
+pub struct GetStatusChanges;
+impl Mail for GetStatusChanges {
+    type Result = Vec<StatusKind>;
+}
+impl MailHandler<GetStatusChanges> for StatusConditionActor {
+    fn handle(&mut self, _: GetStatusChanges) -> <GetStatusChanges as Mail>::Result {
+        self.get_status_changes()
+    }
+}
+
 pub struct AddCommunicationState {
     pub state: StatusKind,
 }
@@ -129,3 +180,31 @@ impl MailHandler<RemoveCommunicationState> for StatusConditionActor {
         self.remove_communication_state(message.state);
     }
 }
+
+pub struct WaitForCommunicationState {
+    pub state: StatusKind,
+}
+impl Mail for WaitForCommunicationState {
+    type Result = OneshotReceiver<()>;
+}
+impl MailHandler<WaitForCommunicationState> for StatusConditionActor {
+    fn handle(
+        &mut self,
+        message: WaitForCommunicationState,
+    ) -> <WaitForCommunicationState as Mail>::Result {
+        self.wait_for_communication_state(message.state)
+    }
+}
+
+pub struct CancelCommunicationStateWaiters;
+impl Mail for CancelCommunicationStateWaiters {
+    type Result = ();
+}
+impl MailHandler<CancelCommunicationStateWaiters> for StatusConditionActor {
+    fn handle(
+        &mut self,
+        _: CancelCommunicationStateWaiters,
+    ) -> <CancelCommunicationStateWaiters as Mail>::Result {
+        self.cancel_communication_state_waiters();
+    }
+}
@@ -1,14 +1,20 @@
 use crate::{
-    infrastructure::instance::InstanceHandle,
+    infrastructure::{
+        error::DdsResult,
+        instance::InstanceHandle,
+        qos_policy::{XCDR2_DATA_REPRESENTATION, XCDR_DATA_REPRESENTATION},
+    },
+    topic_definition::type_support::{deserialize_rtps_encapsulated_dynamic_data, DdsSerialize},
     xtypes::{
-        deserializer::{DeserializeSequence, XTypesDeserializer},
+        deserializer::{DeserializeFinalStruct, DeserializeSequence, XTypesDeserializer},
         dynamic_type::{DynamicType, MemberDescriptor},
         error::XTypesError,
         serialize::{Write, XTypesSerializer},
         serializer::SerializeFinalStruct,
         type_object::TypeIdentifier,
         xcdr_deserializer::{
-            Xcdr1BeDeserializer, Xcdr1LeDeserializer, Xcdr2BeDeserializer, Xcdr2LeDeserializer,
+            delimited_body_v2, find_member_v2, Xcdr1BeDeserializer, Xcdr1LeDeserializer,
+            Xcdr2BeDeserializer, Xcdr2LeDeserializer,
         },
         xcdr_serializer::{Xcdr1LeSerializer, Xcdr2BeSerializer},
     },
@@ -189,6 +195,15 @@ where
 {
     for member_descriptor in dynamic_type.into_iter() {
         let member_descriptor = member_descriptor?;
+        // The presence of an optional member is read without consuming its value
+        if member_descriptor.is_optional
+            && de
+                .deserialize_final_struct()?
+                .deserialize_optional_field::<()>("")?
+                .is_none()
+        {
+            continue;
+        }
         deserialize_and_serialize_if_key_field(
             member_descriptor.type_,
             member_descriptor.is_key,
@@ -219,14 +234,19 @@ where
 fn go_to_pid_le(mut reader: &[u8], pid: u32) -> Result<&[u8], XTypesError> {
     const PID_SENTINEL: u16 = 1;
     loop {
-        let current_pid = u16::from_le_bytes([reader[0], reader[1]]);
+        let header = reader.get(..4).ok_or(XTypesError::InvalidData)?;
+        let current_pid = u16::from_le_bytes([header[0], header[1]]);
         if current_pid == pid as u16 {
             return Ok(&reader[4..]);
         } else if current_pid == PID_SENTINEL {
             return Err(XTypesError::PidNotFound(pid as u16));
         } else {
-            let length = u16::from_le_bytes([reader[2], reader[3]]) as usize;
-            reader = &reader[length + 4..];
+            // Parameters of unknown members are skipped including the padding to the next
+            // parameter header which is not always accounted for in the length
+            let length = u16::from_le_bytes([header[2], header[3]]) as usize;
+            reader = reader
+                .get(4 + ((length + 3) & !3)..)
+                .ok_or(XTypesError::InvalidData)?;
         }
     }
 }
@@ -234,14 +254,19 @@ fn go_to_pid_le(mut reader: &[u8], pid: u32) -> Result<&[u8], XTypesError> {
 fn go_to_pid_be(mut reader: &[u8], pid: u32) -> Result<&[u8], XTypesError> {
     const PID_SENTINEL: u16 = 1;
     loop {
-        let current_pid = u16::from_be_bytes([reader[0], reader[1]]);
+        let header = reader.get(..4).ok_or(XTypesError::InvalidData)?;
+        let current_pid = u16::from_be_bytes([header[0], header[1]]);
         if current_pid == pid as u16 {
             return Ok(&reader[4..]);
         } else if current_pid == PID_SENTINEL {
             return Err(XTypesError::PidNotFound(pid as u16));
         } else {
-            let length = u16::from_be_bytes([reader[2], reader[3]]) as usize;
-            reader = &reader[length + 4..];
+            // Parameters of unknown members are skipped including the padding to the next
+            // parameter header which is not always accounted for in the length
+            let length = u16::from_be_bytes([header[2], header[3]]) as usize;
+            reader = reader
+                .get(4 + ((length + 3) & !3)..)
+                .ok_or(XTypesError::InvalidData)?;
         }
     }
 }
@@ -309,15 +334,53 @@ fn push_to_key_parameter_list_be(
     Ok(())
 }
 
+fn push_to_key_member_list_le(
+    dynamic_type: &dyn DynamicType,
+    serializer: &mut impl SerializeFinalStruct,
+    data: &[u8],
+) -> Result<(), XTypesError> {
+    let members = delimited_body_v2(data, u32::from_le_bytes)?;
+    for descriptor in dynamic_type.into_iter() {
+        let descriptor = descriptor?;
+        if descriptor.is_key {
+            let member = find_member_v2(members, descriptor.id, u32::from_le_bytes)?
+                .ok_or(XTypesError::PidNotFound(descriptor.id as u16))?;
+            let mut de = Xcdr2LeDeserializer::new(member);
+            deserialize_and_serialize_if_key_field(descriptor.type_, true, &mut de, serializer)?;
+        }
+    }
+    Ok(())
+}
+
+fn push_to_key_member_list_be(
+    dynamic_type: &dyn DynamicType,
+    serializer: &mut impl SerializeFinalStruct,
+    data: &[u8],
+) -> Result<(), XTypesError> {
+    let members = delimited_body_v2(data, u32::from_be_bytes)?;
+    for descriptor in dynamic_type.into_iter() {
+        let descriptor = descriptor?;
+        if descriptor.is_key {
+            let member = find_member_v2(members, descriptor.id, u32::from_be_bytes)?
+                .ok_or(XTypesError::PidNotFound(descriptor.id as u16))?;
+            let mut de = Xcdr2BeDeserializer::new(member);
+            deserialize_and_serialize_if_key_field(descriptor.type_, true, &mut de, serializer)?;
+        }
+    }
+    Ok(())
+}
+
 type RepresentationIdentifier = [u8; 2];
 const CDR_BE: RepresentationIdentifier = [0x00, 0x00];
 const CDR_LE: RepresentationIdentifier = [0x00, 0x01];
 const CDR2_BE: RepresentationIdentifier = [0x00, 0x06];
 const CDR2_LE: RepresentationIdentifier = [0x00, 0x07];
-const _D_CDR2_BE: RepresentationIdentifier = [0x00, 0x08];
-const _D_CDR2_LE: RepresentationIdentifier = [0x00, 0x09];
+const D_CDR2_BE: RepresentationIdentifier = [0x00, 0x08];
+const D_CDR2_LE: RepresentationIdentifier = [0x00, 0x09];
 const PL_CDR_BE: RepresentationIdentifier = [0x00, 0x02];
 const PL_CDR_LE: RepresentationIdentifier = [0x00, 0x03];
+const PL_CDR2_BE: RepresentationIdentifier = [0x00, 0x0a];
+const PL_CDR2_LE: RepresentationIdentifier = [0x00, 0x0b];
 
 pub fn get_instance_handle_from_serialized_key(
     mut data: &[u8],
@@ -346,6 +409,18 @@ pub fn get_instance_handle_from_serialized_key(
             CDR2_LE => {
                 push_to_key_for_key(dynamic_type, &mut s, &mut Xcdr2LeDeserializer::new(data))?
             }
+            D_CDR2_BE => push_to_key_for_key(
+                dynamic_type,
+                &mut s,
+                &mut Xcdr2BeDeserializer::new(delimited_body_v2(data, u32::from_be_bytes)?),
+            )?,
+            D_CDR2_LE => push_to_key_for_key(
+                dynamic_type,
+                &mut s,
+                &mut Xcdr2LeDeserializer::new(delimited_body_v2(data, u32::from_le_bytes)?),
+            )?,
+            PL_CDR2_BE => push_to_key_member_list_be(dynamic_type, &mut s, data)?,
+            PL_CDR2_LE => push_to_key_member_list_le(dynamic_type, &mut s, data)?,
             _ => panic!("representation_identifier not supported"),
         }
     }
@@ -373,6 +448,18 @@ pub fn get_instance_handle_from_serialized_foo(
             CDR2_LE => push_to_key(dynamic_type, &mut s, &mut Xcdr2LeDeserializer::new(data))?,
             PL_CDR_BE => push_to_key_parameter_list_be(dynamic_type, &mut s, data)?,
             PL_CDR_LE => push_to_key_parameter_list_le(dynamic_type, &mut s, data)?,
+            D_CDR2_BE => push_to_key(
+                dynamic_type,
+                &mut s,
+                &mut Xcdr2BeDeserializer::new(delimited_body_v2(data, u32::from_be_bytes)?),
+            )?,
+            D_CDR2_LE => push_to_key(
+                dynamic_type,
+                &mut s,
+                &mut Xcdr2LeDeserializer::new(delimited_body_v2(data, u32::from_le_bytes)?),
+            )?,
+            PL_CDR2_BE => push_to_key_member_list_be(dynamic_type, &mut s, data)?,
+            PL_CDR2_LE => push_to_key_member_list_le(dynamic_type, &mut s, data)?,
             _ => panic!("representation_identifier not supported"),
         }
     }
@@ -400,6 +487,18 @@ pub fn get_serialized_key_from_serialized_foo(
             CDR2_LE => push_to_key(dynamic_type, &mut s, &mut Xcdr2LeDeserializer::new(data))?,
             PL_CDR_BE => push_to_key_parameter_list_be(dynamic_type, &mut s, data)?,
             PL_CDR_LE => push_to_key_parameter_list_le(dynamic_type, &mut s, data)?,
+            D_CDR2_BE => push_to_key(
+                dynamic_type,
+                &mut s,
+                &mut Xcdr2BeDeserializer::new(delimited_body_v2(data, u32::from_be_bytes)?),
+            )?,
+            D_CDR2_LE => push_to_key(
+                dynamic_type,
+                &mut s,
+                &mut Xcdr2LeDeserializer::new(delimited_body_v2(data, u32::from_le_bytes)?),
+            )?,
+            PL_CDR2_BE => push_to_key_member_list_be(dynamic_type, &mut s, data)?,
+            PL_CDR2_LE => push_to_key_member_list_le(dynamic_type, &mut s, data)?,
             _ => panic!("representation_identifier not supported"),
         }
     }
@@ -410,6 +509,26 @@ pub fn get_serialized_key_from_serialized_foo(
     Ok(collection)
 }
 
+// Replaces the key members of the serialized foo by the ones of a serialized key as created by
+// get_serialized_key_from_serialized_foo. The returned data uses the same XCDR version as the
+// serialized foo.
+pub fn set_key_in_serialized_foo(
+    serialized_foo: &[u8],
+    serialized_key: &[u8],
+    dynamic_type: &dyn DynamicType,
+) -> DdsResult<Vec<u8>> {
+    let mut key_holder = deserialize_rtps_encapsulated_dynamic_data(serialized_foo, dynamic_type)?;
+    key_holder.deserialize_key_members(
+        dynamic_type,
+        &mut Xcdr1LeDeserializer::new(&serialized_key[4..]),
+    )?;
+    let representation = match [serialized_foo[0], serialized_foo[1]] {
+        CDR_BE | CDR_LE | PL_CDR_BE | PL_CDR_LE => XCDR_DATA_REPRESENTATION,
+        _ => XCDR2_DATA_REPRESENTATION,
+    };
+    key_holder.serialize_data_w_representation(representation)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -493,6 +612,84 @@ mod tests {
         )
     }
 
+    #[test]
+    fn set_key_in_serialized_mutable_struct_le() {
+        let key_holder = [
+            0, 3, 0, 0, //rtps header (PL_CDR_LE: version 1)
+            10, 0, 4, 0, // PID | length (CDR1: incl padding)
+            0, 0, 0, 0, //key_field1 (u8) | padding (3bytes)
+            20, 0, 4, 0, // PID | length (CDR1: incl padding)
+            7, 0, 0, 0, //field_inbetween (u32)
+            11, 0, 4, 0, // PID | length (CDR1: incl padding)
+            0, 0, 0, 0, //key_field2 (u16) | padding (2bytes)
+            1, 0, 0, 0, // Sentinel
+        ];
+        let key = [
+            0, 1, 0, 0, // RTPS header
+            1, 0, 2, 0, // key_field1 (u8) | padding (1byte) | key_field2 (u16)
+        ];
+        let data =
+            set_key_in_serialized_foo(&key_holder, &key, &MutableStruct::get_type()).unwrap();
+        assert_eq!(
+            get_serialized_key_from_serialized_foo(&data, &MutableStruct::get_type()).unwrap(),
+            key
+        );
+        let dynamic_data =
+            deserialize_rtps_encapsulated_dynamic_data(&data, &MutableStruct::get_type()).unwrap();
+        assert_eq!(dynamic_data.get_uint32_value(20), Ok(7));
+    }
+
+    #[test]
+    fn key_from_mutable_struct_with_unpadded_unknown_member_le() {
+        let data = [
+            0, 3, 0, 0, //rtps header (PL_CDR_LE: version 1)
+            30, 0, 1, 0, // PID | length (excl padding)
+            9, 0, 0, 0, //unknown member (u8) | padding (3bytes)
+            10, 0, 1, 0, // PID | length (excl padding)
+            1, 0, 0, 0, //key_field1 (u8) | padding (3bytes)
+            77, 0, 6, 0, // PID | length (excl padding)
+            2, 0, 0, 0, 1, 2, 0, 0, //unknown member (Vec<u8>) | padding (2bytes)
+            11, 0, 2, 0, // PID | length (excl padding)
+            2, 0, 0, 0, //key_field2 (u16) | padding (2bytes)
+            1, 0, 0, 0, // Sentinel
+        ];
+        let expected_instance_handle =
+            InstanceHandle::new([1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            get_instance_handle_from_serialized_foo(&data, &MutableStruct::get_type()).unwrap(),
+            expected_instance_handle
+        );
+    }
+
+    #[test]
+    fn key_from_mutable_struct_pl_cdr2_le() {
+        let data = [
+            0, 0x0b, 0, 2, //rtps header (PL_CDR2_LE)
+            26, 0, 0, 0, // DHEADER
+            10, 0, 0, 0x00, // EMHEADER1 (LC=0)
+            1, 0, 0, 0, //key_field1 (u8) | padding (3bytes)
+            20, 0, 0, 0x20, // EMHEADER1 (LC=2)
+            7, 0, 0, 0, //field_inbetween (u32)
+            11, 0, 0, 0x40, // EMHEADER1 (LC=4)
+            2, 0, 0, 0, // NEXTINT
+            2, 0, 0, 0, //key_field2 (u16) | padding (2bytes)
+        ];
+        let expected_instance_handle =
+            InstanceHandle::new([1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            get_instance_handle_from_serialized_foo(&data, &MutableStruct::get_type()).unwrap(),
+            expected_instance_handle
+        );
+        let expected_key = vec![
+            0, 1, 0, 0, // RTPS header
+            1, 0, 2, 0, // key_field1 (u8) | padding (1byte) | key_field2 (u16)
+        ];
+        assert_eq!(
+            get_serialized_key_from_serialized_foo(&data, &MutableStruct::get_type()).unwrap(),
+            expected_key
+        );
+    }
+
     #[derive(TypeSupport)]
     #[dust_dds(extensibility = "Final")]
     struct Nested {
@@ -549,6 +746,40 @@ mod tests {
         )
     }
 
+    #[derive(TypeSupport)]
+    #[dust_dds(extensibility = "Final")]
+    struct WithOptional {
+        #[dust_dds(optional)]
+        _optional_field: Option<u32>,
+        #[dust_dds(key)]
+        _key_field: u16,
+    }
+
+    #[test]
+    fn key_after_optional_member_le() {
+        let present = [
+            0, 1, 0, 0b0000_0010, //rtps header
+            0, 0, 4, 0, // PID | length
+            9, 0, 0, 0, //optional_field (u32)
+            3, 0, 0, 0, //key_field (u16) | padding 2 bytes
+        ];
+        let absent = [
+            0, 1, 0, 0b0000_0010, //rtps header
+            0, 0, 0, 0, // PID | length
+            3, 0, 0, 0, //key_field (u16) | padding 2 bytes
+        ];
+        let expected_instance_handle =
+            InstanceHandle::new([0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            get_instance_handle_from_serialized_foo(&present, &WithOptional::get_type()).unwrap(),
+            expected_instance_handle
+        );
+        assert_eq!(
+            get_instance_handle_from_serialized_foo(&absent, &WithOptional::get_type()).unwrap(),
+            expected_instance_handle
+        );
+    }
+
     #[test]
     fn simple_key_le() {
         let data = [
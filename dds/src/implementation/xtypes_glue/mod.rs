@@ -1,2 +1,5 @@
 pub mod key_and_instance_handle;
 pub mod dynamic_type;
+pub mod sql_filter;
+pub mod type_information;
+pub mod type_lookup;
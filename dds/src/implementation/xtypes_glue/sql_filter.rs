@@ -0,0 +1,755 @@
+use crate::{
+    infrastructure::error::{DdsError, DdsResult},
+    xtypes::{
+        deserializer::{DeserializeSequence, XTypesDeserializer},
+        dynamic_type::DynamicType,
+        error::XTypesError,
+        type_object::TypeIdentifier,
+        xcdr_deserializer::{
+            delimited_body_v2, Xcdr1BeDeserializer, Xcdr1LeDeserializer, Xcdr2BeDeserializer,
+            Xcdr2LeDeserializer,
+        },
+    },
+};
+use std::{cmp::Ordering, collections::HashMap};
+
+// Filter expressions of the DDSSQL filter class as defined in Annex B of the DDS standard. The
+// expressions are evaluated directly on the serialized samples so that a writer can skip the
+// samples which are not relevant to a content filtered reader.
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+}
+
+impl Value {
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Integer(a), Value::Integer(b)) => a.partial_cmp(b),
+            (Value::Integer(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+            (Value::Float(a), Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+            (Value::Boolean(a), Value::Boolean(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Field(String),
+    Value(Value),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RelationalOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+    Not(Box<Condition>),
+    Compare(Operand, RelationalOperator, Operand),
+    Between(Operand, Operand, Operand),
+    Like(Operand, Operand),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Parameter(usize),
+    Operator(RelationalOperator),
+    OpenParenthesis,
+    CloseParenthesis,
+}
+
+fn tokenize(expression: &str) -> DdsResult<Vec<Token>> {
+    let invalid = || DdsError::BadParameter;
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::OpenParenthesis);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::CloseParenthesis);
+            }
+            '=' => {
+                chars.next();
+                tokens.push(Token::Operator(RelationalOperator::Equal));
+            }
+            '<' | '>' | '!' => {
+                chars.next();
+                let operator = match (c, chars.peek()) {
+                    ('<', Some('=')) => RelationalOperator::LessOrEqual,
+                    ('<', Some('>')) | ('!', Some('=')) => RelationalOperator::NotEqual,
+                    ('>', Some('=')) => RelationalOperator::GreaterOrEqual,
+                    ('<', _) => RelationalOperator::Less,
+                    ('>', _) => RelationalOperator::Greater,
+                    _ => return Err(invalid()),
+                };
+                if matches!(
+                    operator,
+                    RelationalOperator::LessOrEqual
+                        | RelationalOperator::NotEqual
+                        | RelationalOperator::GreaterOrEqual
+                ) {
+                    chars.next();
+                }
+                tokens.push(Token::Operator(operator));
+            }
+            '\'' | '`' => {
+                chars.next();
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => string.push(c),
+                        None => return Err(invalid()),
+                    }
+                }
+                tokens.push(Token::String(string));
+            }
+            '%' => {
+                chars.next();
+                let mut index = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+                    index.push(c);
+                }
+                tokens.push(Token::Parameter(index.parse().map_err(|_| invalid())?));
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                let mut number = String::new();
+                number.push(c);
+                chars.next();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '.') {
+                    number.push(c);
+                }
+                tokens.push(parse_number(&number).ok_or_else(invalid)?);
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut identifier = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                {
+                    identifier.push(c);
+                }
+                tokens.push(Token::Identifier(identifier));
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_number(number: &str) -> Option<Token> {
+    if let Some(hex) = number.strip_prefix("0x").or(number.strip_prefix("0X")) {
+        i128::from_str_radix(hex, 16).ok().map(Token::Integer)
+    } else if let Ok(integer) = number.parse() {
+        Some(Token::Integer(integer))
+    } else {
+        number.parse().ok().map(Token::Float)
+    }
+}
+
+// The parameters are strings which are interpreted with the same syntax as the literals of the
+// expression. Parameters which are not a valid literal are taken as a string.
+fn parse_parameter(parameter: &str) -> Value {
+    let parameter = parameter.trim();
+    match tokenize(parameter).as_deref() {
+        Ok([Token::Integer(v)]) => Value::Integer(*v),
+        Ok([Token::Float(v)]) => Value::Float(*v),
+        Ok([Token::String(v)]) => Value::String(v.clone()),
+        Ok([Token::Identifier(v)]) if v.eq_ignore_ascii_case("TRUE") => Value::Boolean(true),
+        Ok([Token::Identifier(v)]) if v.eq_ignore_ascii_case("FALSE") => Value::Boolean(false),
+        _ => Value::String(parameter.to_string()),
+    }
+}
+
+// The filter expressions of the remote readers are parsed as well, so the nesting of the
+// parentheses and NOT operators is bounded to keep the recursion of the parser from overflowing
+// the stack.
+const MAX_NESTING_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    parameters: &'a [String],
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn next_is_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Identifier(i)) if i.eq_ignore_ascii_case(keyword) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_nested(
+        &mut self,
+        parse: fn(&mut Self) -> DdsResult<Condition>,
+    ) -> DdsResult<Condition> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(DdsError::BadParameter);
+        }
+        self.depth += 1;
+        let condition = parse(self);
+        self.depth -= 1;
+        condition
+    }
+
+    fn parse_or(&mut self) -> DdsResult<Condition> {
+        let mut conditions = vec![self.parse_and()?];
+        while self.next_is_keyword("OR") {
+            conditions.push(self.parse_and()?);
+        }
+        Ok(match conditions.len() {
+            1 => conditions.remove(0),
+            _ => Condition::Or(conditions),
+        })
+    }
+
+    fn parse_and(&mut self) -> DdsResult<Condition> {
+        let mut conditions = vec![self.parse_not()?];
+        while self.next_is_keyword("AND") {
+            conditions.push(self.parse_not()?);
+        }
+        Ok(match conditions.len() {
+            1 => conditions.remove(0),
+            _ => Condition::And(conditions),
+        })
+    }
+
+    fn parse_not(&mut self) -> DdsResult<Condition> {
+        if self.next_is_keyword("NOT") {
+            Ok(Condition::Not(Box::new(
+                self.parse_nested(Self::parse_not)?,
+            )))
+        } else {
+            self.parse_predicate()
+        }
+    }
+
+    fn parse_predicate(&mut self) -> DdsResult<Condition> {
+        if self.peek() == Some(&Token::OpenParenthesis) {
+            self.position += 1;
+            let condition = self.parse_nested(Self::parse_or)?;
+            return match self.next() {
+                Some(Token::CloseParenthesis) => Ok(condition),
+                _ => Err(DdsError::BadParameter),
+            };
+        }
+
+        let left = self.parse_operand()?;
+        if let Some(&Token::Operator(operator)) = self.peek() {
+            self.position += 1;
+            return Ok(Condition::Compare(left, operator, self.parse_operand()?));
+        }
+        let negated = self.next_is_keyword("NOT");
+        let condition = if self.next_is_keyword("BETWEEN") {
+            let low = self.parse_operand()?;
+            if !self.next_is_keyword("AND") {
+                return Err(DdsError::BadParameter);
+            }
+            Condition::Between(left, low, self.parse_operand()?)
+        } else if self.next_is_keyword("LIKE") {
+            Condition::Like(left, self.parse_operand()?)
+        } else {
+            return Err(DdsError::BadParameter);
+        };
+        if negated {
+            Ok(Condition::Not(Box::new(condition)))
+        } else {
+            Ok(condition)
+        }
+    }
+
+    fn parse_operand(&mut self) -> DdsResult<Operand> {
+        let parameters = self.parameters;
+        match self.next() {
+            Some(Token::Identifier(i)) if i.eq_ignore_ascii_case("TRUE") => {
+                Ok(Operand::Value(Value::Boolean(true)))
+            }
+            Some(Token::Identifier(i)) if i.eq_ignore_ascii_case("FALSE") => {
+                Ok(Operand::Value(Value::Boolean(false)))
+            }
+            Some(Token::Identifier(i)) => Ok(Operand::Field(i.clone())),
+            Some(Token::Integer(v)) => Ok(Operand::Value(Value::Integer(*v))),
+            Some(Token::Float(v)) => Ok(Operand::Value(Value::Float(*v))),
+            Some(Token::String(v)) => Ok(Operand::Value(Value::String(v.clone()))),
+            Some(Token::Parameter(index)) => parameters
+                .get(*index)
+                .map(|p| Operand::Value(parse_parameter(p)))
+                .ok_or(DdsError::BadParameter),
+            _ => Err(DdsError::BadParameter),
+        }
+    }
+}
+
+// Matches the value with the pattern going back only to the last '%' seen when a character does
+// not match, which takes at most a number of steps proportional to the product of their lengths.
+fn is_like(value: &[char], pattern: &[char]) -> bool {
+    let (mut v, mut p) = (0, 0);
+    // Position in the pattern after the last '%' and position in the value it is matched up to
+    let mut last_percent = None;
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                p += 1;
+                last_percent = Some((p, v));
+            }
+            Some(&c) if c == '_' || c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match last_percent {
+                Some((percent_p, percent_v)) => {
+                    p = percent_p;
+                    v = percent_v + 1;
+                    last_percent = Some((percent_p, v));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '%')
+}
+
+impl Condition {
+    fn fields<'a>(&'a self, fields: &mut Vec<&'a str>) {
+        let mut push = |operand: &'a Operand| {
+            if let Operand::Field(f) = operand {
+                fields.push(f);
+            }
+        };
+        match self {
+            Condition::And(conditions) | Condition::Or(conditions) => {
+                for condition in conditions {
+                    condition.fields(fields);
+                }
+            }
+            Condition::Not(a) => a.fields(fields),
+            Condition::Compare(a, _, b) | Condition::Like(a, b) => {
+                push(a);
+                push(b);
+            }
+            Condition::Between(a, b, c) => {
+                push(a);
+                push(b);
+                push(c);
+            }
+        }
+    }
+
+    fn evaluate(&self, fields: &HashMap<String, Value>) -> Option<bool> {
+        let value = |operand: &Operand| match operand {
+            Operand::Field(f) => fields.get(f).cloned(),
+            Operand::Value(v) => Some(v.clone()),
+        };
+        Some(match self {
+            // Evaluated up to the first condition which is not true or not false respectively
+            Condition::And(conditions) => conditions
+                .iter()
+                .map(|c| c.evaluate(fields))
+                .find(|r| *r != Some(true))
+                .unwrap_or(Some(true))?,
+            Condition::Or(conditions) => conditions
+                .iter()
+                .map(|c| c.evaluate(fields))
+                .find(|r| *r != Some(false))
+                .unwrap_or(Some(false))?,
+            Condition::Not(a) => !a.evaluate(fields)?,
+            Condition::Compare(a, operator, b) => {
+                let ordering = value(a)?.partial_cmp(&value(b)?)?;
+                match operator {
+                    RelationalOperator::Equal => ordering == Ordering::Equal,
+                    RelationalOperator::NotEqual => ordering != Ordering::Equal,
+                    RelationalOperator::Less => ordering == Ordering::Less,
+                    RelationalOperator::LessOrEqual => ordering != Ordering::Greater,
+                    RelationalOperator::Greater => ordering == Ordering::Greater,
+                    RelationalOperator::GreaterOrEqual => ordering != Ordering::Less,
+                }
+            }
+            Condition::Between(a, low, high) => {
+                let a = value(a)?;
+                a.partial_cmp(&value(low)?)? != Ordering::Less
+                    && a.partial_cmp(&value(high)?)? != Ordering::Greater
+            }
+            Condition::Like(a, pattern) => match (value(a)?, value(pattern)?) {
+                (Value::String(a), Value::String(pattern)) => is_like(
+                    &a.chars().collect::<Vec<_>>(),
+                    &pattern.chars().collect::<Vec<_>>(),
+                ),
+                _ => return None,
+            },
+        })
+    }
+}
+
+// Reads the members of the type from the serialized data and keeps the values of the fields used
+// by the filter. The fields of nested structures are named with their path separated by dots.
+fn read_fields<'a, T>(
+    dynamic_type: &dyn DynamicType,
+    prefix: &str,
+    wanted: &[&str],
+    de: &mut T,
+    fields: &mut HashMap<String, Value>,
+) -> Result<(), XTypesError>
+where
+    for<'b> &'b mut T: XTypesDeserializer<'a>,
+{
+    for member_descriptor in dynamic_type.into_iter() {
+        let member_descriptor = member_descriptor?;
+        let name = format!("{}{}", prefix, member_descriptor.name);
+        read_field(member_descriptor.type_, &name, wanted, de, fields)?;
+    }
+    Ok(())
+}
+
+fn read_field<'a, T>(
+    type_identifier: &TypeIdentifier,
+    name: &str,
+    wanted: &[&str],
+    de: &mut T,
+    fields: &mut HashMap<String, Value>,
+) -> Result<(), XTypesError>
+where
+    for<'b> &'b mut T: XTypesDeserializer<'a>,
+{
+    let value = match type_identifier {
+        TypeIdentifier::TkBoolean => Value::Boolean(de.deserialize_boolean()?),
+        TypeIdentifier::TkByteType | TypeIdentifier::TkUint8Type => {
+            Value::Integer(de.deserialize_uint8()?.into())
+        }
+        TypeIdentifier::TkInt8Type => Value::Integer(de.deserialize_int8()?.into()),
+        TypeIdentifier::TkInt16Type => Value::Integer(de.deserialize_int16()?.into()),
+        TypeIdentifier::TkInt32Type => Value::Integer(de.deserialize_int32()?.into()),
+        TypeIdentifier::TkInt64Type => Value::Integer(de.deserialize_int64()?.into()),
+        TypeIdentifier::TkUint16Type => Value::Integer(de.deserialize_uint16()?.into()),
+        TypeIdentifier::TkUint32Type => Value::Integer(de.deserialize_uint32()?.into()),
+        TypeIdentifier::TkUint64Type => Value::Integer(de.deserialize_uint64()?.into()),
+        TypeIdentifier::TkFloat32Type => Value::Float(de.deserialize_float32()?.into()),
+        TypeIdentifier::TkFloat64Type => Value::Float(de.deserialize_float64()?),
+        TypeIdentifier::TkChar8Type => Value::String(de.deserialize_char8()?.to_string()),
+        TypeIdentifier::TiString8Small { .. } | TypeIdentifier::TiString8Large { .. } => {
+            Value::String(de.deserialize_string()?.to_string())
+        }
+        TypeIdentifier::TiPlainSequenceSmall { seq_sdefn } => {
+            let len = de.deserialize_sequence()?.len();
+            for _ in 0..len {
+                read_field(&seq_sdefn.element_identifier, "", &[], de, fields)?;
+            }
+            return Ok(());
+        }
+        TypeIdentifier::TiPlainArraySmall { array_sdefn } => {
+            let len: u32 = array_sdefn
+                .array_bound_seq
+                .iter()
+                .map(|&b| b as u32)
+                .product();
+            for _ in 0..len {
+                read_field(&array_sdefn.element_identifier, "", &[], de, fields)?;
+            }
+            return Ok(());
+        }
+        TypeIdentifier::EkComplete { complete } => {
+            return read_fields(complete.as_ref(), &format!("{}.", name), wanted, de, fields);
+        }
+        _ => return Err(XTypesError::InvalidData),
+    };
+    if wanted.contains(&name) {
+        fields.insert(name.to_string(), value);
+    }
+    Ok(())
+}
+
+type RepresentationIdentifier = [u8; 2];
+const CDR_BE: RepresentationIdentifier = [0x00, 0x00];
+const CDR_LE: RepresentationIdentifier = [0x00, 0x01];
+const CDR2_BE: RepresentationIdentifier = [0x00, 0x06];
+const CDR2_LE: RepresentationIdentifier = [0x00, 0x07];
+const D_CDR2_BE: RepresentationIdentifier = [0x00, 0x08];
+const D_CDR2_LE: RepresentationIdentifier = [0x00, 0x09];
+
+/// Filter expression of the DDSSQL filter class with its parameters already substituted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlFilter {
+    condition: Condition,
+}
+
+impl SqlFilter {
+    pub fn new(filter_expression: &str, expression_parameters: &[String]) -> DdsResult<Self> {
+        let tokens = tokenize(filter_expression)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            parameters: expression_parameters,
+            depth: 0,
+        };
+        let condition = parser.parse_or()?;
+        if parser.position != tokens.len() {
+            return Err(DdsError::BadParameter);
+        }
+        Ok(Self { condition })
+    }
+
+    /// Whether the serialized sample passes the filter. Samples using a representation or member
+    /// types which can not be evaluated return an error.
+    pub fn evaluate(&self, data: &[u8], dynamic_type: &dyn DynamicType) -> DdsResult<bool> {
+        let mut wanted = Vec::new();
+        self.condition.fields(&mut wanted);
+        let mut fields = HashMap::new();
+        if data.len() < 4 {
+            return Err(XTypesError::InvalidData.into());
+        }
+        let representation_identifier = [data[0], data[1]];
+        let data = &data[4..];
+        match representation_identifier {
+            CDR_BE => read_fields(
+                dynamic_type,
+                "",
+                &wanted,
+                &mut Xcdr1BeDeserializer::new(data),
+                &mut fields,
+            )?,
+            CDR_LE => read_fields(
+                dynamic_type,
+                "",
+                &wanted,
+                &mut Xcdr1LeDeserializer::new(data),
+                &mut fields,
+            )?,
+            CDR2_BE => read_fields(
+                dynamic_type,
+                "",
+                &wanted,
+                &mut Xcdr2BeDeserializer::new(data),
+                &mut fields,
+            )?,
+            CDR2_LE => read_fields(
+                dynamic_type,
+                "",
+                &wanted,
+                &mut Xcdr2LeDeserializer::new(data),
+                &mut fields,
+            )?,
+            D_CDR2_BE => read_fields(
+                dynamic_type,
+                "",
+                &wanted,
+                &mut Xcdr2BeDeserializer::new(delimited_body_v2(data, u32::from_be_bytes)?),
+                &mut fields,
+            )?,
+            D_CDR2_LE => read_fields(
+                dynamic_type,
+                "",
+                &wanted,
+                &mut Xcdr2LeDeserializer::new(delimited_body_v2(data, u32::from_le_bytes)?),
+                &mut fields,
+            )?,
+            _ => return Err(XTypesError::InvalidData.into()),
+        }
+        self.condition
+            .evaluate(&fields)
+            .ok_or(DdsError::BadParameter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        topic_definition::type_support::TypeSupport,
+        xtypes::{serialize::XTypesSerialize, xcdr_serializer::Xcdr1LeSerializer},
+    };
+    use dust_dds_derive::TypeSupport;
+
+    #[derive(TypeSupport, XTypesSerialize)]
+    struct Inner {
+        level: i32,
+    }
+
+    #[derive(TypeSupport, XTypesSerialize)]
+    struct Shape {
+        color: String,
+        x: i32,
+        y: i32,
+        size: f64,
+        points: Vec<u16>,
+        inner: Inner,
+    }
+
+    fn serialize(value: &impl XTypesSerialize) -> Vec<u8> {
+        let mut data = vec![0x00, 0x01, 0x00, 0x00];
+        value
+            .serialize(&mut Xcdr1LeSerializer::new(&mut data))
+            .unwrap();
+        data
+    }
+
+    fn shape() -> Vec<u8> {
+        serialize(&Shape {
+            color: "BLUE".to_string(),
+            x: 10,
+            y: 200,
+            size: 2.5,
+            points: vec![1, 2, 3],
+            inner: Inner { level: 3 },
+        })
+    }
+
+    fn evaluate(expression: &str, parameters: &[&str]) -> bool {
+        let parameters: Vec<String> = parameters.iter().map(|p| p.to_string()).collect();
+        SqlFilter::new(expression, &parameters)
+            .unwrap()
+            .evaluate(&shape(), &Shape::get_type())
+            .unwrap()
+    }
+
+    #[test]
+    fn relational_operators() {
+        assert!(evaluate("x = 10", &[]));
+        assert!(evaluate("x <> 11", &[]));
+        assert!(evaluate("x < 11 AND x <= 10 AND x > 9 AND x >= 10", &[]));
+        assert!(!evaluate("x > 10", &[]));
+        assert!(evaluate("size > 2", &[]));
+        assert!(evaluate("color = 'BLUE'", &[]));
+        assert!(evaluate("10 = x", &[]));
+    }
+
+    #[test]
+    fn logical_operators() {
+        assert!(evaluate("x = 1 OR y = 200", &[]));
+        assert!(!evaluate("x = 1 AND y = 200", &[]));
+        assert!(evaluate("NOT (x = 1 AND y = 200)", &[]));
+        assert!(evaluate("(x = 1 OR x = 10) AND color = 'BLUE'", &[]));
+    }
+
+    #[test]
+    fn between_and_like() {
+        assert!(evaluate("y BETWEEN 100 AND 300", &[]));
+        assert!(evaluate("y NOT BETWEEN 0 AND 100", &[]));
+        assert!(evaluate("color LIKE 'B%'", &[]));
+        assert!(evaluate("color LIKE '_LU_'", &[]));
+        assert!(!evaluate("color LIKE 'R%'", &[]));
+    }
+
+    #[test]
+    fn parameters() {
+        assert!(evaluate("x = %0 AND color = %1", &["10", "'BLUE'"]));
+        assert!(!evaluate("x > %0", &["10"]));
+    }
+
+    #[test]
+    fn nested_field_after_sequence() {
+        assert!(evaluate("inner.level = 3", &[]));
+    }
+
+    #[test]
+    fn invalid_expression() {
+        assert!(SqlFilter::new("x = ", &[]).is_err());
+        assert!(SqlFilter::new("x = %1", &["1".to_string()]).is_err());
+        assert!(SqlFilter::new("(x = 1", &[]).is_err());
+    }
+
+    #[test]
+    fn deeply_nested_expression_is_rejected() {
+        // Each repetition nests a NOT and a parenthesis
+        let nested = |repetitions: usize| {
+            format!(
+                "{}x = 10{}",
+                "NOT (".repeat(repetitions),
+                ")".repeat(repetitions)
+            )
+        };
+        assert!(evaluate(&nested(MAX_NESTING_DEPTH / 2), &[]));
+        assert_eq!(
+            SqlFilter::new(&nested(MAX_NESTING_DEPTH / 2 + 1), &[]),
+            Err(DdsError::BadParameter)
+        );
+        let parenthesized = format!("{}x = 10{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert_eq!(
+            SqlFilter::new(&parenthesized, &[]),
+            Err(DdsError::BadParameter)
+        );
+        let negated = format!("{}x = 10", "NOT ".repeat(100_000));
+        assert_eq!(SqlFilter::new(&negated, &[]), Err(DdsError::BadParameter));
+    }
+
+    #[test]
+    fn long_chain_of_conditions() {
+        let chain = vec!["x = 1"; 10_000].join(" OR ") + " OR x = 10";
+        assert!(evaluate(&chain, &[]));
+        let chain = vec!["x = 10"; 10_000].join(" AND ") + " AND x = 1";
+        assert!(!evaluate(&chain, &[]));
+    }
+
+    #[test]
+    fn like_patterns() {
+        let is_like = |value: &str, pattern: &str| {
+            is_like(
+                &value.chars().collect::<Vec<_>>(),
+                &pattern.chars().collect::<Vec<_>>(),
+            )
+        };
+        assert!(is_like("", ""));
+        assert!(is_like("", "%"));
+        assert!(!is_like("", "_"));
+        assert!(is_like("abc", "abc"));
+        assert!(is_like("abc", "a%"));
+        assert!(is_like("abc", "%c"));
+        assert!(is_like("abc", "a_c"));
+        assert!(is_like("abc", "%%b%%"));
+        assert!(is_like("xaxbxab", "%a%b"));
+        assert!(is_like("aab", "%ab"));
+        assert!(!is_like("abc", "ab"));
+        assert!(!is_like("abc", "%b"));
+        assert!(!is_like("ab", "a_c"));
+
+        // A pattern with many '%' is matched without backtracking through all its combinations
+        let value = "a".repeat(10_000);
+        assert!(!is_like(&value, "%a%a%a%a%a%a%a%a%a%a%b"));
+        assert!(is_like(&value, "%a%a%a%a%a%a%a%a%a%a%"));
+    }
+
+    #[test]
+    fn unknown_field_can_not_be_evaluated() {
+        assert!(SqlFilter::new("z = 1", &[])
+            .unwrap()
+            .evaluate(&shape(), &Shape::get_type())
+            .is_err());
+    }
+}
@@ -0,0 +1,787 @@
+use crate::xtypes::{
+    dynamic_type::{DynamicType, ExtensibilityKind, MemberDescriptor, TryConstructKind},
+    error::XTypesError,
+    type_object::{
+        CollectionElementFlag, EquivalenceHash, EquivalenceKind, NameHash, TypeIdentifier,
+        TypeIdentifierWithDependencies, TypeIdentifierWithSize, TypeInformation, TypeObjectHashId,
+        EK_BOTH, EK_COMPLETE, EK_MINIMAL, TI_PLAIN_ARRAY_LARGE, TI_PLAIN_ARRAY_SMALL,
+        TI_PLAIN_MAP_LARGE, TI_PLAIN_MAP_SMALL, TI_PLAIN_SEQUENCE_LARGE, TI_PLAIN_SEQUENCE_SMALL,
+        TI_STRING16_LARGE, TI_STRING16_SMALL, TI_STRING8_LARGE, TI_STRING8_SMALL, TK_NONE,
+        TK_STRUCTURE,
+    },
+};
+
+// Flags of the struct types as defined in XTypes 7.3.4.3 "TypeFlag"
+const IS_FINAL: u16 = 1 << 0;
+const IS_APPENDABLE: u16 = 1 << 1;
+const IS_MUTABLE: u16 = 1 << 2;
+const IS_NESTED: u16 = 1 << 3;
+
+// Flags of the members as defined in XTypes 7.3.4.3 "MemberFlag"
+const TRY_CONSTRUCT1: u16 = 1 << 0;
+const TRY_CONSTRUCT2: u16 = 1 << 1;
+const IS_EXTERNAL: u16 = 1 << 2;
+const IS_OPTIONAL: u16 = 1 << 3;
+const IS_MUST_UNDERSTAND: u16 = 1 << 4;
+const IS_KEY: u16 = 1 << 5;
+
+// Length code of the EMHEADER whose member size is given by the NEXTINT
+const LC_NEXTINT: u32 = 4;
+
+// Member ids of the TypeInformation as defined in its IDL
+const TYPE_INFORMATION_MINIMAL_ID: u32 = 0x1001;
+const TYPE_INFORMATION_COMPLETE_ID: u32 = 0x1002;
+
+// Writer of the XCDR version 2 little endian encoding used by the TypeObject and the
+// TypeLookup service. The alignment is relative to the start of the buffer and it is
+// never larger than 4 bytes.
+#[derive(Default)]
+pub struct Xcdr2Writer {
+    buffer: Vec<u8>,
+}
+
+impl Xcdr2Writer {
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    pub fn align(&mut self, alignment: usize) {
+        while !self.buffer.len().is_multiple_of(alignment) {
+            self.buffer.push(0);
+        }
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.align(2);
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.align(4);
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_i32(&mut self, value: i32) {
+        self.write_u32(value as u32);
+    }
+
+    pub fn write_bytes(&mut self, value: &[u8]) {
+        self.buffer.extend_from_slice(value);
+    }
+
+    pub fn write_string(&mut self, value: &str) {
+        self.write_u32(value.len() as u32 + 1);
+        self.buffer.extend_from_slice(value.as_bytes());
+        self.buffer.push(0);
+    }
+
+    // Writes a placeholder for the DHEADER whose value is set by end_dheader
+    pub fn begin_dheader(&mut self) -> usize {
+        self.write_u32(0);
+        self.buffer.len() - 4
+    }
+
+    pub fn end_dheader(&mut self, position: usize) {
+        let length = (self.buffer.len() - position - 4) as u32;
+        self.buffer[position..position + 4].copy_from_slice(&length.to_le_bytes());
+    }
+
+    // Writes the EMHEADER of a member of a mutable type followed by the NEXTINT with the member
+    // size. The NEXTINT is finished with end_dheader.
+    pub fn begin_member(&mut self, member_id: u32) -> usize {
+        self.write_u32(LC_NEXTINT << 28 | member_id);
+        self.begin_dheader()
+    }
+}
+
+// Reader of the XCDR version 2 little endian encoding written by the Xcdr2Writer
+pub struct Xcdr2Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Xcdr2Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: usize) -> Result<(), XTypesError> {
+        if position > self.data.len() {
+            return Err(XTypesError::InvalidData);
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    pub fn align(&mut self, alignment: usize) -> Result<(), XTypesError> {
+        self.set_position(self.position.next_multiple_of(alignment))
+    }
+
+    pub fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], XTypesError> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + length)
+            .ok_or(XTypesError::InvalidData)?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, XTypesError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, XTypesError> {
+        self.align(4)?;
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32, XTypesError> {
+        Ok(self.read_u32()? as i32)
+    }
+
+    pub fn read_string(&mut self) -> Result<&'a str, XTypesError> {
+        let length = self.read_u32()? as usize;
+        let bytes = self.read_bytes(length)?;
+        core::str::from_utf8(bytes.strip_suffix(&[0]).unwrap_or(bytes))
+            .map_err(|_| XTypesError::InvalidData)
+    }
+
+    // Reads a DHEADER and returns the position where the delimited type ends
+    pub fn read_dheader(&mut self) -> Result<usize, XTypesError> {
+        let length = self.read_u32()? as usize;
+        let end = self.position + length;
+        if end > self.data.len() {
+            return Err(XTypesError::InvalidData);
+        }
+        Ok(end)
+    }
+
+    // Reads the EMHEADER of a member of a mutable type and returns the member id and the
+    // position where the member ends
+    pub fn read_member_header(&mut self) -> Result<(u32, usize), XTypesError> {
+        let emheader = self.read_u32()?;
+        let member_id = emheader & 0x0fff_ffff;
+        let length = match (emheader >> 28) & 0x7 {
+            0 => 1,
+            1 => 2,
+            2 => 4,
+            3 => 8,
+            LC_NEXTINT => self.read_u32()? as usize,
+            // The NEXTINT is also the first element of the member so it is not consumed
+            length_code => {
+                let next_int = self.read_u32()? as usize;
+                self.position -= 4;
+                4 + match length_code {
+                    5 => next_int,
+                    6 => 4 * next_int,
+                    _ => 8 * next_int,
+                }
+            }
+        };
+        let end = self.position + length;
+        if end > self.data.len() {
+            return Err(XTypesError::InvalidData);
+        }
+        Ok((member_id, end))
+    }
+}
+
+// First 4 bytes of the MD5 of the member name
+pub fn name_hash(name: &str) -> NameHash {
+    let digest = md5::compute(name.as_bytes());
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+// Member id of the members annotated with @hashid
+pub fn hash_member_id(name: &str) -> u32 {
+    u32::from_le_bytes(name_hash(name)) & 0x0fff_ffff
+}
+
+fn try_construct_flags(try_construct_kind: TryConstructKind) -> u16 {
+    match try_construct_kind {
+        TryConstructKind::Discard => TRY_CONSTRUCT1,
+        TryConstructKind::UseDefault => TRY_CONSTRUCT2,
+        TryConstructKind::Trim => TRY_CONSTRUCT1 | TRY_CONSTRUCT2,
+    }
+}
+
+fn member_flags(member: &MemberDescriptor) -> u16 {
+    let mut flags = try_construct_flags(member.try_construct_kind);
+    if member.is_optional {
+        flags |= IS_OPTIONAL;
+    }
+    if member.is_must_understand {
+        flags |= IS_MUST_UNDERSTAND;
+    }
+    if member.is_key {
+        flags |= IS_KEY;
+    }
+    flags
+}
+
+fn collection_element_flags(flags: &CollectionElementFlag) -> u16 {
+    let mut element_flags = try_construct_flags(flags.try_construct);
+    if flags.is_external {
+        element_flags |= IS_EXTERNAL;
+    }
+    element_flags
+}
+
+// A type is fully descriptive when its TypeIdentifier does not depend on any TypeObject hash.
+// The collections of fully descriptive elements are the same for the minimal and complete
+// representations.
+fn is_fully_descriptive(type_identifier: &TypeIdentifier) -> bool {
+    match type_identifier {
+        TypeIdentifier::TiPlainSequenceSmall { seq_sdefn } => {
+            is_fully_descriptive(&seq_sdefn.element_identifier)
+        }
+        TypeIdentifier::TiPlainSequenceLarge { seq_ldefn } => {
+            is_fully_descriptive(&seq_ldefn.element_identifier)
+        }
+        TypeIdentifier::TiPlainArraySmall { array_sdefn } => {
+            is_fully_descriptive(&array_sdefn.element_identifier)
+        }
+        TypeIdentifier::TiPlainArrayLarge { array_ldefn } => {
+            is_fully_descriptive(&array_ldefn.element_identifier)
+        }
+        TypeIdentifier::TiPlainMapSmall { map_sdefn } => {
+            is_fully_descriptive(&map_sdefn.element_identifier)
+                && is_fully_descriptive(&map_sdefn.key_identifier)
+        }
+        TypeIdentifier::TiPlainMapLarge { map_ldefn } => {
+            is_fully_descriptive(&map_ldefn.element_identifier)
+                && is_fully_descriptive(&map_ldefn.key_identifier)
+        }
+        TypeIdentifier::TiStronglyConnectedComponent { .. }
+        | TypeIdentifier::EkComplete { .. }
+        | TypeIdentifier::EkMinimal { .. } => false,
+        _ => true,
+    }
+}
+
+// The equivalence kind of the PlainCollectionHeader is derived from the element since it
+// depends on the representation being serialized
+fn write_collection_header(
+    writer: &mut Xcdr2Writer,
+    element_flags: &CollectionElementFlag,
+    element_identifier: &TypeIdentifier,
+    equivalence_kind: EquivalenceKind,
+) {
+    if is_fully_descriptive(element_identifier) {
+        writer.write_u8(EK_BOTH);
+    } else {
+        writer.write_u8(equivalence_kind);
+    }
+    writer.write_u16(collection_element_flags(element_flags));
+}
+
+fn write_type_identifier(
+    writer: &mut Xcdr2Writer,
+    type_identifier: &TypeIdentifier,
+    equivalence_kind: EquivalenceKind,
+) -> Result<(), XTypesError> {
+    match type_identifier {
+        TypeIdentifier::TiString8Small { string_sdefn } => {
+            writer.write_u8(TI_STRING8_SMALL);
+            writer.write_u8(string_sdefn.bound);
+        }
+        TypeIdentifier::TiString16Small { string_sdefn } => {
+            writer.write_u8(TI_STRING16_SMALL);
+            writer.write_u8(string_sdefn.bound);
+        }
+        TypeIdentifier::TiString8Large { string_ldefn } => {
+            writer.write_u8(TI_STRING8_LARGE);
+            writer.write_u32(string_ldefn.bound);
+        }
+        TypeIdentifier::TiString16Large { string_ldefn } => {
+            writer.write_u8(TI_STRING16_LARGE);
+            writer.write_u32(string_ldefn.bound);
+        }
+        TypeIdentifier::TiPlainSequenceSmall { seq_sdefn } => {
+            writer.write_u8(TI_PLAIN_SEQUENCE_SMALL);
+            write_collection_header(
+                writer,
+                &seq_sdefn.header.element_flags,
+                &seq_sdefn.element_identifier,
+                equivalence_kind,
+            );
+            writer.write_u8(seq_sdefn.bound);
+            write_type_identifier(writer, &seq_sdefn.element_identifier, equivalence_kind)?;
+        }
+        TypeIdentifier::TiPlainSequenceLarge { seq_ldefn } => {
+            writer.write_u8(TI_PLAIN_SEQUENCE_LARGE);
+            write_collection_header(
+                writer,
+                &seq_ldefn.header.element_flags,
+                &seq_ldefn.element_identifier,
+                equivalence_kind,
+            );
+            writer.write_u32(seq_ldefn.bound);
+            write_type_identifier(writer, &seq_ldefn.element_identifier, equivalence_kind)?;
+        }
+        TypeIdentifier::TiPlainArraySmall { array_sdefn } => {
+            writer.write_u8(TI_PLAIN_ARRAY_SMALL);
+            write_collection_header(
+                writer,
+                &array_sdefn.header.element_flags,
+                &array_sdefn.element_identifier,
+                equivalence_kind,
+            );
+            writer.write_u32(array_sdefn.array_bound_seq.len() as u32);
+            writer.write_bytes(&array_sdefn.array_bound_seq);
+            write_type_identifier(writer, &array_sdefn.element_identifier, equivalence_kind)?;
+        }
+        TypeIdentifier::TiPlainArrayLarge { array_ldefn } => {
+            writer.write_u8(TI_PLAIN_ARRAY_LARGE);
+            write_collection_header(
+                writer,
+                &array_ldefn.header.element_flags,
+                &array_ldefn.element_identifier,
+                equivalence_kind,
+            );
+            writer.write_u32(array_ldefn.array_bound_seq.len() as u32);
+            for &bound in &array_ldefn.array_bound_seq {
+                writer.write_u32(bound);
+            }
+            write_type_identifier(writer, &array_ldefn.element_identifier, equivalence_kind)?;
+        }
+        TypeIdentifier::TiPlainMapSmall { map_sdefn } => {
+            writer.write_u8(TI_PLAIN_MAP_SMALL);
+            write_collection_header(
+                writer,
+                &map_sdefn.header.element_flags,
+                &map_sdefn.element_identifier,
+                equivalence_kind,
+            );
+            writer.write_u8(map_sdefn.bound);
+            write_type_identifier(writer, &map_sdefn.element_identifier, equivalence_kind)?;
+            writer.write_u16(collection_element_flags(&map_sdefn.key_flags));
+            write_type_identifier(writer, &map_sdefn.key_identifier, equivalence_kind)?;
+        }
+        TypeIdentifier::TiPlainMapLarge { map_ldefn } => {
+            writer.write_u8(TI_PLAIN_MAP_LARGE);
+            write_collection_header(
+                writer,
+                &map_ldefn.header.element_flags,
+                &map_ldefn.element_identifier,
+                equivalence_kind,
+            );
+            writer.write_u32(map_ldefn.bound);
+            write_type_identifier(writer, &map_ldefn.element_identifier, equivalence_kind)?;
+            writer.write_u16(collection_element_flags(&map_ldefn.key_flags));
+            write_type_identifier(writer, &map_ldefn.key_identifier, equivalence_kind)?;
+        }
+        TypeIdentifier::TiStronglyConnectedComponent { .. } | TypeIdentifier::EkMinimal { .. } => {
+            return Err(XTypesError::InvalidData)
+        }
+        TypeIdentifier::EkComplete { complete } => {
+            let type_id = type_identifier_with_size(complete.as_ref(), equivalence_kind)?.type_id;
+            write_type_object_hash_id(writer, &type_id);
+        }
+        // The TypeIdentifier of the primitive types is its TypeKind
+        primitive_type => writer.write_u8(primitive_type.get_kind()),
+    }
+    Ok(())
+}
+
+pub fn write_type_object_hash_id(writer: &mut Xcdr2Writer, type_id: &TypeObjectHashId) {
+    let (equivalence_kind, hash) = match type_id {
+        TypeObjectHashId::EkComplete { hash } => (EK_COMPLETE, hash),
+        TypeObjectHashId::EkMinimal { hash } => (EK_MINIMAL, hash),
+    };
+    writer.write_u8(equivalence_kind);
+    writer.write_bytes(hash);
+}
+
+pub fn read_type_object_hash_id(reader: &mut Xcdr2Reader) -> Result<TypeObjectHashId, XTypesError> {
+    let equivalence_kind = reader.read_u8()?;
+    let mut hash = EquivalenceHash::default();
+    hash.copy_from_slice(reader.read_bytes(size_of::<EquivalenceHash>())?);
+    match equivalence_kind {
+        EK_COMPLETE => Ok(TypeObjectHashId::EkComplete { hash }),
+        EK_MINIMAL => Ok(TypeObjectHashId::EkMinimal { hash }),
+        _ => Err(XTypesError::InvalidData),
+    }
+}
+
+// Serializes the Complete or Minimal TypeObject of the type with the XCDR version 2 little
+// endian encoding. Only the structures are supported.
+pub fn serialize_type_object(
+    dynamic_type: &dyn DynamicType,
+    equivalence_kind: EquivalenceKind,
+) -> Result<Vec<u8>, XTypesError> {
+    if dynamic_type.get_kind() != TK_STRUCTURE {
+        return Err(XTypesError::InvalidData);
+    }
+    let descriptor = dynamic_type.get_descriptor()?;
+    let mut writer = Xcdr2Writer::default();
+
+    // TypeObject: appendable union
+    let type_object = writer.begin_dheader();
+    writer.write_u8(equivalence_kind);
+    // CompleteTypeObject / MinimalTypeObject: final union
+    writer.write_u8(TK_STRUCTURE);
+
+    // struct_flags
+    let mut struct_flags = match descriptor.extensibility_kind {
+        ExtensibilityKind::Final => IS_FINAL,
+        ExtensibilityKind::Appendable => IS_APPENDABLE,
+        ExtensibilityKind::Mutable => IS_MUTABLE,
+    };
+    if descriptor.is_nested {
+        struct_flags |= IS_NESTED;
+    }
+    writer.write_u16(struct_flags);
+
+    // header: appendable struct
+    let header = writer.begin_dheader();
+    writer.write_u8(TK_NONE); // base_type
+    if equivalence_kind == EK_COMPLETE {
+        writer.write_bool(false); // ann_builtin
+        writer.write_bool(false); // ann_custom
+        writer.write_string(&descriptor.name);
+    }
+    writer.end_dheader(header);
+
+    // member_seq: sequence of appendable structs
+    let member_seq = writer.begin_dheader();
+    writer.write_u32(dynamic_type.get_member_count());
+    for member in dynamic_type {
+        let member = member?;
+        let struct_member = writer.begin_dheader();
+        writer.write_u32(member.id);
+        writer.write_u16(member_flags(&member));
+        write_type_identifier(&mut writer, member.type_, equivalence_kind)?;
+        if equivalence_kind == EK_COMPLETE {
+            writer.write_string(&member.name);
+            writer.write_bool(false); // ann_builtin
+            writer.write_bool(false); // ann_custom
+        } else {
+            writer.write_bytes(&name_hash(&member.name));
+        }
+        writer.end_dheader(struct_member);
+    }
+    writer.end_dheader(member_seq);
+
+    writer.end_dheader(type_object);
+    Ok(writer.into_bytes())
+}
+
+// The TypeIdentifier of a TypeObject is the first 14 bytes of the MD5 of its serialization
+pub fn type_object_hash_id(
+    serialized_type_object: &[u8],
+    equivalence_kind: EquivalenceKind,
+) -> TypeObjectHashId {
+    let digest = md5::compute(serialized_type_object);
+    let mut hash = EquivalenceHash::default();
+    hash.copy_from_slice(&digest[..size_of::<EquivalenceHash>()]);
+    if equivalence_kind == EK_MINIMAL {
+        TypeObjectHashId::EkMinimal { hash }
+    } else {
+        TypeObjectHashId::EkComplete { hash }
+    }
+}
+
+pub fn type_identifier_with_size(
+    dynamic_type: &dyn DynamicType,
+    equivalence_kind: EquivalenceKind,
+) -> Result<TypeIdentifierWithSize, XTypesError> {
+    let type_object = serialize_type_object(dynamic_type, equivalence_kind)?;
+    Ok(TypeIdentifierWithSize {
+        type_id: type_object_hash_id(&type_object, equivalence_kind),
+        typeobject_serialized_size: type_object.len() as u32,
+    })
+}
+
+fn add_dependencies<'a>(
+    type_identifier: &'a TypeIdentifier,
+    dependencies: &mut Vec<&'a dyn DynamicType>,
+) -> Result<(), XTypesError> {
+    match type_identifier {
+        TypeIdentifier::TiPlainSequenceSmall { seq_sdefn } => {
+            add_dependencies(&seq_sdefn.element_identifier, dependencies)
+        }
+        TypeIdentifier::TiPlainSequenceLarge { seq_ldefn } => {
+            add_dependencies(&seq_ldefn.element_identifier, dependencies)
+        }
+        TypeIdentifier::TiPlainArraySmall { array_sdefn } => {
+            add_dependencies(&array_sdefn.element_identifier, dependencies)
+        }
+        TypeIdentifier::TiPlainArrayLarge { array_ldefn } => {
+            add_dependencies(&array_ldefn.element_identifier, dependencies)
+        }
+        TypeIdentifier::TiPlainMapSmall { map_sdefn } => {
+            add_dependencies(&map_sdefn.element_identifier, dependencies)?;
+            add_dependencies(&map_sdefn.key_identifier, dependencies)
+        }
+        TypeIdentifier::TiPlainMapLarge { map_ldefn } => {
+            add_dependencies(&map_ldefn.element_identifier, dependencies)?;
+            add_dependencies(&map_ldefn.key_identifier, dependencies)
+        }
+        TypeIdentifier::EkComplete { complete } => {
+            dependencies.push(complete.as_ref());
+            for member in complete.as_ref() {
+                add_dependencies(member?.type_, dependencies)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+// All the types on which the type depends directly or indirectly
+pub fn type_dependencies(
+    dynamic_type: &dyn DynamicType,
+) -> Result<Vec<&dyn DynamicType>, XTypesError> {
+    let mut dependencies = Vec::new();
+    for member in dynamic_type {
+        add_dependencies(member?.type_, &mut dependencies)?;
+    }
+    Ok(dependencies)
+}
+
+fn type_identifier_with_dependencies(
+    dynamic_type: &dyn DynamicType,
+    equivalence_kind: EquivalenceKind,
+) -> Result<TypeIdentifierWithDependencies, XTypesError> {
+    let mut dependent_typeids: Vec<TypeIdentifierWithSize> = Vec::new();
+    for dependency in type_dependencies(dynamic_type)? {
+        let dependent_typeid = type_identifier_with_size(dependency, equivalence_kind)?;
+        if !dependent_typeids.contains(&dependent_typeid) {
+            dependent_typeids.push(dependent_typeid);
+        }
+    }
+    Ok(TypeIdentifierWithDependencies {
+        typeid_with_size: type_identifier_with_size(dynamic_type, equivalence_kind)?,
+        dependent_typeid_count: dependent_typeids.len() as i32,
+        dependent_typeids,
+    })
+}
+
+// TypeInformation announced in the discovery of the endpoints as defined in
+// XTypes 7.6.3.2.2 "TypeInformation"
+pub fn type_information(dynamic_type: &dyn DynamicType) -> Result<TypeInformation, XTypesError> {
+    Ok(TypeInformation {
+        minimal: type_identifier_with_dependencies(dynamic_type, EK_MINIMAL)?,
+        complete: type_identifier_with_dependencies(dynamic_type, EK_COMPLETE)?,
+    })
+}
+
+fn write_type_identifier_with_size(writer: &mut Xcdr2Writer, value: &TypeIdentifierWithSize) {
+    let dheader = writer.begin_dheader();
+    write_type_object_hash_id(writer, &value.type_id);
+    writer.write_u32(value.typeobject_serialized_size);
+    writer.end_dheader(dheader);
+}
+
+fn read_type_identifier_with_size(
+    reader: &mut Xcdr2Reader,
+) -> Result<TypeIdentifierWithSize, XTypesError> {
+    let end = reader.read_dheader()?;
+    let value = TypeIdentifierWithSize {
+        type_id: read_type_object_hash_id(reader)?,
+        typeobject_serialized_size: reader.read_u32()?,
+    };
+    reader.set_position(end)?;
+    Ok(value)
+}
+
+fn write_type_identifier_with_dependencies(
+    writer: &mut Xcdr2Writer,
+    value: &TypeIdentifierWithDependencies,
+) {
+    let dheader = writer.begin_dheader();
+    write_type_identifier_with_size(writer, &value.typeid_with_size);
+    writer.write_i32(value.dependent_typeid_count);
+    let dependent_typeids = writer.begin_dheader();
+    writer.write_u32(value.dependent_typeids.len() as u32);
+    for dependent_typeid in &value.dependent_typeids {
+        write_type_identifier_with_size(writer, dependent_typeid);
+    }
+    writer.end_dheader(dependent_typeids);
+    writer.end_dheader(dheader);
+}
+
+fn read_type_identifier_with_dependencies(
+    reader: &mut Xcdr2Reader,
+) -> Result<TypeIdentifierWithDependencies, XTypesError> {
+    let end = reader.read_dheader()?;
+    let typeid_with_size = read_type_identifier_with_size(reader)?;
+    let dependent_typeid_count = reader.read_i32()?;
+    reader.read_dheader()?;
+    let length = reader.read_u32()?;
+    let mut dependent_typeids = Vec::new();
+    for _ in 0..length {
+        dependent_typeids.push(read_type_identifier_with_size(reader)?);
+    }
+    reader.set_position(end)?;
+    Ok(TypeIdentifierWithDependencies {
+        typeid_with_size,
+        dependent_typeid_count,
+        dependent_typeids,
+    })
+}
+
+pub fn serialize_type_information(type_information: &TypeInformation) -> Vec<u8> {
+    let mut writer = Xcdr2Writer::default();
+    let dheader = writer.begin_dheader();
+    for (member_id, value) in [
+        (TYPE_INFORMATION_MINIMAL_ID, &type_information.minimal),
+        (TYPE_INFORMATION_COMPLETE_ID, &type_information.complete),
+    ] {
+        let member = writer.begin_member(member_id);
+        write_type_identifier_with_dependencies(&mut writer, value);
+        writer.end_dheader(member);
+    }
+    writer.end_dheader(dheader);
+    writer.into_bytes()
+}
+
+pub fn deserialize_type_information(data: &[u8]) -> Result<TypeInformation, XTypesError> {
+    let mut reader = Xcdr2Reader::new(data);
+    let end = reader.read_dheader()?;
+    let mut minimal = None;
+    let mut complete = None;
+    while reader.position() < end {
+        let (member_id, member_end) = reader.read_member_header()?;
+        match member_id {
+            TYPE_INFORMATION_MINIMAL_ID => {
+                minimal = Some(read_type_identifier_with_dependencies(&mut reader)?)
+            }
+            TYPE_INFORMATION_COMPLETE_ID => {
+                complete = Some(read_type_identifier_with_dependencies(&mut reader)?)
+            }
+            _ => (),
+        }
+        reader.set_position(member_end)?;
+    }
+    Ok(TypeInformation {
+        minimal: minimal.ok_or(XTypesError::InvalidData)?,
+        complete: complete.ok_or(XTypesError::InvalidData)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::type_support::TypeSupport;
+    use dust_dds_derive::TypeSupport;
+
+    #[derive(TypeSupport)]
+    #[dust_dds(extensibility = "Final")]
+    struct Simple {
+        #[dust_dds(key)]
+        _id: i32,
+    }
+
+    #[derive(TypeSupport)]
+    #[dust_dds(extensibility = "Appendable")]
+    struct Nested {
+        _simple: Simple,
+        _simple_list: Vec<Simple>,
+        _value: u8,
+    }
+
+    #[test]
+    fn name_hash_of_member() {
+        assert_eq!(name_hash("color"), [0x70, 0xDD, 0xA5, 0xDF]);
+        assert_eq!(hash_member_id("getTypes"), 0x018252d3);
+    }
+
+    #[test]
+    fn serialize_minimal_struct_type_object() {
+        let expected = [
+            &[35, 0, 0, 0][..],                      // DHEADER TypeObject
+            &[EK_MINIMAL, TK_STRUCTURE, 0x01, 0x00], // discriminators | struct_flags (IS_FINAL)
+            &[1, 0, 0, 0],                           // DHEADER header
+            &[TK_NONE, 0, 0, 0],                     // base_type | padding (3 bytes)
+            &[19, 0, 0, 0],                          // DHEADER member_seq
+            &[1, 0, 0, 0],                           // member_seq length
+            &[11, 0, 0, 0],                          // DHEADER member
+            &[0, 0, 0, 0],                           // member_id
+            &[0x31, 0x00, 0x04],                     // member_flags | member_type_id (TK_INT32)
+            &name_hash("_id"),
+        ]
+        .concat();
+
+        assert_eq!(
+            serialize_type_object(&Simple::get_type(), EK_MINIMAL).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn serialize_complete_struct_type_object() {
+        let expected = [
+            &[54, 0, 0, 0][..],                       // DHEADER TypeObject
+            &[EK_COMPLETE, TK_STRUCTURE, 0x01, 0x00], // discriminators | struct_flags (IS_FINAL)
+            &[15, 0, 0, 0],                           // DHEADER header
+            &[TK_NONE, 0, 0, 0], // base_type | ann_builtin | ann_custom | padding (1 byte)
+            &[7, 0, 0, 0],       // type_name length
+            b"Simple\0",
+            &[0],                   // padding (1 byte)
+            &[26, 0, 0, 0],         // DHEADER member_seq
+            &[1, 0, 0, 0],          // member_seq length
+            &[18, 0, 0, 0],         // DHEADER member
+            &[0, 0, 0, 0],          // member_id
+            &[0x31, 0x00, 0x04, 0], // member_flags | member_type_id | padding (1 byte)
+            &[4, 0, 0, 0],          // name length
+            b"_id\0",
+            &[0, 0], // ann_builtin | ann_custom
+        ]
+        .concat();
+
+        assert_eq!(
+            serialize_type_object(&Simple::get_type(), EK_COMPLETE).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn type_information_contains_dependencies() {
+        let type_information = type_information(&Nested::get_type()).unwrap();
+        let simple_minimal = type_identifier_with_size(&Simple::get_type(), EK_MINIMAL).unwrap();
+        let simple_complete = type_identifier_with_size(&Simple::get_type(), EK_COMPLETE).unwrap();
+
+        assert_eq!(type_information.minimal.dependent_typeid_count, 1);
+        assert_eq!(
+            type_information.minimal.dependent_typeids,
+            vec![simple_minimal]
+        );
+        assert_eq!(
+            type_information.complete.dependent_typeids,
+            vec![simple_complete]
+        );
+        assert_eq!(
+            type_information.complete.typeid_with_size,
+            type_identifier_with_size(&Nested::get_type(), EK_COMPLETE).unwrap()
+        );
+    }
+
+    #[test]
+    fn serialize_and_deserialize_type_information() {
+        let type_information = type_information(&Nested::get_type()).unwrap();
+        let serialized = serialize_type_information(&type_information);
+
+        // DHEADER, EMHEADER of the minimal member with LC=4 and its NEXTINT
+        assert_eq!(&serialized[4..8], &[0x01, 0x10, 0x00, 0x40]);
+        assert_eq!(&serialized[8..12], &[64, 0, 0, 0]);
+        assert_eq!(
+            deserialize_type_information(&serialized).unwrap(),
+            type_information
+        );
+    }
+}
@@ -0,0 +1,661 @@
+use super::type_information::{
+    hash_member_id, read_type_object_hash_id, serialize_type_object, type_dependencies,
+    type_object_hash_id, write_type_object_hash_id, Xcdr2Reader, Xcdr2Writer,
+};
+use crate::{
+    implementation::data_representation_builtin_endpoints::spdp_discovered_participant_data::{
+        BuiltinEndpointSet, ParticipantProxy,
+    },
+    transport::{
+        history_cache::CacheChange,
+        reader::{TransportStatefulReader, WriterProxy},
+        types::{
+            ChangeKind, DurabilityKind, EntityId, Guid, GuidPrefix, ReliabilityKind,
+            BUILT_IN_READER_NO_KEY, BUILT_IN_WRITER_NO_KEY, ENTITYID_UNKNOWN,
+        },
+        writer::{ReaderProxy, TransportStatefulWriter},
+    },
+    xtypes::{
+        dynamic_type::DynamicType,
+        error::XTypesError,
+        type_object::{TypeInformation, TypeObjectHashId, EK_COMPLETE, EK_MINIMAL},
+    },
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+pub const ENTITYID_TL_SVC_REQ_WRITER: EntityId =
+    EntityId::new([0x00, 0x03, 0x00], BUILT_IN_WRITER_NO_KEY);
+
+pub const ENTITYID_TL_SVC_REQ_READER: EntityId =
+    EntityId::new([0x00, 0x03, 0x00], BUILT_IN_READER_NO_KEY);
+
+pub const ENTITYID_TL_SVC_REPLY_WRITER: EntityId =
+    EntityId::new([0x00, 0x03, 0x01], BUILT_IN_WRITER_NO_KEY);
+
+pub const ENTITYID_TL_SVC_REPLY_READER: EntityId =
+    EntityId::new([0x00, 0x03, 0x01], BUILT_IN_READER_NO_KEY);
+
+// The requests and replies are encoded with the PL_CDR2_LE representation since their types
+// are mutable
+const PL_CDR2_LE: [u8; 4] = [0x00, 0x0b, 0x00, 0x00];
+
+// Member ids of the TypeLookup_Request and TypeLookup_Reply structures
+const HEADER_MEMBER_ID: u32 = 0;
+const DATA_MEMBER_ID: u32 = 1;
+
+// Member id of the discriminator of the mutable unions and the member id of their selected case
+const DISCRIMINATOR_MEMBER_ID: u32 = 0;
+const SELECTED_MEMBER_ID: u32 = 1;
+// Length code of the EMHEADER of the 4 bytes discriminator
+const LC_4_BYTES: u32 = 2;
+
+const DDS_RETCODE_OK: i32 = 0;
+const REMOTE_EX_OK: i32 = 0;
+
+fn get_types_hash_id() -> i32 {
+    hash_member_id("getTypes") as i32
+}
+
+// The requests are received by all the participants so each participant only replies to the
+// requests whose instance name is derived from its own GuidPrefix
+fn instance_name(guid_prefix: GuidPrefix) -> String {
+    let mut instance_name = String::from("dds.builtin.TOS.");
+    for byte in guid_prefix {
+        instance_name.push_str(&format!("{:02x}", byte));
+    }
+    instance_name
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct SampleIdentity {
+    writer_guid: Guid,
+    sequence_number: i64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct TypeLookupRequest {
+    request_id: SampleIdentity,
+    instance_name: String,
+    type_ids: Vec<TypeObjectHashId>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct TypeLookupReply {
+    related_request_id: SampleIdentity,
+    types: Vec<(TypeObjectHashId, Vec<u8>)>,
+    complete_to_minimal: Vec<(TypeObjectHashId, TypeObjectHashId)>,
+}
+
+fn write_sample_identity(writer: &mut Xcdr2Writer, sample_identity: &SampleIdentity) {
+    writer.write_bytes(&<[u8; 16]>::from(sample_identity.writer_guid));
+    writer.write_i32((sample_identity.sequence_number >> 32) as i32);
+    writer.write_u32(sample_identity.sequence_number as u32);
+}
+
+fn read_sample_identity(reader: &mut Xcdr2Reader) -> Result<SampleIdentity, XTypesError> {
+    let mut writer_guid = [0; 16];
+    writer_guid.copy_from_slice(reader.read_bytes(16)?);
+    let high = reader.read_i32()?;
+    let low = reader.read_u32()?;
+    Ok(SampleIdentity {
+        writer_guid: Guid::from(writer_guid),
+        sequence_number: ((high as i64) << 32) + low as i64,
+    })
+}
+
+// Writes the header of a mutable union followed by the EMHEADER of its selected case. Both
+// are finished with end_dheader.
+fn begin_mutable_union(writer: &mut Xcdr2Writer, discriminator: i32) -> (usize, usize) {
+    let union = writer.begin_dheader();
+    writer.write_u32(LC_4_BYTES << 28 | DISCRIMINATOR_MEMBER_ID);
+    writer.write_i32(discriminator);
+    let selected_member = writer.begin_member(SELECTED_MEMBER_ID);
+    (union, selected_member)
+}
+
+fn end_mutable_union(writer: &mut Xcdr2Writer, (union, selected_member): (usize, usize)) {
+    writer.end_dheader(selected_member);
+    writer.end_dheader(union);
+}
+
+// Reads the header of a mutable union and positions the reader at its selected case. Returns
+// the discriminator and the position where the union ends.
+fn read_mutable_union(reader: &mut Xcdr2Reader) -> Result<(i32, usize), XTypesError> {
+    let end = reader.read_dheader()?;
+    let mut discriminator = None;
+    while reader.position() < end {
+        let (member_id, member_end) = reader.read_member_header()?;
+        if member_id != DISCRIMINATOR_MEMBER_ID {
+            return Ok((discriminator.ok_or(XTypesError::InvalidData)?, end));
+        }
+        discriminator = Some(reader.read_i32()?);
+        reader.set_position(member_end)?;
+    }
+    Err(XTypesError::InvalidData)
+}
+
+fn serialize_request(request: &TypeLookupRequest) -> Vec<u8> {
+    let mut writer = Xcdr2Writer::default();
+    let request_dheader = writer.begin_dheader();
+
+    let header = writer.begin_member(HEADER_MEMBER_ID);
+    write_sample_identity(&mut writer, &request.request_id);
+    writer.write_string(&request.instance_name);
+    writer.end_dheader(header);
+
+    let data = writer.begin_member(DATA_MEMBER_ID);
+    let call = begin_mutable_union(&mut writer, get_types_hash_id());
+    let get_types_in = writer.begin_dheader();
+    let type_ids = writer.begin_member(hash_member_id("type_ids"));
+    let type_ids_sequence = writer.begin_dheader();
+    writer.write_u32(request.type_ids.len() as u32);
+    for type_id in &request.type_ids {
+        write_type_object_hash_id(&mut writer, type_id);
+    }
+    writer.end_dheader(type_ids_sequence);
+    writer.end_dheader(type_ids);
+    writer.end_dheader(get_types_in);
+    end_mutable_union(&mut writer, call);
+    writer.end_dheader(data);
+
+    writer.end_dheader(request_dheader);
+    [&PL_CDR2_LE[..], &writer.into_bytes()].concat()
+}
+
+fn read_type_ids(reader: &mut Xcdr2Reader) -> Result<Vec<TypeObjectHashId>, XTypesError> {
+    let mut type_ids = Vec::new();
+    let end = reader.read_dheader()?;
+    while reader.position() < end {
+        let (member_id, member_end) = reader.read_member_header()?;
+        if member_id == hash_member_id("type_ids") {
+            reader.read_dheader()?;
+            let length = reader.read_u32()?;
+            for _ in 0..length {
+                type_ids.push(read_type_object_hash_id(reader)?);
+            }
+        }
+        reader.set_position(member_end)?;
+    }
+    Ok(type_ids)
+}
+
+fn deserialize_request(data: &[u8]) -> Result<TypeLookupRequest, XTypesError> {
+    let mut reader = Xcdr2Reader::new(data.get(4..).ok_or(XTypesError::InvalidData)?);
+    let end = reader.read_dheader()?;
+    let mut header = None;
+    let mut type_ids = None;
+    while reader.position() < end {
+        let (member_id, member_end) = reader.read_member_header()?;
+        match member_id {
+            HEADER_MEMBER_ID => {
+                header = Some((
+                    read_sample_identity(&mut reader)?,
+                    reader.read_string()?.to_owned(),
+                ))
+            }
+            DATA_MEMBER_ID => {
+                let (discriminator, _) = read_mutable_union(&mut reader)?;
+                // Only the getTypes operation is provided by the service
+                if discriminator != get_types_hash_id() {
+                    return Err(XTypesError::InvalidData);
+                }
+                type_ids = Some(read_type_ids(&mut reader)?);
+            }
+            _ => (),
+        }
+        reader.set_position(member_end)?;
+    }
+    let (request_id, instance_name) = header.ok_or(XTypesError::InvalidData)?;
+    Ok(TypeLookupRequest {
+        request_id,
+        instance_name,
+        type_ids: type_ids.ok_or(XTypesError::InvalidData)?,
+    })
+}
+
+fn serialize_reply(reply: &TypeLookupReply) -> Vec<u8> {
+    let mut writer = Xcdr2Writer::default();
+    let reply_dheader = writer.begin_dheader();
+
+    let header = writer.begin_member(HEADER_MEMBER_ID);
+    write_sample_identity(&mut writer, &reply.related_request_id);
+    writer.write_i32(REMOTE_EX_OK);
+    writer.end_dheader(header);
+
+    let data = writer.begin_member(DATA_MEMBER_ID);
+    let return_union = begin_mutable_union(&mut writer, get_types_hash_id());
+    let result_union = begin_mutable_union(&mut writer, DDS_RETCODE_OK);
+    let get_types_out = writer.begin_dheader();
+
+    let types = writer.begin_member(hash_member_id("types"));
+    let types_sequence = writer.begin_dheader();
+    writer.write_u32(reply.types.len() as u32);
+    for (type_id, type_object) in &reply.types {
+        write_type_object_hash_id(&mut writer, type_id);
+        // The serialized TypeObject starts with its DHEADER
+        writer.align(4);
+        writer.write_bytes(type_object);
+    }
+    writer.end_dheader(types_sequence);
+    writer.end_dheader(types);
+
+    let complete_to_minimal = writer.begin_member(hash_member_id("complete_to_minimal"));
+    let complete_to_minimal_sequence = writer.begin_dheader();
+    writer.write_u32(reply.complete_to_minimal.len() as u32);
+    for (complete, minimal) in &reply.complete_to_minimal {
+        write_type_object_hash_id(&mut writer, complete);
+        write_type_object_hash_id(&mut writer, minimal);
+    }
+    writer.end_dheader(complete_to_minimal_sequence);
+    writer.end_dheader(complete_to_minimal);
+
+    writer.end_dheader(get_types_out);
+    end_mutable_union(&mut writer, result_union);
+    end_mutable_union(&mut writer, return_union);
+    writer.end_dheader(data);
+
+    writer.end_dheader(reply_dheader);
+    [&PL_CDR2_LE[..], &writer.into_bytes()].concat()
+}
+
+type GetTypesOut = (
+    Vec<(TypeObjectHashId, Vec<u8>)>,
+    Vec<(TypeObjectHashId, TypeObjectHashId)>,
+);
+
+fn read_get_types_out(reader: &mut Xcdr2Reader) -> Result<GetTypesOut, XTypesError> {
+    let mut types = Vec::new();
+    let mut complete_to_minimal = Vec::new();
+    let end = reader.read_dheader()?;
+    while reader.position() < end {
+        let (member_id, member_end) = reader.read_member_header()?;
+        if member_id == hash_member_id("types") {
+            reader.read_dheader()?;
+            let length = reader.read_u32()?;
+            for _ in 0..length {
+                let type_id = read_type_object_hash_id(reader)?;
+                reader.align(4)?;
+                let type_object_start = reader.position();
+                let type_object_end = reader.read_dheader()?;
+                reader.set_position(type_object_start)?;
+                let type_object = reader.read_bytes(type_object_end - type_object_start)?;
+                types.push((type_id, type_object.to_vec()));
+            }
+        } else if member_id == hash_member_id("complete_to_minimal") {
+            reader.read_dheader()?;
+            let length = reader.read_u32()?;
+            for _ in 0..length {
+                let complete = read_type_object_hash_id(reader)?;
+                let minimal = read_type_object_hash_id(reader)?;
+                complete_to_minimal.push((complete, minimal));
+            }
+        }
+        reader.set_position(member_end)?;
+    }
+    Ok((types, complete_to_minimal))
+}
+
+fn deserialize_reply(data: &[u8]) -> Result<TypeLookupReply, XTypesError> {
+    let mut reader = Xcdr2Reader::new(data.get(4..).ok_or(XTypesError::InvalidData)?);
+    let end = reader.read_dheader()?;
+    let mut related_request_id = None;
+    let mut get_types_out = None;
+    while reader.position() < end {
+        let (member_id, member_end) = reader.read_member_header()?;
+        match member_id {
+            HEADER_MEMBER_ID => {
+                related_request_id = Some(read_sample_identity(&mut reader)?);
+                if reader.read_i32()? != REMOTE_EX_OK {
+                    return Err(XTypesError::InvalidData);
+                }
+            }
+            DATA_MEMBER_ID => {
+                let (operation, _) = read_mutable_union(&mut reader)?;
+                let (return_code, _) = read_mutable_union(&mut reader)?;
+                if operation != get_types_hash_id() || return_code != DDS_RETCODE_OK {
+                    return Err(XTypesError::InvalidData);
+                }
+                get_types_out = Some(read_get_types_out(&mut reader)?);
+            }
+            _ => (),
+        }
+        reader.set_position(member_end)?;
+    }
+    let (types, complete_to_minimal) = get_types_out.ok_or(XTypesError::InvalidData)?;
+    Ok(TypeLookupReply {
+        related_request_id: related_request_id.ok_or(XTypesError::InvalidData)?,
+        types,
+        complete_to_minimal,
+    })
+}
+
+// Stateful writer of the service whose changes are kept in the history until they are
+// acknowledged by all the matched readers
+struct ServiceWriter {
+    transport_writer: Box<dyn TransportStatefulWriter>,
+    last_sequence_number: i64,
+    change_list: Vec<i64>,
+}
+
+impl ServiceWriter {
+    fn new(transport_writer: Box<dyn TransportStatefulWriter>) -> Self {
+        Self {
+            transport_writer,
+            last_sequence_number: 0,
+            change_list: Vec::new(),
+        }
+    }
+
+    fn next_sample_identity(&self) -> SampleIdentity {
+        SampleIdentity {
+            writer_guid: self.transport_writer.guid(),
+            sequence_number: self.last_sequence_number + 1,
+        }
+    }
+
+    fn write(&mut self, data: Vec<u8>) {
+        for sequence_number in self.change_list.clone() {
+            if self
+                .transport_writer
+                .is_change_acknowledged(sequence_number)
+            {
+                self.transport_writer
+                    .history_cache()
+                    .remove_change(sequence_number);
+                self.change_list.retain(|&s| s != sequence_number);
+            }
+        }
+
+        self.last_sequence_number += 1;
+        let change = CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid: self.transport_writer.guid(),
+            sequence_number: self.last_sequence_number,
+            source_timestamp: None,
+            instance_handle: None,
+            data_value: data.into(),
+        };
+        self.transport_writer.history_cache().add_change(change);
+        self.change_list.push(self.last_sequence_number);
+    }
+}
+
+// Builtin endpoints of the TypeLookup service as defined in XTypes 7.6.3.3 "Built-in TypeLookup
+// service". The service replies to the getTypes requests with the TypeObjects of the local
+// types and requests the complete TypeObjects announced by the discovered endpoints.
+pub struct TypeLookup {
+    request_writer: ServiceWriter,
+    request_reader: Box<dyn TransportStatefulReader>,
+    reply_writer: ServiceWriter,
+    reply_reader: Box<dyn TransportStatefulReader>,
+    // Participants whose service endpoints are matched
+    matched_participant_list: HashSet<GuidPrefix>,
+    requested_type_ids: HashSet<TypeObjectHashId>,
+    remote_type_objects: HashMap<TypeObjectHashId, Vec<u8>>,
+}
+
+impl TypeLookup {
+    pub fn new(
+        request_writer: Box<dyn TransportStatefulWriter>,
+        request_reader: Box<dyn TransportStatefulReader>,
+        reply_writer: Box<dyn TransportStatefulWriter>,
+        reply_reader: Box<dyn TransportStatefulReader>,
+    ) -> Self {
+        Self {
+            request_writer: ServiceWriter::new(request_writer),
+            request_reader,
+            reply_writer: ServiceWriter::new(reply_writer),
+            reply_reader,
+            matched_participant_list: HashSet::new(),
+            requested_type_ids: HashSet::new(),
+            remote_type_objects: HashMap::new(),
+        }
+    }
+
+    pub fn add_matched_participant(&mut self, participant_proxy: &ParticipantProxy) {
+        let available_builtin_endpoints = &participant_proxy.available_builtin_endpoints;
+        let guid_prefix = participant_proxy.guid_prefix;
+        let reader_proxy = |entity_id| ReaderProxy {
+            remote_reader_guid: Guid::new(guid_prefix, entity_id),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: participant_proxy.metatraffic_unicast_locator_list.clone(),
+            multicast_locator_list: participant_proxy.metatraffic_multicast_locator_list.clone(),
+            expects_inline_qos: false,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
+        };
+        let writer_proxy = |entity_id| WriterProxy {
+            remote_writer_guid: Guid::new(guid_prefix, entity_id),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: participant_proxy.metatraffic_unicast_locator_list.clone(),
+            multicast_locator_list: participant_proxy.metatraffic_multicast_locator_list.clone(),
+            data_max_size_serialized: Default::default(),
+            disable_positive_acks: false,
+        };
+
+        if available_builtin_endpoints
+            .has(BuiltinEndpointSet::BUILTIN_TYPE_LOOKUP_SERVICE_REQUEST_DATA_READER)
+        {
+            self.request_writer
+                .transport_writer
+                .add_matched_reader(reader_proxy(ENTITYID_TL_SVC_REQ_READER));
+        }
+        if available_builtin_endpoints
+            .has(BuiltinEndpointSet::BUILTIN_TYPE_LOOKUP_SERVICE_REQUEST_DATA_WRITER)
+        {
+            self.request_reader
+                .add_matched_writer(writer_proxy(ENTITYID_TL_SVC_REQ_WRITER));
+        }
+        if available_builtin_endpoints
+            .has(BuiltinEndpointSet::BUILTIN_TYPE_LOOKUP_SERVICE_REPLY_DATA_READER)
+        {
+            self.reply_writer
+                .transport_writer
+                .add_matched_reader(reader_proxy(ENTITYID_TL_SVC_REPLY_READER));
+        }
+        if available_builtin_endpoints
+            .has(BuiltinEndpointSet::BUILTIN_TYPE_LOOKUP_SERVICE_REPLY_DATA_WRITER)
+        {
+            self.reply_reader
+                .add_matched_writer(writer_proxy(ENTITYID_TL_SVC_REPLY_WRITER));
+        }
+        if available_builtin_endpoints.has(
+            BuiltinEndpointSet::BUILTIN_TYPE_LOOKUP_SERVICE_REQUEST_DATA_READER
+                | BuiltinEndpointSet::BUILTIN_TYPE_LOOKUP_SERVICE_REPLY_DATA_WRITER,
+        ) {
+            self.matched_participant_list.insert(guid_prefix);
+        }
+    }
+
+    pub fn remove_matched_participant(&mut self, guid_prefix: GuidPrefix) {
+        self.request_writer
+            .transport_writer
+            .remove_matched_reader(Guid::new(guid_prefix, ENTITYID_TL_SVC_REQ_READER));
+        self.request_reader
+            .remove_matched_writer(Guid::new(guid_prefix, ENTITYID_TL_SVC_REQ_WRITER));
+        self.reply_writer
+            .transport_writer
+            .remove_matched_reader(Guid::new(guid_prefix, ENTITYID_TL_SVC_REPLY_READER));
+        self.reply_reader
+            .remove_matched_writer(Guid::new(guid_prefix, ENTITYID_TL_SVC_REPLY_WRITER));
+        self.matched_participant_list.remove(&guid_prefix);
+    }
+
+    // Requests the complete TypeObjects of the type information announced by a remote endpoint
+    // which are neither received nor already requested
+    pub fn request_type_objects(
+        &mut self,
+        remote_guid_prefix: GuidPrefix,
+        type_information: &TypeInformation,
+    ) {
+        if !self.matched_participant_list.contains(&remote_guid_prefix) {
+            return;
+        }
+        let complete = &type_information.complete;
+        let type_ids: Vec<TypeObjectHashId> = core::iter::once(&complete.typeid_with_size)
+            .chain(&complete.dependent_typeids)
+            .map(|t| t.type_id)
+            .filter(|t| {
+                !self.remote_type_objects.contains_key(t) && !self.requested_type_ids.contains(t)
+            })
+            .collect();
+        if type_ids.is_empty() {
+            return;
+        }
+        self.requested_type_ids.extend(type_ids.iter().copied());
+        let request = TypeLookupRequest {
+            request_id: self.request_writer.next_sample_identity(),
+            instance_name: instance_name(remote_guid_prefix),
+            type_ids,
+        };
+        self.request_writer.write(serialize_request(&request));
+    }
+
+    pub fn process_request(
+        &mut self,
+        local_guid_prefix: GuidPrefix,
+        data: &[u8],
+        local_types: &[Arc<dyn DynamicType + Send + Sync>],
+    ) -> Result<(), XTypesError> {
+        let request = deserialize_request(data)?;
+        if request.instance_name != instance_name(local_guid_prefix) {
+            return Ok(());
+        }
+
+        let mut reply = TypeLookupReply {
+            related_request_id: request.request_id,
+            types: Vec::new(),
+            complete_to_minimal: Vec::new(),
+        };
+        for local_type in local_types {
+            let local_type = local_type.as_ref() as &dyn DynamicType;
+            // Types which can not be described with a TypeObject are not announced so they
+            // are never requested
+            let Ok(dependencies) = type_dependencies(local_type) else {
+                continue;
+            };
+            for dynamic_type in core::iter::once(local_type).chain(dependencies) {
+                let (Ok(complete), Ok(minimal)) = (
+                    serialize_type_object(dynamic_type, EK_COMPLETE),
+                    serialize_type_object(dynamic_type, EK_MINIMAL),
+                ) else {
+                    continue;
+                };
+                let complete_id = type_object_hash_id(&complete, EK_COMPLETE);
+                let minimal_id = type_object_hash_id(&minimal, EK_MINIMAL);
+                if request.type_ids.contains(&complete_id)
+                    && !reply.types.iter().any(|(t, _)| t == &complete_id)
+                {
+                    reply.types.push((complete_id, complete));
+                    reply.complete_to_minimal.push((complete_id, minimal_id));
+                }
+                if request.type_ids.contains(&minimal_id)
+                    && !reply.types.iter().any(|(t, _)| t == &minimal_id)
+                {
+                    reply.types.push((minimal_id, minimal));
+                }
+            }
+        }
+        self.reply_writer.write(serialize_reply(&reply));
+        Ok(())
+    }
+
+    pub fn process_reply(&mut self, data: &[u8]) -> Result<(), XTypesError> {
+        let reply = deserialize_reply(data)?;
+        if reply.related_request_id.writer_guid != self.request_writer.transport_writer.guid() {
+            return Ok(());
+        }
+        for (type_id, type_object) in reply.types {
+            let equivalence_kind = match type_id {
+                TypeObjectHashId::EkComplete { .. } => EK_COMPLETE,
+                TypeObjectHashId::EkMinimal { .. } => EK_MINIMAL,
+            };
+            // The TypeObjects not matching their hash are discarded
+            if self.requested_type_ids.contains(&type_id)
+                && type_object_hash_id(&type_object, equivalence_kind) == type_id
+            {
+                self.requested_type_ids.remove(&type_id);
+                self.remote_type_objects.insert(type_id, type_object);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::type_support::TypeSupport;
+    use dust_dds_derive::TypeSupport;
+
+    #[derive(TypeSupport)]
+    #[dust_dds(extensibility = "Final")]
+    struct Simple {
+        #[dust_dds(key)]
+        _id: i32,
+    }
+
+    #[test]
+    fn serialize_and_deserialize_request() {
+        let request = TypeLookupRequest {
+            request_id: SampleIdentity {
+                writer_guid: Guid::from([1; 16]),
+                sequence_number: 2,
+            },
+            instance_name: instance_name([3; 12]),
+            type_ids: vec![TypeObjectHashId::EkComplete { hash: [4; 14] }],
+        };
+        let serialized = serialize_request(&request);
+
+        assert_eq!(&serialized[..4], &PL_CDR2_LE);
+        // EMHEADER of the header member with LC=4 and its NEXTINT with the size of the
+        // SampleIdentity (24 bytes) and the instance name (4 + 41 bytes)
+        assert_eq!(&serialized[8..12], &[0x00, 0x00, 0x00, 0x40]);
+        assert_eq!(&serialized[12..16], &[69, 0, 0, 0]);
+        assert_eq!(deserialize_request(&serialized).unwrap(), request);
+    }
+
+    #[test]
+    fn serialize_and_deserialize_reply() {
+        let complete = serialize_type_object(&Simple::get_type(), EK_COMPLETE).unwrap();
+        let complete_id = type_object_hash_id(&complete, EK_COMPLETE);
+        let minimal_id = type_object_hash_id(
+            &serialize_type_object(&Simple::get_type(), EK_MINIMAL).unwrap(),
+            EK_MINIMAL,
+        );
+        let reply = TypeLookupReply {
+            related_request_id: SampleIdentity {
+                writer_guid: Guid::from([1; 16]),
+                sequence_number: 1 << 33,
+            },
+            types: vec![(complete_id, complete)],
+            complete_to_minimal: vec![(complete_id, minimal_id)],
+        };
+
+        assert_eq!(deserialize_reply(&serialize_reply(&reply)).unwrap(), reply);
+    }
+
+    #[test]
+    fn request_of_other_operation_is_invalid() {
+        let mut writer = Xcdr2Writer::default();
+        let request_dheader = writer.begin_dheader();
+        let data = writer.begin_member(DATA_MEMBER_ID);
+        let call = begin_mutable_union(&mut writer, hash_member_id("getTypeDependencies") as i32);
+        end_mutable_union(&mut writer, call);
+        writer.end_dheader(data);
+        writer.end_dheader(request_dheader);
+        let serialized = [&PL_CDR2_LE[..], &writer.into_bytes()].concat();
+
+        assert!(deserialize_request(&serialized).is_err());
+    }
+}
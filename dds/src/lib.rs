@@ -23,9 +23,14 @@ pub mod transport;
 #[cfg(feature = "std")]
 mod runtime;
 
+#[cfg(feature = "std")]
+mod security;
+
 /// Contains the XTypes serializer and deserializer
 #[doc(hidden)]
 pub mod xtypes;
 
+extern crate alloc;
+
 // To enable using our own derive macros to allow the name dust_dds:: to be used
 extern crate self as dust_dds;
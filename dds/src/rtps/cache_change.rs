@@ -21,13 +21,22 @@ use super::messages::{
 };
 
 impl CacheChange {
-    pub fn as_data_submessage(&self, reader_id: EntityId, writer_id: EntityId) -> DataSubmessage {
-        let (data_flag, key_flag) = match self.kind {
+    /// Returns the data and key flags of the submessages carrying the change. Changes of the
+    /// instance state only carry the serialized key, or no payload at all, instead of the sample.
+    pub fn payload_flags(&self) -> (bool, bool) {
+        if self.data_value.is_empty() {
+            return (false, false);
+        }
+        match self.kind {
             ChangeKind::Alive | ChangeKind::AliveFiltered => (true, false),
             ChangeKind::NotAliveDisposed
             | ChangeKind::NotAliveUnregistered
             | ChangeKind::NotAliveDisposedUnregistered => (false, true),
-        };
+        }
+    }
+
+    pub fn as_data_submessage(&self, reader_id: EntityId, writer_id: EntityId) -> DataSubmessage {
+        let (data_flag, key_flag) = self.payload_flags();
 
         let mut parameters = Vec::with_capacity(2);
         match self.kind {
@@ -127,3 +136,30 @@ impl CacheChange {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::types::{ENTITYID_UNKNOWN, GUID_UNKNOWN};
+
+    #[test]
+    fn data_submessages_share_the_payload_of_the_change() {
+        let cache_change = CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid: GUID_UNKNOWN,
+            sequence_number: 1,
+            source_timestamp: None,
+            instance_handle: None,
+            data_value: vec![1, 2, 3, 4].into(),
+        };
+
+        let data_submessage = cache_change.as_data_submessage(ENTITYID_UNKNOWN, ENTITYID_UNKNOWN);
+        let received_change =
+            CacheChange::try_from_data_submessage(&data_submessage, [1; 12], None).unwrap();
+
+        assert!(Arc::ptr_eq(
+            cache_change.data_value(),
+            received_change.data_value()
+        ));
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+// Shapes the rate at which the writers sharing it send the serialized data of their changes. The
+// clones of a flow controller share the same token bucket.
+#[derive(Clone)]
+pub struct FlowController {
+    token_bucket: Arc<Mutex<TokenBucket>>,
+}
+
+impl FlowController {
+    pub fn new(max_bytes_per_second: usize) -> Self {
+        Self {
+            token_bucket: Arc::new(Mutex::new(TokenBucket::new(
+                max_bytes_per_second,
+                Instant::now(),
+            ))),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.token_bucket
+            .lock()
+            .expect("Token bucket lock must not be poisoned")
+            .is_available(Instant::now())
+    }
+
+    pub fn consume(&self, bytes: usize) {
+        self.token_bucket
+            .lock()
+            .expect("Token bucket lock must not be poisoned")
+            .consume(bytes, Instant::now());
+    }
+}
+
+// The bucket holds at most one second worth of bytes. Sending is allowed as long as any byte is
+// available, even if the data is larger, so that a change bigger than the bucket is never blocked
+// forever. The bytes sent beyond the available ones are paid back before sending again.
+struct TokenBucket {
+    max_bytes_per_second: usize,
+    available_bytes: f64,
+    last_refill_time: Instant,
+}
+
+impl TokenBucket {
+    fn new(max_bytes_per_second: usize, now: Instant) -> Self {
+        Self {
+            max_bytes_per_second,
+            available_bytes: max_bytes_per_second as f64,
+            last_refill_time: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill_time);
+        self.available_bytes = (self.available_bytes
+            + elapsed.as_secs_f64() * self.max_bytes_per_second as f64)
+            .min(self.max_bytes_per_second as f64);
+        self.last_refill_time = now;
+    }
+
+    fn is_available(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.available_bytes > 0.0
+    }
+
+    fn consume(&mut self, bytes: usize, now: Instant) {
+        self.refill(now);
+        self.available_bytes -= bytes as f64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn token_bucket_limits_bytes_sent_per_second() {
+        let start = Instant::now();
+        let mut token_bucket = TokenBucket::new(1000, start);
+
+        token_bucket.consume(600, start);
+        assert!(token_bucket.is_available(start));
+        token_bucket.consume(600, start);
+        assert!(!token_bucket.is_available(start));
+
+        // The 200 bytes sent beyond the available ones are paid back first
+        assert!(!token_bucket.is_available(start + Duration::from_millis(200)));
+        assert!(token_bucket.is_available(start + Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn token_bucket_holds_at_most_one_second_of_bytes() {
+        let start = Instant::now();
+        let mut token_bucket = TokenBucket::new(1000, start);
+
+        let later = start + Duration::from_secs(10);
+        token_bucket.consume(1000, later);
+        assert!(!token_bucket.is_available(later));
+    }
+}
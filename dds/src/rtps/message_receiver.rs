@@ -40,7 +40,7 @@ impl Iterator for MessageReceiver {
                 RtpsSubmessageReadKind::InfoDestination(m) => {
                     self.dest_guid_prefix = m.guid_prefix();
                 }
-                RtpsSubmessageReadKind::InfoReply(_) => todo!(),
+                RtpsSubmessageReadKind::InfoReply(_) => (), // Reply locators are not used
                 RtpsSubmessageReadKind::InfoSource(m) => {
                     self.source_vendor_id = m.vendor_id();
                     self.source_version = m.protocol_version();
@@ -56,6 +56,10 @@ impl Iterator for MessageReceiver {
                     }
                 }
                 RtpsSubmessageReadKind::Pad(_) => (),
+                // The protected submessages which could be decoded were already replaced
+                RtpsSubmessageReadKind::SecureBody(_)
+                | RtpsSubmessageReadKind::SecurePrefix(_)
+                | RtpsSubmessageReadKind::SecurePostfix(_) => (),
             }
         }
         None
@@ -136,6 +140,7 @@ impl MessageReceiver {
                         stateful_reader.on_heartbeat_frag_submessage_received(
                             heartbeat_frag_submessage,
                             self.source_guid_prefix,
+                            message_sender,
                         );
                     }
                 }
@@ -167,7 +172,7 @@ impl MessageReceiver {
                 RtpsSubmessageReadKind::InfoDestination(m) => {
                     self.dest_guid_prefix = m.guid_prefix();
                 }
-                RtpsSubmessageReadKind::InfoReply(_) => todo!(),
+                RtpsSubmessageReadKind::InfoReply(_) => (), // Reply locators are not used
                 RtpsSubmessageReadKind::InfoSource(m) => {
                     self.source_vendor_id = m.vendor_id();
                     self.source_version = m.protocol_version();
@@ -183,6 +188,10 @@ impl MessageReceiver {
                     }
                 }
                 RtpsSubmessageReadKind::Pad(_) => (),
+                // The protected submessages which could be decoded were already replaced
+                RtpsSubmessageReadKind::SecureBody(_)
+                | RtpsSubmessageReadKind::SecurePrefix(_)
+                | RtpsSubmessageReadKind::SecurePostfix(_) => (),
             }
         }
     }
@@ -1,47 +1,327 @@
-use core::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4};
-use std::net::{ToSocketAddrs, UdpSocket};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    net::{ToSocketAddrs, UdpSocket},
+    sync::Arc,
+};
 
 use network_interface::{Addr, NetworkInterface, NetworkInterfaceConfig};
 
 use super::{
-    messages::overall_structure::{RtpsMessageHeader, RtpsMessageWrite, Submessage},
+    messages::overall_structure::{
+        RtpsMessageHeader, RtpsMessageWrite, Submessage, SubmessageCrypto,
+        MAX_SECURE_SUBMESSAGE_OVERHEAD,
+    },
+    stateful_writer::MAX_DATA_MESSAGE_OVERHEAD,
+    tcp_transport::TcpTransport,
     types::{PROTOCOLVERSION_2_4, VENDOR_ID_S2E},
+    udp_batch::send_datagrams,
 };
 
-use crate::transport::types::{
-    GuidPrefix, Locator, ProtocolVersion, VendorId, LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6,
+use crate::{
+    configuration::DestinationMtu,
+    transport::types::{
+        GuidPrefix, Locator, ProtocolVersion, VendorId, LOCATOR_KIND_TCP_V4, LOCATOR_KIND_UDP_V4,
+        LOCATOR_KIND_UDP_V6,
+    },
 };
 
+// Smallest IP packet which every IPv4 host must accept
+const MIN_MTU: usize = 576;
+const IPV4_UDP_HEADER_SIZE: usize = 28;
+const IPV6_UDP_HEADER_SIZE: usize = 48;
+
+fn is_in_subnet(address: IpAddr, interface_address: &Addr) -> bool {
+    match (address, interface_address) {
+        (IpAddr::V4(address), Addr::V4(v4)) => v4.netmask.is_some_and(|netmask| {
+            u32::from(address) & u32::from(netmask) == u32::from(v4.ip) & u32::from(netmask)
+        }),
+        (IpAddr::V6(address), Addr::V6(v6)) => v6.netmask.is_some_and(|netmask| {
+            u128::from(address) & u128::from(netmask) == u128::from(v6.ip) & u128::from(netmask)
+        }),
+        _ => false,
+    }
+}
+
+// MTU of the network interface on the subnet of the address or, for the addresses reached through
+// a router, the lowest MTU of the interfaces which are not loopback. The path beyond the router
+// is assumed to accept packets of that size.
+#[cfg(target_os = "linux")]
+fn interface_mtu(address: IpAddr) -> Option<usize> {
+    let mtu = |name: &str| -> Option<usize> {
+        std::fs::read_to_string(format!("/sys/class/net/{}/mtu", name))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    };
+    let interface_list = NetworkInterface::show().ok()?;
+    let subnet_mtu = interface_list
+        .iter()
+        .find(|i| i.addr.iter().any(|a| is_in_subnet(address, a)))
+        .and_then(|i| mtu(&i.name));
+    subnet_mtu
+        .or_else(|| {
+            interface_list
+                .iter()
+                .filter(|i| {
+                    i.addr.iter().any(|a| {
+                        a.ip().is_ipv4() == address.is_ipv4() && !a.ip().is_loopback()
+                    })
+                })
+                .filter_map(|i| mtu(&i.name))
+                .min()
+        })
+        .filter(|&mtu| mtu >= MIN_MTU)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn interface_mtu(_address: IpAddr) -> Option<usize> {
+    None
+}
+
 pub struct MessageSender {
     protocol_version: ProtocolVersion,
     vendor_id: VendorId,
     guid_prefix: GuidPrefix,
     socket: UdpSocket,
+    socket_v6: Option<UdpSocket>,
+    max_message_size: usize,
+    submessage_crypto: Option<Arc<dyn SubmessageCrypto>>,
+    tcp_transport: Option<Arc<TcpTransport>>,
+    batch: RefCell<Option<MessageBatch>>,
+    dscp_socket_list: Vec<(u8, UdpSocket)>,
+    dscp: Cell<Option<u8>>,
+    path_mtu_discovery: bool,
+    destination_mtu_list: Vec<DestinationMtu>,
+    // Maximum size of the messages sent to each destination address, limited by the MTU of the
+    // path to it. The MTU is only looked up once so that the fragments of a sample keep their size.
+    destination_max_message_size: RefCell<HashMap<IpAddr, usize>>,
 }
 
+type MessageBatch = Vec<(Vec<Box<dyn Submessage + Send>>, Vec<Locator>)>;
+
 impl MessageSender {
-    pub fn new(guid_prefix: GuidPrefix, socket: UdpSocket) -> Self {
+    pub fn new(guid_prefix: GuidPrefix, socket: UdpSocket, max_message_size: usize) -> Self {
         Self {
             protocol_version: PROTOCOLVERSION_2_4,
             vendor_id: VENDOR_ID_S2E,
             guid_prefix,
             socket,
+            socket_v6: None,
+            max_message_size,
+            submessage_crypto: None,
+            tcp_transport: None,
+            batch: RefCell::new(None),
+            dscp_socket_list: Vec::new(),
+            dscp: Cell::new(None),
+            path_mtu_discovery: false,
+            destination_mtu_list: Vec::new(),
+            destination_max_message_size: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn max_message_size(&self) -> usize {
+        self.max_message_size
+    }
+
+    pub fn set_path_mtu(
+        &mut self,
+        path_mtu_discovery: bool,
+        destination_mtu_list: Vec<DestinationMtu>,
+    ) {
+        self.path_mtu_discovery = path_mtu_discovery;
+        self.destination_mtu_list = destination_mtu_list;
+        self.destination_max_message_size.borrow_mut().clear();
+    }
+
+    // The messages sent over TCP are not limited by the MTU since the stream is segmented by TCP
+    pub fn max_message_size_to(&self, locator: &Locator) -> usize {
+        if !self.path_mtu_discovery && self.destination_mtu_list.is_empty() {
+            return self.max_message_size;
+        }
+        let Some(address) = UdpLocator(*locator).socket_address().map(|a| a.ip()) else {
+            return self.max_message_size;
+        };
+        *self
+            .destination_max_message_size
+            .borrow_mut()
+            .entry(address)
+            .or_insert_with(|| {
+                let mtu = self
+                    .destination_mtu_list
+                    .iter()
+                    .find(|m| m.address == address)
+                    .map(|m| m.mtu)
+                    .or_else(|| {
+                        self.path_mtu_discovery
+                            .then(|| interface_mtu(address))
+                            .flatten()
+                    });
+                let ip_and_udp_header_size = match address {
+                    IpAddr::V4(_) => IPV4_UDP_HEADER_SIZE,
+                    IpAddr::V6(_) => IPV6_UDP_HEADER_SIZE,
+                };
+                mtu.map_or(self.max_message_size, |mtu| {
+                    self.max_message_size
+                        .min(mtu.saturating_sub(ip_and_udp_header_size))
+                })
+            })
+    }
+
+    // Largest serialized data of a DATA or DATA_FRAG message fitting in the messages sent to all
+    // the locators, or in the maximum message size without any locator
+    pub fn max_data_size_serialized(&self, destination_locator_list: &[Locator]) -> usize {
+        let mut max_data_message_overhead = MAX_DATA_MESSAGE_OVERHEAD;
+        if self.submessage_crypto.is_some() {
+            max_data_message_overhead += MAX_SECURE_SUBMESSAGE_OVERHEAD;
+        }
+        destination_locator_list
+            .iter()
+            .map(|l| self.max_message_size_to(l))
+            .min()
+            .unwrap_or(self.max_message_size)
+            .saturating_sub(max_data_message_overhead)
+            .max(1)
+    }
+
+    pub fn submessage_crypto(&self) -> Option<&dyn SubmessageCrypto> {
+        self.submessage_crypto.as_deref()
+    }
+
+    pub fn set_submessage_crypto(&mut self, submessage_crypto: Arc<dyn SubmessageCrypto>) {
+        self.submessage_crypto = Some(submessage_crypto);
+    }
+
+    // The messages for the UDPv6 locators are only sent when an IPv6 socket is set
+    pub fn set_socket_v6(&mut self, socket_v6: UdpSocket) {
+        self.socket_v6 = Some(socket_v6);
+    }
+
+    // The messages for the TCP locators are sent through the connections of the TCP transport
+    pub fn set_tcp_transport(&mut self, tcp_transport: Arc<TcpTransport>) {
+        self.tcp_transport = Some(tcp_transport);
+    }
+
+    // The UDPv4 datagrams with the DSCP are sent through this socket, which must have it set
+    pub fn add_dscp_socket(&mut self, dscp: u8, socket: UdpSocket) {
+        self.dscp_socket_list.push((dscp, socket));
+    }
+
+    // The UDPv4 datagrams of the messages written by the closure are sent with the DSCP
+    pub fn write_message_with_dscp(&self, dscp: Option<u8>, f: impl FnOnce(&Self)) {
+        let previous_dscp = self.dscp.replace(dscp);
+        f(self);
+        self.dscp.set(previous_dscp);
+    }
+
+    fn socket_v4(&self) -> &UdpSocket {
+        self.dscp
+            .get()
+            .and_then(|dscp| self.dscp_socket_list.iter().find(|(d, _)| *d == dscp))
+            .map_or(&self.socket, |(_, socket)| socket)
+    }
+
+    // The messages written by the closure are held back and sent once it returns. The submessages
+    // written consecutively to the same destinations are packed in as few messages as possible
+    pub fn write_message_batch(&self, f: impl FnOnce(&Self)) {
+        let previous_batch = self.batch.replace(Some(Vec::new()));
+        f(self);
+        let batch = self.batch.replace(previous_batch).unwrap_or_default();
+        for (submessages, destination_locator_list) in batch {
+            self.write_message(submessages, destination_locator_list);
         }
     }
 
     pub fn write_message(
         &self,
-        submessages: &[Box<dyn Submessage + Send>],
+        submessages: Vec<Box<dyn Submessage + Send>>,
         destination_locator_list: Vec<Locator>,
     ) {
+        if let Some(batch) = self.batch.borrow_mut().as_mut() {
+            match batch.last_mut() {
+                Some((batch_submessages, batch_destination_locator_list))
+                    if *batch_destination_locator_list == destination_locator_list =>
+                {
+                    batch_submessages.extend(submessages)
+                }
+                _ => batch.push((submessages, destination_locator_list)),
+            }
+            return;
+        }
+
         let header =
             RtpsMessageHeader::new(self.protocol_version, self.vendor_id, self.guid_prefix);
-        let rtpmessage = RtpsMessageWrite::new(&header, submessages);
-        let buf = rtpmessage.buffer();
+        // The submessages are packed once for each of the maximum message sizes of the destinations
+        let mut packed_message_list: Vec<(usize, Vec<RtpsMessageWrite>)> = Vec::new();
+        for destination_locator in &destination_locator_list {
+            let max_message_size = self.max_message_size_to(destination_locator);
+            if !packed_message_list
+                .iter()
+                .any(|(size, _)| *size == max_message_size)
+            {
+                packed_message_list.push((
+                    max_message_size,
+                    RtpsMessageWrite::new_packed_list(
+                        &header,
+                        &submessages,
+                        max_message_size,
+                        self.submessage_crypto(),
+                    ),
+                ));
+            }
+        }
 
+        // The datagrams to the unicast destinations are sent together once all of them are known
+        let mut datagram_v4_list = Vec::new();
+        let mut datagram_v6_list = Vec::new();
         for destination_locator in destination_locator_list {
-            if UdpLocator(destination_locator).is_multicast() {
-                let socket2: socket2::Socket = self.socket.try_clone().unwrap().into();
+            let max_message_size = self.max_message_size_to(&destination_locator);
+            let rtps_message_list = packed_message_list
+                .iter()
+                .find(|(size, _)| *size == max_message_size)
+                .map(|(_, list)| list)
+                .expect("Messages packed for every size");
+            if destination_locator.kind() == LOCATOR_KIND_TCP_V4 {
+                if let Some(tcp_transport) = &self.tcp_transport {
+                    tcp_transport.write_message(
+                        &destination_locator,
+                        rtps_message_list.iter().map(|m| m.buffer()),
+                    );
+                }
+                continue;
+            }
+            let Some(socket_address) = UdpLocator(destination_locator).socket_address() else {
+                continue;
+            };
+            let datagram_list = rtps_message_list
+                .iter()
+                .map(|m| (m.buffer(), socket_address));
+            if destination_locator.kind() == LOCATOR_KIND_UDP_V6 {
+                if let Some(socket_v6) = &self.socket_v6 {
+                    if UdpLocator(destination_locator).is_multicast() {
+                        let datagram_list: Vec<_> = datagram_list.collect();
+                        let socket2: socket2::Socket = socket_v6.try_clone().unwrap().into();
+                        let interface_index_list: Vec<_> = NetworkInterface::show()
+                            .expect("Could not scan interfaces")
+                            .into_iter()
+                            .filter(|i| i.addr.iter().any(|a| matches!(a, Addr::V6(_))))
+                            .map(|i| i.index)
+                            .collect();
+                        for interface_index in interface_index_list {
+                            if socket2.set_multicast_if_v6(interface_index).is_ok() {
+                                send_datagrams(socket_v6, &datagram_list);
+                            }
+                        }
+                    } else {
+                        datagram_v6_list.extend(datagram_list);
+                    }
+                }
+            } else if UdpLocator(destination_locator).is_multicast() {
+                let datagram_list: Vec<_> = datagram_list.collect();
+                let socket = self.socket_v4();
+                let socket2: socket2::Socket = socket.try_clone().unwrap().into();
                 let interface_addresses = NetworkInterface::show();
                 let interface_addresses: Vec<_> = interface_addresses
                     .expect("Could not scan interfaces")
@@ -55,17 +335,17 @@ impl MessageSender {
                     .collect();
                 for address in interface_addresses {
                     if socket2.set_multicast_if_v4(&address).is_ok() {
-                        self.socket
-                            .send_to(buf, UdpLocator(destination_locator))
-                            .ok();
+                        send_datagrams(socket, &datagram_list);
                     }
                 }
             } else {
-                self.socket
-                    .send_to(buf, UdpLocator(destination_locator))
-                    .ok();
+                datagram_v4_list.extend(datagram_list);
             }
         }
+        send_datagrams(self.socket_v4(), &datagram_v4_list);
+        if let Some(socket_v6) = &self.socket_v6 {
+            send_datagrams(socket_v6, &datagram_v6_list);
+        }
     }
 }
 struct UdpLocator(Locator);
@@ -88,13 +368,21 @@ impl ToSocketAddrs for UdpLocator {
                 );
                 Ok(Some(SocketAddr::V4(address)).into_iter())
             }
-            LOCATOR_KIND_UDP_V6 => todo!(),
+            LOCATOR_KIND_UDP_V6 => {
+                let address =
+                    SocketAddrV6::new(Ipv6Addr::from(locator_address), self.0.port() as u16, 0, 0);
+                Ok(Some(SocketAddr::V6(address)).into_iter())
+            }
             _ => Err(std::io::ErrorKind::InvalidInput.into()),
         }
     }
 }
 
 impl UdpLocator {
+    fn socket_address(&self) -> Option<SocketAddr> {
+        self.to_socket_addrs().ok()?.next()
+    }
+
     fn is_multicast(&self) -> bool {
         let locator_address = self.0.address();
         match self.0.kind() {
@@ -1,4 +1,7 @@
-use crate::transport::types::{GuidPrefix, ProtocolVersion, VendorId};
+use crate::{
+    configuration::DeserializationLimits,
+    transport::types::{EntityId, Guid, GuidPrefix, ProtocolVersion, VendorId},
+};
 
 use super::{
     super::{
@@ -10,11 +13,12 @@ use super::{
                 heartbeat_frag::HeartbeatFragSubmessage,
                 info_destination::InfoDestinationSubmessage, info_reply::InfoReplySubmessage,
                 info_source::InfoSourceSubmessage, info_timestamp::InfoTimestampSubmessage,
-                nack_frag::NackFragSubmessage, pad::PadSubmessage,
+                nack_frag::NackFragSubmessage, pad::PadSubmessage, sec_body::SecureBodySubmessage,
+                sec_postfix::SecurePostfixSubmessage, sec_prefix::SecurePrefixSubmessage,
             },
             types::{
                 ACKNACK, DATA, DATA_FRAG, GAP, HEARTBEAT, HEARTBEAT_FRAG, INFO_DST, INFO_REPLY,
-                INFO_SRC, INFO_TS, NACK_FRAG, PAD,
+                INFO_SRC, INFO_TS, NACK_FRAG, PAD, SEC_BODY, SEC_POSTFIX, SEC_PREFIX,
             },
         },
     },
@@ -50,6 +54,44 @@ pub trait WriteIntoBytes {
 pub trait Submessage {
     fn write_submessage_header_into_bytes(&self, octets_to_next_header: u16, buf: &mut dyn Write);
     fn write_submessage_elements_into_bytes(&self, buf: &mut dyn Write);
+
+    /// Entity of the endpoint sending the submessage. The interpreter submessages are not sent by
+    /// any endpoint.
+    fn endpoint_entity_id(&self) -> Option<EntityId> {
+        None
+    }
+}
+
+// Largest increase of the size of a submessage when it is protected: SEC_PREFIX (24) +
+// SEC_BODY header and content length (8) + content alignment padding (3) + SEC_POSTFIX (24)
+pub const MAX_SECURE_SUBMESSAGE_OVERHEAD: usize = 59;
+
+/// Transformation of the submessages of the endpoints protected by the security plugins as
+/// defined in DDS Security 8.5 "Cryptographic plugin"
+pub trait SubmessageCrypto: Send + Sync {
+    /// Whether the submessages sent by the local endpoint are protected
+    fn is_protected_endpoint(&self, local_entity_id: EntityId) -> bool;
+
+    /// Transforms the submessage of a protected local endpoint into the SEC_PREFIX, SEC_BODY and
+    /// SEC_POSTFIX submessages
+    fn encode_submessage(
+        &self,
+        local_entity_id: EntityId,
+        plain_submessage: &[u8],
+    ) -> RtpsResult<(
+        SecurePrefixSubmessage,
+        SecureBodySubmessage,
+        SecurePostfixSubmessage,
+    )>;
+
+    /// Returns the submessage protected by the remote participant
+    fn decode_submessage(
+        &self,
+        source_guid_prefix: GuidPrefix,
+        secure_prefix: &SecurePrefixSubmessage,
+        secure_body: &SecureBodySubmessage,
+        secure_postfix: &SecurePostfixSubmessage,
+    ) -> RtpsResult<Vec<u8>>;
 }
 
 impl dyn Submessage + Send + '_ {
@@ -129,6 +171,8 @@ impl SubmessageHeaderRead {
 pub struct RtpsMessageRead {
     header: RtpsMessageHeader,
     submessages: Vec<RtpsSubmessageReadKind>,
+    discarded_submessage_count: usize,
+    skipped_submessage_count: usize,
 }
 
 impl RtpsMessageRead {
@@ -136,15 +180,126 @@ impl RtpsMessageRead {
         self.header
     }
 
+    /// Number of submessages discarded because they could not be read or were invalid.
+    pub fn discarded_submessage_count(&self) -> usize {
+        self.discarded_submessage_count
+    }
+
+    /// Number of submessages skipped because their kind is unknown or vendor-specific.
+    pub fn skipped_submessage_count(&self) -> usize {
+        self.skipped_submessage_count
+    }
+
     pub fn submessages(self) -> Vec<RtpsSubmessageReadKind> {
         self.submessages
     }
+
+    /// Replaces each sequence of SEC_PREFIX, SEC_BODY and SEC_POSTFIX submessages by the
+    /// submessage they protect. The submessages which cannot be decoded and the submessages
+    /// received without protection from the remote endpoints which must protect them are
+    /// discarded.
+    pub fn decode_secure_submessages(
+        self,
+        crypto: &dyn SubmessageCrypto,
+        max_parameter_list_length: usize,
+        is_protected_remote_endpoint: impl Fn(Guid) -> bool,
+    ) -> Self {
+        let mut source_guid_prefix = self.header.guid_prefix;
+        let mut discarded_submessage_count = self.discarded_submessage_count;
+        let mut submessages = Vec::with_capacity(self.submessages.len());
+        let mut submessage_iter = self.submessages.into_iter();
+        while let Some(submessage) = submessage_iter.next() {
+            match submessage {
+                RtpsSubmessageReadKind::SecurePrefix(secure_prefix) => {
+                    let plain_submessage = match (submessage_iter.next(), submessage_iter.next()) {
+                        (
+                            Some(RtpsSubmessageReadKind::SecureBody(secure_body)),
+                            Some(RtpsSubmessageReadKind::SecurePostfix(secure_postfix)),
+                        ) => crypto
+                            .decode_submessage(
+                                source_guid_prefix,
+                                &secure_prefix,
+                                &secure_body,
+                                &secure_postfix,
+                            )
+                            .and_then(|plain_submessage| {
+                                read_plain_submessage(&plain_submessage, max_parameter_list_length)
+                            }),
+                        _ => Err(RtpsError::new(
+                            RtpsErrorKind::InvalidData,
+                            "SEC_PREFIX not followed by SEC_BODY and SEC_POSTFIX",
+                        )),
+                    };
+                    match plain_submessage {
+                        Ok(plain_submessage) => submessages.push(plain_submessage),
+                        Err(_) => discarded_submessage_count += 1,
+                    }
+                }
+                RtpsSubmessageReadKind::SecureBody(_)
+                | RtpsSubmessageReadKind::SecurePostfix(_) => discarded_submessage_count += 1,
+                RtpsSubmessageReadKind::InfoSource(ref m) => {
+                    source_guid_prefix = m.guid_prefix();
+                    submessages.push(submessage);
+                }
+                _ => {
+                    if submessage.endpoint_entity_id().is_some_and(|entity_id| {
+                        is_protected_remote_endpoint(Guid::new(source_guid_prefix, entity_id))
+                    }) {
+                        discarded_submessage_count += 1;
+                    } else {
+                        submessages.push(submessage);
+                    }
+                }
+            }
+        }
+        Self {
+            header: self.header,
+            submessages,
+            discarded_submessage_count,
+            skipped_submessage_count: self.skipped_submessage_count,
+        }
+    }
+}
+
+// Reads a complete submessage recovered from its protected form. Only the submessages of the
+// endpoints can be protected.
+fn read_plain_submessage(
+    mut data: &[u8],
+    max_parameter_list_length: usize,
+) -> RtpsResult<RtpsSubmessageReadKind> {
+    let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data)?;
+    if data.len() < submessage_header.submessage_length() as usize {
+        return Err(RtpsError::new(
+            RtpsErrorKind::NotEnoughData,
+            "Protected submessage",
+        ));
+    }
+    match RtpsSubmessageReadKind::try_from_bytes(
+        &submessage_header,
+        data,
+        max_parameter_list_length,
+    )? {
+        Some(submessage) if submessage.endpoint_entity_id().is_some() => Ok(submessage),
+        _ => Err(RtpsError::new(
+            RtpsErrorKind::InvalidData,
+            "Protected submessage is not an endpoint submessage",
+        )),
+    }
 }
 
 impl TryFrom<&[u8]> for RtpsMessageRead {
     type Error = RtpsError;
 
-    fn try_from(mut v: &[u8]) -> RtpsResult<Self> {
+    fn try_from(v: &[u8]) -> RtpsResult<Self> {
+        Self::try_from_bytes(
+            v,
+            DeserializationLimits::default().max_parameter_list_length,
+        )
+    }
+}
+
+impl RtpsMessageRead {
+    pub fn try_from_bytes(mut v: &[u8], max_parameter_list_length: usize) -> RtpsResult<Self> {
         if v.len() >= 20 {
             if b"RTPS" == &[v[0], v[1], v[2], v[3]] {
                 let major = v[4];
@@ -164,6 +319,8 @@ impl TryFrom<&[u8]> for RtpsMessageRead {
 
                 const MAX_SUBMESSAGES: usize = 2_usize.pow(16);
                 let mut submessages = vec![];
+                let mut discarded_submessage_count = 0;
+                let mut skipped_submessage_count = 0;
                 for _ in 0..MAX_SUBMESSAGES {
                     if v.len() < 4 {
                         break;
@@ -174,46 +331,22 @@ impl TryFrom<&[u8]> for RtpsMessageRead {
                         if v.len() < submessage_length {
                             break;
                         }
-                        let submessage = match submessage_header.submessage_id() {
-                            ACKNACK => AckNackSubmessage::try_from_bytes(&submessage_header, v)
-                                .map(RtpsSubmessageReadKind::AckNack),
-                            DATA => DataSubmessage::try_from_bytes(&submessage_header, v)
-                                .map(RtpsSubmessageReadKind::Data),
-                            DATA_FRAG => DataFragSubmessage::try_from_bytes(&submessage_header, v)
-                                .map(RtpsSubmessageReadKind::DataFrag),
-                            GAP => GapSubmessage::try_from_bytes(&submessage_header, v)
-                                .map(RtpsSubmessageReadKind::Gap),
-                            HEARTBEAT => HeartbeatSubmessage::try_from_bytes(&submessage_header, v)
-                                .map(RtpsSubmessageReadKind::Heartbeat),
-                            HEARTBEAT_FRAG => {
-                                HeartbeatFragSubmessage::try_from_bytes(&submessage_header, v)
-                                    .map(RtpsSubmessageReadKind::HeartbeatFrag)
-                            }
-                            INFO_DST => {
-                                InfoDestinationSubmessage::try_from_bytes(&submessage_header, v)
-                                    .map(RtpsSubmessageReadKind::InfoDestination)
-                            }
-                            INFO_REPLY => {
-                                InfoReplySubmessage::try_from_bytes(&submessage_header, v)
-                                    .map(RtpsSubmessageReadKind::InfoReply)
+                        match RtpsSubmessageReadKind::try_from_bytes(
+                            &submessage_header,
+                            v,
+                            max_parameter_list_length,
+                        ) {
+                            Ok(Some(submessage)) => submessages.push(submessage),
+                            Ok(None) => {
+                                // Unknown and vendor-specific submessages are skipped using the
+                                // submessage length. As of 9.4.5.1.3 a length of zero indicates
+                                // that the submessage extends up to the end of the message.
+                                skipped_submessage_count += 1;
+                                if submessage_length == 0 {
+                                    break;
+                                }
                             }
-                            INFO_SRC => InfoSourceSubmessage::try_from_bytes(&submessage_header, v)
-                                .map(RtpsSubmessageReadKind::InfoSource),
-                            INFO_TS => {
-                                InfoTimestampSubmessage::try_from_bytes(&submessage_header, v)
-                                    .map(RtpsSubmessageReadKind::InfoTimestamp)
-                            }
-                            NACK_FRAG => NackFragSubmessage::try_from_bytes(&submessage_header, v)
-                                .map(RtpsSubmessageReadKind::NackFrag),
-                            PAD => PadSubmessage::try_from_bytes(&submessage_header, v)
-                                .map(RtpsSubmessageReadKind::Pad),
-                            _ => Err(RtpsError::new(
-                                RtpsErrorKind::InvalidData,
-                                "Unknown message",
-                            )),
-                        };
-                        if let Ok(submessage) = submessage {
-                            submessages.push(submessage);
+                            Err(_) => discarded_submessage_count += 1,
                         }
                         v.consume(submessage_length);
                     }
@@ -221,6 +354,8 @@ impl TryFrom<&[u8]> for RtpsMessageRead {
                 Ok(Self {
                     header,
                     submessages,
+                    discarded_submessage_count,
+                    skipped_submessage_count,
                 })
             } else {
                 Err(RtpsError::new(
@@ -244,7 +379,6 @@ pub fn write_into_bytes_vec(value: impl WriteIntoBytes) -> Vec<u8> {
     cursor.into_inner()
 }
 
-#[allow(dead_code)] // Only used as convenience in tests
 pub fn write_submessage_into_bytes_vec(value: &(dyn Submessage + Send)) -> Vec<u8> {
     let mut cursor = Cursor::new(Vec::new());
     value.write_submessage_into_bytes(&mut cursor);
@@ -269,6 +403,73 @@ impl RtpsMessageWrite {
         }
     }
 
+    /// Pack the submessages in as few messages as possible without exceeding the maximum message
+    /// size. Each message after the first one starts with the interpreter submessages (INFO_DST,
+    /// INFO_TS, ...) in effect so that the entity submessages keep being interpreted in the same
+    /// context. A single entity submessage larger than the maximum is sent in a message of its own.
+    /// The submessages of the endpoints protected by the crypto are replaced by their SEC_PREFIX,
+    /// SEC_BODY and SEC_POSTFIX submessages.
+    pub fn new_packed_list(
+        header: &RtpsMessageHeader,
+        submessages: &[Box<dyn Submessage + Send>],
+        max_message_size: usize,
+        crypto: Option<&dyn SubmessageCrypto>,
+    ) -> Vec<Self> {
+        let mut header_cursor = Cursor::new(Vec::new());
+        header.write_into_bytes(&mut header_cursor);
+        let header_bytes = header_cursor.into_inner();
+
+        let mut message_list = Vec::new();
+        let mut interpreter_submessage_list: Vec<Vec<u8>> = Vec::new();
+        let mut buffer = header_bytes.clone();
+        let mut has_entity_submessage = false;
+        for submessage in submessages {
+            let mut submessage_bytes = write_submessage_into_bytes_vec(submessage.as_ref());
+            if let (Some(crypto), Some(entity_id)) = (crypto, submessage.endpoint_entity_id()) {
+                if crypto.is_protected_endpoint(entity_id) {
+                    match crypto.encode_submessage(entity_id, &submessage_bytes) {
+                        Ok((secure_prefix, secure_body, secure_postfix)) => {
+                            submessage_bytes = [
+                                write_submessage_into_bytes_vec(&secure_prefix),
+                                write_submessage_into_bytes_vec(&secure_body),
+                                write_submessage_into_bytes_vec(&secure_postfix),
+                            ]
+                            .concat();
+                        }
+                        // The submessages of the protected endpoints are never sent in clear
+                        Err(_) => continue,
+                    }
+                }
+            }
+            let submessage_id = submessage_bytes[0];
+            let is_interpreter_submessage =
+                matches!(submessage_id, INFO_DST | INFO_TS | INFO_SRC | INFO_REPLY);
+            if is_interpreter_submessage {
+                interpreter_submessage_list.retain(|s| s[0] != submessage_id);
+            }
+            if has_entity_submessage && buffer.len() + submessage_bytes.len() > max_message_size {
+                message_list.push(Self {
+                    data: Arc::from(buffer.into_boxed_slice()),
+                });
+                buffer = header_bytes.clone();
+                for interpreter_submessage in &interpreter_submessage_list {
+                    buffer.extend_from_slice(interpreter_submessage);
+                }
+                has_entity_submessage = false;
+            }
+            buffer.extend_from_slice(&submessage_bytes);
+            if is_interpreter_submessage {
+                interpreter_submessage_list.push(submessage_bytes);
+            } else {
+                has_entity_submessage = true;
+            }
+        }
+        message_list.push(Self {
+            data: Arc::from(buffer.into_boxed_slice()),
+        });
+        message_list
+    }
+
     pub fn buffer(&self) -> &[u8] {
         &self.data
     }
@@ -288,6 +489,72 @@ pub enum RtpsSubmessageReadKind {
     InfoTimestamp(InfoTimestampSubmessage),
     NackFrag(NackFragSubmessage),
     Pad(PadSubmessage),
+    SecureBody(SecureBodySubmessage),
+    SecurePrefix(SecurePrefixSubmessage),
+    SecurePostfix(SecurePostfixSubmessage),
+}
+
+impl RtpsSubmessageReadKind {
+    /// Reads the submessage following the header. Returns None if the kind of submessage is
+    /// unknown or vendor-specific.
+    pub fn try_from_bytes(
+        submessage_header: &SubmessageHeaderRead,
+        v: &[u8],
+        max_parameter_list_length: usize,
+    ) -> RtpsResult<Option<Self>> {
+        let submessage = match submessage_header.submessage_id() {
+            ACKNACK => AckNackSubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::AckNack),
+            DATA => DataSubmessage::try_from_bytes(submessage_header, v, max_parameter_list_length)
+                .map(RtpsSubmessageReadKind::Data),
+            DATA_FRAG => {
+                DataFragSubmessage::try_from_bytes(submessage_header, v, max_parameter_list_length)
+                    .map(RtpsSubmessageReadKind::DataFrag)
+            }
+            GAP => {
+                GapSubmessage::try_from_bytes(submessage_header, v).map(RtpsSubmessageReadKind::Gap)
+            }
+            HEARTBEAT => HeartbeatSubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::Heartbeat),
+            HEARTBEAT_FRAG => HeartbeatFragSubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::HeartbeatFrag),
+            INFO_DST => InfoDestinationSubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::InfoDestination),
+            INFO_REPLY => InfoReplySubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::InfoReply),
+            INFO_SRC => InfoSourceSubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::InfoSource),
+            INFO_TS => InfoTimestampSubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::InfoTimestamp),
+            NACK_FRAG => NackFragSubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::NackFrag),
+            PAD => {
+                PadSubmessage::try_from_bytes(submessage_header, v).map(RtpsSubmessageReadKind::Pad)
+            }
+            SEC_BODY => SecureBodySubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::SecureBody),
+            SEC_PREFIX => SecurePrefixSubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::SecurePrefix),
+            SEC_POSTFIX => SecurePostfixSubmessage::try_from_bytes(submessage_header, v)
+                .map(RtpsSubmessageReadKind::SecurePostfix),
+            _ => return Ok(None),
+        };
+        submessage.map(Some)
+    }
+
+    // Entity of the remote endpoint which sent the submessage
+    fn endpoint_entity_id(&self) -> Option<EntityId> {
+        match self {
+            RtpsSubmessageReadKind::AckNack(m) => Some(*m.reader_id()),
+            RtpsSubmessageReadKind::Data(m) => Some(m.writer_id()),
+            RtpsSubmessageReadKind::DataFrag(m) => Some(m.writer_id()),
+            RtpsSubmessageReadKind::Gap(m) => Some(m.writer_id()),
+            RtpsSubmessageReadKind::Heartbeat(m) => Some(m.writer_id()),
+            RtpsSubmessageReadKind::HeartbeatFrag(m) => Some(m.writer_id()),
+            RtpsSubmessageReadKind::NackFrag(m) => Some(m.reader_id()),
+            _ => None,
+        }
+    }
 }
 #[derive(Clone, Debug, PartialEq, Eq, Copy)]
 pub struct RtpsMessageHeader {
@@ -394,6 +661,49 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn pack_rtps_messages_within_max_message_size() {
+        let header = RtpsMessageHeader {
+            version: ProtocolVersion::new(2, 3),
+            vendor_id: [9, 8],
+            guid_prefix: [3; 12],
+        };
+        let info_timestamp = || -> Box<dyn Submessage + Send> {
+            Box::new(InfoTimestampSubmessage::new(false, Time::new(4, 0)))
+        };
+        let data = |writer_sn| -> Box<dyn Submessage + Send> {
+            Box::new(DataSubmessage::new(
+                false,
+                false,
+                false,
+                false,
+                EntityId::new([1, 2, 3], USER_DEFINED_READER_NO_KEY),
+                EntityId::new([6, 7, 8], USER_DEFINED_READER_GROUP),
+                writer_sn,
+                ParameterList::empty(),
+                Data::new(vec![].into()),
+            ))
+        };
+        let submessages = [info_timestamp(), data(1), data(2)];
+
+        // Header (20) + INFO_TS (12) + 2 * DATA (24) fit in a single message
+        let message_list = RtpsMessageWrite::new_packed_list(&header, &submessages, 80, None);
+        assert_eq!(
+            message_list,
+            vec![RtpsMessageWrite::new(&header, &submessages)]
+        );
+
+        // The second DATA is sent in its own message preceded by the INFO_TS in effect
+        let message_list = RtpsMessageWrite::new_packed_list(&header, &submessages, 79, None);
+        assert_eq!(
+            message_list,
+            vec![
+                RtpsMessageWrite::new(&header, &[info_timestamp(), data(1)]),
+                RtpsMessageWrite::new(&header, &[info_timestamp(), data(2)]),
+            ]
+        );
+    }
+
     #[test]
     fn serialize_rtps_message() {
         let header = RtpsMessageHeader {
@@ -630,6 +940,75 @@ mod tests {
         ];
 
         let rtps_message = RtpsMessageRead::try_from(&data[..]).unwrap();
+        assert_eq!(rtps_message.skipped_submessage_count(), 1);
         assert_eq!(expected_submessages, rtps_message.submessages());
     }
+
+    #[test]
+    fn deserialize_rtps_message_unknown_last_submessage_without_length() {
+        #[rustfmt::skip]
+        let data = [
+            b'R', b'T', b'P', b'S', // Protocol
+            2, 3, 9, 8, // ProtocolVersion | VendorId
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+            0x07, 0b_0000_0101, 28, 0, // Submessage header
+            1, 2, 3, 4, // readerId: value[4]
+            6, 7, 8, 9, // writerId: value[4]
+            0, 0, 0, 0, // firstSN: SequenceNumber: high
+            5, 0, 0, 0, // firstSN: SequenceNumber: low
+            0, 0, 0, 0, // lastSN: SequenceNumberSet: high
+            7, 0, 0, 0, // lastSN: SequenceNumberSet: low
+            2, 0, 0, 0, // count: Count: value (long)
+            0x80, 0b_0000_0001, 0, 0, // Vendor-specific submessage header
+            0x07, 0b_0000_0101, 28, 0, // Vendor-specific data
+            9, 9, 9, 9, // Vendor-specific data
+        ];
+
+        let rtps_message = RtpsMessageRead::try_from(&data[..]).unwrap();
+        assert_eq!(rtps_message.skipped_submessage_count(), 1);
+        assert_eq!(rtps_message.discarded_submessage_count(), 0);
+        let submessages = rtps_message.submessages();
+        assert_eq!(submessages.len(), 1);
+        assert!(matches!(
+            submessages[0],
+            RtpsSubmessageReadKind::Heartbeat(..)
+        ));
+    }
+
+    #[test]
+    fn deserialize_rtps_message_invalid_sequence_number() {
+        #[rustfmt::skip]
+        let data = [
+            b'R', b'T', b'P', b'S', // Protocol
+            2, 3, 9, 8, // ProtocolVersion | VendorId
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+            0x15, 0b_0000_0001, 20, 0, // Submessage header
+            0, 0, 16, 0, // extraFlags, octetsToInlineQos
+            1, 2, 3, 4, // readerId: value[4]
+            6, 7, 8, 9, // writerId: value[4]
+            0xff, 0xff, 0xff, 0xff, // writerSN: high
+            0, 0, 0, 0, // writerSN: low
+            0x07, 0b_0000_0101, 28, 0, // Submessage header
+            1, 2, 3, 4, // readerId: value[4]
+            6, 7, 8, 9, // writerId: value[4]
+            0, 0, 0, 0, // firstSN: SequenceNumber: high
+            5, 0, 0, 0, // firstSN: SequenceNumber: low
+            0, 0, 0, 0, // lastSN: SequenceNumberSet: high
+            7, 0, 0, 0, // lastSN: SequenceNumberSet: low
+            2, 0, 0, 0, // count: Count: value (long)
+        ];
+
+        let rtps_message = RtpsMessageRead::try_from(&data[..]).unwrap();
+        assert_eq!(rtps_message.discarded_submessage_count(), 1);
+        let submessages = rtps_message.submessages();
+        assert_eq!(submessages.len(), 1);
+        assert!(matches!(
+            submessages[0],
+            RtpsSubmessageReadKind::Heartbeat(..)
+        ));
+    }
 }
@@ -6,7 +6,7 @@ use super::{
     overall_structure::{Endianness, TryReadFromBytes, WriteIntoBytes},
     types::ParameterId,
 };
-use crate::transport::types::{Locator, SequenceNumber};
+use crate::transport::types::{Locator, SequenceNumber, SEQUENCENUMBER_MAX};
 use std::{
     io::{BufRead, Write},
     ops::Range,
@@ -80,9 +80,23 @@ impl SequenceNumberSet {
     }
 }
 
+/// Checks that a sequence number received from a remote endpoint is strictly positive (which also
+/// excludes SEQUENCENUMBER_UNKNOWN) and not above [`SEQUENCENUMBER_MAX`].
+pub fn validate_sequence_number(seq_num: SequenceNumber) -> RtpsResult<SequenceNumber> {
+    if (1..=SEQUENCENUMBER_MAX).contains(&seq_num) {
+        Ok(seq_num)
+    } else {
+        Err(RtpsError::new(
+            RtpsErrorKind::InvalidData,
+            format!("Invalid sequence number {}", seq_num),
+        ))
+    }
+}
+
 impl TryReadFromBytes for SequenceNumberSet {
     fn try_read_from_bytes(data: &mut &[u8], endianness: &Endianness) -> RtpsResult<Self> {
-        let base = SequenceNumber::try_read_from_bytes(data, endianness)?;
+        let base =
+            validate_sequence_number(SequenceNumber::try_read_from_bytes(data, endianness)?)?;
         let num_bits = u32::try_read_from_bytes(data, endianness)?;
         if num_bits > 256 {
             return Err(RtpsError::new(
@@ -129,6 +143,10 @@ impl FragmentNumberSet {
         }
     }
 
+    pub fn set(&self) -> impl Iterator<Item = FragmentNumber> + '_ {
+        self.set.iter().copied()
+    }
+
     pub fn try_read_from_bytes(data: &mut &[u8], endianness: &Endianness) -> RtpsResult<Self> {
         let base = FragmentNumber::try_read_from_bytes(data, endianness)?;
         let num_bits = u32::try_read_from_bytes(data, endianness)?;
@@ -295,14 +313,21 @@ impl ParameterList {
         self.parameter.as_ref()
     }
 
-    pub fn try_read_from_bytes(data: &mut &[u8], endianness: &Endianness) -> RtpsResult<Self> {
-        const MAX_PARAMETERS: usize = 2_usize.pow(16);
-
+    pub fn try_read_from_bytes(
+        data: &mut &[u8],
+        endianness: &Endianness,
+        max_parameter_list_length: usize,
+    ) -> RtpsResult<Self> {
         let mut parameter = vec![];
-        for _ in 0..MAX_PARAMETERS {
+        loop {
             let parameter_i = Parameter::try_read_from_bytes(data, endianness)?;
             if parameter_i.parameter_id() == PID_SENTINEL {
                 break;
+            } else if parameter.len() == max_parameter_list_length {
+                return Err(RtpsError::new(
+                    RtpsErrorKind::InvalidData,
+                    "Parameter list exceeds the maximum length",
+                ));
             } else {
                 parameter.push(parameter_i);
             }
@@ -418,7 +443,7 @@ impl From<Arc<[u8]>> for Data {
 
 impl From<Data> for Arc<[u8]> {
     fn from(value: Data) -> Self {
-        value.0.clone()
+        value.0
     }
 }
 
@@ -901,6 +926,7 @@ mod tests {
             ]
             .as_slice(),
             &Endianness::LittleEndian,
+            16,
         )
         .unwrap();
         assert_eq!(expected, result);
@@ -932,6 +958,7 @@ mod tests {
             ]
             .as_slice(),
             &Endianness::LittleEndian,
+            16,
         )
         .unwrap();
         assert_eq!(expected, result);
@@ -979,7 +1006,24 @@ mod tests {
             0x02, 0x02, 0x02, 0x02, // Parameter value
             0x02, 0x02, 0x02, 0x02, // Parameter value
             0x01, 0x00, 0x00, 0x00, // PID_SENTINEL, Length: 0
-        ].as_slice(), &Endianness::LittleEndian).unwrap();
+        ].as_slice(), &Endianness::LittleEndian, 16).unwrap();
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn deserialize_parameter_list_above_maximum_length() {
+        let result = ParameterList::try_read_from_bytes(
+            &mut [
+                0x02, 0x00, 4, 0, // Parameter ID | length
+                15, 16, 17, 18, // value
+                0x03, 0x00, 4, 0, // Parameter ID | length
+                25, 26, 27, 28, // value
+                0x01, 0x00, 0, 0, // Sentinel: Parameter ID | length
+            ]
+            .as_slice(),
+            &Endianness::LittleEndian,
+            1,
+        );
+        assert!(result.is_err());
+    }
 }
@@ -37,7 +37,7 @@ impl AckNackSubmessage {
         })
     }
 
-    pub fn _final_flag(&self) -> bool {
+    pub fn final_flag(&self) -> bool {
         self.final_flag
     }
 
@@ -92,6 +92,10 @@ impl Submessage for AckNackSubmessage {
         )
         .write_into_bytes(buf);
     }
+
+    fn endpoint_entity_id(&self) -> Option<EntityId> {
+        Some(self.reader_id)
+    }
 }
 
 #[cfg(test)]
@@ -147,7 +151,7 @@ mod tests {
         let expected_reader_sn_state = SequenceNumberSet::new(10, []);
         let expected_count = 2;
 
-        assert_eq!(expected_final_flag, submessage._final_flag());
+        assert_eq!(expected_final_flag, submessage.final_flag());
         assert_eq!(&expected_reader_id, submessage.reader_id());
         assert_eq!(&expected_writer_id, submessage.writer_id());
         assert_eq!(&expected_reader_sn_state, submessage.reader_sn_state());
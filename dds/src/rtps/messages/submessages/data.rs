@@ -5,7 +5,7 @@ use super::super::super::{
             Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes,
             WriteIntoBytes,
         },
-        submessage_elements::{Data, ParameterList},
+        submessage_elements::{validate_sequence_number, Data, ParameterList},
         types::{SubmessageFlag, SubmessageKind},
     },
 };
@@ -32,6 +32,7 @@ impl DataSubmessage {
     pub fn try_from_bytes(
         submessage_header: &SubmessageHeaderRead,
         data: &[u8],
+        max_parameter_list_length: usize,
     ) -> RtpsResult<Self> {
         if submessage_header.submessage_length() as usize > data.len() {
             return Err(RtpsError::new(
@@ -50,7 +51,8 @@ impl DataSubmessage {
         let octets_to_inline_qos = u16::try_read_from_bytes(&mut slice, endianness)? as usize + 4;
         let reader_id = EntityId::try_read_from_bytes(&mut slice, endianness)?;
         let writer_id = EntityId::try_read_from_bytes(&mut slice, endianness)?;
-        let writer_sn = SequenceNumber::try_read_from_bytes(&mut slice, endianness)?;
+        let writer_sn =
+            validate_sequence_number(SequenceNumber::try_read_from_bytes(&mut slice, endianness)?)?;
 
         if octets_to_inline_qos > submessage_header.submessage_length() as usize {
             return Err(RtpsError::new(
@@ -64,6 +66,7 @@ impl DataSubmessage {
             ParameterList::try_read_from_bytes(
                 &mut data_starting_at_inline_qos,
                 submessage_header.endianness(),
+                max_parameter_list_length,
             )?
         } else {
             ParameterList::empty()
@@ -180,6 +183,10 @@ impl Submessage for DataSubmessage {
             self.serialized_payload.write_into_bytes(buf);
         }
     }
+
+    fn endpoint_entity_id(&self) -> Option<EntityId> {
+        Some(self.writer_id)
+    }
 }
 
 #[cfg(test)]
@@ -324,7 +331,7 @@ mod tests {
             5, 0, 0, 0, // writerSN: low
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
-        let data_submessage = DataSubmessage::try_from_bytes(&submessage_header, data).unwrap();
+        let data_submessage = DataSubmessage::try_from_bytes(&submessage_header, data, 16).unwrap();
 
         assert_eq!(inline_qos_flag, data_submessage._inline_qos_flag());
         assert_eq!(data_flag, data_submessage._data_flag());
@@ -353,7 +360,7 @@ mod tests {
             123, 123, 123 // Following data
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
-        let data_submessage = DataSubmessage::try_from_bytes(&submessage_header, data).unwrap();
+        let data_submessage = DataSubmessage::try_from_bytes(&submessage_header, data, 16).unwrap();
         assert_eq!(&expected_inline_qos, data_submessage.inline_qos());
         assert_eq!(
             &expected_serialized_payload,
@@ -384,7 +391,7 @@ mod tests {
             1, 0, 1, 0, // inlineQos: Sentinel
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
-        let data_submessage = DataSubmessage::try_from_bytes(&submessage_header, data).unwrap();
+        let data_submessage = DataSubmessage::try_from_bytes(&submessage_header, data, 16).unwrap();
         assert_eq!(&inline_qos, data_submessage.inline_qos());
         assert_eq!(&serialized_payload, data_submessage.serialized_payload());
     }
@@ -413,7 +420,7 @@ mod tests {
             1, 2, 3, 4, // SerializedPayload
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
-        let data_submessage = DataSubmessage::try_from_bytes(&submessage_header, data).unwrap();
+        let data_submessage = DataSubmessage::try_from_bytes(&submessage_header, data, 16).unwrap();
 
         assert_eq!(&expected_inline_qos, data_submessage.inline_qos());
         assert_eq!(
@@ -444,7 +451,7 @@ mod tests {
             1, 0, 1, 0, // inlineQos: Sentinel
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
-        let data_submessage = DataSubmessage::try_from_bytes(&submessage_header, data).unwrap();
+        let data_submessage = DataSubmessage::try_from_bytes(&submessage_header, data, 16).unwrap();
 
         assert_eq!(&expected_inline_qos, data_submessage.inline_qos());
     }
@@ -462,7 +469,7 @@ mod tests {
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
         // Should not panic with this input
-        let _ = DataSubmessage::try_from_bytes(&submessage_header, data);
+        let _ = DataSubmessage::try_from_bytes(&submessage_header, data, 16);
     }
 
     #[test]
@@ -480,7 +487,7 @@ mod tests {
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
         // Should not panic with this input
-        let _ = DataSubmessage::try_from_bytes(&submessage_header, data);
+        let _ = DataSubmessage::try_from_bytes(&submessage_header, data, 16);
     }
 
     #[test]
@@ -492,6 +499,6 @@ mod tests {
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
         // Should not panic with this input
-        let _ = DataSubmessage::try_from_bytes(&submessage_header, data);
+        let _ = DataSubmessage::try_from_bytes(&submessage_header, data, 16);
     }
 }
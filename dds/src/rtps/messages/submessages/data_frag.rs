@@ -10,7 +10,7 @@ use super::super::super::{
             Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes,
             WriteIntoBytes,
         },
-        submessage_elements::ParameterList,
+        submessage_elements::{validate_sequence_number, ParameterList},
         types::{FragmentNumber, SubmessageFlag, SubmessageKind},
     },
 };
@@ -36,6 +36,7 @@ impl DataFragSubmessage {
     pub fn try_from_bytes(
         submessage_header: &SubmessageHeaderRead,
         data: &[u8],
+        max_parameter_list_length: usize,
     ) -> RtpsResult<Self> {
         if submessage_header.submessage_length() as usize > data.len() {
             return Err(RtpsError::new(
@@ -56,13 +57,22 @@ impl DataFragSubmessage {
                 u16::try_read_from_bytes(&mut slice, endianness)? as usize + 4;
             let reader_id = EntityId::try_read_from_bytes(&mut slice, endianness)?;
             let writer_id = EntityId::try_read_from_bytes(&mut slice, endianness)?;
-            let writer_sn = SequenceNumber::try_read_from_bytes(&mut slice, endianness)?;
+            let writer_sn = validate_sequence_number(SequenceNumber::try_read_from_bytes(
+                &mut slice, endianness,
+            )?)?;
             let fragment_starting_num =
                 FragmentNumber::try_read_from_bytes(&mut slice, endianness)?;
             let fragments_in_submessage = u16::try_read_from_bytes(&mut slice, endianness)?;
             let fragment_size = u16::try_read_from_bytes(&mut slice, endianness)?;
             let data_size = u32::try_read_from_bytes(&mut slice, endianness)?;
 
+            if fragment_size == 0 {
+                return Err(RtpsError::new(
+                    RtpsErrorKind::InvalidData,
+                    "Invalid fragment size",
+                ));
+            }
+
             if octets_to_inline_qos > submessage_header.submessage_length() as usize {
                 return Err(RtpsError::new(
                     RtpsErrorKind::InvalidData,
@@ -74,7 +84,11 @@ impl DataFragSubmessage {
                 &data[octets_to_inline_qos..submessage_header.submessage_length() as usize];
 
             let inline_qos = if inline_qos_flag {
-                ParameterList::try_read_from_bytes(&mut data_starting_at_inline_qos, endianness)?
+                ParameterList::try_read_from_bytes(
+                    &mut data_starting_at_inline_qos,
+                    endianness,
+                    max_parameter_list_length,
+                )?
             } else {
                 ParameterList::empty()
             };
@@ -215,6 +229,10 @@ impl Submessage for DataFragSubmessage {
         }
         self.serialized_payload.write_into_bytes(buf);
     }
+
+    fn endpoint_entity_id(&self) -> Option<EntityId> {
+        Some(self.writer_id)
+    }
 }
 
 #[cfg(test)]
@@ -312,7 +330,7 @@ mod tests {
             4, 0, 0, 0, // sampleSize
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
-        let submessage = DataFragSubmessage::try_from_bytes(&submessage_header, data).unwrap();
+        let submessage = DataFragSubmessage::try_from_bytes(&submessage_header, data, 16).unwrap();
 
         let expected_inline_qos_flag = false;
         let expected_non_standard_payload_flag = false;
@@ -372,8 +390,7 @@ mod tests {
             1, 2, 3, 0, // serializedPayload
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
-        let submessage =
-            DataFragSubmessage::try_from_bytes(&submessage_header, data.into()).unwrap();
+        let submessage = DataFragSubmessage::try_from_bytes(&submessage_header, data, 16).unwrap();
 
         let expected_inline_qos_flag = true;
         let expected_non_standard_payload_flag = false;
@@ -424,6 +441,6 @@ mod tests {
         ][..];
         let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
         // Should not panic with this input
-        let _ = DataFragSubmessage::try_from_bytes(&submessage_header, data.into());
+        let _ = DataFragSubmessage::try_from_bytes(&submessage_header, data, 16);
     }
 }
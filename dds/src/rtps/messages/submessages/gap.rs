@@ -7,7 +7,7 @@ use super::super::super::{
             Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes,
             WriteIntoBytes,
         },
-        submessage_elements::SequenceNumberSet,
+        submessage_elements::{validate_sequence_number, SequenceNumberSet},
         types::SubmessageKind,
     },
 };
@@ -30,7 +30,9 @@ impl GapSubmessage {
         Ok(Self {
             reader_id: EntityId::try_read_from_bytes(&mut data, endianness)?,
             writer_id: EntityId::try_read_from_bytes(&mut data, endianness)?,
-            gap_start: SequenceNumber::try_read_from_bytes(&mut data, endianness)?,
+            gap_start: validate_sequence_number(SequenceNumber::try_read_from_bytes(
+                &mut data, endianness,
+            )?)?,
             gap_list: SequenceNumberSet::try_read_from_bytes(&mut data, endianness)?,
         })
     }
@@ -80,6 +82,10 @@ impl Submessage for GapSubmessage {
         self.gap_start.write_into_bytes(buf);
         self.gap_list.write_into_bytes(buf);
     }
+
+    fn endpoint_entity_id(&self) -> Option<EntityId> {
+        Some(self.writer_id)
+    }
 }
 
 #[cfg(test)]
@@ -1,12 +1,13 @@
-use crate::transport::types::{EntityId, SequenceNumber};
+use crate::transport::types::{EntityId, SequenceNumber, SEQUENCENUMBER_MAX};
 
 use super::super::super::{
-    error::RtpsResult,
+    error::{RtpsError, RtpsErrorKind, RtpsResult},
     messages::{
         overall_structure::{
             Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes,
             WriteIntoBytes,
         },
+        submessage_elements::validate_sequence_number,
         types::{Count, SubmessageFlag, SubmessageKind},
     },
 };
@@ -29,13 +30,25 @@ impl HeartbeatSubmessage {
         mut data: &[u8],
     ) -> RtpsResult<Self> {
         let endianness = submessage_header.endianness();
+        let reader_id = EntityId::try_read_from_bytes(&mut data, endianness)?;
+        let writer_id = EntityId::try_read_from_bytes(&mut data, endianness)?;
+        let first_sn =
+            validate_sequence_number(SequenceNumber::try_read_from_bytes(&mut data, endianness)?)?;
+        let last_sn = SequenceNumber::try_read_from_bytes(&mut data, endianness)?;
+        // As of 8.3.7.5.3 the last_sn can be first_sn - 1 to indicate that no changes are available
+        if last_sn < first_sn - 1 || last_sn > SEQUENCENUMBER_MAX {
+            return Err(RtpsError::new(
+                RtpsErrorKind::InvalidData,
+                "Invalid heartbeat sequence number range",
+            ));
+        }
         Ok(Self {
             final_flag: submessage_header.flags()[1],
             liveliness_flag: submessage_header.flags()[2],
-            reader_id: EntityId::try_read_from_bytes(&mut data, endianness)?,
-            writer_id: EntityId::try_read_from_bytes(&mut data, endianness)?,
-            first_sn: SequenceNumber::try_read_from_bytes(&mut data, endianness)?,
-            last_sn: SequenceNumber::try_read_from_bytes(&mut data, endianness)?,
+            reader_id,
+            writer_id,
+            first_sn,
+            last_sn,
             count: Count::try_read_from_bytes(&mut data, endianness)?,
         })
     }
@@ -108,6 +121,10 @@ impl Submessage for HeartbeatSubmessage {
         self.last_sn.write_into_bytes(buf);
         self.count.write_into_bytes(buf);
     }
+
+    fn endpoint_entity_id(&self) -> Option<EntityId> {
+        Some(self.writer_id)
+    }
 }
 
 #[cfg(test)]
@@ -180,4 +197,38 @@ mod tests {
         assert_eq!(expected_last_sn, submessage.last_sn());
         assert_eq!(expected_count, submessage.count());
     }
+
+    #[test]
+    fn deserialize_heart_beat_with_invalid_sequence_number_range() {
+        #[rustfmt::skip]
+        let mut data = &[
+            0x07, 0b_0000_0101, 28, 0, // Submessage header
+            1, 2, 3, 4, // readerId: value[4]
+            6, 7, 8, 9, // writerId: value[4]
+            0, 0, 0, 0, // firstSN: SequenceNumber: high
+            5, 0, 0, 0, // firstSN: SequenceNumber: low
+            0, 0, 0, 0, // lastSN: SequenceNumberSet: high
+            3, 0, 0, 0, // lastSN: SequenceNumberSet: low
+            2, 0, 0, 0, // count: Count: value (long)
+        ][..];
+        let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
+        assert!(HeartbeatSubmessage::try_from_bytes(&submessage_header, data).is_err());
+    }
+
+    #[test]
+    fn deserialize_heart_beat_with_unknown_first_sequence_number() {
+        #[rustfmt::skip]
+        let mut data = &[
+            0x07, 0b_0000_0101, 28, 0, // Submessage header
+            1, 2, 3, 4, // readerId: value[4]
+            6, 7, 8, 9, // writerId: value[4]
+            0xff, 0xff, 0xff, 0xff, // firstSN: SequenceNumber: high
+            0, 0, 0, 0, // firstSN: SequenceNumber: low
+            0, 0, 0, 0, // lastSN: SequenceNumberSet: high
+            7, 0, 0, 0, // lastSN: SequenceNumberSet: low
+            2, 0, 0, 0, // count: Count: value (long)
+        ][..];
+        let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
+        assert!(HeartbeatSubmessage::try_from_bytes(&submessage_header, data).is_err());
+    }
 }
@@ -7,6 +7,7 @@ use super::super::super::{
             Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes,
             WriteIntoBytes,
         },
+        submessage_elements::validate_sequence_number,
         types::{Count, FragmentNumber, SubmessageKind},
     },
 };
@@ -30,7 +31,9 @@ impl HeartbeatFragSubmessage {
         Ok(Self {
             reader_id: EntityId::try_read_from_bytes(&mut data, endianness)?,
             writer_id: EntityId::try_read_from_bytes(&mut data, endianness)?,
-            writer_sn: SequenceNumber::try_read_from_bytes(&mut data, endianness)?,
+            writer_sn: validate_sequence_number(SequenceNumber::try_read_from_bytes(
+                &mut data, endianness,
+            )?)?,
             last_fragment_num: FragmentNumber::try_read_from_bytes(&mut data, endianness)?,
             count: Count::try_read_from_bytes(&mut data, endianness)?,
         })
@@ -44,11 +47,11 @@ impl HeartbeatFragSubmessage {
         self.writer_id
     }
 
-    pub fn _writer_sn(&self) -> SequenceNumber {
+    pub fn writer_sn(&self) -> SequenceNumber {
         self.writer_sn
     }
 
-    pub fn _last_fragment_num(&self) -> FragmentNumber {
+    pub fn last_fragment_num(&self) -> FragmentNumber {
         self.last_fragment_num
     }
 
@@ -58,7 +61,7 @@ impl HeartbeatFragSubmessage {
 }
 
 impl HeartbeatFragSubmessage {
-    pub fn _new(
+    pub fn new(
         reader_id: EntityId,
         writer_id: EntityId,
         writer_sn: SequenceNumber,
@@ -88,6 +91,10 @@ impl Submessage for HeartbeatFragSubmessage {
         self.last_fragment_num.write_into_bytes(buf);
         self.count.write_into_bytes(buf);
     }
+
+    fn endpoint_entity_id(&self) -> Option<EntityId> {
+        Some(self.writer_id)
+    }
 }
 
 #[cfg(test)]
@@ -100,7 +107,7 @@ mod tests {
 
     #[test]
     fn serialize_heart_beat() {
-        let submessage = HeartbeatFragSubmessage::_new(
+        let submessage = HeartbeatFragSubmessage::new(
             EntityId::new([1, 2, 3], USER_DEFINED_READER_NO_KEY),
             EntityId::new([6, 7, 8], USER_DEFINED_READER_GROUP),
             5,
@@ -143,8 +150,8 @@ mod tests {
 
         assert_eq!(expected_reader_id, submessage._reader_id());
         assert_eq!(expected_writer_id, submessage.writer_id());
-        assert_eq!(expected_writer_sn, submessage._writer_sn());
-        assert_eq!(expected_last_fragment_num, submessage._last_fragment_num());
+        assert_eq!(expected_writer_sn, submessage.writer_sn());
+        assert_eq!(expected_last_fragment_num, submessage.last_fragment_num());
         assert_eq!(expected_count, submessage.count());
     }
 }
@@ -10,3 +10,6 @@ pub mod info_source;
 pub mod info_timestamp;
 pub mod nack_frag;
 pub mod pad;
+pub mod sec_body;
+pub mod sec_postfix;
+pub mod sec_prefix;
@@ -7,7 +7,7 @@ use super::super::super::{
             Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes,
             WriteIntoBytes,
         },
-        submessage_elements::FragmentNumberSet,
+        submessage_elements::{validate_sequence_number, FragmentNumberSet},
         types::{Count, SubmessageKind},
     },
 };
@@ -31,7 +31,9 @@ impl NackFragSubmessage {
         Ok(Self {
             reader_id: EntityId::try_read_from_bytes(&mut data, endianness)?,
             writer_id: EntityId::try_read_from_bytes(&mut data, endianness)?,
-            writer_sn: SequenceNumber::try_read_from_bytes(&mut data, endianness)?,
+            writer_sn: validate_sequence_number(SequenceNumber::try_read_from_bytes(
+                &mut data, endianness,
+            )?)?,
             fragment_number_state: FragmentNumberSet::try_read_from_bytes(&mut data, endianness)?,
             count: Count::try_read_from_bytes(&mut data, endianness)?,
         })
@@ -49,7 +51,7 @@ impl NackFragSubmessage {
         self.writer_sn
     }
 
-    pub fn _fragment_number_state(&self) -> &FragmentNumberSet {
+    pub fn fragment_number_state(&self) -> &FragmentNumberSet {
         &self.fragment_number_state
     }
 
@@ -89,6 +91,10 @@ impl Submessage for NackFragSubmessage {
         self.fragment_number_state.write_into_bytes(buf);
         self.count.write_into_bytes(buf);
     }
+
+    fn endpoint_entity_id(&self) -> Option<EntityId> {
+        Some(self.reader_id)
+    }
 }
 
 #[cfg(test)]
@@ -149,7 +155,7 @@ mod tests {
         assert_eq!(expected_writer_sn, submessage.writer_sn());
         assert_eq!(
             &expected_fragment_number_state,
-            submessage._fragment_number_state()
+            submessage.fragment_number_state()
         );
         assert_eq!(expected_count, submessage.count());
     }
@@ -0,0 +1,108 @@
+use super::super::super::{
+    error::{RtpsError, RtpsErrorKind, RtpsResult},
+    messages::{
+        overall_structure::{
+            Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes,
+            WriteIntoBytes,
+        },
+        types::SubmessageKind,
+    },
+};
+use std::io::Write;
+
+/// SEC_BODY carrying the CryptoContent of DDS Security 9.5.2.4. The content is the encrypted
+/// submessage or, when the submessage is only signed, the submessage itself.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SecureBodySubmessage {
+    crypto_content: Vec<u8>,
+}
+
+impl SecureBodySubmessage {
+    pub fn try_from_bytes(
+        submessage_header: &SubmessageHeaderRead,
+        mut data: &[u8],
+    ) -> RtpsResult<Self> {
+        let length = u32::try_read_from_bytes(&mut data, submessage_header.endianness())? as usize;
+        if data.len() < length {
+            return Err(RtpsError::new(
+                RtpsErrorKind::NotEnoughData,
+                "Secure body crypto content",
+            ));
+        }
+        Ok(Self {
+            crypto_content: data[..length].to_vec(),
+        })
+    }
+
+    pub fn crypto_content(&self) -> &[u8] {
+        &self.crypto_content
+    }
+}
+
+impl SecureBodySubmessage {
+    pub fn new(crypto_content: Vec<u8>) -> Self {
+        Self { crypto_content }
+    }
+}
+
+impl Submessage for SecureBodySubmessage {
+    fn write_submessage_header_into_bytes(&self, octets_to_next_header: u16, buf: &mut dyn Write) {
+        SubmessageHeaderWrite::new(SubmessageKind::SEC_BODY, &[], octets_to_next_header)
+            .write_into_bytes(buf);
+    }
+
+    fn write_submessage_elements_into_bytes(&self, buf: &mut dyn Write) {
+        (self.crypto_content.len() as u32).write_into_bytes(buf);
+        self.crypto_content.as_slice().write_into_bytes(buf);
+        // The next submessage header is aligned to 4 bytes
+        let padding = (4 - self.crypto_content.len() % 4) % 4;
+        (&[0_u8; 3][..padding]).write_into_bytes(buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::messages::overall_structure::write_submessage_into_bytes_vec;
+
+    #[test]
+    fn serialize_secure_body() {
+        let submessage = SecureBodySubmessage::new(vec![1, 2, 3, 4, 5]);
+        #[rustfmt::skip]
+        assert_eq!(write_submessage_into_bytes_vec(&submessage), vec![
+                0x30, 0b_0000_0001, 12, 0, // Submessage header
+                5, 0, 0, 0, // crypto_content length
+                1, 2, 3, 4, // crypto_content
+                5, 0, 0, 0, // crypto_content | padding
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_secure_body() {
+        #[rustfmt::skip]
+        let mut data = &[
+            0x30, 0b_0000_0001, 12, 0, // Submessage header
+            5, 0, 0, 0, // crypto_content length
+            1, 2, 3, 4, // crypto_content
+            5, 0, 0, 0, // crypto_content | padding
+        ][..];
+        let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
+        let submessage = SecureBodySubmessage::try_from_bytes(&submessage_header, data).unwrap();
+
+        assert_eq!(submessage.crypto_content(), &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn deserialize_secure_body_with_invalid_length() {
+        #[rustfmt::skip]
+        let mut data = &[
+            0x30, 0b_0000_0001, 8, 0, // Submessage header
+            5, 0, 0, 0, // crypto_content length
+            1, 2, 3, 4, // crypto_content
+        ][..];
+        let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
+
+        assert!(SecureBodySubmessage::try_from_bytes(&submessage_header, data).is_err());
+    }
+}
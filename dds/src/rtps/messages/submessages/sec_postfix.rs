@@ -0,0 +1,92 @@
+use super::super::super::{
+    error::RtpsResult,
+    messages::{
+        overall_structure::{
+            Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, WriteIntoBytes,
+        },
+        types::SubmessageKind,
+    },
+};
+use std::io::{Read, Write};
+
+/// SEC_POSTFIX carrying the CryptoFooter of DDS Security 9.5.2.5. Only the common MAC is used,
+/// the receiver specific MACs are neither sent nor checked.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SecurePostfixSubmessage {
+    common_mac: [u8; 16],
+}
+
+impl SecurePostfixSubmessage {
+    pub fn try_from_bytes(
+        _submessage_header: &SubmessageHeaderRead,
+        mut data: &[u8],
+    ) -> RtpsResult<Self> {
+        let mut common_mac = [0; 16];
+        data.read_exact(&mut common_mac)?;
+        Ok(Self { common_mac })
+    }
+
+    pub fn common_mac(&self) -> &[u8; 16] {
+        &self.common_mac
+    }
+}
+
+impl SecurePostfixSubmessage {
+    pub fn new(common_mac: [u8; 16]) -> Self {
+        Self { common_mac }
+    }
+}
+
+impl Submessage for SecurePostfixSubmessage {
+    fn write_submessage_header_into_bytes(&self, octets_to_next_header: u16, buf: &mut dyn Write) {
+        SubmessageHeaderWrite::new(SubmessageKind::SEC_POSTFIX, &[], octets_to_next_header)
+            .write_into_bytes(buf);
+    }
+
+    fn write_submessage_elements_into_bytes(&self, buf: &mut dyn Write) {
+        self.common_mac.write_into_bytes(buf);
+        // Empty sequence of receiver specific MACs
+        0_u32.write_into_bytes(buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::messages::overall_structure::write_submessage_into_bytes_vec;
+
+    #[test]
+    fn serialize_secure_postfix() {
+        let submessage = SecurePostfixSubmessage::new([7; 16]);
+        #[rustfmt::skip]
+        assert_eq!(write_submessage_into_bytes_vec(&submessage), vec![
+                0x32, 0b_0000_0001, 20, 0, // Submessage header
+                7, 7, 7, 7, // common_mac
+                7, 7, 7, 7, // common_mac
+                7, 7, 7, 7, // common_mac
+                7, 7, 7, 7, // common_mac
+                0, 0, 0, 0, // receiver_specific_macs length
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_secure_postfix() {
+        #[rustfmt::skip]
+        let mut data = &[
+            0x32, 0b_0000_0001, 20, 0, // Submessage header
+            1, 2, 3, 4, // common_mac
+            5, 6, 7, 8, // common_mac
+            9, 10, 11, 12, // common_mac
+            13, 14, 15, 16, // common_mac
+            0, 0, 0, 0, // receiver_specific_macs length
+        ][..];
+        let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
+        let submessage = SecurePostfixSubmessage::try_from_bytes(&submessage_header, data).unwrap();
+
+        assert_eq!(
+            submessage.common_mac(),
+            &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+        );
+    }
+}
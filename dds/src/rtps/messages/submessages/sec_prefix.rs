@@ -0,0 +1,123 @@
+use super::super::super::{
+    error::RtpsResult,
+    messages::{
+        overall_structure::{
+            Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, WriteIntoBytes,
+        },
+        types::SubmessageKind,
+    },
+};
+use std::io::{Read, Write};
+
+/// CryptoHeader as defined in DDS Security 9.5.2.3 identifying the transformation, the key
+/// and the initialization vector used to protect the submessage
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CryptoHeader {
+    pub transformation_kind: [u8; 4],
+    pub transformation_key_id: [u8; 4],
+    pub session_id: [u8; 4],
+    pub initialization_vector_suffix: [u8; 8],
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SecurePrefixSubmessage {
+    crypto_header: CryptoHeader,
+}
+
+impl SecurePrefixSubmessage {
+    pub fn try_from_bytes(
+        _submessage_header: &SubmessageHeaderRead,
+        mut data: &[u8],
+    ) -> RtpsResult<Self> {
+        let mut crypto_header = CryptoHeader {
+            transformation_kind: [0; 4],
+            transformation_key_id: [0; 4],
+            session_id: [0; 4],
+            initialization_vector_suffix: [0; 8],
+        };
+        data.read_exact(&mut crypto_header.transformation_kind)?;
+        data.read_exact(&mut crypto_header.transformation_key_id)?;
+        data.read_exact(&mut crypto_header.session_id)?;
+        data.read_exact(&mut crypto_header.initialization_vector_suffix)?;
+        Ok(Self { crypto_header })
+    }
+
+    pub fn crypto_header(&self) -> &CryptoHeader {
+        &self.crypto_header
+    }
+}
+
+impl SecurePrefixSubmessage {
+    pub fn new(crypto_header: CryptoHeader) -> Self {
+        Self { crypto_header }
+    }
+}
+
+impl Submessage for SecurePrefixSubmessage {
+    fn write_submessage_header_into_bytes(&self, octets_to_next_header: u16, buf: &mut dyn Write) {
+        SubmessageHeaderWrite::new(SubmessageKind::SEC_PREFIX, &[], octets_to_next_header)
+            .write_into_bytes(buf);
+    }
+
+    fn write_submessage_elements_into_bytes(&self, buf: &mut dyn Write) {
+        self.crypto_header.transformation_kind.write_into_bytes(buf);
+        self.crypto_header
+            .transformation_key_id
+            .write_into_bytes(buf);
+        self.crypto_header.session_id.write_into_bytes(buf);
+        self.crypto_header
+            .initialization_vector_suffix
+            .write_into_bytes(buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::messages::overall_structure::write_submessage_into_bytes_vec;
+
+    #[test]
+    fn serialize_secure_prefix() {
+        let submessage = SecurePrefixSubmessage::new(CryptoHeader {
+            transformation_kind: [0, 0, 0, 4],
+            transformation_key_id: [1, 2, 3, 4],
+            session_id: [5, 6, 7, 8],
+            initialization_vector_suffix: [9, 10, 11, 12, 13, 14, 15, 16],
+        });
+        #[rustfmt::skip]
+        assert_eq!(write_submessage_into_bytes_vec(&submessage), vec![
+                0x31, 0b_0000_0001, 20, 0, // Submessage header
+                0, 0, 0, 4, // transformation_kind
+                1, 2, 3, 4, // transformation_key_id
+                5, 6, 7, 8, // session_id
+                9, 10, 11, 12, // initialization_vector_suffix
+                13, 14, 15, 16, // initialization_vector_suffix
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_secure_prefix() {
+        #[rustfmt::skip]
+        let mut data = &[
+            0x31, 0b_0000_0001, 20, 0, // Submessage header
+            0, 0, 0, 3, // transformation_kind
+            1, 2, 3, 4, // transformation_key_id
+            5, 6, 7, 8, // session_id
+            9, 10, 11, 12, // initialization_vector_suffix
+            13, 14, 15, 16, // initialization_vector_suffix
+        ][..];
+        let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
+        let submessage = SecurePrefixSubmessage::try_from_bytes(&submessage_header, data).unwrap();
+
+        assert_eq!(
+            submessage.crypto_header(),
+            &CryptoHeader {
+                transformation_kind: [0, 0, 0, 3],
+                transformation_key_id: [1, 2, 3, 4],
+                session_id: [5, 6, 7, 8],
+                initialization_vector_suffix: [9, 10, 11, 12, 13, 14, 15, 16],
+            }
+        );
+    }
+}
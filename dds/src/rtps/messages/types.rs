@@ -81,6 +81,7 @@ pub type SubmessageFlag = bool;
 /// Enumeration used to identify the kind of Submessage.
 /// The following values are reserved by this version of the protocol:
 /// DATA, GAP, HEARTBEAT, ACKNACK, PAD, INFO_TS, INFO_REPLY, INFO_DST, INFO_SRC, DATA_FRAG, NACK_FRAG, HEARTBEAT_FRAG
+/// The SEC_BODY, SEC_PREFIX and SEC_POSTFIX submessages are defined by DDS Security 7.3.6
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[allow(non_camel_case_types)]
 #[allow(clippy::upper_case_acronyms)]
@@ -97,6 +98,9 @@ pub enum SubmessageKind {
     DATA_FRAG,
     NACK_FRAG,
     HEARTBEAT_FRAG,
+    SEC_BODY,
+    SEC_PREFIX,
+    SEC_POSTFIX,
 }
 
 pub const DATA: u8 = 0x15;
@@ -111,6 +115,9 @@ pub const INFO_SRC: u8 = 0x0c;
 pub const DATA_FRAG: u8 = 0x16;
 pub const NACK_FRAG: u8 = 0x12;
 pub const HEARTBEAT_FRAG: u8 = 0x13;
+pub const SEC_BODY: u8 = 0x30;
+pub const SEC_PREFIX: u8 = 0x31;
+pub const SEC_POSTFIX: u8 = 0x32;
 
 impl WriteIntoBytes for SubmessageKind {
     fn write_into_bytes(&self, buf: &mut dyn Write) {
@@ -127,6 +134,9 @@ impl WriteIntoBytes for SubmessageKind {
             SubmessageKind::DATA_FRAG => DATA_FRAG,
             SubmessageKind::NACK_FRAG => NACK_FRAG,
             SubmessageKind::HEARTBEAT_FRAG => HEARTBEAT_FRAG,
+            SubmessageKind::SEC_BODY => SEC_BODY,
+            SubmessageKind::SEC_PREFIX => SEC_PREFIX,
+            SubmessageKind::SEC_POSTFIX => SEC_POSTFIX,
         };
         data.write_into_bytes(buf);
     }
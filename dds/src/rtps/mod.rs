@@ -1,6 +1,7 @@
 pub mod behavior_types;
 pub mod cache_change;
 pub mod error;
+pub mod flow_controller;
 pub mod message_receiver;
 pub mod message_sender;
 pub mod messages;
@@ -11,6 +12,9 @@ pub mod stateful_reader;
 pub mod stateful_writer;
 pub mod stateless_reader;
 pub mod stateless_writer;
+pub mod tcp_transport;
 pub mod transport;
 pub mod types;
+pub mod udp_batch;
+pub mod writer_history_cache;
 pub mod writer_proxy;
@@ -1,5 +1,16 @@
 use crate::{
-    rtps::{message_receiver::MessageReceiver, stateful_writer::RtpsStatefulWriter},
+    configuration::{
+        DeserializationLimits, DestinationMtu, FlowControl, FlowControlScope, IpVersion,
+        RetransmissionBackoff, TransportPriorityDscp, WriterBatching,
+    },
+    implementation::domain_participant_factory::domain_participant_factory_actor::{
+        ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+        ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER, ENTITYID_SEDP_BUILTIN_TOPICS_ANNOUNCER,
+    },
+    rtps::{
+        message_receiver::MessageReceiver, messages::overall_structure::SubmessageCrypto,
+        stateful_writer::RtpsStatefulWriter,
+    },
     runtime::{
         actor::{ActorAddress, Mail, MailHandler},
         executor::block_on,
@@ -7,20 +18,26 @@ use crate::{
     transport::{
         history_cache::{CacheChange, HistoryCache},
         reader::WriterProxy,
-        types::{Guid, Locator, ProtocolVersion, SequenceNumber, VendorId},
+        types::{
+            Guid, Locator, ProtocolVersion, SequenceNumber, VendorId, USER_DEFINED_WRITER_NO_KEY,
+            USER_DEFINED_WRITER_WITH_KEY,
+        },
         writer::{ReaderProxy, TransportStatefulWriter, TransportStatelessWriter},
     },
 };
 
 use super::{
     error::RtpsResult,
+    flow_controller::FlowController,
     message_sender::MessageSender,
     messages::overall_structure::RtpsMessageRead,
     stateful_reader::RtpsStatefulReader,
     stateless_reader::RtpsStatelessReader,
     stateless_writer::RtpsStatelessWriter,
+    tcp_transport::TcpTransport,
     types::{PROTOCOLVERSION_2_4, VENDOR_ID_S2E},
 };
+use std::sync::Arc;
 
 pub struct RtpsParticipant {
     guid: Guid,
@@ -35,6 +52,32 @@ pub struct RtpsParticipant {
     stateless_reader_list: Vec<RtpsStatelessReader>,
     stateful_reader_list: Vec<RtpsStatefulReader>,
     message_sender: MessageSender,
+    retransmission_backoff: RetransmissionBackoff,
+    samples_per_piggyback_heartbeat: usize,
+    heartbeat_response_jitter: std::time::Duration,
+    user_data_multicast_threshold: Option<usize>,
+    writer_batching: Option<WriterBatching>,
+    sedp_batching: Option<WriterBatching>,
+    flow_control: Option<FlowControl>,
+    // Shared by all the user-defined writers when the flow control applies to the whole participant
+    participant_flow_controller: Option<FlowController>,
+    transport_priority_dscp_mapping: Vec<TransportPriorityDscp>,
+    disable_multicast: bool,
+    deserialization_limits: DeserializationLimits,
+    discarded_submessage_count: u64,
+    skipped_submessage_count: u64,
+}
+
+// Socket sending the UDPv4 datagrams with the DSCP in the upper six bits of the type of service
+fn dscp_socket(dscp: u8) -> std::io::Result<std::net::UdpSocket> {
+    let socket = socket2::Socket::new(
+        socket2::Domain::IPV4,
+        socket2::Type::DGRAM,
+        Some(socket2::Protocol::UDP),
+    )?;
+    socket.set_tos((dscp as u32) << 2)?;
+    socket.bind(&std::net::SocketAddr::from(([0, 0, 0, 0], 0)).into())?;
+    Ok(socket.into())
 }
 
 impl RtpsParticipant {
@@ -45,10 +88,45 @@ impl RtpsParticipant {
         default_multicast_locator_list: Vec<Locator>,
         metatraffic_unicast_locator_list: Vec<Locator>,
         metatraffic_multicast_locator_list: Vec<Locator>,
+        max_message_size: usize,
+        path_mtu_discovery: bool,
+        destination_mtu_list: Vec<DestinationMtu>,
+        retransmission_backoff: RetransmissionBackoff,
+        samples_per_piggyback_heartbeat: usize,
+        heartbeat_response_jitter: std::time::Duration,
+        user_data_multicast_threshold: Option<usize>,
+        writer_batching: Option<WriterBatching>,
+        sedp_batching: Option<WriterBatching>,
+        flow_control: Option<FlowControl>,
+        transport_priority_dscp_mapping: Vec<TransportPriorityDscp>,
+        disable_multicast: bool,
+        deserialization_limits: DeserializationLimits,
+        ip_version: IpVersion,
+        tcp_transport: Option<Arc<TcpTransport>>,
     ) -> RtpsResult<Self> {
         let guid_prefix = guid.prefix();
-        let message_sender =
-            MessageSender::new(guid_prefix, std::net::UdpSocket::bind("0.0.0.0:0000")?);
+        let mut message_sender = MessageSender::new(
+            guid_prefix,
+            std::net::UdpSocket::bind("0.0.0.0:0000")?,
+            max_message_size,
+        );
+        message_sender.set_path_mtu(path_mtu_discovery, destination_mtu_list);
+        if ip_version.is_v6_enabled() {
+            message_sender.set_socket_v6(std::net::UdpSocket::bind("[::]:0")?);
+        }
+        if let Some(tcp_transport) = tcp_transport {
+            message_sender.set_tcp_transport(tcp_transport);
+        }
+        for dscp in transport_priority_dscp_mapping.iter().map(|m| m.dscp) {
+            message_sender.add_dscp_socket(dscp, dscp_socket(dscp)?);
+        }
+        let participant_flow_controller = match flow_control {
+            Some(FlowControl {
+                max_bytes_per_second,
+                scope: FlowControlScope::Participant,
+            }) => Some(FlowController::new(max_bytes_per_second)),
+            _ => None,
+        };
 
         Ok(Self {
             guid,
@@ -64,6 +142,19 @@ impl RtpsParticipant {
             stateful_reader_list: vec![],
 
             message_sender,
+            retransmission_backoff,
+            samples_per_piggyback_heartbeat,
+            heartbeat_response_jitter,
+            user_data_multicast_threshold,
+            writer_batching,
+            sedp_batching,
+            flow_control,
+            participant_flow_controller,
+            transport_priority_dscp_mapping,
+            disable_multicast,
+            deserialization_limits,
+            discarded_submessage_count: 0,
+            skipped_submessage_count: 0,
         })
     }
 
@@ -117,8 +208,66 @@ impl RtpsParticipant {
     }
 
     pub fn create_stateful_writer(&mut self, writer_guid: Guid, data_max_size_serialized: usize) {
-        let writer = RtpsStatefulWriter::new(writer_guid, data_max_size_serialized);
+        // The payload is fragmented so that each DATA or DATA_FRAG message fits in the maximum message size
+        let data_max_size_serialized =
+            data_max_size_serialized.min(self.message_sender.max_data_size_serialized(&[]));
+        // Among the built-in writers only the SEDP announcements are batched, and separately from the user data, so
+        // that the participant announcements and the liveliness are never delayed
+        let (writer_batching, flow_controller) = match writer_guid.entity_id().entity_kind() {
+            _ if [
+                ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+                ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+                ENTITYID_SEDP_BUILTIN_TOPICS_ANNOUNCER,
+            ]
+            .contains(&writer_guid.entity_id()) =>
+            {
+                (self.sedp_batching, None)
+            }
+            USER_DEFINED_WRITER_WITH_KEY | USER_DEFINED_WRITER_NO_KEY => {
+                let flow_controller = match self.flow_control {
+                    Some(FlowControl {
+                        max_bytes_per_second,
+                        scope: FlowControlScope::Writer,
+                    }) => Some(FlowController::new(max_bytes_per_second)),
+                    _ => self.participant_flow_controller.clone(),
+                };
+                (self.writer_batching, flow_controller)
+            }
+            _ => (None, None),
+        };
+        let writer = RtpsStatefulWriter::new(
+            writer_guid,
+            data_max_size_serialized,
+            self.retransmission_backoff,
+            self.samples_per_piggyback_heartbeat,
+            self.user_data_multicast_threshold,
+            writer_batching,
+            flow_controller,
+        );
         self.stateful_writer_list.push(writer);
+        self.stateful_writer_list
+            .sort_by_key(|w| core::cmp::Reverse(w.transport_priority()));
+    }
+
+    // The writers are kept ordered by decreasing transport priority so that the ones with the
+    // highest priority are the first served by the flow control
+    pub fn set_writer_transport_priority(&mut self, writer_guid: Guid, transport_priority: i32) {
+        let dscp = self
+            .transport_priority_dscp_mapping
+            .iter()
+            .filter(|m| m.min_transport_priority <= transport_priority)
+            .max_by_key(|m| m.min_transport_priority)
+            .map(|m| m.dscp);
+        if let Some(w) = self
+            .stateful_writer_list
+            .iter_mut()
+            .find(|w| w.guid() == writer_guid)
+        {
+            w.set_transport_priority(transport_priority);
+            w.set_dscp(dscp);
+        }
+        self.stateful_writer_list
+            .sort_by_key(|w| core::cmp::Reverse(w.transport_priority()));
     }
 
     pub fn delete_writer(&mut self, writer_guid: Guid) {
@@ -131,7 +280,15 @@ impl RtpsParticipant {
         reader_guid: Guid,
         reader_history_cache: Box<dyn HistoryCache>,
     ) {
-        let reader = RtpsStatefulReader::new(reader_guid, reader_history_cache);
+        let reader = RtpsStatefulReader::new(
+            reader_guid,
+            reader_history_cache,
+            self.heartbeat_response_jitter,
+            self.deserialization_limits.max_fragments_per_sample,
+            self.deserialization_limits.max_pending_fragmented_samples,
+            self.deserialization_limits
+                .pending_fragmented_sample_timeout,
+        );
 
         self.stateful_reader_list.push(reader);
     }
@@ -151,7 +308,13 @@ impl RtpsParticipant {
             .retain(|x| x.guid() != reader_guid);
     }
 
+    pub fn set_submessage_crypto(&mut self, submessage_crypto: Arc<dyn SubmessageCrypto>) {
+        self.message_sender.set_submessage_crypto(submessage_crypto);
+    }
+
     pub fn process_builtin_rtps_message(&mut self, message: RtpsMessageRead) {
+        let message = self.decode_secure_submessages(message);
+        self.count_ignored_submessages(&message);
         MessageReceiver::new(message).process_message(
             &mut self.stateless_reader_list,
             &mut self.stateful_reader_list,
@@ -161,6 +324,8 @@ impl RtpsParticipant {
     }
 
     pub fn process_user_defined_rtps_message(&mut self, message: RtpsMessageRead) {
+        let message = self.decode_secure_submessages(message);
+        self.count_ignored_submessages(&message);
         MessageReceiver::new(message).process_message(
             &mut self.stateless_reader_list,
             &mut self.stateful_reader_list,
@@ -168,6 +333,58 @@ impl RtpsParticipant {
             &self.message_sender,
         );
     }
+
+    pub fn discarded_submessage_count(&self) -> u64 {
+        self.discarded_submessage_count
+    }
+
+    pub fn skipped_submessage_count(&self) -> u64 {
+        self.skipped_submessage_count
+    }
+
+    // The remote endpoints matched with the protected local endpoints must send their
+    // submessages protected as well
+    fn decode_secure_submessages(&self, message: RtpsMessageRead) -> RtpsMessageRead {
+        let Some(crypto) = self.message_sender.submessage_crypto() else {
+            return message;
+        };
+        message.decode_secure_submessages(
+            crypto,
+            self.deserialization_limits.max_parameter_list_length,
+            |remote_endpoint_guid| {
+                self.stateful_reader_list.iter().any(|r| {
+                    crypto.is_protected_endpoint(r.guid().entity_id())
+                        && r.is_writer_matched(remote_endpoint_guid)
+                }) || self.stateful_writer_list.iter().any(|w| {
+                    crypto.is_protected_endpoint(w.guid().entity_id())
+                        && w.is_reader_matched(remote_endpoint_guid)
+                })
+            },
+        )
+    }
+
+    fn count_ignored_submessages(&mut self, message: &RtpsMessageRead) {
+        let discarded_submessage_count = message.discarded_submessage_count() as u64;
+        if discarded_submessage_count > 0 {
+            self.discarded_submessage_count += discarded_submessage_count;
+            tracing::warn!(
+                source_guid_prefix = ?message.header().guid_prefix(),
+                total_discarded_submessage_count = self.discarded_submessage_count,
+                "Discarded {} invalid submessages",
+                discarded_submessage_count
+            );
+        }
+        let skipped_submessage_count = message.skipped_submessage_count() as u64;
+        if skipped_submessage_count > 0 {
+            self.skipped_submessage_count += skipped_submessage_count;
+            tracing::debug!(
+                source_guid_prefix = ?message.header().guid_prefix(),
+                total_skipped_submessage_count = self.skipped_submessage_count,
+                "Skipped {} unknown submessages",
+                skipped_submessage_count
+            );
+        }
+    }
 }
 
 pub struct ProcessBuiltinRtpsMessage {
@@ -200,6 +417,18 @@ impl MailHandler<ProcessUserDefinedRtpsMessage> for RtpsParticipant {
     }
 }
 
+pub struct SetSubmessageCrypto {
+    pub submessage_crypto: Arc<dyn SubmessageCrypto>,
+}
+impl Mail for SetSubmessageCrypto {
+    type Result = ();
+}
+impl MailHandler<SetSubmessageCrypto> for RtpsParticipant {
+    fn handle(&mut self, message: SetSubmessageCrypto) -> <SetSubmessageCrypto as Mail>::Result {
+        self.set_submessage_crypto(message.submessage_crypto);
+    }
+}
+
 pub struct SendHeartbeat;
 impl Mail for SendHeartbeat {
     type Result = ();
@@ -209,6 +438,9 @@ impl MailHandler<SendHeartbeat> for RtpsParticipant {
         for writer in self.stateful_writer_list.iter_mut() {
             writer.send_message(&self.message_sender);
         }
+        for reader in self.stateful_reader_list.iter_mut() {
+            reader.send_message(&self.message_sender);
+        }
     }
 }
 
@@ -250,6 +482,20 @@ impl MailHandler<CreateStatefulWriter> for RtpsParticipant {
                 )
             }
 
+            fn on_change_acknowledged(
+                &mut self,
+                sequence_number: SequenceNumber,
+                callback: Box<dyn FnOnce() + Send>,
+            ) {
+                self.rtps_participant_address
+                    .send_actor_mail(AddAcknowledgmentCallback {
+                        writer: self.guid,
+                        sequence_number,
+                        callback,
+                    })
+                    .ok();
+            }
+
             fn add_matched_reader(&mut self, reader_proxy: ReaderProxy) {
                 block_on(
                     self.rtps_participant_address
@@ -273,6 +519,33 @@ impl MailHandler<CreateStatefulWriter> for RtpsParticipant {
                         .receive_reply(),
                 )
             }
+
+            fn assert_liveliness(&mut self) {
+                self.rtps_participant_address
+                    .send_actor_mail(AssertStatefulWriterLiveliness { writer: self.guid })
+                    .ok();
+            }
+
+            fn send_heartbeat(&mut self) {
+                self.rtps_participant_address
+                    .send_actor_mail(SendStatefulWriterHeartbeat { writer: self.guid })
+                    .ok();
+            }
+
+            fn flush(&mut self) {
+                self.rtps_participant_address
+                    .send_actor_mail(FlushStatefulWriter { writer: self.guid })
+                    .ok();
+            }
+
+            fn set_transport_priority(&mut self, transport_priority: i32) {
+                self.rtps_participant_address
+                    .send_actor_mail(SetStatefulWriterTransportPriority {
+                        writer: self.guid,
+                        transport_priority,
+                    })
+                    .ok();
+            }
         }
         impl HistoryCache for RtpsUserDefinedWriterHistoryCache {
             fn add_change(&mut self, cache_change: CacheChange) {
@@ -284,6 +557,15 @@ impl MailHandler<CreateStatefulWriter> for RtpsParticipant {
                     .ok();
             }
 
+            fn add_change_list(&mut self, cache_change_list: Vec<CacheChange>) {
+                self.rtps_participant_address
+                    .send_actor_mail(AddStatefulWriterCacheChangeList {
+                        writer: self.guid,
+                        cache_change_list,
+                    })
+                    .ok();
+            }
+
             fn remove_change(&mut self, sequence_number: SequenceNumber) {
                 self.rtps_participant_address
                     .send_actor_mail(RemoveStatefulWriterCacheChange {
@@ -420,6 +702,8 @@ impl MailHandler<AddReaderLocator> for RtpsParticipant {
             .find(|x| x.guid() == message.writer)
         {
             w.reader_locator_add(message.locator);
+            // The changes already in the writer history are sent right away to the new locator
+            w.send_message(&self.message_sender);
         }
     }
 }
@@ -470,6 +754,11 @@ impl MailHandler<AddMatchedReader> for RtpsParticipant {
                     .clone_from(&self.default_multicast_locator_list);
             }
 
+            // The multicast locators advertised by the remote participant are not reachable
+            if self.disable_multicast {
+                reader_proxy.multicast_locator_list.clear();
+            }
+
             w.add_matched_reader(&reader_proxy);
         }
     }
@@ -521,6 +810,10 @@ impl MailHandler<AddMatchedWriter> for RtpsParticipant {
                     .clone_from(&self.default_multicast_locator_list);
             }
 
+            if self.disable_multicast {
+                writer_proxy.multicast_locator_list.clear();
+            }
+
             r.add_matched_writer(&writer_proxy);
         }
     }
@@ -613,6 +906,28 @@ impl MailHandler<AddStatefulWriterCacheChange> for RtpsParticipant {
     }
 }
 
+pub struct AddStatefulWriterCacheChangeList {
+    pub writer: Guid,
+    pub cache_change_list: Vec<CacheChange>,
+}
+impl Mail for AddStatefulWriterCacheChangeList {
+    type Result = ();
+}
+impl MailHandler<AddStatefulWriterCacheChangeList> for RtpsParticipant {
+    fn handle(
+        &mut self,
+        message: AddStatefulWriterCacheChangeList,
+    ) -> <AddStatefulWriterCacheChangeList as Mail>::Result {
+        if let Some(w) = self
+            .stateful_writer_list
+            .iter_mut()
+            .find(|dw| dw.guid() == message.writer)
+        {
+            w.add_change_list(message.cache_change_list, &self.message_sender);
+        }
+    }
+}
+
 pub struct RemoveStatefulWriterCacheChange {
     pub writer: Guid,
     pub sequence_number: SequenceNumber,
@@ -635,6 +950,82 @@ impl MailHandler<RemoveStatefulWriterCacheChange> for RtpsParticipant {
     }
 }
 
+pub struct AssertStatefulWriterLiveliness {
+    pub writer: Guid,
+}
+impl Mail for AssertStatefulWriterLiveliness {
+    type Result = ();
+}
+impl MailHandler<AssertStatefulWriterLiveliness> for RtpsParticipant {
+    fn handle(
+        &mut self,
+        message: AssertStatefulWriterLiveliness,
+    ) -> <AssertStatefulWriterLiveliness as Mail>::Result {
+        if let Some(w) = self
+            .stateful_writer_list
+            .iter_mut()
+            .find(|dw| dw.guid() == message.writer)
+        {
+            w.send_liveliness_heartbeat(&self.message_sender);
+        }
+    }
+}
+
+pub struct SendStatefulWriterHeartbeat {
+    pub writer: Guid,
+}
+impl Mail for SendStatefulWriterHeartbeat {
+    type Result = ();
+}
+impl MailHandler<SendStatefulWriterHeartbeat> for RtpsParticipant {
+    fn handle(
+        &mut self,
+        message: SendStatefulWriterHeartbeat,
+    ) -> <SendStatefulWriterHeartbeat as Mail>::Result {
+        if let Some(w) = self
+            .stateful_writer_list
+            .iter_mut()
+            .find(|dw| dw.guid() == message.writer)
+        {
+            w.send_heartbeat(&self.message_sender);
+        }
+    }
+}
+
+pub struct SetStatefulWriterTransportPriority {
+    pub writer: Guid,
+    pub transport_priority: i32,
+}
+impl Mail for SetStatefulWriterTransportPriority {
+    type Result = ();
+}
+impl MailHandler<SetStatefulWriterTransportPriority> for RtpsParticipant {
+    fn handle(
+        &mut self,
+        message: SetStatefulWriterTransportPriority,
+    ) -> <SetStatefulWriterTransportPriority as Mail>::Result {
+        self.set_writer_transport_priority(message.writer, message.transport_priority);
+    }
+}
+
+pub struct FlushStatefulWriter {
+    pub writer: Guid,
+}
+impl Mail for FlushStatefulWriter {
+    type Result = ();
+}
+impl MailHandler<FlushStatefulWriter> for RtpsParticipant {
+    fn handle(&mut self, message: FlushStatefulWriter) -> <FlushStatefulWriter as Mail>::Result {
+        if let Some(w) = self
+            .stateful_writer_list
+            .iter_mut()
+            .find(|dw| dw.guid() == message.writer)
+        {
+            w.flush(&self.message_sender);
+        }
+    }
+}
+
 pub struct IsChangeAcknowledged {
     pub guid: Guid,
     pub sequence_number: SequenceNumber,
@@ -649,6 +1040,7 @@ impl MailHandler<IsChangeAcknowledged> for RtpsParticipant {
             .iter_mut()
             .find(|dw| dw.guid() == message.guid)
         {
+            w.flush_change(message.sequence_number, &self.message_sender);
             w.is_change_acknowledged(message.sequence_number)
         } else {
             false
@@ -656,6 +1048,30 @@ impl MailHandler<IsChangeAcknowledged> for RtpsParticipant {
     }
 }
 
+pub struct AddAcknowledgmentCallback {
+    pub writer: Guid,
+    pub sequence_number: SequenceNumber,
+    pub callback: Box<dyn FnOnce() + Send>,
+}
+impl Mail for AddAcknowledgmentCallback {
+    type Result = ();
+}
+impl MailHandler<AddAcknowledgmentCallback> for RtpsParticipant {
+    fn handle(
+        &mut self,
+        message: AddAcknowledgmentCallback,
+    ) -> <AddAcknowledgmentCallback as Mail>::Result {
+        if let Some(w) = self
+            .stateful_writer_list
+            .iter_mut()
+            .find(|dw| dw.guid() == message.writer)
+        {
+            w.flush_change(message.sequence_number, &self.message_sender);
+            w.add_acknowledgment_callback(message.sequence_number, message.callback);
+        }
+    }
+}
+
 pub struct IsHistoricalDataReceived {
     pub guid: Guid,
 }
@@ -1,11 +1,19 @@
-use crate::transport::{
-    history_cache::CacheChange,
-    types::{EntityId, Guid, Locator, ReliabilityKind, SequenceNumber},
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::{
+    configuration::RetransmissionBackoff,
+    transport::{
+        types::{EntityId, Guid, Locator, ReliabilityKind, SequenceNumber},
+        writer::ChangeFilter,
+    },
 };
 
-use super::messages::{
-    submessages::{heartbeat::HeartbeatSubmessage, heartbeat_frag::HeartbeatFragSubmessage},
-    types::{Count, FragmentNumber},
+use super::{
+    messages::{
+        submessages::{heartbeat::HeartbeatSubmessage, heartbeat_frag::HeartbeatFragSubmessage},
+        types::{Count, FragmentNumber},
+    },
+    writer_history_cache::WriterHistoryCache,
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -13,6 +21,7 @@ pub struct HeartbeatMachine {
     count: Count,
     reader_id: EntityId,
     timer: std::time::Instant,
+    samples_since_heartbeat: usize,
 }
 impl HeartbeatMachine {
     fn new(reader_id: EntityId) -> Self {
@@ -20,11 +29,18 @@ impl HeartbeatMachine {
             count: 0,
             reader_id,
             timer: std::time::Instant::now(),
+            samples_since_heartbeat: 0,
         }
     }
     pub fn is_time_for_heartbeat(&self, heartbeat_period: std::time::Duration) -> bool {
         self.timer.elapsed() >= heartbeat_period
     }
+    // Counts a sample sent to the reader and returns whether a heartbeat should be
+    // appended to it. The count is reset when the heartbeat is generated.
+    pub fn is_time_for_piggyback_heartbeat(&mut self, samples_per_heartbeat: usize) -> bool {
+        self.samples_since_heartbeat += 1;
+        self.samples_since_heartbeat >= samples_per_heartbeat
+    }
     pub fn generate_new_heartbeat(
         &mut self,
         writer_id: EntityId,
@@ -33,6 +49,7 @@ impl HeartbeatMachine {
     ) -> HeartbeatSubmessage {
         self.count = self.count.wrapping_add(1);
         self.timer = std::time::Instant::now();
+        self.samples_since_heartbeat = 0;
         HeartbeatSubmessage::new(
             false,
             false,
@@ -43,6 +60,26 @@ impl HeartbeatMachine {
             self.count,
         )
     }
+    pub fn generate_new_liveliness_heartbeat(
+        &mut self,
+        writer_id: EntityId,
+        first_sn: SequenceNumber,
+        last_sn: SequenceNumber,
+    ) -> HeartbeatSubmessage {
+        // A liveliness heartbeat only asserts the writer liveliness so the
+        // final flag is set to indicate that no response is required
+        self.count = self.count.wrapping_add(1);
+        self.timer = std::time::Instant::now();
+        HeartbeatSubmessage::new(
+            true,
+            true,
+            self.reader_id,
+            writer_id,
+            first_sn,
+            last_sn,
+            self.count,
+        )
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -58,14 +95,14 @@ impl HeartbeatFragMachine {
             reader_id,
         }
     }
-    pub fn _submessage(
+    pub fn submessage(
         &mut self,
         writer_id: EntityId,
         writer_sn: SequenceNumber,
         last_fragment_num: FragmentNumber,
     ) -> HeartbeatFragSubmessage {
         self.count = self.count.wrapping_add(1);
-        HeartbeatFragSubmessage::_new(
+        HeartbeatFragSubmessage::new(
             self.reader_id,
             writer_id,
             writer_sn,
@@ -75,6 +112,77 @@ impl HeartbeatFragMachine {
     }
 }
 
+// Smallest number of fragments sent to a reader in each pacing interval once losses are
+// detected. The window grows by the same amount after each interval without losses.
+const MIN_FRAGMENT_WINDOW: usize = 4;
+const FRAGMENT_PACING_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FragmentPacing {
+    window: Option<usize>,
+    interval_start: std::time::Instant,
+    fragments_sent: usize,
+    peak_fragments_sent: usize,
+    loss_in_interval: bool,
+    pending_fragments: Option<(SequenceNumber, usize)>,
+}
+impl FragmentPacing {
+    fn new() -> Self {
+        FragmentPacing {
+            window: None,
+            interval_start: std::time::Instant::now(),
+            fragments_sent: 0,
+            peak_fragments_sent: 0,
+            loss_in_interval: false,
+            pending_fragments: None,
+        }
+    }
+    fn update_interval(&mut self) {
+        if self.interval_start.elapsed() >= FRAGMENT_PACING_INTERVAL {
+            if let Some(window) = &mut self.window {
+                // The window only grows when it limited the transmission without losses
+                if !self.loss_in_interval && self.fragments_sent >= *window {
+                    *window += MIN_FRAGMENT_WINDOW;
+                }
+            }
+            self.interval_start = std::time::Instant::now();
+            self.fragments_sent = 0;
+            self.loss_in_interval = false;
+        }
+    }
+    pub fn try_send_fragment(&mut self) -> bool {
+        self.update_interval();
+        if self
+            .window
+            .is_some_and(|window| self.fragments_sent >= window)
+        {
+            false
+        } else {
+            self.fragments_sent += 1;
+            self.peak_fragments_sent = self.peak_fragments_sent.max(self.fragments_sent);
+            true
+        }
+    }
+    pub fn on_fragments_lost(&mut self) {
+        self.update_interval();
+        // All the fragments lost in the same interval are due to the same congestion
+        if !self.loss_in_interval {
+            self.loss_in_interval = true;
+            let window = self.window.unwrap_or(self.peak_fragments_sent);
+            self.window = Some((window / 2).max(MIN_FRAGMENT_WINDOW));
+        }
+    }
+    pub fn set_pending_fragments(&mut self, seq_num: SequenceNumber, next_fragment: usize) {
+        self.pending_fragments = Some((seq_num, next_fragment));
+    }
+    pub fn take_pending_fragments(&mut self) -> Option<(SequenceNumber, usize)> {
+        self.pending_fragments.take()
+    }
+    pub fn has_pending_fragments(&self) -> bool {
+        self.pending_fragments.is_some()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct RtpsReaderProxy {
     remote_reader_guid: Guid,
@@ -84,14 +192,26 @@ pub struct RtpsReaderProxy {
     highest_sent_seq_num: SequenceNumber,
     highest_acked_seq_num: SequenceNumber,
     requested_changes: Vec<SequenceNumber>,
+    // Fragments requested with NACK_FRAG for the changes which are only partially missing
+    requested_fragments: HashMap<SequenceNumber, BTreeSet<FragmentNumber>>,
     expects_inline_qos: bool,
     is_active: bool,
     last_received_acknack_count: Count,
     last_received_nack_frag_count: Count,
     heartbeat_machine: HeartbeatMachine,
     heartbeat_frag_machine: HeartbeatFragMachine,
+    fragment_pacing: FragmentPacing,
     reliability: ReliabilityKind,
     first_relevant_sample_seq_num: SequenceNumber,
+    irrelevant_changes: HashSet<SequenceNumber>,
+    change_filter: Option<ChangeFilter>,
+    highest_filtered_seq_num: SequenceNumber,
+    disable_positive_acks_keep_duration: Option<std::time::Duration>,
+    prefer_multicast: bool,
+    push_mode: bool,
+    sent_change_list: VecDeque<(SequenceNumber, std::time::Instant)>,
+    retransmission_backoff: RetransmissionBackoff,
+    retransmitted_change_list: HashMap<SequenceNumber, (u32, std::time::Instant)>,
 }
 
 impl RtpsReaderProxy {
@@ -105,6 +225,10 @@ impl RtpsReaderProxy {
         is_active: bool,
         reliability: ReliabilityKind,
         first_relevant_sample_seq_num: SequenceNumber,
+        disable_positive_acks_keep_duration: Option<std::time::Duration>,
+        prefer_multicast: bool,
+        push_mode: bool,
+        retransmission_backoff: RetransmissionBackoff,
     ) -> Self {
         let heartbeat_machine = HeartbeatMachine::new(remote_reader_guid.entity_id());
         let heartbeat_frag_machine = HeartbeatFragMachine::new(remote_reader_guid.entity_id());
@@ -116,14 +240,25 @@ impl RtpsReaderProxy {
             highest_sent_seq_num: 0,
             highest_acked_seq_num: 0,
             requested_changes: Vec::new(),
+            requested_fragments: HashMap::new(),
             expects_inline_qos,
             is_active,
             last_received_acknack_count: 0,
             last_received_nack_frag_count: 0,
             heartbeat_machine,
             heartbeat_frag_machine,
+            fragment_pacing: FragmentPacing::new(),
             reliability,
             first_relevant_sample_seq_num,
+            irrelevant_changes: HashSet::new(),
+            change_filter: None,
+            highest_filtered_seq_num: 0,
+            disable_positive_acks_keep_duration,
+            prefer_multicast,
+            push_mode,
+            sent_change_list: VecDeque::new(),
+            retransmission_backoff,
+            retransmitted_change_list: HashMap::new(),
         }
     }
 
@@ -135,24 +270,48 @@ impl RtpsReaderProxy {
         self.unicast_locator_list.as_slice()
     }
 
+    pub fn multicast_locator_list(&self) -> &[Locator] {
+        self.multicast_locator_list.as_slice()
+    }
+
+    pub fn destination_locator_list(&self) -> &[Locator] {
+        if self.prefer_multicast && !self.multicast_locator_list.is_empty() {
+            self.multicast_locator_list.as_slice()
+        } else {
+            self.unicast_locator_list.as_slice()
+        }
+    }
+
     pub fn reliability(&self) -> ReliabilityKind {
         self.reliability
     }
 
+    pub fn push_mode(&self) -> bool {
+        self.push_mode
+    }
+
     pub fn heartbeat_machine(&mut self) -> &mut HeartbeatMachine {
         &mut self.heartbeat_machine
     }
 
-    pub fn _heartbeat_frag_machine(&mut self) -> &mut HeartbeatFragMachine {
+    pub fn heartbeat_frag_machine(&mut self) -> &mut HeartbeatFragMachine {
         &mut self.heartbeat_frag_machine
     }
 
+    pub fn fragment_pacing(&mut self) -> &mut FragmentPacing {
+        &mut self.fragment_pacing
+    }
+
     // //////////////   ReaderProxy operations defined in the Rtps Standard
 
     pub fn acked_changes_set(&mut self, committed_seq_num: SequenceNumber) {
         if committed_seq_num > self.highest_acked_seq_num {
             self.highest_acked_seq_num = committed_seq_num
         }
+        self.retransmitted_change_list
+            .retain(|&seq_num, _| seq_num > committed_seq_num);
+        self.requested_fragments
+            .retain(|&seq_num, _| seq_num > committed_seq_num);
     }
 
     pub fn next_requested_change(&mut self) -> Option<SequenceNumber> {
@@ -160,14 +319,20 @@ impl RtpsReaderProxy {
 
         if let Some(next_sn) = &next_requested_change {
             self.requested_changes.retain(|sn| sn != next_sn);
+            let (retransmission_count, retransmission_time) = self
+                .retransmitted_change_list
+                .entry(*next_sn)
+                .or_insert((0, std::time::Instant::now()));
+            *retransmission_count += 1;
+            *retransmission_time = std::time::Instant::now();
         }
 
         next_requested_change
     }
 
-    pub fn next_unsent_change<'a>(
-        &'a self,
-        writer_history_cache: impl Iterator<Item = &'a CacheChange>,
+    pub fn next_unsent_change(
+        &self,
+        writer_history_cache: &WriterHistoryCache,
     ) -> Option<SequenceNumber> {
         //         unsent_changes :=
         // { changes SUCH_THAT change.sequenceNumber > this.highestSentChangeSN }
@@ -175,15 +340,12 @@ impl RtpsReaderProxy {
         // IF unsent_changes == <empty> return SEQUENCE_NUMBER_INVALID
         // ELSE return MIN { unsent_changes.sequenceNumber }
         writer_history_cache
+            .changes_after(self.highest_sent_seq_num)
+            .next()
             .map(|cc| cc.sequence_number())
-            .filter(|cc_sn| cc_sn > &self.highest_sent_seq_num)
-            .min()
     }
 
-    pub fn unsent_changes<'a>(
-        &'a self,
-        writer_history_cache: impl Iterator<Item = &'a CacheChange>,
-    ) -> bool {
+    pub fn unsent_changes(&self, writer_history_cache: &WriterHistoryCache) -> bool {
         // return this.next_unsent_change() != SEQUENCE_NUMBER_INVALID;
         self.next_unsent_change(writer_history_cache).is_some()
     }
@@ -199,12 +361,47 @@ impl RtpsReaderProxy {
         //     change_for_reader.status := REQUESTED;
         // END"
         for seq_num in req_seq_num_set {
+            // Requests for a change retransmitted too recently according to the backoff are ignored.
+            // The reader keeps requesting the changes it misses so they are sent once the delay elapses.
+            if let Some((retransmission_count, retransmission_time)) =
+                self.retransmitted_change_list.get(&seq_num)
+            {
+                if retransmission_time.elapsed()
+                    < self.retransmission_backoff.delay(*retransmission_count)
+                {
+                    continue;
+                }
+            }
             if !self.requested_changes.contains(&seq_num) {
                 self.requested_changes.push(seq_num);
             }
         }
     }
 
+    // Only the requested fragments of the change are sent when it is the next requested change,
+    // unless the whole change is requested again in the meantime
+    pub fn requested_fragments_set(
+        &mut self,
+        seq_num: SequenceNumber,
+        fragment_number_set: impl Iterator<Item = FragmentNumber>,
+    ) {
+        self.requested_fragments
+            .entry(seq_num)
+            .or_default()
+            .extend(fragment_number_set);
+    }
+
+    pub fn requested_fragments_clear(&mut self, seq_num: SequenceNumber) {
+        self.requested_fragments.remove(&seq_num);
+    }
+
+    pub fn take_requested_fragments(
+        &mut self,
+        seq_num: SequenceNumber,
+    ) -> Option<BTreeSet<FragmentNumber>> {
+        self.requested_fragments.remove(&seq_num)
+    }
+
     pub fn unacked_changes(&self, highest_available_seq_num: Option<SequenceNumber>) -> bool {
         // highest_available_seq_num := MAX { change.sequenceNumber }
         // highest_acked_seq_num := MAX { this.acknowledged_changes }
@@ -225,6 +422,24 @@ impl RtpsReaderProxy {
     pub fn set_highest_sent_seq_num(&mut self, seq_num: SequenceNumber) {
         if seq_num > self.highest_sent_seq_num {
             self.highest_sent_seq_num = seq_num;
+            if self.disable_positive_acks_keep_duration.is_some() {
+                self.sent_change_list
+                    .push_back((seq_num, std::time::Instant::now()));
+            }
+        }
+    }
+
+    pub fn acknowledge_kept_changes(&mut self) {
+        // A reader without positive acknowledgments never acknowledges the received changes
+        // so they are considered acknowledged once they have been kept for the keep duration
+        if let Some(keep_duration) = self.disable_positive_acks_keep_duration {
+            while let Some(&(seq_num, sent_time)) = self.sent_change_list.front() {
+                if sent_time.elapsed() < keep_duration {
+                    break;
+                }
+                self.acked_changes_set(seq_num);
+                self.sent_change_list.pop_front();
+            }
         }
     }
 
@@ -236,6 +451,36 @@ impl RtpsReaderProxy {
         self.first_relevant_sample_seq_num = seq_num;
     }
 
+    pub fn set_irrelevant_changes(&mut self, irrelevant_changes: HashSet<SequenceNumber>) {
+        self.irrelevant_changes = irrelevant_changes;
+    }
+
+    pub fn set_change_filter(&mut self, change_filter: Option<ChangeFilter>) {
+        self.change_filter = change_filter;
+    }
+
+    // Evaluates the filter of the reader on the changes which were not yet evaluated and marks
+    // the ones not passing it as irrelevant. The changes no longer in the history are forgotten.
+    pub fn filter_changes(&mut self, changes: &WriterHistoryCache) {
+        let Some(change_filter) = &self.change_filter else {
+            return;
+        };
+        for change in changes.changes_after(self.highest_filtered_seq_num) {
+            if !change_filter.is_relevant(change) {
+                self.irrelevant_changes.insert(change.sequence_number());
+            }
+            self.highest_filtered_seq_num = change.sequence_number();
+        }
+        if let Some(seq_num_min) = changes.seq_num_min() {
+            self.irrelevant_changes
+                .retain(|&seq_num| seq_num >= seq_num_min);
+        }
+    }
+
+    pub fn is_change_relevant(&self, seq_num: SequenceNumber) -> bool {
+        seq_num > self.first_relevant_sample_seq_num && !self.irrelevant_changes.contains(&seq_num)
+    }
+
     pub fn last_received_acknack_count(&self) -> Count {
         self.last_received_acknack_count
     }
@@ -252,3 +497,183 @@ impl RtpsReaderProxy {
         self.last_received_nack_frag_count = count;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::types::{ENTITYID_UNKNOWN, GUID_UNKNOWN};
+
+    fn reader_proxy(retransmission_backoff: RetransmissionBackoff) -> RtpsReaderProxy {
+        RtpsReaderProxy::new(
+            GUID_UNKNOWN,
+            ENTITYID_UNKNOWN,
+            &[],
+            &[],
+            false,
+            true,
+            ReliabilityKind::Reliable,
+            0,
+            None,
+            false,
+            true,
+            retransmission_backoff,
+        )
+    }
+
+    #[test]
+    fn repeated_requests_within_backoff_are_ignored() {
+        let mut reader_proxy = reader_proxy(RetransmissionBackoff::Fixed(
+            std::time::Duration::from_secs(10),
+        ));
+
+        reader_proxy.requested_changes_set([1, 2].into_iter());
+        assert_eq!(reader_proxy.next_requested_change(), Some(1));
+        assert_eq!(reader_proxy.next_requested_change(), Some(2));
+
+        reader_proxy.requested_changes_set([1, 2, 3].into_iter());
+        assert_eq!(reader_proxy.requested_changes(), vec![3]);
+    }
+
+    #[test]
+    fn acknowledged_changes_are_not_subject_to_backoff() {
+        let mut reader_proxy = reader_proxy(RetransmissionBackoff::Fixed(
+            std::time::Duration::from_secs(10),
+        ));
+
+        reader_proxy.requested_changes_set([1].into_iter());
+        assert_eq!(reader_proxy.next_requested_change(), Some(1));
+        reader_proxy.acked_changes_set(1);
+
+        reader_proxy.requested_changes_set([1].into_iter());
+        assert_eq!(reader_proxy.requested_changes(), vec![1]);
+    }
+
+    #[test]
+    fn default_backoff_retransmits_every_request() {
+        let mut reader_proxy = reader_proxy(RetransmissionBackoff::default());
+
+        reader_proxy.requested_changes_set([1].into_iter());
+        assert_eq!(reader_proxy.next_requested_change(), Some(1));
+        reader_proxy.requested_changes_set([1].into_iter());
+        assert_eq!(reader_proxy.next_requested_change(), Some(1));
+    }
+
+    #[test]
+    fn piggyback_heartbeat_every_n_samples() {
+        let mut heartbeat_machine = HeartbeatMachine::new(ENTITYID_UNKNOWN);
+
+        assert!(!heartbeat_machine.is_time_for_piggyback_heartbeat(3));
+        assert!(!heartbeat_machine.is_time_for_piggyback_heartbeat(3));
+        assert!(heartbeat_machine.is_time_for_piggyback_heartbeat(3));
+        heartbeat_machine.generate_new_heartbeat(ENTITYID_UNKNOWN, 1, 3);
+
+        assert!(!heartbeat_machine.is_time_for_piggyback_heartbeat(3));
+        assert!(heartbeat_machine.is_time_for_piggyback_heartbeat(1));
+    }
+
+    #[test]
+    fn fragments_are_paced_after_loss() {
+        let mut fragment_pacing = FragmentPacing::new();
+
+        for _ in 0..20 {
+            assert!(fragment_pacing.try_send_fragment());
+        }
+        fragment_pacing.on_fragments_lost();
+        assert!(!fragment_pacing.try_send_fragment());
+
+        std::thread::sleep(FRAGMENT_PACING_INTERVAL);
+        for _ in 0..10 {
+            assert!(fragment_pacing.try_send_fragment());
+        }
+        assert!(!fragment_pacing.try_send_fragment());
+    }
+
+    #[test]
+    fn fragment_window_grows_without_loss() {
+        let mut fragment_pacing = FragmentPacing::new();
+        fragment_pacing.on_fragments_lost();
+        for _ in 0..MIN_FRAGMENT_WINDOW {
+            assert!(fragment_pacing.try_send_fragment());
+        }
+        assert!(!fragment_pacing.try_send_fragment());
+
+        // The window does not grow at the end of the interval in which the loss happened
+        std::thread::sleep(FRAGMENT_PACING_INTERVAL);
+        for _ in 0..MIN_FRAGMENT_WINDOW {
+            assert!(fragment_pacing.try_send_fragment());
+        }
+        assert!(!fragment_pacing.try_send_fragment());
+
+        std::thread::sleep(FRAGMENT_PACING_INTERVAL);
+        for _ in 0..2 * MIN_FRAGMENT_WINDOW {
+            assert!(fragment_pacing.try_send_fragment());
+        }
+        assert!(!fragment_pacing.try_send_fragment());
+    }
+
+    #[test]
+    fn destination_locators_prefer_multicast() {
+        let unicast_locator =
+            Locator::new(1, 7400, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 10, 0, 0, 1]);
+        let multicast_locator = Locator::new(
+            1,
+            7401,
+            [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 239, 255, 0, 1],
+        );
+        let new_reader_proxy = |multicast_locator_list: &[Locator], prefer_multicast| {
+            RtpsReaderProxy::new(
+                GUID_UNKNOWN,
+                ENTITYID_UNKNOWN,
+                &[unicast_locator],
+                multicast_locator_list,
+                false,
+                true,
+                ReliabilityKind::BestEffort,
+                0,
+                None,
+                prefer_multicast,
+                true,
+                RetransmissionBackoff::default(),
+            )
+        };
+
+        assert_eq!(
+            new_reader_proxy(&[multicast_locator], false).destination_locator_list(),
+            &[unicast_locator]
+        );
+        assert_eq!(
+            new_reader_proxy(&[multicast_locator], true).destination_locator_list(),
+            &[multicast_locator]
+        );
+        assert_eq!(
+            new_reader_proxy(&[], true).destination_locator_list(),
+            &[unicast_locator]
+        );
+    }
+
+    #[test]
+    fn backoff_delays() {
+        let ms = std::time::Duration::from_millis;
+        let fixed = RetransmissionBackoff::Fixed(ms(5));
+        assert_eq!(fixed.delay(1), ms(5));
+        assert_eq!(fixed.delay(7), ms(5));
+
+        let linear = RetransmissionBackoff::Linear {
+            increment: ms(10),
+            max: ms(25),
+        };
+        assert_eq!(linear.delay(1), ms(10));
+        assert_eq!(linear.delay(2), ms(20));
+        assert_eq!(linear.delay(3), ms(25));
+
+        let exponential = RetransmissionBackoff::Exponential {
+            initial: ms(10),
+            max: ms(100),
+        };
+        assert_eq!(exponential.delay(1), ms(10));
+        assert_eq!(exponential.delay(2), ms(20));
+        assert_eq!(exponential.delay(4), ms(80));
+        assert_eq!(exponential.delay(5), ms(100));
+        assert_eq!(exponential.delay(u32::MAX), ms(100));
+    }
+}
@@ -14,20 +14,47 @@ use crate::transport::{
     reader::WriterProxy,
     types::{Guid, GuidPrefix, ReliabilityKind},
 };
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
 use tracing::error;
 
+// Random delay in [0, max_delay]. The keys of a new RandomState are enough to
+// spread the responses of the readers without an additional dependency.
+fn random_delay(max_delay: Duration) -> Duration {
+    let random = RandomState::new().build_hasher().finish();
+    max_delay.mul_f64(random as f64 / u64::MAX as f64)
+}
+
 pub struct RtpsStatefulReader {
     guid: Guid,
     matched_writers: Vec<RtpsWriterProxy>,
     history_cache: Box<dyn HistoryCache>,
+    heartbeat_response_jitter: Duration,
+    max_fragments_per_sample: usize,
+    max_pending_fragmented_samples: usize,
+    pending_fragmented_sample_timeout: Duration,
 }
 
 impl RtpsStatefulReader {
-    pub fn new(guid: Guid, history_cache: Box<dyn HistoryCache>) -> Self {
+    pub fn new(
+        guid: Guid,
+        history_cache: Box<dyn HistoryCache>,
+        heartbeat_response_jitter: Duration,
+        max_fragments_per_sample: usize,
+        max_pending_fragmented_samples: usize,
+        pending_fragmented_sample_timeout: Duration,
+    ) -> Self {
         Self {
             guid,
             matched_writers: Vec::new(),
             history_cache,
+            heartbeat_response_jitter,
+            max_fragments_per_sample,
+            max_pending_fragmented_samples,
+            pending_fragmented_sample_timeout,
         }
     }
 
@@ -51,6 +78,7 @@ impl RtpsStatefulReader {
             Some(writer_proxy.data_max_size_serialized),
             writer_proxy.remote_group_entity_id,
             writer_proxy.reliability_kind,
+            writer_proxy.disable_positive_acks,
         );
         self.matched_writers.push(rtps_writer_proxy);
     }
@@ -60,6 +88,12 @@ impl RtpsStatefulReader {
             .retain(|x| x.remote_writer_guid() != writer_guid)
     }
 
+    pub fn is_writer_matched(&self, writer_guid: Guid) -> bool {
+        self.matched_writers
+            .iter()
+            .any(|wp| wp.remote_writer_guid() == writer_guid)
+    }
+
     pub fn matched_writer_lookup(&mut self, a_writer_guid: Guid) -> Option<&mut RtpsWriterProxy> {
         self.matched_writers
             .iter_mut()
@@ -79,9 +113,12 @@ impl RtpsStatefulReader {
                 ReliabilityKind::BestEffort => {
                     let expected_seq_num = writer_proxy.available_changes_max() + 1;
                     if sequence_number >= expected_seq_num {
+                        // The changes skipped by a best effort writer are not sent again
+                        let lost_changes_count = writer_proxy.lost_changes_update(sequence_number);
                         writer_proxy.received_change_set(sequence_number);
-                        if sequence_number > expected_seq_num {
-                            writer_proxy.lost_changes_update(sequence_number);
+                        if lost_changes_count > 0 {
+                            self.history_cache
+                                .report_lost_changes(writer_guid, lost_changes_count);
                         }
 
                         if let Ok(change) = CacheChange::try_from_data_submessage(
@@ -123,8 +160,14 @@ impl RtpsStatefulReader {
     ) {
         let writer_guid = Guid::new(source_guid_prefix, data_frag_submessage.writer_id());
         let sequence_number = data_frag_submessage.writer_sn();
+        let max_fragments_per_sample = self.max_fragments_per_sample;
+        let max_pending_fragmented_samples = self.max_pending_fragmented_samples;
         if let Some(writer_proxy) = self.matched_writer_lookup(writer_guid) {
-            writer_proxy.push_data_frag(data_frag_submessage.clone());
+            writer_proxy.push_data_frag(
+                data_frag_submessage.clone(),
+                max_fragments_per_sample,
+                max_pending_fragmented_samples,
+            );
             if let Some(data_submessage) = writer_proxy.reconstruct_data_from_frag(sequence_number)
             {
                 self.on_data_submessage_received(
@@ -147,8 +190,13 @@ impl RtpsStatefulReader {
             .iter_mut()
             .find(|w| w.remote_writer_guid() == writer_guid)
         {
-            for seq_num in gap_submessage.gap_start()..gap_submessage.gap_list().base() {
-                writer_proxy.irrelevant_change_set(seq_num)
+            // Marking the highest sequence number of the range as irrelevant is equivalent to
+            // marking the whole range and avoids iterating over arbitrarily large gaps
+            if gap_submessage.gap_start() < gap_submessage.gap_list().base() {
+                writer_proxy.irrelevant_change_range_set(
+                    gap_submessage.gap_start(),
+                    gap_submessage.gap_list().base() - 1,
+                )
             }
 
             for seq_num in gap_submessage.gap_list().set() {
@@ -172,18 +220,31 @@ impl RtpsStatefulReader {
             if writer_proxy.last_received_heartbeat_count() < heartbeat_submessage.count() {
                 writer_proxy.set_last_received_heartbeat_count(heartbeat_submessage.count());
 
+                writer_proxy.missing_changes_update(heartbeat_submessage.last_sn());
+                // The changes no longer available in the writer can't be repaired
+                let lost_changes_count =
+                    writer_proxy.lost_changes_update(heartbeat_submessage.first_sn());
+                if lost_changes_count > 0 {
+                    self.history_cache
+                        .report_lost_changes(writer_guid, lost_changes_count);
+                }
+
+                // A heartbeat with the liveliness flag only asserts the liveliness of the
+                // writer so it is only answered if the final flag is not set
                 writer_proxy.set_must_send_acknacks(
                     !heartbeat_submessage.final_flag()
                         || (!heartbeat_submessage.liveliness_flag()
-                            && !writer_proxy.missing_changes().count() == 0),
+                            && writer_proxy.missing_changes().next().is_some()),
                 );
-
-                if !heartbeat_submessage.final_flag() {
-                    writer_proxy.set_must_send_acknacks(true);
+                if writer_proxy.must_send_acknacks() && !self.heartbeat_response_jitter.is_zero() {
+                    writer_proxy
+                        .schedule_heartbeat_response(random_delay(self.heartbeat_response_jitter));
                 }
-                writer_proxy.missing_changes_update(heartbeat_submessage.last_sn());
-                writer_proxy.lost_changes_update(heartbeat_submessage.first_sn());
                 writer_proxy.send_message(&self.guid, message_sender);
+
+                if heartbeat_submessage.liveliness_flag() {
+                    self.history_cache.assert_writer_liveliness(writer_guid);
+                }
             }
         }
     }
@@ -192,6 +253,7 @@ impl RtpsStatefulReader {
         &mut self,
         heartbeat_frag_submessage: &HeartbeatFragSubmessage,
         source_guid_prefix: GuidPrefix,
+        message_sender: &MessageSender,
     ) {
         let writer_guid = Guid::new(source_guid_prefix, heartbeat_frag_submessage.writer_id());
         if let Some(writer_proxy) = self
@@ -199,9 +261,23 @@ impl RtpsStatefulReader {
             .iter_mut()
             .find(|w| w.remote_writer_guid() == writer_guid)
         {
-            if writer_proxy.last_received_heartbeat_count() < heartbeat_frag_submessage.count() {
+            if writer_proxy.reliability() == ReliabilityKind::Reliable
+                && writer_proxy.last_received_heartbeat_frag_count()
+                    < heartbeat_frag_submessage.count()
+            {
                 writer_proxy
                     .set_last_received_heartbeat_frag_count(heartbeat_frag_submessage.count());
+
+                writer_proxy.announced_fragments_update(
+                    heartbeat_frag_submessage.writer_sn(),
+                    heartbeat_frag_submessage.last_fragment_num(),
+                );
+                if writer_proxy.must_send_nack_frags() && !self.heartbeat_response_jitter.is_zero()
+                {
+                    writer_proxy
+                        .schedule_heartbeat_response(random_delay(self.heartbeat_response_jitter));
+                }
+                writer_proxy.send_message(&self.guid, message_sender);
             }
         }
     }
@@ -209,6 +285,13 @@ impl RtpsStatefulReader {
 
 // The methods in this impl block are not defined by the standard
 impl RtpsStatefulReader {
+    pub fn send_message(&mut self, message_sender: &MessageSender) {
+        for writer_proxy in self.matched_writers.iter_mut() {
+            writer_proxy.discard_stale_fragmented_samples(self.pending_fragmented_sample_timeout);
+            writer_proxy.send_message(&self.guid, message_sender);
+        }
+    }
+
     pub fn is_historical_data_received(&self) -> bool {
         !self
             .matched_writers
@@ -1,11 +1,19 @@
-use crate::transport::{
-    history_cache::CacheChange,
-    types::{ChangeKind, ReliabilityKind},
-    writer::ReaderProxy,
+use crate::{
+    configuration::{RetransmissionBackoff, WriterBatching},
+    transport::{
+        history_cache::CacheChange,
+        types::ReliabilityKind,
+        writer::{HistoricalDataLimit, ReaderProxy},
+    },
+};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 
 use super::{
     behavior_types::Duration,
+    flow_controller::FlowController,
     message_sender::MessageSender,
     messages::{
         submessage_elements::{ParameterList, SequenceNumberSet, SerializedDataFragment},
@@ -14,30 +22,65 @@ use super::{
             info_destination::InfoDestinationSubmessage, info_timestamp::InfoTimestampSubmessage,
             nack_frag::NackFragSubmessage,
         },
-        types::TIME_INVALID,
+        types::{FragmentNumber, TIME_INVALID},
     },
     reader_proxy::RtpsReaderProxy,
+    writer_history_cache::WriterHistoryCache,
 };
 use crate::transport::types::{
-    DurabilityKind, EntityId, Guid, GuidPrefix, SequenceNumber, ENTITYID_UNKNOWN,
+    DurabilityKind, EntityId, Guid, GuidPrefix, Locator, SequenceNumber, ENTITYID_UNKNOWN,
 };
 
+// Largest size of a message carrying a single DATA or DATA_FRAG submessage excluding the payload:
+// RTPS header (20) + INFO_DST (16) + INFO_TS (12) + DATA header (24) + inline QoS with
+// status info and key hash (32) + payload alignment padding (3)
+pub const MAX_DATA_MESSAGE_OVERHEAD: usize = 107;
+
 pub struct RtpsStatefulWriter {
     guid: Guid,
-    changes: Vec<CacheChange>,
+    changes: WriterHistoryCache,
     matched_readers: Vec<RtpsReaderProxy>,
     heartbeat_period: Duration,
     data_max_size_serialized: usize,
+    retransmission_backoff: RetransmissionBackoff,
+    samples_per_piggyback_heartbeat: usize,
+    user_data_multicast_threshold: Option<usize>,
+    acknowledgment_callback_list: Vec<(SequenceNumber, Box<dyn FnOnce() + Send>)>,
+    writer_batching: Option<WriterBatching>,
+    // Changes held back by the batching. They are not known to the readers until they are released
+    batched_changes: Vec<CacheChange>,
+    batch_start_time: Option<Instant>,
+    flow_controller: Option<FlowController>,
+    transport_priority: i32,
+    dscp: Option<u8>,
 }
 
 impl RtpsStatefulWriter {
-    pub fn new(guid: Guid, data_max_size_serialized: usize) -> Self {
+    pub fn new(
+        guid: Guid,
+        data_max_size_serialized: usize,
+        retransmission_backoff: RetransmissionBackoff,
+        samples_per_piggyback_heartbeat: usize,
+        user_data_multicast_threshold: Option<usize>,
+        writer_batching: Option<WriterBatching>,
+        flow_controller: Option<FlowController>,
+    ) -> Self {
         Self {
             guid,
-            changes: Vec::new(),
+            changes: WriterHistoryCache::new(),
             matched_readers: Vec::new(),
             heartbeat_period: Duration::from_millis(200),
             data_max_size_serialized,
+            retransmission_backoff,
+            samples_per_piggyback_heartbeat,
+            user_data_multicast_threshold,
+            acknowledgment_callback_list: Vec::new(),
+            writer_batching,
+            batched_changes: Vec::new(),
+            batch_start_time: None,
+            flow_controller,
+            transport_priority: 0,
+            dscp: None,
         }
     }
 
@@ -49,22 +92,114 @@ impl RtpsStatefulWriter {
         self.data_max_size_serialized
     }
 
+    pub fn transport_priority(&self) -> i32 {
+        self.transport_priority
+    }
+
+    pub fn set_transport_priority(&mut self, transport_priority: i32) {
+        self.transport_priority = transport_priority;
+    }
+
+    pub fn set_dscp(&mut self, dscp: Option<u8>) {
+        self.dscp = dscp;
+    }
+
     pub fn add_change(&mut self, cache_change: CacheChange, message_sender: &MessageSender) {
-        self.changes.push(cache_change);
+        if let Some(writer_batching) = self.writer_batching {
+            self.batch_start_time.get_or_insert_with(Instant::now);
+            self.batched_changes.push(cache_change);
+            let batch_size: usize = self
+                .batched_changes
+                .iter()
+                .map(|cc| cc.data_value().len())
+                .sum();
+            if batch_size >= writer_batching.max_batch_size {
+                self.flush(message_sender);
+            }
+        } else {
+            self.changes.add_change(cache_change);
+            self.send_message(message_sender);
+        }
+    }
+
+    pub fn add_change_list(
+        &mut self,
+        cache_change_list: Vec<CacheChange>,
+        message_sender: &MessageSender,
+    ) {
+        self.release_batched_changes();
+        for cache_change in cache_change_list {
+            self.changes.add_change(cache_change);
+        }
         self.send_message(message_sender);
     }
 
     pub fn remove_change(&mut self, sequence_number: SequenceNumber) {
-        self.changes
+        self.changes.remove_change(sequence_number);
+        self.batched_changes
             .retain(|cc| cc.sequence_number() != sequence_number);
     }
 
-    pub fn is_change_acknowledged(&self, sequence_number: SequenceNumber) -> bool {
+    // Sends the changes held back by the batching right away
+    pub fn flush(&mut self, message_sender: &MessageSender) {
+        self.release_batched_changes();
+        self.send_message(message_sender);
+    }
+
+    // The batch is sent right away when someone waits for one of its changes to be acknowledged
+    pub fn flush_change(
+        &mut self,
+        sequence_number: SequenceNumber,
+        message_sender: &MessageSender,
+    ) {
+        if self
+            .batched_changes
+            .iter()
+            .any(|cc| cc.sequence_number() <= sequence_number)
+        {
+            self.flush(message_sender);
+        }
+    }
+
+    fn release_batched_changes(&mut self) {
+        for cache_change in self.batched_changes.drain(..) {
+            self.changes.add_change(cache_change);
+        }
+        self.batch_start_time = None;
+    }
+
+    pub fn is_change_acknowledged(&mut self, sequence_number: SequenceNumber) -> bool {
         !self
             .matched_readers
-            .iter()
+            .iter_mut()
             .filter(|rp| rp.reliability() == ReliabilityKind::Reliable)
-            .any(|rp| rp.unacked_changes(Some(sequence_number)))
+            .any(|rp| {
+                rp.acknowledge_kept_changes();
+                rp.unacked_changes(Some(sequence_number))
+            })
+    }
+
+    // The callback is called once the change is acknowledged by all the matched reliable readers.
+    // It is dropped without being called if the writer is deleted before.
+    pub fn add_acknowledgment_callback(
+        &mut self,
+        sequence_number: SequenceNumber,
+        callback: Box<dyn FnOnce() + Send>,
+    ) {
+        self.acknowledgment_callback_list
+            .push((sequence_number, callback));
+        self.call_acknowledgment_callbacks();
+    }
+
+    fn call_acknowledgment_callbacks(&mut self) {
+        for (sequence_number, callback) in core::mem::take(&mut self.acknowledgment_callback_list) {
+            if self.is_change_acknowledged(sequence_number) {
+                callback();
+            } else {
+                self.acknowledgment_callback_list
+                    .push((sequence_number, callback));
+            }
+        }
     }
 
     pub fn add_matched_reader(&mut self, reader_proxy: &ReaderProxy) {
@@ -78,16 +213,17 @@ impl RtpsStatefulWriter {
 
         let first_relevant_sample_seq_num = match reader_proxy.durability_kind {
             DurabilityKind::Volatile => self
-                .changes
+                .batched_changes
                 .iter()
                 .map(|cc| cc.sequence_number)
+                .chain(self.changes.seq_num_max())
                 .max()
                 .unwrap_or(0),
             DurabilityKind::TransientLocal
             | DurabilityKind::Transient
             | DurabilityKind::Persistent => 0,
         };
-        let rtps_reader_proxy = RtpsReaderProxy::new(
+        let mut rtps_reader_proxy = RtpsReaderProxy::new(
             reader_proxy.remote_reader_guid,
             reader_proxy.remote_group_entity_id,
             &reader_proxy.unicast_locator_list,
@@ -96,118 +232,373 @@ impl RtpsStatefulWriter {
             true,
             reader_proxy.reliability_kind,
             first_relevant_sample_seq_num,
+            reader_proxy.disable_positive_acks_keep_duration,
+            reader_proxy.prefer_multicast,
+            // Best-effort readers never request changes so they can only be pushed
+            reader_proxy.push_mode || reader_proxy.reliability_kind == ReliabilityKind::BestEffort,
+            self.retransmission_backoff,
         );
+        if reader_proxy.durability_kind != DurabilityKind::Volatile {
+            rtps_reader_proxy.set_irrelevant_changes(historical_changes_beyond_limit(
+                &self.changes,
+                &reader_proxy.historical_data_limit,
+            ));
+        }
+        rtps_reader_proxy.set_change_filter(reader_proxy.content_filter.clone());
         self.matched_readers.push(rtps_reader_proxy);
     }
 
+    pub fn is_reader_matched(&self, reader_guid: Guid) -> bool {
+        self.matched_readers
+            .iter()
+            .any(|rp| rp.remote_reader_guid() == reader_guid)
+    }
+
     pub fn delete_matched_reader(&mut self, reader_guid: Guid) {
         self.matched_readers
             .retain(|rp| rp.remote_reader_guid() != reader_guid);
+        self.call_acknowledgment_callbacks();
     }
 
     pub fn send_message(&mut self, message_sender: &MessageSender) {
+        match (self.writer_batching, self.batch_start_time) {
+            (Some(writer_batching), Some(batch_start_time))
+                if batch_start_time.elapsed() >= writer_batching.flush_period =>
+            {
+                self.release_batched_changes()
+            }
+            _ => (),
+        }
+
+        let dscp = self.dscp;
+        message_sender.write_message_with_dscp(dscp, |message_sender| {
+            if self.writer_batching.is_some() {
+                message_sender
+                    .write_message_batch(|message_sender| self.send_changes(message_sender));
+            } else {
+                self.send_changes(message_sender);
+            }
+        });
+    }
+
+    fn send_changes(&mut self, message_sender: &MessageSender) {
+        // Changes kept for the readers without positive acknowledgments become acknowledged with time
+        self.call_acknowledgment_callbacks();
         for reader_proxy in &mut self.matched_readers {
+            reader_proxy.filter_changes(&self.changes);
+        }
+
+        if let Some(threshold) = self.user_data_multicast_threshold {
+            self.send_multicast_message(threshold, message_sender);
+        }
+
+        for reader_proxy in &mut self.matched_readers {
+            reader_proxy.acknowledge_kept_changes();
             match reader_proxy.reliability() {
                 ReliabilityKind::BestEffort => send_message_to_reader_proxy_best_effort(
                     reader_proxy,
                     self.guid.entity_id(),
                     &self.changes,
                     self.data_max_size_serialized,
+                    self.flow_controller.as_ref(),
                     message_sender,
                 ),
                 ReliabilityKind::Reliable => send_message_to_reader_proxy_reliable(
                     reader_proxy,
                     self.guid.entity_id(),
                     &self.changes,
-                    self.changes.iter().map(|cc| cc.sequence_number()).min(),
-                    self.changes.iter().map(|cc| cc.sequence_number()).max(),
+                    self.changes.seq_num_min(),
+                    self.changes.seq_num_max(),
                     self.data_max_size_serialized,
                     self.heartbeat_period,
+                    self.samples_per_piggyback_heartbeat,
+                    self.flow_controller.as_ref(),
                     message_sender,
                 ),
             }
         }
     }
 
+    // Sends the changes which are the next one to be sent to every reader of a group sharing a
+    // multicast locator once to that locator. The readers which are not in sync with the rest of
+    // their group, or which need a GAP first, keep being served with unicast.
+    fn send_multicast_message(&mut self, threshold: usize, message_sender: &MessageSender) {
+        let mut multicast_locator_list: Vec<Locator> = Vec::new();
+        for locator in self
+            .matched_readers
+            .iter()
+            .flat_map(|rp| rp.multicast_locator_list())
+        {
+            if !multicast_locator_list.contains(locator) {
+                multicast_locator_list.push(*locator);
+            }
+        }
+
+        for locator in multicast_locator_list {
+            let mut group: Vec<&mut RtpsReaderProxy> = self
+                .matched_readers
+                .iter_mut()
+                .filter(|rp| rp.push_mode() && rp.multicast_locator_list().contains(&locator))
+                .collect();
+            if group.len() <= threshold {
+                continue;
+            }
+
+            // The changes already sent to the whole group are skipped
+            let group_highest_sent_seq_num = group
+                .iter()
+                .map(|rp| rp.highest_sent_seq_num())
+                .min()
+                .unwrap_or_default();
+            for cache_change in self.changes.changes_after(group_highest_sent_seq_num) {
+                let seq_num = cache_change.sequence_number();
+                let is_next_for_group = group.iter_mut().all(|rp| {
+                    rp.next_unsent_change(&self.changes) == Some(seq_num)
+                        && rp.highest_sent_seq_num() + 1 == seq_num
+                        && rp.is_change_relevant(seq_num)
+                        && !rp.fragment_pacing().has_pending_fragments()
+                });
+                if !is_next_for_group || !is_flow_available(self.flow_controller.as_ref()) {
+                    break;
+                }
+
+                if let Some(flow_controller) = &self.flow_controller {
+                    flow_controller.consume(cache_change.data_value().len());
+                }
+                send_change_message_multicast(
+                    cache_change,
+                    self.guid.entity_id(),
+                    self.data_max_size_serialized,
+                    locator,
+                    message_sender,
+                );
+                for reader_proxy in group.iter_mut() {
+                    reader_proxy.set_highest_sent_seq_num(seq_num);
+                }
+            }
+        }
+    }
+
+    pub fn send_liveliness_heartbeat(&mut self, message_sender: &MessageSender) {
+        let writer_id = self.guid.entity_id();
+        let first_sn = self.changes.seq_num_min().unwrap_or(1);
+        let last_sn = self.changes.seq_num_max().unwrap_or(0);
+        for reader_proxy in &mut self.matched_readers {
+            let heartbeat_submessage = Box::new(
+                reader_proxy
+                    .heartbeat_machine()
+                    .generate_new_liveliness_heartbeat(writer_id, first_sn, last_sn),
+            );
+            let info_dst = Box::new(InfoDestinationSubmessage::new(
+                reader_proxy.remote_reader_guid().prefix(),
+            ));
+            message_sender.write_message(
+                vec![info_dst, heartbeat_submessage],
+                reader_proxy.destination_locator_list().to_vec(),
+            );
+        }
+    }
+
+    // Sends a heartbeat to every reliable reader right away instead of waiting for the heartbeat
+    // period, so that the readers request any change they might have missed.
+    pub fn send_heartbeat(&mut self, message_sender: &MessageSender) {
+        let seq_num_min = self.changes.seq_num_min();
+        let seq_num_max = self.changes.seq_num_max();
+        for reader_proxy in self
+            .matched_readers
+            .iter_mut()
+            .filter(|rp| rp.reliability() == ReliabilityKind::Reliable)
+        {
+            send_heartbeat_to_reader_proxy(
+                reader_proxy,
+                self.guid.entity_id(),
+                seq_num_min,
+                seq_num_max,
+                message_sender,
+            );
+        }
+    }
+
     pub fn on_acknack_submessage_received(
         &mut self,
         acknack_submessage: &AckNackSubmessage,
         source_guid_prefix: GuidPrefix,
         message_sender: &MessageSender,
     ) {
-        if &self.guid.entity_id() == acknack_submessage.writer_id() {
-            let reader_guid = Guid::new(source_guid_prefix, *acknack_submessage.reader_id());
+        let dscp = self.dscp;
+        message_sender.write_message_with_dscp(dscp, |message_sender| {
+            if &self.guid.entity_id() == acknack_submessage.writer_id() {
+                let reader_guid = Guid::new(source_guid_prefix, *acknack_submessage.reader_id());
 
+                if let Some(reader_proxy) = self
+                    .matched_readers
+                    .iter_mut()
+                    .find(|x| x.remote_reader_guid() == reader_guid)
+                {
+                    if reader_proxy.reliability() == ReliabilityKind::Reliable
+                        && acknack_submessage.count() > reader_proxy.last_received_acknack_count()
+                    {
+                        reader_proxy
+                            .acked_changes_set(acknack_submessage.reader_sn_state().base() - 1);
+                        // The changes requested as a whole are sent with all their fragments
+                        for seq_num in acknack_submessage.reader_sn_state().set() {
+                            reader_proxy.requested_fragments_clear(seq_num);
+                        }
+                        reader_proxy
+                            .requested_changes_set(acknack_submessage.reader_sn_state().set());
+
+                        reader_proxy.set_last_received_acknack_count(acknack_submessage.count());
+
+                        let seq_num_min = self.changes.seq_num_min();
+                        let seq_num_max = self.changes.seq_num_max();
+                        send_message_to_reader_proxy_reliable(
+                            reader_proxy,
+                            self.guid.entity_id(),
+                            &self.changes,
+                            seq_num_min,
+                            seq_num_max,
+                            self.data_max_size_serialized,
+                            self.heartbeat_period,
+                            self.samples_per_piggyback_heartbeat,
+                            self.flow_controller.as_ref(),
+                            message_sender,
+                        );
+
+                        // A reader clearing the final flag expects a heartbeat in response
+                        // whereas otherwise the writer does not have to answer the acknack
+                        if !acknack_submessage.final_flag() {
+                            send_heartbeat_to_reader_proxy(
+                                reader_proxy,
+                                self.guid.entity_id(),
+                                seq_num_min,
+                                seq_num_max,
+                                message_sender,
+                            );
+                        }
+                        self.call_acknowledgment_callbacks();
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn on_nack_frag_submessage_received(
+        &mut self,
+        nackfrag_submessage: &NackFragSubmessage,
+        source_guid_prefix: GuidPrefix,
+        message_sender: &MessageSender,
+    ) {
+        let reader_guid = Guid::new(source_guid_prefix, nackfrag_submessage.reader_id());
+        let dscp = self.dscp;
+        message_sender.write_message_with_dscp(dscp, |message_sender| {
             if let Some(reader_proxy) = self
                 .matched_readers
                 .iter_mut()
                 .find(|x| x.remote_reader_guid() == reader_guid)
             {
                 if reader_proxy.reliability() == ReliabilityKind::Reliable
-                    && acknack_submessage.count() > reader_proxy.last_received_acknack_count()
+                    && nackfrag_submessage.count() > reader_proxy.last_received_nack_frag_count()
                 {
-                    reader_proxy.acked_changes_set(acknack_submessage.reader_sn_state().base() - 1);
-                    reader_proxy.requested_changes_set(acknack_submessage.reader_sn_state().set());
-
-                    reader_proxy.set_last_received_acknack_count(acknack_submessage.count());
+                    reader_proxy.requested_fragments_set(
+                        nackfrag_submessage.writer_sn(),
+                        nackfrag_submessage.fragment_number_state().set(),
+                    );
+                    reader_proxy
+                        .requested_changes_set(std::iter::once(nackfrag_submessage.writer_sn()));
+                    reader_proxy.set_last_received_nack_frag_count(nackfrag_submessage.count());
+                    reader_proxy.fragment_pacing().on_fragments_lost();
 
                     send_message_to_reader_proxy_reliable(
                         reader_proxy,
                         self.guid.entity_id(),
                         &self.changes,
-                        self.changes.iter().map(|cc| cc.sequence_number()).min(),
-                        self.changes.iter().map(|cc| cc.sequence_number()).max(),
+                        self.changes.seq_num_min(),
+                        self.changes.seq_num_max(),
                         self.data_max_size_serialized,
                         self.heartbeat_period,
+                        self.samples_per_piggyback_heartbeat,
+                        self.flow_controller.as_ref(),
                         message_sender,
                     );
                 }
             }
-        }
+        });
     }
+}
 
-    pub fn on_nack_frag_submessage_received(
-        &mut self,
-        nackfrag_submessage: &NackFragSubmessage,
-        source_guid_prefix: GuidPrefix,
-        message_sender: &MessageSender,
-    ) {
-        let reader_guid = Guid::new(source_guid_prefix, nackfrag_submessage.reader_id());
+fn is_flow_available(flow_controller: Option<&FlowController>) -> bool {
+    flow_controller.is_none_or(FlowController::is_available)
+}
 
-        if let Some(reader_proxy) = self
-            .matched_readers
-            .iter_mut()
-            .find(|x| x.remote_reader_guid() == reader_guid)
-        {
-            if reader_proxy.reliability() == ReliabilityKind::Reliable
-                && nackfrag_submessage.count() > reader_proxy.last_received_nack_frag_count()
-            {
-                reader_proxy
-                    .requested_changes_set(std::iter::once(nackfrag_submessage.writer_sn()));
-                reader_proxy.set_last_received_nack_frag_count(nackfrag_submessage.count());
+fn send_change_message_multicast(
+    cache_change: &CacheChange,
+    writer_id: EntityId,
+    data_max_size_serialized: usize,
+    locator: Locator,
+    message_sender: &MessageSender,
+) {
+    let data_max_size_serialized =
+        data_max_size_serialized.min(message_sender.max_data_size_serialized(&[locator]));
+    // Without INFO_DST and with an unknown reader id the message is processed by all the
+    // readers receiving it
+    let info_timestamp = |cache_change: &CacheChange| {
+        if let Some(timestamp) = cache_change.source_timestamp() {
+            Box::new(InfoTimestampSubmessage::new(false, timestamp.into()))
+        } else {
+            Box::new(InfoTimestampSubmessage::new(true, TIME_INVALID))
+        }
+    };
+    let number_of_fragments = cache_change
+        .data_value()
+        .len()
+        .div_ceil(data_max_size_serialized);
 
-                send_message_to_reader_proxy_reliable(
-                    reader_proxy,
-                    self.guid.entity_id(),
-                    &self.changes,
-                    self.changes.iter().map(|cc| cc.sequence_number()).min(),
-                    self.changes.iter().map(|cc| cc.sequence_number()).max(),
-                    self.data_max_size_serialized,
-                    self.heartbeat_period,
-                    message_sender,
-                );
-            }
+    if number_of_fragments > 1 {
+        let (_, key_flag) = cache_change.payload_flags();
+        for frag_index in 0..number_of_fragments {
+            let start = frag_index * data_max_size_serialized;
+            let end = std::cmp::min(
+                (frag_index + 1) * data_max_size_serialized,
+                cache_change.data_value().len(),
+            );
+            let data_frag = Box::new(DataFragSubmessage::new(
+                true,
+                false,
+                key_flag,
+                ENTITYID_UNKNOWN,
+                writer_id,
+                cache_change.sequence_number(),
+                (frag_index + 1) as u32,
+                1,
+                data_max_size_serialized as u16,
+                cache_change.data_value().len() as u32,
+                ParameterList::new(vec![]),
+                SerializedDataFragment::new(cache_change.data_value().clone().into(), start..end),
+            ));
+            message_sender
+                .write_message(vec![info_timestamp(cache_change), data_frag], vec![locator]);
         }
+    } else {
+        let data_submessage =
+            Box::new(cache_change.as_data_submessage(ENTITYID_UNKNOWN, writer_id));
+        message_sender.write_message(
+            vec![info_timestamp(cache_change), data_submessage],
+            vec![locator],
+        );
     }
 }
 
 fn send_message_to_reader_proxy_best_effort(
     reader_proxy: &mut RtpsReaderProxy,
     writer_id: EntityId,
-    changes: &[CacheChange],
+    changes: &WriterHistoryCache,
     data_max_size_serialized: usize,
+    flow_controller: Option<&FlowController>,
     message_sender: &MessageSender,
 ) {
+    // The data is fragmented so that the messages fit in the MTU of the path to the reader
+    let data_max_size_serialized = data_max_size_serialized
+        .min(message_sender.max_data_size_serialized(reader_proxy.destination_locator_list()));
     // a_change_seq_num := the_reader_proxy.next_unsent_change();
     // if ( a_change_seq_num > the_reader_proxy.higuest_sent_seq_num +1 ) {
     //      GAP = new GAP(the_reader_locator.higuest_sent_seq_num + 1, a_change_seq_num -1);
@@ -232,7 +623,12 @@ fn send_message_to_reader_proxy_best_effort(
     //      send GAP;
     // }
     // the_reader_proxy.higuest_sent_seq_num := a_change_seq_num;
-    while let Some(next_unsent_change_seq_num) = reader_proxy.next_unsent_change(changes.iter()) {
+    while let Some(next_unsent_change_seq_num) = reader_proxy.next_unsent_change(changes) {
+        // The changes exceeding the rate are sent once the flow controller has bytes available
+        if !is_flow_available(flow_controller) {
+            break;
+        }
+
         if next_unsent_change_seq_num > reader_proxy.highest_sent_seq_num() + 1 {
             let gap_start_sequence_number = reader_proxy.highest_sent_seq_num() + 1;
             let gap_end_sequence_number = next_unsent_change_seq_num - 1;
@@ -244,15 +640,18 @@ fn send_message_to_reader_proxy_best_effort(
             ));
 
             message_sender.write_message(
-                &[gap_submessage],
-                reader_proxy.unicast_locator_list().to_vec(),
+                vec![gap_submessage],
+                reader_proxy.destination_locator_list().to_vec(),
             );
 
             reader_proxy.set_highest_sent_seq_num(next_unsent_change_seq_num);
-        } else if let Some(cache_change) = changes
-            .iter()
-            .find(|cc| cc.sequence_number() == next_unsent_change_seq_num)
-        {
+        } else if !reader_proxy.is_change_relevant(next_unsent_change_seq_num) {
+            // Best-effort readers do not need to be told about the changes which are not relevant
+            reader_proxy.set_highest_sent_seq_num(next_unsent_change_seq_num);
+        } else if let Some(cache_change) = changes.get_change(next_unsent_change_seq_num) {
+            if let Some(flow_controller) = flow_controller {
+                flow_controller.consume(cache_change.data_value().len());
+            }
             let number_of_fragments = cache_change
                 .data_value()
                 .len()
@@ -272,11 +671,7 @@ fn send_message_to_reader_proxy_best_effort(
                     };
 
                     let inline_qos_flag = true;
-                    let key_flag = match cache_change.kind() {
-                        ChangeKind::Alive => false,
-                        ChangeKind::NotAliveDisposed | ChangeKind::NotAliveUnregistered => true,
-                        _ => todo!(),
-                    };
+                    let (_, key_flag) = cache_change.payload_flags();
                     let non_standard_payload_flag = false;
                     let reader_id = reader_proxy.remote_reader_guid().entity_id();
                     let writer_sn = cache_change.sequence_number();
@@ -312,8 +707,8 @@ fn send_message_to_reader_proxy_best_effort(
                     ));
 
                     message_sender.write_message(
-                        &[info_dst, info_timestamp, data_frag],
-                        reader_proxy.unicast_locator_list().to_vec(),
+                        vec![info_dst, info_timestamp, data_frag],
+                        reader_proxy.destination_locator_list().to_vec(),
                     );
                 }
             } else {
@@ -334,19 +729,19 @@ fn send_message_to_reader_proxy_best_effort(
                     ));
 
                 message_sender.write_message(
-                    &[info_dst, info_timestamp, data_submessage],
-                    reader_proxy.unicast_locator_list().to_vec(),
+                    vec![info_dst, info_timestamp, data_submessage],
+                    reader_proxy.destination_locator_list().to_vec(),
                 );
             }
         } else {
             message_sender.write_message(
-                &[Box::new(GapSubmessage::new(
+                vec![Box::new(GapSubmessage::new(
                     ENTITYID_UNKNOWN,
                     writer_id,
                     next_unsent_change_seq_num,
                     SequenceNumberSet::new(next_unsent_change_seq_num + 1, []),
                 ))],
-                reader_proxy.unicast_locator_list().to_vec(),
+                reader_proxy.destination_locator_list().to_vec(),
             );
         }
 
@@ -358,17 +753,68 @@ fn send_message_to_reader_proxy_best_effort(
 fn send_message_to_reader_proxy_reliable(
     reader_proxy: &mut RtpsReaderProxy,
     writer_id: EntityId,
-    changes: &[CacheChange],
+    changes: &WriterHistoryCache,
     seq_num_min: Option<SequenceNumber>,
     seq_num_max: Option<SequenceNumber>,
     data_max_size_serialized: usize,
     heartbeat_period: Duration,
+    samples_per_piggyback_heartbeat: usize,
+    flow_controller: Option<&FlowController>,
     message_sender: &MessageSender,
 ) {
+    // The data is fragmented so that the messages fit in the MTU of the path to the reader
+    let data_max_size_serialized = data_max_size_serialized
+        .min(message_sender.max_data_size_serialized(reader_proxy.destination_locator_list()));
+    // The fragments held back by the pacing are sent before any other change
+    if let Some((change_seq_num, next_fragment)) =
+        reader_proxy.fragment_pacing().take_pending_fragments()
+    {
+        send_change_message_reader_proxy_reliable(
+            reader_proxy,
+            writer_id,
+            changes,
+            seq_num_min,
+            seq_num_max,
+            data_max_size_serialized,
+            samples_per_piggyback_heartbeat,
+            change_seq_num,
+            next_fragment,
+            flow_controller,
+            message_sender,
+        );
+        if reader_proxy.fragment_pacing().has_pending_fragments() {
+            return;
+        }
+    }
+
     // Top part of the state machine - Figure 8.19 RTPS standard
-    if reader_proxy.unsent_changes(changes.iter()) {
-        while let Some(next_unsent_change_seq_num) = reader_proxy.next_unsent_change(changes.iter())
+    if !reader_proxy.push_mode() {
+        // In pull mode the new changes are only announced with a heartbeat and
+        // they are sent once the reader requests them
+        if reader_proxy.unsent_changes(changes)
+            || (reader_proxy.unacked_changes(seq_num_max)
+                && reader_proxy
+                    .heartbeat_machine()
+                    .is_time_for_heartbeat(heartbeat_period.into()))
         {
+            send_heartbeat_to_reader_proxy(
+                reader_proxy,
+                writer_id,
+                seq_num_min,
+                seq_num_max,
+                message_sender,
+            );
+            if let Some(seq_num_max) = seq_num_max {
+                reader_proxy.set_highest_sent_seq_num(seq_num_max);
+            }
+        }
+    } else if reader_proxy.unsent_changes(changes) {
+        while let Some(next_unsent_change_seq_num) = reader_proxy.next_unsent_change(changes) {
+            // The changes exceeding the rate are sent once the flow controller has bytes available
+            if !is_flow_available(flow_controller) {
+                break;
+            }
+
             if next_unsent_change_seq_num > reader_proxy.highest_sent_seq_num() + 1 {
                 let gap_start_sequence_number = reader_proxy.highest_sent_seq_num() + 1;
                 let gap_end_sequence_number = next_unsent_change_seq_num - 1;
@@ -389,8 +835,8 @@ fn send_message_to_reader_proxy_reliable(
                     reader_proxy.remote_reader_guid().prefix(),
                 ));
                 message_sender.write_message(
-                    &[info_dst, gap_submessage, heartbeat_submessage],
-                    reader_proxy.unicast_locator_list().to_vec(),
+                    vec![info_dst, gap_submessage, heartbeat_submessage],
+                    reader_proxy.destination_locator_list().to_vec(),
                 );
             } else {
                 send_change_message_reader_proxy_reliable(
@@ -400,11 +846,17 @@ fn send_message_to_reader_proxy_reliable(
                     seq_num_min,
                     seq_num_max,
                     data_max_size_serialized,
+                    samples_per_piggyback_heartbeat,
                     next_unsent_change_seq_num,
+                    0,
+                    flow_controller,
                     message_sender,
                 );
             }
             reader_proxy.set_highest_sent_seq_num(next_unsent_change_seq_num);
+            if reader_proxy.fragment_pacing().has_pending_fragments() {
+                return;
+            }
         }
     } else if !reader_proxy.unacked_changes(seq_num_max) {
         // Idle
@@ -412,27 +864,21 @@ fn send_message_to_reader_proxy_reliable(
         .heartbeat_machine()
         .is_time_for_heartbeat(heartbeat_period.into())
     {
-        let first_sn = seq_num_min.unwrap_or(1);
-        let last_sn = seq_num_max.unwrap_or(0);
-        let heartbeat_submessage = Box::new(
-            reader_proxy
-                .heartbeat_machine()
-                .generate_new_heartbeat(writer_id, first_sn, last_sn),
-        );
-
-        let info_dst = Box::new(InfoDestinationSubmessage::new(
-            reader_proxy.remote_reader_guid().prefix(),
-        ));
-
-        message_sender.write_message(
-            &[info_dst, heartbeat_submessage],
-            reader_proxy.unicast_locator_list().to_vec(),
+        send_heartbeat_to_reader_proxy(
+            reader_proxy,
+            writer_id,
+            seq_num_min,
+            seq_num_max,
+            message_sender,
         );
     }
 
     // Middle-part of the state-machine - Figure 8.19 RTPS standard
     if !reader_proxy.requested_changes().is_empty() {
-        while let Some(next_requested_change_seq_num) = reader_proxy.next_requested_change() {
+        while is_flow_available(flow_controller) {
+            let Some(next_requested_change_seq_num) = reader_proxy.next_requested_change() else {
+                break;
+            };
             // "a_change.status := UNDERWAY;" should be done by next_requested_change() as
             // it's not done here to avoid the change being a mutable reference
             // Also the post-condition:
@@ -445,29 +891,105 @@ fn send_message_to_reader_proxy_reliable(
                 seq_num_min,
                 seq_num_max,
                 data_max_size_serialized,
+                samples_per_piggyback_heartbeat,
                 next_requested_change_seq_num,
+                0,
+                flow_controller,
                 message_sender,
             );
+            if reader_proxy.fragment_pacing().has_pending_fragments() {
+                return;
+            }
+        }
+    }
+}
+
+// Returns the changes of the history which a newly matched reader does not receive because
+// they exceed the limit of historical data. The most recent changes are the ones delivered.
+fn historical_changes_beyond_limit(
+    changes: &WriterHistoryCache,
+    limit: &HistoricalDataLimit,
+) -> HashSet<SequenceNumber> {
+    let mut changes_beyond_limit = HashSet::new();
+    if limit == &HistoricalDataLimit::default() {
+        return changes_beyond_limit;
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let mut instance_sample_count: HashMap<Option<[u8; 16]>, usize> = HashMap::new();
+    let mut sample_count = 0;
+    for cache_change in changes.iter().rev() {
+        let is_too_old = match (limit.max_age, cache_change.source_timestamp()) {
+            (Some(max_age), Some(timestamp)) => {
+                let age = now.saturating_sub(core::time::Duration::new(
+                    timestamp.sec().max(0) as u64,
+                    timestamp.nanosec(),
+                ));
+                age > max_age
+            }
+            _ => false,
+        };
+        let instance_count = instance_sample_count
+            .entry(cache_change.instance_handle)
+            .or_default();
+        if is_too_old
+            || limit.depth.is_some_and(|depth| *instance_count >= depth)
+            || limit
+                .max_samples
+                .is_some_and(|max_samples| sample_count >= max_samples)
+        {
+            changes_beyond_limit.insert(cache_change.sequence_number());
+        } else {
+            *instance_count += 1;
+            sample_count += 1;
         }
     }
+    changes_beyond_limit
+}
+
+fn send_heartbeat_to_reader_proxy(
+    reader_proxy: &mut RtpsReaderProxy,
+    writer_id: EntityId,
+    seq_num_min: Option<SequenceNumber>,
+    seq_num_max: Option<SequenceNumber>,
+    message_sender: &MessageSender,
+) {
+    let first_sn = seq_num_min.unwrap_or(1);
+    let last_sn = seq_num_max.unwrap_or(0);
+    let heartbeat_submessage = Box::new(
+        reader_proxy
+            .heartbeat_machine()
+            .generate_new_heartbeat(writer_id, first_sn, last_sn),
+    );
+
+    let info_dst = Box::new(InfoDestinationSubmessage::new(
+        reader_proxy.remote_reader_guid().prefix(),
+    ));
+
+    message_sender.write_message(
+        vec![info_dst, heartbeat_submessage],
+        reader_proxy.destination_locator_list().to_vec(),
+    );
 }
 
 #[allow(clippy::too_many_arguments)]
 fn send_change_message_reader_proxy_reliable(
     reader_proxy: &mut RtpsReaderProxy,
     writer_id: EntityId,
-    changes: &[CacheChange],
+    changes: &WriterHistoryCache,
     seq_num_min: Option<SequenceNumber>,
     seq_num_max: Option<SequenceNumber>,
     data_max_size_serialized: usize,
+    samples_per_piggyback_heartbeat: usize,
     change_seq_num: SequenceNumber,
+    first_fragment: usize,
+    flow_controller: Option<&FlowController>,
     message_sender: &MessageSender,
 ) {
-    match changes
-        .iter()
-        .find(|cc| cc.sequence_number() == change_seq_num)
-    {
-        Some(cache_change) if change_seq_num > reader_proxy.first_relevant_sample_seq_num() => {
+    match changes.get_change(change_seq_num) {
+        Some(cache_change) if reader_proxy.is_change_relevant(change_seq_num) => {
             let number_of_fragments = cache_change
                 .data_value()
                 .len()
@@ -475,7 +997,39 @@ fn send_change_message_reader_proxy_reliable(
 
             // Either send a DATAFRAG submessages or send a single DATA submessage
             if number_of_fragments > 1 {
-                for frag_index in 0..number_of_fragments {
+                // Only the fragments requested with NACK_FRAG are repaired when the change is
+                // partially missing
+                let requested_fragments = reader_proxy.take_requested_fragments(change_seq_num);
+                let fragment_index_list: Vec<usize> = match &requested_fragments {
+                    Some(fragment_number_set) => fragment_number_set
+                        .iter()
+                        .map(|&fragment_number| fragment_number as usize - 1)
+                        .filter(|&i| i >= first_fragment && i < number_of_fragments)
+                        .collect(),
+                    None => (first_fragment..number_of_fragments).collect(),
+                };
+                let mut last_sent_fragment_num = None;
+                for (position, &frag_index) in fragment_index_list.iter().enumerate() {
+                    // Under congestion or above the rate of the flow controller the remaining
+                    // fragments are sent in the next pacing intervals
+                    if !is_flow_available(flow_controller)
+                        || !reader_proxy.fragment_pacing().try_send_fragment()
+                    {
+                        if requested_fragments.is_some() {
+                            reader_proxy.requested_fragments_set(
+                                change_seq_num,
+                                fragment_index_list[position..]
+                                    .iter()
+                                    .map(|&i| (i + 1) as FragmentNumber),
+                            );
+                        }
+                        reader_proxy
+                            .fragment_pacing()
+                            .set_pending_fragments(change_seq_num, frag_index);
+                        break;
+                    }
+                    last_sent_fragment_num = Some((frag_index + 1) as FragmentNumber);
+
                     let info_dst = Box::new(InfoDestinationSubmessage::new(
                         reader_proxy.remote_reader_guid().prefix(),
                     ));
@@ -487,11 +1041,7 @@ fn send_change_message_reader_proxy_reliable(
                     };
 
                     let inline_qos_flag = true;
-                    let key_flag = match cache_change.kind() {
-                        ChangeKind::Alive => false,
-                        ChangeKind::NotAliveDisposed | ChangeKind::NotAliveUnregistered => true,
-                        _ => todo!(),
-                    };
+                    let (_, key_flag) = cache_change.payload_flags();
                     let non_standard_payload_flag = false;
                     let reader_id = reader_proxy.remote_reader_guid().entity_id();
                     let writer_sn = cache_change.sequence_number();
@@ -506,6 +1056,9 @@ fn send_change_message_reader_proxy_reliable(
                         cache_change.data_value().len(),
                     );
 
+                    if let Some(flow_controller) = flow_controller {
+                        flow_controller.consume(end - start);
+                    }
                     let serialized_payload = SerializedDataFragment::new(
                         cache_change.data_value().clone().into(),
                         start..end,
@@ -527,8 +1080,28 @@ fn send_change_message_reader_proxy_reliable(
                     ));
 
                     message_sender.write_message(
-                        &[info_dst, info_timestamp, data_frag],
-                        reader_proxy.unicast_locator_list().to_vec(),
+                        vec![info_dst, info_timestamp, data_frag],
+                        reader_proxy.destination_locator_list().to_vec(),
+                    );
+                }
+
+                // The fragments sent in sequence are announced so that the reader requests the
+                // ones it missed without waiting for the whole change to be sent
+                if let (None, Some(last_fragment_num)) =
+                    (&requested_fragments, last_sent_fragment_num)
+                {
+                    let info_dst = Box::new(InfoDestinationSubmessage::new(
+                        reader_proxy.remote_reader_guid().prefix(),
+                    ));
+                    let heartbeat_frag =
+                        Box::new(reader_proxy.heartbeat_frag_machine().submessage(
+                            writer_id,
+                            change_seq_num,
+                            last_fragment_num,
+                        ));
+                    message_sender.write_message(
+                        vec![info_dst, heartbeat_frag],
+                        reader_proxy.destination_locator_list().to_vec(),
                     );
                 }
             } else {
@@ -548,18 +1121,31 @@ fn send_change_message_reader_proxy_reliable(
                         writer_id,
                     ));
 
-                let first_sn = seq_num_min.unwrap_or(1);
-                let last_sn = seq_num_max.unwrap_or(0);
-                let heartbeat = Box::new(
-                    reader_proxy
-                        .heartbeat_machine()
-                        .generate_new_heartbeat(writer_id, first_sn, last_sn),
-                );
+                if let Some(flow_controller) = flow_controller {
+                    flow_controller.consume(cache_change.data_value().len());
+                }
+                if reader_proxy
+                    .heartbeat_machine()
+                    .is_time_for_piggyback_heartbeat(samples_per_piggyback_heartbeat)
+                {
+                    let first_sn = seq_num_min.unwrap_or(1);
+                    let last_sn = seq_num_max.unwrap_or(0);
+                    let heartbeat = Box::new(
+                        reader_proxy
+                            .heartbeat_machine()
+                            .generate_new_heartbeat(writer_id, first_sn, last_sn),
+                    );
 
-                message_sender.write_message(
-                    &[info_dst, info_timestamp, data_submessage, heartbeat],
-                    reader_proxy.unicast_locator_list().to_vec(),
-                );
+                    message_sender.write_message(
+                        vec![info_dst, info_timestamp, data_submessage, heartbeat],
+                        reader_proxy.destination_locator_list().to_vec(),
+                    );
+                } else {
+                    message_sender.write_message(
+                        vec![info_dst, info_timestamp, data_submessage],
+                        reader_proxy.destination_locator_list().to_vec(),
+                    );
+                }
             }
         }
         _ => {
@@ -575,9 +1161,691 @@ fn send_change_message_reader_proxy_reliable(
             ));
 
             message_sender.write_message(
-                &[info_dst, gap_submessage],
-                reader_proxy.unicast_locator_list().to_vec(),
+                vec![info_dst, gap_submessage],
+                reader_proxy.destination_locator_list().to_vec(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        configuration::DestinationMtu,
+        rtps::messages::{
+            overall_structure::{RtpsMessageRead, RtpsSubmessageReadKind},
+            submessage_elements::FragmentNumberSet,
+        },
+        transport::{
+            types::{ChangeKind, Time, LOCATOR_KIND_UDP_V4},
+            writer::ChangeFilter,
+        },
+    };
+    use std::net::UdpSocket;
+
+    fn local_socket_and_locator() -> (UdpSocket, Locator) {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket
+            .set_read_timeout(Some(std::time::Duration::from_millis(200)))
+            .unwrap();
+        let locator = Locator::new(
+            LOCATOR_KIND_UDP_V4,
+            socket.local_addr().unwrap().port() as u32,
+            [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+        );
+        (socket, locator)
+    }
+
+    fn received_message_count(socket: &UdpSocket) -> usize {
+        let mut buf = [0; 1024];
+        std::iter::from_fn(|| socket.recv(&mut buf).ok()).count()
+    }
+
+    fn received_submessages(socket: &UdpSocket) -> Vec<RtpsSubmessageReadKind> {
+        let mut buf = [0; 1024];
+        let mut submessages = Vec::new();
+        while let Ok(len) = socket.recv(&mut buf) {
+            submessages.extend(
+                RtpsMessageRead::try_from(&buf[..len])
+                    .unwrap()
+                    .submessages(),
+            );
+        }
+        submessages
+    }
+
+    #[test]
+    fn changes_are_sent_once_to_shared_multicast_locator() {
+        // A unicast address stands in for the multicast group so that the datagrams can be counted
+        let (group_socket, group_locator) = local_socket_and_locator();
+        let (reader1_socket, reader1_locator) = local_socket_and_locator();
+        let (reader2_socket, reader2_locator) = local_socket_and_locator();
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 1], 3));
+        let mut writer = RtpsStatefulWriter::new(
+            writer_guid,
+            1000,
+            RetransmissionBackoff::default(),
+            1,
+            Some(1),
+            None,
+            None,
+        );
+        for (entity_key, unicast_locator) in [(1, reader1_locator), (2, reader2_locator)] {
+            writer.add_matched_reader(&ReaderProxy {
+                remote_reader_guid: Guid::new([2; 12], EntityId::new([0, 0, entity_key], 4)),
+                remote_group_entity_id: ENTITYID_UNKNOWN,
+                reliability_kind: ReliabilityKind::BestEffort,
+                durability_kind: DurabilityKind::Volatile,
+                unicast_locator_list: vec![unicast_locator],
+                multicast_locator_list: vec![group_locator],
+                expects_inline_qos: false,
+                disable_positive_acks_keep_duration: None,
+                prefer_multicast: false,
+                push_mode: true,
+                historical_data_limit: Default::default(),
+                content_filter: None,
+            });
+        }
+
+        for sequence_number in 1..=2 {
+            writer.add_change(
+                CacheChange {
+                    kind: ChangeKind::Alive,
+                    writer_guid,
+                    sequence_number,
+                    source_timestamp: None,
+                    instance_handle: None,
+                    data_value: vec![1, 2, 3, 4].into(),
+                },
+                &message_sender,
             );
         }
+
+        assert_eq!(received_message_count(&group_socket), 2);
+        assert_eq!(received_message_count(&reader1_socket), 0);
+        assert_eq!(received_message_count(&reader2_socket), 0);
+    }
+
+    #[test]
+    fn batched_changes_are_sent_together_once_batch_is_full_or_flushed() {
+        let (reader_socket, reader_locator) = local_socket_and_locator();
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 1], 2));
+        let mut writer = RtpsStatefulWriter::new(
+            writer_guid,
+            1000,
+            RetransmissionBackoff::default(),
+            1,
+            None,
+            Some(WriterBatching {
+                max_batch_size: 12,
+                flush_period: std::time::Duration::from_secs(3600),
+            }),
+            None,
+        );
+        writer.add_matched_reader(&ReaderProxy {
+            remote_reader_guid: Guid::new([2; 12], EntityId::new([0, 0, 1], 7)),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::BestEffort,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![reader_locator],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
+        });
+        let cache_change = |sequence_number| CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid,
+            sequence_number,
+            source_timestamp: None,
+            instance_handle: None,
+            data_value: vec![1, 2, 3, 4].into(),
+        };
+
+        writer.add_change(cache_change(1), &message_sender);
+        writer.add_change(cache_change(2), &message_sender);
+        writer.send_message(&message_sender);
+        assert_eq!(received_message_count(&reader_socket), 0);
+
+        writer.add_change(cache_change(3), &message_sender);
+        let submessages = received_submessages(&reader_socket);
+        let data_sequence_numbers: Vec<_> = submessages
+            .iter()
+            .filter_map(|s| match s {
+                RtpsSubmessageReadKind::Data(d) => Some(d.writer_sn()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(data_sequence_numbers, vec![1, 2, 3]);
+
+        writer.add_change(cache_change(4), &message_sender);
+        writer.add_change(cache_change(5), &message_sender);
+        assert_eq!(received_message_count(&reader_socket), 0);
+        writer.flush(&message_sender);
+        assert_eq!(received_message_count(&reader_socket), 1);
+    }
+
+    #[test]
+    fn changes_exceeding_flow_controller_rate_are_held_back() {
+        let (reader_socket, reader_locator) = local_socket_and_locator();
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 1], 2));
+        let mut writer = RtpsStatefulWriter::new(
+            writer_guid,
+            1000,
+            RetransmissionBackoff::default(),
+            1,
+            None,
+            None,
+            Some(FlowController::new(1000)),
+        );
+        writer.add_matched_reader(&ReaderProxy {
+            remote_reader_guid: Guid::new([2; 12], EntityId::new([0, 0, 1], 7)),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::BestEffort,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![reader_locator],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
+        });
+
+        for sequence_number in 1..=3 {
+            writer.add_change(
+                CacheChange {
+                    kind: ChangeKind::Alive,
+                    writer_guid,
+                    sequence_number,
+                    source_timestamp: None,
+                    instance_handle: None,
+                    data_value: vec![0; 600].into(),
+                },
+                &message_sender,
+            );
+        }
+        // The second change exhausts the bytes of the first second
+        assert_eq!(received_message_count(&reader_socket), 2);
+
+        std::thread::sleep(std::time::Duration::from_millis(300));
+        writer.send_message(&message_sender);
+        assert_eq!(received_message_count(&reader_socket), 1);
+    }
+
+    #[test]
+    fn pull_mode_changes_are_only_sent_when_requested() {
+        let (reader_socket, reader_locator) = local_socket_and_locator();
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 1], 2));
+        let reader_guid = Guid::new([2; 12], EntityId::new([0, 0, 1], 7));
+        let mut writer = RtpsStatefulWriter::new(
+            writer_guid,
+            1000,
+            RetransmissionBackoff::default(),
+            1,
+            None,
+            None,
+            None,
+        );
+        writer.add_matched_reader(&ReaderProxy {
+            remote_reader_guid: reader_guid,
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::TransientLocal,
+            unicast_locator_list: vec![reader_locator],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: false,
+            historical_data_limit: Default::default(),
+            content_filter: None,
+        });
+
+        writer.add_change(
+            CacheChange {
+                kind: ChangeKind::Alive,
+                writer_guid,
+                sequence_number: 1,
+                source_timestamp: None,
+                instance_handle: None,
+                data_value: vec![1, 2, 3, 4].into(),
+            },
+            &message_sender,
+        );
+        let submessages = received_submessages(&reader_socket);
+        assert!(submessages
+            .iter()
+            .any(|s| matches!(s, RtpsSubmessageReadKind::Heartbeat(h) if h.last_sn() == 1)));
+        assert!(!submessages
+            .iter()
+            .any(|s| matches!(s, RtpsSubmessageReadKind::Data(_))));
+
+        writer.on_acknack_submessage_received(
+            &AckNackSubmessage::new(
+                true,
+                reader_guid.entity_id(),
+                writer_guid.entity_id(),
+                SequenceNumberSet::new(1, [1]),
+                1,
+            ),
+            reader_guid.prefix(),
+            &message_sender,
+        );
+        let submessages = received_submessages(&reader_socket);
+        assert!(submessages
+            .iter()
+            .any(|s| matches!(s, RtpsSubmessageReadKind::Data(d) if d.writer_sn() == 1)));
+    }
+
+    #[test]
+    fn heartbeat_is_only_sent_in_response_to_non_final_acknack() {
+        let (reader_socket, reader_locator) = local_socket_and_locator();
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 1], 2));
+        let reader_guid = Guid::new([2; 12], EntityId::new([0, 0, 1], 7));
+        let mut writer = RtpsStatefulWriter::new(
+            writer_guid,
+            1000,
+            RetransmissionBackoff::default(),
+            1,
+            None,
+            None,
+            None,
+        );
+        writer.add_matched_reader(&ReaderProxy {
+            remote_reader_guid: reader_guid,
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::TransientLocal,
+            unicast_locator_list: vec![reader_locator],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
+        });
+        writer.add_change(
+            CacheChange {
+                kind: ChangeKind::Alive,
+                writer_guid,
+                sequence_number: 1,
+                source_timestamp: None,
+                instance_handle: None,
+                data_value: vec![1, 2, 3, 4].into(),
+            },
+            &message_sender,
+        );
+        received_submessages(&reader_socket);
+        let is_heartbeat =
+            |s: &RtpsSubmessageReadKind| matches!(s, RtpsSubmessageReadKind::Heartbeat(_));
+
+        writer.on_acknack_submessage_received(
+            &AckNackSubmessage::new(
+                true,
+                reader_guid.entity_id(),
+                writer_guid.entity_id(),
+                SequenceNumberSet::new(2, []),
+                1,
+            ),
+            reader_guid.prefix(),
+            &message_sender,
+        );
+        assert!(!received_submessages(&reader_socket)
+            .iter()
+            .any(is_heartbeat));
+
+        writer.on_acknack_submessage_received(
+            &AckNackSubmessage::new(
+                false,
+                reader_guid.entity_id(),
+                writer_guid.entity_id(),
+                SequenceNumberSet::new(2, []),
+                2,
+            ),
+            reader_guid.prefix(),
+            &message_sender,
+        );
+        assert!(received_submessages(&reader_socket)
+            .iter()
+            .any(is_heartbeat));
+    }
+
+    #[test]
+    fn data_is_fragmented_to_fit_the_mtu_of_the_reader() {
+        let (reader_socket, reader_locator) = local_socket_and_locator();
+        let mut message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        message_sender.set_path_mtu(
+            false,
+            vec![DestinationMtu {
+                address: [127, 0, 0, 1].into(),
+                mtu: 600,
+            }],
+        );
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 1], 3));
+        let mut writer = RtpsStatefulWriter::new(
+            writer_guid,
+            1000,
+            RetransmissionBackoff::default(),
+            1,
+            None,
+            None,
+            None,
+        );
+        writer.add_matched_reader(&ReaderProxy {
+            remote_reader_guid: Guid::new([2; 12], EntityId::new([0, 0, 1], 4)),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::BestEffort,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![reader_locator],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
+        });
+
+        writer.add_change(
+            CacheChange {
+                kind: ChangeKind::Alive,
+                writer_guid,
+                sequence_number: 1,
+                source_timestamp: None,
+                instance_handle: None,
+                data_value: vec![0; 1000].into(),
+            },
+            &message_sender,
+        );
+
+        // The messages are limited to the MTU without the IP and UDP headers
+        let mut buf = [0; 2048];
+        let mut fragment_size_list = Vec::new();
+        while let Ok(len) = reader_socket.recv(&mut buf) {
+            assert!(len <= 572);
+            for submessage in RtpsMessageRead::try_from(&buf[..len])
+                .unwrap()
+                .submessages()
+            {
+                if let RtpsSubmessageReadKind::DataFrag(d) = submessage {
+                    fragment_size_list.push(d.fragment_size());
+                }
+            }
+        }
+        assert_eq!(fragment_size_list, vec![465, 465, 465]);
+    }
+
+    #[test]
+    fn fragments_are_announced_with_heartbeat_frag_and_repaired_individually() {
+        let (reader_socket, reader_locator) = local_socket_and_locator();
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 1], 2));
+        let reader_guid = Guid::new([2; 12], EntityId::new([0, 0, 1], 7));
+        let mut writer = RtpsStatefulWriter::new(
+            writer_guid,
+            4,
+            RetransmissionBackoff::default(),
+            1,
+            None,
+            None,
+            None,
+        );
+        writer.add_matched_reader(&ReaderProxy {
+            remote_reader_guid: reader_guid,
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![reader_locator],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
+        });
+
+        writer.add_change(
+            CacheChange {
+                kind: ChangeKind::Alive,
+                writer_guid,
+                sequence_number: 1,
+                source_timestamp: None,
+                instance_handle: None,
+                data_value: vec![0; 12].into(),
+            },
+            &message_sender,
+        );
+        let submessages = received_submessages(&reader_socket);
+        let fragment_number_list: Vec<_> = submessages
+            .iter()
+            .filter_map(|s| match s {
+                RtpsSubmessageReadKind::DataFrag(d) => Some(d.fragment_starting_num()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(fragment_number_list, vec![1, 2, 3]);
+        assert!(submessages.iter().any(|s| matches!(
+            s,
+            RtpsSubmessageReadKind::HeartbeatFrag(h) if h.writer_sn() == 1 && h.last_fragment_num() == 3
+        )));
+
+        writer.on_nack_frag_submessage_received(
+            &NackFragSubmessage::new(
+                reader_guid.entity_id(),
+                writer_guid.entity_id(),
+                1,
+                FragmentNumberSet::new(2, [2]),
+                1,
+            ),
+            reader_guid.prefix(),
+            &message_sender,
+        );
+        let fragment_number_list: Vec<_> = received_submessages(&reader_socket)
+            .iter()
+            .filter_map(|s| match s {
+                RtpsSubmessageReadKind::DataFrag(d) => Some(d.fragment_starting_num()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(fragment_number_list, vec![2]);
+    }
+
+    #[test]
+    fn acknowledgment_callback_called_when_change_acknowledged() {
+        let (_reader_socket, reader_locator) = local_socket_and_locator();
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 1], 2));
+        let reader_guid = Guid::new([2; 12], EntityId::new([0, 0, 1], 7));
+        let mut writer = RtpsStatefulWriter::new(
+            writer_guid,
+            1000,
+            RetransmissionBackoff::default(),
+            1,
+            None,
+            None,
+            None,
+        );
+        writer.add_matched_reader(&ReaderProxy {
+            remote_reader_guid: reader_guid,
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![reader_locator],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
+        });
+        writer.add_change(
+            CacheChange {
+                kind: ChangeKind::Alive,
+                writer_guid,
+                sequence_number: 1,
+                source_timestamp: None,
+                instance_handle: None,
+                data_value: vec![1, 2, 3, 4].into(),
+            },
+            &message_sender,
+        );
+
+        let acknowledged = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let acknowledged_clone = acknowledged.clone();
+        writer.add_acknowledgment_callback(
+            1,
+            Box::new(move || acknowledged_clone.store(true, std::sync::atomic::Ordering::SeqCst)),
+        );
+        assert!(!acknowledged.load(std::sync::atomic::Ordering::SeqCst));
+
+        writer.on_acknack_submessage_received(
+            &AckNackSubmessage::new(
+                true,
+                reader_guid.entity_id(),
+                writer_guid.entity_id(),
+                SequenceNumberSet::new(2, []),
+                1,
+            ),
+            reader_guid.prefix(),
+            &message_sender,
+        );
+        assert!(acknowledged.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[test]
+    fn changes_not_passing_reader_filter_are_sent_as_gap() {
+        let (reader_socket, reader_locator) = local_socket_and_locator();
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 1], 2));
+        let mut writer = RtpsStatefulWriter::new(
+            writer_guid,
+            1000,
+            RetransmissionBackoff::default(),
+            1,
+            None,
+            None,
+            None,
+        );
+        writer.add_matched_reader(&ReaderProxy {
+            remote_reader_guid: Guid::new([2; 12], EntityId::new([0, 0, 1], 7)),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![reader_locator],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: Some(ChangeFilter::new(|cc| cc.data_value()[4] == 1)),
+        });
+
+        for (sequence_number, value) in [(1, 1), (2, 2)] {
+            writer.add_change(
+                CacheChange {
+                    kind: ChangeKind::Alive,
+                    writer_guid,
+                    sequence_number,
+                    source_timestamp: None,
+                    instance_handle: None,
+                    data_value: vec![0, 1, 0, 0, value, 0, 0, 0].into(),
+                },
+                &message_sender,
+            );
+        }
+
+        let submessages = received_submessages(&reader_socket);
+        assert!(submessages
+            .iter()
+            .any(|s| matches!(s, RtpsSubmessageReadKind::Data(d) if d.writer_sn() == 1)));
+        assert!(!submessages
+            .iter()
+            .any(|s| matches!(s, RtpsSubmessageReadKind::Data(d) if d.writer_sn() == 2)));
+        assert!(submessages
+            .iter()
+            .any(|s| matches!(s, RtpsSubmessageReadKind::Gap(g) if g.gap_start() == 2)));
+    }
+
+    #[test]
+    fn historical_changes_beyond_limit_keep_most_recent_changes() {
+        let writer_guid = Guid::new([1; 12], EntityId::new([0, 0, 1], 2));
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let recent_timestamp = Time::new(now.as_secs() as i32, 0);
+        let mut changes = WriterHistoryCache::new();
+        for cache_change in [
+            (1, [1; 16], Time::new(0, 0)),
+            (2, [1; 16], recent_timestamp),
+            (3, [2; 16], recent_timestamp),
+            (4, [1; 16], recent_timestamp),
+            (5, [1; 16], recent_timestamp),
+        ]
+        .into_iter()
+        .map(
+            |(sequence_number, instance_handle, timestamp)| CacheChange {
+                kind: ChangeKind::Alive,
+                writer_guid,
+                sequence_number,
+                source_timestamp: Some(timestamp),
+                instance_handle: Some(instance_handle),
+                data_value: vec![1, 2, 3, 4].into(),
+            },
+        ) {
+            changes.add_change(cache_change);
+        }
+
+        let depth_limit = HistoricalDataLimit {
+            depth: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            historical_changes_beyond_limit(&changes, &depth_limit),
+            HashSet::from([1, 2])
+        );
+
+        let max_samples_limit = HistoricalDataLimit {
+            max_samples: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            historical_changes_beyond_limit(&changes, &max_samples_limit),
+            HashSet::from([1, 2, 3])
+        );
+
+        let max_age_limit = HistoricalDataLimit {
+            max_age: Some(core::time::Duration::from_secs(60)),
+            ..Default::default()
+        };
+        assert_eq!(
+            historical_changes_beyond_limit(&changes, &max_age_limit),
+            HashSet::from([1])
+        );
+
+        assert!(
+            historical_changes_beyond_limit(&changes, &HistoricalDataLimit::default()).is_empty()
+        );
     }
 }
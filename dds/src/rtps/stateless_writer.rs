@@ -42,8 +42,10 @@ impl RtpsStatelessWriter {
     }
 
     pub fn reader_locator_add(&mut self, locator: Locator) {
-        self.reader_locators
-            .push(RtpsReaderLocator::new(locator, false));
+        if !self.reader_locators.iter().any(|x| x.locator() == locator) {
+            self.reader_locators
+                .push(RtpsReaderLocator::new(locator, false));
+        }
     }
 
     pub fn reader_locator_remove(&mut self, locator: Locator) {
@@ -77,7 +79,7 @@ impl RtpsStatelessWriter {
                     );
 
                     message_sender.write_message(
-                        &[info_ts_submessage, data_submessage],
+                        vec![info_ts_submessage, data_submessage],
                         vec![reader_locator.locator()],
                     );
                 } else {
@@ -88,7 +90,8 @@ impl RtpsStatelessWriter {
                         SequenceNumberSet::new(unsent_change_seq_num + 1, []),
                     ));
 
-                    message_sender.write_message(&[gap_submessage], vec![reader_locator.locator()]);
+                    message_sender
+                        .write_message(vec![gap_submessage], vec![reader_locator.locator()]);
                 }
                 reader_locator.set_highest_sent_change_sn(unsent_change_seq_num);
             }
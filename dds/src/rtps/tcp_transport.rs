@@ -0,0 +1,867 @@
+use core::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use network_interface::Addr;
+use tracing::info;
+
+use crate::{
+    domain::domain_participant_factory::DomainId,
+    infrastructure::error::DdsError,
+    rtps::participant::{self, RtpsParticipant},
+    runtime::actor::ActorAddress,
+    transport::types::{Locator, LOCATOR_KIND_TCP_V4},
+};
+
+use super::{
+    error::{RtpsError, RtpsErrorKind, RtpsResult},
+    messages::overall_structure::RtpsMessageRead,
+};
+
+// The RTPS messages are sent over a TCP connection as frames made of a header followed by the
+// message. The header holds the "RTCP" magic, the length of the frame including the header, a
+// CRC of the message which is not computed (always 0) and the logical port the message is
+// addressed to. The logical port 0 carries the control messages used to manage the connection.
+pub const TCP_HEADER_SIZE: usize = 14;
+const TCP_HEADER_MAGIC: [u8; 4] = *b"RTCP";
+pub const LOGICAL_PORT_CONTROL: u16 = 0;
+const MAX_FRAME_SIZE: usize = 1 << 20;
+
+// Messages kept for a connection which is being opened. Above this limit the messages are
+// dropped and left to the retransmissions of the RTPS protocol.
+const MAX_PENDING_MESSAGES: usize = 64;
+const CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+const WRITE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+const PB: i32 = 7400;
+const DG: i32 = 250;
+#[allow(non_upper_case_globals)]
+const d1: i32 = 10;
+#[allow(non_upper_case_globals)]
+const d3: i32 = 11;
+
+// The participants listen on a single physical TCP port and distinguish the metatraffic from
+// the user-defined traffic with the logical port, which uses the unicast port mapping of UDP
+pub fn logical_port_metatraffic(domain_id: DomainId) -> u16 {
+    (PB + DG * domain_id + d1) as u16
+}
+
+pub fn logical_port_user(domain_id: DomainId) -> u16 {
+    (PB + DG * domain_id + d3) as u16
+}
+
+// The port of a TCPv4 locator holds the physical port in the lower 16 bits and the logical port
+// in the upper 16 bits
+pub fn tcp_locator(address: Ipv4Addr, physical_port: u16, logical_port: u16) -> Locator {
+    let mut locator_address = [0; 16];
+    locator_address[12..].copy_from_slice(&address.octets());
+    Locator::new(
+        LOCATOR_KIND_TCP_V4,
+        ((logical_port as u32) << 16) | physical_port as u32,
+        locator_address,
+    )
+}
+
+fn locator_socket_address(locator: &Locator) -> Option<SocketAddr> {
+    if locator.kind() == LOCATOR_KIND_TCP_V4 {
+        let a = locator.address();
+        Some(SocketAddr::V4(SocketAddrV4::new(
+            Ipv4Addr::new(a[12], a[13], a[14], a[15]),
+            locator.port() as u16,
+        )))
+    } else {
+        None
+    }
+}
+
+fn locator_logical_port(locator: &Locator) -> u16 {
+    (locator.port() >> 16) as u16
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TcpHeader {
+    pub length: u32,
+    pub logical_port: u16,
+}
+
+impl TcpHeader {
+    pub fn to_bytes(self) -> [u8; TCP_HEADER_SIZE] {
+        let mut bytes = [0; TCP_HEADER_SIZE];
+        bytes[0..4].copy_from_slice(&TCP_HEADER_MAGIC);
+        bytes[4..8].copy_from_slice(&self.length.to_be_bytes());
+        bytes[12..14].copy_from_slice(&self.logical_port.to_be_bytes());
+        bytes
+    }
+
+    pub fn try_from_bytes(bytes: &[u8; TCP_HEADER_SIZE]) -> RtpsResult<Self> {
+        if bytes[0..4] != TCP_HEADER_MAGIC {
+            return Err(RtpsError::new(
+                RtpsErrorKind::InvalidData,
+                "RTCP magic not found in TCP header",
+            ));
+        }
+        let length = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        if (length as usize) < TCP_HEADER_SIZE || length as usize > MAX_FRAME_SIZE {
+            return Err(RtpsError::new(
+                RtpsErrorKind::InvalidData,
+                format!("Invalid TCP frame length {}", length),
+            ));
+        }
+        Ok(Self {
+            length,
+            logical_port: u16::from_be_bytes([bytes[12], bytes[13]]),
+        })
+    }
+}
+
+pub fn frame(logical_port: u16, message: &[u8]) -> Vec<u8> {
+    let header = TcpHeader {
+        length: (TCP_HEADER_SIZE + message.len()) as u32,
+        logical_port,
+    };
+    let mut frame = Vec::with_capacity(TCP_HEADER_SIZE + message.len());
+    frame.extend_from_slice(&header.to_bytes());
+    frame.extend_from_slice(message);
+    frame
+}
+
+const BIND_CONNECTION_REQUEST: u8 = 0xd1;
+const OPEN_LOGICAL_PORT_REQUEST: u8 = 0xd2;
+const KEEP_ALIVE_REQUEST: u8 = 0xd4;
+const UNBIND_CONNECTION_REQUEST: u8 = 0xd5;
+const BIND_CONNECTION_RESPONSE: u8 = 0xe1;
+const OPEN_LOGICAL_PORT_RESPONSE: u8 = 0xe2;
+const KEEP_ALIVE_RESPONSE: u8 = 0xe4;
+
+const CONTROL_HEADER_SIZE: usize = 4;
+const LOCATOR_SIZE: usize = 24;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ControlResult {
+    Ok,
+    Rejected,
+}
+
+impl ControlResult {
+    fn to_u16(self) -> u16 {
+        match self {
+            ControlResult::Ok => 0,
+            ControlResult::Rejected => 1,
+        }
+    }
+
+    fn from_u16(value: u16) -> Self {
+        match value {
+            0 => ControlResult::Ok,
+            _ => ControlResult::Rejected,
+        }
+    }
+}
+
+/// Messages sent on the control logical port to manage a TCP connection. The connecting side
+/// binds the connection to the locators on which it listens, which lets the accepting side send
+/// its messages for those locators through the same connection, and opens the logical ports it
+/// sends messages to.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ControlMessage {
+    BindConnectionRequest {
+        locator_list: Vec<Locator>,
+    },
+    BindConnectionResponse {
+        result: ControlResult,
+    },
+    OpenLogicalPortRequest {
+        logical_port: u16,
+    },
+    OpenLogicalPortResponse {
+        logical_port: u16,
+        result: ControlResult,
+    },
+    KeepAliveRequest,
+    KeepAliveResponse,
+    UnbindConnectionRequest,
+}
+
+impl ControlMessage {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        let kind = match self {
+            ControlMessage::BindConnectionRequest { locator_list } => {
+                body.extend_from_slice(&(locator_list.len() as u32).to_be_bytes());
+                for locator in locator_list {
+                    body.extend_from_slice(&locator.kind().to_be_bytes());
+                    body.extend_from_slice(&locator.port().to_be_bytes());
+                    body.extend_from_slice(&locator.address());
+                }
+                BIND_CONNECTION_REQUEST
+            }
+            ControlMessage::BindConnectionResponse { result } => {
+                body.extend_from_slice(&result.to_u16().to_be_bytes());
+                BIND_CONNECTION_RESPONSE
+            }
+            ControlMessage::OpenLogicalPortRequest { logical_port } => {
+                body.extend_from_slice(&logical_port.to_be_bytes());
+                OPEN_LOGICAL_PORT_REQUEST
+            }
+            ControlMessage::OpenLogicalPortResponse {
+                logical_port,
+                result,
+            } => {
+                body.extend_from_slice(&logical_port.to_be_bytes());
+                body.extend_from_slice(&result.to_u16().to_be_bytes());
+                OPEN_LOGICAL_PORT_RESPONSE
+            }
+            ControlMessage::KeepAliveRequest => KEEP_ALIVE_REQUEST,
+            ControlMessage::KeepAliveResponse => KEEP_ALIVE_RESPONSE,
+            ControlMessage::UnbindConnectionRequest => UNBIND_CONNECTION_REQUEST,
+        };
+        let mut bytes = Vec::with_capacity(CONTROL_HEADER_SIZE + body.len());
+        bytes.push(kind);
+        bytes.push(0);
+        bytes.extend_from_slice(&(body.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(&body);
+        bytes
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> RtpsResult<Self> {
+        let not_enough_data =
+            || RtpsError::new(RtpsErrorKind::NotEnoughData, "Control message too short");
+        let header = bytes
+            .get(..CONTROL_HEADER_SIZE)
+            .ok_or_else(not_enough_data)?;
+        let body_length = u16::from_be_bytes([header[2], header[3]]) as usize;
+        let body = bytes
+            .get(CONTROL_HEADER_SIZE..CONTROL_HEADER_SIZE + body_length)
+            .ok_or_else(not_enough_data)?;
+        let read_u16 = |offset: usize| {
+            body.get(offset..offset + 2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .ok_or_else(not_enough_data)
+        };
+        match header[0] {
+            BIND_CONNECTION_REQUEST => {
+                let count = body.get(0..4).ok_or_else(not_enough_data)?;
+                let count = u32::from_be_bytes([count[0], count[1], count[2], count[3]]) as usize;
+                let locators = body.get(4..).unwrap_or_default();
+                if locators.len() < count.saturating_mul(LOCATOR_SIZE) {
+                    return Err(not_enough_data());
+                }
+                let locator_list = locators
+                    .chunks_exact(LOCATOR_SIZE)
+                    .take(count)
+                    .map(|l| {
+                        let mut address = [0; 16];
+                        address.copy_from_slice(&l[8..24]);
+                        Locator::new(
+                            i32::from_be_bytes([l[0], l[1], l[2], l[3]]),
+                            u32::from_be_bytes([l[4], l[5], l[6], l[7]]),
+                            address,
+                        )
+                    })
+                    .collect();
+                Ok(ControlMessage::BindConnectionRequest { locator_list })
+            }
+            BIND_CONNECTION_RESPONSE => Ok(ControlMessage::BindConnectionResponse {
+                result: ControlResult::from_u16(read_u16(0)?),
+            }),
+            OPEN_LOGICAL_PORT_REQUEST => Ok(ControlMessage::OpenLogicalPortRequest {
+                logical_port: read_u16(0)?,
+            }),
+            OPEN_LOGICAL_PORT_RESPONSE => Ok(ControlMessage::OpenLogicalPortResponse {
+                logical_port: read_u16(0)?,
+                result: ControlResult::from_u16(read_u16(2)?),
+            }),
+            KEEP_ALIVE_REQUEST => Ok(ControlMessage::KeepAliveRequest),
+            KEEP_ALIVE_RESPONSE => Ok(ControlMessage::KeepAliveResponse),
+            UNBIND_CONNECTION_REQUEST => Ok(ControlMessage::UnbindConnectionRequest),
+            kind => Err(RtpsError::new(
+                RtpsErrorKind::InvalidData,
+                format!("Unknown TCP control message kind {:#x}", kind),
+            )),
+        }
+    }
+}
+
+// Logical port and message kept until the connection is opened
+type PendingMessage = (u16, Vec<u8>);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum LogicalPortState {
+    Requested,
+    Open,
+    Rejected,
+}
+
+struct TcpConnection {
+    stream: Mutex<TcpStream>,
+    logical_port_list: Mutex<HashMap<u16, LogicalPortState>>,
+}
+
+impl TcpConnection {
+    fn new(stream: TcpStream) -> Self {
+        Self {
+            stream: Mutex::new(stream),
+            logical_port_list: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn write_frame(&self, logical_port: u16, message: &[u8]) -> std::io::Result<()> {
+        self.stream
+            .lock()
+            .expect("Mutex must not be poisoned")
+            .write_all(&frame(logical_port, message))
+    }
+
+    fn write_control_message(&self, control_message: &ControlMessage) -> std::io::Result<()> {
+        self.write_frame(LOGICAL_PORT_CONTROL, &control_message.to_bytes())
+    }
+
+    fn shutdown(&self) {
+        self.stream
+            .lock()
+            .expect("Mutex must not be poisoned")
+            .shutdown(Shutdown::Both)
+            .ok();
+    }
+}
+
+fn configure_stream(stream: &TcpStream) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_nodelay(true)?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    socket2::SockRef::from(stream).set_keepalive(true)
+}
+
+/// Transport of the RTPS messages over TCP. Each participant accepts connections on a single
+/// physical port and the connections are shared by all the locators of the same remote address,
+/// both the connections opened by this participant and the ones accepted and bound by the remote
+/// participants. This lets a participant which can only open outgoing connections, for example
+/// behind a NAT or a firewall, receive all its messages through the connections it opened.
+pub struct TcpTransport {
+    metatraffic_logical_port: u16,
+    user_logical_port: u16,
+    metatraffic_unicast_locator_list: Vec<Locator>,
+    default_unicast_locator_list: Vec<Locator>,
+    connection_list: Mutex<HashMap<SocketAddr, Arc<TcpConnection>>>,
+    pending_connection_list: Mutex<HashMap<SocketAddr, Vec<PendingMessage>>>,
+    rtps_participant_address: ActorAddress<RtpsParticipant>,
+    max_parameter_list_length: usize,
+    // Each connection is read by a thread of its own, so the number of open connections is bounded
+    max_connections: usize,
+    connection_count: AtomicUsize,
+}
+
+impl TcpTransport {
+    pub fn new(
+        domain_id: DomainId,
+        port: u16,
+        interface_address_list: impl IntoIterator<Item = Addr>,
+        rtps_participant_address: ActorAddress<RtpsParticipant>,
+        max_parameter_list_length: usize,
+        max_connections: usize,
+    ) -> RtpsResult<Arc<Self>> {
+        let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)))?;
+        // The listener is polled so that the thread stops shortly after the transport is dropped
+        listener.set_nonblocking(true)?;
+        let physical_port = listener.local_addr()?.port();
+        let interface_ip_list: Vec<_> = interface_address_list
+            .into_iter()
+            .filter_map(|a| match a {
+                Addr::V4(v4) => Some(v4.ip),
+                Addr::V6(_) => None,
+            })
+            .collect();
+        let metatraffic_logical_port = logical_port_metatraffic(domain_id);
+        let user_logical_port = logical_port_user(domain_id);
+        let metatraffic_unicast_locator_list = interface_ip_list
+            .iter()
+            .map(|&ip| tcp_locator(ip, physical_port, metatraffic_logical_port))
+            .collect();
+        let default_unicast_locator_list = interface_ip_list
+            .iter()
+            .map(|&ip| tcp_locator(ip, physical_port, user_logical_port))
+            .collect();
+
+        let tcp_transport = Arc::new(Self {
+            metatraffic_logical_port,
+            user_logical_port,
+            metatraffic_unicast_locator_list,
+            default_unicast_locator_list,
+            connection_list: Mutex::new(HashMap::new()),
+            pending_connection_list: Mutex::new(HashMap::new()),
+            rtps_participant_address,
+            max_parameter_list_length,
+            max_connections,
+            connection_count: AtomicUsize::new(0),
+        });
+
+        let weak_tcp_transport = Arc::downgrade(&tcp_transport);
+        std::thread::Builder::new()
+            .name("RTPS TCP listener".to_string())
+            .spawn(move || loop {
+                let accept_result = listener.accept();
+                let Some(tcp_transport) = weak_tcp_transport.upgrade() else {
+                    break;
+                };
+                match accept_result {
+                    Ok((stream, _)) => {
+                        if configure_stream(&stream).is_ok() {
+                            tcp_transport.receive_connection(stream);
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        drop(tcp_transport);
+                        std::thread::sleep(std::time::Duration::from_millis(50));
+                    }
+                    Err(e) => info!("Failed to accept TCP connection with error {}", e),
+                }
+            })?;
+
+        Ok(tcp_transport)
+    }
+
+    pub fn metatraffic_unicast_locator_list(&self) -> &[Locator] {
+        &self.metatraffic_unicast_locator_list
+    }
+
+    pub fn default_unicast_locator_list(&self) -> &[Locator] {
+        &self.default_unicast_locator_list
+    }
+
+    pub fn write_message<'a>(
+        self: &Arc<Self>,
+        destination_locator: &Locator,
+        message_list: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        let Some(socket_address) = locator_socket_address(destination_locator) else {
+            return;
+        };
+        let logical_port = locator_logical_port(destination_locator);
+
+        let connection = self
+            .connection_list
+            .lock()
+            .expect("Mutex must not be poisoned")
+            .get(&socket_address)
+            .cloned();
+        match connection {
+            Some(connection) => {
+                // The messages are sent right after requesting to open the logical port without
+                // waiting for the response, and no longer sent once the port is rejected
+                let logical_port_state = {
+                    let mut logical_port_list = connection
+                        .logical_port_list
+                        .lock()
+                        .expect("Mutex must not be poisoned");
+                    let logical_port_state = logical_port_list.get(&logical_port).copied();
+                    if logical_port_state.is_none() {
+                        logical_port_list.insert(logical_port, LogicalPortState::Requested);
+                    }
+                    logical_port_state
+                };
+                let result = match logical_port_state {
+                    Some(LogicalPortState::Rejected) => Ok(()),
+                    Some(LogicalPortState::Requested | LogicalPortState::Open) => message_list
+                        .into_iter()
+                        .try_for_each(|m| connection.write_frame(logical_port, m)),
+                    None => connection
+                        .write_control_message(&ControlMessage::OpenLogicalPortRequest {
+                            logical_port,
+                        })
+                        .and_then(|_| {
+                            message_list
+                                .into_iter()
+                                .try_for_each(|m| connection.write_frame(logical_port, m))
+                        }),
+                };
+                if result.is_err() {
+                    self.remove_connection(&connection);
+                }
+            }
+            None => {
+                let mut pending_connection_list = self
+                    .pending_connection_list
+                    .lock()
+                    .expect("Mutex must not be poisoned");
+                let is_connecting = pending_connection_list.contains_key(&socket_address);
+                let pending_message_list =
+                    pending_connection_list.entry(socket_address).or_default();
+                for message in message_list {
+                    if pending_message_list.len() < MAX_PENDING_MESSAGES {
+                        pending_message_list.push((logical_port, message.to_vec()));
+                    }
+                }
+                if !is_connecting {
+                    let tcp_transport = self.clone();
+                    let spawn_result = std::thread::Builder::new()
+                        .name("RTPS TCP connect".to_string())
+                        .spawn(move || tcp_transport.connect(socket_address));
+                    if let Err(e) = spawn_result {
+                        info!(
+                            "Failed to spawn thread to open TCP connection to {} with error {}",
+                            socket_address, e
+                        );
+                        pending_connection_list.remove(&socket_address);
+                    }
+                }
+            }
+        }
+    }
+
+    fn connect(self: Arc<Self>, socket_address: SocketAddr) {
+        let connection = TcpStream::connect_timeout(&socket_address, CONNECT_TIMEOUT)
+            .and_then(|stream| configure_stream(&stream).map(|_| stream))
+            .and_then(|stream| {
+                let connection = Arc::new(TcpConnection::new(stream.try_clone()?));
+                connection.write_control_message(&ControlMessage::BindConnectionRequest {
+                    locator_list: self.bind_locator_list(),
+                })?;
+                Ok((connection, stream))
+            });
+        let pending_message_list = self
+            .pending_connection_list
+            .lock()
+            .expect("Mutex must not be poisoned")
+            .remove(&socket_address)
+            .unwrap_or_default();
+        match connection {
+            Ok((connection, stream)) => {
+                self.connection_list
+                    .lock()
+                    .expect("Mutex must not be poisoned")
+                    .insert(socket_address, connection.clone());
+                if !self.spawn_receive_thread(connection.clone(), stream) {
+                    self.remove_connection(&connection);
+                    return;
+                }
+                let mut logical_port_list = HashSet::new();
+                for (logical_port, message) in pending_message_list {
+                    if logical_port_list.insert(logical_port) {
+                        connection
+                            .logical_port_list
+                            .lock()
+                            .expect("Mutex must not be poisoned")
+                            .insert(logical_port, LogicalPortState::Requested);
+                        connection
+                            .write_control_message(&ControlMessage::OpenLogicalPortRequest {
+                                logical_port,
+                            })
+                            .ok();
+                    }
+                    connection.write_frame(logical_port, &message).ok();
+                }
+            }
+            Err(e) => info!(
+                "Failed to open TCP connection to {} with error {}",
+                socket_address, e
+            ),
+        }
+    }
+
+    fn bind_locator_list(&self) -> Vec<Locator> {
+        self.metatraffic_unicast_locator_list
+            .iter()
+            .chain(self.default_unicast_locator_list.iter())
+            .copied()
+            .collect()
+    }
+
+    fn receive_connection(self: &Arc<Self>, stream: TcpStream) {
+        if let Ok(write_stream) = stream.try_clone() {
+            self.spawn_receive_thread(Arc::new(TcpConnection::new(write_stream)), stream);
+        }
+    }
+
+    // Returns whether the connection is kept. Connections above the limit or whose thread can not
+    // be spawned are closed.
+    fn spawn_receive_thread(
+        self: &Arc<Self>,
+        connection: Arc<TcpConnection>,
+        mut stream: TcpStream,
+    ) -> bool {
+        if self.connection_count.fetch_add(1, Ordering::Relaxed) >= self.max_connections {
+            self.connection_count.fetch_sub(1, Ordering::Relaxed);
+            info!(
+                "Closing TCP connection above the limit of {} connections",
+                self.max_connections
+            );
+            connection.shutdown();
+            return false;
+        }
+        // The connections do not keep the transport alive so that they are closed when it is dropped
+        let weak_tcp_transport = Arc::downgrade(self);
+        let thread_connection = connection.clone();
+        let spawn_result = std::thread::Builder::new()
+            .name("RTPS TCP connection".to_string())
+            .spawn(move || {
+                let connection = thread_connection;
+                let mut header = [0; TCP_HEADER_SIZE];
+                loop {
+                    if stream.read_exact(&mut header).is_err() {
+                        break;
+                    }
+                    // The framing is lost after an invalid header so the connection is closed
+                    let Ok(header) = TcpHeader::try_from_bytes(&header) else {
+                        break;
+                    };
+                    let mut message = vec![0; header.length as usize - TCP_HEADER_SIZE];
+                    if stream.read_exact(&mut message).is_err() {
+                        break;
+                    }
+                    let Some(tcp_transport) = weak_tcp_transport.upgrade() else {
+                        break;
+                    };
+                    let keep_connection = if header.logical_port == LOGICAL_PORT_CONTROL {
+                        match ControlMessage::try_from_bytes(&message) {
+                            Ok(control_message) => {
+                                tcp_transport.process_control_message(&connection, control_message)
+                            }
+                            Err(_) => true,
+                        }
+                    } else {
+                        tcp_transport.process_rtps_message(header.logical_port, &message)
+                    };
+                    if !keep_connection {
+                        break;
+                    }
+                }
+                if let Some(tcp_transport) = weak_tcp_transport.upgrade() {
+                    tcp_transport.remove_connection(&connection);
+                    tcp_transport
+                        .connection_count
+                        .fetch_sub(1, Ordering::Relaxed);
+                }
+                connection.shutdown();
+            });
+        match spawn_result {
+            Ok(_) => true,
+            Err(e) => {
+                info!("Failed to spawn thread for TCP connection with error {}", e);
+                self.connection_count.fetch_sub(1, Ordering::Relaxed);
+                connection.shutdown();
+                false
+            }
+        }
+    }
+
+    fn process_control_message(
+        &self,
+        connection: &Arc<TcpConnection>,
+        control_message: ControlMessage,
+    ) -> bool {
+        let reply = match control_message {
+            ControlMessage::BindConnectionRequest { locator_list } => {
+                let mut connection_list = self
+                    .connection_list
+                    .lock()
+                    .expect("Mutex must not be poisoned");
+                for socket_address in locator_list.iter().filter_map(locator_socket_address) {
+                    connection_list
+                        .entry(socket_address)
+                        .or_insert_with(|| connection.clone());
+                }
+                Some(ControlMessage::BindConnectionResponse {
+                    result: ControlResult::Ok,
+                })
+            }
+            ControlMessage::OpenLogicalPortRequest { logical_port } => {
+                let result = if logical_port == self.metatraffic_logical_port
+                    || logical_port == self.user_logical_port
+                {
+                    ControlResult::Ok
+                } else {
+                    ControlResult::Rejected
+                };
+                Some(ControlMessage::OpenLogicalPortResponse {
+                    logical_port,
+                    result,
+                })
+            }
+            ControlMessage::OpenLogicalPortResponse {
+                logical_port,
+                result,
+            } => {
+                let logical_port_state = match result {
+                    ControlResult::Ok => LogicalPortState::Open,
+                    ControlResult::Rejected => LogicalPortState::Rejected,
+                };
+                connection
+                    .logical_port_list
+                    .lock()
+                    .expect("Mutex must not be poisoned")
+                    .insert(logical_port, logical_port_state);
+                None
+            }
+            ControlMessage::KeepAliveRequest => Some(ControlMessage::KeepAliveResponse),
+            ControlMessage::BindConnectionResponse { .. } | ControlMessage::KeepAliveResponse => {
+                None
+            }
+            ControlMessage::UnbindConnectionRequest => return false,
+        };
+        match reply {
+            Some(reply) => connection.write_control_message(&reply).is_ok(),
+            None => true,
+        }
+    }
+
+    fn process_rtps_message(&self, logical_port: u16, message: &[u8]) -> bool {
+        let Ok(rtps_message) =
+            RtpsMessageRead::try_from_bytes(message, self.max_parameter_list_length)
+        else {
+            return true;
+        };
+        let r = if logical_port == self.metatraffic_logical_port {
+            tracing::trace!(
+                rtps_message = ?rtps_message,
+                "Received metatraffic TCP RTPS message"
+            );
+            self.rtps_participant_address
+                .send_actor_mail(participant::ProcessBuiltinRtpsMessage { rtps_message })
+                .map(|_| ())
+        } else if logical_port == self.user_logical_port {
+            tracing::trace!(
+                rtps_message = ?rtps_message,
+                "Received user defined data TCP RTPS message"
+            );
+            self.rtps_participant_address
+                .send_actor_mail(participant::ProcessUserDefinedRtpsMessage { rtps_message })
+                .map(|_| ())
+        } else {
+            Ok(())
+        };
+        // The connection is kept when the message is dropped by a full mailbox
+        !matches!(r, Err(DdsError::AlreadyDeleted))
+    }
+
+    fn remove_connection(&self, connection: &Arc<TcpConnection>) {
+        self.connection_list
+            .lock()
+            .expect("Mutex must not be poisoned")
+            .retain(|_, c| !Arc::ptr_eq(c, connection));
+    }
+}
+
+impl Drop for TcpTransport {
+    fn drop(&mut self) {
+        for connection in self
+            .connection_list
+            .get_mut()
+            .expect("Mutex must not be poisoned")
+            .values()
+        {
+            connection
+                .write_control_message(&ControlMessage::UnbindConnectionRequest)
+                .ok();
+            connection.shutdown();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tcp_header_roundtrip() {
+        let header = TcpHeader {
+            length: 34,
+            logical_port: 7410,
+        };
+        let bytes = header.to_bytes();
+        assert_eq!(&bytes[0..4], b"RTCP");
+        assert_eq!(&bytes[8..12], &[0, 0, 0, 0]);
+        assert_eq!(TcpHeader::try_from_bytes(&bytes).unwrap(), header);
+    }
+
+    #[test]
+    fn tcp_header_without_magic_is_rejected() {
+        let mut bytes = TcpHeader {
+            length: 34,
+            logical_port: 7410,
+        }
+        .to_bytes();
+        bytes[0] = b'X';
+        assert!(TcpHeader::try_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn tcp_header_with_invalid_length_is_rejected() {
+        let too_short = TcpHeader {
+            length: 13,
+            logical_port: 7410,
+        }
+        .to_bytes();
+        let too_long = TcpHeader {
+            length: MAX_FRAME_SIZE as u32 + 1,
+            logical_port: 7410,
+        }
+        .to_bytes();
+        assert!(TcpHeader::try_from_bytes(&too_short).is_err());
+        assert!(TcpHeader::try_from_bytes(&too_long).is_err());
+    }
+
+    #[test]
+    fn frame_holds_header_and_message() {
+        let frame = frame(7411, &[1, 2, 3, 4]);
+        assert_eq!(
+            frame,
+            vec![b'R', b'T', b'C', b'P', 0, 0, 0, 18, 0, 0, 0, 0, 0x1c, 0xf3, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn control_message_roundtrip() {
+        let control_message_list = [
+            ControlMessage::BindConnectionRequest {
+                locator_list: vec![
+                    tcp_locator(Ipv4Addr::new(192, 168, 1, 2), 7000, 7410),
+                    tcp_locator(Ipv4Addr::new(192, 168, 1, 2), 7000, 7411),
+                ],
+            },
+            ControlMessage::BindConnectionResponse {
+                result: ControlResult::Ok,
+            },
+            ControlMessage::OpenLogicalPortRequest { logical_port: 7411 },
+            ControlMessage::OpenLogicalPortResponse {
+                logical_port: 7411,
+                result: ControlResult::Rejected,
+            },
+            ControlMessage::KeepAliveRequest,
+            ControlMessage::KeepAliveResponse,
+            ControlMessage::UnbindConnectionRequest,
+        ];
+        for control_message in control_message_list {
+            assert_eq!(
+                ControlMessage::try_from_bytes(&control_message.to_bytes()).unwrap(),
+                control_message
+            );
+        }
+    }
+
+    #[test]
+    fn truncated_bind_connection_request_is_rejected() {
+        let bytes = ControlMessage::BindConnectionRequest {
+            locator_list: vec![tcp_locator(Ipv4Addr::LOCALHOST, 7000, 7410)],
+        }
+        .to_bytes();
+        assert!(ControlMessage::try_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn tcp_locator_holds_physical_and_logical_port() {
+        let locator = tcp_locator(Ipv4Addr::new(10, 0, 0, 1), 7000, 7410);
+        assert_eq!(locator.kind(), LOCATOR_KIND_TCP_V4);
+        assert_eq!(
+            locator_socket_address(&locator),
+            Some(SocketAddr::from((Ipv4Addr::new(10, 0, 0, 1), 7000)))
+        );
+        assert_eq!(locator_logical_port(&locator), 7410);
+    }
+}
@@ -1,23 +1,30 @@
-use core::net::{Ipv4Addr, SocketAddr};
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 
 use network_interface::{Addr, NetworkInterface, NetworkInterfaceConfig};
 use socket2::Socket;
+use std::sync::Arc;
 use tracing::info;
 
 use crate::{
+    configuration::{
+        DeserializationLimits, DestinationMtu, FlowControl, IpVersion, RetransmissionBackoff,
+        TransportPriorityDscp, WriterBatching,
+    },
     domain::domain_participant_factory::DomainId,
+    infrastructure::error::DdsError,
     rtps::participant,
     runtime::{
-        actor::{Actor, ActorAddress, ActorBuilder},
+        actor::{Actor, ActorAddress, ActorBuilder, Mailbox},
         executor::{block_on, Executor},
+        timer::{TimerHandle, TimerTaskHandle},
     },
     transport::{
         history_cache::HistoryCache,
         participant::TransportParticipant,
         reader::{TransportStatefulReader, TransportStatelessReader, WriterProxy},
         types::{
-            EntityId, Guid, GuidPrefix, Locator, ProtocolVersion, ReliabilityKind, VendorId,
-            ENTITYID_PARTICIPANT, LOCATOR_KIND_UDP_V4,
+            EntityId, Guid, GuidPrefix, Locator, Long, ProtocolVersion, ReliabilityKind, VendorId,
+            ENTITYID_PARTICIPANT, LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6,
         },
         writer::{TransportStatefulWriter, TransportStatelessWriter},
     },
@@ -25,9 +32,11 @@ use crate::{
 
 use super::{
     error::{RtpsError, RtpsErrorKind, RtpsResult},
-    messages::overall_structure::RtpsMessageRead,
+    messages::overall_structure::{RtpsMessageRead, SubmessageCrypto},
     participant::RtpsParticipant,
+    tcp_transport::TcpTransport,
     types::{PROTOCOLVERSION, VENDOR_ID_S2E},
+    udp_batch::DatagramReceiver,
 };
 
 const MAX_DATAGRAM_SIZE: usize = 65507;
@@ -36,24 +45,100 @@ type LocatorAddress = [u8; 16];
 // As of 9.6.1.4.1  Default multicast address
 const DEFAULT_MULTICAST_LOCATOR_ADDRESS: LocatorAddress =
     [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 239, 255, 0, 1];
+// The standard only defines the IPv4 default multicast address. This is the same IPv6 address as
+// used by other implementations, with the IPv4 address in the last octets.
+const DEFAULT_MULTICAST_LOCATOR_ADDRESS_V6: LocatorAddress = [
+    0xff, 0x1e, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 239, 255, 0, 1,
+];
 
 const PB: i32 = 7400;
 const DG: i32 = 250;
 #[allow(non_upper_case_globals)]
 const d0: i32 = 0;
+const PG: i32 = 2;
+#[allow(non_upper_case_globals)]
+const d1: i32 = 10;
+#[allow(non_upper_case_globals)]
+const d2: i32 = 1;
+// Participant identifiers tried for the well-known metatraffic unicast port before falling back
+// to a port chosen by the OS
+const MAX_PARTICIPANT_ID: i32 = 119;
 fn port_builtin_multicast(domain_id: DomainId) -> u16 {
     (PB + DG * domain_id + d0) as u16
 }
 
+// None when the port is out of range for the domain and participant identifiers
+pub fn port_builtin_unicast(domain_id: DomainId, participant_id: i32) -> Option<u16> {
+    u16::try_from(PB + DG * domain_id + d1 + PG * participant_id).ok()
+}
+
+fn port_user_multicast(domain_id: DomainId) -> u16 {
+    (PB + DG * domain_id + d2) as u16
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AddressFamily {
+    V4,
+    V6,
+}
+
+impl AddressFamily {
+    fn list(ip_version: IpVersion) -> Vec<Self> {
+        let mut list = Vec::new();
+        if ip_version.is_v4_enabled() {
+            list.push(AddressFamily::V4);
+        }
+        if ip_version.is_v6_enabled() {
+            list.push(AddressFamily::V6);
+        }
+        list
+    }
+
+    fn contains(&self, address: &Addr) -> bool {
+        matches!(
+            (self, address),
+            (AddressFamily::V4, Addr::V4(_)) | (AddressFamily::V6, Addr::V6(_))
+        )
+    }
+
+    fn domain(&self) -> socket2::Domain {
+        match self {
+            AddressFamily::V4 => socket2::Domain::IPV4,
+            AddressFamily::V6 => socket2::Domain::IPV6,
+        }
+    }
+
+    fn unspecified_socket_address(&self, port: u16) -> SocketAddr {
+        match self {
+            AddressFamily::V4 => SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)),
+            AddressFamily::V6 => SocketAddr::from((Ipv6Addr::UNSPECIFIED, port)),
+        }
+    }
+
+    fn locator_kind(&self) -> Long {
+        match self {
+            AddressFamily::V4 => LOCATOR_KIND_UDP_V4,
+            AddressFamily::V6 => LOCATOR_KIND_UDP_V6,
+        }
+    }
+
+    fn default_multicast_locator_address(&self) -> LocatorAddress {
+        match self {
+            AddressFamily::V4 => DEFAULT_MULTICAST_LOCATOR_ADDRESS,
+            AddressFamily::V6 => DEFAULT_MULTICAST_LOCATOR_ADDRESS_V6,
+        }
+    }
+}
+
+// The IPv4 group is joined on each interface address and the IPv6 group on each interface index
 fn get_multicast_socket(
-    multicast_address: LocatorAddress,
+    address_family: AddressFamily,
     port: u16,
     interface_address_list: impl IntoIterator<Item = Addr>,
+    interface_v6_index_list: impl IntoIterator<Item = u32>,
 ) -> std::io::Result<std::net::UdpSocket> {
-    let socket_addr = SocketAddr::from((Ipv4Addr::UNSPECIFIED, port));
-
     let socket = Socket::new(
-        socket2::Domain::IPV4,
+        address_family.domain(),
         socket2::Type::DGRAM,
         Some(socket2::Protocol::UDP),
     )?;
@@ -61,43 +146,130 @@ fn get_multicast_socket(
     socket.set_reuse_address(true)?;
     #[cfg(target_family = "unix")]
     socket.set_reuse_port(true)?;
+    if address_family == AddressFamily::V6 {
+        socket.set_only_v6(true)?;
+    }
     socket.set_nonblocking(false)?;
     socket.set_read_timeout(Some(std::time::Duration::from_millis(50)))?;
 
-    socket.bind(&socket_addr.into())?;
-    let addr = Ipv4Addr::new(
-        multicast_address[12],
-        multicast_address[13],
-        multicast_address[14],
-        multicast_address[15],
-    );
-    for interface_addr in interface_address_list {
-        match interface_addr {
-            Addr::V4(a) => {
-                let r = socket.join_multicast_v4(&addr, &a.ip);
+    socket.bind(&address_family.unspecified_socket_address(port).into())?;
+    let multicast_address = address_family.default_multicast_locator_address();
+    match address_family {
+        AddressFamily::V4 => {
+            let addr = Ipv4Addr::new(
+                multicast_address[12],
+                multicast_address[13],
+                multicast_address[14],
+                multicast_address[15],
+            );
+            for interface_addr in interface_address_list {
+                if let Addr::V4(a) = interface_addr {
+                    let r = socket.join_multicast_v4(&addr, &a.ip);
+                    if let Err(e) = r {
+                        info!(
+                            "Failed to join multicast group on address {} with error {}",
+                            a.ip, e
+                        )
+                    }
+                }
+            }
+            socket.set_multicast_loop_v4(true)?;
+        }
+        AddressFamily::V6 => {
+            let addr = Ipv6Addr::from(multicast_address);
+            for interface_index in interface_v6_index_list {
+                let r = socket.join_multicast_v6(&addr, interface_index);
                 if let Err(e) = r {
                     info!(
-                        "Failed to join multicast group on address {} with error {}",
-                        a.ip, e
+                        "Failed to join multicast group on interface {} with error {}",
+                        interface_index, e
                     )
                 }
             }
-            Addr::V6(_) => (),
+            socket.set_multicast_loop_v6(true)?;
         }
     }
 
-    socket.set_multicast_loop_v4(true)?;
+    Ok(socket.into())
+}
+
+fn get_default_unicast_socket(
+    address_family: AddressFamily,
+    port: u16,
+    reuse_port: bool,
+    nonblocking: bool,
+    udp_receive_buffer_size: Option<usize>,
+) -> std::io::Result<std::net::UdpSocket> {
+    let socket = Socket::new(address_family.domain(), socket2::Type::DGRAM, None)?;
+    if reuse_port {
+        #[cfg(target_os = "linux")]
+        socket.set_reuse_port(true)?;
+    }
+    if address_family == AddressFamily::V6 {
+        socket.set_only_v6(true)?;
+    }
+    socket.bind(&address_family.unspecified_socket_address(port).into())?;
+    socket.set_nonblocking(nonblocking)?;
+    if let Some(buffer_size) = udp_receive_buffer_size {
+        socket.set_recv_buffer_size(buffer_size)?;
+    }
 
     Ok(socket.into())
 }
 
-pub fn read_message(
+// The datagrams which are not valid RTPS messages are dropped
+pub fn read_messages(
     socket: &mut std::net::UdpSocket,
-    buf: &mut [u8],
-) -> RtpsResult<RtpsMessageRead> {
-    let (bytes, _) = socket.recv_from(buf)?;
-    if bytes > 0 {
-        Ok(RtpsMessageRead::try_from(&buf[0..bytes])?)
+    datagram_receiver: &mut DatagramReceiver,
+    max_parameter_list_length: usize,
+) -> RtpsResult<Vec<RtpsMessageRead>> {
+    datagram_receiver.receive(socket)?;
+    Ok(datagram_receiver
+        .datagrams()
+        .filter_map(|d| parse_message(d, max_parameter_list_length).ok())
+        .collect())
+}
+
+pub fn read_messages_busy_poll(
+    socket: &mut std::net::UdpSocket,
+    datagram_receiver: &mut DatagramReceiver,
+    busy_poll_duration: std::time::Duration,
+    max_parameter_list_length: usize,
+) -> RtpsResult<Vec<RtpsMessageRead>> {
+    // The socket is non-blocking and it is polled in a spin loop to avoid the
+    // thread wake-up latency. Once the busy-poll duration is exceeded without
+    // receiving any data the thread is parked on a blocking read.
+    let busy_poll_start = std::time::Instant::now();
+    loop {
+        match datagram_receiver.receive(socket) {
+            Ok(()) => {
+                return Ok(datagram_receiver
+                    .datagrams()
+                    .filter_map(|d| parse_message(d, max_parameter_list_length).ok())
+                    .collect())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                if busy_poll_start.elapsed() < busy_poll_duration {
+                    std::hint::spin_loop();
+                } else {
+                    socket.set_nonblocking(false)?;
+                    let rtps_message_list =
+                        read_messages(socket, datagram_receiver, max_parameter_list_length);
+                    socket.set_nonblocking(true)?;
+                    return rtps_message_list;
+                }
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+fn parse_message(data: &[u8], max_parameter_list_length: usize) -> RtpsResult<RtpsMessageRead> {
+    if !data.is_empty() {
+        Ok(RtpsMessageRead::try_from_bytes(
+            data,
+            max_parameter_list_length,
+        )?)
     } else {
         Err(RtpsError::new(RtpsErrorKind::NotEnoughData, ""))
     }
@@ -110,6 +282,7 @@ pub struct RtpsTransport {
     default_multicast_locator_list: Vec<Locator>,
     metatraffic_unicast_locator_list: Vec<Locator>,
     metatraffic_multicast_locator_list: Vec<Locator>,
+    heartbeat_task: TimerTaskHandle,
     _executor: Executor,
 }
 
@@ -120,11 +293,33 @@ impl RtpsTransport {
         domain_id: DomainId,
         interface_name: Option<&str>,
         udp_receive_buffer_size: Option<usize>,
+        receive_busy_poll_duration: Option<std::time::Duration>,
+        receive_threads: usize,
+        max_message_size: usize,
+        path_mtu_discovery: bool,
+        destination_mtu_list: Vec<DestinationMtu>,
+        retransmission_backoff: RetransmissionBackoff,
+        samples_per_piggyback_heartbeat: usize,
+        heartbeat_response_jitter: std::time::Duration,
+        user_data_multicast_threshold: Option<usize>,
+        writer_batching: Option<WriterBatching>,
+        sedp_batching: Option<WriterBatching>,
+        flow_control: Option<FlowControl>,
+        transport_priority_dscp_mapping: Vec<TransportPriorityDscp>,
+        disable_multicast: bool,
+        deserialization_limits: DeserializationLimits,
+        tcp_port: Option<u16>,
+        tcp_max_connections: usize,
+        ip_version: IpVersion,
+        mailbox: Mailbox,
+        timer_handle: TimerHandle,
     ) -> RtpsResult<Self> {
         let executor = Executor::new();
+        let max_parameter_list_length = deserialization_limits.max_parameter_list_length;
 
-        // Open socket for unicast user-defined data
-        let interface_address_list = NetworkInterface::show()
+        // The link-local IPv6 addresses are not used since they are only valid within the scope
+        // of their interface, which is not part of the locators
+        let interface_list: Vec<_> = NetworkInterface::show()
             .expect("Could not scan interfaces")
             .into_iter()
             .filter(|x| {
@@ -134,56 +329,141 @@ impl RtpsTransport {
                     true
                 }
             })
+            .collect();
+        let interface_address_list: Vec<_> = interface_list
+            .iter()
             .flat_map(|i| {
-                i.addr.into_iter().filter(|a| match a {
-                    #[rustfmt::skip]
-                    Addr::V4(_) => true,
-                    _ => false,
+                i.addr.iter().copied().filter(|a| match a {
+                    Addr::V4(_) => ip_version.is_v4_enabled(),
+                    Addr::V6(v6) => ip_version.is_v6_enabled() && !v6.ip.is_unicast_link_local(),
                 })
-            });
-
-        let default_unicast_socket =
-            socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::DGRAM, None)?;
-        default_unicast_socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)).into())?;
-        default_unicast_socket.set_nonblocking(false)?;
-        if let Some(buffer_size) = udp_receive_buffer_size {
-            default_unicast_socket.set_recv_buffer_size(buffer_size)?;
-        }
-
-        let mut default_unicast_socket = std::net::UdpSocket::from(default_unicast_socket);
-        let user_defined_unicast_port = default_unicast_socket.local_addr()?.port().into();
-        let default_unicast_locator_list: Vec<_> = interface_address_list
-            .clone()
-            .map(|a| Locator::from_ip_and_port(&a, user_defined_unicast_port))
+            })
             .collect();
-
-        let default_multicast_locator_list = vec![];
-
-        // Open socket for unicast metatraffic data
-        let mut metatraffic_unicast_socket =
-            std::net::UdpSocket::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)))?;
-        metatraffic_unicast_socket.set_nonblocking(false)?;
-        let metattrafic_unicast_locator_port =
-            metatraffic_unicast_socket.local_addr()?.port().into();
-        let metatraffic_unicast_locator_list: Vec<Locator> = interface_address_list
-            .clone()
-            .map(|a| Locator::from_ip_and_port(&a, metattrafic_unicast_locator_port))
+        let interface_v6_index_list: Vec<_> = interface_list
+            .iter()
+            .filter(|i| i.addr.iter().any(|a| matches!(a, Addr::V6(_))))
+            .map(|i| i.index)
             .collect();
+        let address_family_list = AddressFamily::list(ip_version);
+
+        let mut default_unicast_socket_list = Vec::new();
+        let mut default_unicast_locator_list = Vec::new();
+        let mut default_multicast_socket_list = Vec::new();
+        let mut default_multicast_locator_list = Vec::new();
+        let mut metatraffic_unicast_socket_list = Vec::new();
+        let mut metatraffic_unicast_locator_list = Vec::new();
+        let mut metatraffic_multicast_socket_list = Vec::new();
+        let mut metatraffic_multicast_locator_list = Vec::new();
+        for &address_family in &address_family_list {
+            let family_address_list = interface_address_list
+                .iter()
+                .filter(|a| address_family.contains(a));
+
+            // Open socket for unicast user-defined data. The kernel distributes the datagrams
+            // among sockets sharing a port with SO_REUSEPORT by hashing the source address so
+            // each source is always received by the same socket.
+            let shard_default_unicast_socket = cfg!(target_os = "linux") && receive_threads > 1;
+            let default_unicast_socket = get_default_unicast_socket(
+                address_family,
+                0,
+                shard_default_unicast_socket,
+                receive_busy_poll_duration.is_some(),
+                udp_receive_buffer_size,
+            )?;
+            let user_defined_unicast_port = default_unicast_socket.local_addr()?.port();
+            default_unicast_socket_list.push(default_unicast_socket);
+            if shard_default_unicast_socket {
+                for _ in 1..receive_threads {
+                    default_unicast_socket_list.push(get_default_unicast_socket(
+                        address_family,
+                        user_defined_unicast_port,
+                        true,
+                        receive_busy_poll_duration.is_some(),
+                        udp_receive_buffer_size,
+                    )?);
+                }
+            }
+            default_unicast_locator_list.extend(
+                family_address_list
+                    .clone()
+                    .map(|a| Locator::from_ip_and_port(a, user_defined_unicast_port.into())),
+            );
+
+            // Open socket for multicast user-defined data. The group is only joined and
+            // advertised when the writers are allowed to send the user data with multicast
+            if user_data_multicast_threshold.is_some() {
+                default_multicast_socket_list.push(get_multicast_socket(
+                    address_family,
+                    port_user_multicast(domain_id),
+                    family_address_list.clone().copied(),
+                    interface_v6_index_list.iter().copied(),
+                )?);
+                default_multicast_locator_list.push(Locator::new(
+                    address_family.locator_kind(),
+                    port_user_multicast(domain_id) as u32,
+                    address_family.default_multicast_locator_address(),
+                ));
+            }
 
-        // Open socket for multicast metatraffic data
-        let metatraffic_multicast_locator_list = vec![Locator::new(
-            LOCATOR_KIND_UDP_V4,
-            port_builtin_multicast(domain_id) as u32,
-            DEFAULT_MULTICAST_LOCATOR_ADDRESS,
-        )];
-
-        let mut metatraffic_multicast_socket = get_multicast_socket(
-            DEFAULT_MULTICAST_LOCATOR_ADDRESS,
-            port_builtin_multicast(domain_id),
-            interface_address_list,
-        )?;
+            // Open socket for unicast metatraffic data on the well-known port of the first free
+            // participant identifier, so that the participant can be reached by the remote
+            // participants which only know its address
+            let metatraffic_unicast_socket = (0..=MAX_PARTICIPANT_ID)
+                .filter_map(|participant_id| port_builtin_unicast(domain_id, participant_id))
+                .find_map(|port| {
+                    get_default_unicast_socket(address_family, port, false, false, None).ok()
+                })
+                .map_or_else(
+                    || get_default_unicast_socket(address_family, 0, false, false, None),
+                    Ok,
+                )?;
+            let metattrafic_unicast_locator_port =
+                metatraffic_unicast_socket.local_addr()?.port().into();
+            metatraffic_unicast_socket_list.push(metatraffic_unicast_socket);
+            metatraffic_unicast_locator_list.extend(
+                family_address_list
+                    .clone()
+                    .map(|a| Locator::from_ip_and_port(a, metattrafic_unicast_locator_port)),
+            );
+
+            // Open socket for multicast metatraffic data
+            if !disable_multicast {
+                metatraffic_multicast_socket_list.push(get_multicast_socket(
+                    address_family,
+                    port_builtin_multicast(domain_id),
+                    family_address_list.copied(),
+                    interface_v6_index_list.iter().copied(),
+                )?);
+                metatraffic_multicast_locator_list.push(Locator::new(
+                    address_family.locator_kind(),
+                    port_builtin_multicast(domain_id) as u32,
+                    address_family.default_multicast_locator_address(),
+                ));
+            }
+        }
 
-        let rtps_participant_actor_builder = ActorBuilder::new();
+        let rtps_participant_actor_builder = ActorBuilder::with_mailbox(mailbox);
+
+        // With TCP the unicast traffic is received on the TCP port only, so the UDP unicast
+        // locators are replaced by the TCP ones
+        let tcp_transport = match tcp_port {
+            Some(port) => {
+                let tcp_transport = TcpTransport::new(
+                    domain_id,
+                    port,
+                    interface_address_list,
+                    rtps_participant_actor_builder.address(),
+                    max_parameter_list_length,
+                    tcp_max_connections,
+                )?;
+                metatraffic_unicast_locator_list =
+                    tcp_transport.metatraffic_unicast_locator_list().to_vec();
+                default_unicast_locator_list =
+                    tcp_transport.default_unicast_locator_list().to_vec();
+                Some(tcp_transport)
+            }
+            None => None,
+        };
 
         let guid = Guid::new(guid_prefix, ENTITYID_PARTICIPANT);
         let rtps_participant = rtps_participant_actor_builder.build(
@@ -193,95 +473,168 @@ impl RtpsTransport {
                 default_multicast_locator_list.clone(),
                 metatraffic_unicast_locator_list.clone(),
                 metatraffic_multicast_locator_list.clone(),
+                max_message_size,
+                path_mtu_discovery,
+                destination_mtu_list,
+                retransmission_backoff,
+                samples_per_piggyback_heartbeat,
+                heartbeat_response_jitter,
+                user_data_multicast_threshold,
+                writer_batching,
+                sedp_batching,
+                flow_control,
+                transport_priority_dscp_mapping,
+                disable_multicast,
+                deserialization_limits,
+                ip_version,
+                tcp_transport,
             )?,
             &executor.handle(),
         );
 
-        let rtps_participant_address = rtps_participant.address();
-        std::thread::Builder::new()
-            .name("RTPS metatraffic multicast discovery".to_string())
-            .spawn(move || {
-                let mut buf = Box::new([0; MAX_DATAGRAM_SIZE]);
-                loop {
-                    if let Ok(rtps_message) =
-                        read_message(&mut metatraffic_multicast_socket, buf.as_mut_slice())
-                    {
-                        tracing::trace!(
-                            rtps_message = ?rtps_message,
-                            "Received metatraffic multicast RTPS message"
-                        );
-                        let r = rtps_participant_address.send_actor_mail(
-                            participant::ProcessBuiltinRtpsMessage { rtps_message },
-                        );
-                        if r.is_err() {
-                            break;
+        for mut metatraffic_multicast_socket in metatraffic_multicast_socket_list {
+            let rtps_participant_address = rtps_participant.address();
+            std::thread::Builder::new()
+                .name("RTPS metatraffic multicast discovery".to_string())
+                .spawn(move || {
+                    let mut datagram_receiver = DatagramReceiver::new(MAX_DATAGRAM_SIZE);
+                    loop {
+                        let Ok(rtps_message_list) = read_messages(
+                            &mut metatraffic_multicast_socket,
+                            &mut datagram_receiver,
+                            max_parameter_list_length,
+                        ) else {
+                            continue;
+                        };
+                        for rtps_message in rtps_message_list {
+                            tracing::trace!(
+                                rtps_message = ?rtps_message,
+                                "Received metatraffic multicast RTPS message"
+                            );
+                            let r = rtps_participant_address.send_actor_mail(
+                                participant::ProcessBuiltinRtpsMessage { rtps_message },
+                            );
+                            // The messages dropped by a full mailbox are lost but the thread
+                            // keeps receiving until the participant is deleted
+                            if let Err(DdsError::AlreadyDeleted) = r {
+                                return;
+                            }
                         }
                     }
-                }
-            })
-            .expect("failed to spawn thread");
+                })
+                .expect("failed to spawn thread");
+        }
 
-        let rtps_participant_address = rtps_participant.address();
-        std::thread::Builder::new()
-            .name("RTPS metatraffic unicast discovery".to_string())
-            .spawn(move || {
-                let mut buf = Box::new([0; MAX_DATAGRAM_SIZE]);
-                loop {
-                    if let Ok(rtps_message) =
-                        read_message(&mut metatraffic_unicast_socket, buf.as_mut_slice())
-                    {
-                        tracing::trace!(
-                            rtps_message = ?rtps_message,
-                            "Received metatraffic unicast RTPS message"
-                        );
-
-                        let r = rtps_participant_address.send_actor_mail(
-                            participant::ProcessBuiltinRtpsMessage { rtps_message },
-                        );
-                        if r.is_err() {
-                            break;
+        for mut metatraffic_unicast_socket in metatraffic_unicast_socket_list {
+            let rtps_participant_address = rtps_participant.address();
+            std::thread::Builder::new()
+                .name("RTPS metatraffic unicast discovery".to_string())
+                .spawn(move || {
+                    let mut datagram_receiver = DatagramReceiver::new(MAX_DATAGRAM_SIZE);
+                    loop {
+                        let Ok(rtps_message_list) = read_messages(
+                            &mut metatraffic_unicast_socket,
+                            &mut datagram_receiver,
+                            max_parameter_list_length,
+                        ) else {
+                            continue;
+                        };
+                        for rtps_message in rtps_message_list {
+                            tracing::trace!(
+                                rtps_message = ?rtps_message,
+                                "Received metatraffic unicast RTPS message"
+                            );
+                            let r = rtps_participant_address.send_actor_mail(
+                                participant::ProcessBuiltinRtpsMessage { rtps_message },
+                            );
+                            if let Err(DdsError::AlreadyDeleted) = r {
+                                return;
+                            }
                         }
                     }
-                }
-            })
-            .expect("failed to spawn thread");
+                })
+                .expect("failed to spawn thread");
+        }
 
-        let rtps_participant_address = rtps_participant.address();
-        std::thread::Builder::new()
-            .name("RTPS user defined traffic".to_string())
-            .spawn(move || {
-                let mut buf = Box::new([0; MAX_DATAGRAM_SIZE]);
-                loop {
-                    if let Ok(rtps_message) =
-                        read_message(&mut default_unicast_socket, buf.as_mut_slice())
-                    {
-                        tracing::trace!(
-                            rtps_message = ?rtps_message,
-                            "Received user defined data unicast RTPS message"
-                        );
-                        let r = rtps_participant_address.send_actor_mail(
-                            participant::ProcessUserDefinedRtpsMessage { rtps_message },
-                        );
-                        if r.is_err() {
-                            break;
+        for mut default_unicast_socket in default_unicast_socket_list {
+            let rtps_participant_address = rtps_participant.address();
+            std::thread::Builder::new()
+                .name("RTPS user defined traffic".to_string())
+                .spawn(move || {
+                    let mut datagram_receiver = DatagramReceiver::new(MAX_DATAGRAM_SIZE);
+                    loop {
+                        let rtps_message_list = match receive_busy_poll_duration {
+                            Some(busy_poll_duration) => read_messages_busy_poll(
+                                &mut default_unicast_socket,
+                                &mut datagram_receiver,
+                                busy_poll_duration,
+                                max_parameter_list_length,
+                            ),
+                            None => read_messages(
+                                &mut default_unicast_socket,
+                                &mut datagram_receiver,
+                                max_parameter_list_length,
+                            ),
+                        };
+                        let Ok(rtps_message_list) = rtps_message_list else {
+                            continue;
+                        };
+                        for rtps_message in rtps_message_list {
+                            tracing::trace!(
+                                rtps_message = ?rtps_message,
+                                "Received user defined data unicast RTPS message"
+                            );
+                            let r = rtps_participant_address.send_actor_mail(
+                                participant::ProcessUserDefinedRtpsMessage { rtps_message },
+                            );
+                            if let Err(DdsError::AlreadyDeleted) = r {
+                                return;
+                            }
                         }
                     }
-                }
-            })
-            .expect("failed to spawn thread");
+                })
+                .expect("failed to spawn thread");
+        }
 
-        // Heartbeat thread
+        for mut default_multicast_socket in default_multicast_socket_list {
+            let rtps_participant_address = rtps_participant.address();
+            std::thread::Builder::new()
+                .name("RTPS user defined multicast traffic".to_string())
+                .spawn(move || {
+                    let mut datagram_receiver = DatagramReceiver::new(MAX_DATAGRAM_SIZE);
+                    loop {
+                        let Ok(rtps_message_list) = read_messages(
+                            &mut default_multicast_socket,
+                            &mut datagram_receiver,
+                            max_parameter_list_length,
+                        ) else {
+                            continue;
+                        };
+                        for rtps_message in rtps_message_list {
+                            tracing::trace!(
+                                rtps_message = ?rtps_message,
+                                "Received user defined data multicast RTPS message"
+                            );
+                            let r = rtps_participant_address.send_actor_mail(
+                                participant::ProcessUserDefinedRtpsMessage { rtps_message },
+                            );
+                            if let Err(DdsError::AlreadyDeleted) = r {
+                                return;
+                            }
+                        }
+                    }
+                })
+                .expect("failed to spawn thread");
+        }
+
+        // Heartbeat timer. It also sends the heartbeat responses delayed by the jitter
         let rtps_participant_address = rtps_participant.address();
-        std::thread::Builder::new()
-            .name("RTPS heartbeat".to_string())
-            .spawn(move || loop {
-                std::thread::sleep(std::time::Duration::from_millis(50));
-                let r = rtps_participant_address.send_actor_mail(participant::SendHeartbeat);
-                if r.is_err() {
-                    break;
-                }
-            })
-            .expect("failed to spawn thread");
+        let heartbeat_task =
+            timer_handle.schedule_periodic(std::time::Duration::from_millis(50), move || {
+                rtps_participant_address
+                    .send_actor_mail(participant::SendHeartbeat)
+                    .ok();
+            });
 
         Ok(Self {
             guid,
@@ -290,9 +643,27 @@ impl RtpsTransport {
             default_multicast_locator_list,
             metatraffic_unicast_locator_list,
             metatraffic_multicast_locator_list,
+            heartbeat_task,
             _executor: executor,
         })
     }
+
+    // The submessages of the protected endpoints are transformed by the crypto when they are
+    // sent and received
+    pub fn set_submessage_crypto(&mut self, submessage_crypto: Arc<dyn SubmessageCrypto>) {
+        block_on(
+            self.rtps_participant
+                .send_actor_mail(participant::SetSubmessageCrypto { submessage_crypto })
+                .expect("Actor must exist")
+                .receive_reply(),
+        );
+    }
+}
+
+impl Drop for RtpsTransport {
+    fn drop(&mut self) {
+        self.heartbeat_task.abort();
+    }
 }
 
 impl TransportParticipant for RtpsTransport {
@@ -344,7 +715,8 @@ impl TransportParticipant for RtpsTransport {
             .send_actor_mail(participant::CreateStatelessReader {
                 reader_guid: guid,
                 reader_history_cache,
-            });
+            })
+            .ok();
 
         Box::new(StatelessReader { guid })
     }
@@ -361,6 +733,7 @@ impl TransportParticipant for RtpsTransport {
                     writer_guid: guid,
                     rtps_participant_address: self.rtps_participant.address(),
                 })
+                .expect("Actor must exist")
                 .receive_reply(),
         )
     }
@@ -405,8 +778,16 @@ impl TransportParticipant for RtpsTransport {
                 )
             }
 
-            fn remove_matched_writer(&mut self, _remote_writer_guid: Guid) {
-                todo!()
+            fn remove_matched_writer(&mut self, remote_writer_guid: Guid) {
+                block_on(
+                    self.rtps_participant_address
+                        .send_actor_mail(participant::RemoveMatchedWriter {
+                            reader: self.guid,
+                            writer_proxy: remote_writer_guid,
+                        })
+                        .expect("Actor must exist")
+                        .receive_reply(),
+                )
             }
         }
 
@@ -414,7 +795,8 @@ impl TransportParticipant for RtpsTransport {
             .send_actor_mail(participant::CreateStatefulReader {
                 reader_guid: guid,
                 reader_history_cache,
-            });
+            })
+            .ok();
 
         Box::new(StatefulReader {
             rtps_participant_address: self.rtps_participant.address(),
@@ -436,6 +818,7 @@ impl TransportParticipant for RtpsTransport {
                     data_max_size_serialized,
                     rtps_participant_address: self.rtps_participant.address(),
                 })
+                .expect("Actor must exist")
                 .receive_reply(),
         )
     }
@@ -445,10 +828,13 @@ impl TransportParticipant for RtpsTransport {
 mod tests {
     use std::sync::mpsc::{sync_channel, SyncSender};
 
-    use crate::transport::{
-        history_cache::CacheChange,
-        types::{ChangeKind, DurabilityKind, ENTITYID_UNKNOWN},
-        writer::ReaderProxy,
+    use crate::{
+        runtime::timer::TimerDriver,
+        transport::{
+            history_cache::CacheChange,
+            types::{ChangeKind, DurabilityKind, ENTITYID_UNKNOWN},
+            writer::ReaderProxy,
+        },
     };
 
     use super::*;
@@ -459,11 +845,50 @@ mod tests {
         let domain_id = 0;
         let interface_name = None;
         let udp_receive_buffer_size = None;
+        let receive_busy_poll_duration = None;
+        let receive_threads = 1;
+        let max_message_size = 65507;
+        let path_mtu_discovery = false;
+        let destination_mtu_list = Vec::new();
+        let retransmission_backoff = RetransmissionBackoff::default();
+        let samples_per_piggyback_heartbeat = 1;
+        let heartbeat_response_jitter = std::time::Duration::ZERO;
+        let user_data_multicast_threshold = None;
+        let writer_batching = None;
+        let sedp_batching = None;
+        let flow_control = None;
+        let transport_priority_dscp_mapping = Vec::new();
+        let disable_multicast = false;
+        let deserialization_limits = DeserializationLimits::default();
+        let tcp_port = None;
+        let tcp_max_connections = 1;
+        let ip_version = IpVersion::V4;
+        let timer_driver = TimerDriver::new();
         let mut transport = RtpsTransport::new(
             guid_prefix,
             domain_id,
             interface_name,
             udp_receive_buffer_size,
+            receive_busy_poll_duration,
+            receive_threads,
+            max_message_size,
+            path_mtu_discovery,
+            destination_mtu_list,
+            retransmission_backoff,
+            samples_per_piggyback_heartbeat,
+            heartbeat_response_jitter,
+            user_data_multicast_threshold,
+            writer_batching,
+            sedp_batching,
+            flow_control,
+            transport_priority_dscp_mapping,
+            disable_multicast,
+            deserialization_limits,
+            tcp_port,
+            tcp_max_connections,
+            ip_version,
+            Mailbox::default(),
+            timer_driver.handle(),
         )
         .unwrap();
 
@@ -499,6 +924,11 @@ mod tests {
             unicast_locator_list: vec![],
             multicast_locator_list: vec![],
             expects_inline_qos: false,
+            disable_positive_acks_keep_duration: None,
+            prefer_multicast: false,
+            push_mode: true,
+            historical_data_limit: Default::default(),
+            content_filter: None,
         };
         writer.add_matched_reader(reader_proxy);
 
@@ -510,6 +940,7 @@ mod tests {
             unicast_locator_list: vec![],
             multicast_locator_list: vec![],
             data_max_size_serialized: 5000,
+            disable_positive_acks: false,
         };
         reader.add_matched_writer(writer_proxy);
 
@@ -535,11 +966,50 @@ mod tests {
         let domain_id = 0;
         let interface_name = None;
         let udp_receive_buffer_size = None;
+        let receive_busy_poll_duration = None;
+        let receive_threads = 1;
+        let max_message_size = 65507;
+        let path_mtu_discovery = false;
+        let destination_mtu_list = Vec::new();
+        let retransmission_backoff = RetransmissionBackoff::default();
+        let samples_per_piggyback_heartbeat = 1;
+        let heartbeat_response_jitter = std::time::Duration::ZERO;
+        let user_data_multicast_threshold = None;
+        let writer_batching = None;
+        let sedp_batching = None;
+        let flow_control = None;
+        let transport_priority_dscp_mapping = Vec::new();
+        let disable_multicast = false;
+        let deserialization_limits = DeserializationLimits::default();
+        let tcp_port = None;
+        let tcp_max_connections = 1;
+        let ip_version = IpVersion::V4;
+        let timer_driver = TimerDriver::new();
         let mut transport = RtpsTransport::new(
             guid_prefix,
             domain_id,
             interface_name,
             udp_receive_buffer_size,
+            receive_busy_poll_duration,
+            receive_threads,
+            max_message_size,
+            path_mtu_discovery,
+            destination_mtu_list,
+            retransmission_backoff,
+            samples_per_piggyback_heartbeat,
+            heartbeat_response_jitter,
+            user_data_multicast_threshold,
+            writer_batching,
+            sedp_batching,
+            flow_control,
+            transport_priority_dscp_mapping,
+            disable_multicast,
+            deserialization_limits,
+            tcp_port,
+            tcp_max_connections,
+            ip_version,
+            Mailbox::default(),
+            timer_driver.handle(),
         )
         .unwrap();
 
@@ -564,7 +1034,7 @@ mod tests {
         let data_max_size_serialized = 1000;
         let mut writer = transport.create_stateless_writer(entity_id, data_max_size_serialized);
         for locator in transport.default_unicast_locator_list() {
-            writer.add_reader_locator(locator.clone());
+            writer.add_reader_locator(*locator);
         }
 
         let cache_change = CacheChange {
@@ -0,0 +1,150 @@
+use std::net::{SocketAddr, UdpSocket};
+
+// Maximum number of datagrams sent or received with a single system call
+const MAX_BATCH_SIZE: usize = 16;
+
+// Sends the datagrams with as few system calls as possible. As with a single send_to, a datagram
+// which can not be sent is dropped since the reliability is provided by the RTPS protocol.
+pub fn send_datagrams(socket: &UdpSocket, datagram_list: &[(&[u8], SocketAddr)]) {
+    #[cfg(target_os = "linux")]
+    linux::send_datagrams(socket, datagram_list);
+
+    #[cfg(not(target_os = "linux"))]
+    for (datagram, address) in datagram_list {
+        socket.send_to(datagram, address).ok();
+    }
+}
+
+// Receives the datagrams queued in a socket in batches. On Linux all the datagrams already
+// queued, up to the batch size, are received with a single system call. On other platforms the
+// datagrams are received one at a time.
+pub struct DatagramReceiver {
+    buffer: Vec<u8>,
+    max_datagram_size: usize,
+    length_list: Vec<usize>,
+    #[cfg(target_os = "linux")]
+    headers: nix::sys::socket::MultiHeaders<nix::sys::socket::SockaddrStorage>,
+}
+
+impl DatagramReceiver {
+    pub fn new(max_datagram_size: usize) -> Self {
+        Self {
+            buffer: vec![0; max_datagram_size * MAX_BATCH_SIZE],
+            max_datagram_size,
+            length_list: Vec::with_capacity(MAX_BATCH_SIZE),
+            #[cfg(target_os = "linux")]
+            headers: nix::sys::socket::MultiHeaders::preallocate(MAX_BATCH_SIZE, None),
+        }
+    }
+
+    // Waits for at least one datagram unless the socket is non-blocking
+    pub fn receive(&mut self, socket: &UdpSocket) -> std::io::Result<()> {
+        #[cfg(target_os = "linux")]
+        {
+            use nix::sys::socket::{recvmmsg, MsgFlags};
+            use std::{io::IoSliceMut, os::fd::AsRawFd};
+
+            let mut slice_list: Vec<[IoSliceMut; 1]> = self
+                .buffer
+                .chunks_mut(self.max_datagram_size)
+                .map(|b| [IoSliceMut::new(b)])
+                .collect();
+            let length_list: Vec<usize> = recvmmsg(
+                socket.as_raw_fd(),
+                &mut self.headers,
+                &mut slice_list,
+                MsgFlags::MSG_WAITFORONE,
+                None,
+            )?
+            .map(|r| r.bytes)
+            .collect();
+            self.length_list = length_list;
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let (bytes, _) = socket.recv_from(&mut self.buffer[..self.max_datagram_size])?;
+            self.length_list = vec![bytes];
+        }
+
+        Ok(())
+    }
+
+    pub fn datagrams(&self) -> impl Iterator<Item = &[u8]> {
+        self.buffer
+            .chunks(self.max_datagram_size)
+            .zip(&self.length_list)
+            .map(|(datagram, &length)| &datagram[..length])
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::MAX_BATCH_SIZE;
+    use nix::sys::socket::{sendmmsg, ControlMessage, MsgFlags, MultiHeaders, SockaddrStorage};
+    use std::{
+        io::IoSlice,
+        net::{SocketAddr, UdpSocket},
+        os::fd::AsRawFd,
+    };
+
+    pub fn send_datagrams(socket: &UdpSocket, datagram_list: &[(&[u8], SocketAddr)]) {
+        let mut headers = MultiHeaders::<SockaddrStorage>::preallocate(
+            datagram_list.len().min(MAX_BATCH_SIZE),
+            None,
+        );
+        let mut remaining_datagram_list = datagram_list;
+        while !remaining_datagram_list.is_empty() {
+            let batch =
+                &remaining_datagram_list[..remaining_datagram_list.len().min(MAX_BATCH_SIZE)];
+            let slice_list: Vec<[IoSlice; 1]> =
+                batch.iter().map(|(d, _)| [IoSlice::new(d)]).collect();
+            let address_list: Vec<Option<SockaddrStorage>> = batch
+                .iter()
+                .map(|&(_, a)| Some(SockaddrStorage::from(a)))
+                .collect();
+            let control_message_list: [ControlMessage; 0] = [];
+            // The system call stops at the first datagram which can not be sent. That datagram
+            // is dropped and the sending resumes with the next one.
+            let sent_count = sendmmsg(
+                socket.as_raw_fd(),
+                &mut headers,
+                &slice_list,
+                &address_list,
+                control_message_list,
+                MsgFlags::empty(),
+            )
+            .map_or(0, |r| r.count());
+            remaining_datagram_list = &remaining_datagram_list[sent_count.max(1)..];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn datagrams_are_sent_and_received_in_batches() {
+        let receiver_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let sender_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let address = receiver_socket.local_addr().unwrap();
+        let datagram_list: Vec<Vec<u8>> = (0..20u8).map(|i| vec![i; i as usize + 1]).collect();
+
+        send_datagrams(
+            &sender_socket,
+            &datagram_list
+                .iter()
+                .map(|d| (d.as_slice(), address))
+                .collect::<Vec<_>>(),
+        );
+
+        let mut datagram_receiver = DatagramReceiver::new(64);
+        let mut received_datagram_list = Vec::new();
+        while received_datagram_list.len() < datagram_list.len() {
+            datagram_receiver.receive(&receiver_socket).unwrap();
+            received_datagram_list.extend(datagram_receiver.datagrams().map(|d| d.to_vec()));
+        }
+        assert_eq!(received_datagram_list, datagram_list);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::transport::{history_cache::CacheChange, types::SequenceNumber};
+use std::{collections::BTreeMap, ops::Bound};
+
+// History of the changes of a writer indexed by their sequence number. The lowest and highest
+// sequence numbers are kept up to date so that they are available without a lookup.
+#[derive(Default)]
+pub struct WriterHistoryCache {
+    changes: BTreeMap<SequenceNumber, CacheChange>,
+    seq_num_min: Option<SequenceNumber>,
+    seq_num_max: Option<SequenceNumber>,
+}
+
+impl WriterHistoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_change(&mut self, cache_change: CacheChange) {
+        let sequence_number = cache_change.sequence_number();
+        self.changes.insert(sequence_number, cache_change);
+        self.seq_num_min = Some(
+            self.seq_num_min
+                .map_or(sequence_number, |min| min.min(sequence_number)),
+        );
+        self.seq_num_max = Some(
+            self.seq_num_max
+                .map_or(sequence_number, |max| max.max(sequence_number)),
+        );
+    }
+
+    pub fn remove_change(&mut self, sequence_number: SequenceNumber) {
+        if self.changes.remove(&sequence_number).is_some() {
+            if self.seq_num_min == Some(sequence_number) {
+                self.seq_num_min = self.changes.keys().next().copied();
+            }
+            if self.seq_num_max == Some(sequence_number) {
+                self.seq_num_max = self.changes.keys().next_back().copied();
+            }
+        }
+    }
+
+    pub fn get_change(&self, sequence_number: SequenceNumber) -> Option<&CacheChange> {
+        self.changes.get(&sequence_number)
+    }
+
+    pub fn seq_num_min(&self) -> Option<SequenceNumber> {
+        self.seq_num_min
+    }
+
+    pub fn seq_num_max(&self) -> Option<SequenceNumber> {
+        self.seq_num_max
+    }
+
+    // Changes in increasing order of sequence number
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &CacheChange> {
+        self.changes.values()
+    }
+
+    // Changes with a sequence number higher than the given one in increasing order
+    pub fn changes_after(
+        &self,
+        sequence_number: SequenceNumber,
+    ) -> impl Iterator<Item = &CacheChange> {
+        self.changes
+            .range((Bound::Excluded(sequence_number), Bound::Unbounded))
+            .map(|(_, cache_change)| cache_change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::types::{ChangeKind, GUID_UNKNOWN};
+
+    fn cache_change(sequence_number: SequenceNumber) -> CacheChange {
+        CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid: GUID_UNKNOWN,
+            sequence_number,
+            source_timestamp: None,
+            instance_handle: None,
+            data_value: vec![].into(),
+        }
+    }
+
+    #[test]
+    fn changes_are_indexed_by_sequence_number() {
+        let mut history_cache = WriterHistoryCache::new();
+        for sequence_number in [3, 1, 4, 2, 5] {
+            history_cache.add_change(cache_change(sequence_number));
+        }
+        assert_eq!(history_cache.seq_num_min(), Some(1));
+        assert_eq!(history_cache.seq_num_max(), Some(5));
+        assert_eq!(
+            history_cache.get_change(4).map(|cc| cc.sequence_number()),
+            Some(4)
+        );
+        assert_eq!(
+            history_cache
+                .changes_after(2)
+                .map(|cc| cc.sequence_number())
+                .collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+
+        history_cache.remove_change(1);
+        history_cache.remove_change(5);
+        history_cache.remove_change(3);
+        assert!(history_cache.get_change(3).is_none());
+        assert_eq!(history_cache.seq_num_min(), Some(2));
+        assert_eq!(history_cache.seq_num_max(), Some(4));
+
+        history_cache.remove_change(2);
+        history_cache.remove_change(4);
+        assert_eq!(history_cache.seq_num_min(), None);
+        assert_eq!(history_cache.seq_num_max(), None);
+        assert_eq!(history_cache.iter().count(), 0);
+    }
+}
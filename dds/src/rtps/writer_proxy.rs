@@ -9,11 +9,15 @@ use super::{
             ack_nack::AckNackSubmessage, data::DataSubmessage, data_frag::DataFragSubmessage,
             info_destination::InfoDestinationSubmessage, nack_frag::NackFragSubmessage,
         },
-        types::Count,
+        types::{Count, FragmentNumber},
     },
 };
 
-use std::{cmp::max, collections::HashMap, sync::Arc};
+use std::{
+    cmp::max,
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 fn total_fragments_expected(data_frag_submessage: &DataFragSubmessage) -> u32 {
     let data_size = data_frag_submessage.data_size();
@@ -22,6 +26,108 @@ fn total_fragments_expected(data_frag_submessage: &DataFragSubmessage) -> u32 {
     data_size / fragment_size + total_fragments_correction
 }
 
+// Reassembly buffer of a sample received in fragments. The fragments are accepted in any order and
+// the ones already received are tracked with a bitmap so that duplicated or overlapping DATA_FRAG
+// submessages don't count twice towards the completeness of the sample.
+#[derive(Debug, PartialEq, Eq)]
+struct FragmentedSample {
+    data_size: u32,
+    fragment_size: u16,
+    total_fragments: u32,
+    received_fragments: Vec<u32>,
+    received_fragments_count: u32,
+    data_frag_list: Vec<DataFragSubmessage>,
+    last_received_time: std::time::Instant,
+}
+
+impl FragmentedSample {
+    fn new(data_frag_submessage: &DataFragSubmessage, now: std::time::Instant) -> Self {
+        let total_fragments = total_fragments_expected(data_frag_submessage);
+        Self {
+            data_size: data_frag_submessage.data_size(),
+            fragment_size: data_frag_submessage.fragment_size(),
+            total_fragments,
+            received_fragments: vec![0; total_fragments.div_ceil(32) as usize],
+            received_fragments_count: 0,
+            data_frag_list: Vec::new(),
+            last_received_time: now,
+        }
+    }
+
+    fn is_fragment_received(&self, fragment_number: FragmentNumber) -> bool {
+        let index = (fragment_number - 1) as usize;
+        self.received_fragments[index / 32] & (1 << (index % 32)) != 0
+    }
+
+    fn is_complete(&self) -> bool {
+        self.received_fragments_count == self.total_fragments
+    }
+
+    // The submessages which don't match the size of the sample or whose fragments were all
+    // received already are discarded
+    fn add_data_frag(&mut self, data_frag_submessage: DataFragSubmessage, now: std::time::Instant) {
+        let fragment_starting_num = data_frag_submessage.fragment_starting_num();
+        if data_frag_submessage.data_size() != self.data_size
+            || data_frag_submessage.fragment_size() != self.fragment_size
+            || fragment_starting_num == 0
+            || fragment_starting_num > self.total_fragments
+        {
+            return;
+        }
+        let last_fragment_num = (fragment_starting_num - 1)
+            .saturating_add(data_frag_submessage.fragments_in_submessage() as u32)
+            .min(self.total_fragments);
+        let data_start = (fragment_starting_num - 1) as usize * self.fragment_size as usize;
+        let data_end =
+            (last_fragment_num as usize * self.fragment_size as usize).min(self.data_size as usize);
+        if data_frag_submessage.serialized_payload().as_ref().len() < data_end - data_start {
+            return;
+        }
+
+        let mut is_new_fragment_received = false;
+        for fragment_number in fragment_starting_num..=last_fragment_num {
+            if !self.is_fragment_received(fragment_number) {
+                let index = (fragment_number - 1) as usize;
+                self.received_fragments[index / 32] |= 1 << (index % 32);
+                self.received_fragments_count += 1;
+                is_new_fragment_received = true;
+            }
+        }
+        if is_new_fragment_received {
+            self.data_frag_list.push(data_frag_submessage);
+            self.last_received_time = now;
+        }
+    }
+
+    // Each fragment is copied at its position in the sample, dropping the padding after the last one.
+    // The sample is reassembled directly in the shared buffer handed over to the history cache
+    // instead of in a vector which would be copied again when converted into the shared buffer.
+    fn reassemble(self, writer_id: EntityId, writer_sn: SequenceNumber) -> DataSubmessage {
+        let mut sample: Arc<[u8]> = std::iter::repeat_n(0, self.data_size as usize).collect();
+        let data = Arc::get_mut(&mut sample).expect("Sample buffer is not shared yet");
+        for data_frag in &self.data_frag_list {
+            let data_start =
+                (data_frag.fragment_starting_num() - 1) as usize * self.fragment_size as usize;
+            let payload = data_frag.serialized_payload().as_ref();
+            let len = payload.len().min(data.len() - data_start);
+            data[data_start..data_start + len].copy_from_slice(&payload[..len]);
+        }
+
+        let first_data_frag = &self.data_frag_list[0];
+        DataSubmessage::new(
+            first_data_frag.inline_qos_flag(),
+            !first_data_frag.key_flag(),
+            first_data_frag.key_flag(),
+            false,
+            first_data_frag.reader_id(),
+            writer_id,
+            writer_sn,
+            first_data_frag.inline_qos().clone(),
+            Data::new(sample),
+        )
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct RtpsWriterProxy {
     remote_writer_guid: Guid,
@@ -32,13 +138,24 @@ pub struct RtpsWriterProxy {
     first_available_seq_num: SequenceNumber,
     last_available_seq_num: SequenceNumber,
     highest_received_change_sn: SequenceNumber,
+    // Ranges of irrelevant changes above the highest received change indexed by their first
+    // sequence number. They are kept until all the changes before them are received
+    irrelevant_changes: BTreeMap<SequenceNumber, SequenceNumber>,
+    // The changes written before the first change known by the reader are not lost since they
+    // were written before the writer was matched
+    is_first_change_known: bool,
     must_send_acknacks: bool,
     last_received_heartbeat_count: Count,
     last_received_heartbeat_frag_count: Count,
     acknack_count: Count,
     nack_frag_count: Count,
-    frag_buffer: HashMap<SequenceNumber, Vec<DataFragSubmessage>>,
+    frag_buffer: HashMap<SequenceNumber, FragmentedSample>,
+    // Last fragment announced with a HEARTBEAT_FRAG for each of the samples not yet received
+    announced_fragments: HashMap<SequenceNumber, FragmentNumber>,
+    must_send_nack_frags: bool,
     reliability: ReliabilityKind,
+    disable_positive_acks: bool,
+    heartbeat_response_deadline: Option<std::time::Instant>,
 }
 
 impl RtpsWriterProxy {
@@ -49,6 +166,7 @@ impl RtpsWriterProxy {
         data_max_size_serialized: Option<i32>,
         remote_group_entity_id: EntityId,
         reliability: ReliabilityKind,
+        disable_positive_acks: bool,
     ) -> Self {
         Self {
             remote_writer_guid,
@@ -59,71 +177,68 @@ impl RtpsWriterProxy {
             first_available_seq_num: 1,
             last_available_seq_num: 0,
             highest_received_change_sn: 0,
+            irrelevant_changes: BTreeMap::new(),
+            is_first_change_known: false,
             must_send_acknacks: false,
             last_received_heartbeat_count: 0,
             last_received_heartbeat_frag_count: 0,
             acknack_count: 0,
             nack_frag_count: 0,
             frag_buffer: HashMap::new(),
+            announced_fragments: HashMap::new(),
+            must_send_nack_frags: false,
             reliability,
+            disable_positive_acks,
+            heartbeat_response_deadline: None,
         }
     }
 
-    pub fn push_data_frag(&mut self, submessage: DataFragSubmessage) {
-        let frag_bug_seq_num = self.frag_buffer.entry(submessage.writer_sn()).or_default();
-        if !frag_bug_seq_num.contains(&submessage) {
-            frag_bug_seq_num.push(submessage);
+    pub fn push_data_frag(
+        &mut self,
+        submessage: DataFragSubmessage,
+        max_fragments_per_sample: usize,
+        max_pending_fragmented_samples: usize,
+    ) {
+        let total_fragments = total_fragments_expected(&submessage) as usize;
+        if total_fragments == 0 || total_fragments > max_fragments_per_sample {
+            return;
+        }
+        // Evicting the oldest pending sample bounds the buffer even if some samples are never completed. A reliable
+        // writer repairs the evicted sample when it is requested again.
+        if !self.frag_buffer.contains_key(&submessage.writer_sn())
+            && self.frag_buffer.len() >= max_pending_fragmented_samples
+        {
+            if let Some(oldest_seq_num) = self.frag_buffer.keys().min().copied() {
+                self.frag_buffer.remove(&oldest_seq_num);
+            }
         }
+        let now = std::time::Instant::now();
+        self.frag_buffer
+            .entry(submessage.writer_sn())
+            .or_insert_with(|| FragmentedSample::new(&submessage, now))
+            .add_data_frag(submessage, now);
     }
 
     pub fn reconstruct_data_from_frag(
         &mut self,
         seq_num: SequenceNumber,
     ) -> Option<DataSubmessage> {
-        if let Some(seq_num_frag) = self.frag_buffer.get(&seq_num) {
-            let total_fragments_expected = total_fragments_expected(&seq_num_frag[0]);
-
-            let mut total_fragments = 0;
-            for frag_seq_num in seq_num_frag {
-                total_fragments += frag_seq_num.fragments_in_submessage() as u32;
-            }
-
-            if total_fragments == total_fragments_expected {
-                let mut frag_seq_num_list = self.frag_buffer.remove(&seq_num).expect("Must exist");
-                frag_seq_num_list.sort_by_key(|k| k.fragment_starting_num());
-
-                let inline_qos_flag = frag_seq_num_list[0].inline_qos_flag();
-                let data_flag = !frag_seq_num_list[0].key_flag();
-                let key_flag = frag_seq_num_list[0].key_flag();
-                let non_standard_payload_flag = false;
-                let writer_id = self.remote_writer_guid.entity_id();
-                let reader_id = frag_seq_num_list[0].reader_id();
-                let writer_sn = seq_num;
-                let inline_qos = frag_seq_num_list[0].inline_qos().clone();
-                let mut data = Vec::new();
-                for frag in frag_seq_num_list {
-                    data.extend_from_slice(frag.serialized_payload().as_ref());
-                }
-
-                Some(DataSubmessage::new(
-                    inline_qos_flag,
-                    data_flag,
-                    key_flag,
-                    non_standard_payload_flag,
-                    reader_id,
-                    writer_id,
-                    writer_sn,
-                    inline_qos,
-                    Data::new(Arc::from(data)),
-                ))
-            } else {
-                None
-            }
+        if self.frag_buffer.get(&seq_num)?.is_complete() {
+            let fragmented_sample = self.frag_buffer.remove(&seq_num).expect("Must exist");
+            Some(fragmented_sample.reassemble(self.remote_writer_guid.entity_id(), seq_num))
         } else {
             None
         }
     }
 
+    // The partially received samples without any new fragment during the timeout are not
+    // expected to complete anymore. A reliable writer sends them again when they are requested.
+    pub fn discard_stale_fragmented_samples(&mut self, timeout: std::time::Duration) {
+        let now = std::time::Instant::now();
+        self.frag_buffer
+            .retain(|_, s| now.duration_since(s.last_received_time) < timeout);
+    }
+
     pub fn remote_writer_guid(&self) -> Guid {
         self.remote_writer_guid
     }
@@ -152,21 +267,71 @@ impl RtpsWriterProxy {
         // FIND change FROM this.changes_from_writer SUCH-THAT
         // (change.sequenceNumber == a_seq_num);
         // change.status := RECEIVED; change.is_relevant := FALSE;
-        if a_seq_num > self.highest_received_change_sn {
-            self.highest_received_change_sn = a_seq_num;
+        self.irrelevant_change_range_set(a_seq_num, a_seq_num);
+    }
+
+    // Marks all the changes from first_seq_num to last_seq_num as irrelevant without going through
+    // each of them
+    pub fn irrelevant_change_range_set(
+        &mut self,
+        first_seq_num: SequenceNumber,
+        last_seq_num: SequenceNumber,
+    ) {
+        self.is_first_change_known = true;
+        if last_seq_num > self.available_changes_max() {
+            let range_end = self
+                .irrelevant_changes
+                .entry(first_seq_num)
+                .or_insert(last_seq_num);
+            *range_end = max(*range_end, last_seq_num);
         }
+        self.skip_irrelevant_changes();
     }
 
-    pub fn lost_changes_update(&mut self, first_available_seq_num: SequenceNumber) {
+    fn is_irrelevant(&self, seq_num: SequenceNumber) -> bool {
+        self.irrelevant_changes
+            .range(..=seq_num)
+            .any(|(_, &range_end)| seq_num <= range_end)
+    }
+
+    // Advances the highest received change over the irrelevant changes which follow it
+    fn skip_irrelevant_changes(&mut self) {
+        while let Some((&range_start, &range_end)) = self.irrelevant_changes.first_key_value() {
+            if range_start > self.available_changes_max() + 1 {
+                break;
+            }
+            self.irrelevant_changes.remove(&range_start);
+            self.highest_received_change_sn = max(self.highest_received_change_sn, range_end);
+        }
+    }
+
+    /// Returns the number of changes which are now lost because they were neither received nor
+    /// irrelevant
+    pub fn lost_changes_update(&mut self, first_available_seq_num: SequenceNumber) -> i64 {
         // FOREACH change IN this.changes_from_writer
         // SUCH-THAT ( change.status == UNKNOWN OR change.status == MISSING
         // AND seq_num < first_available_seq_num ) DO {
         // change.status := LOST;
         // }
-        self.first_available_seq_num = first_available_seq_num;
+        let mut lost_changes_count = 0;
+        if self.is_first_change_known {
+            let mut seq_num = self.available_changes_max() + 1;
+            for (&range_start, &range_end) in self.irrelevant_changes.iter() {
+                if range_start >= first_available_seq_num {
+                    break;
+                }
+                lost_changes_count += max(range_start - seq_num, 0);
+                seq_num = max(seq_num, range_end + 1);
+            }
+            lost_changes_count += max(first_available_seq_num - seq_num, 0);
+        }
+        self.is_first_change_known = true;
+        self.first_available_seq_num = max(self.first_available_seq_num, first_available_seq_num);
+        self.skip_irrelevant_changes();
+        lost_changes_count
     }
 
-    pub fn missing_changes(&self) -> impl Iterator<Item = SequenceNumber> {
+    pub fn missing_changes(&self) -> impl Iterator<Item = SequenceNumber> + '_ {
         // The changes with status 'MISSING' represent the set of changes available in the HistoryCache of the RTPS Writer
         // represented by the RTPS WriterProxy that have not been received by the RTPS Reader.
         // return { change IN this.changes_from_writer SUCH-THAT change.status == MISSING};
@@ -180,7 +345,7 @@ impl RtpsWriterProxy {
             self.first_available_seq_num,
             self.highest_received_change_sn + 1,
         );
-        first_missing_change..=highest_number
+        (first_missing_change..=highest_number).filter(|&seq_num| !self.is_irrelevant(seq_num))
     }
 
     pub fn missing_changes_update(&mut self, last_available_seq_num: SequenceNumber) {
@@ -196,9 +361,11 @@ impl RtpsWriterProxy {
         // FIND change FROM this.changes_from_writer
         //     SUCH-THAT change.sequenceNumber == a_seq_num;
         // change.status := RECEIVED
+        self.is_first_change_known = true;
         if a_seq_num > self.highest_received_change_sn {
             self.highest_received_change_sn = a_seq_num;
         }
+        self.skip_irrelevant_changes();
     }
 
     pub fn set_must_send_acknacks(&mut self, must_send_acknacks: bool) {
@@ -217,6 +384,10 @@ impl RtpsWriterProxy {
         self.last_received_heartbeat_count = last_received_heartbeat_count;
     }
 
+    pub fn last_received_heartbeat_frag_count(&self) -> Count {
+        self.last_received_heartbeat_frag_count
+    }
+
     pub fn set_last_received_heartbeat_frag_count(
         &mut self,
         last_received_heartbeat_frag_count: Count,
@@ -224,6 +395,86 @@ impl RtpsWriterProxy {
         self.last_received_heartbeat_frag_count = last_received_heartbeat_frag_count;
     }
 
+    // The fragments of the sample up to the last one available in the writer which were not
+    // received are requested without waiting for the next heartbeat
+    pub fn announced_fragments_update(
+        &mut self,
+        writer_sn: SequenceNumber,
+        last_fragment_num: FragmentNumber,
+    ) {
+        if writer_sn <= self.available_changes_max() || self.is_irrelevant(writer_sn) {
+            return;
+        }
+        let announced_fragment_num = self.announced_fragments.entry(writer_sn).or_default();
+        *announced_fragment_num = max(*announced_fragment_num, last_fragment_num);
+        if !self.missing_fragments(writer_sn).is_empty() {
+            self.must_send_nack_frags = true;
+        }
+    }
+
+    pub fn must_send_nack_frags(&self) -> bool {
+        self.must_send_nack_frags
+    }
+
+    // The fragments of a sample which are known to exist and were not received. Without any
+    // fragment received only the announced fragments are known to exist. The fragments are
+    // limited to the 256 which fit in a fragment number set.
+    fn missing_fragments(&self, seq_num: SequenceNumber) -> Vec<FragmentNumber> {
+        let fragmented_sample = self.frag_buffer.get(&seq_num);
+        let last_fragment_num = match (
+            fragmented_sample.map(|s| s.total_fragments),
+            self.announced_fragments.get(&seq_num),
+        ) {
+            (Some(total_fragments_expected), Some(&announced_fragment_num)) => {
+                total_fragments_expected.min(announced_fragment_num)
+            }
+            (Some(total_fragments_expected), None) => total_fragments_expected,
+            (None, Some(&announced_fragment_num)) => announced_fragment_num,
+            (None, None) => 0,
+        };
+        let mut missing_fragment_number = (1..=last_fragment_num).filter(|&fragment_number| {
+            !fragmented_sample.is_some_and(|s| s.is_fragment_received(fragment_number))
+        });
+        match missing_fragment_number.next() {
+            Some(first_missing_fragment) => std::iter::once(first_missing_fragment)
+                .chain(missing_fragment_number.take_while(|&n| n < first_missing_fragment + 256))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn nack_frag_submessages(&mut self, reader_guid: &Guid) -> Vec<NackFragSubmessage> {
+        let available_changes_max = self.available_changes_max();
+        self.announced_fragments
+            .retain(|&seq_num, _| seq_num > available_changes_max);
+
+        let mut seq_num_list: Vec<_> = self
+            .frag_buffer
+            .keys()
+            .chain(self.announced_fragments.keys())
+            .copied()
+            .collect();
+        seq_num_list.sort();
+        seq_num_list.dedup();
+
+        let mut submessages = Vec::new();
+        for seq_num in seq_num_list {
+            let missing_fragment_number = self.missing_fragments(seq_num);
+            if !missing_fragment_number.is_empty() {
+                self.nack_frag_count = self.nack_frag_count.wrapping_add(1);
+                let nack_frag_submessage = NackFragSubmessage::new(
+                    reader_guid.entity_id(),
+                    self.remote_writer_guid().entity_id(),
+                    seq_num,
+                    FragmentNumberSet::new(missing_fragment_number[0], missing_fragment_number),
+                    self.nack_frag_count,
+                );
+                submessages.push(nack_frag_submessage);
+            }
+        }
+        submessages
+    }
+
     pub fn acknack_count(&self) -> Count {
         self.acknack_count
     }
@@ -232,64 +483,436 @@ impl RtpsWriterProxy {
         self.acknack_count = self.acknack_count.wrapping_add(1);
     }
 
+    // Delays the acknack answering the last heartbeat. A response already scheduled
+    // is not postponed by the heartbeats received in the meantime.
+    pub fn schedule_heartbeat_response(&mut self, delay: std::time::Duration) {
+        self.heartbeat_response_deadline
+            .get_or_insert_with(|| std::time::Instant::now() + delay);
+    }
+
     pub fn send_message(&mut self, reader_guid: &Guid, message_sender: &MessageSender) {
-        if self.must_send_acknacks() || !self.missing_changes().count() == 0 {
+        if self
+            .heartbeat_response_deadline
+            .is_some_and(|deadline| std::time::Instant::now() < deadline)
+        {
+            return;
+        }
+        self.heartbeat_response_deadline = None;
+
+        if self.disable_positive_acks
+            && self.missing_changes().next().is_none()
+            && !self.must_send_nack_frags
+        {
+            // Without positive acknowledgments the writer is only notified of missing changes
+            self.set_must_send_acknacks(false);
+            return;
+        }
+
+        if self.must_send_acknacks() {
             self.set_must_send_acknacks(false);
             self.increment_acknack_count();
 
             let info_dst_submessage =
                 InfoDestinationSubmessage::new(self.remote_writer_guid().prefix());
+            let nack_frag_submessages = self.nack_frag_submessages(reader_guid);
 
+            // The final flag is only cleared when changes are missing so that the writer
+            // responds with a heartbeat after sending the repairs. The changes partially
+            // received are not requested as a whole since their missing fragments are.
+            let final_flag = self.missing_changes().next().is_none();
+            let base = self.available_changes_max() + 1;
             let acknack_submessage = AckNackSubmessage::new(
-                true,
+                final_flag,
                 reader_guid.entity_id(),
                 self.remote_writer_guid().entity_id(),
                 SequenceNumberSet::new(
-                    self.available_changes_max() + 1,
-                    self.missing_changes().take(256),
+                    base,
+                    self.missing_changes()
+                        .filter(|&seq_num| {
+                            !nack_frag_submessages
+                                .iter()
+                                .any(|n| n.writer_sn() == seq_num)
+                        })
+                        .take_while(|&seq_num| seq_num < base + 256),
                 ),
                 self.acknack_count(),
             );
 
             let mut submessages: Vec<Box<dyn Submessage + Send>> =
                 vec![Box::new(info_dst_submessage), Box::new(acknack_submessage)];
+            for nack_frag_submessage in nack_frag_submessages {
+                submessages.push(Box::new(nack_frag_submessage));
+            }
 
-            for (seq_num, owning_data_frag_list) in self.frag_buffer.iter() {
-                let total_fragments_expected = total_fragments_expected(&owning_data_frag_list[0]);
-                let mut missing_fragment_number = Vec::new();
-                for fragment_number in 1..=total_fragments_expected {
-                    if !owning_data_frag_list.iter().any(|x| {
-                        fragment_number >= x.fragment_starting_num()
-                            && fragment_number
-                                < x.fragment_starting_num() + (x.fragments_in_submessage() as u32)
-                    }) {
-                        missing_fragment_number.push(fragment_number)
-                    }
-                }
-
-                if !missing_fragment_number.is_empty() {
-                    self.nack_frag_count = self.nack_frag_count.wrapping_add(1);
-                    let nack_frag_submessage = NackFragSubmessage::new(
-                        reader_guid.entity_id(),
-                        self.remote_writer_guid().entity_id(),
-                        *seq_num,
-                        FragmentNumberSet::new(
-                            missing_fragment_number[0],
-                            missing_fragment_number.into_iter(),
-                        ),
-                        self.nack_frag_count,
-                    );
-
-                    submessages.push(Box::new(nack_frag_submessage))
+            message_sender.write_message(submessages, self.unicast_locator_list().to_vec());
+        } else if self.must_send_nack_frags {
+            // The fragments announced by a HEARTBEAT_FRAG are requested on their own
+            let nack_frag_submessages = self.nack_frag_submessages(reader_guid);
+            if !nack_frag_submessages.is_empty() {
+                let info_dst_submessage =
+                    InfoDestinationSubmessage::new(self.remote_writer_guid().prefix());
+                let mut submessages: Vec<Box<dyn Submessage + Send>> =
+                    vec![Box::new(info_dst_submessage)];
+                for nack_frag_submessage in nack_frag_submessages {
+                    submessages.push(Box::new(nack_frag_submessage));
                 }
+                message_sender.write_message(submessages, self.unicast_locator_list().to_vec());
             }
-
-            message_sender.write_message(&submessages, self.unicast_locator_list().to_vec());
         }
+        self.must_send_nack_frags = false;
     }
 
     pub fn is_historical_data_received(&self) -> bool {
         let at_least_one_heartbeat_received = self.last_received_heartbeat_count > 0;
-        at_least_one_heartbeat_received && self.missing_changes().count() == 0
+        at_least_one_heartbeat_received && self.missing_changes().next().is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rtps::messages::{
+            overall_structure::{RtpsMessageRead, RtpsSubmessageReadKind},
+            submessage_elements::ParameterList,
+        },
+        transport::types::{ENTITYID_UNKNOWN, GUID_UNKNOWN, LOCATOR_KIND_UDP_V4},
+    };
+    use std::net::UdpSocket;
+
+    #[test]
+    fn scheduled_heartbeat_response_is_sent_after_delay() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver.set_nonblocking(true).unwrap();
+        let locator = Locator::new(
+            LOCATOR_KIND_UDP_V4,
+            receiver.local_addr().unwrap().port() as u32,
+            [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+        );
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let mut writer_proxy = RtpsWriterProxy::new(
+            GUID_UNKNOWN,
+            &[locator],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+            ReliabilityKind::Reliable,
+            false,
+        );
+        let mut buf = [0; 1024];
+
+        writer_proxy.set_must_send_acknacks(true);
+        writer_proxy.schedule_heartbeat_response(std::time::Duration::from_millis(100));
+        writer_proxy.send_message(&GUID_UNKNOWN, &message_sender);
+        assert!(writer_proxy.must_send_acknacks());
+        assert!(receiver.recv(&mut buf).is_err());
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        writer_proxy.send_message(&GUID_UNKNOWN, &message_sender);
+        assert!(!writer_proxy.must_send_acknacks());
+        receiver.set_nonblocking(false).unwrap();
+        receiver
+            .set_read_timeout(Some(std::time::Duration::from_secs(1)))
+            .unwrap();
+        assert!(receiver.recv(&mut buf).is_ok());
+    }
+
+    #[test]
+    fn acknack_is_only_final_when_no_changes_are_missing() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(std::time::Duration::from_secs(1)))
+            .unwrap();
+        let locator = Locator::new(
+            LOCATOR_KIND_UDP_V4,
+            receiver.local_addr().unwrap().port() as u32,
+            [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+        );
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let mut writer_proxy = RtpsWriterProxy::new(
+            GUID_UNKNOWN,
+            &[locator],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+            ReliabilityKind::Reliable,
+            false,
+        );
+        let received_acknack = || {
+            let mut buf = [0; 1024];
+            let len = receiver.recv(&mut buf).unwrap();
+            RtpsMessageRead::try_from(&buf[..len])
+                .unwrap()
+                .submessages()
+                .into_iter()
+                .find_map(|s| match s {
+                    RtpsSubmessageReadKind::AckNack(a) => Some(a),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        // The changes announced by the heartbeat and not yet received are requested with a non-final acknack
+        writer_proxy.missing_changes_update(2);
+        writer_proxy.set_must_send_acknacks(true);
+        writer_proxy.send_message(&GUID_UNKNOWN, &message_sender);
+        let acknack = received_acknack();
+        assert!(!acknack.final_flag());
+        assert_eq!(
+            acknack.reader_sn_state().set().collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        writer_proxy.received_change_set(1);
+        writer_proxy.received_change_set(2);
+        writer_proxy.set_must_send_acknacks(true);
+        writer_proxy.send_message(&GUID_UNKNOWN, &message_sender);
+        let acknack = received_acknack();
+        assert!(acknack.final_flag());
+        assert_eq!(acknack.reader_sn_state().base(), 3);
+    }
+
+    fn data_frag(
+        writer_sn: SequenceNumber,
+        fragment_starting_num: u32,
+        data_size: u32,
+    ) -> DataFragSubmessage {
+        DataFragSubmessage::new(
+            false,
+            false,
+            false,
+            ENTITYID_UNKNOWN,
+            ENTITYID_UNKNOWN,
+            writer_sn,
+            fragment_starting_num,
+            1,
+            4,
+            data_size,
+            ParameterList::empty(),
+            [1, 2, 3, 4][..].into(),
+        )
+    }
+
+    #[test]
+    fn fragments_announced_by_heartbeat_frag_and_not_received_are_requested() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(std::time::Duration::from_secs(1)))
+            .unwrap();
+        let locator = Locator::new(
+            LOCATOR_KIND_UDP_V4,
+            receiver.local_addr().unwrap().port() as u32,
+            [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+        );
+        let message_sender =
+            MessageSender::new([1; 12], UdpSocket::bind("127.0.0.1:0").unwrap(), 65507);
+        let mut writer_proxy = RtpsWriterProxy::new(
+            GUID_UNKNOWN,
+            &[locator],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+            ReliabilityKind::Reliable,
+            false,
+        );
+
+        writer_proxy.push_data_frag(data_frag(1, 1, 16), 10, 10);
+        writer_proxy.push_data_frag(data_frag(1, 3, 16), 10, 10);
+        writer_proxy.announced_fragments_update(1, 3);
+        assert!(writer_proxy.must_send_nack_frags());
+        writer_proxy.send_message(&GUID_UNKNOWN, &message_sender);
+        assert!(!writer_proxy.must_send_nack_frags());
+
+        let mut buf = [0; 1024];
+        let len = receiver.recv(&mut buf).unwrap();
+        let nack_frag_list: Vec<_> = RtpsMessageRead::try_from(&buf[..len])
+            .unwrap()
+            .submessages()
+            .into_iter()
+            .filter_map(|s| match s {
+                RtpsSubmessageReadKind::NackFrag(nack_frag) => Some(nack_frag),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(nack_frag_list.len(), 1);
+        assert_eq!(nack_frag_list[0].writer_sn(), 1);
+        // The fourth fragment is not requested since it was not announced yet
+        assert_eq!(
+            nack_frag_list[0].fragment_number_state(),
+            &FragmentNumberSet::new(2, [2])
+        );
+    }
+
+    #[test]
+    fn fragments_of_sample_above_maximum_fragments_are_discarded() {
+        let mut writer_proxy = RtpsWriterProxy::new(
+            GUID_UNKNOWN,
+            &[],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+            ReliabilityKind::Reliable,
+            false,
+        );
+
+        writer_proxy.push_data_frag(data_frag(1, 1, 12), 2, 10);
+        writer_proxy.push_data_frag(data_frag(1, 2, 12), 2, 10);
+        writer_proxy.push_data_frag(data_frag(1, 3, 12), 2, 10);
+
+        assert!(writer_proxy.reconstruct_data_from_frag(1).is_none());
+        assert!(writer_proxy.frag_buffer.is_empty());
+    }
+
+    #[test]
+    fn changes_no_longer_available_are_lost_unless_irrelevant() {
+        let mut writer_proxy = RtpsWriterProxy::new(
+            GUID_UNKNOWN,
+            &[],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+            ReliabilityKind::Reliable,
+            false,
+        );
+
+        // The changes removed before the first heartbeat were written before the match
+        assert_eq!(writer_proxy.lost_changes_update(5), 0);
+        writer_proxy.missing_changes_update(10);
+        writer_proxy.received_change_set(5);
+        writer_proxy.irrelevant_change_set(7);
+        assert_eq!(
+            writer_proxy.missing_changes().collect::<Vec<_>>(),
+            vec![6, 8, 9, 10]
+        );
+
+        assert_eq!(writer_proxy.lost_changes_update(9), 2);
+        assert_eq!(writer_proxy.available_changes_max(), 8);
+        assert_eq!(
+            writer_proxy.missing_changes().collect::<Vec<_>>(),
+            vec![9, 10]
+        );
+    }
+
+    #[test]
+    fn irrelevant_change_range_is_skipped_once_previous_changes_are_received() {
+        let mut writer_proxy = RtpsWriterProxy::new(
+            GUID_UNKNOWN,
+            &[],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+            ReliabilityKind::Reliable,
+            false,
+        );
+
+        writer_proxy.missing_changes_update(6);
+        writer_proxy.irrelevant_change_range_set(3, 5);
+        assert_eq!(writer_proxy.available_changes_max(), 0);
+        assert_eq!(
+            writer_proxy.missing_changes().collect::<Vec<_>>(),
+            vec![1, 2, 6]
+        );
+
+        writer_proxy.received_change_set(1);
+        writer_proxy.received_change_set(2);
+        assert_eq!(writer_proxy.available_changes_max(), 5);
+        assert_eq!(writer_proxy.missing_changes().collect::<Vec<_>>(), vec![6]);
+    }
+
+    #[test]
+    fn oldest_pending_fragmented_sample_is_evicted() {
+        let mut writer_proxy = RtpsWriterProxy::new(
+            GUID_UNKNOWN,
+            &[],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+            ReliabilityKind::Reliable,
+            false,
+        );
+
+        writer_proxy.push_data_frag(data_frag(1, 1, 8), 10, 2);
+        writer_proxy.push_data_frag(data_frag(2, 1, 8), 10, 2);
+        writer_proxy.push_data_frag(data_frag(3, 1, 8), 10, 2);
+        writer_proxy.push_data_frag(data_frag(1, 2, 8), 10, 2);
+        writer_proxy.push_data_frag(data_frag(3, 2, 8), 10, 2);
+
+        assert!(writer_proxy.reconstruct_data_from_frag(1).is_none());
+        assert!(writer_proxy.reconstruct_data_from_frag(2).is_none());
+        assert_eq!(
+            writer_proxy
+                .reconstruct_data_from_frag(3)
+                .unwrap()
+                .serialized_payload()
+                .as_ref(),
+            &[1, 2, 3, 4, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn fragments_received_out_of_order_and_overlapping_are_reassembled() {
+        let mut writer_proxy = RtpsWriterProxy::new(
+            GUID_UNKNOWN,
+            &[],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+            ReliabilityKind::Reliable,
+            false,
+        );
+        let two_fragments = DataFragSubmessage::new(
+            false,
+            false,
+            false,
+            ENTITYID_UNKNOWN,
+            ENTITYID_UNKNOWN,
+            1,
+            2,
+            2,
+            4,
+            10,
+            ParameterList::empty(),
+            [5, 6, 7, 8, 9, 10, 0, 0][..].into(),
+        );
+
+        writer_proxy.push_data_frag(two_fragments, 10, 10);
+        writer_proxy.push_data_frag(data_frag(1, 2, 10), 10, 10);
+        assert!(writer_proxy.reconstruct_data_from_frag(1).is_none());
+        assert_eq!(writer_proxy.missing_fragments(1), vec![1]);
+
+        writer_proxy.push_data_frag(data_frag(1, 1, 10), 10, 10);
+        assert_eq!(
+            writer_proxy
+                .reconstruct_data_from_frag(1)
+                .unwrap()
+                .serialized_payload()
+                .as_ref(),
+            &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]
+        );
+    }
+
+    #[test]
+    fn stale_fragmented_samples_are_discarded() {
+        let mut writer_proxy = RtpsWriterProxy::new(
+            GUID_UNKNOWN,
+            &[],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+            ReliabilityKind::Reliable,
+            false,
+        );
+
+        writer_proxy.push_data_frag(data_frag(1, 1, 8), 10, 10);
+        writer_proxy.discard_stale_fragmented_samples(std::time::Duration::from_secs(10));
+        assert_eq!(writer_proxy.frag_buffer.len(), 1);
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        writer_proxy.discard_stale_fragmented_samples(std::time::Duration::from_millis(10));
+        assert!(writer_proxy.frag_buffer.is_empty());
+        writer_proxy.push_data_frag(data_frag(1, 2, 8), 10, 10);
+        assert!(writer_proxy.reconstruct_data_from_frag(1).is_none());
     }
 }
@@ -1,9 +1,12 @@
 use super::{
     executor::ExecutorHandle,
-    mpsc::{mpsc_channel, MpscReceiver, MpscSender},
+    mpsc::{mpsc_channel_with_capacity, MpscReceiver, MpscSender, MpscSenderError},
     oneshot::{oneshot, OneshotReceiver, OneshotSender},
 };
-use crate::infrastructure::error::{DdsError, DdsResult};
+use crate::{
+    configuration::MailboxOverflowPolicy,
+    infrastructure::error::{DdsError, DdsResult},
+};
 
 pub trait Mail {
     type Result;
@@ -91,11 +94,22 @@ impl<A> ActorAddress<A> {
                 mail: Some(mail),
                 reply_sender: Some(reply_sender),
             }))
-            .map_err(|_| DdsError::AlreadyDeleted)?;
+            .map_err(|e| match e {
+                MpscSenderError::Closed => DdsError::AlreadyDeleted,
+                MpscSenderError::Full => DdsError::OutOfResources,
+            })?;
         Ok(ReplyReceiver { reply_receiver })
     }
 }
 
+// Capacity of the mailbox of an actor, [`None`] meaning that it is unbounded, and behavior of the
+// mailbox once it is full
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Mailbox {
+    pub capacity: Option<usize>,
+    pub overflow_policy: MailboxOverflowPolicy,
+}
+
 pub struct Actor<A> {
     mail_sender: MpscSender<Box<dyn GenericHandler<A> + Send>>,
     // join_handle: tokio::task::JoinHandle<()>,
@@ -105,15 +119,12 @@ impl<A> Actor<A>
 where
     A: Send + 'static,
 {
-    pub fn spawn(mut actor: A, runtime: &ExecutorHandle) -> Self {
-        let (mail_sender, mailbox_recv) = mpsc_channel::<Box<dyn GenericHandler<A> + Send>>();
+    pub fn spawn(actor: A, runtime: &ExecutorHandle) -> Self {
+        Self::spawn_with_mailbox(actor, Mailbox::default(), runtime)
+    }
 
-        runtime.spawn(async move {
-            while let Some(mut m) = mailbox_recv.recv().await {
-                m.handle(&mut actor);
-            }
-        });
-        Actor { mail_sender }
+    pub fn spawn_with_mailbox(actor: A, mailbox: Mailbox, runtime: &ExecutorHandle) -> Self {
+        ActorBuilder::with_mailbox(mailbox).build(actor, runtime)
     }
 
     pub fn address(&self) -> ActorAddress<A> {
@@ -126,7 +137,7 @@ where
         self.mail_sender.close();
     }
 
-    pub fn send_actor_mail<M>(&self, mail: M) -> ReplyReceiver<M>
+    pub fn send_actor_mail<M>(&self, mail: M) -> DdsResult<ReplyReceiver<M>>
     where
         A: MailHandler<M>,
         M: Mail + Send + 'static,
@@ -138,8 +149,11 @@ where
                 mail: Some(mail),
                 reply_sender: Some(reply_sender),
             }))
-            .expect("Message will always be sent when actor exists");
-        ReplyReceiver { reply_receiver }
+            .map_err(|e| match e {
+                MpscSenderError::Closed => DdsError::AlreadyDeleted,
+                MpscSenderError::Full => DdsError::OutOfResources,
+            })?;
+        Ok(ReplyReceiver { reply_receiver })
     }
 }
 
@@ -152,8 +166,10 @@ impl<A> ActorBuilder<A>
 where
     A: Send + 'static,
 {
-    pub fn new() -> Self {
-        let (mail_sender, mailbox_recv) = mpsc_channel::<Box<dyn GenericHandler<A> + Send>>();
+    pub fn with_mailbox(mailbox: Mailbox) -> Self {
+        let (mail_sender, mailbox_recv) = mpsc_channel_with_capacity::<
+            Box<dyn GenericHandler<A> + Send>,
+        >(mailbox.capacity, mailbox.overflow_policy);
 
         Self {
             mail_sender,
@@ -213,6 +229,7 @@ mod tests {
             block_on(async {
                 actor
                     .send_actor_mail(Increment { value: 10 })
+                    .unwrap()
                     .receive_reply()
                     .await
             }),
@@ -230,6 +247,7 @@ mod tests {
             block_on(async {
                 actor
                     .send_actor_mail(Increment { value: 10 })
+                    .unwrap()
                     .receive_reply()
                     .await
             }),
@@ -250,6 +268,7 @@ mod tests {
             block_on(async {
                 actor
                     .send_actor_mail(Increment { value: 10 })
+                    .unwrap()
                     .receive_reply()
                     .await
             }),
@@ -262,4 +281,88 @@ mod tests {
             .send_actor_mail(Increment { value: 10 })
             .is_err());
     }
+
+    #[test]
+    fn full_mailbox_drops_mail_with_drop_policy() {
+        let actor_builder = ActorBuilder::<MyActor>::with_mailbox(Mailbox {
+            capacity: Some(1),
+            overflow_policy: MailboxOverflowPolicy::Drop,
+        });
+        let actor_address = actor_builder.address();
+        let reply = actor_address
+            .send_actor_mail(Increment { value: 10 })
+            .unwrap();
+        assert!(matches!(
+            actor_address.send_actor_mail(Increment { value: 10 }),
+            Err(DdsError::OutOfResources)
+        ));
+
+        let executor = Executor::new();
+        let _actor = actor_builder.build(MyActor { data: 0 }, &executor.handle());
+        assert_eq!(block_on(reply.receive_reply()), 10);
+    }
+
+    pub struct Wait {
+        resume: std::sync::mpsc::Receiver<()>,
+    }
+    impl Mail for Wait {
+        type Result = ();
+    }
+    impl MailHandler<Wait> for MyActor {
+        fn handle(&mut self, message: Wait) -> <Wait as Mail>::Result {
+            message.resume.recv().ok();
+        }
+    }
+
+    #[test]
+    fn full_mailbox_returns_error_to_actor_sender_with_drop_policy() {
+        let executor = Executor::new();
+        let actor = Actor::spawn_with_mailbox(
+            MyActor { data: 0 },
+            Mailbox {
+                capacity: Some(1),
+                overflow_policy: MailboxOverflowPolicy::Drop,
+            },
+            &executor.handle(),
+        );
+        let (resume_sender, resume) = std::sync::mpsc::channel();
+        let wait_reply = actor.send_actor_mail(Wait { resume }).unwrap();
+        // Whether or not the actor already took the wait mail, the mailbox is full after this one
+        actor.send_actor_mail(Increment { value: 10 }).ok();
+
+        assert!(matches!(
+            actor.send_actor_mail(Increment { value: 10 }),
+            Err(DdsError::OutOfResources)
+        ));
+
+        resume_sender.send(()).unwrap();
+        block_on(wait_reply.receive_reply());
+    }
+
+    #[test]
+    fn full_mailbox_blocks_sender_until_there_is_room_with_block_policy() {
+        let actor_builder = ActorBuilder::<MyActor>::with_mailbox(Mailbox {
+            capacity: Some(1),
+            overflow_policy: MailboxOverflowPolicy::Block,
+        });
+        let actor_address = actor_builder.address();
+        let reply = actor_address
+            .send_actor_mail(Increment { value: 10 })
+            .unwrap();
+        let sender_thread = std::thread::spawn(move || {
+            block_on(
+                actor_address
+                    .send_actor_mail(Increment { value: 5 })
+                    .unwrap()
+                    .receive_reply(),
+            )
+        });
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(!sender_thread.is_finished());
+
+        let executor = Executor::new();
+        let _actor = actor_builder.build(MyActor { data: 0 }, &executor.handle());
+        assert_eq!(block_on(reply.receive_reply()), 10);
+        assert_eq!(sender_thread.join().unwrap(), 15);
+    }
 }
@@ -1,8 +1,9 @@
 use std::{
+    cell::Cell,
     future::Future,
     pin::{pin, Pin},
     sync::{
-        atomic::{self, AtomicBool},
+        atomic::{self, AtomicUsize},
         mpsc::{channel, Sender, TryRecvError},
         Arc, Mutex,
     },
@@ -10,6 +11,29 @@ use std::{
     thread::{self, JoinHandle, Thread},
 };
 
+thread_local! {
+    static IS_POLLING_TASK: Cell<bool> = const { Cell::new(false) };
+}
+
+// Whether the current thread is polling one of the tasks spawned by Dust DDS. Such a thread must never
+// wait for another task since that task might only progress on the same thread.
+pub fn is_polling_task() -> bool {
+    IS_POLLING_TASK.get()
+}
+
+struct SpawnedTask(Pin<Box<dyn Future<Output = ()> + Send>>);
+
+impl Future for SpawnedTask {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let was_polling_task = IS_POLLING_TASK.replace(true);
+        let poll = self.0.as_mut().poll(cx);
+        IS_POLLING_TASK.set(was_polling_task);
+        poll
+    }
+}
+
 pub fn block_on<T>(f: impl Future<Output = T>) -> T {
     struct ThreadWake(Thread);
     impl Wake for ThreadWake {
@@ -36,88 +60,153 @@ pub struct Task {
     future: Mutex<Pin<Box<dyn Future<Output = ()> + Send>>>,
     task_sender: Sender<Arc<Task>>,
     thread_handle: Thread,
-    abort: AtomicBool,
 }
 
-impl Task {
-    fn abort(&self) {
-        self.abort.store(true, atomic::Ordering::Release);
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
     }
 
-    fn is_aborted(&self) -> bool {
-        self.abort.load(atomic::Ordering::Acquire)
+    fn wake_by_ref(self: &Arc<Self>) {
+        // The executor thread is gone if it panicked, in which case the task is never run again
+        self.task_sender.send(self.clone()).ok();
+        self.thread_handle.unpark();
     }
 }
 
-impl Wake for Task {
-    fn wake(self: Arc<Self>) {
-        self.wake_by_ref()
+/// Async runtime on which Dust DDS spawns the tasks of the factory and of the participants. The timers and the
+/// network sockets run on threads of their own so spawning the tasks is all that is needed from the runtime.
+/// Implementations are provided for the [Tokio](https://crates.io/crates/tokio), [smol](https://crates.io/crates/smol)
+/// and [async-std](https://crates.io/crates/async-std) runtimes when the feature with the same name is enabled.
+pub trait Spawner: Send + Sync + 'static {
+    /// Spawn the task on the runtime. The task is never awaited by Dust DDS so it must be polled to completion by the
+    /// runtime itself.
+    fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>);
+}
+
+#[cfg(feature = "tokio")]
+impl Spawner for tokio::runtime::Handle {
+    fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+        tokio::runtime::Handle::spawn(self, task);
     }
+}
 
-    fn wake_by_ref(self: &Arc<Self>) {
-        if !self.is_aborted() {
-            self.task_sender.send(self.clone()).unwrap();
-            self.thread_handle.unpark();
-        }
+#[cfg(feature = "smol")]
+impl Spawner for Arc<smol::Executor<'static>> {
+    fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+        smol::Executor::spawn(self, task).detach();
     }
 }
 
-pub struct TaskHandle {
-    task: Arc<Task>,
+/// [`Spawner`] which spawns the tasks on the global executor of [smol](https://crates.io/crates/smol).
+#[cfg(feature = "smol")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SmolSpawner;
+
+#[cfg(feature = "smol")]
+impl Spawner for SmolSpawner {
+    fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+        smol::spawn(task).detach();
+    }
 }
 
-impl TaskHandle {
-    pub fn abort(&self) {
-        self.task.abort()
+/// [`Spawner`] which spawns the tasks on the global executor of [async-std](https://crates.io/crates/async-std).
+#[cfg(feature = "async-std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsyncStdSpawner;
+
+#[cfg(feature = "async-std")]
+impl Spawner for AsyncStdSpawner {
+    fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+        // Dropping the join handle detaches the task
+        async_std::task::spawn(task);
     }
 }
 
 #[derive(Clone)]
-pub struct ExecutorHandle {
-    task_sender: Sender<Arc<Task>>,
-    thread_handle: Thread,
+pub enum ExecutorHandle {
+    Thread {
+        task_sender: Sender<Arc<Task>>,
+        thread_handle: Thread,
+    },
+    Spawner(Arc<dyn Spawner>),
 }
 
 impl ExecutorHandle {
-    pub fn spawn(&self, f: impl Future<Output = ()> + Send + 'static) -> TaskHandle {
-        let future = Box::pin(f);
-        let task = Arc::new(Task {
-            future: Mutex::new(future),
-            task_sender: self.task_sender.clone(),
-            thread_handle: self.thread_handle.clone(),
-            abort: AtomicBool::new(false),
-        });
-        self.task_sender
-            .send(task.clone())
-            .expect("Should never fail to send");
-        self.thread_handle.unpark();
-        TaskHandle { task }
+    pub fn spawn(&self, f: impl Future<Output = ()> + Send + 'static) {
+        let future = Box::pin(SpawnedTask(Box::pin(f)));
+        match self {
+            ExecutorHandle::Thread {
+                task_sender,
+                thread_handle,
+            } => {
+                let task = Arc::new(Task {
+                    future: Mutex::new(future),
+                    task_sender: task_sender.clone(),
+                    thread_handle: thread_handle.clone(),
+                });
+                task_sender.send(task).expect("Should never fail to send");
+                thread_handle.unpark();
+            }
+            ExecutorHandle::Spawner(spawner) => spawner.spawn(future),
+        }
     }
 }
 
-pub struct Executor {
-    task_sender: Sender<Arc<Task>>,
-    executor_thread_handle: JoinHandle<()>,
+/// Runtime on which the executors of the factory and of the participant backends run their tasks.
+#[derive(Clone, Default)]
+pub enum RuntimeHandle {
+    /// Each executor runs its tasks on its own thread
+    #[default]
+    Threads,
+    /// All the executors share the threads of the pool
+    ThreadPool(Arc<ExecutorPool>),
+    /// All the executors spawn their tasks on an existing async runtime
+    Spawner(Arc<dyn Spawner>),
+}
+
+impl RuntimeHandle {
+    pub fn executor(&self) -> Executor {
+        match self {
+            RuntimeHandle::Threads => Executor::new(),
+            RuntimeHandle::ThreadPool(pool) => Executor::Shared(pool.handle()),
+            RuntimeHandle::Spawner(spawner) => {
+                Executor::Shared(ExecutorHandle::Spawner(spawner.clone()))
+            }
+        }
+    }
+}
+
+pub enum Executor {
+    Thread {
+        task_sender: Sender<Arc<Task>>,
+        executor_thread_handle: JoinHandle<()>,
+    },
+    // Runs its tasks on the thread of an executor owned by someone else, which keeps running
+    // as long as any of its handles exists
+    Shared(ExecutorHandle),
 }
 
 impl Executor {
     pub fn new() -> Self {
+        Self::with_thread_name("Dust DDS Executor".to_string())
+    }
+
+    pub fn with_thread_name(thread_name: String) -> Self {
         let (task_sender, task_receiver) = channel::<Arc<Task>>();
         let executor_thread_handle = std::thread::Builder::new()
-            .name("Dust DDS Executor".to_string())
+            .name(thread_name)
             .spawn(move || loop {
                 match task_receiver.try_recv() {
                     Ok(task) => {
-                        if !task.is_aborted() {
-                            let waker = Waker::from(task.clone());
-                            let mut cx = Context::from_waker(&waker);
-                            let _ = task
-                                .future
-                                .try_lock()
-                                .expect("Only ever locked here")
-                                .as_mut()
-                                .poll(&mut cx);
-                        }
+                        let waker = Waker::from(task.clone());
+                        let mut cx = Context::from_waker(&waker);
+                        let _ = task
+                            .future
+                            .try_lock()
+                            .expect("Only ever locked here")
+                            .as_mut()
+                            .poll(&mut cx);
                     }
                     Err(TryRecvError::Empty) => thread::park(),
                     Err(TryRecvError::Disconnected) => break,
@@ -125,16 +214,57 @@ impl Executor {
             })
             .expect("failed to spawn thread");
 
-        Self {
+        Self::Thread {
             task_sender,
             executor_thread_handle,
         }
     }
 
     pub fn handle(&self) -> ExecutorHandle {
-        ExecutorHandle {
-            task_sender: self.task_sender.clone(),
-            thread_handle: self.executor_thread_handle.thread().clone(),
+        match self {
+            Executor::Thread {
+                task_sender,
+                executor_thread_handle,
+            } => ExecutorHandle::Thread {
+                task_sender: task_sender.clone(),
+                thread_handle: executor_thread_handle.thread().clone(),
+            },
+            Executor::Shared(handle) => handle.clone(),
+        }
+    }
+}
+
+pub struct ExecutorPool {
+    executor_list: Vec<Executor>,
+    next_executor: AtomicUsize,
+}
+
+impl ExecutorPool {
+    pub fn new(number_of_threads: usize) -> Self {
+        Self::with_thread_name(number_of_threads, "Dust DDS Executor")
+    }
+
+    pub fn with_thread_name(number_of_threads: usize, thread_name: &str) -> Self {
+        Self {
+            executor_list: (0..number_of_threads.max(1))
+                .map(|_| Executor::with_thread_name(thread_name.to_string()))
+                .collect(),
+            next_executor: AtomicUsize::new(0),
         }
     }
+
+    pub fn with_spawner(spawner: Arc<dyn Spawner>) -> Self {
+        Self {
+            executor_list: vec![Executor::Shared(ExecutorHandle::Spawner(spawner))],
+            next_executor: AtomicUsize::new(0),
+        }
+    }
+
+    // Each call returns the handle of the next executor in the pool so that the spawned tasks are distributed
+    // among the threads. A task always runs to completion on the thread it was spawned on.
+    pub fn handle(&self) -> ExecutorHandle {
+        let index =
+            self.next_executor.fetch_add(1, atomic::Ordering::Relaxed) % self.executor_list.len();
+        self.executor_list[index].handle()
+    }
 }
@@ -2,21 +2,40 @@ use std::{
     collections::VecDeque,
     future::Future,
     pin::Pin,
-    sync::{Arc, Mutex},
+    sync::{Arc, Condvar, Mutex},
     task::{Context, Poll, Waker},
 };
 
+use super::executor::is_polling_task;
+use crate::configuration::MailboxOverflowPolicy;
+
 pub fn mpsc_channel<T>() -> (MpscSender<T>, MpscReceiver<T>) {
+    mpsc_channel_with_capacity(None, MailboxOverflowPolicy::Block)
+}
+
+// Channel holding at most `capacity` values, [`None`] meaning that it is unbounded. The overflow
+// policy decides what happens to the values sent while the channel is full.
+pub fn mpsc_channel_with_capacity<T>(
+    capacity: Option<usize>,
+    overflow_policy: MailboxOverflowPolicy,
+) -> (MpscSender<T>, MpscReceiver<T>) {
     let inner = Arc::new(Mutex::new(MpscInner {
         data: VecDeque::with_capacity(64),
         waker: None,
         is_closed: false,
+        capacity,
+        overflow_policy,
     }));
+    let space_available = Arc::new(Condvar::new());
     (
         MpscSender {
             inner: inner.clone(),
+            space_available: space_available.clone(),
+        },
+        MpscReceiver {
+            inner,
+            space_available,
         },
-        MpscReceiver { inner },
     )
 }
 
@@ -24,6 +43,8 @@ struct MpscInner<T> {
     data: VecDeque<T>,
     waker: Option<Waker>,
     is_closed: bool,
+    capacity: Option<usize>,
+    overflow_policy: MailboxOverflowPolicy,
 }
 
 impl<T> MpscInner<T> {
@@ -47,16 +68,19 @@ impl<T> std::fmt::Debug for MpscInner<T> {
 #[derive(Debug)]
 pub enum MpscSenderError {
     Closed,
+    Full,
 }
 
 pub struct MpscSender<T> {
     inner: Arc<Mutex<MpscInner<T>>>,
+    space_available: Arc<Condvar>,
 }
 
 impl<T> Clone for MpscSender<T> {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
+            space_available: self.space_available.clone(),
         }
     }
 }
@@ -72,6 +96,22 @@ impl<T> std::fmt::Debug for MpscSender<T> {
 impl<T> MpscSender<T> {
     pub fn send(&self, value: T) -> Result<(), MpscSenderError> {
         let mut inner_lock = self.inner.lock().expect("Mutex shouldn't be poisoned");
+        if let Some(capacity) = inner_lock.capacity {
+            if inner_lock.data.len() >= capacity && !inner_lock.is_closed {
+                match inner_lock.overflow_policy {
+                    MailboxOverflowPolicy::Drop => return Err(MpscSenderError::Full),
+                    // A task waiting for room could prevent the receiver from ever running so the
+                    // values sent by the tasks are accepted above the capacity
+                    MailboxOverflowPolicy::Block if !is_polling_task() => {
+                        inner_lock = self
+                            .space_available
+                            .wait_while(inner_lock, |i| i.data.len() >= capacity && !i.is_closed)
+                            .expect("Mutex shouldn't be poisoned");
+                    }
+                    MailboxOverflowPolicy::Block => (),
+                }
+            }
+        }
         if inner_lock.is_closed {
             Err(MpscSenderError::Closed)
         } else {
@@ -88,6 +128,7 @@ impl<T> MpscSender<T> {
             .lock()
             .expect("Mutex shouldn't be poisoned")
             .close();
+        self.space_available.notify_all();
     }
 
     pub fn is_closed(&self) -> bool {
@@ -100,19 +141,30 @@ impl<T> MpscSender<T> {
 
 pub struct MpscReceiver<T> {
     inner: Arc<Mutex<MpscInner<T>>>,
+    space_available: Arc<Condvar>,
 }
 
 impl<T> MpscReceiver<T> {
     pub async fn recv(&self) -> Option<T> {
         MpscReceiverFuture {
             inner: self.inner.clone(),
+            space_available: self.space_available.clone(),
         }
         .await
     }
+
+    pub fn close(&self) {
+        self.inner
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .close();
+        self.space_available.notify_all();
+    }
 }
 
 struct MpscReceiverFuture<T> {
     inner: Arc<Mutex<MpscInner<T>>>,
+    space_available: Arc<Condvar>,
 }
 
 impl<T> Future for MpscReceiverFuture<T> {
@@ -121,6 +173,9 @@ impl<T> Future for MpscReceiverFuture<T> {
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let mut inner_lock = self.inner.lock().expect("Mutex shouldn't be poisoned");
         if let Some(value) = inner_lock.data.pop_front() {
+            if inner_lock.capacity.is_some() {
+                self.space_available.notify_one();
+            }
             Poll::Ready(Some(value))
         } else if inner_lock.is_closed {
             Poll::Ready(None)
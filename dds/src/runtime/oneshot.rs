@@ -34,6 +34,12 @@ pub struct OneshotSender<T> {
     inner: Arc<Mutex<OneshotInner<T>>>,
 }
 
+impl<T> std::fmt::Debug for OneshotSender<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OneshotSender").finish_non_exhaustive()
+    }
+}
+
 impl<T> OneshotSender<T> {
     pub fn send(self, value: T) {
         {
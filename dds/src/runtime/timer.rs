@@ -1,8 +1,11 @@
 use std::{
-    collections::BinaryHeap,
+    collections::HashMap,
     future::{poll_fn, Future},
     pin::{pin, Pin},
-    sync::{mpsc::RecvTimeoutError, Arc, Mutex},
+    sync::{
+        mpsc::{RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
     task::{Context, Poll, Waker},
     thread::JoinHandle,
     time::{Duration, Instant},
@@ -14,33 +17,24 @@ pub struct TimerWake {
     waker: Waker,
 }
 
-impl PartialEq for TimerWake {
-    fn eq(&self, other: &Self) -> bool {
-        self.id == other.id && self.deadline == other.deadline
-    }
-}
-
-impl Eq for TimerWake {}
-
-impl PartialOrd for TimerWake {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+struct ScheduledTask {
+    id: usize,
+    deadline: Instant,
+    period: Option<Duration>,
+    callback: Box<dyn FnMut() + Send>,
 }
 
-impl Ord for TimerWake {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // Reverse order compared to usual implementation
-        // since the binary heap is a max tree
-        other.deadline.cmp(&self.deadline)
-    }
+enum TimerCommand {
+    Wake(TimerWake),
+    Schedule(ScheduledTask),
+    Abort(usize),
 }
 
 pub struct Sleep {
     id: usize,
     deadline: Option<Instant>,
     duration: Duration,
-    periodic_task_sender: std::sync::mpsc::Sender<TimerWake>,
+    timer_command_sender: Sender<TimerCommand>,
 }
 
 impl Sleep {
@@ -77,8 +71,8 @@ impl Future for Sleep {
                 deadline,
                 waker: cx.waker().clone(),
             };
-            this.periodic_task_sender
-                .send(timer_wake)
+            this.timer_command_sender
+                .send(TimerCommand::Wake(timer_wake))
                 .expect("Shouldn't fail to send");
             Poll::Pending
         }
@@ -90,49 +84,196 @@ pub enum TimeoutError {
     Timeout,
 }
 
-struct TimerHeap {
-    heap: BinaryHeap<TimerWake>,
+/// Handle to a callback scheduled on the timer. Dropping the handle does not
+/// cancel the callback, [`TimerTaskHandle::abort`] has to be called explicitly.
+pub struct TimerTaskHandle {
+    id: usize,
+    timer_command_sender: Sender<TimerCommand>,
+}
+
+impl TimerTaskHandle {
+    pub fn abort(&self) {
+        self.timer_command_sender
+            .send(TimerCommand::Abort(self.id))
+            .ok();
+    }
 }
 
-impl TimerHeap {
+const SLOT_BITS: u32 = 6;
+const NUM_SLOTS: usize = 1 << SLOT_BITS;
+const SLOT_MASK: u64 = NUM_SLOTS as u64 - 1;
+// Enough levels for the wheel to cover the complete range of the u64 ticks
+const NUM_LEVELS: usize = (u64::BITS as usize).div_ceil(SLOT_BITS as usize);
+
+struct WheelEntry<T> {
+    deadline: u64,
+    level: usize,
+    slot: usize,
+    value: T,
+}
+
+struct WheelLevel {
+    occupied: u64,
+    slots: Vec<Vec<usize>>,
+}
+
+impl WheelLevel {
     fn new() -> Self {
         Self {
-            heap: BinaryHeap::with_capacity(10),
+            occupied: 0,
+            slots: (0..NUM_SLOTS).map(|_| Vec::new()).collect(),
         }
     }
 
-    #[inline(always)]
-    fn push(&mut self, item: TimerWake) {
-        self.heap.push(item)
+    fn push(&mut self, slot: usize, id: usize) {
+        self.slots[slot].push(id);
+        self.occupied |= 1 << slot;
     }
 
-    #[inline(always)]
-    fn duration_until_next_timer(&self) -> Option<Duration> {
-        if let Some(t) = self.heap.peek() {
-            let d = t.deadline.duration_since(Instant::now());
-            Some(d)
-        } else {
-            None
+    fn remove(&mut self, slot: usize, id: usize) {
+        self.slots[slot].retain(|x| x != &id);
+        if self.slots[slot].is_empty() {
+            self.occupied &= !(1 << slot);
         }
     }
 
-    #[inline(always)]
-    fn is_next_timer_elapsed(&self) -> bool {
-        matches!(self.heap.peek(), Some(t) if t.deadline < Instant::now())
+    fn take(&mut self, slot: usize) -> Vec<usize> {
+        self.occupied &= !(1 << slot);
+        std::mem::take(&mut self.slots[slot])
     }
+}
+
+/// Hierarchical timing wheel in which every level has [`NUM_SLOTS`] slots and
+/// each slot of a level spans the complete range of the level below. Inserting
+/// and removing a timer is independent of the number of timers in the wheel
+/// which keeps the cost low for participants with many instances with deadlines.
+struct TimerWheel<T> {
+    elapsed: u64,
+    entries: HashMap<usize, WheelEntry<T>>,
+    levels: Vec<WheelLevel>,
+}
 
-    #[inline(always)]
-    fn notify_next_timer(&mut self) {
-        if let Some(t) = self.heap.pop() {
-            debug_assert!(t.deadline < Instant::now());
-            t.waker.wake();
+impl<T> TimerWheel<T> {
+    fn new() -> Self {
+        Self {
+            elapsed: 0,
+            entries: HashMap::new(),
+            levels: (0..NUM_LEVELS).map(|_| WheelLevel::new()).collect(),
+        }
+    }
+
+    fn level_for(elapsed: u64, deadline: u64) -> usize {
+        let masked = (elapsed ^ deadline) | SLOT_MASK;
+        let significant_bit = u64::BITS - 1 - masked.leading_zeros();
+        (significant_bit / SLOT_BITS) as usize
+    }
+
+    fn slot_for(deadline: u64, level: usize) -> usize {
+        ((deadline >> (level as u32 * SLOT_BITS)) & SLOT_MASK) as usize
+    }
+
+    fn insert(&mut self, id: usize, deadline: u64, value: T) {
+        self.remove(id);
+        let deadline = deadline.max(self.elapsed);
+        let level = Self::level_for(self.elapsed, deadline);
+        let slot = Self::slot_for(deadline, level);
+        self.levels[level].push(slot, id);
+        self.entries.insert(
+            id,
+            WheelEntry {
+                deadline,
+                level,
+                slot,
+                value,
+            },
+        );
+    }
+
+    fn remove(&mut self, id: usize) -> Option<T> {
+        let entry = self.entries.remove(&id)?;
+        self.levels[entry.level].remove(entry.slot, id);
+        Some(entry.value)
+    }
+
+    // Returns the level, slot and start tick of the first occupied slot of the wheel
+    fn next_expiration(&self) -> Option<(usize, usize, u64)> {
+        self.levels
+            .iter()
+            .enumerate()
+            .find_map(|(level, wheel_level)| {
+                if wheel_level.occupied == 0 {
+                    return None;
+                }
+                let level_shift = level as u32 * SLOT_BITS;
+                let now_slot = (self.elapsed >> level_shift) & SLOT_MASK;
+                let slot = (now_slot
+                    + wheel_level
+                        .occupied
+                        .rotate_right(now_slot as u32)
+                        .trailing_zeros() as u64)
+                    & SLOT_MASK;
+                let level_start = match 1u64.checked_shl(level_shift + SLOT_BITS) {
+                    Some(level_range) => self.elapsed & !(level_range - 1),
+                    None => 0,
+                };
+                Some((level, slot as usize, level_start + (slot << level_shift)))
+            })
+    }
+
+    fn next_deadline(&self) -> Option<u64> {
+        self.next_expiration().map(|(_, _, deadline)| deadline)
+    }
+
+    // Advance the wheel up to the tick "now" and return all the entries whose deadline has expired
+    fn poll(&mut self, now: u64) -> Vec<(usize, T)> {
+        let mut expired = Vec::new();
+        while let Some((level, slot, deadline)) = self.next_expiration() {
+            if deadline > now {
+                break;
+            }
+            self.elapsed = deadline;
+            for id in self.levels[level].take(slot) {
+                let entry = self
+                    .entries
+                    .remove(&id)
+                    .expect("Entry must exist for occupied slot");
+                if level == 0 {
+                    expired.push((id, entry.value));
+                } else {
+                    // Cascade the entry to the lower levels of the wheel
+                    self.insert(id, entry.deadline, entry.value);
+                }
+            }
         }
+        self.elapsed = self.elapsed.max(now);
+        expired
     }
 }
 
+enum TimerEntry {
+    Wake(Waker),
+    Task {
+        period: Option<Duration>,
+        callback: Box<dyn FnMut() + Send>,
+    },
+}
+
+// The timer resolution is one millisecond. Deadlines are rounded up so that
+// timers never expire before their deadline.
+fn deadline_to_ticks(start: Instant, deadline: Instant) -> u64 {
+    deadline
+        .saturating_duration_since(start)
+        .as_nanos()
+        .div_ceil(1_000_000) as u64
+}
+
+fn instant_to_ticks(start: Instant, instant: Instant) -> u64 {
+    instant.saturating_duration_since(start).as_millis() as u64
+}
+
 struct HandleInner {
     sleep_task_id: usize,
-    periodic_task_sender: std::sync::mpsc::Sender<TimerWake>,
+    timer_command_sender: Sender<TimerCommand>,
 }
 
 #[derive(Clone)]
@@ -149,7 +290,7 @@ impl TimerHandle {
             id,
             deadline: None,
             duration,
-            periodic_task_sender: inner_lock.periodic_task_sender.clone(),
+            timer_command_sender: inner_lock.timer_command_sender.clone(),
         }
     }
 
@@ -171,6 +312,55 @@ impl TimerHandle {
             Poll::Pending
         })
     }
+
+    /// Run the callback once after the duration has elapsed. The callback runs on the timer thread
+    /// so it must not block, typically it only sends a mail to an actor.
+    pub fn schedule(
+        &self,
+        duration: Duration,
+        callback: impl FnOnce() + Send + 'static,
+    ) -> TimerTaskHandle {
+        let mut callback = Some(callback);
+        self.schedule_task(duration, None, move || {
+            if let Some(c) = callback.take() {
+                c()
+            }
+        })
+    }
+
+    /// Run the callback every period, the first time after one period has elapsed. The callback runs
+    /// on the timer thread so it must not block, typically it only sends a mail to an actor.
+    pub fn schedule_periodic(
+        &self,
+        period: Duration,
+        callback: impl FnMut() + Send + 'static,
+    ) -> TimerTaskHandle {
+        self.schedule_task(period, Some(period), callback)
+    }
+
+    fn schedule_task(
+        &self,
+        duration: Duration,
+        period: Option<Duration>,
+        callback: impl FnMut() + Send + 'static,
+    ) -> TimerTaskHandle {
+        let mut inner_lock = self.inner.lock().expect("Mutex should not be poisoned");
+        let id = inner_lock.sleep_task_id;
+        inner_lock.sleep_task_id += 1;
+        inner_lock
+            .timer_command_sender
+            .send(TimerCommand::Schedule(ScheduledTask {
+                id,
+                deadline: Instant::now() + duration,
+                period,
+                callback: Box::new(callback),
+            }))
+            .ok();
+        TimerTaskHandle {
+            id,
+            timer_command_sender: inner_lock.timer_command_sender.clone(),
+        }
+    }
 }
 
 pub struct TimerDriver {
@@ -180,31 +370,68 @@ pub struct TimerDriver {
 
 impl TimerDriver {
     pub fn new() -> Self {
-        let (periodic_task_sender, periodic_task_receiver) =
-            std::sync::mpsc::channel::<TimerWake>();
+        let (timer_command_sender, timer_command_receiver) =
+            std::sync::mpsc::channel::<TimerCommand>();
         let timer_thread_join_handle = std::thread::Builder::new()
             .name("Dust DDS Timer".to_string())
             .spawn(move || {
-                let mut timer_heap = TimerHeap::new();
+                let start = Instant::now();
+                let mut timer_wheel = TimerWheel::new();
                 loop {
-                    // Check if there are any elapsed tasks and wake them
-                    while timer_heap.is_next_timer_elapsed() {
-                        timer_heap.notify_next_timer();
+                    // Check if there are any elapsed timers and wake or run them
+                    let now = instant_to_ticks(start, Instant::now());
+                    for (id, timer_entry) in timer_wheel.poll(now) {
+                        match timer_entry {
+                            TimerEntry::Wake(waker) => waker.wake(),
+                            TimerEntry::Task {
+                                period,
+                                mut callback,
+                            } => {
+                                callback();
+                                if let Some(p) = period {
+                                    let next_deadline =
+                                        now + deadline_to_ticks(start, start + p).max(1);
+                                    timer_wheel.insert(
+                                        id,
+                                        next_deadline,
+                                        TimerEntry::Task { period, callback },
+                                    );
+                                }
+                            }
+                        }
                     }
 
-                    // Wait for a new timer wake to come. Sleep forever
-                    // if there are no timer tasks on the queue otherwise
-                    // sleep until the next deadline so that the tasks can be
-                    // notified at the correct time
-                    let new_timer = match timer_heap.duration_until_next_timer() {
-                        Some(d) => periodic_task_receiver.recv_timeout(d),
-                        None => periodic_task_receiver
+                    // Wait for a new timer command to come. Sleep forever
+                    // if there are no timers on the wheel otherwise
+                    // sleep until the next deadline so that the timers can be
+                    // processed at the correct time
+                    let timer_command = match timer_wheel.next_deadline() {
+                        Some(d) => timer_command_receiver.recv_timeout(
+                            (start + Duration::from_millis(d))
+                                .saturating_duration_since(Instant::now()),
+                        ),
+                        None => timer_command_receiver
                             .recv()
                             .map_err(|_| RecvTimeoutError::Disconnected),
                     };
 
-                    match new_timer {
-                        Ok(t) => timer_heap.push(t),
+                    match timer_command {
+                        Ok(TimerCommand::Wake(t)) => timer_wheel.insert(
+                            t.id,
+                            deadline_to_ticks(start, t.deadline),
+                            TimerEntry::Wake(t.waker),
+                        ),
+                        Ok(TimerCommand::Schedule(t)) => timer_wheel.insert(
+                            t.id,
+                            deadline_to_ticks(start, t.deadline),
+                            TimerEntry::Task {
+                                period: t.period,
+                                callback: t.callback,
+                            },
+                        ),
+                        Ok(TimerCommand::Abort(id)) => {
+                            timer_wheel.remove(id);
+                        }
                         Err(RecvTimeoutError::Timeout) => (),
                         Err(RecvTimeoutError::Disconnected) => break,
                     }
@@ -213,7 +440,7 @@ impl TimerDriver {
             .expect("failed to spawn thread");
         let inner = Arc::new(Mutex::new(HandleInner {
             sleep_task_id: 0,
-            periodic_task_sender,
+            timer_command_sender,
         }));
         Self {
             inner,
@@ -227,3 +454,86 @@ impl TimerDriver {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timer_wheel_expires_entries_in_deadline_order() {
+        let mut wheel = TimerWheel::new();
+        wheel.insert(0, 5000, 'c');
+        wheel.insert(1, 3, 'a');
+        wheel.insert(2, 70, 'b');
+        wheel.insert(3, 300_000, 'd');
+
+        assert_eq!(wheel.poll(2), vec![]);
+        assert_eq!(wheel.poll(3), vec![(1, 'a')]);
+        assert_eq!(wheel.poll(4999), vec![(2, 'b')]);
+        assert_eq!(wheel.poll(5000), vec![(0, 'c')]);
+        assert_eq!(wheel.poll(299_999), vec![]);
+        assert_eq!(wheel.poll(1_000_000), vec![(3, 'd')]);
+        assert_eq!(wheel.next_deadline(), None);
+    }
+
+    #[test]
+    fn timer_wheel_removed_entries_do_not_expire() {
+        let mut wheel = TimerWheel::new();
+        wheel.insert(0, 10, 'a');
+        wheel.insert(1, 10, 'b');
+        wheel.insert(2, 100, 'c');
+
+        assert_eq!(wheel.remove(1), Some('b'));
+        assert_eq!(wheel.remove(2), Some('c'));
+        assert_eq!(wheel.remove(2), None);
+
+        assert_eq!(wheel.poll(1000), vec![(0, 'a')]);
+        assert_eq!(wheel.next_deadline(), None);
+    }
+
+    #[test]
+    fn timer_wheel_reinserted_entry_replaces_previous_deadline() {
+        let mut wheel = TimerWheel::new();
+        wheel.insert(0, 10, 'a');
+        wheel.poll(5);
+        wheel.insert(0, 20, 'b');
+
+        assert_eq!(wheel.poll(10), vec![]);
+        assert_eq!(wheel.poll(20), vec![(0, 'b')]);
+    }
+
+    #[test]
+    fn timer_wheel_deadline_in_the_past_expires_on_next_poll() {
+        let mut wheel = TimerWheel::new();
+        wheel.poll(1000);
+        wheel.insert(0, 10, 'a');
+
+        assert_eq!(wheel.next_deadline(), Some(1000));
+        assert_eq!(wheel.poll(1000), vec![(0, 'a')]);
+    }
+
+    #[test]
+    fn scheduled_callbacks_run_until_aborted() {
+        let timer_driver = TimerDriver::new();
+        let timer_handle = timer_driver.handle();
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let once_sender = sender.clone();
+        timer_handle.schedule(Duration::from_millis(5), move || {
+            once_sender.send("once").unwrap();
+        });
+        let periodic_task = timer_handle.schedule_periodic(Duration::from_millis(10), move || {
+            sender.send("periodic").ok();
+        });
+
+        let timeout = Duration::from_secs(5);
+        assert_eq!(receiver.recv_timeout(timeout), Ok("once"));
+        assert_eq!(receiver.recv_timeout(timeout), Ok("periodic"));
+        assert_eq!(receiver.recv_timeout(timeout), Ok("periodic"));
+
+        periodic_task.abort();
+        std::thread::sleep(Duration::from_millis(50));
+        while receiver.try_recv().is_ok() {}
+        assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+    }
+}
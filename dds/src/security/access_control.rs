@@ -0,0 +1,986 @@
+use super::{
+    authentication::{public_key_of, read_property_value},
+    types::ParticipantSecurityInfo,
+};
+use crate::{
+    domain::domain_participant_factory::DomainId,
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        qos_policy::PropertyQosPolicy,
+    },
+    transport::types::GuidPrefix,
+};
+use base64ct::{Base64, Encoding};
+use cms::{content_info::ContentInfo, signed_data::SignedData};
+use fnmatch_regex::glob_to_regex;
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use roxmltree::{Document, Node};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use x509_cert::{
+    der::{asn1::OctetString, oid::ObjectIdentifier, Decode, DecodePem, Encode},
+    Certificate,
+};
+
+// Names of the participant properties configuring the builtin access control plugin
+// as defined in DDS Security 9.4.1 "Configuration"
+pub const PROPERTY_PERMISSIONS_CA: &str = "dds.sec.access.permissions_ca";
+pub const PROPERTY_GOVERNANCE: &str = "dds.sec.access.governance";
+pub const PROPERTY_PERMISSIONS: &str = "dds.sec.access.permissions";
+
+const ID_MESSAGE_DIGEST: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.4");
+const ID_SHA_256: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1");
+
+fn access_control_error(reason: impl core::fmt::Display) -> DdsError {
+    DdsError::Error(format!("Access control: {}", reason))
+}
+
+// The governance and permissions documents are S/MIME messages (multipart/signed) whose
+// first part is signed by the permissions CA. Returns the XML document of the first part.
+fn verify_signed_document(document: &str, permissions_ca: &VerifyingKey) -> DdsResult<String> {
+    let (headers, body) =
+        split_mime_headers(document).ok_or_else(|| access_control_error("invalid S/MIME"))?;
+    let boundary = headers
+        .split(';')
+        .find_map(|p| p.trim().strip_prefix("boundary="))
+        .map(|b| b.trim().trim_matches('"'))
+        .ok_or_else(|| access_control_error("S/MIME document is not multipart/signed"))?;
+    let delimiter = format!("--{}", boundary);
+    let mut parts = body.split(delimiter.as_str()).skip(1);
+    let (Some(content), Some(signature)) = (parts.next(), parts.next()) else {
+        return Err(access_control_error("S/MIME document without signature"));
+    };
+    // The line breaks around the delimiters belong to the delimiters
+    let content = strip_line_break_prefix(content);
+    let content = content
+        .strip_suffix("\r\n")
+        .or_else(|| content.strip_suffix('\n'))
+        .unwrap_or(content);
+    let (_, signature) = split_mime_headers(strip_line_break_prefix(signature))
+        .ok_or_else(|| access_control_error("invalid S/MIME signature"))?;
+    let signature: String = signature
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    let signature = Base64::decode_vec(&signature).map_err(access_control_error)?;
+
+    // The signature is computed over the canonical form of the content
+    let canonical_content = content.replace("\r\n", "\n").replace('\n', "\r\n");
+    verify_cms_signature(canonical_content.as_bytes(), &signature, permissions_ca)?;
+
+    let xml = match split_mime_headers(content) {
+        Some((headers, xml)) if headers.starts_with("Content-Type") => xml,
+        _ => content,
+    };
+    Ok(xml.to_owned())
+}
+
+fn strip_line_break_prefix(text: &str) -> &str {
+    text.strip_prefix("\r\n")
+        .or_else(|| text.strip_prefix('\n'))
+        .unwrap_or(text)
+}
+
+fn split_mime_headers(text: &str) -> Option<(&str, &str)> {
+    let crlf = text.find("\r\n\r\n").map(|i| (i, 4));
+    let lf = text.find("\n\n").map(|i| (i, 2));
+    let (index, length) = match (crlf, lf) {
+        (Some(crlf), Some(lf)) => crlf.min(lf),
+        (crlf, lf) => crlf.or(lf)?,
+    };
+    Some((&text[..index], &text[index + length..]))
+}
+
+fn verify_cms_signature(
+    content: &[u8],
+    signature: &[u8],
+    permissions_ca: &VerifyingKey,
+) -> DdsResult<()> {
+    let content_info = ContentInfo::from_der(signature).map_err(access_control_error)?;
+    let signed_data: SignedData = content_info
+        .content
+        .decode_as()
+        .map_err(access_control_error)?;
+    let signer_info = signed_data
+        .signer_infos
+        .0
+        .iter()
+        .next()
+        .ok_or_else(|| access_control_error("document without signer"))?;
+    if signer_info.digest_alg.oid != ID_SHA_256 {
+        return Err(access_control_error(
+            "unsupported document digest algorithm",
+        ));
+    }
+    let signature =
+        Signature::from_der(signer_info.signature.as_bytes()).map_err(access_control_error)?;
+
+    // With signed attributes the signature covers the attributes which include the digest of
+    // the content
+    let signed_message = match &signer_info.signed_attrs {
+        Some(signed_attributes) => {
+            let message_digest = signed_attributes
+                .iter()
+                .find(|a| a.oid == ID_MESSAGE_DIGEST)
+                .and_then(|a| a.values.iter().next())
+                .and_then(|v| v.decode_as::<OctetString>().ok())
+                .ok_or_else(|| access_control_error("document without message digest"))?;
+            if message_digest.as_bytes() != Sha256::digest(content).as_slice() {
+                return Err(access_control_error("document content was modified"));
+            }
+            signed_attributes.to_der().map_err(access_control_error)?
+        }
+        None => content.to_vec(),
+    };
+    permissions_ca
+        .verify(&signed_message, &signature)
+        .map_err(|_| access_control_error("document not signed by the permissions CA"))
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(name))
+}
+
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children().filter(move |n| n.has_tag_name(name))
+}
+
+fn text<'a>(node: Node<'a, '_>) -> &'a str {
+    node.text().unwrap_or_default().trim()
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> DdsResult<&'a str> {
+    child(node, name)
+        .map(text)
+        .ok_or_else(|| access_control_error(format!("missing element <{}>", name)))
+}
+
+fn child_bool(node: Node, name: &str) -> DdsResult<bool> {
+    match child(node, name).map(text) {
+        None | Some("false") | Some("0") => Ok(false),
+        Some("true") | Some("1") => Ok(true),
+        Some(value) => Err(access_control_error(format!(
+            "invalid value {} of <{}>",
+            value, name
+        ))),
+    }
+}
+
+fn matches_glob(pattern: &str, name: &str) -> bool {
+    glob_to_regex(pattern).is_ok_and(|regex| regex.is_match(name))
+}
+
+// Date and time of the grant validity in the xs:dateTime format. The time is UTC if no time
+// zone is given.
+fn parse_date_time(value: &str) -> Option<SystemTime> {
+    let (date, time) = value.trim().split_once('T')?;
+    let mut date = date.splitn(3, '-');
+    let year: i64 = date.next()?.parse().ok()?;
+    let month: i64 = date.next()?.parse().ok()?;
+    let day: i64 = date.next()?.parse().ok()?;
+
+    let (time, utc_offset) = if let Some(time) = time.strip_suffix('Z') {
+        (time, 0)
+    } else if let Some(index) = time.rfind(['+', '-']) {
+        let (hours, minutes) = time[index + 1..].split_once(':')?;
+        let offset = hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+        let sign = if time[index..].starts_with('-') {
+            -1
+        } else {
+            1
+        };
+        (&time[..index], sign * offset)
+    } else {
+        (time, 0)
+    };
+    let mut time = time.splitn(3, ':');
+    let hours: i64 = time.next()?.parse().ok()?;
+    let minutes: i64 = time.next()?.parse().ok()?;
+    let seconds: i64 = time.next()?.split('.').next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hours > 23 || minutes > 59 {
+        return None;
+    }
+
+    // Days since the UNIX epoch of the civil date
+    let shifted_year = if month <= 2 { year - 1 } else { year };
+    let era = shifted_year.div_euclid(400);
+    let year_of_era = shifted_year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+
+    let seconds = days * 86400 + hours * 3600 + minutes * 60 + seconds - utc_offset;
+    Some(UNIX_EPOCH + Duration::from_secs(u64::try_from(seconds).ok()?))
+}
+
+// Subject names are compared attribute by attribute since the order and the spacing of the
+// attributes differ between the certificates and the permissions documents
+fn normalize_subject_name(subject_name: &str) -> Vec<(String, String)> {
+    let mut attributes: Vec<_> = subject_name
+        .split([',', '/'])
+        .filter_map(|attribute| {
+            let (name, value) = attribute.split_once('=')?;
+            Some((name.trim().to_uppercase(), value.trim().to_owned()))
+        })
+        .collect();
+    attributes.sort();
+    attributes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtectionKind {
+    None,
+    Sign,
+    Encrypt,
+    SignWithOriginAuthentication,
+    EncryptWithOriginAuthentication,
+}
+
+impl ProtectionKind {
+    fn parse(node: Node, name: &str) -> DdsResult<Self> {
+        match child(node, name).map(text) {
+            None | Some("NONE") => Ok(Self::None),
+            Some("SIGN") => Ok(Self::Sign),
+            Some("ENCRYPT") => Ok(Self::Encrypt),
+            Some("SIGN_WITH_ORIGIN_AUTHENTICATION") => Ok(Self::SignWithOriginAuthentication),
+            Some("ENCRYPT_WITH_ORIGIN_AUTHENTICATION") => Ok(Self::EncryptWithOriginAuthentication),
+            Some(value) => Err(access_control_error(format!(
+                "invalid protection kind {}",
+                value
+            ))),
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self, Self::Encrypt | Self::EncryptWithOriginAuthentication)
+    }
+
+    fn is_origin_authenticated(&self) -> bool {
+        matches!(
+            self,
+            Self::SignWithOriginAuthentication | Self::EncryptWithOriginAuthentication
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DomainIdRange {
+    min: DomainId,
+    max: Option<DomainId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DomainIdSet(Vec<DomainIdRange>);
+
+impl DomainIdSet {
+    fn parse(node: Node) -> DdsResult<Self> {
+        let parse_id = |value: &str| {
+            value
+                .parse::<DomainId>()
+                .map_err(|_| access_control_error(format!("invalid domain id {}", value)))
+        };
+        let mut ranges = Vec::new();
+        for domains in children(node, "domains") {
+            for element in domains.children().filter(|n| n.is_element()) {
+                if element.has_tag_name("id") {
+                    let id = parse_id(text(element))?;
+                    ranges.push(DomainIdRange {
+                        min: id,
+                        max: Some(id),
+                    });
+                } else if element.has_tag_name("id_range") {
+                    let min = child(element, "min")
+                        .map(|n| parse_id(text(n)))
+                        .transpose()?;
+                    let max = child(element, "max")
+                        .map(|n| parse_id(text(n)))
+                        .transpose()?;
+                    ranges.push(DomainIdRange {
+                        min: min.unwrap_or(0),
+                        max,
+                    });
+                }
+            }
+        }
+        Ok(Self(ranges))
+    }
+
+    fn contains(&self, domain_id: DomainId) -> bool {
+        self.0
+            .iter()
+            .any(|r| r.min <= domain_id && r.max.is_none_or(|max| domain_id <= max))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TopicRule {
+    topic_expression: String,
+    enable_discovery_protection: bool,
+    enable_liveliness_protection: bool,
+    enable_read_access_control: bool,
+    enable_write_access_control: bool,
+    metadata_protection_kind: ProtectionKind,
+    data_protection_kind: ProtectionKind,
+}
+
+impl TopicRule {
+    fn parse(node: Node) -> DdsResult<Self> {
+        Ok(Self {
+            topic_expression: child_text(node, "topic_expression")?.to_owned(),
+            enable_discovery_protection: child_bool(node, "enable_discovery_protection")?,
+            enable_liveliness_protection: child_bool(node, "enable_liveliness_protection")?,
+            enable_read_access_control: child_bool(node, "enable_read_access_control")?,
+            enable_write_access_control: child_bool(node, "enable_write_access_control")?,
+            metadata_protection_kind: ProtectionKind::parse(node, "metadata_protection_kind")?,
+            data_protection_kind: ProtectionKind::parse(node, "data_protection_kind")?,
+        })
+    }
+
+    // The topics without any protection can be used by unauthenticated participants
+    fn is_unprotected(&self) -> bool {
+        !self.enable_discovery_protection
+            && !self.enable_liveliness_protection
+            && !self.enable_read_access_control
+            && !self.enable_write_access_control
+            && self.metadata_protection_kind == ProtectionKind::None
+            && self.data_protection_kind == ProtectionKind::None
+    }
+}
+
+// Rules of the governance document applying to a domain as defined in
+// DDS Security 9.4.1.2 "Domain Governance Document"
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DomainRule {
+    domains: DomainIdSet,
+    allow_unauthenticated_participants: bool,
+    enable_join_access_control: bool,
+    discovery_protection_kind: ProtectionKind,
+    liveliness_protection_kind: ProtectionKind,
+    rtps_protection_kind: ProtectionKind,
+    topic_rules: Vec<TopicRule>,
+}
+
+impl DomainRule {
+    fn parse(node: Node) -> DdsResult<Self> {
+        let topic_rules = child(node, "topic_access_rules")
+            .map(|n| children(n, "topic_rule").map(TopicRule::parse).collect())
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Self {
+            domains: DomainIdSet::parse(node)?,
+            allow_unauthenticated_participants: child_bool(
+                node,
+                "allow_unauthenticated_participants",
+            )?,
+            enable_join_access_control: child_bool(node, "enable_join_access_control")?,
+            discovery_protection_kind: ProtectionKind::parse(node, "discovery_protection_kind")?,
+            liveliness_protection_kind: ProtectionKind::parse(node, "liveliness_protection_kind")?,
+            rtps_protection_kind: ProtectionKind::parse(node, "rtps_protection_kind")?,
+            topic_rules,
+        })
+    }
+
+    // The domain rule applying to the domain is the first one containing it
+    fn from_governance(governance: &str, domain_id: DomainId) -> DdsResult<Self> {
+        let document = Document::parse(governance).map_err(access_control_error)?;
+        let domain_access_rules = child(document.root_element(), "domain_access_rules")
+            .ok_or_else(|| access_control_error("invalid governance document"))?;
+        for node in children(domain_access_rules, "domain_rule") {
+            let domain_rule = Self::parse(node)?;
+            if domain_rule.domains.contains(domain_id) {
+                return Ok(domain_rule);
+            }
+        }
+        Err(access_control_error(format!(
+            "governance without rules for domain {}",
+            domain_id
+        )))
+    }
+
+    fn topic_rule(&self, topic_name: &str) -> Option<&TopicRule> {
+        self.topic_rules
+            .iter()
+            .find(|r| matches_glob(&r.topic_expression, topic_name))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleKind {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Publish,
+    Subscribe,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Criteria {
+    topics: Vec<String>,
+    partitions: Vec<String>,
+}
+
+impl Criteria {
+    fn parse(node: Node) -> Self {
+        let names = |list: &str, element: &str| -> Vec<String> {
+            children(node, list)
+                .flat_map(|n| children(n, element).map(|n| text(n).to_owned()))
+                .collect()
+        };
+        Self {
+            topics: names("topics", "topic"),
+            partitions: names("partitions", "partition"),
+        }
+    }
+
+    // An allow rule applies if all the partitions are allowed whereas a deny rule applies if
+    // any of them is denied. Without partitions only the default partition is included.
+    fn applies(&self, kind: RuleKind, topic_name: &str, partitions: &[String]) -> bool {
+        let default_partition = [String::new()];
+        let partitions = if partitions.is_empty() {
+            &default_partition[..]
+        } else {
+            partitions
+        };
+        let criteria_partitions = if self.partitions.is_empty() {
+            &default_partition[..]
+        } else {
+            &self.partitions[..]
+        };
+        let is_partition_matched = |partition: &String| {
+            criteria_partitions
+                .iter()
+                .any(|p| matches_glob(p, partition))
+        };
+
+        self.topics.iter().any(|t| matches_glob(t, topic_name))
+            && match kind {
+                RuleKind::Allow => partitions.iter().all(is_partition_matched),
+                RuleKind::Deny => partitions.iter().any(is_partition_matched),
+            }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PermissionsRule {
+    kind: RuleKind,
+    domains: DomainIdSet,
+    publish: Vec<Criteria>,
+    subscribe: Vec<Criteria>,
+}
+
+// Permissions of a participant identified by its subject name as defined in
+// DDS Security 9.4.1.3 "DomainParticipant permissions document"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grant {
+    subject_name: String,
+    not_before: SystemTime,
+    not_after: SystemTime,
+    rules: Vec<PermissionsRule>,
+    default: RuleKind,
+}
+
+impl Grant {
+    fn parse(node: Node) -> DdsResult<Self> {
+        let validity = child(node, "validity")
+            .ok_or_else(|| access_control_error("grant without validity"))?;
+        let date_time = |name| {
+            parse_date_time(child_text(validity, name)?)
+                .ok_or_else(|| access_control_error(format!("invalid <{}>", name)))
+        };
+        let mut rules = Vec::new();
+        for rule in node.children().filter(|n| n.is_element()) {
+            let kind = match rule.tag_name().name() {
+                "allow_rule" => RuleKind::Allow,
+                "deny_rule" => RuleKind::Deny,
+                _ => continue,
+            };
+            rules.push(PermissionsRule {
+                kind,
+                domains: DomainIdSet::parse(rule)?,
+                publish: children(rule, "publish").map(Criteria::parse).collect(),
+                subscribe: children(rule, "subscribe").map(Criteria::parse).collect(),
+            });
+        }
+        let default = match child_text(node, "default")? {
+            "ALLOW" => RuleKind::Allow,
+            "DENY" => RuleKind::Deny,
+            value => {
+                return Err(access_control_error(format!(
+                    "invalid default rule {}",
+                    value
+                )))
+            }
+        };
+        Ok(Self {
+            subject_name: child_text(node, "subject_name")?.to_owned(),
+            not_before: date_time("not_before")?,
+            not_after: date_time("not_after")?,
+            rules,
+            default,
+        })
+    }
+
+    // Returns the grant of the permissions document for the subject name
+    fn from_permissions(permissions: &str, subject_name: &str) -> DdsResult<Self> {
+        let document = Document::parse(permissions).map_err(access_control_error)?;
+        let permissions = child(document.root_element(), "permissions")
+            .ok_or_else(|| access_control_error("invalid permissions document"))?;
+        let subject_name = normalize_subject_name(subject_name);
+        for node in children(permissions, "grant") {
+            let grant = Self::parse(node)?;
+            if normalize_subject_name(&grant.subject_name) == subject_name {
+                let now = SystemTime::now();
+                if now < grant.not_before || now > grant.not_after {
+                    return Err(access_control_error("grant outside its validity period"));
+                }
+                return Ok(grant);
+            }
+        }
+        Err(access_control_error(
+            "permissions without grant for subject",
+        ))
+    }
+
+    fn allows_join(&self, domain_id: DomainId) -> bool {
+        self.default == RuleKind::Allow
+            || self
+                .rules
+                .iter()
+                .any(|r| r.kind == RuleKind::Allow && r.domains.contains(domain_id))
+    }
+
+    // The rules are evaluated in the order of the document and the first one applying decides
+    fn allows(
+        &self,
+        domain_id: DomainId,
+        action: Action,
+        topic_name: &str,
+        partitions: &[String],
+    ) -> bool {
+        for rule in self.rules.iter().filter(|r| r.domains.contains(domain_id)) {
+            let criteria = match action {
+                Action::Publish => &rule.publish,
+                Action::Subscribe => &rule.subscribe,
+            };
+            if criteria
+                .iter()
+                .any(|c| c.applies(rule.kind, topic_name, partitions))
+            {
+                return rule.kind == RuleKind::Allow;
+            }
+        }
+        self.default == RuleKind::Allow
+    }
+}
+
+enum RemoteParticipantAccess {
+    Granted(Grant),
+    // Participant accepted without authentication as allowed by the governance
+    Unauthenticated,
+}
+
+// Builtin access control plugin DDS:Access:Permissions of DDS Security 9.4. The permissions of
+// the remote participants are received during the authentication handshake and they are
+// checked when their endpoints are discovered.
+pub struct AccessControl {
+    domain_id: DomainId,
+    domain_rule: DomainRule,
+    permissions_ca: VerifyingKey,
+    permissions_document: String,
+    remote_participants: HashMap<GuidPrefix, RemoteParticipantAccess>,
+}
+
+impl AccessControl {
+    // Returns None if the participant is not configured to use access control
+    pub fn from_property_qos(
+        property: &PropertyQosPolicy,
+        domain_id: DomainId,
+        subject_name: &str,
+    ) -> DdsResult<Option<Self>> {
+        let permissions_ca = read_property_value(property, PROPERTY_PERMISSIONS_CA)?;
+        let governance = read_property_value(property, PROPERTY_GOVERNANCE)?;
+        let permissions = read_property_value(property, PROPERTY_PERMISSIONS)?;
+        match (permissions_ca, governance, permissions) {
+            (None, None, None) => Ok(None),
+            (Some(permissions_ca), Some(governance), Some(permissions)) => Self::new(
+                &permissions_ca,
+                &governance,
+                permissions,
+                domain_id,
+                subject_name,
+            )
+            .map(Some),
+            _ => Err(DdsError::Error(format!(
+                "Access control requires the {}, {} and {} properties",
+                PROPERTY_PERMISSIONS_CA, PROPERTY_GOVERNANCE, PROPERTY_PERMISSIONS
+            ))),
+        }
+    }
+
+    pub fn new(
+        permissions_ca_pem: &str,
+        governance_document: &str,
+        permissions_document: String,
+        domain_id: DomainId,
+        subject_name: &str,
+    ) -> DdsResult<Self> {
+        let permissions_ca = Certificate::from_pem(permissions_ca_pem)
+            .map_err(|e| DdsError::Error(format!("Invalid permissions CA: {}", e)))?;
+        let permissions_ca = public_key_of(&permissions_ca)?;
+        let governance = verify_signed_document(governance_document, &permissions_ca)?;
+        let domain_rule = DomainRule::from_governance(&governance, domain_id)?;
+        let permissions = verify_signed_document(&permissions_document, &permissions_ca)?;
+        let grant = Grant::from_permissions(&permissions, subject_name)?;
+        if domain_rule.enable_join_access_control && !grant.allows_join(domain_id) {
+            return Err(access_control_error(format!(
+                "participant not allowed to join domain {}",
+                domain_id
+            )));
+        }
+
+        Ok(Self {
+            domain_id,
+            domain_rule,
+            permissions_ca,
+            permissions_document,
+            remote_participants: HashMap::new(),
+        })
+    }
+
+    pub fn permissions_document(&self) -> &str {
+        &self.permissions_document
+    }
+
+    pub fn participant_security_info(&self) -> ParticipantSecurityInfo {
+        let rule = &self.domain_rule;
+        let mut participant_security_attributes = ParticipantSecurityInfo::IS_VALID;
+        let mut plugin_participant_security_attributes = ParticipantSecurityInfo::IS_VALID;
+        for (protection_kind, is_protected, is_encrypted, is_origin_authenticated) in [
+            (
+                rule.rtps_protection_kind,
+                ParticipantSecurityInfo::IS_RTPS_PROTECTED,
+                ParticipantSecurityInfo::IS_RTPS_ENCRYPTED,
+                ParticipantSecurityInfo::IS_RTPS_ORIGIN_AUTHENTICATED,
+            ),
+            (
+                rule.discovery_protection_kind,
+                ParticipantSecurityInfo::IS_DISCOVERY_PROTECTED,
+                ParticipantSecurityInfo::IS_DISCOVERY_ENCRYPTED,
+                ParticipantSecurityInfo::IS_DISCOVERY_ORIGIN_AUTHENTICATED,
+            ),
+            (
+                rule.liveliness_protection_kind,
+                ParticipantSecurityInfo::IS_LIVELINESS_PROTECTED,
+                ParticipantSecurityInfo::IS_LIVELINESS_ENCRYPTED,
+                ParticipantSecurityInfo::IS_LIVELINESS_ORIGIN_AUTHENTICATED,
+            ),
+        ] {
+            if protection_kind != ProtectionKind::None {
+                participant_security_attributes |= is_protected;
+            }
+            if protection_kind.is_encrypted() {
+                plugin_participant_security_attributes |= is_encrypted;
+            }
+            if protection_kind.is_origin_authenticated() {
+                plugin_participant_security_attributes |= is_origin_authenticated;
+            }
+        }
+        ParticipantSecurityInfo {
+            participant_security_attributes,
+            plugin_participant_security_attributes,
+        }
+    }
+
+    pub fn discovery_protection_kind(&self) -> ProtectionKind {
+        self.domain_rule.discovery_protection_kind
+    }
+
+    // The serialized payload is not transformed separately so the protection of the data is
+    // applied together with the protection of the submessage metadata
+    pub fn endpoint_protection_kind(&self, topic_name: &str) -> ProtectionKind {
+        match self.domain_rule.topic_rule(topic_name) {
+            Some(r) if r.metadata_protection_kind.is_encrypted() => r.metadata_protection_kind,
+            Some(r) if r.data_protection_kind.is_encrypted() => r.data_protection_kind,
+            Some(r) if r.metadata_protection_kind != ProtectionKind::None => {
+                r.metadata_protection_kind
+            }
+            Some(r) => r.data_protection_kind,
+            None => ProtectionKind::None,
+        }
+    }
+
+    // Unauthenticated participants can only be accepted if the RTPS messages are not protected
+    pub fn allows_unauthenticated_participants(&self) -> bool {
+        self.domain_rule.allow_unauthenticated_participants
+            && self.domain_rule.rtps_protection_kind == ProtectionKind::None
+    }
+
+    pub fn add_unauthenticated_participant(&mut self, remote_guid_prefix: GuidPrefix) {
+        self.remote_participants
+            .insert(remote_guid_prefix, RemoteParticipantAccess::Unauthenticated);
+    }
+
+    pub fn validate_remote_permissions(
+        &mut self,
+        remote_guid_prefix: GuidPrefix,
+        remote_subject_name: &str,
+        remote_permissions_document: &[u8],
+    ) -> DdsResult<()> {
+        let permissions_document =
+            core::str::from_utf8(remote_permissions_document).map_err(access_control_error)?;
+        let permissions = verify_signed_document(permissions_document, &self.permissions_ca)?;
+        let grant = Grant::from_permissions(&permissions, remote_subject_name)?;
+        if self.domain_rule.enable_join_access_control && !grant.allows_join(self.domain_id) {
+            return Err(access_control_error(format!(
+                "remote participant not allowed to join domain {}",
+                self.domain_id
+            )));
+        }
+        self.remote_participants
+            .insert(remote_guid_prefix, RemoteParticipantAccess::Granted(grant));
+        Ok(())
+    }
+
+    pub fn is_remote_participant_allowed(&self, remote_guid_prefix: &GuidPrefix) -> bool {
+        self.remote_participants.contains_key(remote_guid_prefix)
+    }
+
+    pub fn remove_remote_participant(&mut self, remote_guid_prefix: &GuidPrefix) {
+        self.remote_participants.remove(remote_guid_prefix);
+    }
+
+    pub fn check_remote_datawriter(
+        &self,
+        remote_guid_prefix: &GuidPrefix,
+        topic_name: &str,
+        partitions: &[String],
+    ) -> bool {
+        self.check_remote_endpoint(remote_guid_prefix, Action::Publish, topic_name, partitions)
+    }
+
+    pub fn check_remote_datareader(
+        &self,
+        remote_guid_prefix: &GuidPrefix,
+        topic_name: &str,
+        partitions: &[String],
+    ) -> bool {
+        self.check_remote_endpoint(
+            remote_guid_prefix,
+            Action::Subscribe,
+            topic_name,
+            partitions,
+        )
+    }
+
+    fn check_remote_endpoint(
+        &self,
+        remote_guid_prefix: &GuidPrefix,
+        action: Action,
+        topic_name: &str,
+        partitions: &[String],
+    ) -> bool {
+        // Topics without a rule are not protected
+        let topic_rule = self.domain_rule.topic_rule(topic_name);
+        match self.remote_participants.get(remote_guid_prefix) {
+            Some(RemoteParticipantAccess::Granted(grant)) => {
+                let is_access_controlled = topic_rule.is_some_and(|r| match action {
+                    Action::Publish => r.enable_write_access_control,
+                    Action::Subscribe => r.enable_read_access_control,
+                });
+                !is_access_controlled
+                    || grant.allows(self.domain_id, action, topic_name, partitions)
+            }
+            Some(RemoteParticipantAccess::Unauthenticated) => {
+                topic_rule.is_none_or(TopicRule::is_unprotected)
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PERMISSIONS_CA: &str = include_str!("../../tests/security/identity_ca.pem");
+    const INTRUDER_CA: &str = include_str!("../../tests/security/intruder_ca.pem");
+    const GOVERNANCE: &str = include_str!("../../tests/security/governance.p7s");
+    const GOVERNANCE_RTPS_SIGN: &str =
+        include_str!("../../tests/security/governance_rtps_sign.p7s");
+    const PERMISSIONS: &str = include_str!("../../tests/security/permissions.p7s");
+    const PARTICIPANT1_SUBJECT: &str = "CN=Participant 1,O=Dust DDS,C=PT";
+    const PARTICIPANT2_SUBJECT: &str = "CN=Participant 2,O=Dust DDS,C=PT";
+
+    fn access_control(governance: &str, subject_name: &str) -> AccessControl {
+        AccessControl::new(
+            PERMISSIONS_CA,
+            governance,
+            PERMISSIONS.to_string(),
+            0,
+            subject_name,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_date_time_in_utc_and_with_time_zone() {
+        assert_eq!(
+            parse_date_time("1970-01-02T00:00:01"),
+            Some(UNIX_EPOCH + Duration::from_secs(86401))
+        );
+        assert_eq!(
+            parse_date_time("2024-03-01T12:30:00Z"),
+            Some(UNIX_EPOCH + Duration::from_secs(1709296200))
+        );
+        assert_eq!(
+            parse_date_time("2024-03-01T14:30:00.5+02:00"),
+            Some(UNIX_EPOCH + Duration::from_secs(1709296200))
+        );
+        assert_eq!(parse_date_time("2024-13-01T00:00:00"), None);
+        assert_eq!(parse_date_time("2024-01-01"), None);
+    }
+
+    #[test]
+    fn signed_documents_are_verified() {
+        let permissions_ca =
+            public_key_of(&Certificate::from_pem(PERMISSIONS_CA).unwrap()).unwrap();
+        let governance = verify_signed_document(GOVERNANCE, &permissions_ca).unwrap();
+        assert!(governance.starts_with("<?xml"));
+        assert!(governance.trim_end().ends_with("</dds>"));
+
+        let intruder_ca = public_key_of(&Certificate::from_pem(INTRUDER_CA).unwrap()).unwrap();
+        assert!(verify_signed_document(GOVERNANCE, &intruder_ca).is_err());
+
+        let tampered_governance = GOVERNANCE.replacen(
+            "<enable_join_access_control>true",
+            "<enable_join_access_control>false",
+            1,
+        );
+        assert_ne!(tampered_governance, GOVERNANCE);
+        assert!(verify_signed_document(&tampered_governance, &permissions_ca).is_err());
+    }
+
+    #[test]
+    fn governance_domain_rule() {
+        let permissions_ca =
+            public_key_of(&Certificate::from_pem(PERMISSIONS_CA).unwrap()).unwrap();
+        let governance = verify_signed_document(GOVERNANCE, &permissions_ca).unwrap();
+        let domain_rule = DomainRule::from_governance(&governance, 10).unwrap();
+
+        assert!(!domain_rule.allow_unauthenticated_participants);
+        assert!(domain_rule.enable_join_access_control);
+        assert_eq!(domain_rule.rtps_protection_kind, ProtectionKind::None);
+        assert!(
+            domain_rule
+                .topic_rule("Square")
+                .unwrap()
+                .enable_read_access_control
+        );
+        assert!(domain_rule.topic_rule("Triangle").unwrap().is_unprotected());
+        assert!(DomainRule::from_governance(&governance, 233).is_err());
+    }
+
+    #[test]
+    fn grant_rules_are_evaluated_in_order() {
+        let permissions_ca =
+            public_key_of(&Certificate::from_pem(PERMISSIONS_CA).unwrap()).unwrap();
+        let permissions = verify_signed_document(PERMISSIONS, &permissions_ca).unwrap();
+        let participant1 = Grant::from_permissions(&permissions, PARTICIPANT1_SUBJECT).unwrap();
+        let participant2 = Grant::from_permissions(&permissions, PARTICIPANT2_SUBJECT).unwrap();
+
+        assert!(participant1.allows(0, Action::Publish, "Circle", &[]));
+        assert!(!participant1.allows(0, Action::Subscribe, "Circle", &[]));
+        assert!(!participant1.allows(0, Action::Publish, "Square", &["A".to_string()]));
+        assert!(!participant1.allows(233, Action::Publish, "Square", &[]));
+        assert!(participant2.allows(0, Action::Subscribe, "Square", &[]));
+        assert!(!participant2.allows(0, Action::Subscribe, "Circle", &[]));
+        assert!(!participant2.allows(0, Action::Publish, "Square", &[]));
+        assert!(Grant::from_permissions(&permissions, "CN=Intruder,O=Dust DDS,C=PT").is_err());
+    }
+
+    #[test]
+    fn remote_endpoints_are_checked_against_remote_permissions() {
+        let mut access_control = access_control(GOVERNANCE, PARTICIPANT1_SUBJECT);
+        let remote_guid_prefix = [2; 12];
+        assert!(!access_control.check_remote_datareader(&remote_guid_prefix, "Square", &[]));
+
+        access_control
+            .validate_remote_permissions(
+                remote_guid_prefix,
+                PARTICIPANT2_SUBJECT,
+                PERMISSIONS.as_bytes(),
+            )
+            .unwrap();
+        assert!(access_control.is_remote_participant_allowed(&remote_guid_prefix));
+        assert!(access_control.check_remote_datareader(&remote_guid_prefix, "Square", &[]));
+        assert!(!access_control.check_remote_datareader(&remote_guid_prefix, "Circle", &[]));
+        assert!(!access_control.check_remote_datawriter(&remote_guid_prefix, "Square", &[]));
+        // Topics matching a rule without access control
+        assert!(access_control.check_remote_datawriter(&remote_guid_prefix, "Triangle", &[]));
+
+        access_control.remove_remote_participant(&remote_guid_prefix);
+        assert!(!access_control.is_remote_participant_allowed(&remote_guid_prefix));
+    }
+
+    #[test]
+    fn unauthenticated_participants_only_use_unprotected_topics() {
+        let mut access_control = access_control(GOVERNANCE, PARTICIPANT1_SUBJECT);
+        let remote_guid_prefix = [2; 12];
+        access_control.add_unauthenticated_participant(remote_guid_prefix);
+
+        assert!(access_control.check_remote_datareader(&remote_guid_prefix, "Triangle", &[]));
+        assert!(!access_control.check_remote_datareader(&remote_guid_prefix, "Square", &[]));
+    }
+
+    #[test]
+    fn participant_security_info_from_governance() {
+        let access_control_none = access_control(GOVERNANCE, PARTICIPANT1_SUBJECT);
+        let access_control_sign = access_control(GOVERNANCE_RTPS_SIGN, PARTICIPANT1_SUBJECT);
+
+        assert_eq!(
+            access_control_none.participant_security_info(),
+            ParticipantSecurityInfo {
+                participant_security_attributes: ParticipantSecurityInfo::IS_VALID,
+                plugin_participant_security_attributes: ParticipantSecurityInfo::IS_VALID,
+            }
+        );
+        assert_eq!(
+            access_control_sign.participant_security_info(),
+            ParticipantSecurityInfo {
+                participant_security_attributes: ParticipantSecurityInfo::IS_VALID
+                    | ParticipantSecurityInfo::IS_RTPS_PROTECTED,
+                plugin_participant_security_attributes: ParticipantSecurityInfo::IS_VALID,
+            }
+        );
+    }
+
+    #[test]
+    fn participant_without_grant_is_rejected() {
+        assert!(AccessControl::new(
+            PERMISSIONS_CA,
+            GOVERNANCE,
+            PERMISSIONS.to_string(),
+            0,
+            "CN=Intruder,O=Dust DDS,C=PT",
+        )
+        .is_err());
+        // The governance does not include the domain
+        assert!(AccessControl::new(
+            PERMISSIONS_CA,
+            GOVERNANCE,
+            PERMISSIONS.to_string(),
+            233,
+            PARTICIPANT1_SUBJECT,
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,998 @@
+use super::types::DataHolder;
+use crate::{
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        qos_policy::{BinaryProperty, PropertyQosPolicy},
+    },
+    transport::types::{Guid, GuidPrefix},
+    xtypes::{serialize::XTypesSerialize, xcdr_serializer::Xcdr1BeSerializer},
+};
+use p256::{
+    ecdh::EphemeralSecret,
+    ecdsa::{
+        signature::{Signer, Verifier},
+        Signature, SigningKey, VerifyingKey,
+    },
+    elliptic_curve::sec1::ToEncodedPoint,
+    pkcs8::DecodePrivateKey,
+    PublicKey, SecretKey,
+};
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, time::SystemTime};
+use x509_cert::{
+    der::{oid::ObjectIdentifier, DecodePem, Encode},
+    Certificate,
+};
+
+// Names of the participant properties configuring the builtin authentication plugin
+// as defined in DDS Security 9.3.1 "Configuration"
+pub const PROPERTY_IDENTITY_CA: &str = "dds.sec.auth.identity_ca";
+pub const PROPERTY_IDENTITY_CERTIFICATE: &str = "dds.sec.auth.identity_certificate";
+pub const PROPERTY_PRIVATE_KEY: &str = "dds.sec.auth.private_key";
+
+const AUTH_PKI_DH_CLASS_ID: &str = "DDS:Auth:PKI-DH:1.0";
+const HANDSHAKE_REQUEST_CLASS_ID: &str = "DDS:Auth:PKI-DH:1.0+Req";
+const HANDSHAKE_REPLY_CLASS_ID: &str = "DDS:Auth:PKI-DH:1.0+Reply";
+const HANDSHAKE_FINAL_CLASS_ID: &str = "DDS:Auth:PKI-DH:1.0+Final";
+
+const DSIGN_ALGO: &str = "ECDSA-SHA256";
+const KAGREE_ALGO: &str = "ECDH+prime256v1-CEUM";
+const CERTIFICATE_ALGO: &str = "EC-prime256v1";
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+
+const CHALLENGE_LENGTH: usize = 32;
+
+fn security_error(reason: impl core::fmt::Display) -> DdsError {
+    DdsError::Error(format!("Authentication failed: {}", reason))
+}
+
+// The value of the configuration properties is either the URI of a file ("file:") or the
+// content itself ("data:,")
+pub(super) fn read_property_value(
+    property: &PropertyQosPolicy,
+    name: &str,
+) -> DdsResult<Option<String>> {
+    let Some(value) = property.value.iter().find(|p| p.name == name) else {
+        return Ok(None);
+    };
+    if let Some(path) = value.value.strip_prefix("file:") {
+        Ok(Some(std::fs::read_to_string(path)?))
+    } else if let Some(data) = value.value.strip_prefix("data:,") {
+        Ok(Some(data.to_owned()))
+    } else {
+        Err(DdsError::Error(format!(
+            "Property {} must start with \"file:\" or \"data:,\"",
+            name
+        )))
+    }
+}
+
+fn verify_certificate(certificate: &Certificate, identity_ca: &Certificate) -> DdsResult<()> {
+    if certificate.signature_algorithm.oid != ECDSA_WITH_SHA256 {
+        return Err(security_error(
+            "unsupported certificate signature algorithm",
+        ));
+    }
+    if certificate.tbs_certificate.issuer != identity_ca.tbs_certificate.subject {
+        return Err(security_error("certificate not issued by the identity CA"));
+    }
+    let ca_key = public_key_of(identity_ca)?;
+    let tbs_certificate = certificate
+        .tbs_certificate
+        .to_der()
+        .map_err(security_error)?;
+    let signature =
+        Signature::from_der(certificate.signature.raw_bytes()).map_err(security_error)?;
+    ca_key
+        .verify(&tbs_certificate, &signature)
+        .map_err(|_| security_error("invalid certificate signature"))?;
+
+    let now = SystemTime::now();
+    let validity = &certificate.tbs_certificate.validity;
+    if now < validity.not_before.to_system_time() || now > validity.not_after.to_system_time() {
+        return Err(security_error("certificate outside its validity period"));
+    }
+    Ok(())
+}
+
+pub(super) fn public_key_of(certificate: &Certificate) -> DdsResult<VerifyingKey> {
+    VerifyingKey::from_sec1_bytes(
+        certificate
+            .tbs_certificate
+            .subject_public_key_info
+            .subject_public_key
+            .raw_bytes(),
+    )
+    .map_err(|_| security_error("certificate key is not an EC prime256v1 key"))
+}
+
+// Hashes and signatures are computed over the CDR big-endian serialization of a
+// BinaryPropertySeq as specified in DDS Security 9.3.2.5
+fn serialize_binary_properties(properties: &[(&str, &[u8])]) -> DdsResult<Vec<u8>> {
+    let binary_properties: Vec<BinaryProperty> = properties
+        .iter()
+        .map(|(name, value)| BinaryProperty {
+            name: (*name).to_owned(),
+            value: value.to_vec(),
+            propagate: true,
+        })
+        .collect();
+    let mut buffer = Vec::new();
+    XTypesSerialize::serialize(&binary_properties, &mut Xcdr1BeSerializer::new(&mut buffer))?;
+    Ok(buffer)
+}
+
+fn random_challenge() -> [u8; CHALLENGE_LENGTH] {
+    let mut challenge = [0; CHALLENGE_LENGTH];
+    OsRng.fill_bytes(&mut challenge);
+    challenge
+}
+
+fn required_property<'a>(token: &'a DataHolder, name: &str) -> DdsResult<&'a [u8]> {
+    token
+        .binary_property(name)
+        .ok_or_else(|| security_error(format!("handshake message without {}", name)))
+}
+
+pub struct IdentityCredentials {
+    identity_ca: Certificate,
+    identity_certificate: Certificate,
+    identity_certificate_pem: String,
+    signing_key: SigningKey,
+}
+
+impl IdentityCredentials {
+    // Returns None if the participant is not configured to use authentication
+    pub fn from_property_qos(property: &PropertyQosPolicy) -> DdsResult<Option<Self>> {
+        let identity_ca = read_property_value(property, PROPERTY_IDENTITY_CA)?;
+        let identity_certificate = read_property_value(property, PROPERTY_IDENTITY_CERTIFICATE)?;
+        let private_key = read_property_value(property, PROPERTY_PRIVATE_KEY)?;
+        match (identity_ca, identity_certificate, private_key) {
+            (None, None, None) => Ok(None),
+            (Some(identity_ca), Some(identity_certificate), Some(private_key)) => {
+                Self::new(&identity_ca, &identity_certificate, &private_key).map(Some)
+            }
+            _ => Err(DdsError::Error(format!(
+                "Authentication requires the {}, {} and {} properties",
+                PROPERTY_IDENTITY_CA, PROPERTY_IDENTITY_CERTIFICATE, PROPERTY_PRIVATE_KEY
+            ))),
+        }
+    }
+
+    pub fn new(
+        identity_ca_pem: &str,
+        identity_certificate_pem: &str,
+        private_key_pem: &str,
+    ) -> DdsResult<Self> {
+        let identity_ca = Certificate::from_pem(identity_ca_pem)
+            .map_err(|e| DdsError::Error(format!("Invalid identity CA: {}", e)))?;
+        let identity_certificate = Certificate::from_pem(identity_certificate_pem)
+            .map_err(|e| DdsError::Error(format!("Invalid identity certificate: {}", e)))?;
+        let secret_key = SecretKey::from_pkcs8_pem(private_key_pem)
+            .or_else(|_| SecretKey::from_sec1_pem(private_key_pem))
+            .map_err(|e| DdsError::Error(format!("Invalid private key: {}", e)))?;
+        let signing_key = SigningKey::from(secret_key);
+
+        verify_certificate(&identity_certificate, &identity_ca)?;
+        if public_key_of(&identity_certificate)? != *signing_key.verifying_key() {
+            return Err(DdsError::Error(
+                "Private key does not match the identity certificate".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            identity_ca,
+            identity_certificate,
+            identity_certificate_pem: identity_certificate_pem.to_owned(),
+            signing_key,
+        })
+    }
+
+    pub fn subject_name(&self) -> String {
+        self.identity_certificate
+            .tbs_certificate
+            .subject
+            .to_string()
+    }
+}
+
+pub enum RemoteIdentityStatus {
+    Authenticated,
+    // The handshake is ongoing. The handshake message, if any, must be sent to the remote
+    // participant.
+    PendingHandshake(Option<DataHolder>),
+    Rejected,
+}
+
+pub struct HandshakeResult {
+    pub message: Option<DataHolder>,
+    pub completed: bool,
+}
+
+// Information obtained from the handshake from which the keys protecting the communication
+// with the remote participant are derived
+pub struct SharedSecret {
+    pub challenge1: [u8; CHALLENGE_LENGTH],
+    pub challenge2: [u8; CHALLENGE_LENGTH],
+    pub secret: Vec<u8>,
+}
+
+// Identity of the remote participant authenticated with the handshake
+pub struct RemoteIdentity {
+    pub subject_name: String,
+    pub permissions_document: Vec<u8>,
+}
+
+// Values of the handshake identifying one of the participants
+struct HandshakeIdentity {
+    hash_c: [u8; 32],
+    challenge: [u8; CHALLENGE_LENGTH],
+    dh: Vec<u8>,
+}
+
+enum HandshakeState {
+    WaitingReply {
+        request: DataHolder,
+        local: HandshakeIdentity,
+        dh_secret: EphemeralSecret,
+    },
+    WaitingFinal {
+        reply: DataHolder,
+        remote: HandshakeIdentity,
+        local: HandshakeIdentity,
+        remote_key: VerifyingKey,
+        remote_identity: RemoteIdentity,
+        secret: Vec<u8>,
+    },
+    Completed {
+        final_message: Option<DataHolder>,
+        remote_identity: RemoteIdentity,
+        shared_secret: SharedSecret,
+    },
+}
+
+// Builtin authentication plugin DDS:Auth:PKI-DH of DDS Security 9.3. The participant with
+// the greatest GUID starts the handshake with a request, the other participant answers it with
+// a reply and the handshake is concluded with a final message from the initiator.
+pub struct Authentication {
+    credentials: IdentityCredentials,
+    participant_guid: Guid,
+    // Permissions document sent to the remote participants during the handshake
+    permissions_document: Vec<u8>,
+    handshakes: HashMap<GuidPrefix, HandshakeState>,
+}
+
+impl Authentication {
+    pub fn new(
+        credentials: IdentityCredentials,
+        participant_guid: Guid,
+        permissions_document: Vec<u8>,
+    ) -> Self {
+        Self {
+            credentials,
+            participant_guid,
+            permissions_document,
+            handshakes: HashMap::new(),
+        }
+    }
+
+    pub fn identity_token(&self) -> DataHolder {
+        let mut token = DataHolder::new(AUTH_PKI_DH_CLASS_ID);
+        token.add_property(
+            "dds.cert.sn",
+            self.credentials
+                .identity_certificate
+                .tbs_certificate
+                .subject
+                .to_string(),
+        );
+        token.add_property("dds.cert.algo", CERTIFICATE_ALGO);
+        token.add_property(
+            "dds.ca.sn",
+            self.credentials
+                .identity_ca
+                .tbs_certificate
+                .subject
+                .to_string(),
+        );
+        token.add_property("dds.ca.algo", CERTIFICATE_ALGO);
+        token
+    }
+
+    pub fn validate_remote_identity(
+        &mut self,
+        remote_guid_prefix: GuidPrefix,
+        identity_token: Option<&DataHolder>,
+    ) -> RemoteIdentityStatus {
+        if remote_guid_prefix == self.participant_guid.prefix() {
+            return RemoteIdentityStatus::Authenticated;
+        }
+        if identity_token.is_none_or(|t| t.class_id != AUTH_PKI_DH_CLASS_ID) {
+            return RemoteIdentityStatus::Rejected;
+        }
+
+        match self.handshakes.get(&remote_guid_prefix) {
+            Some(HandshakeState::Completed { .. }) => RemoteIdentityStatus::Authenticated,
+            // Handshake messages are sent best-effort so they are repeated until answered
+            Some(HandshakeState::WaitingReply { request, .. }) => {
+                RemoteIdentityStatus::PendingHandshake(Some(request.clone()))
+            }
+            Some(HandshakeState::WaitingFinal { reply, .. }) => {
+                RemoteIdentityStatus::PendingHandshake(Some(reply.clone()))
+            }
+            None if self.participant_guid.prefix() > remote_guid_prefix => {
+                match self.begin_handshake_request() {
+                    Ok((request, state)) => {
+                        self.handshakes.insert(remote_guid_prefix, state);
+                        RemoteIdentityStatus::PendingHandshake(Some(request))
+                    }
+                    Err(_) => RemoteIdentityStatus::Rejected,
+                }
+            }
+            None => RemoteIdentityStatus::PendingHandshake(None),
+        }
+    }
+
+    pub fn shared_secret(&self, remote_guid_prefix: &GuidPrefix) -> Option<&SharedSecret> {
+        match self.handshakes.get(remote_guid_prefix) {
+            Some(HandshakeState::Completed { shared_secret, .. }) => Some(shared_secret),
+            _ => None,
+        }
+    }
+
+    pub fn remote_identity(&self, remote_guid_prefix: &GuidPrefix) -> Option<&RemoteIdentity> {
+        match self.handshakes.get(remote_guid_prefix) {
+            Some(HandshakeState::Completed {
+                remote_identity, ..
+            }) => Some(remote_identity),
+            _ => None,
+        }
+    }
+
+    pub fn remove_remote_identity(&mut self, remote_guid_prefix: &GuidPrefix) {
+        self.handshakes.remove(remote_guid_prefix);
+    }
+
+    pub fn process_handshake(
+        &mut self,
+        remote_participant_guid: Guid,
+        message: &DataHolder,
+    ) -> DdsResult<HandshakeResult> {
+        let remote_guid_prefix = remote_participant_guid.prefix();
+        match message.class_id.as_str() {
+            HANDSHAKE_REQUEST_CLASS_ID => {
+                if let Some(HandshakeState::WaitingFinal { reply, remote, .. }) =
+                    self.handshakes.get(&remote_guid_prefix)
+                {
+                    if message.binary_property("challenge1") == Some(remote.challenge.as_slice()) {
+                        return Ok(HandshakeResult {
+                            message: Some(reply.clone()),
+                            completed: false,
+                        });
+                    }
+                }
+                if matches!(
+                    self.handshakes.get(&remote_guid_prefix),
+                    Some(HandshakeState::WaitingReply { .. } | HandshakeState::Completed { .. })
+                ) {
+                    return Err(security_error("unexpected handshake request"));
+                }
+                let (reply, state) = self.process_request(remote_participant_guid, message)?;
+                self.handshakes.insert(remote_guid_prefix, state);
+                Ok(HandshakeResult {
+                    message: Some(reply),
+                    completed: false,
+                })
+            }
+            HANDSHAKE_REPLY_CLASS_ID => match self.handshakes.remove(&remote_guid_prefix) {
+                Some(HandshakeState::WaitingReply {
+                    request,
+                    local,
+                    dh_secret,
+                }) => {
+                    match self.process_reply(remote_participant_guid, message, &local, &dh_secret) {
+                        Ok((final_message, remote_identity, shared_secret)) => {
+                            self.handshakes.insert(
+                                remote_guid_prefix,
+                                HandshakeState::Completed {
+                                    final_message: Some(final_message.clone()),
+                                    remote_identity,
+                                    shared_secret,
+                                },
+                            );
+                            Ok(HandshakeResult {
+                                message: Some(final_message),
+                                completed: true,
+                            })
+                        }
+                        Err(e) => {
+                            self.handshakes.insert(
+                                remote_guid_prefix,
+                                HandshakeState::WaitingReply {
+                                    request,
+                                    local,
+                                    dh_secret,
+                                },
+                            );
+                            Err(e)
+                        }
+                    }
+                }
+                // The final message got lost and the remote participant repeated its reply
+                Some(HandshakeState::Completed {
+                    final_message: Some(final_message),
+                    remote_identity,
+                    shared_secret,
+                }) => {
+                    let result = if message.binary_property("challenge1")
+                        == final_message.binary_property("challenge1")
+                        && message.binary_property("challenge2")
+                            == final_message.binary_property("challenge2")
+                    {
+                        Ok(HandshakeResult {
+                            message: Some(final_message.clone()),
+                            completed: false,
+                        })
+                    } else {
+                        Err(security_error("unexpected handshake reply"))
+                    };
+                    self.handshakes.insert(
+                        remote_guid_prefix,
+                        HandshakeState::Completed {
+                            final_message: Some(final_message),
+                            remote_identity,
+                            shared_secret,
+                        },
+                    );
+                    result
+                }
+                state => {
+                    if let Some(state) = state {
+                        self.handshakes.insert(remote_guid_prefix, state);
+                    }
+                    Err(security_error("unexpected handshake reply"))
+                }
+            },
+            HANDSHAKE_FINAL_CLASS_ID => match self.handshakes.remove(&remote_guid_prefix) {
+                Some(HandshakeState::WaitingFinal {
+                    reply,
+                    remote,
+                    local,
+                    remote_key,
+                    remote_identity,
+                    secret,
+                }) => {
+                    if let Err(e) = Self::process_final(message, &remote, &local, &remote_key) {
+                        self.handshakes.insert(
+                            remote_guid_prefix,
+                            HandshakeState::WaitingFinal {
+                                reply,
+                                remote,
+                                local,
+                                remote_key,
+                                remote_identity,
+                                secret,
+                            },
+                        );
+                        return Err(e);
+                    }
+                    let shared_secret = SharedSecret {
+                        challenge1: remote.challenge,
+                        challenge2: local.challenge,
+                        secret,
+                    };
+                    self.handshakes.insert(
+                        remote_guid_prefix,
+                        HandshakeState::Completed {
+                            final_message: None,
+                            remote_identity,
+                            shared_secret,
+                        },
+                    );
+                    Ok(HandshakeResult {
+                        message: None,
+                        completed: true,
+                    })
+                }
+                state => {
+                    if let Some(state) = state {
+                        self.handshakes.insert(remote_guid_prefix, state);
+                    }
+                    Err(security_error("unexpected handshake final"))
+                }
+            },
+            _ => Err(security_error("unknown handshake message")),
+        }
+    }
+
+    // Properties describing the local participant: c.id, c.perm, c.pdata, c.dsign_algo and
+    // c.kagree_algo
+    fn local_identity_properties(&self) -> [(&'static str, Vec<u8>); 5] {
+        let participant_guid: [u8; 16] = self.participant_guid.into();
+        [
+            (
+                "c.id",
+                self.credentials
+                    .identity_certificate_pem
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            ("c.perm", self.permissions_document.clone()),
+            ("c.pdata", participant_guid.to_vec()),
+            ("c.dsign_algo", DSIGN_ALGO.as_bytes().to_vec()),
+            ("c.kagree_algo", KAGREE_ALGO.as_bytes().to_vec()),
+        ]
+    }
+
+    fn hash_identity_properties(properties: &[(&str, &[u8])]) -> DdsResult<[u8; 32]> {
+        Ok(Sha256::digest(serialize_binary_properties(properties)?).into())
+    }
+
+    // Validates the identity properties of the remote participant and returns its hash, its
+    // public key and its identity
+    fn validate_remote_identity_properties(
+        &self,
+        remote_participant_guid: Guid,
+        message: &DataHolder,
+    ) -> DdsResult<([u8; 32], VerifyingKey, RemoteIdentity)> {
+        let names = ["c.id", "c.perm", "c.pdata", "c.dsign_algo", "c.kagree_algo"];
+        let mut properties = Vec::with_capacity(names.len());
+        for name in names {
+            properties.push((name, required_property(message, name)?));
+        }
+        let remote_guid: [u8; 16] = remote_participant_guid.into();
+        if properties[2].1 != remote_guid.as_slice() {
+            return Err(security_error("participant data does not match the sender"));
+        }
+        if properties[3].1 != DSIGN_ALGO.as_bytes() || properties[4].1 != KAGREE_ALGO.as_bytes() {
+            return Err(security_error("unsupported handshake algorithms"));
+        }
+
+        let certificate = core::str::from_utf8(properties[0].1)
+            .map_err(security_error)
+            .and_then(|pem| Certificate::from_pem(pem).map_err(security_error))?;
+        verify_certificate(&certificate, &self.credentials.identity_ca)?;
+        let remote_key = public_key_of(&certificate)?;
+        let remote_identity = RemoteIdentity {
+            subject_name: certificate.tbs_certificate.subject.to_string(),
+            permissions_document: properties[1].1.to_vec(),
+        };
+
+        Ok((
+            Self::hash_identity_properties(&properties)?,
+            remote_key,
+            remote_identity,
+        ))
+    }
+
+    fn begin_handshake_request(&self) -> DdsResult<(DataHolder, HandshakeState)> {
+        let identity_properties = self.local_identity_properties();
+        let identity_properties: Vec<_> = identity_properties
+            .iter()
+            .map(|(n, v)| (*n, v.as_slice()))
+            .collect();
+        let dh_secret = EphemeralSecret::random(&mut OsRng);
+        let local = HandshakeIdentity {
+            hash_c: Self::hash_identity_properties(&identity_properties)?,
+            challenge: random_challenge(),
+            dh: dh_secret
+                .public_key()
+                .to_encoded_point(false)
+                .as_bytes()
+                .to_vec(),
+        };
+
+        let mut request = DataHolder::new(HANDSHAKE_REQUEST_CLASS_ID);
+        for (name, value) in identity_properties {
+            request.add_binary_property(name, value);
+        }
+        request.add_binary_property("hash_c1", local.hash_c);
+        request.add_binary_property("dh1", local.dh.clone());
+        request.add_binary_property("challenge1", local.challenge);
+
+        Ok((
+            request.clone(),
+            HandshakeState::WaitingReply {
+                request,
+                local,
+                dh_secret,
+            },
+        ))
+    }
+
+    fn process_request(
+        &self,
+        remote_participant_guid: Guid,
+        request: &DataHolder,
+    ) -> DdsResult<(DataHolder, HandshakeState)> {
+        let (hash_c1, remote_key, remote_identity) =
+            self.validate_remote_identity_properties(remote_participant_guid, request)?;
+        if request
+            .binary_property("hash_c1")
+            .is_some_and(|h| h != hash_c1.as_slice())
+        {
+            return Err(security_error("invalid hash_c1"));
+        }
+        let dh1 = required_property(request, "dh1")?;
+        let dh1_public_key = PublicKey::from_sec1_bytes(dh1).map_err(security_error)?;
+        let challenge1: [u8; CHALLENGE_LENGTH] = required_property(request, "challenge1")?
+            .try_into()
+            .map_err(security_error)?;
+        let remote = HandshakeIdentity {
+            hash_c: hash_c1,
+            challenge: challenge1,
+            dh: dh1.to_vec(),
+        };
+
+        let identity_properties = self.local_identity_properties();
+        let identity_properties: Vec<_> = identity_properties
+            .iter()
+            .map(|(n, v)| (*n, v.as_slice()))
+            .collect();
+        let dh_secret = EphemeralSecret::random(&mut OsRng);
+        let local = HandshakeIdentity {
+            hash_c: Self::hash_identity_properties(&identity_properties)?,
+            challenge: random_challenge(),
+            dh: dh_secret
+                .public_key()
+                .to_encoded_point(false)
+                .as_bytes()
+                .to_vec(),
+        };
+        let signature: Signature = self
+            .credentials
+            .signing_key
+            .sign(&serialize_binary_properties(&[
+                ("hash_c2", &local.hash_c),
+                ("challenge2", &local.challenge),
+                ("dh2", &local.dh),
+                ("challenge1", &remote.challenge),
+                ("dh1", &remote.dh),
+                ("hash_c1", &remote.hash_c),
+            ])?);
+
+        let mut reply = DataHolder::new(HANDSHAKE_REPLY_CLASS_ID);
+        for (name, value) in identity_properties {
+            reply.add_binary_property(name, value);
+        }
+        reply.add_binary_property("hash_c2", local.hash_c);
+        reply.add_binary_property("challenge2", local.challenge);
+        reply.add_binary_property("dh2", local.dh.clone());
+        reply.add_binary_property("challenge1", remote.challenge);
+        reply.add_binary_property("dh1", remote.dh.clone());
+        reply.add_binary_property("hash_c1", remote.hash_c);
+        reply.add_binary_property("signature", signature.to_der().as_bytes());
+
+        let secret = dh_secret
+            .diffie_hellman(&dh1_public_key)
+            .raw_secret_bytes()
+            .to_vec();
+
+        Ok((
+            reply.clone(),
+            HandshakeState::WaitingFinal {
+                reply,
+                remote,
+                local,
+                remote_key,
+                remote_identity,
+                secret,
+            },
+        ))
+    }
+
+    fn process_reply(
+        &self,
+        remote_participant_guid: Guid,
+        reply: &DataHolder,
+        local: &HandshakeIdentity,
+        dh_secret: &EphemeralSecret,
+    ) -> DdsResult<(DataHolder, RemoteIdentity, SharedSecret)> {
+        let (hash_c2, remote_key, remote_identity) =
+            self.validate_remote_identity_properties(remote_participant_guid, reply)?;
+        if reply
+            .binary_property("hash_c2")
+            .is_some_and(|h| h != hash_c2.as_slice())
+        {
+            return Err(security_error("invalid hash_c2"));
+        }
+        if required_property(reply, "challenge1")? != local.challenge.as_slice()
+            || reply
+                .binary_property("dh1")
+                .is_some_and(|dh1| dh1 != local.dh.as_slice())
+            || reply
+                .binary_property("hash_c1")
+                .is_some_and(|h| h != local.hash_c.as_slice())
+        {
+            return Err(security_error("reply does not match the request"));
+        }
+        let dh2 = required_property(reply, "dh2")?;
+        let dh2_public_key = PublicKey::from_sec1_bytes(dh2).map_err(security_error)?;
+        let challenge2: [u8; CHALLENGE_LENGTH] = required_property(reply, "challenge2")?
+            .try_into()
+            .map_err(security_error)?;
+        let remote = HandshakeIdentity {
+            hash_c: hash_c2,
+            challenge: challenge2,
+            dh: dh2.to_vec(),
+        };
+
+        let signature =
+            Signature::from_der(required_property(reply, "signature")?).map_err(security_error)?;
+        remote_key
+            .verify(
+                &serialize_binary_properties(&[
+                    ("hash_c2", &remote.hash_c),
+                    ("challenge2", &remote.challenge),
+                    ("dh2", &remote.dh),
+                    ("challenge1", &local.challenge),
+                    ("dh1", &local.dh),
+                    ("hash_c1", &local.hash_c),
+                ])?,
+                &signature,
+            )
+            .map_err(|_| security_error("invalid reply signature"))?;
+
+        let signature: Signature = self
+            .credentials
+            .signing_key
+            .sign(&serialize_binary_properties(&[
+                ("hash_c1", &local.hash_c),
+                ("challenge1", &local.challenge),
+                ("dh1", &local.dh),
+                ("challenge2", &remote.challenge),
+                ("dh2", &remote.dh),
+                ("hash_c2", &remote.hash_c),
+            ])?);
+        let mut final_message = DataHolder::new(HANDSHAKE_FINAL_CLASS_ID);
+        final_message.add_binary_property("hash_c1", local.hash_c);
+        final_message.add_binary_property("challenge1", local.challenge);
+        final_message.add_binary_property("dh1", local.dh.clone());
+        final_message.add_binary_property("challenge2", remote.challenge);
+        final_message.add_binary_property("dh2", remote.dh.clone());
+        final_message.add_binary_property("hash_c2", remote.hash_c);
+        final_message.add_binary_property("signature", signature.to_der().as_bytes());
+
+        let shared_secret = SharedSecret {
+            challenge1: local.challenge,
+            challenge2: remote.challenge,
+            secret: dh_secret
+                .diffie_hellman(&dh2_public_key)
+                .raw_secret_bytes()
+                .to_vec(),
+        };
+        Ok((final_message, remote_identity, shared_secret))
+    }
+
+    fn process_final(
+        final_message: &DataHolder,
+        remote: &HandshakeIdentity,
+        local: &HandshakeIdentity,
+        remote_key: &VerifyingKey,
+    ) -> DdsResult<()> {
+        if required_property(final_message, "challenge1")? != remote.challenge.as_slice()
+            || required_property(final_message, "challenge2")? != local.challenge.as_slice()
+            || final_message
+                .binary_property("dh1")
+                .is_some_and(|dh1| dh1 != remote.dh.as_slice())
+            || final_message
+                .binary_property("dh2")
+                .is_some_and(|dh2| dh2 != local.dh.as_slice())
+            || final_message
+                .binary_property("hash_c1")
+                .is_some_and(|h| h != remote.hash_c.as_slice())
+            || final_message
+                .binary_property("hash_c2")
+                .is_some_and(|h| h != local.hash_c.as_slice())
+        {
+            return Err(security_error("final does not match the reply"));
+        }
+        let signature = Signature::from_der(required_property(final_message, "signature")?)
+            .map_err(security_error)?;
+        remote_key
+            .verify(
+                &serialize_binary_properties(&[
+                    ("hash_c1", &remote.hash_c),
+                    ("challenge1", &remote.challenge),
+                    ("dh1", &remote.dh),
+                    ("challenge2", &local.challenge),
+                    ("dh2", &local.dh),
+                    ("hash_c2", &local.hash_c),
+                ])?,
+                &signature,
+            )
+            .map_err(|_| security_error("invalid final signature"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{infrastructure::qos_policy::Property, transport::types::ENTITYID_PARTICIPANT};
+
+    const IDENTITY_CA: &str = include_str!("../../tests/security/identity_ca.pem");
+    const PARTICIPANT1_CERTIFICATE: &str = include_str!("../../tests/security/participant1.pem");
+    const PARTICIPANT1_KEY: &str = include_str!("../../tests/security/participant1_key.pem");
+    const PARTICIPANT2_CERTIFICATE: &str = include_str!("../../tests/security/participant2.pem");
+    const PARTICIPANT2_KEY: &str = include_str!("../../tests/security/participant2_key.pem");
+    const INTRUDER_CA: &str = include_str!("../../tests/security/intruder_ca.pem");
+    const INTRUDER_CERTIFICATE: &str = include_str!("../../tests/security/intruder.pem");
+    const INTRUDER_KEY: &str = include_str!("../../tests/security/intruder_key.pem");
+
+    fn authentication(certificate: &str, key: &str, guid_prefix: u8) -> Authentication {
+        Authentication::new(
+            IdentityCredentials::new(IDENTITY_CA, certificate, key).unwrap(),
+            Guid::new([guid_prefix; 12], ENTITYID_PARTICIPANT),
+            Vec::new(),
+        )
+    }
+
+    fn take_message(status: RemoteIdentityStatus) -> DataHolder {
+        match status {
+            RemoteIdentityStatus::PendingHandshake(Some(message)) => message,
+            _ => panic!("Expected a handshake message"),
+        }
+    }
+
+    #[test]
+    fn handshake_produces_same_shared_secret() {
+        let mut initiator = authentication(PARTICIPANT1_CERTIFICATE, PARTICIPANT1_KEY, 2);
+        let mut replier = authentication(PARTICIPANT2_CERTIFICATE, PARTICIPANT2_KEY, 1);
+        let initiator_guid = initiator.participant_guid;
+        let replier_guid = replier.participant_guid;
+
+        assert!(matches!(
+            replier.validate_remote_identity(
+                initiator_guid.prefix(),
+                Some(&initiator.identity_token())
+            ),
+            RemoteIdentityStatus::PendingHandshake(None)
+        ));
+        let request = take_message(
+            initiator
+                .validate_remote_identity(replier_guid.prefix(), Some(&replier.identity_token())),
+        );
+
+        let reply_result = replier.process_handshake(initiator_guid, &request).unwrap();
+        assert!(!reply_result.completed);
+        let final_result = initiator
+            .process_handshake(replier_guid, &reply_result.message.unwrap())
+            .unwrap();
+        assert!(final_result.completed);
+        let result = replier
+            .process_handshake(initiator_guid, &final_result.message.unwrap())
+            .unwrap();
+        assert!(result.completed);
+        assert!(result.message.is_none());
+
+        let initiator_secret = initiator.shared_secret(&replier_guid.prefix()).unwrap();
+        let replier_secret = replier.shared_secret(&initiator_guid.prefix()).unwrap();
+        assert_eq!(initiator_secret.secret, replier_secret.secret);
+        assert_eq!(initiator_secret.challenge1, replier_secret.challenge1);
+        assert_eq!(initiator_secret.challenge2, replier_secret.challenge2);
+        assert_eq!(
+            initiator
+                .remote_identity(&replier_guid.prefix())
+                .unwrap()
+                .subject_name,
+            "CN=Participant 2,O=Dust DDS,C=PT"
+        );
+        assert_eq!(
+            replier
+                .remote_identity(&initiator_guid.prefix())
+                .unwrap()
+                .subject_name,
+            "CN=Participant 1,O=Dust DDS,C=PT"
+        );
+        assert!(matches!(
+            replier.validate_remote_identity(
+                initiator_guid.prefix(),
+                Some(&initiator.identity_token())
+            ),
+            RemoteIdentityStatus::Authenticated
+        ));
+    }
+
+    #[test]
+    fn handshake_request_with_certificate_of_other_ca_is_rejected() {
+        let mut intruder = Authentication::new(
+            IdentityCredentials::new(INTRUDER_CA, INTRUDER_CERTIFICATE, INTRUDER_KEY).unwrap(),
+            Guid::new([2; 12], ENTITYID_PARTICIPANT),
+            Vec::new(),
+        );
+        let mut replier = authentication(PARTICIPANT2_CERTIFICATE, PARTICIPANT2_KEY, 1);
+        let request = take_message(intruder.validate_remote_identity(
+            replier.participant_guid.prefix(),
+            Some(&replier.identity_token()),
+        ));
+
+        assert!(replier
+            .process_handshake(intruder.participant_guid, &request)
+            .is_err());
+        assert!(replier
+            .shared_secret(&intruder.participant_guid.prefix())
+            .is_none());
+    }
+
+    #[test]
+    fn handshake_reply_with_invalid_signature_is_rejected() {
+        let mut initiator = authentication(PARTICIPANT1_CERTIFICATE, PARTICIPANT1_KEY, 2);
+        let mut replier = authentication(PARTICIPANT2_CERTIFICATE, PARTICIPANT2_KEY, 1);
+        let request = take_message(initiator.validate_remote_identity(
+            replier.participant_guid.prefix(),
+            Some(&replier.identity_token()),
+        ));
+        let mut reply = replier
+            .process_handshake(initiator.participant_guid, &request)
+            .unwrap()
+            .message
+            .unwrap();
+        for property in reply.binary_properties.iter_mut() {
+            if property.name == "challenge2" {
+                property.value[0] ^= 0xff;
+            }
+        }
+
+        assert!(initiator
+            .process_handshake(replier.participant_guid, &reply)
+            .is_err());
+        // The handshake can still be completed with the genuine reply
+        assert!(matches!(
+            initiator.validate_remote_identity(
+                replier.participant_guid.prefix(),
+                Some(&replier.identity_token())
+            ),
+            RemoteIdentityStatus::PendingHandshake(Some(_))
+        ));
+    }
+
+    #[test]
+    fn participant_without_identity_token_is_rejected() {
+        let mut authentication = authentication(PARTICIPANT1_CERTIFICATE, PARTICIPANT1_KEY, 2);
+        assert!(matches!(
+            authentication.validate_remote_identity([1; 12], None),
+            RemoteIdentityStatus::Rejected
+        ));
+    }
+
+    #[test]
+    fn credentials_from_property_qos() {
+        let property = |name: &str, value: &str| Property {
+            name: name.to_string(),
+            value: format!("data:,{}", value),
+            propagate: false,
+        };
+        let no_security = PropertyQosPolicy::default();
+        assert!(IdentityCredentials::from_property_qos(&no_security)
+            .unwrap()
+            .is_none());
+
+        let security = PropertyQosPolicy {
+            value: vec![
+                property(PROPERTY_IDENTITY_CA, IDENTITY_CA),
+                property(PROPERTY_IDENTITY_CERTIFICATE, PARTICIPANT1_CERTIFICATE),
+                property(PROPERTY_PRIVATE_KEY, PARTICIPANT1_KEY),
+            ],
+            ..Default::default()
+        };
+        assert!(IdentityCredentials::from_property_qos(&security)
+            .unwrap()
+            .is_some());
+
+        let missing_key = PropertyQosPolicy {
+            value: security.value[..2].to_vec(),
+            ..Default::default()
+        };
+        assert!(IdentityCredentials::from_property_qos(&missing_key).is_err());
+    }
+
+    #[test]
+    fn credentials_with_invalid_certificate_or_key() {
+        assert!(
+            IdentityCredentials::new(IDENTITY_CA, PARTICIPANT1_CERTIFICATE, PARTICIPANT2_KEY)
+                .is_err()
+        );
+        assert!(IdentityCredentials::new(IDENTITY_CA, INTRUDER_CERTIFICATE, INTRUDER_KEY).is_err());
+    }
+}
@@ -0,0 +1,613 @@
+use super::{access_control::ProtectionKind, authentication::SharedSecret, types::DataHolder};
+use crate::{
+    infrastructure::error::{DdsError, DdsResult},
+    rtps::{
+        error::{RtpsError, RtpsErrorKind, RtpsResult},
+        messages::{
+            overall_structure::SubmessageCrypto,
+            submessages::{
+                sec_body::SecureBodySubmessage,
+                sec_postfix::SecurePostfixSubmessage,
+                sec_prefix::{CryptoHeader, SecurePrefixSubmessage},
+            },
+        },
+    },
+    topic_definition::type_support::{
+        deserialize_rtps_encapsulated_data, serialize_rtps_xtypes_xcdr1_le,
+    },
+    transport::types::{EntityId, GuidPrefix},
+    xtypes::{deserialize::XTypesDeserialize, serialize::XTypesSerialize},
+};
+use aes_gcm::{
+    aead::{AeadInPlace, KeyInit},
+    Aes256Gcm, Key, Nonce, Tag,
+};
+use hmac::{Hmac, Mac};
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, sync::Mutex};
+
+// Transformation kinds of the builtin cryptographic plugin as defined in
+// DDS Security 9.5.2.1.1 "CryptoTransformKind"
+const CRYPTO_TRANSFORMATION_KIND_AES256_GMAC: [u8; 4] = [0, 0, 0, 3];
+const CRYPTO_TRANSFORMATION_KIND_AES256_GCM: [u8; 4] = [0, 0, 0, 4];
+
+const AES_GCM_GMAC_CLASS_ID: &str = "DDS:Crypto:AES_GCM_GMAC";
+const KEY_MATERIAL_PROPERTY: &str = "dds.cryp.keymat";
+// Key id of the key material last received from the remote participant. The tokens are sent
+// best-effort so the participants which have not received the key material answer again.
+const RECEIVED_KEY_ID_PROPERTY: &str = "dds.cryp.received_key_id";
+
+// Number of submessages protected with the same session key before it is renewed
+const MAX_SUBMESSAGES_PER_SESSION: u64 = 1 << 16;
+
+fn crypto_error(reason: impl core::fmt::Display) -> DdsError {
+    DdsError::Error(format!("Cryptographic transformation failed: {}", reason))
+}
+
+fn invalid_submessage(reason: &str) -> RtpsError {
+    RtpsError::new(RtpsErrorKind::InvalidData, reason)
+}
+
+// Key material exchanged in the crypto tokens as defined in
+// DDS Security 9.5.2.1.2 "KeyMaterial_AES_GCM_GMAC"
+#[derive(Debug, PartialEq, Eq, Clone, XTypesSerialize, XTypesDeserialize)]
+struct KeyMaterial {
+    transformation_kind: [u8; 4],
+    master_salt: Vec<u8>,
+    sender_key_id: [u8; 4],
+    master_sender_key: Vec<u8>,
+    receiver_specific_key_id: [u8; 4],
+    master_receiver_specific_key: Vec<u8>,
+}
+
+impl KeyMaterial {
+    fn generate() -> Self {
+        let mut master_salt = vec![0; 32];
+        let mut sender_key_id = [0; 4];
+        let mut master_sender_key = vec![0; 32];
+        OsRng.fill_bytes(&mut master_salt);
+        OsRng.fill_bytes(&mut sender_key_id);
+        OsRng.fill_bytes(&mut master_sender_key);
+        Self {
+            transformation_kind: CRYPTO_TRANSFORMATION_KIND_AES256_GCM,
+            master_salt,
+            sender_key_id,
+            master_sender_key,
+            receiver_specific_key_id: [0; 4],
+            master_receiver_specific_key: Vec::new(),
+        }
+    }
+
+    // Key material protecting the exchange of the crypto tokens derived from the secret
+    // shared in the authentication handshake as in DDS Security 9.5.2.1.2
+    fn key_exchange(shared_secret: &SharedSecret) -> Self {
+        let master_salt = Sha256::new()
+            .chain_update(shared_secret.challenge1)
+            .chain_update(b"keyexchange salt")
+            .chain_update(&shared_secret.secret)
+            .chain_update(shared_secret.challenge2)
+            .finalize();
+        let master_sender_key = Sha256::new()
+            .chain_update(shared_secret.challenge2)
+            .chain_update(b"key exchange key")
+            .chain_update(&shared_secret.secret)
+            .chain_update(shared_secret.challenge1)
+            .finalize();
+        Self {
+            transformation_kind: CRYPTO_TRANSFORMATION_KIND_AES256_GCM,
+            master_salt: master_salt.to_vec(),
+            sender_key_id: [0; 4],
+            master_sender_key: master_sender_key.to_vec(),
+            receiver_specific_key_id: [0; 4],
+            master_receiver_specific_key: Vec::new(),
+        }
+    }
+
+    // DDS Security 9.5.3.3.3: SessionKey = HMAC256(MasterKey, "SessionKey" | MasterSalt | SessionId)
+    fn session_key(&self, session_id: [u8; 4]) -> Aes256Gcm {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.master_sender_key)
+            .expect("HMAC accepts keys of any size");
+        mac.update(b"SessionKey");
+        mac.update(&self.master_salt);
+        mac.update(&session_id);
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&mac.finalize().into_bytes()))
+    }
+}
+
+// The content of the encrypted submessages is the ciphertext and the content of the signed
+// submessages is the submessage itself. In both cases the MAC is the GCM authentication tag.
+fn seal(
+    session_key: &Aes256Gcm,
+    crypto_header: &CryptoHeader,
+    plaintext: &[u8],
+) -> Result<(Vec<u8>, [u8; 16]), aes_gcm::Error> {
+    let nonce = [
+        &crypto_header.session_id[..],
+        &crypto_header.initialization_vector_suffix[..],
+    ]
+    .concat();
+    let nonce = Nonce::from_slice(&nonce);
+    let mut content = plaintext.to_vec();
+    let tag = if crypto_header.transformation_kind == CRYPTO_TRANSFORMATION_KIND_AES256_GCM {
+        session_key.encrypt_in_place_detached(nonce, &[], &mut content)?
+    } else {
+        session_key.encrypt_in_place_detached(nonce, plaintext, &mut [])?
+    };
+    Ok((content, tag.into()))
+}
+
+fn open(
+    session_key: &Aes256Gcm,
+    crypto_header: &CryptoHeader,
+    content: &[u8],
+    common_mac: &[u8; 16],
+) -> Result<Vec<u8>, aes_gcm::Error> {
+    let nonce = [
+        &crypto_header.session_id[..],
+        &crypto_header.initialization_vector_suffix[..],
+    ]
+    .concat();
+    let nonce = Nonce::from_slice(&nonce);
+    let tag = Tag::from_slice(common_mac);
+    match crypto_header.transformation_kind {
+        CRYPTO_TRANSFORMATION_KIND_AES256_GCM => {
+            let mut plaintext = content.to_vec();
+            session_key.decrypt_in_place_detached(nonce, &[], &mut plaintext, tag)?;
+            Ok(plaintext)
+        }
+        CRYPTO_TRANSFORMATION_KIND_AES256_GMAC => {
+            session_key.decrypt_in_place_detached(nonce, content, &mut [], tag)?;
+            Ok(content.to_vec())
+        }
+        _ => Err(aes_gcm::Error),
+    }
+}
+
+struct Session {
+    session_id: u32,
+    session_key: Aes256Gcm,
+    // The initialization vector is never repeated for the same key
+    initialization_vector_suffix: u64,
+}
+
+// Builtin cryptographic plugin DDS:Crypto:AES-GCM-GMAC of DDS Security 9.5. Each participant
+// protects the submessages of its endpoints with its own key material which is sent to the
+// authenticated remote participants in the participant crypto tokens. The serialized payload is
+// not transformed separately so the data protection of the topics applies to the whole
+// submessage. The origin authentication with receiver specific MACs is not supported.
+pub struct Cryptography {
+    local_key_material: KeyMaterial,
+    local_session: Mutex<Session>,
+    protected_endpoint_list: Mutex<HashMap<EntityId, ProtectionKind>>,
+    remote_key_material_list: Mutex<HashMap<GuidPrefix, KeyMaterial>>,
+}
+
+impl Default for Cryptography {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cryptography {
+    pub fn new() -> Self {
+        let local_key_material = KeyMaterial::generate();
+        let session_id = OsRng.next_u32();
+        let local_session = Session {
+            session_id,
+            session_key: local_key_material.session_key(session_id.to_be_bytes()),
+            initialization_vector_suffix: 0,
+        };
+        Self {
+            local_key_material,
+            local_session: Mutex::new(local_session),
+            protected_endpoint_list: Mutex::new(HashMap::new()),
+            remote_key_material_list: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn register_local_endpoint(&self, entity_id: EntityId, protection_kind: ProtectionKind) {
+        if protection_kind != ProtectionKind::None {
+            self.protected_endpoint_list
+                .lock()
+                .expect("Mutex should not be poisoned")
+                .insert(entity_id, protection_kind);
+        }
+    }
+
+    pub fn has_remote_participant_key_material(&self, remote_guid_prefix: &GuidPrefix) -> bool {
+        self.remote_key_material_list
+            .lock()
+            .expect("Mutex should not be poisoned")
+            .contains_key(remote_guid_prefix)
+    }
+
+    pub fn remove_remote_participant(&self, remote_guid_prefix: &GuidPrefix) {
+        self.remote_key_material_list
+            .lock()
+            .expect("Mutex should not be poisoned")
+            .remove(remote_guid_prefix);
+    }
+
+    // The key material is encrypted with the key derived from the handshake with the remote
+    // participant since the tokens are sent through the ParticipantStatelessMessage
+    pub fn participant_crypto_tokens(
+        &self,
+        remote_guid_prefix: &GuidPrefix,
+        shared_secret: &SharedSecret,
+    ) -> DdsResult<DataHolder> {
+        let key_exchange = KeyMaterial::key_exchange(shared_secret);
+        let mut crypto_header = CryptoHeader {
+            transformation_kind: CRYPTO_TRANSFORMATION_KIND_AES256_GCM,
+            transformation_key_id: key_exchange.sender_key_id,
+            session_id: [0; 4],
+            initialization_vector_suffix: [0; 8],
+        };
+        OsRng.fill_bytes(&mut crypto_header.session_id);
+        OsRng.fill_bytes(&mut crypto_header.initialization_vector_suffix);
+        let key_material = serialize_rtps_xtypes_xcdr1_le(&self.local_key_material)?;
+        let (content, common_mac) = seal(
+            &key_exchange.session_key(crypto_header.session_id),
+            &crypto_header,
+            &key_material,
+        )
+        .map_err(|_| crypto_error("key material could not be encrypted"))?;
+
+        let mut token = DataHolder::new(AES_GCM_GMAC_CLASS_ID);
+        token.add_binary_property(
+            KEY_MATERIAL_PROPERTY,
+            [
+                &crypto_header.session_id[..],
+                &crypto_header.initialization_vector_suffix,
+                &content,
+                &common_mac,
+            ]
+            .concat(),
+        );
+        if let Some(remote_key_material) = self
+            .remote_key_material_list
+            .lock()
+            .expect("Mutex should not be poisoned")
+            .get(remote_guid_prefix)
+        {
+            token.add_binary_property(RECEIVED_KEY_ID_PROPERTY, remote_key_material.sender_key_id);
+        }
+        Ok(token)
+    }
+
+    // Returns whether the remote participant has not received the local key material yet
+    pub fn set_remote_participant_crypto_tokens(
+        &self,
+        remote_guid_prefix: GuidPrefix,
+        shared_secret: &SharedSecret,
+        token: &DataHolder,
+    ) -> DdsResult<bool> {
+        if token.class_id != AES_GCM_GMAC_CLASS_ID {
+            return Err(crypto_error("unsupported crypto token"));
+        }
+        let encrypted_key_material = token
+            .binary_property(KEY_MATERIAL_PROPERTY)
+            .filter(|k| k.len() >= 28)
+            .ok_or_else(|| crypto_error("crypto token without key material"))?;
+        let key_exchange = KeyMaterial::key_exchange(shared_secret);
+        let crypto_header = CryptoHeader {
+            transformation_kind: CRYPTO_TRANSFORMATION_KIND_AES256_GCM,
+            transformation_key_id: key_exchange.sender_key_id,
+            session_id: encrypted_key_material[0..4].try_into().expect("4 bytes"),
+            initialization_vector_suffix: encrypted_key_material[4..12]
+                .try_into()
+                .expect("8 bytes"),
+        };
+        let (content, common_mac) =
+            encrypted_key_material[12..].split_at(encrypted_key_material.len() - 28);
+        let key_material = open(
+            &key_exchange.session_key(crypto_header.session_id),
+            &crypto_header,
+            content,
+            common_mac.try_into().expect("16 bytes"),
+        )
+        .map_err(|_| crypto_error("key material could not be decrypted"))?;
+        let remote_key_material: KeyMaterial =
+            deserialize_rtps_encapsulated_data(&mut key_material.as_slice())?;
+        if remote_key_material.transformation_kind != CRYPTO_TRANSFORMATION_KIND_AES256_GCM
+            || remote_key_material.master_salt.len() != 32
+            || remote_key_material.master_sender_key.len() != 32
+        {
+            return Err(crypto_error("unsupported key material"));
+        }
+        self.remote_key_material_list
+            .lock()
+            .expect("Mutex should not be poisoned")
+            .insert(remote_guid_prefix, remote_key_material);
+
+        Ok(token.binary_property(RECEIVED_KEY_ID_PROPERTY)
+            != Some(&self.local_key_material.sender_key_id[..]))
+    }
+}
+
+impl SubmessageCrypto for Cryptography {
+    fn is_protected_endpoint(&self, local_entity_id: EntityId) -> bool {
+        self.protected_endpoint_list
+            .lock()
+            .expect("Mutex should not be poisoned")
+            .contains_key(&local_entity_id)
+    }
+
+    fn encode_submessage(
+        &self,
+        local_entity_id: EntityId,
+        plain_submessage: &[u8],
+    ) -> RtpsResult<(
+        SecurePrefixSubmessage,
+        SecureBodySubmessage,
+        SecurePostfixSubmessage,
+    )> {
+        let protection_kind = self
+            .protected_endpoint_list
+            .lock()
+            .expect("Mutex should not be poisoned")
+            .get(&local_entity_id)
+            .copied()
+            .ok_or_else(|| invalid_submessage("Submessage of an unprotected endpoint"))?;
+        let transformation_kind = if protection_kind.is_encrypted() {
+            CRYPTO_TRANSFORMATION_KIND_AES256_GCM
+        } else {
+            CRYPTO_TRANSFORMATION_KIND_AES256_GMAC
+        };
+
+        let mut session = self
+            .local_session
+            .lock()
+            .expect("Mutex should not be poisoned");
+        session.initialization_vector_suffix += 1;
+        if session
+            .initialization_vector_suffix
+            .is_multiple_of(MAX_SUBMESSAGES_PER_SESSION)
+        {
+            session.session_id = session.session_id.wrapping_add(1);
+            session.session_key = self
+                .local_key_material
+                .session_key(session.session_id.to_be_bytes());
+        }
+        let crypto_header = CryptoHeader {
+            transformation_kind,
+            transformation_key_id: self.local_key_material.sender_key_id,
+            session_id: session.session_id.to_be_bytes(),
+            initialization_vector_suffix: session.initialization_vector_suffix.to_be_bytes(),
+        };
+        let (crypto_content, common_mac) =
+            seal(&session.session_key, &crypto_header, plain_submessage)
+                .map_err(|_| invalid_submessage("Submessage could not be protected"))?;
+
+        Ok((
+            SecurePrefixSubmessage::new(crypto_header),
+            SecureBodySubmessage::new(crypto_content),
+            SecurePostfixSubmessage::new(common_mac),
+        ))
+    }
+
+    fn decode_submessage(
+        &self,
+        source_guid_prefix: GuidPrefix,
+        secure_prefix: &SecurePrefixSubmessage,
+        secure_body: &SecureBodySubmessage,
+        secure_postfix: &SecurePostfixSubmessage,
+    ) -> RtpsResult<Vec<u8>> {
+        let crypto_header = secure_prefix.crypto_header();
+        let session_key = {
+            let remote_key_material_list = self
+                .remote_key_material_list
+                .lock()
+                .expect("Mutex should not be poisoned");
+            let remote_key_material = remote_key_material_list
+                .get(&source_guid_prefix)
+                .filter(|k| k.sender_key_id == crypto_header.transformation_key_id)
+                .ok_or_else(|| invalid_submessage("Unknown key of the protected submessage"))?;
+            remote_key_material.session_key(crypto_header.session_id)
+        };
+        open(
+            &session_key,
+            crypto_header,
+            secure_body.crypto_content(),
+            secure_postfix.common_mac(),
+        )
+        .map_err(|_| invalid_submessage("Protected submessage could not be verified"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rtps::messages::{
+            overall_structure::{
+                write_submessage_into_bytes_vec, RtpsMessageHeader, RtpsMessageRead,
+                RtpsMessageWrite, RtpsSubmessageReadKind, Submessage,
+            },
+            submessage_elements::{Data, ParameterList},
+            submessages::data::DataSubmessage,
+        },
+        transport::types::{Guid, ProtocolVersion, USER_DEFINED_WRITER_WITH_KEY},
+    };
+
+    const WRITER_ID: EntityId = EntityId::new([0, 0, 1], USER_DEFINED_WRITER_WITH_KEY);
+
+    fn shared_secret(secret: u8) -> SharedSecret {
+        SharedSecret {
+            challenge1: [1; 32],
+            challenge2: [2; 32],
+            secret: vec![secret; 32],
+        }
+    }
+
+    fn data_submessage() -> Box<dyn Submessage + Send> {
+        Box::new(DataSubmessage::new(
+            false,
+            false,
+            true,
+            false,
+            EntityId::new([0, 0, 2], 0x07),
+            WRITER_ID,
+            1,
+            ParameterList::empty(),
+            Data::new(b"secret data".to_vec().into()),
+        ))
+    }
+
+    // The remote participant receives the local key material as it would after the handshake
+    fn exchange_key_material(local: &Cryptography, local_guid_prefix: GuidPrefix) -> Cryptography {
+        let remote = Cryptography::new();
+        let token = local
+            .participant_crypto_tokens(&[2; 12], &shared_secret(7))
+            .unwrap();
+        remote
+            .set_remote_participant_crypto_tokens(local_guid_prefix, &shared_secret(7), &token)
+            .unwrap();
+        remote
+    }
+
+    fn send_and_receive(
+        local: &Cryptography,
+        remote: &Cryptography,
+        tamper: impl Fn(&mut Vec<u8>),
+    ) -> RtpsMessageRead {
+        let header = RtpsMessageHeader::new(ProtocolVersion::new(2, 4), [1, 2], [1; 12]);
+        let message_list =
+            RtpsMessageWrite::new_packed_list(&header, &[data_submessage()], 65000, Some(local));
+        let mut buffer = message_list[0].buffer().to_vec();
+        tamper(&mut buffer);
+        RtpsMessageRead::try_from(buffer.as_slice())
+            .unwrap()
+            .decode_secure_submessages(remote, 1000, |_| true)
+    }
+
+    #[test]
+    fn encrypted_submessage_is_decoded_by_remote_participant() {
+        let local = Cryptography::new();
+        local.register_local_endpoint(WRITER_ID, ProtectionKind::Encrypt);
+        let remote = exchange_key_material(&local, [1; 12]);
+
+        let header = RtpsMessageHeader::new(ProtocolVersion::new(2, 4), [1, 2], [1; 12]);
+        let message_list =
+            RtpsMessageWrite::new_packed_list(&header, &[data_submessage()], 65000, Some(&local));
+        let buffer = message_list[0].buffer();
+        assert!(!buffer
+            .windows(b"secret data".len())
+            .any(|w| w == b"secret data"));
+
+        let message = send_and_receive(&local, &remote, |_| ());
+        assert_eq!(message.discarded_submessage_count(), 0);
+        let submessages = message.submessages();
+        assert_eq!(submessages.len(), 1);
+        assert!(matches!(
+            &submessages[0],
+            RtpsSubmessageReadKind::Data(d) if d.serialized_payload().as_ref() == b"secret data"
+        ));
+    }
+
+    #[test]
+    fn signed_submessage_is_sent_in_clear() {
+        let local = Cryptography::new();
+        local.register_local_endpoint(WRITER_ID, ProtectionKind::Sign);
+        let remote = exchange_key_material(&local, [1; 12]);
+
+        let (secure_prefix, secure_body, _) = local
+            .encode_submessage(
+                WRITER_ID,
+                &write_submessage_into_bytes_vec(data_submessage().as_ref()),
+            )
+            .unwrap();
+        assert_eq!(
+            secure_prefix.crypto_header().transformation_kind,
+            CRYPTO_TRANSFORMATION_KIND_AES256_GMAC
+        );
+        assert_eq!(
+            secure_body.crypto_content(),
+            write_submessage_into_bytes_vec(data_submessage().as_ref())
+        );
+
+        let message = send_and_receive(&local, &remote, |_| ());
+        assert_eq!(message.submessages().len(), 1);
+    }
+
+    #[test]
+    fn tampered_submessage_is_discarded() {
+        let local = Cryptography::new();
+        local.register_local_endpoint(WRITER_ID, ProtectionKind::Sign);
+        let remote = exchange_key_material(&local, [1; 12]);
+
+        // Modify the last byte of the payload in the SEC_BODY
+        let message = send_and_receive(&local, &remote, |buffer| {
+            let position = buffer
+                .windows(b"secret data".len())
+                .position(|w| w == b"secret data")
+                .unwrap();
+            buffer[position] = b'S';
+        });
+        assert_eq!(message.discarded_submessage_count(), 1);
+        assert!(message.submessages().is_empty());
+    }
+
+    #[test]
+    fn submessage_of_participant_without_key_material_is_discarded() {
+        let local = Cryptography::new();
+        local.register_local_endpoint(WRITER_ID, ProtectionKind::Encrypt);
+        let remote = Cryptography::new();
+
+        let message = send_and_receive(&local, &remote, |_| ());
+        assert_eq!(message.discarded_submessage_count(), 1);
+        assert!(message.submessages().is_empty());
+    }
+
+    #[test]
+    fn unprotected_submessage_of_protected_endpoint_is_discarded() {
+        let local = Cryptography::new();
+        let remote = Cryptography::new();
+
+        let message = send_and_receive(&local, &remote, |_| ());
+        assert_eq!(message.discarded_submessage_count(), 1);
+
+        let header = RtpsMessageHeader::new(ProtocolVersion::new(2, 4), [1, 2], [1; 12]);
+        let message_list =
+            RtpsMessageWrite::new_packed_list(&header, &[data_submessage()], 65000, None);
+        let message = RtpsMessageRead::try_from(message_list[0].buffer())
+            .unwrap()
+            .decode_secure_submessages(&remote, 1000, |guid| guid != Guid::new([1; 12], WRITER_ID));
+        assert_eq!(message.submessages().len(), 1);
+    }
+
+    #[test]
+    fn crypto_tokens_require_the_same_shared_secret() {
+        let local = Cryptography::new();
+        let remote = Cryptography::new();
+        let token = local
+            .participant_crypto_tokens(&[2; 12], &shared_secret(7))
+            .unwrap();
+
+        assert!(remote
+            .set_remote_participant_crypto_tokens([1; 12], &shared_secret(8), &token)
+            .is_err());
+        assert!(!remote.has_remote_participant_key_material(&[1; 12]));
+    }
+
+    #[test]
+    fn crypto_tokens_are_answered_until_received_by_remote_participant() {
+        let local = Cryptography::new();
+        let remote = Cryptography::new();
+
+        let local_token = local
+            .participant_crypto_tokens(&[2; 12], &shared_secret(7))
+            .unwrap();
+        assert!(remote
+            .set_remote_participant_crypto_tokens([1; 12], &shared_secret(7), &local_token)
+            .unwrap());
+
+        let remote_token = remote
+            .participant_crypto_tokens(&[1; 12], &shared_secret(7))
+            .unwrap();
+        assert!(!local
+            .set_remote_participant_crypto_tokens([2; 12], &shared_secret(7), &remote_token)
+            .unwrap());
+        assert!(local.has_remote_participant_key_material(&[2; 12]));
+    }
+}
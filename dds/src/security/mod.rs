@@ -0,0 +1,14 @@
+// Builtin plugins of the DDS Security specification. The plugins are only available with the
+// "security" feature and they are configured with the properties of the DomainParticipantQos.
+// Without the plugins only the tokens announced by the remote participants are kept
+#[cfg_attr(not(feature = "security"), allow(dead_code))]
+pub mod types;
+
+#[cfg(feature = "security")]
+pub mod access_control;
+#[cfg(feature = "security")]
+pub mod authentication;
+#[cfg(feature = "security")]
+pub mod cryptography;
+#[cfg(feature = "security")]
+pub mod participant_security;
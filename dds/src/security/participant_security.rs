@@ -0,0 +1,143 @@
+use super::{
+    access_control::AccessControl,
+    authentication::Authentication,
+    cryptography::Cryptography,
+    types::{
+        DataHolder, MessageIdentity, ParticipantGenericMessage, ParticipantSecurityInfo,
+        GMCLASSID_SECURITY_PARTICIPANT_CRYPTO_TOKENS,
+    },
+};
+use crate::{
+    implementation::data_representation_builtin_endpoints::spdp_discovered_participant_data::SpdpDiscoveredParticipantData,
+    infrastructure::error::DdsResult,
+    topic_definition::type_support::serialize_rtps_xtypes_xcdr1_le,
+    transport::{
+        history_cache::CacheChange,
+        reader::TransportStatelessReader,
+        types::{
+            ChangeKind, EntityId, Guid, GuidPrefix, Locator, ENTITYID_PARTICIPANT, GUID_UNKNOWN,
+        },
+        writer::TransportStatelessWriter,
+    },
+};
+use std::{collections::HashMap, sync::Arc};
+
+pub struct ParticipantSecurity {
+    pub authentication: Authentication,
+    pub access_control: Option<AccessControl>,
+    // The submessages are only protected when the governance of the access control requires it
+    pub cryptography: Option<Arc<Cryptography>>,
+    stateless_message_writer: Box<dyn TransportStatelessWriter>,
+    _stateless_message_reader: Box<dyn TransportStatelessReader>,
+    stateless_message_sequence_number: i64,
+    // Participants discovered whose authentication is not yet completed
+    pub pending_participant_list: HashMap<GuidPrefix, SpdpDiscoveredParticipantData>,
+}
+
+impl ParticipantSecurity {
+    pub fn new(
+        authentication: Authentication,
+        access_control: Option<AccessControl>,
+        cryptography: Option<Arc<Cryptography>>,
+        stateless_message_writer: Box<dyn TransportStatelessWriter>,
+        stateless_message_reader: Box<dyn TransportStatelessReader>,
+    ) -> Self {
+        Self {
+            authentication,
+            access_control,
+            cryptography,
+            stateless_message_writer,
+            _stateless_message_reader: stateless_message_reader,
+            stateless_message_sequence_number: 0,
+            pending_participant_list: HashMap::new(),
+        }
+    }
+
+    pub fn participant_security_info(&self) -> Option<ParticipantSecurityInfo> {
+        self.access_control
+            .as_ref()
+            .map(|a| a.participant_security_info())
+    }
+
+    pub fn register_local_endpoint(&self, entity_id: EntityId, topic_name: &str) {
+        if let (Some(access_control), Some(cryptography)) =
+            (&self.access_control, &self.cryptography)
+        {
+            cryptography.register_local_endpoint(
+                entity_id,
+                access_control.endpoint_protection_kind(topic_name),
+            );
+        }
+    }
+
+    // Sends the key material of the local submessages to the participant authenticated with
+    // the handshake
+    pub fn send_participant_crypto_tokens(
+        &mut self,
+        remote_guid_prefix: GuidPrefix,
+    ) -> DdsResult<()> {
+        let (Some(cryptography), Some(shared_secret)) = (
+            &self.cryptography,
+            self.authentication.shared_secret(&remote_guid_prefix),
+        ) else {
+            return Ok(());
+        };
+        let crypto_tokens =
+            cryptography.participant_crypto_tokens(&remote_guid_prefix, shared_secret)?;
+        self.send_stateless_message(
+            Guid::new(remote_guid_prefix, ENTITYID_PARTICIPANT),
+            GMCLASSID_SECURITY_PARTICIPANT_CRYPTO_TOKENS,
+            crypto_tokens,
+        )
+    }
+
+    pub fn add_remote_participant_locators(&mut self, locator_list: &[Locator]) {
+        for &locator in locator_list {
+            self.stateless_message_writer.add_reader_locator(locator);
+        }
+    }
+
+    pub fn remove_remote_participant_locators(&mut self, locator_list: &[Locator]) {
+        for locator in locator_list {
+            self.stateless_message_writer.remove_reader_locator(locator);
+        }
+    }
+
+    // The stateless messages are sent to all the remote participant locators and each
+    // participant only processes the messages whose destination it is.
+    pub fn send_stateless_message(
+        &mut self,
+        destination_participant_guid: Guid,
+        message_class_id: &str,
+        message_data: DataHolder,
+    ) -> DdsResult<()> {
+        self.stateless_message_sequence_number += 1;
+        let writer_guid = self.stateless_message_writer.guid();
+        let message = ParticipantGenericMessage {
+            message_identity: MessageIdentity {
+                source_guid: writer_guid,
+                sequence_number: self.stateless_message_sequence_number,
+            },
+            related_message_identity: MessageIdentity::default(),
+            destination_participant_guid,
+            destination_endpoint_guid: GUID_UNKNOWN,
+            source_endpoint_guid: writer_guid,
+            message_class_id: message_class_id.to_owned(),
+            message_data: vec![message_data],
+        };
+        let change = CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid,
+            sequence_number: self.stateless_message_sequence_number,
+            source_timestamp: None,
+            instance_handle: None,
+            data_value: serialize_rtps_xtypes_xcdr1_le(&message)?.into(),
+        };
+        let history_cache = self.stateless_message_writer.history_cache();
+        history_cache.add_change(change);
+        // The messages are sent as soon as they are added so they are not kept in the history
+        // where they would be resent to the locators added later
+        history_cache.remove_change(self.stateless_message_sequence_number);
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    infrastructure::qos_policy::{BinaryProperty, Property},
+    transport::types::{
+        EntityId, Guid, BUILT_IN_READER_NO_KEY, BUILT_IN_WRITER_NO_KEY, GUID_UNKNOWN,
+    },
+    xtypes::{deserialize::XTypesDeserialize, serialize::XTypesSerialize},
+};
+
+pub const ENTITYID_P2P_BUILTIN_PARTICIPANT_STATELESS_WRITER: EntityId =
+    EntityId::new([0x00, 0x02, 0x01], BUILT_IN_WRITER_NO_KEY);
+
+pub const ENTITYID_P2P_BUILTIN_PARTICIPANT_STATELESS_READER: EntityId =
+    EntityId::new([0x00, 0x02, 0x01], BUILT_IN_READER_NO_KEY);
+
+// GenericMessageClassId of the messages exchanged during the authentication handshake
+pub const GMCLASSID_SECURITY_AUTH_HANDSHAKE: &str = "dds.sec.auth";
+
+// GenericMessageClassId of the messages with the crypto tokens of the participant. The
+// ParticipantVolatileMessageSecure endpoints are not available so they are exchanged with the
+// stateless messages, encrypted with the key derived from the handshake.
+pub const GMCLASSID_SECURITY_PARTICIPANT_CRYPTO_TOKENS: &str = "dds.sec.participant_crypto_tokens";
+
+// Container of the tokens exchanged by the security plugins as defined in
+// DDS Security 7.2.3 "DataHolder"
+#[derive(Debug, PartialEq, Eq, Clone, Default, XTypesSerialize, XTypesDeserialize)]
+pub struct DataHolder {
+    pub class_id: String,
+    pub properties: Vec<Property>,
+    pub binary_properties: Vec<BinaryProperty>,
+}
+
+impl DataHolder {
+    pub fn new(class_id: impl Into<String>) -> Self {
+        Self {
+            class_id: class_id.into(),
+            properties: Vec::new(),
+            binary_properties: Vec::new(),
+        }
+    }
+
+    pub fn add_property(&mut self, name: &str, value: impl Into<String>) {
+        self.properties.push(Property {
+            name: name.to_owned(),
+            value: value.into(),
+            propagate: true,
+        });
+    }
+
+    pub fn add_binary_property(&mut self, name: &str, value: impl Into<Vec<u8>>) {
+        self.binary_properties.push(BinaryProperty {
+            name: name.to_owned(),
+            value: value.into(),
+            propagate: true,
+        });
+    }
+
+    pub fn binary_property(&self, name: &str) -> Option<&[u8]> {
+        self.binary_properties
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.value.as_slice())
+    }
+}
+
+// Security attributes of a participant as defined in
+// DDS Security 7.4.1.5 "Extension to RTPS Standard DCPSParticipants Builtin Topic"
+#[derive(Debug, PartialEq, Eq, Clone, Copy, XTypesSerialize, XTypesDeserialize)]
+pub struct ParticipantSecurityInfo {
+    pub participant_security_attributes: u32,
+    pub plugin_participant_security_attributes: u32,
+}
+
+impl ParticipantSecurityInfo {
+    pub const IS_VALID: u32 = 1 << 31;
+    pub const IS_RTPS_PROTECTED: u32 = 1 << 0;
+    pub const IS_DISCOVERY_PROTECTED: u32 = 1 << 1;
+    pub const IS_LIVELINESS_PROTECTED: u32 = 1 << 2;
+
+    pub const IS_RTPS_ENCRYPTED: u32 = 1 << 0;
+    pub const IS_DISCOVERY_ENCRYPTED: u32 = 1 << 1;
+    pub const IS_LIVELINESS_ENCRYPTED: u32 = 1 << 2;
+    pub const IS_RTPS_ORIGIN_AUTHENTICATED: u32 = 1 << 3;
+    pub const IS_DISCOVERY_ORIGIN_AUTHENTICATED: u32 = 1 << 4;
+    pub const IS_LIVELINESS_ORIGIN_AUTHENTICATED: u32 = 1 << 5;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, XTypesSerialize, XTypesDeserialize)]
+pub struct MessageIdentity {
+    pub source_guid: Guid,
+    pub sequence_number: i64,
+}
+
+impl Default for MessageIdentity {
+    fn default() -> Self {
+        Self {
+            source_guid: GUID_UNKNOWN,
+            sequence_number: 0,
+        }
+    }
+}
+
+// Data type of the ParticipantStatelessMessage builtin topic as defined in
+// DDS Security 7.2.6 "ParticipantGenericMessage"
+#[derive(Debug, PartialEq, Eq, Clone, XTypesSerialize, XTypesDeserialize)]
+pub struct ParticipantGenericMessage {
+    pub message_identity: MessageIdentity,
+    pub related_message_identity: MessageIdentity,
+    pub destination_participant_guid: Guid,
+    pub destination_endpoint_guid: Guid,
+    pub source_endpoint_guid: Guid,
+    pub message_class_id: String,
+    pub message_data: Vec<DataHolder>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::type_support::{
+        deserialize_rtps_encapsulated_data, serialize_rtps_xtypes_xcdr1_le,
+    };
+
+    #[test]
+    fn serialize_participant_generic_message() {
+        let mut token = DataHolder::new("A");
+        token.add_binary_property("b", [7]);
+        let message = ParticipantGenericMessage {
+            message_identity: MessageIdentity {
+                source_guid: Guid::from([1; 16]),
+                sequence_number: 2,
+            },
+            related_message_identity: MessageIdentity::default(),
+            destination_participant_guid: Guid::from([3; 16]),
+            destination_endpoint_guid: GUID_UNKNOWN,
+            source_endpoint_guid: Guid::from([4; 16]),
+            message_class_id: "c".to_string(),
+            message_data: vec![token],
+        };
+
+        let expected = [
+            &[0x00, 0x01, 0x00, 0x03][..], // CDR_LE, 3 bytes of padding
+            &[1; 16],                      // message_identity.source_guid
+            &[2, 0, 0, 0, 0, 0, 0, 0],     // message_identity.sequence_number
+            &[0; 16],                      // related_message_identity.source_guid
+            &[0; 8],                       // related_message_identity.sequence_number
+            &[3; 16],                      // destination_participant_guid
+            &[0; 16],                      // destination_endpoint_guid
+            &[4; 16],                      // source_endpoint_guid
+            &[2, 0, 0, 0, b'c', 0, 0, 0],  // message_class_id + padding
+            &[1, 0, 0, 0],                 // message_data length
+            &[2, 0, 0, 0, b'A', 0, 0, 0],  // class_id + padding
+            &[0, 0, 0, 0],                 // properties length
+            &[1, 0, 0, 0],                 // binary_properties length
+            &[2, 0, 0, 0, b'b', 0, 0, 0],  // name + padding
+            &[1, 0, 0, 0, 7, 0, 0, 0],     // value + padding
+        ]
+        .concat();
+        let serialized = serialize_rtps_xtypes_xcdr1_le(&message).unwrap();
+        assert_eq!(serialized, expected);
+
+        let deserialized: ParticipantGenericMessage =
+            deserialize_rtps_encapsulated_data(&mut serialized.as_slice()).unwrap();
+        assert_eq!(deserialized, message);
+    }
+}
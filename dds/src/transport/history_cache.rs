@@ -33,5 +33,15 @@ impl CacheChange {
 pub trait HistoryCache: Send + Sync {
     fn add_change(&mut self, cache_change: CacheChange);
 
+    fn add_change_list(&mut self, cache_change_list: Vec<CacheChange>) {
+        for cache_change in cache_change_list {
+            self.add_change(cache_change);
+        }
+    }
+
     fn remove_change(&mut self, sequence_number: i64);
+
+    fn assert_writer_liveliness(&mut self, _writer_guid: Guid) {}
+
+    fn report_lost_changes(&mut self, _writer_guid: Guid, _lost_changes_count: i64) {}
 }
@@ -9,6 +9,7 @@ pub struct WriterProxy {
     pub unicast_locator_list: Vec<Locator>,
     pub multicast_locator_list: Vec<Locator>,
     pub data_max_size_serialized: i32,
+    pub disable_positive_acks: bool,
 }
 
 pub trait TransportStatelessReader: Send + Sync {
@@ -103,7 +103,7 @@ pub const GUIDPREFIX_UNKNOWN: GuidPrefix = [0; 12];
 /// Type used to hold the suffix part of the globally-unique RTPS-entity identifiers. The
 /// EntityId_t uniquely identifies an Entity within a Participant. Must be possible to represent using 4 octets.
 /// The following values are reserved by the protocol: ENTITYID_UNKNOWN Additional pre-defined values are defined by the Discovery module in 8.5
-#[derive(Clone, Copy, PartialEq, Eq, Debug, XTypesSerialize, XTypesDeserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, XTypesSerialize, XTypesDeserialize)]
 pub struct EntityId {
     entity_key: OctetArray3,
     entity_kind: Octet,
@@ -140,6 +140,13 @@ pub const ENTITYID_PARTICIPANT: EntityId = EntityId::new([0, 0, 0x01], BUILT_IN_
 /// Must be possible to represent using 64 bits.
 pub type SequenceNumber = i64;
 
+/// Special value of the SequenceNumber used to indicate an unknown sequence number.
+pub const SEQUENCENUMBER_UNKNOWN: SequenceNumber = -(1 << 32);
+
+/// Highest SequenceNumber accepted from a remote endpoint. Submessages with sequence numbers
+/// above this value are discarded as invalid so that the sequence number arithmetic never overflows.
+pub const SEQUENCENUMBER_MAX: SequenceNumber = (1 << 62) - 1;
+
 /// TopicKind_t
 /// Enumeration used to distinguish whether a Topic has defined some fields within to be used as the 'key' that identifies data-instances within the Topic. See the DDS specification for more details on keys.
 /// The following values are reserved by the protocol: NO_KEY, WITH_KEY
@@ -199,6 +206,8 @@ pub const LOCATOR_KIND_INVALID: Long = -1;
 pub const LOCATOR_KIND_RESERVED: Long = 0;
 pub const LOCATOR_KIND_UDP_V4: Long = 1;
 pub const LOCATOR_KIND_UDP_V6: Long = 2;
+// Not defined by the RTPS standard. Same value as the other implementations of RTPS over TCP
+pub const LOCATOR_KIND_TCP_V4: Long = 4;
 pub const LOCATOR_PORT_INVALID: UnsignedLong = 0;
 pub const LOCATOR_ADDRESS_INVALID: [Octet; 16] = [0; 16];
 
@@ -1,7 +1,8 @@
 use super::{
-    history_cache::HistoryCache,
+    history_cache::{CacheChange, HistoryCache},
     types::{DurabilityKind, EntityId, Guid, Locator, ReliabilityKind},
 };
+use std::sync::Arc;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ReaderProxy {
@@ -12,8 +13,50 @@ pub struct ReaderProxy {
     pub unicast_locator_list: Vec<Locator>,
     pub multicast_locator_list: Vec<Locator>,
     pub expects_inline_qos: bool,
+    pub disable_positive_acks_keep_duration: Option<core::time::Duration>,
+    pub prefer_multicast: bool,
+    pub push_mode: bool,
+    pub historical_data_limit: HistoricalDataLimit,
+    pub content_filter: Option<ChangeFilter>,
 }
 
+// Limits of the changes already in the history which are sent to a reader when it is matched
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct HistoricalDataLimit {
+    pub depth: Option<usize>,
+    pub max_samples: Option<usize>,
+    pub max_age: Option<core::time::Duration>,
+}
+
+// Filter of the reader on the changes of the writer. Changes for which the filter returns false
+// are not relevant to the reader and are announced with a GAP instead of being sent.
+#[derive(Clone)]
+pub struct ChangeFilter(Arc<dyn Fn(&CacheChange) -> bool + Send + Sync>);
+
+impl ChangeFilter {
+    pub fn new(filter: impl Fn(&CacheChange) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(filter))
+    }
+
+    pub fn is_relevant(&self, change: &CacheChange) -> bool {
+        (self.0)(change)
+    }
+}
+
+impl core::fmt::Debug for ChangeFilter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ChangeFilter").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ChangeFilter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ChangeFilter {}
+
 pub trait TransportStatelessWriter: Send + Sync {
     fn guid(&self) -> Guid;
 
@@ -30,7 +73,20 @@ pub trait TransportStatefulWriter: Send + Sync {
 
     fn is_change_acknowledged(&self, sequence_number: i64) -> bool;
 
+    // Calls the callback once the change is acknowledged by all the matched reliable readers
+    fn on_change_acknowledged(&mut self, sequence_number: i64, callback: Box<dyn FnOnce() + Send>);
+
     fn add_matched_reader(&mut self, reader_proxy: ReaderProxy);
 
     fn remove_matched_reader(&mut self, remote_reader_guid: Guid);
+
+    fn assert_liveliness(&mut self);
+
+    fn send_heartbeat(&mut self);
+
+    // Sends the changes held back by the batching right away
+    fn flush(&mut self);
+
+    // The writers with the highest transport priority are the first served by the flow control
+    fn set_transport_priority(&mut self, transport_priority: i32);
 }
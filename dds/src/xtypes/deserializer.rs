@@ -6,10 +6,20 @@ pub trait DeserializeFinalStruct<'a> {
         &mut self,
         name: &str,
     ) -> Result<Option<T>, XTypesError>;
+    /// Deserialize the last field of the struct which is omitted altogether by some writers.
+    /// The default value is returned if there is no data left for the field.
+    fn deserialize_trailing_field<T: XTypesDeserialize<'a> + Default>(
+        &mut self,
+        name: &str,
+    ) -> Result<T, XTypesError>;
 }
 
 pub trait DeserializeAppendableStruct<'a> {
     fn deserialize_field<T: XTypesDeserialize<'a>>(&mut self, name: &str) -> Result<T, XTypesError>;
+    fn deserialize_optional_field<T: XTypesDeserialize<'a>>(
+        &mut self,
+        name: &str,
+    ) -> Result<Option<T>, XTypesError>;
 }
 
 pub trait DeserializeMutableStruct<'a> {
@@ -0,0 +1,853 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use super::{
+    deserializer::{DeserializeSequence, XTypesDeserializer},
+    dynamic_type::{DynamicType, ExtensibilityKind, MemberId, ObjectName},
+    error::XTypesError,
+    serialize::XTypesSerialize,
+    serializer::{
+        SerializeAppendableStruct, SerializeCollection, SerializeFinalStruct,
+        SerializeMutableStruct, XTypesSerializer,
+    },
+    type_object::{TypeIdentifier, TK_STRUCTURE},
+    xcdr_deserializer::{
+        read_member_v2, Xcdr1BeDeserializer, Xcdr1LeDeserializer, Xcdr2BeDeserializer,
+        Xcdr2LeDeserializer,
+    },
+};
+
+/// Factory to create [`DynamicData`] objects of a given type.
+pub struct DynamicDataFactory;
+
+impl DynamicDataFactory {
+    /// Create a [`DynamicData`] object of the given structure type with all its members
+    /// initialized to their default value.
+    pub fn create_data(dynamic_type: &dyn DynamicType) -> Result<DynamicData, XTypesError> {
+        if dynamic_type.get_kind() != TK_STRUCTURE {
+            return Err(XTypesError::InvalidType);
+        }
+        let descriptor = dynamic_type.get_descriptor()?;
+        let mut member_list = Vec::new();
+        for index in 0..dynamic_type.get_member_count() {
+            let member_descriptor = dynamic_type.get_member_by_index(index)?.get_descriptor()?;
+            member_list.push(DynamicDataMember {
+                id: member_descriptor.id,
+                name: member_descriptor.name,
+                value: DataValue::default_for(member_descriptor.type_)?,
+            });
+        }
+        Ok(DynamicData {
+            type_name: descriptor.name,
+            extensibility_kind: descriptor.extensibility_kind,
+            member_list,
+        })
+    }
+}
+
+/// Sample of a structure type whose members are accessed by their [`MemberId`] instead of through
+/// a compile-time type. Writing a [`DynamicData`] uses the type it was created with and reading
+/// it requires the type of the topic, e.g. as returned by `Topic::get_type_support()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicData {
+    type_name: ObjectName,
+    extensibility_kind: ExtensibilityKind,
+    member_list: Vec<DynamicDataMember>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DynamicDataMember {
+    id: MemberId,
+    name: ObjectName,
+    value: DataValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DataValue {
+    Boolean(bool),
+    Byte(u8),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Uint8(u8),
+    Uint16(u16),
+    Uint32(u32),
+    Uint64(u64),
+    Float32(f32),
+    Float64(f64),
+    Char8(char),
+    String(String),
+    Complex(DynamicData),
+    // The element holds the default value of the element type to check the type of
+    // the values set on an empty sequence
+    Sequence {
+        element: Box<DataValue>,
+        values: Vec<DataValue>,
+    },
+    Array(Vec<DataValue>),
+}
+
+impl DataValue {
+    fn default_for(type_identifier: &TypeIdentifier) -> Result<Self, XTypesError> {
+        Ok(match type_identifier {
+            TypeIdentifier::TkBoolean => DataValue::Boolean(false),
+            TypeIdentifier::TkByteType => DataValue::Byte(0),
+            TypeIdentifier::TkInt8Type => DataValue::Int8(0),
+            TypeIdentifier::TkInt16Type => DataValue::Int16(0),
+            TypeIdentifier::TkInt32Type => DataValue::Int32(0),
+            TypeIdentifier::TkInt64Type => DataValue::Int64(0),
+            TypeIdentifier::TkUint8Type => DataValue::Uint8(0),
+            TypeIdentifier::TkUint16Type => DataValue::Uint16(0),
+            TypeIdentifier::TkUint32Type => DataValue::Uint32(0),
+            TypeIdentifier::TkUint64Type => DataValue::Uint64(0),
+            TypeIdentifier::TkFloat32Type => DataValue::Float32(0.0),
+            TypeIdentifier::TkFloat64Type => DataValue::Float64(0.0),
+            TypeIdentifier::TkChar8Type => DataValue::Char8('\0'),
+            TypeIdentifier::TiString8Small { .. } | TypeIdentifier::TiString8Large { .. } => {
+                DataValue::String(String::new())
+            }
+            TypeIdentifier::TiPlainSequenceSmall { seq_sdefn } => DataValue::Sequence {
+                element: Box::new(DataValue::default_for(&seq_sdefn.element_identifier)?),
+                values: Vec::new(),
+            },
+            TypeIdentifier::TiPlainSequenceLarge { seq_ldefn } => DataValue::Sequence {
+                element: Box::new(DataValue::default_for(&seq_ldefn.element_identifier)?),
+                values: Vec::new(),
+            },
+            TypeIdentifier::TiPlainArraySmall { array_sdefn } => DataValue::Array(vec![
+                    DataValue::default_for(&array_sdefn.element_identifier)?;
+                    array_sdefn
+                        .array_bound_seq
+                        .iter()
+                        .map(|&b| b as usize)
+                        .product()
+                ]),
+            TypeIdentifier::TiPlainArrayLarge { array_ldefn } => DataValue::Array(vec![
+                    DataValue::default_for(&array_ldefn.element_identifier)?;
+                    array_ldefn
+                        .array_bound_seq
+                        .iter()
+                        .map(|&b| b as usize)
+                        .product()
+                ]),
+            TypeIdentifier::EkComplete { complete } => {
+                DataValue::Complex(DynamicDataFactory::create_data(complete.as_ref())?)
+            }
+            _ => return Err(XTypesError::InvalidType),
+        })
+    }
+
+    fn is_same_type(&self, other: &DataValue) -> bool {
+        match (self, other) {
+            (DataValue::Complex(a), DataValue::Complex(b)) => a.type_name == b.type_name,
+            _ => core::mem::discriminant(self) == core::mem::discriminant(other),
+        }
+    }
+}
+
+macro_rules! value_accessors {
+    ($type:ty, $variant:ident, $get:ident, $set:ident, $get_values:ident, $set_values:ident) => {
+        #[doc = concat!("Get the value of the member with the given id of type `", stringify!($type), "`.")]
+        pub fn $get(&self, id: MemberId) -> Result<$type, XTypesError> {
+            match self.get_value(id)? {
+                DataValue::$variant(v) => Ok(v.clone()),
+                _ => Err(XTypesError::InvalidType),
+            }
+        }
+
+        #[doc = concat!("Set the value of the member with the given id of type `", stringify!($type), "`.")]
+        pub fn $set(&mut self, id: MemberId, value: $type) -> Result<(), XTypesError> {
+            self.set_value(id, DataValue::$variant(value))
+        }
+
+        #[doc = concat!("Get the values of the sequence or array member with the given id of element type `", stringify!($type), "`.")]
+        pub fn $get_values(&self, id: MemberId) -> Result<Vec<$type>, XTypesError> {
+            self.get_values(id)?
+                .iter()
+                .map(|v| match v {
+                    DataValue::$variant(v) => Ok(v.clone()),
+                    _ => Err(XTypesError::InvalidType),
+                })
+                .collect()
+        }
+
+        #[doc = concat!("Set the values of the sequence or array member with the given id of element type `", stringify!($type), "`.")]
+        pub fn $set_values(&mut self, id: MemberId, values: Vec<$type>) -> Result<(), XTypesError> {
+            self.set_values(id, values.into_iter().map(DataValue::$variant).collect())
+        }
+    };
+}
+
+impl DynamicData {
+    /// Get the name of the type of this data.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Get the number of members of this data.
+    pub fn get_item_count(&self) -> u32 {
+        self.member_list.len() as u32
+    }
+
+    /// Get the id of the member with the given name.
+    pub fn get_member_id_by_name(&self, name: &str) -> Option<MemberId> {
+        self.member_list
+            .iter()
+            .find(|m| m.name == name)
+            .map(|m| m.id)
+    }
+
+    /// Get the id of the member at the given index.
+    pub fn get_member_id_at_index(&self, index: u32) -> Option<MemberId> {
+        self.member_list.get(index as usize).map(|m| m.id)
+    }
+
+    value_accessors!(
+        bool,
+        Boolean,
+        get_boolean_value,
+        set_boolean_value,
+        get_boolean_values,
+        set_boolean_values
+    );
+    value_accessors!(
+        u8,
+        Byte,
+        get_byte_value,
+        set_byte_value,
+        get_byte_values,
+        set_byte_values
+    );
+    value_accessors!(
+        i8,
+        Int8,
+        get_int8_value,
+        set_int8_value,
+        get_int8_values,
+        set_int8_values
+    );
+    value_accessors!(
+        i16,
+        Int16,
+        get_int16_value,
+        set_int16_value,
+        get_int16_values,
+        set_int16_values
+    );
+    value_accessors!(
+        i32,
+        Int32,
+        get_int32_value,
+        set_int32_value,
+        get_int32_values,
+        set_int32_values
+    );
+    value_accessors!(
+        i64,
+        Int64,
+        get_int64_value,
+        set_int64_value,
+        get_int64_values,
+        set_int64_values
+    );
+    value_accessors!(
+        u8,
+        Uint8,
+        get_uint8_value,
+        set_uint8_value,
+        get_uint8_values,
+        set_uint8_values
+    );
+    value_accessors!(
+        u16,
+        Uint16,
+        get_uint16_value,
+        set_uint16_value,
+        get_uint16_values,
+        set_uint16_values
+    );
+    value_accessors!(
+        u32,
+        Uint32,
+        get_uint32_value,
+        set_uint32_value,
+        get_uint32_values,
+        set_uint32_values
+    );
+    value_accessors!(
+        u64,
+        Uint64,
+        get_uint64_value,
+        set_uint64_value,
+        get_uint64_values,
+        set_uint64_values
+    );
+    value_accessors!(
+        f32,
+        Float32,
+        get_float32_value,
+        set_float32_value,
+        get_float32_values,
+        set_float32_values
+    );
+    value_accessors!(
+        f64,
+        Float64,
+        get_float64_value,
+        set_float64_value,
+        get_float64_values,
+        set_float64_values
+    );
+    value_accessors!(
+        char,
+        Char8,
+        get_char8_value,
+        set_char8_value,
+        get_char8_values,
+        set_char8_values
+    );
+    value_accessors!(
+        String,
+        String,
+        get_string_value,
+        set_string_value,
+        get_string_values,
+        set_string_values
+    );
+    value_accessors!(
+        DynamicData,
+        Complex,
+        get_complex_value,
+        set_complex_value,
+        get_complex_values,
+        set_complex_values
+    );
+
+    fn get_value(&self, id: MemberId) -> Result<&DataValue, XTypesError> {
+        self.member_list
+            .iter()
+            .find(|m| m.id == id)
+            .map(|m| &m.value)
+            .ok_or(XTypesError::InvalidIndex)
+    }
+
+    fn get_value_mut(&mut self, id: MemberId) -> Result<&mut DataValue, XTypesError> {
+        self.member_list
+            .iter_mut()
+            .find(|m| m.id == id)
+            .map(|m| &mut m.value)
+            .ok_or(XTypesError::InvalidIndex)
+    }
+
+    fn set_value(&mut self, id: MemberId, value: DataValue) -> Result<(), XTypesError> {
+        let member_value = self.get_value_mut(id)?;
+        if !member_value.is_same_type(&value) {
+            return Err(XTypesError::InvalidType);
+        }
+        *member_value = value;
+        Ok(())
+    }
+
+    fn get_values(&self, id: MemberId) -> Result<&[DataValue], XTypesError> {
+        match self.get_value(id)? {
+            DataValue::Sequence { values, .. } | DataValue::Array(values) => Ok(values),
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+
+    fn set_values(&mut self, id: MemberId, new_values: Vec<DataValue>) -> Result<(), XTypesError> {
+        match self.get_value_mut(id)? {
+            DataValue::Sequence { element, values } => {
+                if !new_values.iter().all(|v| element.is_same_type(v)) {
+                    return Err(XTypesError::InvalidType);
+                }
+                *values = new_values;
+            }
+            DataValue::Array(values) => {
+                if values.len() != new_values.len()
+                    || !values
+                        .iter()
+                        .zip(&new_values)
+                        .all(|(a, b)| a.is_same_type(b))
+                {
+                    return Err(XTypesError::InvalidType);
+                }
+                *values = new_values;
+            }
+            _ => return Err(XTypesError::InvalidType),
+        }
+        Ok(())
+    }
+
+    /// Deserialize a [`DynamicData`] of the given type. Mutable types are encoded as a parameter
+    /// list and must be deserialized with [`DynamicData::deserialize_parameter_list_le`] or
+    /// [`DynamicData::deserialize_parameter_list_be`] instead.
+    pub fn deserialize<'de, D>(
+        dynamic_type: &dyn DynamicType,
+        deserializer: &mut D,
+    ) -> Result<Self, XTypesError>
+    where
+        for<'a> &'a mut D: XTypesDeserializer<'de>,
+    {
+        let mut data = DynamicDataFactory::create_data(dynamic_type)?;
+        if data.extensibility_kind == ExtensibilityKind::Mutable {
+            return Err(XTypesError::InvalidType);
+        }
+        for index in 0..dynamic_type.get_member_count() {
+            let member_descriptor = dynamic_type.get_member_by_index(index)?.get_descriptor()?;
+            data.member_list[index as usize].value =
+                deserialize_value(member_descriptor.type_, deserializer)?;
+        }
+        Ok(data)
+    }
+
+    /// Set the key members of this data from a serialized key which contains only the key members,
+    /// leaving the other members unchanged.
+    pub(crate) fn deserialize_key_members<'de, D>(
+        &mut self,
+        dynamic_type: &dyn DynamicType,
+        deserializer: &mut D,
+    ) -> Result<(), XTypesError>
+    where
+        for<'a> &'a mut D: XTypesDeserializer<'de>,
+    {
+        for index in 0..dynamic_type.get_member_count() {
+            let member_descriptor = dynamic_type.get_member_by_index(index)?.get_descriptor()?;
+            // The key members of the nested structures are part of the serialized key
+            match (
+                member_descriptor.type_,
+                &mut self.member_list[index as usize].value,
+            ) {
+                (TypeIdentifier::EkComplete { complete }, DataValue::Complex(data)) => {
+                    data.deserialize_key_members(complete.as_ref(), deserializer)?
+                }
+                (type_, value) if member_descriptor.is_key => {
+                    *value = deserialize_value(type_, deserializer)?
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Deserialize a [`DynamicData`] of the given mutable type from a little endian parameter list.
+    pub fn deserialize_parameter_list_le(
+        dynamic_type: &dyn DynamicType,
+        data: &[u8],
+        max_sequence_length: usize,
+    ) -> Result<Self, XTypesError> {
+        Self::deserialize_parameter_list(dynamic_type, data, u16::from_le_bytes, |type_, p| {
+            deserialize_value(
+                type_,
+                &mut Xcdr1LeDeserializer::with_max_sequence_length(p, max_sequence_length),
+            )
+        })
+    }
+
+    /// Deserialize a [`DynamicData`] of the given mutable type from a big endian parameter list.
+    pub fn deserialize_parameter_list_be(
+        dynamic_type: &dyn DynamicType,
+        data: &[u8],
+        max_sequence_length: usize,
+    ) -> Result<Self, XTypesError> {
+        Self::deserialize_parameter_list(dynamic_type, data, u16::from_be_bytes, |type_, p| {
+            deserialize_value(
+                type_,
+                &mut Xcdr1BeDeserializer::with_max_sequence_length(p, max_sequence_length),
+            )
+        })
+    }
+
+    /// Deserialize a [`DynamicData`] of the given mutable type from the little endian PL_CDR2 members
+    /// which follow the DHEADER of the type.
+    pub fn deserialize_member_list_le(
+        dynamic_type: &dyn DynamicType,
+        data: &[u8],
+        max_sequence_length: usize,
+    ) -> Result<Self, XTypesError> {
+        Self::deserialize_member_list(dynamic_type, data, u32::from_le_bytes, |type_, m| {
+            deserialize_value(
+                type_,
+                &mut Xcdr2LeDeserializer::with_max_sequence_length(m, max_sequence_length),
+            )
+        })
+    }
+
+    /// Deserialize a [`DynamicData`] of the given mutable type from the big endian PL_CDR2 members
+    /// which follow the DHEADER of the type.
+    pub fn deserialize_member_list_be(
+        dynamic_type: &dyn DynamicType,
+        data: &[u8],
+        max_sequence_length: usize,
+    ) -> Result<Self, XTypesError> {
+        Self::deserialize_member_list(dynamic_type, data, u32::from_be_bytes, |type_, m| {
+            deserialize_value(
+                type_,
+                &mut Xcdr2BeDeserializer::with_max_sequence_length(m, max_sequence_length),
+            )
+        })
+    }
+
+    fn deserialize_member_list(
+        dynamic_type: &dyn DynamicType,
+        mut data: &[u8],
+        read_u32: fn([u8; 4]) -> u32,
+        deserialize_member: impl Fn(&TypeIdentifier, &[u8]) -> Result<DataValue, XTypesError>,
+    ) -> Result<Self, XTypesError> {
+        let mut dynamic_data = DynamicDataFactory::create_data(dynamic_type)?;
+        while !data.is_empty() {
+            let (member_id, member) = read_member_v2(&mut data, read_u32)?;
+            // Members not found in the type are ignored and missing members keep their default
+            for index in 0..dynamic_type.get_member_count() {
+                let member_descriptor =
+                    dynamic_type.get_member_by_index(index)?.get_descriptor()?;
+                if member_descriptor.id == member_id {
+                    dynamic_data.member_list[index as usize].value =
+                        deserialize_member(member_descriptor.type_, member)?;
+                }
+            }
+        }
+        Ok(dynamic_data)
+    }
+
+    fn deserialize_parameter_list(
+        dynamic_type: &dyn DynamicType,
+        data: &[u8],
+        read_u16: fn([u8; 2]) -> u16,
+        deserialize_parameter: impl Fn(&TypeIdentifier, &[u8]) -> Result<DataValue, XTypesError>,
+    ) -> Result<Self, XTypesError> {
+        const PID_SENTINEL: u16 = 1;
+        let mut dynamic_data = DynamicDataFactory::create_data(dynamic_type)?;
+        let mut reader = data;
+        loop {
+            if reader.len() < 4 {
+                return Err(XTypesError::InvalidData);
+            }
+            let pid = read_u16([reader[0], reader[1]]);
+            let length = read_u16([reader[2], reader[3]]) as usize;
+            if pid == PID_SENTINEL {
+                break;
+            }
+            let parameter = reader.get(4..4 + length).ok_or(XTypesError::InvalidData)?;
+            // Members not found in the type are ignored and missing members keep their default
+            for index in 0..dynamic_type.get_member_count() {
+                let member_descriptor =
+                    dynamic_type.get_member_by_index(index)?.get_descriptor()?;
+                if member_descriptor.id == pid as MemberId {
+                    dynamic_data.member_list[index as usize].value =
+                        deserialize_parameter(member_descriptor.type_, parameter)?;
+                }
+            }
+            // The length does not include the padding to the next parameter
+            reader = reader.get(4 + ((length + 3) & !3)..).unwrap_or_default();
+        }
+        Ok(dynamic_data)
+    }
+}
+
+fn deserialize_value<'de, D>(
+    type_identifier: &TypeIdentifier,
+    de: &mut D,
+) -> Result<DataValue, XTypesError>
+where
+    for<'a> &'a mut D: XTypesDeserializer<'de>,
+{
+    Ok(match type_identifier {
+        TypeIdentifier::TkBoolean => DataValue::Boolean(de.deserialize_boolean()?),
+        TypeIdentifier::TkByteType => DataValue::Byte(de.deserialize_uint8()?),
+        TypeIdentifier::TkInt8Type => DataValue::Int8(de.deserialize_int8()?),
+        TypeIdentifier::TkInt16Type => DataValue::Int16(de.deserialize_int16()?),
+        TypeIdentifier::TkInt32Type => DataValue::Int32(de.deserialize_int32()?),
+        TypeIdentifier::TkInt64Type => DataValue::Int64(de.deserialize_int64()?),
+        TypeIdentifier::TkUint8Type => DataValue::Uint8(de.deserialize_uint8()?),
+        TypeIdentifier::TkUint16Type => DataValue::Uint16(de.deserialize_uint16()?),
+        TypeIdentifier::TkUint32Type => DataValue::Uint32(de.deserialize_uint32()?),
+        TypeIdentifier::TkUint64Type => DataValue::Uint64(de.deserialize_uint64()?),
+        TypeIdentifier::TkFloat32Type => DataValue::Float32(de.deserialize_float32()?),
+        TypeIdentifier::TkFloat64Type => DataValue::Float64(de.deserialize_float64()?),
+        TypeIdentifier::TkChar8Type => DataValue::Char8(de.deserialize_char8()?),
+        TypeIdentifier::TiString8Small { .. } | TypeIdentifier::TiString8Large { .. } => {
+            DataValue::String(de.deserialize_string()?.to_string())
+        }
+        TypeIdentifier::TiPlainSequenceSmall { seq_sdefn } => {
+            deserialize_sequence(&seq_sdefn.element_identifier, de)?
+        }
+        TypeIdentifier::TiPlainSequenceLarge { seq_ldefn } => {
+            deserialize_sequence(&seq_ldefn.element_identifier, de)?
+        }
+        TypeIdentifier::TiPlainArraySmall { array_sdefn } => {
+            let length = array_sdefn
+                .array_bound_seq
+                .iter()
+                .map(|&b| b as usize)
+                .product();
+            deserialize_array(&array_sdefn.element_identifier, length, de)?
+        }
+        TypeIdentifier::TiPlainArrayLarge { array_ldefn } => {
+            let length = array_ldefn
+                .array_bound_seq
+                .iter()
+                .map(|&b| b as usize)
+                .product();
+            deserialize_array(&array_ldefn.element_identifier, length, de)?
+        }
+        TypeIdentifier::EkComplete { complete } => {
+            DataValue::Complex(DynamicData::deserialize(complete.as_ref(), de)?)
+        }
+        _ => return Err(XTypesError::InvalidType),
+    })
+}
+
+fn deserialize_sequence<'de, D>(
+    element_identifier: &TypeIdentifier,
+    de: &mut D,
+) -> Result<DataValue, XTypesError>
+where
+    for<'a> &'a mut D: XTypesDeserializer<'de>,
+{
+    // The length is read as a sequence to check it against the limits of the deserializer
+    let length = de.deserialize_sequence()?.len();
+    let mut values = Vec::new();
+    for _ in 0..length {
+        values.push(deserialize_value(element_identifier, de)?);
+    }
+    Ok(DataValue::Sequence {
+        element: Box::new(DataValue::default_for(element_identifier)?),
+        values,
+    })
+}
+
+fn deserialize_array<'de, D>(
+    element_identifier: &TypeIdentifier,
+    length: usize,
+    de: &mut D,
+) -> Result<DataValue, XTypesError>
+where
+    for<'a> &'a mut D: XTypesDeserializer<'de>,
+{
+    let mut values = Vec::with_capacity(length);
+    for _ in 0..length {
+        values.push(deserialize_value(element_identifier, de)?);
+    }
+    Ok(DataValue::Array(values))
+}
+
+impl XTypesSerialize for DynamicData {
+    fn serialize(&self, serializer: impl XTypesSerializer) -> Result<(), XTypesError> {
+        match self.extensibility_kind {
+            ExtensibilityKind::Final => {
+                let mut s = serializer.serialize_final_struct()?;
+                for member in &self.member_list {
+                    s.serialize_field(&member.value, &member.name)?;
+                }
+                Ok(())
+            }
+            ExtensibilityKind::Appendable => {
+                let mut s = serializer.serialize_appendable_struct()?;
+                for member in &self.member_list {
+                    s.serialize_field(&member.value, &member.name)?;
+                }
+                s.end()
+            }
+            ExtensibilityKind::Mutable => {
+                let mut s = serializer.serialize_mutable_struct()?;
+                for member in &self.member_list {
+                    s.serialize_field(&member.value, member.id, &member.name)?;
+                }
+                s.end()
+            }
+        }
+    }
+}
+
+impl XTypesSerialize for DataValue {
+    fn serialize(&self, serializer: impl XTypesSerializer) -> Result<(), XTypesError> {
+        match self {
+            DataValue::Boolean(v) => serializer.serialize_boolean(*v),
+            DataValue::Byte(v) => serializer.serialize_uint8(*v),
+            DataValue::Int8(v) => serializer.serialize_int8(*v),
+            DataValue::Int16(v) => serializer.serialize_int16(*v),
+            DataValue::Int32(v) => serializer.serialize_int32(*v),
+            DataValue::Int64(v) => serializer.serialize_int64(*v),
+            DataValue::Uint8(v) => serializer.serialize_uint8(*v),
+            DataValue::Uint16(v) => serializer.serialize_uint16(*v),
+            DataValue::Uint32(v) => serializer.serialize_uint32(*v),
+            DataValue::Uint64(v) => serializer.serialize_uint64(*v),
+            DataValue::Float32(v) => serializer.serialize_float32(*v),
+            DataValue::Float64(v) => serializer.serialize_float64(*v),
+            DataValue::Char8(v) => serializer.serialize_char8(*v),
+            DataValue::String(v) => serializer.serialize_string(v),
+            DataValue::Complex(v) => v.serialize(serializer),
+            DataValue::Sequence { values, .. } => {
+                let mut s = serializer.serialize_sequence(values.len())?;
+                for v in values {
+                    s.serialize_element(v)?;
+                }
+                Ok(())
+            }
+            DataValue::Array(values) => {
+                let mut s = serializer.serialize_array()?;
+                for v in values {
+                    s.serialize_element(v)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xtypes::{
+        dynamic_type::TypeDescriptor,
+        dynamic_type_builder::DynamicTypeBuilderFactory,
+        type_object::{TK_INT32, TK_UINT8},
+        xcdr_serializer::Xcdr1LeSerializer,
+    };
+
+    fn point_type() -> TypeIdentifier {
+        let mut builder = DynamicTypeBuilderFactory::create_type(TypeDescriptor {
+            kind: TK_STRUCTURE,
+            name: "Point".to_string(),
+            extensibility_kind: ExtensibilityKind::Final,
+            is_nested: true,
+        })
+        .unwrap();
+        let int32 = || DynamicTypeBuilderFactory::get_primitive_type(TK_INT32).unwrap();
+        builder.add_member("x", 0, int32(), false).unwrap();
+        builder.add_member("y", 1, int32(), false).unwrap();
+        builder.build()
+    }
+
+    fn shape_type(extensibility_kind: ExtensibilityKind) -> TypeIdentifier {
+        let mut builder = DynamicTypeBuilderFactory::create_type(TypeDescriptor {
+            kind: TK_STRUCTURE,
+            name: "Shape".to_string(),
+            extensibility_kind,
+            is_nested: false,
+        })
+        .unwrap();
+        builder
+            .add_member(
+                "color",
+                10,
+                DynamicTypeBuilderFactory::create_string_type(0),
+                true,
+            )
+            .unwrap();
+        builder
+            .add_member("position", 20, point_type(), false)
+            .unwrap();
+        builder
+            .add_member(
+                "data",
+                30,
+                DynamicTypeBuilderFactory::create_sequence_type(
+                    DynamicTypeBuilderFactory::get_primitive_type(TK_UINT8).unwrap(),
+                    0,
+                ),
+                false,
+            )
+            .unwrap();
+        builder.build()
+    }
+
+    #[derive(XTypesSerialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(XTypesSerialize)]
+    struct Shape {
+        color: String,
+        position: Point,
+        data: Vec<u8>,
+    }
+
+    fn serialize_v1_le<T: XTypesSerialize>(v: &T) -> Vec<u8> {
+        let mut buffer = vec![];
+        v.serialize(&mut Xcdr1LeSerializer::new(&mut buffer))
+            .unwrap();
+        buffer
+    }
+
+    fn shape_data(shape_type: &TypeIdentifier) -> DynamicData {
+        let mut data = DynamicDataFactory::create_data(shape_type).unwrap();
+        data.set_string_value(10, "BLUE".to_string()).unwrap();
+        let mut position = data.get_complex_value(20).unwrap();
+        position.set_int32_value(0, 5).unwrap();
+        position.set_int32_value(1, -3).unwrap();
+        data.set_complex_value(20, position).unwrap();
+        data.set_uint8_values(30, vec![1, 2, 3]).unwrap();
+        data
+    }
+
+    #[test]
+    fn create_data_with_default_values() {
+        let data = DynamicDataFactory::create_data(&shape_type(ExtensibilityKind::Final)).unwrap();
+        assert_eq!(data.type_name(), "Shape");
+        assert_eq!(data.get_item_count(), 3);
+        assert_eq!(data.get_member_id_by_name("position"), Some(20));
+        assert_eq!(data.get_string_value(10), Ok(String::new()));
+        assert_eq!(
+            data.get_complex_value(20).unwrap().get_int32_value(1),
+            Ok(0)
+        );
+        assert_eq!(data.get_uint8_values(30), Ok(vec![]));
+    }
+
+    #[test]
+    fn set_value_of_wrong_type_or_member() {
+        let mut data =
+            DynamicDataFactory::create_data(&shape_type(ExtensibilityKind::Final)).unwrap();
+        assert_eq!(data.set_int32_value(10, 1), Err(XTypesError::InvalidType));
+        assert_eq!(
+            data.set_int32_values(30, vec![1]),
+            Err(XTypesError::InvalidType)
+        );
+        assert_eq!(
+            data.set_string_value(40, String::new()),
+            Err(XTypesError::InvalidIndex)
+        );
+    }
+
+    #[test]
+    fn serialize_as_equivalent_static_type() {
+        let data = shape_data(&shape_type(ExtensibilityKind::Final));
+        let shape = Shape {
+            color: "BLUE".to_string(),
+            position: Point { x: 5, y: -3 },
+            data: vec![1, 2, 3],
+        };
+        assert_eq!(serialize_v1_le(&data), serialize_v1_le(&shape));
+    }
+
+    #[test]
+    fn serialize_and_deserialize() {
+        let shape_type = shape_type(ExtensibilityKind::Final);
+        let data = shape_data(&shape_type);
+        let buffer = serialize_v1_le(&data);
+        assert_eq!(
+            DynamicData::deserialize(&shape_type, &mut Xcdr1LeDeserializer::new(&buffer)),
+            Ok(data)
+        );
+    }
+
+    #[test]
+    fn serialize_and_deserialize_parameter_list() {
+        let shape_type = shape_type(ExtensibilityKind::Mutable);
+        let data = shape_data(&shape_type);
+        let buffer = serialize_v1_le(&data);
+        assert_eq!(
+            DynamicData::deserialize_parameter_list_le(&shape_type, &buffer, usize::MAX),
+            Ok(data)
+        );
+    }
+}
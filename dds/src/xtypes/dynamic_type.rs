@@ -5,7 +5,7 @@ use super::{
 
 pub type ObjectName = String;
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExtensibilityKind {
     Final,
     Appendable,
@@ -0,0 +1,200 @@
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
+
+use super::{
+    dynamic_type::{ExtensibilityKind, MemberId, TryConstructKind, TypeDescriptor},
+    error::XTypesError,
+    type_object::{
+        CollectionElementFlag, CommonStructMember, CompleteMemberDetail, CompleteStructHeader,
+        CompleteStructMember, CompleteStructType, CompleteTypeDetail, CompleteTypeObject,
+        PlainArrayLElemDefn, PlainArraySElemDefn, PlainCollectionHeader, PlainSequenceLElemDefn,
+        PlainSequenceSElemDefn, StringLTypeDefn, StringSTypeDefn, StructMemberFlag, StructTypeFlag,
+        TypeIdentifier, TypeKind, TypeObject, EK_COMPLETE, TK_BOOLEAN, TK_BYTE, TK_CHAR8,
+        TK_FLOAT32, TK_FLOAT64, TK_INT16, TK_INT32, TK_INT64, TK_INT8, TK_STRUCTURE, TK_UINT16,
+        TK_UINT32, TK_UINT64, TK_UINT8,
+    },
+};
+
+/// Factory to construct types at runtime. The created types are represented by their
+/// [`TypeIdentifier`] which implements [`DynamicType`](super::dynamic_type::DynamicType) and
+/// can be used as the type of a dynamic topic or as the type of a member of another type.
+pub struct DynamicTypeBuilderFactory;
+
+impl DynamicTypeBuilderFactory {
+    /// Get the type of the primitive type of the given kind.
+    pub fn get_primitive_type(kind: TypeKind) -> Result<TypeIdentifier, XTypesError> {
+        match kind {
+            TK_BOOLEAN => Ok(TypeIdentifier::TkBoolean),
+            TK_BYTE => Ok(TypeIdentifier::TkByteType),
+            TK_INT8 => Ok(TypeIdentifier::TkInt8Type),
+            TK_INT16 => Ok(TypeIdentifier::TkInt16Type),
+            TK_INT32 => Ok(TypeIdentifier::TkInt32Type),
+            TK_INT64 => Ok(TypeIdentifier::TkInt64Type),
+            TK_UINT8 => Ok(TypeIdentifier::TkUint8Type),
+            TK_UINT16 => Ok(TypeIdentifier::TkUint16Type),
+            TK_UINT32 => Ok(TypeIdentifier::TkUint32Type),
+            TK_UINT64 => Ok(TypeIdentifier::TkUint64Type),
+            TK_FLOAT32 => Ok(TypeIdentifier::TkFloat32Type),
+            TK_FLOAT64 => Ok(TypeIdentifier::TkFloat64Type),
+            TK_CHAR8 => Ok(TypeIdentifier::TkChar8Type),
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+
+    /// Create a string type with the given bound. A bound of 0 means the string is unbounded.
+    pub fn create_string_type(bound: u32) -> TypeIdentifier {
+        match u8::try_from(bound) {
+            Ok(bound) => TypeIdentifier::TiString8Small {
+                string_sdefn: StringSTypeDefn { bound },
+            },
+            Err(_) => TypeIdentifier::TiString8Large {
+                string_ldefn: StringLTypeDefn { bound },
+            },
+        }
+    }
+
+    /// Create a sequence type of the given element type. A bound of 0 means the sequence is unbounded.
+    pub fn create_sequence_type(element_type: TypeIdentifier, bound: u32) -> TypeIdentifier {
+        let header = collection_header();
+        match u8::try_from(bound) {
+            Ok(bound) => TypeIdentifier::TiPlainSequenceSmall {
+                seq_sdefn: Box::new(PlainSequenceSElemDefn {
+                    header,
+                    bound,
+                    element_identifier: element_type,
+                }),
+            },
+            Err(_) => TypeIdentifier::TiPlainSequenceLarge {
+                seq_ldefn: Box::new(PlainSequenceLElemDefn {
+                    header,
+                    bound,
+                    element_identifier: element_type,
+                }),
+            },
+        }
+    }
+
+    /// Create a single dimension array type of the given element type and length.
+    pub fn create_array_type(element_type: TypeIdentifier, length: u32) -> TypeIdentifier {
+        let header = collection_header();
+        match u8::try_from(length) {
+            Ok(length) => TypeIdentifier::TiPlainArraySmall {
+                array_sdefn: Box::new(PlainArraySElemDefn {
+                    header,
+                    array_bound_seq: vec![length],
+                    element_identifier: element_type,
+                }),
+            },
+            Err(_) => TypeIdentifier::TiPlainArrayLarge {
+                array_ldefn: Box::new(PlainArrayLElemDefn {
+                    header,
+                    array_bound_seq: vec![length],
+                    element_identifier: element_type,
+                }),
+            },
+        }
+    }
+
+    /// Create a builder for a type with the given descriptor. Only structure types can be built.
+    pub fn create_type(descriptor: TypeDescriptor) -> Result<DynamicTypeBuilder, XTypesError> {
+        if descriptor.kind != TK_STRUCTURE {
+            return Err(XTypesError::InvalidType);
+        }
+        Ok(DynamicTypeBuilder {
+            descriptor,
+            member_seq: Vec::new(),
+        })
+    }
+
+    /// Create a type from its complete [`TypeObject`], e.g. one received from the TypeLookup service.
+    pub fn create_type_w_type_object(
+        type_object: TypeObject,
+    ) -> Result<TypeIdentifier, XTypesError> {
+        match type_object {
+            TypeObject::EkComplete { complete } => Ok(TypeIdentifier::EkComplete {
+                complete: Box::new(complete),
+            }),
+            TypeObject::EkMinimal { .. } => Err(XTypesError::InvalidType),
+        }
+    }
+}
+
+fn collection_header() -> PlainCollectionHeader {
+    PlainCollectionHeader {
+        equiv_kind: EK_COMPLETE,
+        element_flags: CollectionElementFlag {
+            try_construct: TryConstructKind::Discard,
+            is_external: false,
+        },
+    }
+}
+
+/// Builder of a structure type created with [`DynamicTypeBuilderFactory::create_type`].
+pub struct DynamicTypeBuilder {
+    descriptor: TypeDescriptor,
+    member_seq: Vec<CompleteStructMember>,
+}
+
+impl DynamicTypeBuilder {
+    /// Add a member to the type. The members are serialized in the order in which they are added
+    /// and the id is used as parameter id if the type is mutable.
+    pub fn add_member(
+        &mut self,
+        name: &str,
+        id: MemberId,
+        member_type: TypeIdentifier,
+        is_key: bool,
+    ) -> Result<(), XTypesError> {
+        if self
+            .member_seq
+            .iter()
+            .any(|m| m.common.member_id == id || m.detail.name == name)
+        {
+            return Err(XTypesError::InvalidData);
+        }
+        self.member_seq.push(CompleteStructMember {
+            common: CommonStructMember {
+                member_id: id,
+                member_flags: StructMemberFlag {
+                    try_construct: TryConstructKind::Discard,
+                    is_external: false,
+                    is_optional: false,
+                    is_must_undestand: is_key,
+                    is_key,
+                },
+                member_type_id: member_type,
+            },
+            detail: CompleteMemberDetail {
+                name: name.to_string(),
+                ann_builtin: None,
+                ann_custom: None,
+            },
+        });
+        Ok(())
+    }
+
+    /// Create the type with the members added to the builder.
+    pub fn build(self) -> TypeIdentifier {
+        let extensibility_kind = self.descriptor.extensibility_kind;
+        let struct_type = CompleteStructType {
+            struct_flags: StructTypeFlag {
+                is_final: extensibility_kind == ExtensibilityKind::Final,
+                is_appendable: extensibility_kind == ExtensibilityKind::Appendable,
+                is_mutable: extensibility_kind == ExtensibilityKind::Mutable,
+                is_nested: self.descriptor.is_nested,
+                is_autoid_hash: false,
+            },
+            header: CompleteStructHeader {
+                base_type: TypeIdentifier::TkNone,
+                detail: CompleteTypeDetail {
+                    ann_builtin: None,
+                    ann_custom: None,
+                    type_name: self.descriptor.name,
+                },
+            },
+            member_seq: self.member_seq,
+        };
+        TypeIdentifier::EkComplete {
+            complete: Box::new(CompleteTypeObject::TkStructure { struct_type }),
+        }
+    }
+}
@@ -4,4 +4,5 @@ pub enum XTypesError {
     InvalidData,
     PidNotFound(u16),
     InvalidIndex,
+    InvalidType,
 }
@@ -1,7 +1,9 @@
 pub mod bytes;
 pub mod deserialize;
 pub mod deserializer;
+pub mod dynamic_data;
 pub mod dynamic_type;
+pub mod dynamic_type_builder;
 pub mod error;
 pub mod serialize;
 pub mod serializer;
@@ -18,6 +18,12 @@ pub trait SerializeAppendableStruct {
         value: &T,
         name: &str,
     ) -> Result<(), XTypesError>;
+    fn serialize_optional_field<T: XTypesSerialize>(
+        &mut self,
+        value: &Option<T>,
+        name: &str,
+    ) -> Result<(), XTypesError>;
+    fn end(self) -> Result<(), XTypesError>;
 }
 pub trait SerializeMutableStruct {
     fn serialize_field<T: XTypesSerialize>(
@@ -26,6 +32,12 @@ pub trait SerializeMutableStruct {
         pid: u32,
         name: &str,
     ) -> Result<(), XTypesError>;
+    fn serialize_optional_field<T: XTypesSerialize>(
+        &mut self,
+        value: &Option<T>,
+        pid: u32,
+        name: &str,
+    ) -> Result<(), XTypesError>;
     fn end(self) -> Result<(), XTypesError>;
 }
 pub trait SerializeCollection {
@@ -99,6 +99,7 @@ pub type SBoundSeq = Vec<SBound>;
 pub const INVALID_SBOUND: SBound = 0;
 
 // @extensibility(FINAL) @nested
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum TypeObjectHashId {
     EkComplete { hash: EquivalenceHash },
@@ -1075,13 +1076,17 @@ pub struct TypeIdentifierPair {
 pub type TypeIdentifierPairSeq = Vec<TypeIdentifierPair>;
 
 // @extensibility(APPENDABLE) @nested
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypeIdentifierWithSize {
-    pub type_id: TypeIdentifier,
+    // The TypeIdentifier of the TypeInformation is always the hash of a TypeObject
+    // pub type_id: TypeIdentifier, // Original in IDL
+    pub type_id: TypeObjectHashId,
     pub typeobject_serialized_size: u32,
 }
 pub type TypeIdentfierWithSizeSeq = Vec<TypeIdentifierWithSize>;
 
 // @extensibility(APPENDABLE) @nested
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypeIdentifierWithDependencies {
     pub typeid_with_size: TypeIdentifierWithSize,
     // The total additional types related to minimal_type
@@ -1091,6 +1096,7 @@ pub struct TypeIdentifierWithDependencies {
 pub type TypeIdentifierWithDependenciesSeq = Vec<TypeIdentifierWithDependencies>;
 
 // @extensibility(MUTABLE) @nested
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypeInformation {
     pub minimal: TypeIdentifierWithDependencies, //@id(0x1001)
     pub complete: TypeIdentifierWithDependencies, //@id(0x1002)
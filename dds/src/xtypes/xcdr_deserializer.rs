@@ -20,6 +20,13 @@ impl<'a> Xcdr1BeDeserializer<'a> {
             reader: Reader::new(buffer),
         }
     }
+
+    /// Create a deserializer which rejects the sequences longer than `max_sequence_length`.
+    pub fn with_max_sequence_length(buffer: &'a [u8], max_sequence_length: usize) -> Self {
+        Self {
+            reader: Reader::with_max_sequence_length(buffer, max_sequence_length),
+        }
+    }
 }
 
 pub struct Xcdr1LeDeserializer<'a> {
@@ -32,6 +39,13 @@ impl<'a> Xcdr1LeDeserializer<'a> {
             reader: Reader::new(buffer),
         }
     }
+
+    /// Create a deserializer which rejects the sequences longer than `max_sequence_length`.
+    pub fn with_max_sequence_length(buffer: &'a [u8], max_sequence_length: usize) -> Self {
+        Self {
+            reader: Reader::with_max_sequence_length(buffer, max_sequence_length),
+        }
+    }
 }
 
 pub struct Xcdr2BeDeserializer<'a> {
@@ -44,6 +58,13 @@ impl<'a> Xcdr2BeDeserializer<'a> {
             reader: Reader::new(buffer),
         }
     }
+
+    /// Create a deserializer which rejects the sequences longer than `max_sequence_length`.
+    pub fn with_max_sequence_length(buffer: &'a [u8], max_sequence_length: usize) -> Self {
+        Self {
+            reader: Reader::with_max_sequence_length(buffer, max_sequence_length),
+        }
+    }
 }
 
 pub struct Xcdr2LeDeserializer<'a> {
@@ -56,16 +77,35 @@ impl<'a> Xcdr2LeDeserializer<'a> {
             reader: Reader::new(buffer),
         }
     }
+
+    /// Create a deserializer which rejects the sequences longer than `max_sequence_length`.
+    pub fn with_max_sequence_length(buffer: &'a [u8], max_sequence_length: usize) -> Self {
+        Self {
+            reader: Reader::with_max_sequence_length(buffer, max_sequence_length),
+        }
+    }
 }
 
 struct Reader<'a> {
     buffer: &'a [u8],
     pos: usize,
+    max_sequence_length: usize,
 }
 
 impl<'a> Reader<'a> {
     fn new(buffer: &'a [u8]) -> Self {
-        Self { buffer, pos: 0 }
+        Self::with_max_sequence_length(buffer, usize::MAX)
+    }
+    fn with_max_sequence_length(buffer: &'a [u8], max_sequence_length: usize) -> Self {
+        Self {
+            buffer,
+            pos: 0,
+            max_sequence_length,
+        }
+    }
+    // Reader of a part of the buffer, such as a member of a mutable struct, with the same limits
+    fn nested(&self, buffer: &'a [u8]) -> Self {
+        Self::with_max_sequence_length(buffer, self.max_sequence_length)
     }
     fn buffer(&self) -> &'a [u8] {
         &self.buffer[self.pos..]
@@ -98,6 +138,17 @@ impl<'a> Reader<'a> {
     }
 }
 
+// Every element of a sequence takes at least one octet, so a length above the remaining data can only come from a
+// malformed or malicious input and is rejected before deserializing any element.
+fn read_sequence_length(len: u32, reader: &Reader) -> Result<usize, XTypesError> {
+    let len = len as usize;
+    if len > reader.buffer().len() || len > reader.max_sequence_length {
+        Err(XTypesError::InvalidData)
+    } else {
+        Ok(len)
+    }
+}
+
 fn read_with_padding_v1<const N: usize>(reader: &mut Reader) -> Result<[u8; N], XTypesError> {
     reader.seek_padding(N);
     reader.read().cloned()
@@ -176,10 +227,85 @@ fn into_bool(v: u8) -> Result<bool, XTypesError> {
     }
 }
 
-struct DelimitedCdrDecoder<'a, D> {
-    deserializer: &'a mut D,
+/// Returns the members of an appendable or mutable XCDR2 encoded struct which are delimited by
+/// its DHEADER.
+pub(crate) fn delimited_body_v2(
+    buffer: &[u8],
+    read_u32: fn([u8; 4]) -> u32,
+) -> Result<&[u8], XTypesError> {
+    let (dheader, body) = buffer
+        .split_first_chunk()
+        .ok_or(XTypesError::InvalidData)?;
+    body.get(..read_u32(*dheader) as usize)
+        .ok_or(XTypesError::InvalidData)
+}
+
+/// Reads the next member of the body of a PL_CDR2 encoded struct returning its member id and
+/// its serialized value. The body must start aligned to 4 with respect to the stream.
+pub(crate) fn read_member_v2<'a>(
+    buffer: &mut &'a [u8],
+    read_u32: fn([u8; 4]) -> u32,
+) -> Result<(u32, &'a [u8]), XTypesError> {
+    let next_u32 = |buffer: &mut &'a [u8]| -> Result<u32, XTypesError> {
+        let (bytes, rest) = buffer.split_first_chunk().ok_or(XTypesError::InvalidData)?;
+        *buffer = rest;
+        Ok(read_u32(*bytes))
+    };
+    // EMHEADER1
+    let emheader = next_u32(buffer)?;
+    let member_id = emheader & 0x0FFF_FFFF;
+    let length = match (emheader >> 28) & 0x7 {
+        lc @ 0..=3 => 1 << lc,
+        4 => next_u32(buffer)? as usize,
+        // The NEXTINT is also the first element of the member, e.g. the length of a sequence
+        lc => {
+            let nextint = next_u32(&mut &buffer[..])? as usize;
+            let element_size = [1, 4, 8][lc as usize - 5];
+            nextint
+                .checked_mul(element_size)
+                .and_then(|l| l.checked_add(4))
+                .ok_or(XTypesError::InvalidData)?
+        }
+    };
+    let value = buffer.get(..length).ok_or(XTypesError::InvalidData)?;
+    *buffer = buffer.get((length + 3) & !3..).unwrap_or_default();
+    Ok((member_id, value))
+}
+
+pub(crate) fn find_member_v2(
+    mut buffer: &[u8],
+    member_id: u32,
+    read_u32: fn([u8; 4]) -> u32,
+) -> Result<Option<&[u8]>, XTypesError> {
+    while !buffer.is_empty() {
+        let (id, value) = read_member_v2(&mut buffer, read_u32)?;
+        if id == member_id {
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
+
+trait DelimitedBody {
+    fn is_exhausted(&self) -> bool;
+}
+
+impl DelimitedBody for Xcdr2BeDeserializer<'_> {
+    fn is_exhausted(&self) -> bool {
+        self.reader.buffer().is_empty()
+    }
+}
+
+impl DelimitedBody for Xcdr2LeDeserializer<'_> {
+    fn is_exhausted(&self) -> bool {
+        self.reader.buffer().is_empty()
+    }
+}
+
+struct DelimitedCdrDecoder<D> {
+    deserializer: D,
 }
-impl<'de, D> DeserializeAppendableStruct<'de> for DelimitedCdrDecoder<'_, D>
+impl<'de, D: DelimitedBody> DeserializeAppendableStruct<'de> for DelimitedCdrDecoder<D>
 where
     for<'a> &'a mut D: XTypesDeserializer<'de>,
 {
@@ -187,13 +313,25 @@ where
         &mut self,
         _name: &str,
     ) -> Result<T, XTypesError> {
-        u32::deserialize(&mut *self.deserializer)?;
-        T::deserialize(&mut *self.deserializer)
+        T::deserialize(&mut self.deserializer)
+    }
+
+    fn deserialize_optional_field<T: XTypesDeserialize<'de>>(
+        &mut self,
+        _name: &str,
+    ) -> Result<Option<T>, XTypesError> {
+        // Optional members appended in a later version of the type are missing
+        // from the body written by the previous versions
+        if self.deserializer.is_exhausted() || !bool::deserialize(&mut self.deserializer)? {
+            Ok(None)
+        } else {
+            Ok(Some(T::deserialize(&mut self.deserializer)?))
+        }
     }
 }
 
 struct PlCdrBeDecoder<'a> {
-    buffer: &'a [u8],
+    reader: Reader<'a>,
 }
 
 impl<'de> DeserializeMutableStruct<'de> for PlCdrBeDecoder<'de> {
@@ -202,7 +340,7 @@ impl<'de> DeserializeMutableStruct<'de> for PlCdrBeDecoder<'de> {
         pid: u32,
         _name: &str,
     ) -> Result<T, XTypesError> {
-        let mut reader = Reader::new(self.buffer);
+        let mut reader = self.reader.nested(self.reader.buffer());
         seek_to_pid_be(&mut reader, pid as u16)?;
         T::deserialize(&mut Xcdr1BeDeserializer { reader })
     }
@@ -212,7 +350,7 @@ impl<'de> DeserializeMutableStruct<'de> for PlCdrBeDecoder<'de> {
         pid: u32,
         _name: &str,
     ) -> Result<Option<T>, XTypesError> {
-        let mut reader = Reader::new(self.buffer);
+        let mut reader = self.reader.nested(self.reader.buffer());
         Ok(if seek_to_optional_pid_be(&mut reader, pid as u16)? {
             Some(T::deserialize(&mut Xcdr1BeDeserializer { reader })?)
         } else {
@@ -222,7 +360,7 @@ impl<'de> DeserializeMutableStruct<'de> for PlCdrBeDecoder<'de> {
 }
 
 struct PlCdrLeDecoder<'a> {
-    buffer: &'a [u8],
+    reader: Reader<'a>,
 }
 
 impl<'de> DeserializeMutableStruct<'de> for PlCdrLeDecoder<'de> {
@@ -231,7 +369,7 @@ impl<'de> DeserializeMutableStruct<'de> for PlCdrLeDecoder<'de> {
         pid: u32,
         _name: &str,
     ) -> Result<T, XTypesError> {
-        let mut reader = Reader::new(self.buffer);
+        let mut reader = self.reader.nested(self.reader.buffer());
         seek_to_pid_le(&mut reader, pid as u16)?;
         T::deserialize(&mut Xcdr1LeDeserializer { reader })
     }
@@ -241,7 +379,7 @@ impl<'de> DeserializeMutableStruct<'de> for PlCdrLeDecoder<'de> {
         pid: u32,
         _name: &str,
     ) -> Result<Option<T>, XTypesError> {
-        let mut reader = Reader::new(self.buffer);
+        let mut reader = self.reader.nested(self.reader.buffer());
         Ok(if seek_to_optional_pid_le(&mut reader, pid as u16)? {
             Some(T::deserialize(&mut Xcdr1LeDeserializer { reader })?)
         } else {
@@ -251,7 +389,7 @@ impl<'de> DeserializeMutableStruct<'de> for PlCdrLeDecoder<'de> {
 }
 
 struct PlCdr2BeDecoder<'a> {
-    buffer: &'a [u8],
+    reader: Reader<'a>,
 }
 
 impl<'de> DeserializeMutableStruct<'de> for PlCdr2BeDecoder<'de> {
@@ -260,9 +398,11 @@ impl<'de> DeserializeMutableStruct<'de> for PlCdr2BeDecoder<'de> {
         pid: u32,
         _name: &str,
     ) -> Result<T, XTypesError> {
-        let mut reader = Reader::new(self.buffer);
-        seek_to_pid_be(&mut reader, pid as u16)?;
-        T::deserialize(&mut Xcdr2BeDeserializer { reader })
+        let value = find_member_v2(self.reader.buffer(), pid, u32::from_be_bytes)?
+            .ok_or(XTypesError::PidNotFound(pid as u16))?;
+        T::deserialize(&mut Xcdr2BeDeserializer {
+            reader: self.reader.nested(value),
+        })
     }
 
     fn deserialize_optional_field<T: XTypesDeserialize<'de>>(
@@ -270,17 +410,18 @@ impl<'de> DeserializeMutableStruct<'de> for PlCdr2BeDecoder<'de> {
         pid: u32,
         _name: &str,
     ) -> Result<Option<T>, XTypesError> {
-        let mut reader = Reader::new(self.buffer);
-        Ok(if seek_to_optional_pid_be(&mut reader, pid as u16)? {
-            Some(T::deserialize(&mut Xcdr2BeDeserializer { reader })?)
-        } else {
-            None
-        })
+        find_member_v2(self.reader.buffer(), pid, u32::from_be_bytes)?
+            .map(|value| {
+                T::deserialize(&mut Xcdr2BeDeserializer {
+                    reader: self.reader.nested(value),
+                })
+            })
+            .transpose()
     }
 }
 
 struct PlCdr2LeDecoder<'a> {
-    buffer: &'a [u8],
+    reader: Reader<'a>,
 }
 
 impl<'de> DeserializeMutableStruct<'de> for PlCdr2LeDecoder<'de> {
@@ -289,9 +430,11 @@ impl<'de> DeserializeMutableStruct<'de> for PlCdr2LeDecoder<'de> {
         pid: u32,
         _name: &str,
     ) -> Result<T, XTypesError> {
-        let mut reader = Reader::new(self.buffer);
-        seek_to_pid_le(&mut reader, pid as u16)?;
-        T::deserialize(&mut Xcdr2LeDeserializer { reader })
+        let value = find_member_v2(self.reader.buffer(), pid, u32::from_le_bytes)?
+            .ok_or(XTypesError::PidNotFound(pid as u16))?;
+        T::deserialize(&mut Xcdr2LeDeserializer {
+            reader: self.reader.nested(value),
+        })
     }
 
     fn deserialize_optional_field<T: XTypesDeserialize<'de>>(
@@ -299,12 +442,13 @@ impl<'de> DeserializeMutableStruct<'de> for PlCdr2LeDecoder<'de> {
         pid: u32,
         _name: &str,
     ) -> Result<Option<T>, XTypesError> {
-        let mut reader = Reader::new(self.buffer);
-        Ok(if seek_to_optional_pid_le(&mut reader, pid as u16)? {
-            Some(T::deserialize(&mut Xcdr2LeDeserializer { reader })?)
-        } else {
-            None
-        })
+        find_member_v2(self.reader.buffer(), pid, u32::from_le_bytes)?
+            .map(|value| {
+                T::deserialize(&mut Xcdr2LeDeserializer {
+                    reader: self.reader.nested(value),
+                })
+            })
+            .transpose()
     }
 }
 
@@ -351,6 +495,14 @@ impl<'de> DeserializeAppendableStruct<'de> for PlainCdrBeDecoder<'_, 'de> {
     ) -> Result<T, XTypesError> {
         T::deserialize(&mut *self.deserializer)
     }
+
+    fn deserialize_optional_field<T: XTypesDeserialize<'de>>(
+        &mut self,
+        name: &str,
+    ) -> Result<Option<T>, XTypesError> {
+        // Without DHEADER the appendable structs are encoded as the final ones
+        DeserializeFinalStruct::deserialize_optional_field(self, name)
+    }
 }
 
 impl<'de> DeserializeFinalStruct<'de> for PlainCdrBeDecoder<'_, 'de> {
@@ -374,6 +526,17 @@ impl<'de> DeserializeFinalStruct<'de> for PlainCdrBeDecoder<'_, 'de> {
             Ok(Some(T::deserialize(&mut *self.deserializer)?))
         }
     }
+
+    fn deserialize_trailing_field<T: XTypesDeserialize<'de> + Default>(
+        &mut self,
+        _name: &str,
+    ) -> Result<T, XTypesError> {
+        if self.deserializer.reader.buffer().is_empty() {
+            Ok(T::default())
+        } else {
+            T::deserialize(&mut *self.deserializer)
+        }
+    }
 }
 
 struct PlainCdrLeDecoder<'a, 'de> {
@@ -386,6 +549,14 @@ impl<'de> DeserializeAppendableStruct<'de> for PlainCdrLeDecoder<'_, 'de> {
     ) -> Result<T, XTypesError> {
         T::deserialize(&mut *self.deserializer)
     }
+
+    fn deserialize_optional_field<T: XTypesDeserialize<'de>>(
+        &mut self,
+        name: &str,
+    ) -> Result<Option<T>, XTypesError> {
+        // Without DHEADER the appendable structs are encoded as the final ones
+        DeserializeFinalStruct::deserialize_optional_field(self, name)
+    }
 }
 
 impl<'de> DeserializeFinalStruct<'de> for PlainCdrLeDecoder<'_, 'de> {
@@ -409,12 +580,23 @@ impl<'de> DeserializeFinalStruct<'de> for PlainCdrLeDecoder<'_, 'de> {
             Ok(Some(T::deserialize(&mut *self.deserializer)?))
         }
     }
+
+    fn deserialize_trailing_field<T: XTypesDeserialize<'de> + Default>(
+        &mut self,
+        _name: &str,
+    ) -> Result<T, XTypesError> {
+        if self.deserializer.reader.buffer().is_empty() {
+            Ok(T::default())
+        } else {
+            T::deserialize(&mut *self.deserializer)
+        }
+    }
 }
 
 struct PlainCdr2Decoder<'a, D> {
     deserializer: &'a mut D,
 }
-impl<'de, D> DeserializeFinalStruct<'de> for PlainCdr2Decoder<'_, D>
+impl<'de, D: DelimitedBody> DeserializeFinalStruct<'de> for PlainCdr2Decoder<'_, D>
 where
     for<'a> &'a mut D: XTypesDeserializer<'de>,
 {
@@ -435,6 +617,17 @@ where
             Ok(None)
         }
     }
+
+    fn deserialize_trailing_field<T: XTypesDeserialize<'de> + Default>(
+        &mut self,
+        _name: &str,
+    ) -> Result<T, XTypesError> {
+        if self.deserializer.is_exhausted() {
+            Ok(T::default())
+        } else {
+            T::deserialize(&mut *self.deserializer)
+        }
+    }
 }
 
 impl<'de> XTypesDeserializer<'de> for &mut Xcdr1BeDeserializer<'de> {
@@ -448,14 +641,14 @@ impl<'de> XTypesDeserializer<'de> for &mut Xcdr1BeDeserializer<'de> {
     }
     fn deserialize_mutable_struct(self) -> Result<impl DeserializeMutableStruct<'de>, XTypesError> {
         Ok(PlCdrBeDecoder {
-            buffer: self.reader.buffer(),
+            reader: self.reader.nested(self.reader.buffer()),
         })
     }
     fn deserialize_array(self) -> Result<impl DeserializeArray<'de>, XTypesError> {
         Ok(ArrayDecoder { deserializer: self })
     }
     fn deserialize_sequence(self) -> Result<impl DeserializeSequence<'de>, XTypesError> {
-        let len = self.deserialize_uint32()? as usize;
+        let len = read_sequence_length(self.deserialize_uint32()?, &self.reader)?;
         Ok(SequenceDecoder {
             deserializer: self,
             len,
@@ -528,14 +721,14 @@ impl<'de> XTypesDeserializer<'de> for &mut Xcdr1LeDeserializer<'de> {
     }
     fn deserialize_mutable_struct(self) -> Result<impl DeserializeMutableStruct<'de>, XTypesError> {
         Ok(PlCdrLeDecoder {
-            buffer: self.reader.buffer(),
+            reader: self.reader.nested(self.reader.buffer()),
         })
     }
     fn deserialize_array(self) -> Result<impl DeserializeArray<'de>, XTypesError> {
         Ok(ArrayDecoder { deserializer: self })
     }
     fn deserialize_sequence(self) -> Result<impl DeserializeSequence<'de>, XTypesError> {
-        let len = self.deserialize_uint32()? as usize;
+        let len = read_sequence_length(self.deserialize_uint32()?, &self.reader)?;
         Ok(SequenceDecoder {
             deserializer: self,
             len,
@@ -603,18 +796,26 @@ impl<'de> XTypesDeserializer<'de> for &mut Xcdr2BeDeserializer<'de> {
     fn deserialize_appendable_struct(
         self,
     ) -> Result<impl DeserializeAppendableStruct<'de>, XTypesError> {
-        Ok(DelimitedCdrDecoder { deserializer: self })
+        let length = self.deserialize_uint32()? as usize;
+        let body = self.reader.read_all(length)?;
+        Ok(DelimitedCdrDecoder {
+            deserializer: Xcdr2BeDeserializer {
+                reader: self.reader.nested(body),
+            },
+        })
     }
     fn deserialize_mutable_struct(self) -> Result<impl DeserializeMutableStruct<'de>, XTypesError> {
+        let length = self.deserialize_uint32()? as usize;
+        let body = self.reader.read_all(length)?;
         Ok(PlCdr2BeDecoder {
-            buffer: self.reader.buffer(),
+            reader: self.reader.nested(body),
         })
     }
     fn deserialize_array(self) -> Result<impl DeserializeArray<'de>, XTypesError> {
         Ok(ArrayDecoder { deserializer: self })
     }
     fn deserialize_sequence(self) -> Result<impl DeserializeSequence<'de>, XTypesError> {
-        let len = self.deserialize_uint32()? as usize;
+        let len = read_sequence_length(self.deserialize_uint32()?, &self.reader)?;
         Ok(SequenceDecoder {
             deserializer: self,
             len,
@@ -682,20 +883,26 @@ impl<'de> XTypesDeserializer<'de> for &mut Xcdr2LeDeserializer<'de> {
     fn deserialize_appendable_struct(
         self,
     ) -> Result<impl DeserializeAppendableStruct<'de>, XTypesError> {
+        let length = self.deserialize_uint32()? as usize;
+        let body = self.reader.read_all(length)?;
         Ok(DelimitedCdrDecoder {
-            deserializer: &mut *self,
+            deserializer: Xcdr2LeDeserializer {
+                reader: self.reader.nested(body),
+            },
         })
     }
     fn deserialize_mutable_struct(self) -> Result<impl DeserializeMutableStruct<'de>, XTypesError> {
+        let length = self.deserialize_uint32()? as usize;
+        let body = self.reader.read_all(length)?;
         Ok(PlCdr2LeDecoder {
-            buffer: self.reader.buffer(),
+            reader: self.reader.nested(body),
         })
     }
     fn deserialize_array(self) -> Result<impl DeserializeArray<'de>, XTypesError> {
         Ok(ArrayDecoder { deserializer: self })
     }
     fn deserialize_sequence(self) -> Result<impl DeserializeSequence<'de>, XTypesError> {
-        let len = self.deserialize_uint32()? as usize;
+        let len = read_sequence_length(self.deserialize_uint32()?, &self.reader)?;
         Ok(SequenceDecoder {
             deserializer: self,
             len,
@@ -1038,6 +1245,69 @@ mod tests {
         assert_eq!(deserialize_v1_be(&[1, 2, 77]), expected);
     }
 
+    #[test]
+    fn deserialize_sequence_longer_than_data() {
+        let data = [
+            0xff, 0xff, 0xff, 0xff, // length
+            1, 0, 2, 0, // data
+        ];
+        assert_eq!(
+            deserialize_v1_le::<Vec<u16>>(&data),
+            Err(XTypesError::InvalidData)
+        );
+        assert_eq!(
+            deserialize_v2_le::<Vec<u16>>(&data),
+            Err(XTypesError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn deserialize_sequence_longer_than_max_sequence_length() {
+        struct AppendableType(Vec<u8>);
+        impl<'de> XTypesDeserialize<'de> for AppendableType {
+            fn deserialize(
+                deserializer: impl XTypesDeserializer<'de>,
+            ) -> Result<Self, XTypesError> {
+                let mut deserializer = deserializer.deserialize_appendable_struct()?;
+                Ok(AppendableType(deserializer.deserialize_field("values")?))
+            }
+        }
+
+        let data = [
+            3, 0, 0, 0, // length
+            1, 2, 3, // data
+        ];
+        assert_eq!(
+            Vec::<u8>::deserialize(&mut Xcdr1LeDeserializer::with_max_sequence_length(&data, 3)),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            Vec::<u8>::deserialize(&mut Xcdr1LeDeserializer::with_max_sequence_length(&data, 2)),
+            Err(XTypesError::InvalidData)
+        );
+
+        // The limit also applies to the members of the nested delimited types
+        let data = [
+            7, 0, 0, 0, // DHEADER
+            3, 0, 0, 0, // length
+            1, 2, 3, // data
+        ];
+        assert_eq!(
+            AppendableType::deserialize(&mut Xcdr2LeDeserializer::with_max_sequence_length(
+                &data, 3
+            ))
+            .map(|v| v.0),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            AppendableType::deserialize(&mut Xcdr2LeDeserializer::with_max_sequence_length(
+                &data, 2
+            ))
+            .map(|v| v.0),
+            Err(XTypesError::InvalidData)
+        );
+    }
+
     #[derive(Debug, PartialEq)]
     //@extensibility(FINAL)
     struct FinalType {
@@ -1348,6 +1618,92 @@ mod tests {
         );
     }
 
+    #[derive(Debug, PartialEq)]
+    //@extensibility(FINAL)
+    struct FinalWithAppendableType {
+        appendable: AppendableType,
+        value: u16,
+    }
+    impl<'de> XTypesDeserialize<'de> for FinalWithAppendableType {
+        fn deserialize(deserializer: impl XTypesDeserializer<'de>) -> Result<Self, XTypesError> {
+            let mut deserializer = deserializer.deserialize_final_struct()?;
+            Ok(Self {
+                appendable: deserializer.deserialize_field("appendable")?,
+                value: deserializer.deserialize_field("value")?,
+            })
+        }
+    }
+
+    #[test]
+    fn deserialize_appendable_struct_skips_appended_members() {
+        assert_eq!(
+            deserialize_v2_le::<FinalWithAppendableType>(&[
+                4, 0, 0, 0, // DHEADER
+                7, 0, // appendable: value
+                1, 2, // appendable: member unknown to the type
+                9, 0, // value
+            ]),
+            Ok(FinalWithAppendableType {
+                appendable: AppendableType { value: 7 },
+                value: 9,
+            })
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    //@extensibility(APPENDABLE)
+    struct AppendableOptionalType {
+        value: u16,
+        optional_value: Option<u16>,
+    }
+    impl<'de> XTypesDeserialize<'de> for AppendableOptionalType {
+        fn deserialize(deserializer: impl XTypesDeserializer<'de>) -> Result<Self, XTypesError> {
+            let mut deserializer = deserializer.deserialize_appendable_struct()?;
+            Ok(Self {
+                value: deserializer.deserialize_field("value")?,
+                optional_value: deserializer.deserialize_optional_field("optional_value")?,
+            })
+        }
+    }
+
+    #[test]
+    fn deserialize_appendable_struct_with_optional_member() {
+        assert_eq!(
+            deserialize_v2_le::<AppendableOptionalType>(&[
+                6, 0, 0, 0, // DHEADER
+                7, 0, // value
+                1, 0, // boolean for option | padding
+                9, 0, // optional_value
+            ]),
+            Ok(AppendableOptionalType {
+                value: 7,
+                optional_value: Some(9),
+            })
+        );
+        assert_eq!(
+            deserialize_v2_le::<AppendableOptionalType>(&[
+                3, 0, 0, 0, // DHEADER
+                7, 0, // value
+                0, // boolean for option
+            ]),
+            Ok(AppendableOptionalType {
+                value: 7,
+                optional_value: None,
+            })
+        );
+        // Written by a previous version of the type without the optional member
+        assert_eq!(
+            deserialize_v2_le::<AppendableOptionalType>(&[
+                2, 0, 0, 0, // DHEADER
+                7, 0, // value
+            ]),
+            Ok(AppendableOptionalType {
+                value: 7,
+                optional_value: None,
+            })
+        );
+    }
+
     #[derive(Debug, PartialEq)]
     //@extensibility(MUTABLE)
     struct MutableType {
@@ -1397,21 +1753,43 @@ mod tests {
         // PL_CDR2:
         assert_eq!(
             deserialize_v2_be::<MutableType>(&[
-                0x00, 0x05A, 0, 1, // PID | length
+                0, 0, 0, 16, // DHEADER
+                0x00, 0, 0, 0x5A, // EMHEADER1 (LC=0)
                 7, 0, 0, 0, // key | padding
-                0x00, 0x050, 0, 4, // PID | length
+                0x20, 0, 0, 0x50, // EMHEADER1 (LC=2)
                 0, 0, 0, 8, // participant_key
-                0, 0, 0, 0, // Sentinel
             ]),
             expected
         );
         assert_eq!(
             deserialize_v2_le::<MutableType>(&[
-                0x05A, 0x00, 1, 0, // PID | length
+                16, 0, 0, 0, // DHEADER
+                0x5A, 0, 0, 0x00, // EMHEADER1 (LC=0)
                 7, 0, 0, 0, // key | padding
-                0x050, 0x00, 4, 0, // PID | length
+                0x50, 0, 0, 0x20, // EMHEADER1 (LC=2)
                 8, 0, 0, 0, // participant_key
-                0, 0, 0, 0, // Sentinel
+            ]),
+            expected
+        );
+    }
+
+    #[test]
+    fn deserialize_mutable_struct_with_nextint_and_unknown_member() {
+        let expected = Ok(MutableType {
+            key: 7,
+            participant_key: 8,
+        });
+        assert_eq!(
+            deserialize_v2_le::<MutableType>(&[
+                32, 0, 0, 0, // DHEADER
+                0x10, 0, 0, 0x40, // EMHEADER1 (LC=4) of a member unknown to the type
+                3, 0, 0, 0, // NEXTINT
+                1, 2, 3, 0, // unknown member | padding
+                0x50, 0, 0, 0x40, // EMHEADER1 (LC=4)
+                4, 0, 0, 0, // NEXTINT
+                8, 0, 0, 0, // participant_key
+                0x5A, 0, 0, 0x80, // EMHEADER1 (M_FLAG, LC=0)
+                7, 0, 0, 0, // key | padding
             ]),
             expected
         );
@@ -1,4 +1,5 @@
 use super::{
+    dynamic_type::ExtensibilityKind,
     error::XTypesError,
     serialize::{Write, XTypesSerialize},
     serializer::{
@@ -94,14 +95,22 @@ fn str_len(v: &str) -> Result<u32, XTypesError> {
 
 pub struct Xcdr1BeSerializer<'a, C> {
     writer: CollectionWriter<'a, C>,
+    extensibility: Option<ExtensibilityKind>,
 }
 
 impl<'a, C: Write> Xcdr1BeSerializer<'a, C> {
     pub fn new(collection: &'a mut C) -> Self {
         Self {
             writer: CollectionWriter::new(collection),
+            extensibility: None,
         }
     }
+
+    /// Extensibility of the first struct serialized, which determines the representation
+    /// identifier of the encapsulated data.
+    pub fn extensibility(&self) -> Option<ExtensibilityKind> {
+        self.extensibility
+    }
 }
 
 impl Xcdr1BeSerializer<'_, ()> {
@@ -148,6 +157,19 @@ impl<C: Write> SerializeAppendableStruct for &mut Xcdr1BeSerializer<'_, C> {
     ) -> Result<(), XTypesError> {
         XTypesSerialize::serialize(value, &mut **self)
     }
+
+    fn serialize_optional_field<T: XTypesSerialize>(
+        &mut self,
+        value: &Option<T>,
+        name: &str,
+    ) -> Result<(), XTypesError> {
+        // Without DHEADER the appendable structs are encoded as the final ones
+        SerializeFinalStruct::serialize_optional_field(self, value, name)
+    }
+
+    fn end(self) -> Result<(), XTypesError> {
+        Ok(())
+    }
 }
 impl<C: Write> SerializeMutableStruct for &mut Xcdr1BeSerializer<'_, C> {
     fn serialize_field<T: XTypesSerialize>(
@@ -164,6 +186,19 @@ impl<C: Write> SerializeMutableStruct for &mut Xcdr1BeSerializer<'_, C> {
         Ok(())
     }
 
+    fn serialize_optional_field<T: XTypesSerialize>(
+        &mut self,
+        value: &Option<T>,
+        pid: u32,
+        name: &str,
+    ) -> Result<(), XTypesError> {
+        // Absent members are omitted from the parameter list
+        match value {
+            Some(value) => SerializeMutableStruct::serialize_field(self, value, pid, name),
+            None => Ok(()),
+        }
+    }
+
     fn end(self) -> Result<(), XTypesError> {
         self.writer.write_slice(&PID_SENTINEL.to_be_bytes());
         self.writer.write_slice(&0u16.to_be_bytes());
@@ -178,12 +213,16 @@ impl<C: Write> SerializeCollection for &mut Xcdr1BeSerializer<'_, C> {
 
 impl<C: Write> XTypesSerializer for &mut Xcdr1BeSerializer<'_, C> {
     fn serialize_final_struct(self) -> Result<impl SerializeFinalStruct, XTypesError> {
+        self.extensibility.get_or_insert(ExtensibilityKind::Final);
         Ok(self)
     }
     fn serialize_appendable_struct(self) -> Result<impl SerializeAppendableStruct, XTypesError> {
+        self.extensibility
+            .get_or_insert(ExtensibilityKind::Appendable);
         Ok(self)
     }
     fn serialize_mutable_struct(self) -> Result<impl SerializeMutableStruct, XTypesError> {
+        self.extensibility.get_or_insert(ExtensibilityKind::Mutable);
         Ok(self)
     }
     fn serialize_sequence(self, len: usize) -> Result<impl SerializeCollection, XTypesError> {
@@ -263,14 +302,22 @@ impl<C: Write> XTypesSerializer for &mut Xcdr1BeSerializer<'_, C> {
 
 pub struct Xcdr1LeSerializer<'a, C> {
     writer: CollectionWriter<'a, C>,
+    extensibility: Option<ExtensibilityKind>,
 }
 
 impl<'a, C: Write> Xcdr1LeSerializer<'a, C> {
     pub fn new(collection: &'a mut C) -> Self {
         Self {
             writer: CollectionWriter::new(collection),
+            extensibility: None,
         }
     }
+
+    /// Extensibility of the first struct serialized, which determines the representation
+    /// identifier of the encapsulated data.
+    pub fn extensibility(&self) -> Option<ExtensibilityKind> {
+        self.extensibility
+    }
 }
 
 impl Xcdr1LeSerializer<'_, ()> {
@@ -318,6 +365,19 @@ impl<C: Write> SerializeAppendableStruct for &mut Xcdr1LeSerializer<'_, C> {
     ) -> Result<(), XTypesError> {
         XTypesSerialize::serialize(value, &mut **self)
     }
+
+    fn serialize_optional_field<T: XTypesSerialize>(
+        &mut self,
+        value: &Option<T>,
+        name: &str,
+    ) -> Result<(), XTypesError> {
+        // Without DHEADER the appendable structs are encoded as the final ones
+        SerializeFinalStruct::serialize_optional_field(self, value, name)
+    }
+
+    fn end(self) -> Result<(), XTypesError> {
+        Ok(())
+    }
 }
 impl<C: Write> SerializeMutableStruct for &mut Xcdr1LeSerializer<'_, C> {
     fn serialize_field<T: XTypesSerialize>(
@@ -334,6 +394,19 @@ impl<C: Write> SerializeMutableStruct for &mut Xcdr1LeSerializer<'_, C> {
         Ok(())
     }
 
+    fn serialize_optional_field<T: XTypesSerialize>(
+        &mut self,
+        value: &Option<T>,
+        pid: u32,
+        name: &str,
+    ) -> Result<(), XTypesError> {
+        // Absent members are omitted from the parameter list
+        match value {
+            Some(value) => SerializeMutableStruct::serialize_field(self, value, pid, name),
+            None => Ok(()),
+        }
+    }
+
     fn end(self) -> Result<(), XTypesError> {
         self.writer.write_slice(&PID_SENTINEL.to_le_bytes());
         self.writer.write_slice(&0u16.to_le_bytes());
@@ -348,12 +421,16 @@ impl<C: Write> SerializeCollection for &mut Xcdr1LeSerializer<'_, C> {
 
 impl<C: Write> XTypesSerializer for &mut Xcdr1LeSerializer<'_, C> {
     fn serialize_final_struct(self) -> Result<impl SerializeFinalStruct, XTypesError> {
+        self.extensibility.get_or_insert(ExtensibilityKind::Final);
         Ok(self)
     }
     fn serialize_appendable_struct(self) -> Result<impl SerializeAppendableStruct, XTypesError> {
+        self.extensibility
+            .get_or_insert(ExtensibilityKind::Appendable);
         Ok(self)
     }
     fn serialize_mutable_struct(self) -> Result<impl SerializeMutableStruct, XTypesError> {
+        self.extensibility.get_or_insert(ExtensibilityKind::Mutable);
         Ok(self)
     }
     fn serialize_sequence(self, len: usize) -> Result<impl SerializeCollection, XTypesError> {
@@ -433,14 +510,22 @@ impl<C: Write> XTypesSerializer for &mut Xcdr1LeSerializer<'_, C> {
 
 pub struct Xcdr2BeSerializer<'a, C> {
     writer: CollectionWriter<'a, C>,
+    extensibility: Option<ExtensibilityKind>,
 }
 
 impl<'a, C: Write> Xcdr2BeSerializer<'a, C> {
     pub fn new(collection: &'a mut C) -> Self {
         Self {
             writer: CollectionWriter::new(collection),
+            extensibility: None,
         }
     }
+
+    /// Extensibility of the first struct serialized, which determines the representation
+    /// identifier of the encapsulated data.
+    pub fn extensibility(&self) -> Option<ExtensibilityKind> {
+        self.extensibility
+    }
 }
 
 impl Xcdr2BeSerializer<'_, ()> {
@@ -452,16 +537,39 @@ impl Xcdr2BeSerializer<'_, ()> {
     }
 }
 
+impl<'a> Xcdr2BeSerializer<'a, Vec<u8>> {
+    // Serializer appending to a buffer which is written after a DHEADER. Since the
+    // XCDR2 alignment is at most 4 the buffer keeps the alignment of the outer stream.
+    fn delimited(buffer: &'a mut Vec<u8>) -> Self {
+        let position = buffer.len();
+        Self {
+            writer: CollectionWriter {
+                collection: buffer,
+                position,
+            },
+            extensibility: None,
+        }
+    }
+}
+
 pub struct Xcdr2LeSerializer<'a, C> {
     writer: CollectionWriter<'a, C>,
+    extensibility: Option<ExtensibilityKind>,
 }
 
 impl<'a, C: Write> Xcdr2LeSerializer<'a, C> {
     pub fn new(collection: &'a mut C) -> Self {
         Self {
             writer: CollectionWriter::new(collection),
+            extensibility: None,
         }
     }
+
+    /// Extensibility of the first struct serialized, which determines the representation
+    /// identifier of the encapsulated data.
+    pub fn extensibility(&self) -> Option<ExtensibilityKind> {
+        self.extensibility
+    }
 }
 
 impl Xcdr2LeSerializer<'_, ()> {
@@ -473,62 +581,111 @@ impl Xcdr2LeSerializer<'_, ()> {
     }
 }
 
-impl<'a, C: Write> SerializeMutableStruct for &mut Xcdr2BeSerializer<'a, C> {
+impl<'a> Xcdr2LeSerializer<'a, Vec<u8>> {
+    // Serializer appending to a buffer which is written after a DHEADER. Since the
+    // XCDR2 alignment is at most 4 the buffer keeps the alignment of the outer stream.
+    fn delimited(buffer: &'a mut Vec<u8>) -> Self {
+        let position = buffer.len();
+        Self {
+            writer: CollectionWriter {
+                collection: buffer,
+                position,
+            },
+            extensibility: None,
+        }
+    }
+}
+
+// EMHEADER1 with the length code indicating that the member length is given by the NEXTINT
+const LC_NEXTINT: u32 = 4 << 28;
+
+fn emheader(member_id: u32) -> Result<u32, XTypesError> {
+    if member_id > 0x0FFF_FFFF {
+        Err(XTypesError::InvalidData)
+    } else {
+        Ok(LC_NEXTINT | member_id)
+    }
+}
+
+struct PlainCdr2Encoder<'a, S> {
+    serializer: &'a mut S,
+}
+
+impl<S> SerializeFinalStruct for PlainCdr2Encoder<'_, S>
+where
+    for<'a> &'a mut S: XTypesSerializer,
+{
     fn serialize_field<T: XTypesSerialize>(
         &mut self,
         value: &T,
-        pid: u32,
         _name: &str,
     ) -> Result<(), XTypesError> {
-        let length = Xcdr2BeSerializer::bytes_len(value)? as u16;
-        self.writer.write_slice(&(pid as u16).to_be_bytes());
-        self.writer.write_slice(&length.to_be_bytes());
-        XTypesSerialize::serialize(value, &mut **self)?;
-        self.writer.pad(4);
-        Ok(())
+        XTypesSerialize::serialize(value, &mut *self.serializer)
     }
-    fn end(self) -> Result<(), XTypesError> {
-        self.writer.write_slice(&PID_SENTINEL.to_be_bytes());
-        self.writer.write_slice(&0u16.to_be_bytes());
-        Ok(())
+
+    fn serialize_optional_field<T: XTypesSerialize>(
+        &mut self,
+        value: &Option<T>,
+        _name: &str,
+    ) -> Result<(), XTypesError> {
+        if let Some(value) = value {
+            true.serialize(&mut *self.serializer)?;
+            value.serialize(&mut *self.serializer)
+        } else {
+            false.serialize(&mut *self.serializer)
+        }
     }
 }
 
-impl<'a, C: Write> SerializeMutableStruct for &mut Xcdr2LeSerializer<'a, C> {
+struct DelimitedCdr2BeEncoder<'a, 'b, C> {
+    serializer: &'a mut Xcdr2BeSerializer<'b, C>,
+    buffer: Vec<u8>,
+}
+
+impl<C: Write> SerializeAppendableStruct for DelimitedCdr2BeEncoder<'_, '_, C> {
     fn serialize_field<T: XTypesSerialize>(
         &mut self,
         value: &T,
-        pid: u32,
         _name: &str,
     ) -> Result<(), XTypesError> {
-        let length = Xcdr2LeSerializer::bytes_len(value)? as u16;
-        self.writer.write_slice(&(pid as u16).to_le_bytes());
-        self.writer.write_slice(&length.to_le_bytes());
-        XTypesSerialize::serialize(value, &mut **self)?;
-        self.writer.pad(4);
-        Ok(())
+        XTypesSerialize::serialize(value, &mut Xcdr2BeSerializer::delimited(&mut self.buffer))
     }
+
+    fn serialize_optional_field<T: XTypesSerialize>(
+        &mut self,
+        value: &Option<T>,
+        _name: &str,
+    ) -> Result<(), XTypesError> {
+        let mut serializer = Xcdr2BeSerializer::delimited(&mut self.buffer);
+        if let Some(value) = value {
+            true.serialize(&mut serializer)?;
+            value.serialize(&mut serializer)
+        } else {
+            false.serialize(&mut serializer)
+        }
+    }
+
     fn end(self) -> Result<(), XTypesError> {
-        self.writer.write_slice(&PID_SENTINEL.to_le_bytes());
-        self.writer.write_slice(&0u16.to_le_bytes());
+        // DHEADER
+        self.serializer
+            .serialize_uint32(into_u32(self.buffer.len())?)?;
+        self.serializer.writer.write_slice(&self.buffer);
         Ok(())
     }
 }
 
-struct PlainCdr2Encoder<'a, S> {
-    serializer: &'a mut S,
+struct DelimitedCdr2LeEncoder<'a, 'b, C> {
+    serializer: &'a mut Xcdr2LeSerializer<'b, C>,
+    buffer: Vec<u8>,
 }
 
-impl<S> SerializeFinalStruct for PlainCdr2Encoder<'_, S>
-where
-    for<'a> &'a mut S: XTypesSerializer,
-{
+impl<C: Write> SerializeAppendableStruct for DelimitedCdr2LeEncoder<'_, '_, C> {
     fn serialize_field<T: XTypesSerialize>(
         &mut self,
         value: &T,
         _name: &str,
     ) -> Result<(), XTypesError> {
-        XTypesSerialize::serialize(value, &mut *self.serializer)
+        XTypesSerialize::serialize(value, &mut Xcdr2LeSerializer::delimited(&mut self.buffer))
     }
 
     fn serialize_optional_field<T: XTypesSerialize>(
@@ -536,39 +693,102 @@ where
         value: &Option<T>,
         _name: &str,
     ) -> Result<(), XTypesError> {
+        let mut serializer = Xcdr2LeSerializer::delimited(&mut self.buffer);
         if let Some(value) = value {
-            true.serialize(&mut *self.serializer)?;
-            value.serialize(&mut *self.serializer)
+            true.serialize(&mut serializer)?;
+            value.serialize(&mut serializer)
         } else {
-            false.serialize(&mut *self.serializer)
+            false.serialize(&mut serializer)
         }
     }
+
+    fn end(self) -> Result<(), XTypesError> {
+        // DHEADER
+        self.serializer
+            .serialize_uint32(into_u32(self.buffer.len())?)?;
+        self.serializer.writer.write_slice(&self.buffer);
+        Ok(())
+    }
+}
+
+struct PlCdr2BeEncoder<'a, 'b, C> {
+    serializer: &'a mut Xcdr2BeSerializer<'b, C>,
+    buffer: Vec<u8>,
 }
 
-impl<'a, C: Write> SerializeAppendableStruct for &mut Xcdr2BeSerializer<'a, C> {
+impl<C: Write> SerializeMutableStruct for PlCdr2BeEncoder<'_, '_, C> {
     fn serialize_field<T: XTypesSerialize>(
         &mut self,
         value: &T,
+        pid: u32,
         _name: &str,
     ) -> Result<(), XTypesError> {
-        let length = Xcdr2BeSerializer::bytes_len(value)? as u32;
+        let mut serializer = Xcdr2BeSerializer::delimited(&mut self.buffer);
+        // EMHEADER1 | NEXTINT
+        serializer.serialize_uint32(emheader(pid)?)?;
+        serializer.serialize_uint32(into_u32(Xcdr2BeSerializer::bytes_len(value)?)?)?;
+        XTypesSerialize::serialize(value, &mut serializer)
+    }
+
+    fn serialize_optional_field<T: XTypesSerialize>(
+        &mut self,
+        value: &Option<T>,
+        pid: u32,
+        name: &str,
+    ) -> Result<(), XTypesError> {
+        // Absent members are omitted from the member list
+        match value {
+            Some(value) => self.serialize_field(value, pid, name),
+            None => Ok(()),
+        }
+    }
+
+    fn end(self) -> Result<(), XTypesError> {
         // DHEADER
-        XTypesSerialize::serialize(&length, &mut **self)?;
-        XTypesSerialize::serialize(value, &mut **self)?;
+        self.serializer
+            .serialize_uint32(into_u32(self.buffer.len())?)?;
+        self.serializer.writer.write_slice(&self.buffer);
         Ok(())
     }
 }
 
-impl<'a, C: Write> SerializeAppendableStruct for &mut Xcdr2LeSerializer<'a, C> {
+struct PlCdr2LeEncoder<'a, 'b, C> {
+    serializer: &'a mut Xcdr2LeSerializer<'b, C>,
+    buffer: Vec<u8>,
+}
+
+impl<C: Write> SerializeMutableStruct for PlCdr2LeEncoder<'_, '_, C> {
     fn serialize_field<T: XTypesSerialize>(
         &mut self,
         value: &T,
+        pid: u32,
         _name: &str,
     ) -> Result<(), XTypesError> {
-        let length = Xcdr2LeSerializer::bytes_len(value)? as u32;
+        let mut serializer = Xcdr2LeSerializer::delimited(&mut self.buffer);
+        // EMHEADER1 | NEXTINT
+        serializer.serialize_uint32(emheader(pid)?)?;
+        serializer.serialize_uint32(into_u32(Xcdr2LeSerializer::bytes_len(value)?)?)?;
+        XTypesSerialize::serialize(value, &mut serializer)
+    }
+
+    fn serialize_optional_field<T: XTypesSerialize>(
+        &mut self,
+        value: &Option<T>,
+        pid: u32,
+        name: &str,
+    ) -> Result<(), XTypesError> {
+        // Absent members are omitted from the member list
+        match value {
+            Some(value) => self.serialize_field(value, pid, name),
+            None => Ok(()),
+        }
+    }
+
+    fn end(self) -> Result<(), XTypesError> {
         // DHEADER
-        XTypesSerialize::serialize(&length, &mut **self)?;
-        XTypesSerialize::serialize(value, &mut **self)?;
+        self.serializer
+            .serialize_uint32(into_u32(self.buffer.len())?)?;
+        self.serializer.writer.write_slice(&self.buffer);
         Ok(())
     }
 }
@@ -587,13 +807,23 @@ where
 
 impl<C: Write> XTypesSerializer for &mut Xcdr2BeSerializer<'_, C> {
     fn serialize_final_struct(self) -> Result<impl SerializeFinalStruct, XTypesError> {
+        self.extensibility.get_or_insert(ExtensibilityKind::Final);
         Ok(PlainCdr2Encoder { serializer: self })
     }
     fn serialize_appendable_struct(self) -> Result<impl SerializeAppendableStruct, XTypesError> {
-        Ok(self)
+        self.extensibility
+            .get_or_insert(ExtensibilityKind::Appendable);
+        Ok(DelimitedCdr2BeEncoder {
+            serializer: self,
+            buffer: Vec::new(),
+        })
     }
     fn serialize_mutable_struct(self) -> Result<impl SerializeMutableStruct, XTypesError> {
-        Ok(self)
+        self.extensibility.get_or_insert(ExtensibilityKind::Mutable);
+        Ok(PlCdr2BeEncoder {
+            serializer: self,
+            buffer: Vec::new(),
+        })
     }
     fn serialize_sequence(self, len: usize) -> Result<impl SerializeCollection, XTypesError> {
         self.serialize_uint32(into_u32(len)?)?;
@@ -658,13 +888,23 @@ impl<C: Write> XTypesSerializer for &mut Xcdr2BeSerializer<'_, C> {
 
 impl<C: Write> XTypesSerializer for &mut Xcdr2LeSerializer<'_, C> {
     fn serialize_final_struct(self) -> Result<impl SerializeFinalStruct, XTypesError> {
+        self.extensibility.get_or_insert(ExtensibilityKind::Final);
         Ok(PlainCdr2Encoder { serializer: self })
     }
     fn serialize_appendable_struct(self) -> Result<impl SerializeAppendableStruct, XTypesError> {
-        Ok(self)
+        self.extensibility
+            .get_or_insert(ExtensibilityKind::Appendable);
+        Ok(DelimitedCdr2LeEncoder {
+            serializer: self,
+            buffer: Vec::new(),
+        })
     }
     fn serialize_mutable_struct(self) -> Result<impl SerializeMutableStruct, XTypesError> {
-        Ok(self)
+        self.extensibility.get_or_insert(ExtensibilityKind::Mutable);
+        Ok(PlCdr2LeEncoder {
+            serializer: self,
+            buffer: Vec::new(),
+        })
     }
     fn serialize_sequence(self, len: usize) -> Result<impl SerializeCollection, XTypesError> {
         self.serialize_uint32(into_u32(len)?)?;
@@ -1219,7 +1459,8 @@ mod tests {
     impl XTypesSerialize for AppendableType {
         fn serialize(&self, serializer: impl XTypesSerializer) -> Result<(), XTypesError> {
             let mut serializer = serializer.serialize_appendable_struct()?;
-            serializer.serialize_field(&self.value, "value")
+            serializer.serialize_field(&self.value, "value")?;
+            serializer.end()
         }
     }
 
@@ -1293,23 +1534,135 @@ mod tests {
         assert_eq!(
             serialize_v2_be(&v),
             vec![
-                0x00, 0x05A, 0, 1, // PID | length
+                0, 0, 0, 22, // DHEADER
+                0x40, 0, 0, 0x5A, // EMHEADER1 (LC=4)
+                0, 0, 0, 1, // NEXTINT
                 7, 0, 0, 0, // key | padding
-                0x00, 0x050, 0, 2, // PID | length
-                0, 8, 0, 0, // participant_key | padding (2 bytes)
-                0, 1, 0, 0, // Sentinel
+                0x40, 0, 0, 0x50, // EMHEADER1 (LC=4)
+                0, 0, 0, 2, // NEXTINT
+                0, 8, // participant_key
             ]
         );
         assert_eq!(
             serialize_v2_le(&v),
+            vec![
+                22, 0, 0, 0, // DHEADER
+                0x5A, 0, 0, 0x40, // EMHEADER1 (LC=4)
+                1, 0, 0, 0, // NEXTINT
+                7, 0, 0, 0, // key | padding
+                0x50, 0, 0, 0x40, // EMHEADER1 (LC=4)
+                2, 0, 0, 0, // NEXTINT
+                8, 0, // participant_key
+            ]
+        );
+    }
+
+    //@extensibility(MUTABLE)
+    struct MutableOptionalType {
+        // @id(0x005A) @key
+        key: u8,
+        // @id(0x0050) @optional
+        optional_field: Option<u16>,
+    }
+    impl XTypesSerialize for MutableOptionalType {
+        fn serialize(&self, serializer: impl XTypesSerializer) -> Result<(), XTypesError> {
+            let mut s = serializer.serialize_mutable_struct()?;
+            s.serialize_field(&self.key, 0x005A, "key")?;
+            s.serialize_optional_field(&self.optional_field, 0x0050, "optional_field")?;
+            s.end()
+        }
+    }
+
+    #[test]
+    fn serialize_mutable_optional_struct() {
+        let some = MutableOptionalType {
+            key: 7,
+            optional_field: Some(8),
+        };
+        let none = MutableOptionalType {
+            key: 7,
+            optional_field: None,
+        };
+        // PL_CDR:
+        assert_eq!(
+            serialize_v1_le(&some),
             vec![
                 0x05A, 0x00, 1, 0, // PID | length
                 7, 0, 0, 0, // key | padding
                 0x050, 0x00, 2, 0, // PID | length
-                8, 0, 0, 0, // participant_key | padding (2 bytes)
+                8, 0, 0, 0, // optional_field | padding (2 bytes)
+                1, 0, 0, 0, // Sentinel
+            ]
+        );
+        assert_eq!(
+            serialize_v1_le(&none),
+            vec![
+                0x05A, 0x00, 1, 0, // PID | length
+                7, 0, 0, 0, // key | padding
                 1, 0, 0, 0, // Sentinel
             ]
         );
+        // PL_CDR2:
+        assert_eq!(
+            serialize_v2_le(&none),
+            vec![
+                9, 0, 0, 0, // DHEADER
+                0x5A, 0, 0, 0x40, // EMHEADER1 (LC=4)
+                1, 0, 0, 0, // NEXTINT
+                7, // key
+            ]
+        );
+    }
+
+    //@extensibility(APPENDABLE)
+    struct AppendableOptionalType {
+        field: u8,
+        optional_field: Option<u16>,
+    }
+    impl XTypesSerialize for AppendableOptionalType {
+        fn serialize(&self, serializer: impl XTypesSerializer) -> Result<(), XTypesError> {
+            let mut s = serializer.serialize_appendable_struct()?;
+            s.serialize_field(&self.field, "field")?;
+            s.serialize_optional_field(&self.optional_field, "optional_field")?;
+            s.end()
+        }
+    }
+
+    #[test]
+    fn serialize_appendable_optional_struct() {
+        let some = AppendableOptionalType {
+            field: 6,
+            optional_field: Some(7),
+        };
+        let none = AppendableOptionalType {
+            field: 6,
+            optional_field: None,
+        };
+        // PLAIN_CDR:
+        assert_eq!(
+            serialize_v1_le(&some),
+            vec![
+                6, 0, 0, 0, // u8 | padding
+                0, 0, 2, 0, // HEADER (FLAGS+ID | length)
+                7, 0 // optional_field value
+            ]
+        );
+        // DELIMITED_CDR2:
+        assert_eq!(
+            serialize_v2_le(&some),
+            vec![
+                4, 0, 0, 0, // DHEADER
+                6, 1, // u8 | boolean for option
+                7, 0 // optional_field value
+            ]
+        );
+        assert_eq!(
+            serialize_v2_le(&none),
+            vec![
+                2, 0, 0, 0, // DHEADER
+                6, 0, // u8 | boolean for option
+            ]
+        );
     }
 
     //@extensibility(FINAL)
@@ -1388,33 +1741,43 @@ mod tests {
         assert_eq!(
             serialize_v2_be(&v),
             vec![
-                0x00, 0x060, 0, 1, // PID | length
+                0, 0, 0, 58, // DHEADER
+                0x40, 0, 0, 0x60, // EMHEADER1 (LC=4)
+                0, 0, 0, 1, // NEXTINT
                 5, 0, 0, 0, // field_primitive | padding (3 bytes)
-                0x00, 0x061, 0, 20, // PID | length
-                0x00, 0x05A, 0, 1, // field_mutable: PID | length
+                0x40, 0, 0, 0x61, // EMHEADER1 (LC=4)
+                0, 0, 0, 26, // NEXTINT
+                0, 0, 0, 22, // field_mutable: DHEADER
+                0x40, 0, 0, 0x5A, // field_mutable: EMHEADER1 (LC=4)
+                0, 0, 0, 1, // field_mutable: NEXTINT
                 7, 0, 0, 0, // field_mutable: key | padding (3 bytes)
-                0x00, 0x050, 0, 2, // field_mutable: PID | length
+                0x40, 0, 0, 0x50, // field_mutable: EMHEADER1 (LC=4)
+                0, 0, 0, 2, // field_mutable: NEXTINT
                 0, 8, 0, 0, // field_mutable: participant_key | padding (2 bytes)
-                0, 1, 0, 0, // field_mutable: Sentinel
-                0x00, 0x062, 0, 2, // field_mutable: PID | length
-                0, 9, 0, 0, // field_final: primitive | padding (2 bytes)
-                0, 1, 0, 0, // Sentinel
+                0x40, 0, 0, 0x62, // EMHEADER1 (LC=4)
+                0, 0, 0, 2, // NEXTINT
+                0, 9, // field_final: primitive
             ]
         );
         assert_eq!(
             serialize_v2_le(&v),
             vec![
-                0x060, 0x00, 1, 0, // PID | length
+                58, 0, 0, 0, // DHEADER
+                0x60, 0, 0, 0x40, // EMHEADER1 (LC=4)
+                1, 0, 0, 0, // NEXTINT
                 5, 0, 0, 0, // field_primitive | padding (3 bytes)
-                0x061, 0x00, 20, 0, // PID | length
-                0x05A, 0x00, 1, 0, // field_mutable: PID | length
+                0x61, 0, 0, 0x40, // EMHEADER1 (LC=4)
+                26, 0, 0, 0, // NEXTINT
+                22, 0, 0, 0, // field_mutable: DHEADER
+                0x5A, 0, 0, 0x40, // field_mutable: EMHEADER1 (LC=4)
+                1, 0, 0, 0, // field_mutable: NEXTINT
                 7, 0, 0, 0, // field_mutable: key | padding (3 bytes)
-                0x050, 0x00, 2, 0, // field_mutable: PID | length
+                0x50, 0, 0, 0x40, // field_mutable: EMHEADER1 (LC=4)
+                2, 0, 0, 0, // field_mutable: NEXTINT
                 8, 0, 0, 0, // field_mutable: participant_key | padding (2 bytes)
-                1, 0, 0, 0, // field_mutable: Sentinel
-                0x062, 0x00, 2, 0, // field_mutable: PID | length
-                9, 0, 0, 0, // field_final: primitive | padding (2 bytes)
-                1, 0, 0, 0, // Sentinel
+                0x62, 0, 0, 0x40, // EMHEADER1 (LC=4)
+                2, 0, 0, 0, // NEXTINT
+                9, 0, // field_final: primitive
             ]
         );
     }
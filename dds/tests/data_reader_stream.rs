@@ -0,0 +1,154 @@
+mod utils;
+use dust_dds::{
+    dds_async::{
+        domain_participant_factory::DomainParticipantFactoryAsync,
+        wait_set::{ConditionAsync, WaitSetAsync},
+    },
+    infrastructure::{
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{
+            HistoryQosPolicy, HistoryQosPolicyKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+        },
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+    },
+    topic_definition::type_support::DdsType,
+};
+use futures::StreamExt;
+
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Debug, PartialEq, DdsType)]
+struct UserData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u8,
+}
+
+#[tokio::test]
+async fn data_reader_stream_yields_the_written_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactoryAsync::get_instance();
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .await
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .await
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .await
+        .unwrap();
+    let mut wait_set = WaitSetAsync::new();
+    wait_set
+        .attach_condition(ConditionAsync::StatusCondition(cond))
+        .await
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).await.unwrap();
+
+    let mut reader_stream = reader.into_stream();
+
+    writer
+        .write(&UserData { id: 1, value: 1 }, None)
+        .await
+        .unwrap();
+    let (data, sample_info) = reader_stream.next().await.unwrap();
+    assert_eq!(data, UserData { id: 1, value: 1 });
+    assert!(sample_info.valid_data);
+
+    // The samples written while the stream is waiting are yielded in order
+    let stream_task = tokio::spawn(async move {
+        let mut data_list = Vec::new();
+        while data_list.len() < 3 {
+            data_list.push(reader_stream.next().await.unwrap().0);
+        }
+        data_list
+    });
+    for value in 2..5 {
+        writer
+            .write(&UserData { id: 1, value }, None)
+            .await
+            .unwrap();
+    }
+
+    let data_list = stream_task.await.unwrap();
+    assert_eq!(
+        data_list,
+        vec![
+            UserData { id: 1, value: 2 },
+            UserData { id: 1, value: 3 },
+            UserData { id: 1, value: 4 },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn data_reader_stream_ends_when_the_reader_is_deleted() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactoryAsync::get_instance();
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+
+    let mut reader_stream = reader.into_stream();
+    let stream_task = tokio::spawn(async move { reader_stream.next().await });
+    tokio::task::yield_now().await;
+
+    participant.delete_contained_entities().await.unwrap();
+
+    assert!(stream_task.await.unwrap().is_none());
+}
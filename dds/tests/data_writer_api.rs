@@ -1,11 +1,23 @@
 use dust_dds::{
+    configuration::{
+        DustDdsConfigurationBuilder, FlowControl, FlowControlScope, TransportPriorityDscp,
+        WriterBatching,
+    },
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
         error::DdsError,
-        qos::{DataWriterQos, QosKind},
-        qos_policy::{HistoryQosPolicy, HistoryQosPolicyKind, Length, ResourceLimitsQosPolicy},
+        instance::InstanceHandle,
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{
+            HistoryOverflowQosPolicy, HistoryOverflowQosPolicyKind, HistoryQosPolicy,
+            HistoryQosPolicyKind, Length, PublishRateQosPolicy, ReliabilityQosPolicy,
+            ReliabilityQosPolicyKind, ResourceLimitsQosPolicy, TransportPriorityQosPolicy,
+        },
+        statistics::{DataWriterStatistics, InstanceSampleCount},
         status::NO_STATUS,
+        time::{Duration, DurationKind, Time},
     },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
     topic_definition::type_support::DdsType,
 };
 
@@ -19,6 +31,13 @@ struct KeyedData {
     value: u32,
 }
 
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct LargeData {
+    #[dust_dds(key)]
+    id: u8,
+    value: Vec<u8>,
+}
+
 #[test]
 fn data_writer_write_more_than_max_instances_should_fail() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -125,3 +144,754 @@ fn data_writer_write_more_than_max_samples_should_fail() {
     let result = data_writer.write(&data_instance3, None);
     assert_eq!(result, Err(DdsError::OutOfResources));
 }
+
+#[test]
+fn best_effort_data_writer_drop_oldest_write_more_than_max_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Infinite,
+        },
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Limited(2),
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Limited(1),
+        },
+        history_overflow: HistoryOverflowQosPolicy {
+            kind: HistoryOverflowQosPolicyKind::DropOldest,
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+    let data_instance1 = KeyedData { id: 1, value: 0 };
+    let data_instance2 = KeyedData { id: 2, value: 0 };
+    let data_instance3 = KeyedData { id: 3, value: 0 };
+    data_writer.write(&data_instance1, None).unwrap();
+    data_writer.write(&data_instance2, None).unwrap();
+    assert_eq!(data_writer.get_dropped_sample_count(), Ok(0));
+
+    data_writer.write(&data_instance3, None).unwrap();
+    data_writer.write(&data_instance1, None).unwrap();
+    assert_eq!(data_writer.get_dropped_sample_count(), Ok(2));
+}
+
+#[test]
+fn reliable_data_writer_drop_oldest_is_inconsistent() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_writer_qos = DataWriterQos {
+        history_overflow: HistoryOverflowQosPolicy {
+            kind: HistoryOverflowQosPolicyKind::DropOldest,
+        },
+        ..Default::default()
+    };
+    let result = publisher.create_datawriter::<KeyedData>(
+        &topic,
+        QosKind::Specific(data_writer_qos),
+        None,
+        NO_STATUS,
+    );
+    assert!(matches!(result, Err(DdsError::InconsistentPolicy)));
+}
+
+#[test]
+fn best_effort_data_writer_drops_samples_above_publish_rate() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Infinite,
+        },
+        publish_rate: PublishRateQosPolicy {
+            max_samples_per_second: Length::Limited(2),
+            max_bytes_per_second: Length::Unlimited,
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+    for id in 0..5 {
+        data_writer
+            .write(&KeyedData { id, value: 0 }, None)
+            .unwrap();
+    }
+
+    assert_eq!(data_writer.get_dropped_sample_count(), Ok(3));
+}
+
+#[test]
+fn best_effort_data_writer_drops_samples_above_publish_byte_rate() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    // Each serialized sample takes 12 bytes so only two samples fit in the rate
+    let data_writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Infinite,
+        },
+        publish_rate: PublishRateQosPolicy {
+            max_samples_per_second: Length::Unlimited,
+            max_bytes_per_second: Length::Limited(30),
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+    for id in 0..4 {
+        data_writer
+            .write(&KeyedData { id, value: 0 }, None)
+            .unwrap();
+    }
+
+    assert_eq!(data_writer.get_dropped_sample_count(), Ok(2));
+}
+
+#[test]
+fn reliable_data_writer_blocks_above_publish_rate() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(2, 0)),
+        },
+        publish_rate: PublishRateQosPolicy {
+            max_samples_per_second: Length::Limited(2),
+            max_bytes_per_second: Length::Unlimited,
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+    let start_time = std::time::Instant::now();
+    for id in 0..3 {
+        data_writer
+            .write(&KeyedData { id, value: 0 }, None)
+            .unwrap();
+    }
+
+    assert!(start_time.elapsed() >= std::time::Duration::from_millis(500));
+    assert_eq!(data_writer.get_dropped_sample_count(), Ok(0));
+}
+
+#[test]
+fn reliable_data_writer_above_publish_rate_times_out() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(0, 100_000_000)),
+        },
+        publish_rate: PublishRateQosPolicy {
+            max_samples_per_second: Length::Limited(2),
+            max_bytes_per_second: Length::Unlimited,
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+    data_writer
+        .write(&KeyedData { id: 1, value: 0 }, None)
+        .unwrap();
+    data_writer
+        .write(&KeyedData { id: 2, value: 0 }, None)
+        .unwrap();
+
+    assert_eq!(
+        data_writer.write(&KeyedData { id: 3, value: 0 }, None),
+        Err(DdsError::Timeout)
+    );
+}
+
+#[test]
+fn data_writer_dispose_w_handle_of_unregistered_instance_should_fail() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    data_writer
+        .write(&KeyedData { id: 1, value: 0 }, None)
+        .unwrap();
+
+    let unknown_handle = InstanceHandle::new([1; 16]);
+    assert_eq!(
+        data_writer.dispose_w_handle(unknown_handle),
+        Err(DdsError::BadParameter)
+    );
+    assert_eq!(
+        data_writer.unregister_instance_w_handle(unknown_handle),
+        Err(DdsError::BadParameter)
+    );
+}
+
+#[test]
+fn data_writer_max_instances_counts_only_registered_instances() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer_qos = DataWriterQos {
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Unlimited,
+            max_instances: Length::Limited(2),
+            max_samples_per_instance: Length::Unlimited,
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+    let data_instance1 = KeyedData { id: 1, value: 0 };
+    let data_instance2 = KeyedData { id: 2, value: 0 };
+    let data_instance3 = KeyedData { id: 3, value: 0 };
+
+    let handle1 = data_writer.register_instance(&data_instance1).unwrap();
+    assert!(handle1.is_some());
+    assert_eq!(data_writer.lookup_instance(&data_instance1), Ok(handle1));
+    assert_eq!(
+        data_writer.register_instance(&data_instance1).unwrap(),
+        handle1
+    );
+    data_writer.register_instance(&data_instance2).unwrap();
+    assert_eq!(
+        data_writer.register_instance(&data_instance3),
+        Err(DdsError::OutOfResources)
+    );
+
+    data_writer
+        .unregister_instance(&data_instance1, handle1)
+        .unwrap();
+    assert!(data_writer.register_instance(&data_instance3).is_ok());
+    assert_eq!(
+        data_writer.write(&data_instance1, None),
+        Err(DdsError::OutOfResources)
+    );
+}
+
+#[test]
+fn data_writer_unregister_instance_with_wrong_handle_should_fail() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_instance1 = KeyedData { id: 1, value: 0 };
+    let data_instance2 = KeyedData { id: 2, value: 0 };
+    data_writer
+        .register_instance_w_timestamp(&data_instance1, Time::new(1, 0))
+        .unwrap();
+    let handle2 = data_writer.register_instance(&data_instance2).unwrap();
+
+    assert!(matches!(
+        data_writer.unregister_instance(&data_instance1, handle2),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert_eq!(
+        data_writer.unregister_instance(&data_instance1, Some(InstanceHandle::new([1; 16]))),
+        Err(DdsError::BadParameter)
+    );
+    data_writer
+        .unregister_instance_w_timestamp(&data_instance1, None, Time::new(2, 0))
+        .unwrap();
+    assert_eq!(
+        data_writer.unregister_instance(&data_instance1, None),
+        Err(DdsError::BadParameter)
+    );
+}
+
+#[test]
+fn data_writer_get_key_value_fills_only_key_fields() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data = KeyedData { id: 3, value: 10 };
+    data_writer.write(&data, None).unwrap();
+
+    let handle = data_writer.lookup_instance(&data).unwrap().unwrap();
+    let mut key_holder = KeyedData { id: 0, value: 20 };
+    data_writer.get_key_value(&mut key_holder, handle).unwrap();
+
+    assert_eq!(key_holder, KeyedData { id: 3, value: 20 });
+    assert_eq!(
+        data_writer.get_key_value(&mut key_holder, InstanceHandle::new([1; 16])),
+        Err(DdsError::BadParameter)
+    );
+}
+
+#[test]
+fn data_writer_statistics_count_instances_and_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_writer_qos = DataWriterQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepLast(2),
+        },
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Limited(10),
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Limited(5),
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+    let data_instance1 = KeyedData { id: 1, value: 0 };
+    let data_instance2 = KeyedData { id: 2, value: 0 };
+    let data_instance3 = KeyedData { id: 3, value: 0 };
+    for value in 0..3 {
+        data_writer
+            .write(&KeyedData { id: 1, value }, None)
+            .unwrap();
+    }
+    data_writer.write(&data_instance2, None).unwrap();
+    data_writer.write(&data_instance3, None).unwrap();
+    data_writer.dispose(&data_instance2, None).unwrap();
+    data_writer
+        .unregister_instance(&data_instance3, None)
+        .unwrap();
+
+    let handle = |data| data_writer.lookup_instance(data).unwrap().unwrap();
+    let mut samples_per_instance = vec![
+        InstanceSampleCount {
+            instance_handle: handle(&data_instance1),
+            sample_count: 2,
+        },
+        InstanceSampleCount {
+            instance_handle: handle(&data_instance2),
+            sample_count: 1,
+        },
+        InstanceSampleCount {
+            instance_handle: handle(&data_instance3),
+            sample_count: 1,
+        },
+    ];
+    samples_per_instance.sort_by_key(|s| s.instance_handle);
+    assert_eq!(
+        data_writer.get_statistics(),
+        Ok(DataWriterStatistics {
+            instance_count: 3,
+            alive_instance_count: 1,
+            disposed_instance_count: 1,
+            unregistered_instance_count: 1,
+            samples_per_instance,
+            history_sample_count: 4,
+            history_max_samples: Length::Limited(10),
+        })
+    );
+}
+
+#[test]
+fn data_writer_timestamp_ahead_of_participant_clock_should_fail() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .source_timestamp_tolerance(Some(std::time::Duration::from_secs(10)))
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data = KeyedData { id: 1, value: 0 };
+
+    let now = participant.get_current_time().unwrap();
+    let within_tolerance = Time::new(now.sec() + 1, now.nanosec());
+    let beyond_tolerance = Time::new(now.sec() + 3600, now.nanosec());
+    data_writer
+        .write_w_timestamp(&data, None, within_tolerance)
+        .unwrap();
+    data_writer
+        .write_w_timestamp(&data, None, Time::new(1, 0))
+        .unwrap();
+    assert_eq!(
+        data_writer.write_w_timestamp(&data, None, beyond_tolerance),
+        Err(DdsError::BadParameter)
+    );
+    assert_eq!(
+        data_writer.dispose_w_timestamp(&data, None, beyond_tolerance),
+        Err(DdsError::BadParameter)
+    );
+    assert_eq!(
+        data_writer.unregister_instance_w_timestamp(&data, None, beyond_tolerance),
+        Err(DdsError::BadParameter)
+    );
+}
+
+#[test]
+fn batched_samples_are_sent_when_flushed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .writer_batching(Some(WriterBatching {
+            max_batch_size: 1_000_000,
+            flush_period: std::time::Duration::from_secs(3600),
+        }))
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let data_reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while data_writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    for id in 0..3 {
+        data_writer
+            .write(
+                &KeyedData {
+                    id,
+                    value: id as u32,
+                },
+                None,
+            )
+            .unwrap();
+    }
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    assert_eq!(
+        data_reader
+            .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .err(),
+        Some(DdsError::NoData)
+    );
+
+    data_writer.flush().unwrap();
+    data_writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    let samples = data_reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let values: Vec<_> = samples.iter().map(|s| s.data().unwrap().value).collect();
+    assert_eq!(values, vec![0, 1, 2]);
+}
+
+#[test]
+fn flow_controlled_writer_does_not_exceed_the_configured_rate() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .flow_control(Some(FlowControl {
+            max_bytes_per_second: 20_000,
+            scope: FlowControlScope::Participant,
+        }))
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<LargeData>("MyTopic", "LargeData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let data_reader = subscriber
+        .create_datareader::<LargeData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while data_writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    // The first two samples use the bytes of the first second and the rest are sent at the rate
+    let write_time = std::time::Instant::now();
+    for id in 0..5 {
+        data_writer
+            .write(
+                &LargeData {
+                    id,
+                    value: vec![id; 10_000],
+                },
+                None,
+            )
+            .unwrap();
+    }
+    data_writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    assert!(write_time.elapsed() >= std::time::Duration::from_millis(800));
+
+    let samples = data_reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let ids: Vec<_> = samples.iter().map(|s| s.data().unwrap().id).collect();
+    assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn writer_with_transport_priority_mapped_to_dscp_delivers_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .transport_priority_dscp_mapping(vec![
+            TransportPriorityDscp {
+                min_transport_priority: 1,
+                dscp: 10,
+            },
+            TransportPriorityDscp {
+                min_transport_priority: 10,
+                dscp: 46,
+            },
+        ])
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        transport_priority: TransportPriorityQosPolicy { value: 20 },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let data_reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while data_writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    let data = KeyedData { id: 1, value: 10 };
+    data_writer.write(&data, None).unwrap();
+    data_writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = data_reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data().unwrap(), data);
+}
@@ -1,19 +1,37 @@
 use std::time::Instant;
 
 use dust_dds::{
+    builtin_topics::DCPS_PARTICIPANT,
+    configuration::DustDdsConfigurationBuilder,
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        qos::{DataReaderQos, DataWriterQos, PublisherQos, QosKind, SubscriberQos},
+        error::{DdsError, DdsResult},
+        qos::{
+            DataReaderQos, DataWriterQos, DomainParticipantQos, PublisherQos, QosKind,
+            SubscriberQos,
+        },
         qos_policy::{
-            DataRepresentationQosPolicy, OwnershipQosPolicy, OwnershipQosPolicyKind,
-            PartitionQosPolicy, UserDataQosPolicy, XCDR2_DATA_REPRESENTATION,
+            DataRepresentationQosPolicy, DeadlineQosPolicy, EntityFactoryQosPolicy,
+            EntityNameQosPolicy, OwnershipQosPolicy, OwnershipQosPolicyKind, PartitionQosPolicy,
+            Property, PropertyQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            TopicDataQosPolicy, UserDataQosPolicy, XCDR2_DATA_REPRESENTATION,
             XCDR_DATA_REPRESENTATION,
         },
         status::{StatusKind, NO_STATUS},
-        time::Duration,
+        time::{Duration, DurationKind},
         wait_set::{Condition, WaitSet},
     },
-    topic_definition::type_support::DdsType,
+    rtps::{
+        messages::{
+            overall_structure::{RtpsMessageHeader, RtpsMessageWrite},
+            submessage_elements::{Data, Parameter, ParameterList},
+            submessages::data::DataSubmessage,
+        },
+        types::{PROTOCOLVERSION, VENDOR_ID_S2E},
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::{DdsDeserialize, DdsType},
+    transport::types::{EntityId, BUILT_IN_READER_WITH_KEY, BUILT_IN_WRITER_WITH_KEY},
 };
 
 mod utils;
@@ -22,6 +40,15 @@ use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
 #[derive(DdsType)]
 struct UserType(#[dust_dds(key)] i32);
 
+const ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER: EntityId =
+    EntityId::new([0x00, 0x01, 0x00], BUILT_IN_WRITER_WITH_KEY);
+const ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER: EntityId =
+    EntityId::new([0x00, 0x01, 0x00], BUILT_IN_READER_WITH_KEY);
+const ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER: EntityId =
+    EntityId::new([0, 0, 0x03], BUILT_IN_WRITER_WITH_KEY);
+const ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR: EntityId =
+    EntityId::new([0, 0, 0x03], BUILT_IN_READER_WITH_KEY);
+
 #[test]
 fn writer_discovers_reader_in_same_participant() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -314,6 +341,194 @@ fn two_participants_should_get_subscription_matched() {
     assert_eq!(data_writer.get_matched_subscriptions().unwrap().len(), 1);
 }
 
+#[test]
+fn entity_names_are_propagated_in_discovery() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant_qos = DomainParticipantQos {
+        entity_name: EntityNameQosPolicy {
+            name: String::from("Publishing participant"),
+        },
+        ..Default::default()
+    };
+    let dp1 = domain_participant_factory
+        .create_participant(
+            domain_id,
+            QosKind::Specific(participant_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let topic1 = dp1
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = dp1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        entity_name: EntityNameQosPolicy {
+            name: String::from("Writer"),
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic1, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let dp2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = dp2
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = dp2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        entity_name: EntityNameQosPolicy {
+            name: String::from("Reader"),
+        },
+        ..Default::default()
+    };
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic2, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = data_reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let matched_publications = data_reader.get_matched_publications().unwrap();
+    let matched_publication_data = data_reader
+        .get_matched_publication_data(matched_publications[0])
+        .unwrap();
+    assert_eq!(matched_publication_data.entity_name().name, "Writer");
+
+    let discovered_participant_names: Vec<String> = dp2
+        .get_discovered_participants()
+        .unwrap()
+        .iter()
+        .map(|&handle| {
+            dp2.get_discovered_participant_data(handle)
+                .unwrap()
+                .entity_name()
+                .name
+                .clone()
+        })
+        .collect();
+    assert!(discovered_participant_names.contains(&String::from("Publishing participant")));
+
+    let cond = data_writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let matched_subscriptions = data_writer.get_matched_subscriptions().unwrap();
+    let matched_subscription_data = data_writer
+        .get_matched_subscription_data(matched_subscriptions[0])
+        .unwrap();
+    assert_eq!(matched_subscription_data.entity_name().name, "Reader");
+}
+
+#[test]
+fn propagated_properties_are_sent_in_discovery() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    let property = PropertyQosPolicy {
+        value: vec![
+            Property {
+                name: String::from("deployment.site"),
+                value: String::from("lab"),
+                propagate: true,
+            },
+            Property {
+                name: String::from("local.secret"),
+                value: String::from("hidden"),
+                propagate: false,
+            },
+        ],
+        binary_value: vec![],
+    };
+    let propagated_property = PropertyQosPolicy {
+        value: vec![Property {
+            name: String::from("deployment.site"),
+            value: String::from("lab"),
+            propagate: true,
+        }],
+        binary_value: vec![],
+    };
+
+    let participant_qos = DomainParticipantQos {
+        property: property.clone(),
+        ..Default::default()
+    };
+    let dp1 = domain_participant_factory
+        .create_participant(
+            domain_id,
+            QosKind::Specific(participant_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let topic1 = dp1
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = dp1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        property: property.clone(),
+        ..Default::default()
+    };
+    let _data_writer = publisher
+        .create_datawriter::<UserType>(&topic1, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let dp2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = dp2
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = dp2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let cond = data_reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let matched_publications = data_reader.get_matched_publications().unwrap();
+    let matched_publication_data = data_reader
+        .get_matched_publication_data(matched_publications[0])
+        .unwrap();
+    assert_eq!(matched_publication_data.property(), &propagated_property);
+
+    let discovered_participant_data = dp2
+        .get_discovered_participant_data(dp1.get_instance_handle())
+        .unwrap();
+    assert_eq!(discovered_participant_data.property(), &propagated_property);
+}
+
 #[test]
 fn participant_records_discovered_topics() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -362,6 +577,59 @@ fn participant_records_discovered_topics() {
     assert!(discovered_topic_names.contains(&"Topic 2".to_string()));
 }
 
+#[test]
+fn updated_topic_qos_is_announced() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant1
+        .create_topic::<UserType>(
+            "UpdatedTopic",
+            "UserType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    participant2
+        .find_topic::<UserType>("UpdatedTopic", Duration::new(10, 0))
+        .unwrap();
+
+    let topic_data = TopicDataQosPolicy {
+        value: vec![1, 2, 3],
+    };
+    let mut qos = topic.get_qos().unwrap();
+    qos.topic_data = topic_data.clone();
+    topic.set_qos(QosKind::Specific(qos)).unwrap();
+
+    let topic_handle = topic.get_instance_handle();
+    let start_time = Instant::now();
+    while start_time.elapsed() < std::time::Duration::from_secs(10) {
+        if participant2
+            .get_discovered_topic_data(topic_handle)
+            .is_ok_and(|data| data.topic_data() == &topic_data)
+        {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    assert_eq!(
+        participant2
+            .get_discovered_topic_data(topic_handle)
+            .unwrap()
+            .topic_data(),
+        &topic_data
+    );
+}
+
 #[test]
 fn participant_announces_updated_qos() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -477,6 +745,162 @@ fn publisher_and_subscriber_different_partition_not_matched() {
     assert!(wait_set.wait(Duration::new(5, 0)).is_err());
 }
 
+#[test]
+fn subscriber_partition_change_matches_publisher() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant1 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic1 = participant1
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher_qos = PublisherQos {
+        partition: PartitionQosPolicy {
+            name: vec!["A".to_string()],
+        },
+        ..Default::default()
+    };
+    let publisher = participant1
+        .create_publisher(QosKind::Specific(publisher_qos), None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic2 = participant2
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber_qos = SubscriberQos {
+        partition: PartitionQosPolicy {
+            name: vec!["B".to_string()],
+        },
+        ..Default::default()
+    };
+    let subscriber = participant2
+        .create_subscriber(QosKind::Specific(subscriber_qos), None, NO_STATUS)
+        .unwrap();
+    let _data_reader = subscriber
+        .create_datareader::<UserType>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let start_time = Instant::now();
+    while participant1.get_discovered_participants().unwrap().len() < 2 {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    assert!(data_writer.get_matched_subscriptions().unwrap().is_empty());
+
+    let subscriber_qos = SubscriberQos {
+        partition: PartitionQosPolicy {
+            name: vec!["A".to_string()],
+        },
+        ..Default::default()
+    };
+    subscriber
+        .set_qos(QosKind::Specific(subscriber_qos))
+        .unwrap();
+
+    let start_time = Instant::now();
+    while data_writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn reader_deadline_change_unmatches_incompatible_writer() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant1 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic1 = participant1
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic2 = participant2
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let start_time = Instant::now();
+    while data_writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    // A change of a QoS policy which keeps the endpoints compatible does not count as a new match
+    let reader_qos = DataReaderQos {
+        user_data: UserDataQosPolicy {
+            value: vec![1, 2, 3],
+        },
+        ..Default::default()
+    };
+    data_reader
+        .set_qos(QosKind::Specific(reader_qos.clone()))
+        .unwrap();
+    let subscription_handle = data_writer.get_matched_subscriptions().unwrap()[0];
+    let start_time = Instant::now();
+    while data_writer
+        .get_matched_subscription_data(subscription_handle)
+        .unwrap()
+        .user_data()
+        .value
+        != vec![1, 2, 3]
+    {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    assert_eq!(
+        data_writer
+            .get_publication_matched_status()
+            .unwrap()
+            .total_count,
+        1
+    );
+
+    // The writer offers an infinite deadline which does not satisfy the one requested now
+    let reader_qos = DataReaderQos {
+        deadline: DeadlineQosPolicy {
+            period: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..reader_qos
+    };
+    data_reader.set_qos(QosKind::Specific(reader_qos)).unwrap();
+
+    let start_time = Instant::now();
+    while !data_writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    assert_eq!(
+        data_writer
+            .get_offered_incompatible_qos_status()
+            .unwrap()
+            .total_count,
+        1
+    );
+}
+
 #[test]
 fn publisher_and_subscriber_regex_partition_is_matched() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -957,28 +1381,400 @@ fn reader_requesting_exclusive_ownership_should_not_match_writer_offering_shared
 }
 
 #[test]
-#[ignore]
 fn participant_removed_after_lease_duration() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
-    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant1 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
 
-    let participant1 = domain_participant_factory
+    // The second participant stops announcing itself long before its lease expires
+    let domain_participant_factory2 = DomainParticipantFactory::new();
+    domain_participant_factory2
+        .set_configuration(
+            DustDdsConfigurationBuilder::new()
+                .participant_announcement_interval(std::time::Duration::from_secs(60))
+                .participant_lease_duration(std::time::Duration::from_secs(1))
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    let participant2 = domain_participant_factory2
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
-    let participant2 = domain_participant_factory
+    let topic1 = participant1
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = participant1
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader::<UserType>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = participant2
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter::<UserType>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let cond = data_writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    let start_time = Instant::now();
+    while data_reader.get_matched_publications().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    // The writer becomes alive for the reader with its first sample
+    let cond = data_reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::LivelinessChanged])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    data_writer.write(&UserType(1), None).unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(
+        data_reader
+            .get_liveliness_changed_status()
+            .unwrap()
+            .alive_count,
+        1
+    );
+
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(
+        data_reader
+            .get_liveliness_changed_status()
+            .unwrap()
+            .alive_count,
+        0
+    );
+    assert_eq!(
+        data_reader
+            .get_subscription_matched_status()
+            .unwrap()
+            .current_count,
+        0
+    );
+    assert_eq!(participant1.get_discovered_participants().unwrap().len(), 1);
+}
+
+#[test]
+fn disabled_writer_is_matched_once_enabled() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant1 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = DomainParticipantFactory::get_instance()
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
-    std::thread::sleep(std::time::Duration::from_secs(5));
+    let topic1 = participant1
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher_qos = PublisherQos {
+        entity_factory: EntityFactoryQosPolicy {
+            autoenable_created_entities: false,
+        },
+        ..Default::default()
+    };
+    let publisher = participant1
+        .create_publisher(QosKind::Specific(publisher_qos), None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
 
-    domain_participant_factory
-        .delete_participant(&participant2)
+    let topic2 = participant2
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader_qos = DataReaderQos {
+        representation: DataRepresentationQosPolicy {
+            value: vec![XCDR2_DATA_REPRESENTATION],
+        },
+        ..Default::default()
+    };
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic2, QosKind::Specific(data_reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let start_time = Instant::now();
+    while participant1.get_discovered_participants().unwrap().len() < 2 {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    assert!(data_reader.get_matched_publications().unwrap().is_empty());
+
+    let data_writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(0, 100)),
+        },
+        representation: DataRepresentationQosPolicy {
+            value: vec![XCDR2_DATA_REPRESENTATION],
+        },
+        ..Default::default()
+    };
+    data_writer
+        .set_qos(QosKind::Specific(data_writer_qos))
         .unwrap();
+    data_writer.enable().unwrap();
 
-    std::thread::sleep(std::time::Duration::from_secs(110));
+    let start_time = Instant::now();
+    while data_reader.get_matched_publications().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    assert_eq!(
+        data_writer.set_qos(QosKind::Default),
+        Err(DdsError::ImmutablePolicy)
+    );
+}
+
+struct SerializedParticipantData<'a>(&'a [u8]);
+impl<'de> DdsDeserialize<'de> for SerializedParticipantData<'de> {
+    fn deserialize_data(serialized_data: &'de [u8]) -> DdsResult<Self> {
+        Ok(Self(serialized_data))
+    }
+}
+
+impl SerializedParticipantData<'_> {
+    fn metatraffic_unicast_locator_port(&self) -> u16 {
+        const PID_METATRAFFIC_UNICAST_LOCATOR: i16 = 0x0032;
+        let mut parameters = &self.0[4..];
+        loop {
+            let pid = i16::from_le_bytes([parameters[0], parameters[1]]);
+            let length = u16::from_le_bytes([parameters[2], parameters[3]]) as usize;
+            if pid == PID_METATRAFFIC_UNICAST_LOCATOR {
+                return u32::from_le_bytes(parameters[8..12].try_into().unwrap()) as u16;
+            }
+            parameters = &parameters[4 + length..];
+        }
+    }
+}
+
+fn mock_participant_message(guid_prefix: [u8; 12], data_submessage: DataSubmessage) -> Vec<u8> {
+    RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+        &[Box::new(data_submessage)],
+    )
+    .buffer()
+    .to_vec()
+}
+
+// Announcement of a participant which has only the builtin publications announcer
+fn mock_participant_announcement(guid_prefix: [u8; 12], locator_port: u16) -> Vec<u8> {
+    const BUILTIN_ENDPOINT_PUBLICATIONS_ANNOUNCER: u32 = 1 << 2;
+    let serialized_participant_data = [
+        &[
+            0x00, 0x03, 0x00, 0x00, // PL_CDR_LE
+            0x15, 0x00, 4, 0x00, // PID_PROTOCOL_VERSION, Length
+            0x02, 0x04, 0x00, 0x00, // ProtocolVersion
+            0x16, 0x00, 4, 0x00, // PID_VENDORID, Length
+            73, 74, 0x00, 0x00, // VendorId
+            0x58, 0x00, 4, 0x00, // PID_BUILTIN_ENDPOINT_SET, Length
+        ],
+        BUILTIN_ENDPOINT_PUBLICATIONS_ANNOUNCER
+            .to_le_bytes()
+            .as_slice(),
+        &[
+            0x50, 0x00, 16, 0x00, // PID_PARTICIPANT_GUID, Length
+        ],
+        &guid_prefix,
+        &[
+            0, 0, 1, 0xc1, // EntityId
+            0x32, 0x00, 24, 0x00, // PID_METATRAFFIC_UNICAST_LOCATOR, Length
+            1, 0, 0, 0, // locator kind
+        ],
+        &(locator_port as u32).to_le_bytes(),
+        &[
+            0, 0, 0, 0, // locator address
+            0, 0, 0, 0, // locator address
+            0, 0, 0, 0, // locator address
+            127, 0, 0, 1, // locator address
+            0x01, 0x00, 0x00, 0x00, // PID_SENTINEL, length
+        ],
+    ]
+    .concat();
+    mock_participant_message(
+        guid_prefix,
+        DataSubmessage::new(
+            false,
+            true,
+            false,
+            false,
+            ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+            ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+            1,
+            ParameterList::empty(),
+            Data::new(serialized_participant_data.into()),
+        ),
+    )
+}
+
+// Announcement of a writer of "UserType" on "topic_name"
+fn mock_writer_announcement(writer_guid: [u8; 16]) -> Vec<u8> {
+    let serialized_writer_data = [
+        &[
+            0x00, 0x03, 0x00, 0x00, // PL_CDR_LE
+            0x5a, 0x00, 16, 0x00, // PID_ENDPOINT_GUID, Length
+        ],
+        writer_guid.as_slice(),
+        &[
+            0x05, 0x00, 16, 0x00, // PID_TOPIC_NAME, Length
+            11, 0, 0, 0, // String length
+        ],
+        b"topic_name\0\0",
+        &[
+            0x07, 0x00, 16, 0x00, // PID_TYPE_NAME, Length
+            9, 0, 0, 0, // String length
+        ],
+        b"UserType\0\0\0\0",
+        &[
+            0x01, 0x00, 0x00, 0x00, // PID_SENTINEL, length
+        ],
+    ]
+    .concat();
+    mock_participant_message(
+        writer_guid[..12].try_into().unwrap(),
+        DataSubmessage::new(
+            false,
+            true,
+            false,
+            false,
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+            1,
+            ParameterList::empty(),
+            Data::new(serialized_writer_data.into()),
+        ),
+    )
+}
+
+// Unregistration of the writer identified by the key hash, without it being disposed
+fn mock_writer_unregister(writer_guid: [u8; 16]) -> Vec<u8> {
+    const PID_KEY_HASH: i16 = 0x0070;
+    const PID_STATUS_INFO: i16 = 0x0071;
+    let inline_qos = ParameterList::new(vec![
+        Parameter::new(PID_STATUS_INFO, [0, 0, 0, 2].into()),
+        Parameter::new(PID_KEY_HASH, writer_guid.to_vec().into()),
+    ]);
+    mock_participant_message(
+        writer_guid[..12].try_into().unwrap(),
+        DataSubmessage::new(
+            true,
+            false,
+            false,
+            false,
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+            2,
+            inline_qos,
+            Data::default(),
+        ),
+    )
+}
+
+#[test]
+fn unregistered_remote_writer_is_unmatched_from_reader() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let mock_participant_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mock_participant_port = mock_participant_socket.local_addr().unwrap().port();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
 
-    let discovered_participant = participant1.get_discovered_participants().unwrap();
+    let participant_reader = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<SerializedParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+    let start_time = Instant::now();
+    while participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .is_err()
+    {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+    }
+    let metatraffic_port = participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .metatraffic_unicast_locator_port();
+
+    let mock_participant_guid_prefix = [95; 12];
+    let mock_writer_guid = [
+        mock_participant_guid_prefix.as_slice(),
+        &[0, 0, 1, 0x02], // EntityId
+    ]
+    .concat()
+    .try_into()
+    .unwrap();
+    mock_participant_socket
+        .send_to(
+            &mock_participant_announcement(mock_participant_guid_prefix, mock_participant_port),
+            ("127.0.0.1", metatraffic_port),
+        )
+        .unwrap();
+    let start_time = Instant::now();
+    while participant.get_discovered_participants().unwrap().len() < 2 {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
 
-    assert_eq!(discovered_participant.len(), 1);
+    mock_participant_socket
+        .send_to(
+            &mock_writer_announcement(mock_writer_guid),
+            ("127.0.0.1", metatraffic_port),
+        )
+        .unwrap();
+    let start_time = Instant::now();
+    while data_reader.get_matched_publications().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    mock_participant_socket
+        .send_to(
+            &mock_writer_unregister(mock_writer_guid),
+            ("127.0.0.1", metatraffic_port),
+        )
+        .unwrap();
+    let start_time = Instant::now();
+    while !data_reader.get_matched_publications().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(
+        data_reader
+            .get_subscription_matched_status()
+            .unwrap()
+            .current_count,
+        0
+    );
 }
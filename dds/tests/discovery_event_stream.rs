@@ -0,0 +1,107 @@
+mod utils;
+use dust_dds::{
+    dds_async::domain_participant_factory::DomainParticipantFactoryAsync,
+    infrastructure::{discovery_event::DiscoveryEvent, qos::QosKind, status::NO_STATUS},
+    topic_definition::type_support::DdsType,
+};
+
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Debug, PartialEq, DdsType)]
+struct UserData {
+    #[dust_dds(key)]
+    id: u8,
+    value: Vec<u8>,
+}
+
+#[tokio::test]
+async fn discovered_participants_stream_reports_added_and_removed_participants() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactoryAsync::get_instance();
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let mut participant_stream = participant.discovered_participants_stream().await.unwrap();
+
+    let other_participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let other_participant_handle = other_participant.get_instance_handle().await;
+
+    loop {
+        match participant_stream.next().await.unwrap() {
+            DiscoveryEvent::Added { handle, data } if handle == other_participant_handle => {
+                assert_eq!(data.key().value, <[u8; 16]>::from(handle));
+                break;
+            }
+            DiscoveryEvent::Removed { handle } => {
+                assert_ne!(handle, other_participant_handle)
+            }
+            _ => (),
+        }
+    }
+
+    participant_factory
+        .delete_participant(&other_participant)
+        .await
+        .unwrap();
+
+    loop {
+        match participant_stream.next().await.unwrap() {
+            DiscoveryEvent::Removed { handle } if handle == other_participant_handle => break,
+            DiscoveryEvent::Added { handle, .. } | DiscoveryEvent::Updated { handle, .. } => {
+                assert_ne!(handle, other_participant_handle)
+            }
+            _ => (),
+        }
+    }
+}
+
+#[tokio::test]
+async fn discovered_topics_stream_reports_added_topics() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactoryAsync::get_instance();
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let mut topic_stream = participant.discovered_topics_stream().await.unwrap();
+
+    let other_participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    other_participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+
+    loop {
+        if let DiscoveryEvent::Added { data, .. } = topic_stream.next().await.unwrap() {
+            if data.name() == "MyTopic" {
+                assert_eq!(data.get_type_name(), "UserData");
+                break;
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn discovery_stream_ends_when_participant_is_deleted() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactoryAsync::get_instance();
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let mut participant_stream = participant.discovered_participants_stream().await.unwrap();
+
+    participant_factory
+        .delete_participant(&participant)
+        .await
+        .unwrap();
+
+    while participant_stream.next().await.is_some() {}
+}
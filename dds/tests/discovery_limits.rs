@@ -0,0 +1,154 @@
+use dust_dds::{
+    configuration::{DustDdsConfigurationBuilder, WriterBatching},
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        qos::QosKind,
+        status::{StatusKind, NO_STATUS},
+        time::Duration,
+        wait_set::{Condition, WaitSet},
+    },
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u8,
+}
+
+#[test]
+fn endpoints_above_discovered_endpoint_limit_are_not_matched() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .max_discovered_endpoints(Some(2))
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic1 = participant
+        .create_topic::<KeyedData>("Topic1", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let _writer1 = publisher
+        .create_datawriter::<KeyedData>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader1 = subscriber
+        .create_datareader::<KeyedData>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader1.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let topic2 = participant
+        .create_topic::<KeyedData>("Topic2", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let _writer2 = publisher
+        .create_datawriter::<KeyedData>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader2 = subscriber
+        .create_datareader::<KeyedData>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader2.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    assert!(wait_set.wait(Duration::new(2, 0)).is_err());
+    assert_eq!(reader2.get_matched_publications().unwrap(), vec![]);
+}
+
+#[test]
+fn batched_endpoint_announcements_are_discovered() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .sedp_batching(Some(WriterBatching {
+            max_batch_size: 64 * 1024,
+            flush_period: std::time::Duration::from_millis(100),
+        }))
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let mut writer_list = Vec::new();
+    let mut reader_list = Vec::new();
+    for i in 0..20 {
+        let topic_name = format!("Topic{i}");
+        let topic1 = participant1
+            .create_topic::<KeyedData>(&topic_name, "KeyedData", QosKind::Default, None, NO_STATUS)
+            .unwrap();
+        writer_list.push(
+            publisher
+                .create_datawriter::<KeyedData>(&topic1, QosKind::Default, None, NO_STATUS)
+                .unwrap(),
+        );
+        let topic2 = participant2
+            .create_topic::<KeyedData>(&topic_name, "KeyedData", QosKind::Default, None, NO_STATUS)
+            .unwrap();
+        reader_list.push(
+            subscriber
+                .create_datareader::<KeyedData>(&topic2, QosKind::Default, None, NO_STATUS)
+                .unwrap(),
+        );
+    }
+
+    let start_time = std::time::Instant::now();
+    while reader_list
+        .iter()
+        .any(|r| r.get_matched_publications().unwrap().is_empty())
+    {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn zero_sedp_batch_size_is_rejected() {
+    assert!(DustDdsConfigurationBuilder::new()
+        .sedp_batching(Some(WriterBatching {
+            max_batch_size: 0,
+            flush_period: std::time::Duration::from_millis(100),
+        }))
+        .build()
+        .is_err());
+}
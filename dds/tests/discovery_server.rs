@@ -0,0 +1,165 @@
+use dust_dds::{
+    configuration::{DiscoveryMode, DustDdsConfigurationBuilder},
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u32,
+}
+
+#[test]
+fn discovery_servers_are_validated() {
+    assert!(DustDdsConfigurationBuilder::new()
+        .discovery_mode(DiscoveryMode::Client)
+        .build()
+        .is_err());
+    assert!(DustDdsConfigurationBuilder::new()
+        .discovery_servers(vec!["127.0.0.1:7410".to_string()])
+        .build()
+        .is_err());
+    assert!(DustDdsConfigurationBuilder::new()
+        .discovery_mode(DiscoveryMode::Client)
+        .discovery_servers(vec!["127.0.0.1:port".to_string()])
+        .build()
+        .is_err());
+    assert!(DustDdsConfigurationBuilder::new()
+        .discovery_mode(DiscoveryMode::Client)
+        .discovery_servers(vec!["127.0.0.1:7410".to_string()])
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn discovery_clients_communicate_through_server() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    // The server is the first participant of the domain so it binds the well-known metatraffic
+    // unicast port of the participant 0
+    let server_address = format!("127.0.0.1:{}", 7400 + 250 * domain_id + 10);
+
+    let server_factory = DomainParticipantFactory::new();
+    server_factory
+        .set_configuration(
+            DustDdsConfigurationBuilder::multicast_free()
+                .discovery_mode(DiscoveryMode::Server)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    let _server_participant = server_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    // The clients only announce themselves to the server, so they discover each other and
+    // each other's endpoints through it
+    let client_configuration = DustDdsConfigurationBuilder::multicast_free()
+        .discovery_mode(DiscoveryMode::Client)
+        .discovery_servers(vec![server_address])
+        .build()
+        .unwrap();
+    let writer_factory = DomainParticipantFactory::new();
+    writer_factory
+        .set_configuration(client_configuration.clone())
+        .unwrap();
+    let reader_factory = DomainParticipantFactory::new();
+    reader_factory
+        .set_configuration(client_configuration)
+        .unwrap();
+    let writer_participant = writer_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_participant = reader_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let reliability = ReliabilityQosPolicy {
+        kind: ReliabilityQosPolicyKind::Reliable,
+        max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+    };
+    let writer_topic = writer_participant
+        .create_topic::<KeyedData>(
+            "KeyedDataTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer = writer_participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter(
+            &writer_topic,
+            QosKind::Specific(DataWriterQos {
+                reliability: reliability.clone(),
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader_topic = reader_participant
+        .create_topic::<KeyedData>(
+            "KeyedDataTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader = reader_participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader::<KeyedData>(
+            &reader_topic,
+            QosKind::Specific(DataReaderQos {
+                reliability,
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data = KeyedData { id: 1, value: 7 };
+    writer.write(&data, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data().unwrap(), data);
+    // Each client knows itself, the server and the other client
+    assert_eq!(
+        writer_participant
+            .get_discovered_participants()
+            .unwrap()
+            .len(),
+        3
+    );
+}
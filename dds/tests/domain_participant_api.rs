@@ -6,6 +6,7 @@ use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
         error::DdsError,
+        instance::EntityKind,
         qos::{
             DataReaderQos, DataWriterQos, DomainParticipantQos, PublisherQos, QosKind,
             SubscriberQos, TopicQos,
@@ -394,6 +395,97 @@ fn allowed_to_delete_topic_with_created_and_deleted_reader() {
     assert_eq!(participant.delete_topic(&reader_topic), Ok(()));
 }
 
+#[test]
+fn get_contained_entities() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<TestType>("MyTopic", "TestType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<TestType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<TestType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let contained_entities = participant.get_contained_entities().unwrap();
+
+    assert_eq!(contained_entities.len(), 5);
+    assert!(contained_entities.contains(&(topic.get_instance_handle(), EntityKind::Topic)));
+    assert!(contained_entities.contains(&(publisher.get_instance_handle(), EntityKind::Publisher)));
+    assert!(contained_entities.contains(&(writer.get_instance_handle(), EntityKind::DataWriter)));
+    assert!(
+        contained_entities.contains(&(subscriber.get_instance_handle(), EntityKind::Subscriber))
+    );
+    assert!(contained_entities.contains(&(reader.get_instance_handle(), EntityKind::DataReader)));
+    assert_eq!(
+        participant.contains_entity(reader.get_instance_handle()),
+        Ok(true)
+    );
+    assert_eq!(
+        participant.contains_entity(participant.get_instance_handle()),
+        Ok(false)
+    );
+}
+
+#[test]
+fn get_publishers_and_subscribers() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher1 = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher2 = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher_handles: Vec<_> = participant
+        .get_publishers()
+        .unwrap()
+        .iter()
+        .map(|p| p.get_instance_handle())
+        .collect();
+    let subscribers = participant.get_subscribers().unwrap();
+
+    assert_eq!(
+        publisher_handles,
+        vec![
+            publisher1.get_instance_handle(),
+            publisher2.get_instance_handle()
+        ]
+    );
+    assert_eq!(subscribers.len(), 1);
+    assert_eq!(
+        subscribers[0].get_instance_handle(),
+        subscriber.get_instance_handle()
+    );
+    assert_eq!(
+        subscribers[0].get_qos().unwrap(),
+        subscriber.get_qos().unwrap()
+    );
+
+    participant.delete_publisher(&publisher1).unwrap();
+    assert_eq!(participant.get_publishers().unwrap().len(), 1);
+}
+
 #[test]
 fn default_publisher_qos() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -739,6 +831,17 @@ fn get_discovery_data_from_builtin_reader() {
         &publication_samples[0].data().unwrap().user_data().value,
         &writer_user_data
     );
+
+    assert!(publication_samples[0]
+        .data()
+        .unwrap()
+        .type_information()
+        .is_some());
+    assert!(subscription_samples[0]
+        .data()
+        .unwrap()
+        .type_information()
+        .is_some());
 }
 
 #[test]
@@ -855,6 +958,86 @@ fn ignore_subscription() {
     assert!(wait_set.wait(Duration::new(2, 0)).is_err());
 }
 
+#[test]
+fn ignore_topic() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant1
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant2
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let _writer = publisher
+        .create_datawriter::<MyData>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(
+        reader
+            .get_subscription_matched_status()
+            .unwrap()
+            .current_count,
+        1
+    );
+
+    let remote_topic_handle = participant1
+        .get_discovered_topics()
+        .unwrap()
+        .into_iter()
+        .find(|&handle| {
+            handle == topic2.get_instance_handle()
+                && participant1
+                    .get_discovered_topic_data(handle)
+                    .unwrap()
+                    .name()
+                    == "MyTopic"
+        })
+        .unwrap();
+    participant1.ignore_topic(remote_topic_handle).unwrap();
+
+    // The endpoints of the ignored topic are unmatched and no longer discovered
+    assert_eq!(
+        reader
+            .get_subscription_matched_status()
+            .unwrap()
+            .current_count,
+        0
+    );
+    assert!(!participant1
+        .get_discovered_topics()
+        .unwrap()
+        .contains(&remote_topic_handle));
+    let _new_writer = publisher
+        .create_datawriter::<MyData>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    assert!(wait_set.wait(Duration::new(2, 0)).is_err());
+    assert!(reader.get_matched_publications().unwrap().is_empty());
+}
+
 #[test]
 #[ignore = "Test is flaky. Needs to be investigated"]
 fn ignore_participant() {
@@ -1,9 +1,22 @@
+use std::{future::Future, pin::Pin};
+
 use dust_dds::{
+    configuration::{ActorMailboxCapacities, DustDdsConfigurationBuilder, MailboxOverflowPolicy},
+    dds_async::runtime::Spawner,
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        qos::{DomainParticipantQos, QosKind},
-        qos_policy::UserDataQosPolicy,
-        status::NO_STATUS,
+        qos::{DataReaderQos, DataWriterQos, DomainParticipantQos, QosKind},
+        qos_policy::{
+            HistoryQosPolicy, HistoryQosPolicyKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            UserDataQosPolicy,
+        },
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+    },
+    subscription::{
+        data_reader::DataReader,
+        data_reader_listener::DataReaderListener,
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
     },
     topic_definition::type_support::DdsType,
 };
@@ -117,3 +130,470 @@ fn allowed_to_delete_participant_after_delete_contained_entities() {
         .delete_participant(&participant)
         .is_ok());
 }
+
+#[test]
+fn slow_listener_does_not_block_other_listeners() {
+    struct BlockingListener {
+        release: std::sync::mpsc::Receiver<()>,
+    }
+    impl DataReaderListener<'_> for BlockingListener {
+        type Foo = KeyedData;
+        fn on_data_available(&mut self, _the_reader: DataReader<KeyedData>) {
+            self.release.recv().ok();
+        }
+    }
+
+    struct DataAvailableListener {
+        sender: std::sync::mpsc::SyncSender<()>,
+    }
+    impl DataReaderListener<'_> for DataAvailableListener {
+        type Foo = KeyedData;
+        fn on_data_available(&mut self, _the_reader: DataReader<KeyedData>) {
+            self.sender.send(()).ok();
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .listener_threads(8)
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let (_release_sender, release_receiver) = std::sync::mpsc::channel();
+    let _blocked_reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Specific(reader_qos.clone()),
+            Some(Box::new(BlockingListener {
+                release: release_receiver,
+            })),
+            &[StatusKind::DataAvailable],
+        )
+        .unwrap();
+    let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+    let _reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Specific(reader_qos),
+            Some(Box::new(DataAvailableListener { sender })),
+            &[StatusKind::DataAvailable],
+        )
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while writer.get_matched_subscriptions().unwrap().len() < 2 {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
+
+    assert!(receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .is_ok());
+}
+
+#[test]
+fn listeners_are_invoked_on_listener_spawner() {
+    struct TokioSpawner(tokio::runtime::Handle);
+    impl Spawner for TokioSpawner {
+        fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+            self.0.spawn(task);
+        }
+    }
+
+    struct ThreadNameListener {
+        sender: std::sync::mpsc::SyncSender<Option<String>>,
+    }
+    impl DataReaderListener<'_> for ThreadNameListener {
+        type Foo = KeyedData;
+        fn on_data_available(&mut self, _the_reader: DataReader<KeyedData>) {
+            let thread_name = std::thread::current().name().map(String::from);
+            self.sender.try_send(thread_name).ok();
+        }
+    }
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("Listener Runtime")
+        .build()
+        .unwrap();
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .listener_spawner(TokioSpawner(runtime.handle().clone()))
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+    let _reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Specific(reader_qos),
+            Some(Box::new(ThreadNameListener { sender })),
+            &[StatusKind::DataAvailable],
+        )
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
+
+    assert_eq!(
+        receiver
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .unwrap()
+            .as_deref(),
+        Some("Listener Runtime")
+    );
+}
+
+#[test]
+fn independent_factories_have_own_default_participant_qos() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory1 = DomainParticipantFactory::new();
+    let domain_participant_factory2 = DomainParticipantFactory::new();
+
+    let qos = DomainParticipantQos {
+        user_data: UserDataQosPolicy {
+            value: vec![1, 2, 3],
+        },
+        ..Default::default()
+    };
+    domain_participant_factory1
+        .set_default_participant_qos(QosKind::Specific(qos.clone()))
+        .unwrap();
+
+    assert_eq!(
+        domain_participant_factory1
+            .get_default_participant_qos()
+            .unwrap(),
+        qos
+    );
+    assert_eq!(
+        domain_participant_factory2
+            .get_default_participant_qos()
+            .unwrap(),
+        DomainParticipantQos::default()
+    );
+
+    let participant = domain_participant_factory2
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    assert!(domain_participant_factory1
+        .delete_participant(&participant)
+        .is_err());
+    assert!(domain_participant_factory2
+        .delete_participant(&participant)
+        .is_ok());
+}
+
+#[test]
+fn participants_of_independent_factories_communicate() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory1 = DomainParticipantFactory::new();
+    let domain_participant_factory2 = DomainParticipantFactory::new();
+    domain_participant_factory2
+        .set_configuration(
+            DustDdsConfigurationBuilder::new()
+                .fragment_size(100)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+    let participant1 = domain_participant_factory1
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<KeyedData>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let participant2 = domain_participant_factory2
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let _reader = subscriber
+        .create_datareader::<KeyedData>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    // The configuration of one factory does not affect the singleton or the other factory
+    assert_eq!(
+        domain_participant_factory1
+            .get_configuration()
+            .unwrap()
+            .fragment_size(),
+        1344
+    );
+    assert_eq!(
+        domain_participant_factory2
+            .get_configuration()
+            .unwrap()
+            .fragment_size(),
+        100
+    );
+}
+
+#[test]
+fn participants_of_factory_with_thread_pool_communicate() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory =
+        DomainParticipantFactory::new_with_thread_pool(1, "Dust DDS Test Pool");
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<KeyedData>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic2, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    let data = KeyedData { id: 1, value: 10 };
+    writer.write(&data, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data().unwrap(), data);
+
+    participant1.delete_contained_entities().unwrap();
+    domain_participant_factory
+        .delete_participant(&participant1)
+        .unwrap();
+    participant2.delete_contained_entities().unwrap();
+    domain_participant_factory
+        .delete_participant(&participant2)
+        .unwrap();
+}
+
+#[test]
+fn zero_actor_mailbox_capacity_is_rejected() {
+    assert!(DustDdsConfigurationBuilder::new()
+        .actor_mailbox_capacities(ActorMailboxCapacities {
+            transport: Some(0),
+            ..Default::default()
+        })
+        .build()
+        .is_err());
+    assert!(DustDdsConfigurationBuilder::new()
+        .actor_mailbox_capacities(ActorMailboxCapacities {
+            participant: Some(1),
+            transport: Some(1),
+            listener: Some(1),
+        })
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn participants_with_bounded_mailboxes_communicate() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .actor_mailbox_capacities(ActorMailboxCapacities {
+            participant: Some(2),
+            transport: Some(2),
+            listener: Some(2),
+        })
+        .mailbox_overflow_policy(MailboxOverflowPolicy::Block)
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<KeyedData>(&topic1, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic2, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    for id in 0..20 {
+        writer.write(&KeyedData { id, value: id }, None).unwrap();
+    }
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(20, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 20);
+
+    participant1.delete_contained_entities().unwrap();
+    domain_participant_factory
+        .delete_participant(&participant1)
+        .unwrap();
+    participant2.delete_contained_entities().unwrap();
+    domain_participant_factory
+        .delete_participant(&participant2)
+        .unwrap();
+}
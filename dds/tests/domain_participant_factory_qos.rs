@@ -3,15 +3,26 @@ use dust_dds::{
     infrastructure::{
         error::DdsError,
         instance::HANDLE_NIL,
-        qos::{DomainParticipantFactoryQos, QosKind},
+        qos::{DomainParticipantFactoryQos, DomainParticipantQos, PublisherQos, QosKind},
         qos_policy::EntityFactoryQosPolicy,
         status::NO_STATUS,
     },
+    topic_definition::type_support::DdsType,
 };
 
+#[cfg(feature = "xml")]
+use dust_dds::infrastructure::qos_policy::{HistoryQosPolicyKind, ReliabilityQosPolicyKind};
+
 mod utils;
 use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
 
+#[derive(Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u8,
+}
+
 #[test]
 fn create_not_enabled_entities() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -41,3 +52,169 @@ fn create_not_enabled_entities() {
 
     assert_eq!(result, Err(DdsError::NotEnabled));
 }
+
+#[test]
+fn participant_enable_enables_the_entities_created_while_disabled() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    domain_participant_factory
+        .set_qos(QosKind::Specific(DomainParticipantFactoryQos {
+            entity_factory: EntityFactoryQosPolicy {
+                autoenable_created_entities: false,
+            },
+        }))
+        .unwrap();
+
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<KeyedData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data = KeyedData { id: 1, value: 1 };
+
+    assert_eq!(writer.write(&data, None), Err(DdsError::NotEnabled));
+    assert!(matches!(
+        writer.enable(),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert!(matches!(
+        publisher.enable(),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    participant.enable().unwrap();
+
+    assert_eq!(writer.write(&data, None), Ok(()));
+}
+
+#[test]
+fn entities_of_factory_without_autoenable_are_enabled_explicitly() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let participant_qos = DomainParticipantQos {
+        entity_factory: EntityFactoryQosPolicy {
+            autoenable_created_entities: false,
+        },
+        ..Default::default()
+    };
+    let participant = domain_participant_factory
+        .create_participant(
+            domain_id,
+            QosKind::Specific(participant_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher_qos = PublisherQos {
+        entity_factory: EntityFactoryQosPolicy {
+            autoenable_created_entities: false,
+        },
+        ..Default::default()
+    };
+    let publisher = participant
+        .create_publisher(QosKind::Specific(publisher_qos), None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<KeyedData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data = KeyedData { id: 1, value: 1 };
+
+    // Enabling the publisher leaves its writers disabled since its factory does not enable them
+    topic.enable().unwrap();
+    publisher.enable().unwrap();
+    assert_eq!(writer.write(&data, None), Err(DdsError::NotEnabled));
+
+    writer.enable().unwrap();
+    assert_eq!(writer.write(&data, None), Ok(()));
+}
+
+#[cfg(feature = "xml")]
+#[test]
+fn entities_are_created_with_the_qos_of_a_loaded_profile() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let document = r#"<dds xmlns="http://www.omg.org/dds/">
+            <qos_library name="MyLibrary">
+                <qos_profile name="MyProfile">
+                    <datawriter_qos>
+                        <reliability>
+                            <kind>BEST_EFFORT_RELIABILITY_QOS</kind>
+                        </reliability>
+                        <history>
+                            <kind>KEEP_LAST_HISTORY_QOS</kind>
+                            <depth>5</depth>
+                        </history>
+                    </datawriter_qos>
+                </qos_profile>
+            </qos_library>
+        </dds>"#;
+    let profile_path = std::env::temp_dir().join(format!("dust_dds_qos_profile_{}.xml", domain_id));
+    std::fs::write(&profile_path, document).unwrap();
+    domain_participant_factory
+        .load_qos_profiles_from_file(&profile_path)
+        .unwrap();
+    std::fs::remove_file(&profile_path).unwrap();
+
+    // An invalid document leaves the loaded profiles unchanged
+    assert!(matches!(
+        domain_participant_factory.load_qos_profiles("<dds><qos_library/></dds>"),
+        Err(DdsError::Error(_))
+    ));
+    assert_eq!(
+        domain_participant_factory.get_qos_from_profile("MyLibrary::OtherProfile"),
+        Err(DdsError::BadParameter)
+    );
+    let qos_profile = domain_participant_factory
+        .get_qos_from_profile("MyLibrary::MyProfile")
+        .unwrap();
+
+    let participant = domain_participant_factory
+        .create_participant(
+            domain_id,
+            QosKind::Specific(qos_profile.domain_participant_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Specific(qos_profile.topic_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(
+            QosKind::Specific(qos_profile.publisher_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<KeyedData>(
+            &topic,
+            QosKind::Specific(qos_profile.data_writer_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let writer_qos = writer.get_qos().unwrap();
+    assert_eq!(
+        writer_qos.reliability.kind,
+        ReliabilityQosPolicyKind::BestEffort
+    );
+    assert_eq!(writer_qos.history.kind, HistoryQosPolicyKind::KeepLast(5));
+}
@@ -0,0 +1,101 @@
+use dust_dds::{
+    configuration::DustDdsConfigurationBuilder,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        qos::QosKind,
+        status::{StatusKind, NO_STATUS},
+        time::Duration,
+        wait_set::{Condition, WaitSet},
+    },
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u32,
+}
+
+#[test]
+fn participants_with_accepted_domain_tags_are_matched() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    // Participants with the old and new domain tag accept each other during the migration
+    let old_configuration = DustDdsConfigurationBuilder::new()
+        .domain_tag("old".to_string())
+        .accepted_domain_tags(vec!["new".to_string()])
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(old_configuration)
+        .unwrap();
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let new_configuration = DustDdsConfigurationBuilder::new()
+        .domain_tag("new".to_string())
+        .accepted_domain_tags(vec!["old".to_string()])
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(new_configuration)
+        .unwrap();
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic1 = participant1
+        .create_topic::<KeyedData>(
+            "KeyedDataTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<KeyedData>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic2 = participant2
+        .create_topic::<KeyedData>(
+            "KeyedDataTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let _reader = subscriber
+        .create_datareader::<KeyedData>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    assert_eq!(
+        writer
+            .get_publication_matched_status()
+            .unwrap()
+            .current_count,
+        1
+    );
+}
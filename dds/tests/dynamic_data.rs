@@ -0,0 +1,205 @@
+mod utils;
+use std::sync::Arc;
+
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+use dust_dds::{
+    domain::{
+        domain_participant::DomainParticipant,
+        domain_participant_factory::{DomainId, DomainParticipantFactory},
+    },
+    infrastructure::{
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
+    },
+    publication::data_writer::DataWriter,
+    subscription::{
+        data_reader::DataReader,
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    },
+    topic_definition::{topic::Topic, type_support::DdsType},
+    xtypes::{
+        dynamic_data::{DynamicData, DynamicDataFactory},
+        dynamic_type::{ExtensibilityKind, TypeDescriptor},
+        dynamic_type_builder::DynamicTypeBuilderFactory,
+        type_object::{TK_INT32, TK_STRUCTURE},
+    },
+};
+
+#[derive(Debug, PartialEq, DdsType)]
+struct ShapeType {
+    #[dust_dds(key)]
+    color: String,
+    x: i32,
+    y: i32,
+    history: Vec<i32>,
+}
+
+fn create_participant(domain_id: DomainId) -> DomainParticipant {
+    DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap()
+}
+
+fn create_dynamic_shape_topic(participant: &DomainParticipant) -> Topic {
+    let mut builder = DynamicTypeBuilderFactory::create_type(TypeDescriptor {
+        kind: TK_STRUCTURE,
+        name: "ShapeType".to_string(),
+        extensibility_kind: ExtensibilityKind::Final,
+        is_nested: false,
+    })
+    .unwrap();
+    let int32 = || DynamicTypeBuilderFactory::get_primitive_type(TK_INT32).unwrap();
+    builder
+        .add_member(
+            "color",
+            0,
+            DynamicTypeBuilderFactory::create_string_type(0),
+            true,
+        )
+        .unwrap();
+    builder.add_member("x", 1, int32(), false).unwrap();
+    builder.add_member("y", 2, int32(), false).unwrap();
+    builder
+        .add_member(
+            "history",
+            3,
+            DynamicTypeBuilderFactory::create_sequence_type(int32(), 0),
+            false,
+        )
+        .unwrap();
+
+    participant
+        .create_dynamic_topic(
+            "Square",
+            "ShapeType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+            Arc::new(builder.build()),
+        )
+        .unwrap()
+}
+
+fn create_writer<Foo: 'static>(participant: &DomainParticipant, topic: &Topic) -> DataWriter<Foo> {
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter(topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap()
+}
+
+fn create_reader<Foo: 'static>(participant: &DomainParticipant, topic: &Topic) -> DataReader<Foo> {
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader(topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap()
+}
+
+fn wait_for_publication_matched<Foo>(writer: &DataWriter<Foo>) {
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+}
+
+#[test]
+fn dynamic_data_written_is_read_as_static_type() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant1 = create_participant(domain_id);
+    let participant2 = create_participant(domain_id);
+
+    let dynamic_topic = create_dynamic_shape_topic(&participant1);
+    let writer = create_writer::<DynamicData>(&participant1, &dynamic_topic);
+    let topic = participant2
+        .create_topic::<ShapeType>("Square", "ShapeType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = create_reader::<ShapeType>(&participant2, &topic);
+    wait_for_publication_matched(&writer);
+
+    let mut data =
+        DynamicDataFactory::create_data(dynamic_topic.get_type_support().unwrap().as_ref())
+            .unwrap();
+    data.set_string_value(0, "BLUE".to_string()).unwrap();
+    data.set_int32_value(1, 10).unwrap();
+    data.set_int32_value(2, 20).unwrap();
+    data.set_int32_values(3, vec![1, 2]).unwrap();
+    writer.write(&data, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        samples[0].data().unwrap(),
+        ShapeType {
+            color: "BLUE".to_string(),
+            x: 10,
+            y: 20,
+            history: vec![1, 2],
+        }
+    );
+}
+
+#[test]
+fn static_type_written_is_read_as_dynamic_data() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant1 = create_participant(domain_id);
+    let participant2 = create_participant(domain_id);
+
+    let topic = participant1
+        .create_topic::<ShapeType>("Square", "ShapeType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = create_writer::<ShapeType>(&participant1, &topic);
+    let dynamic_topic = create_dynamic_shape_topic(&participant2);
+    let reader = create_reader::<DynamicData>(&participant2, &dynamic_topic);
+    wait_for_publication_matched(&writer);
+
+    writer
+        .write(
+            &ShapeType {
+                color: "RED".to_string(),
+                x: -5,
+                y: 7,
+                history: vec![3],
+            },
+            None,
+        )
+        .unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let shape_type = dynamic_topic.get_type_support().unwrap();
+    let data = samples[0].dynamic_data(shape_type.as_ref()).unwrap();
+    assert_eq!(data.get_string_value(0), Ok("RED".to_string()));
+    assert_eq!(data.get_int32_value(1), Ok(-5));
+    assert_eq!(data.get_int32_value(2), Ok(7));
+    assert_eq!(data.get_int32_values(3), Ok(vec![3]));
+}
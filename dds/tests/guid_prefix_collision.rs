@@ -0,0 +1,220 @@
+use dust_dds::{
+    builtin_topics::DCPS_PARTICIPANT,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{qos::QosKind, status::NO_STATUS},
+    rtps::{
+        messages::{
+            overall_structure::{RtpsMessageHeader, RtpsMessageWrite},
+            submessage_elements::{Data, ParameterList},
+            submessages::data::DataSubmessage,
+        },
+        types::{PROTOCOLVERSION, VENDOR_ID_S2E},
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsDeserialize,
+    transport::types::{EntityId, BUILT_IN_READER_WITH_KEY, BUILT_IN_WRITER_WITH_KEY},
+};
+use std::io::{BufRead, Read};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+const ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER: EntityId =
+    EntityId::new([0x00, 0x01, 0x00], BUILT_IN_WRITER_WITH_KEY);
+
+const ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER: EntityId =
+    EntityId::new([0x00, 0x01, 0x00], BUILT_IN_READER_WITH_KEY);
+
+struct DynamicType<'a>(&'a [u8]);
+impl<'de> DdsDeserialize<'de> for DynamicType<'de> {
+    fn deserialize_data(
+        serialized_data: &'de [u8],
+    ) -> dust_dds::infrastructure::error::DdsResult<Self> {
+        Ok(Self(serialized_data))
+    }
+}
+
+impl<'a> DynamicType<'a> {
+    fn metatraffic_unicast_locator_port(&self) -> u32 {
+        const PID_METATRAFFIC_UNICAST_LOCATOR: i16 = 0x0032;
+        let reader = &mut &self.0[4..];
+        let mut pid = [0, 0];
+        let mut length = [0, 0];
+        loop {
+            reader.read_exact(&mut pid).unwrap();
+            reader.read_exact(&mut length).unwrap();
+            if i16::from_le_bytes(pid) == PID_METATRAFFIC_UNICAST_LOCATOR {
+                return u32::from_le_bytes([reader[4], reader[5], reader[6], reader[7]]);
+            } else {
+                reader.consume(u16::from_le_bytes(length) as usize);
+            }
+        }
+    }
+}
+
+fn participant_announcement(
+    guid_prefix: &[u8],
+    locator_port: u16,
+    user_data: [u8; 4],
+    sequence_number: i64,
+) -> RtpsMessageWrite {
+    let serialized_participant_data = [
+        &[
+            0x00, 0x03, 0x00, 0x00, // PL_CDR_LE
+            0x15, 0x00, 4, 0x00, // PID_PROTOCOL_VERSION, Length
+            0x02, 0x04, 0x00, 0x00, // ProtocolVersion
+            0x16, 0x00, 4, 0x00, // PID_VENDORID, Length
+            73, 74, 0x00, 0x00, // VendorId
+            0x58, 0x00, 4, 0x00, // PID_BUILTIN_ENDPOINT_SET, Length
+            0x00, 0x00, 0x00, 0x00, // BuiltinEndpointSet
+            0x2c, 0x00, 8, 0x00, // PID_USER_DATA, Length
+            4, 0x00, 0x00, 0x00, // sequence length
+        ],
+        user_data.as_slice(),
+        &[
+            0x50, 0x00, 16, 0x00, // PID_PARTICIPANT_GUID, Length
+        ],
+        guid_prefix,
+        &[
+            0, 0, 1, 0xc1, // EntityId
+            0x32, 0x00, 24, 0x00, // PID_METATRAFFIC_UNICAST_LOCATOR, Length
+            1, 0, 0, 0, // locator kind
+        ],
+        &(locator_port as u32).to_le_bytes(),
+        &[
+            0, 0, 0, 0, // locator address
+            0, 0, 0, 0, // locator address
+            0, 0, 0, 0, // locator address
+            127, 0, 0, 1, // locator address
+            0x01, 0x00, 0x00, 0x00, // PID_SENTINEL, length
+        ],
+    ]
+    .concat();
+
+    let data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+        ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+        sequence_number,
+        ParameterList::empty(),
+        Data::new(serialized_participant_data.into()),
+    );
+    RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(
+            PROTOCOLVERSION,
+            VENDOR_ID_S2E,
+            guid_prefix.try_into().unwrap(),
+        ),
+        &[Box::new(data_submessage)],
+    )
+}
+
+#[test]
+fn announcements_with_colliding_guid_prefix_are_ignored() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let mock_participant_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mock_participant_port = mock_participant_socket.local_addr().unwrap().port();
+    let cloned_participant_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let cloned_participant_port = cloned_participant_socket.local_addr().unwrap().port();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant_handle = participant.get_instance_handle();
+    let participant_guid_prefix = &participant_handle.as_ref()[..12];
+
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < std::time::Duration::from_secs(10) {
+        if !participant
+            .get_discovered_participants()
+            .unwrap()
+            .is_empty()
+        {
+            break;
+        }
+    }
+    let metatraffic_port = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<DynamicType>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap()
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .metatraffic_unicast_locator_port() as u16;
+
+    let mock_participant_guid_prefix = [99; 12];
+    mock_participant_socket
+        .send_to(
+            participant_announcement(
+                &mock_participant_guid_prefix,
+                mock_participant_port,
+                [1, 1, 1, 1],
+                1,
+            )
+            .buffer(),
+            ("127.0.0.1", metatraffic_port),
+        )
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < std::time::Duration::from_secs(10) {
+        if participant.get_discovered_participants().unwrap().len() == 2 {
+            break;
+        }
+    }
+    let discovered_participants = participant.get_discovered_participants().unwrap();
+    assert_eq!(discovered_participants.len(), 2);
+    let mock_participant_handle = discovered_participants
+        .into_iter()
+        .find(|h| h != &participant_handle)
+        .unwrap();
+
+    // Announcements from a different location using an already discovered GuidPrefix
+    // or the GuidPrefix of the local participant must not replace the existing data
+    cloned_participant_socket
+        .send_to(
+            participant_announcement(
+                &mock_participant_guid_prefix,
+                cloned_participant_port,
+                [2, 2, 2, 2],
+                2,
+            )
+            .buffer(),
+            ("127.0.0.1", metatraffic_port),
+        )
+        .unwrap();
+    cloned_participant_socket
+        .send_to(
+            participant_announcement(
+                participant_guid_prefix,
+                cloned_participant_port,
+                [2, 2, 2, 2],
+                1000,
+            )
+            .buffer(),
+            ("127.0.0.1", metatraffic_port),
+        )
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    assert_eq!(participant.get_discovered_participants().unwrap().len(), 2);
+    assert_eq!(
+        participant
+            .get_discovered_participant_data(mock_participant_handle)
+            .unwrap()
+            .user_data()
+            .value,
+        vec![1, 1, 1, 1]
+    );
+    assert!(participant
+        .get_discovered_participant_data(participant_handle)
+        .unwrap()
+        .user_data()
+        .value
+        .is_empty());
+}
@@ -0,0 +1,160 @@
+use dust_dds::{
+    configuration::DustDdsConfigurationBuilder,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u32,
+}
+
+#[test]
+fn initial_peers_are_validated() {
+    for peer in [
+        "127.0.0.1:7410",
+        "127.0.0.1",
+        "localhost:7410",
+        "udpv4://10.0.0.1",
+        "udpv6://[::1]:7410",
+        "fd00::1",
+    ] {
+        assert!(
+            DustDdsConfigurationBuilder::new()
+                .initial_peers(vec![peer.to_string()])
+                .build()
+                .is_ok(),
+            "{peer}"
+        );
+    }
+    for peer in [
+        "",
+        ":7410",
+        "127.0.0.1:port",
+        "127.0.0.1:70000",
+        "tcpv4://127.0.0.1",
+        "[::1",
+        "[::1]7410",
+    ] {
+        assert!(
+            DustDdsConfigurationBuilder::new()
+                .initial_peers(vec![peer.to_string()])
+                .build()
+                .is_err(),
+            "{peer}"
+        );
+    }
+}
+
+#[test]
+fn participants_are_discovered_through_initial_peers() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    // Only one participant knows the other, which discovers it when receiving its announcement
+    let server_factory = DomainParticipantFactory::new();
+    server_factory
+        .set_configuration(
+            DustDdsConfigurationBuilder::multicast_free()
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    let client_factory = DomainParticipantFactory::new();
+    client_factory
+        .set_configuration(
+            DustDdsConfigurationBuilder::multicast_free()
+                .initial_peers(vec!["127.0.0.1".to_string()])
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+    let server_participant = server_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let client_participant = client_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let reliability = ReliabilityQosPolicy {
+        kind: ReliabilityQosPolicyKind::Reliable,
+        max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+    };
+    let server_topic = server_participant
+        .create_topic::<KeyedData>(
+            "KeyedDataTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer = server_participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter(
+            &server_topic,
+            QosKind::Specific(DataWriterQos {
+                reliability: reliability.clone(),
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let client_topic = client_participant
+        .create_topic::<KeyedData>(
+            "KeyedDataTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader = client_participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader::<KeyedData>(
+            &client_topic,
+            QosKind::Specific(DataReaderQos {
+                reliability,
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data = KeyedData { id: 1, value: 7 };
+    writer.write(&data, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data().unwrap(), data);
+}
@@ -0,0 +1,289 @@
+use dust_dds::{
+    builtin_topics::DCPS_PARTICIPANT,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{instance::InstanceHandle, qos::QosKind, status::NO_STATUS},
+    rtps::{
+        messages::{
+            overall_structure::{RtpsMessageHeader, RtpsMessageWrite},
+            submessage_elements::{Data, Parameter, ParameterList},
+            submessages::data::DataSubmessage,
+        },
+        types::{PROTOCOLVERSION, VENDOR_ID_S2E},
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsDeserialize,
+    transport::types::{EntityId, BUILT_IN_READER_WITH_KEY, BUILT_IN_WRITER_WITH_KEY},
+};
+use std::io::{BufRead, Read};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+const ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER: EntityId =
+    EntityId::new([0x00, 0x01, 0x00], BUILT_IN_WRITER_WITH_KEY);
+
+const ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER: EntityId =
+    EntityId::new([0x00, 0x01, 0x00], BUILT_IN_READER_WITH_KEY);
+
+const PID_KEY_HASH: i16 = 0x0070;
+const PID_STATUS_INFO: i16 = 0x0071;
+
+struct DynamicType<'a>(&'a [u8]);
+impl<'de> DdsDeserialize<'de> for DynamicType<'de> {
+    fn deserialize_data(
+        serialized_data: &'de [u8],
+    ) -> dust_dds::infrastructure::error::DdsResult<Self> {
+        Ok(Self(serialized_data))
+    }
+}
+
+impl<'a> DynamicType<'a> {
+    fn metatraffic_unicast_locator_port(&self) -> u32 {
+        const PID_METATRAFFIC_UNICAST_LOCATOR: i16 = 0x0032;
+        let reader = &mut &self.0[4..];
+        let mut pid = [0, 0];
+        let mut length = [0, 0];
+        loop {
+            reader.read_exact(&mut pid).unwrap();
+            reader.read_exact(&mut length).unwrap();
+            if i16::from_le_bytes(pid) == PID_METATRAFFIC_UNICAST_LOCATOR {
+                return u32::from_le_bytes([reader[4], reader[5], reader[6], reader[7]]);
+            } else {
+                reader.consume(u16::from_le_bytes(length) as usize);
+            }
+        }
+    }
+}
+
+// Announcement of a participant, optionally with the key hash of the instance in the inline QoS
+fn participant_announcement(
+    guid_prefix: &[u8],
+    locator_port: u16,
+    key_hash: Option<[u8; 16]>,
+) -> RtpsMessageWrite {
+    let serialized_participant_data = [
+        &[
+            0x00, 0x03, 0x00, 0x00, // PL_CDR_LE
+            0x15, 0x00, 4, 0x00, // PID_PROTOCOL_VERSION, Length
+            0x02, 0x04, 0x00, 0x00, // ProtocolVersion
+            0x16, 0x00, 4, 0x00, // PID_VENDORID, Length
+            73, 74, 0x00, 0x00, // VendorId
+            0x58, 0x00, 4, 0x00, // PID_BUILTIN_ENDPOINT_SET, Length
+            0x00, 0x00, 0x00, 0x00, // BuiltinEndpointSet
+            0x50, 0x00, 16, 0x00, // PID_PARTICIPANT_GUID, Length
+        ],
+        guid_prefix,
+        &[
+            0, 0, 1, 0xc1, // EntityId
+            0x32, 0x00, 24, 0x00, // PID_METATRAFFIC_UNICAST_LOCATOR, Length
+            1, 0, 0, 0, // locator kind
+        ],
+        &(locator_port as u32).to_le_bytes(),
+        &[
+            0, 0, 0, 0, // locator address
+            0, 0, 0, 0, // locator address
+            0, 0, 0, 0, // locator address
+            127, 0, 0, 1, // locator address
+            0x01, 0x00, 0x00, 0x00, // PID_SENTINEL, length
+        ],
+    ]
+    .concat();
+
+    let inline_qos = match key_hash {
+        Some(key_hash) => {
+            ParameterList::new(vec![Parameter::new(PID_KEY_HASH, key_hash.to_vec().into())])
+        }
+        None => ParameterList::empty(),
+    };
+    let data_submessage = DataSubmessage::new(
+        key_hash.is_some(),
+        true,
+        false,
+        false,
+        ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+        ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+        1,
+        inline_qos,
+        Data::new(serialized_participant_data.into()),
+    );
+    RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(
+            PROTOCOLVERSION,
+            VENDOR_ID_S2E,
+            guid_prefix.try_into().unwrap(),
+        ),
+        &[Box::new(data_submessage)],
+    )
+}
+
+// Dispose of the participant identified only by the key hash, without any payload
+fn participant_dispose(guid_prefix: &[u8]) -> RtpsMessageWrite {
+    let key_hash = [guid_prefix, &[0, 0, 1, 0xc1]].concat();
+    let inline_qos = ParameterList::new(vec![
+        Parameter::new(PID_STATUS_INFO, [0, 0, 0, 3].into()),
+        Parameter::new(PID_KEY_HASH, key_hash.into()),
+    ]);
+    let data_submessage = DataSubmessage::new(
+        true,
+        false,
+        false,
+        false,
+        ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+        ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+        2,
+        inline_qos,
+        Data::default(),
+    );
+    RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(
+            PROTOCOLVERSION,
+            VENDOR_ID_S2E,
+            guid_prefix.try_into().unwrap(),
+        ),
+        &[Box::new(data_submessage)],
+    )
+}
+
+#[test]
+fn participant_disposed_without_payload_is_removed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let mock_participant_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mock_participant_port = mock_participant_socket.local_addr().unwrap().port();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant_handle = participant.get_instance_handle();
+
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < std::time::Duration::from_secs(10) {
+        if !participant
+            .get_discovered_participants()
+            .unwrap()
+            .is_empty()
+        {
+            break;
+        }
+    }
+    let metatraffic_port = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<DynamicType>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap()
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .metatraffic_unicast_locator_port() as u16;
+
+    let mock_participant_guid_prefix = [98; 12];
+    mock_participant_socket
+        .send_to(
+            participant_announcement(&mock_participant_guid_prefix, mock_participant_port, None)
+                .buffer(),
+            ("127.0.0.1", metatraffic_port),
+        )
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < std::time::Duration::from_secs(10) {
+        if participant.get_discovered_participants().unwrap().len() == 2 {
+            break;
+        }
+    }
+    assert_eq!(participant.get_discovered_participants().unwrap().len(), 2);
+
+    mock_participant_socket
+        .send_to(
+            participant_dispose(&mock_participant_guid_prefix).buffer(),
+            ("127.0.0.1", metatraffic_port),
+        )
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < std::time::Duration::from_secs(10) {
+        if participant.get_discovered_participants().unwrap().len() == 1 {
+            break;
+        }
+    }
+    assert_eq!(
+        participant.get_discovered_participants().unwrap(),
+        vec![participant_handle]
+    );
+}
+
+#[test]
+fn sample_instance_is_given_by_the_key_hash() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let mock_participant_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mock_participant_port = mock_participant_socket.local_addr().unwrap().port();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant_reader = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<DynamicType>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .is_err()
+    {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+    }
+    let metatraffic_port = participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .metatraffic_unicast_locator_port() as u16;
+
+    // The key hash differs from the key in the serialized data so that only the instance given
+    // by the key hash can be the one of the sample
+    let mock_participant_guid_prefix = [97; 12];
+    let key_hash = [[96; 12].as_slice(), &[0, 0, 1, 0xc1]]
+        .concat()
+        .try_into()
+        .unwrap();
+    mock_participant_socket
+        .send_to(
+            participant_announcement(
+                &mock_participant_guid_prefix,
+                mock_participant_port,
+                Some(key_hash),
+            )
+            .buffer(),
+            ("127.0.0.1", metatraffic_port),
+        )
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while participant_reader
+        .read_instance(
+            10,
+            InstanceHandle::new(key_hash),
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .is_err()
+    {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+    }
+    let serialized_key = [[97; 12].as_slice(), &[0, 0, 1, 0xc1]]
+        .concat()
+        .try_into()
+        .unwrap();
+    assert!(participant_reader
+        .read_instance(
+            10,
+            InstanceHandle::new(serialized_key),
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .is_err());
+}
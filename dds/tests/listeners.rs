@@ -736,6 +736,93 @@ fn data_on_readers_listener() {
         .is_ok());
 }
 
+#[test]
+fn notify_datareaders_calls_data_available_listener() {
+    struct NotifyDataReadersListener;
+
+    impl SubscriberListener for NotifyDataReadersListener {
+        fn on_data_on_readers(&mut self, the_subscriber: Subscriber) {
+            the_subscriber.notify_datareaders().unwrap();
+        }
+    }
+
+    struct DataAvailableListener {
+        sender: std::sync::mpsc::SyncSender<()>,
+    }
+
+    impl DataReaderListener<'_> for DataAvailableListener {
+        type Foo = MyData;
+        fn on_data_available(&mut self, _the_reader: DataReader<MyData>) {
+            self.sender.send(()).unwrap();
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Default,
+            Some(Box::new(NotifyDataReadersListener)),
+            &[StatusKind::DataOnReaders],
+        )
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+    let reader_listener = DataAvailableListener { sender };
+    let _reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Specific(reader_qos),
+            Some(Box::new(reader_listener)),
+            &[StatusKind::DataAvailable],
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = MyData { id: 1, value: 1 };
+    writer.write(&data1, None).unwrap();
+
+    assert!(receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .is_ok());
+}
+
 #[test]
 fn data_available_listener_not_called_when_data_on_readers_listener() {
     struct DataOnReadersListener {
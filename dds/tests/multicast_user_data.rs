@@ -0,0 +1,163 @@
+use dust_dds::{
+    configuration::DustDdsConfigurationBuilder,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{
+            HistoryQosPolicy, HistoryQosPolicyKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+        },
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: Vec<u8>,
+}
+
+#[test]
+fn samples_sent_with_multicast_are_received_by_all_readers() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    // Both readers share the multicast group of the domain so the writer switches to multicast
+    let configuration = DustDdsConfigurationBuilder::new()
+        .user_data_multicast_threshold(Some(1))
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "KeyedDataTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let mut reader_participant_list = Vec::new();
+    let mut reader_list = Vec::new();
+    for _ in 0..2 {
+        let reader_participant = domain_participant_factory
+            .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+            .unwrap();
+        let topic = reader_participant
+            .create_topic::<KeyedData>(
+                "KeyedDataTopic",
+                "KeyedData",
+                QosKind::Default,
+                None,
+                NO_STATUS,
+            )
+            .unwrap();
+        let subscriber = reader_participant
+            .create_subscriber(QosKind::Default, None, NO_STATUS)
+            .unwrap();
+        let reader = subscriber
+            .create_datareader::<KeyedData>(
+                &topic,
+                QosKind::Specific(reader_qos.clone()),
+                None,
+                NO_STATUS,
+            )
+            .unwrap();
+        reader_participant_list.push(reader_participant);
+        reader_list.push(reader);
+    }
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    while writer
+        .get_publication_matched_status()
+        .unwrap()
+        .current_count
+        < 2
+    {
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    for reader in &reader_list {
+        let cond = reader.get_statuscondition();
+        cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    // The last sample is large enough to be fragmented
+    let data: Vec<_> = (0..5)
+        .map(|value| KeyedData {
+            id: 1,
+            value: vec![value; 1000 + 1000 * value as usize],
+        })
+        .collect();
+    for sample in &data {
+        writer.write(sample, None).unwrap();
+    }
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    for reader in &reader_list {
+        let samples = reader
+            .take(100, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .unwrap();
+        assert_eq!(
+            samples
+                .iter()
+                .map(|s| s.data().unwrap())
+                .collect::<Vec<_>>(),
+            data
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use dust_dds::{
+    builtin_topics::DCPS_PARTICIPANT,
+    configuration::DustDdsConfigurationBuilder,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{qos::QosKind, status::NO_STATUS},
+    rtps::{
+        messages::{
+            overall_structure::{
+                RtpsMessageHeader, RtpsMessageRead, RtpsMessageWrite, RtpsSubmessageReadKind,
+            },
+            submessage_elements::{Data, ParameterList},
+            submessages::data::DataSubmessage,
+        },
+        types::{PROTOCOLVERSION, VENDOR_ID_S2E},
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsDeserialize,
+    transport::types::{EntityId, BUILT_IN_READER_WITH_KEY, BUILT_IN_WRITER_WITH_KEY},
+};
+use std::io::{BufRead, Read};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+const ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER: EntityId =
+    EntityId::new([0x00, 0x01, 0x00], BUILT_IN_WRITER_WITH_KEY);
+
+const ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER: EntityId =
+    EntityId::new([0x00, 0x01, 0x00], BUILT_IN_READER_WITH_KEY);
+
+struct DynamicType<'a>(&'a [u8]);
+impl<'de> DdsDeserialize<'de> for DynamicType<'de> {
+    fn deserialize_data(
+        serialized_data: &'de [u8],
+    ) -> dust_dds::infrastructure::error::DdsResult<Self> {
+        Ok(Self(serialized_data))
+    }
+}
+
+impl<'a> DynamicType<'a> {
+    fn metatraffic_unicast_locator_port(&self) -> u32 {
+        const PID_METATRAFFIC_UNICAST_LOCATOR: i16 = 0x0032;
+        let reader = &mut &self.0[4..];
+        let mut pid = [0, 0];
+        let mut length = [0, 0];
+        loop {
+            reader.read_exact(&mut pid).unwrap();
+            reader.read_exact(&mut length).unwrap();
+            if i16::from_le_bytes(pid) == PID_METATRAFFIC_UNICAST_LOCATOR {
+                return u32::from_le_bytes([reader[4], reader[5], reader[6], reader[7]]);
+            } else {
+                reader.consume(u16::from_le_bytes(length) as usize);
+            }
+        }
+    }
+}
+
+fn participant_announcement(guid_prefix: &[u8], locator_port: u16) -> RtpsMessageWrite {
+    let serialized_participant_data = [
+        &[
+            0x00, 0x03, 0x00, 0x00, // PL_CDR_LE
+            0x15, 0x00, 4, 0x00, // PID_PROTOCOL_VERSION, Length
+            0x02, 0x04, 0x00, 0x00, // ProtocolVersion
+            0x16, 0x00, 4, 0x00, // PID_VENDORID, Length
+            73, 74, 0x00, 0x00, // VendorId
+            0x58, 0x00, 4, 0x00, // PID_BUILTIN_ENDPOINT_SET, Length
+            0x00, 0x00, 0x00, 0x00, // BuiltinEndpointSet
+            0x50, 0x00, 16, 0x00, // PID_PARTICIPANT_GUID, Length
+        ],
+        guid_prefix,
+        &[
+            0, 0, 1, 0xc1, // EntityId
+            0x32, 0x00, 24, 0x00, // PID_METATRAFFIC_UNICAST_LOCATOR, Length
+            1, 0, 0, 0, // locator kind
+        ],
+        &(locator_port as u32).to_le_bytes(),
+        &[
+            0, 0, 0, 0, // locator address
+            0, 0, 0, 0, // locator address
+            0, 0, 0, 0, // locator address
+            127, 0, 0, 1, // locator address
+            0x01, 0x00, 0x00, 0x00, // PID_SENTINEL, length
+        ],
+    ]
+    .concat();
+
+    let data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+        ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+        1,
+        ParameterList::empty(),
+        Data::new(serialized_participant_data.into()),
+    );
+    RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(
+            PROTOCOLVERSION,
+            VENDOR_ID_S2E,
+            guid_prefix.try_into().unwrap(),
+        ),
+        &[Box::new(data_submessage)],
+    )
+}
+
+#[test]
+fn newly_discovered_participant_receives_participant_data_without_waiting_for_announcement() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .participant_announcement_interval(std::time::Duration::from_secs(60))
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    // Give time for the first announcement of participant1 to be sent before participant2 exists
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < std::time::Duration::from_secs(10) {
+        if participant2
+            .get_discovered_participants()
+            .unwrap()
+            .contains(&participant1.get_instance_handle())
+        {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert!(participant2
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant1.get_instance_handle()));
+}
+
+#[test]
+fn announce_sends_participant_data_without_waiting_for_announcement_interval() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .participant_announcement_interval(std::time::Duration::from_secs(60))
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+    let mock_participant_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mock_participant_port = mock_participant_socket.local_addr().unwrap().port();
+
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < std::time::Duration::from_secs(10) {
+        if !participant
+            .get_discovered_participants()
+            .unwrap()
+            .is_empty()
+        {
+            break;
+        }
+    }
+    let metatraffic_port = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<DynamicType>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap()
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .metatraffic_unicast_locator_port() as u16;
+
+    mock_participant_socket
+        .send_to(
+            participant_announcement(&[99; 12], mock_participant_port).buffer(),
+            ("127.0.0.1", metatraffic_port),
+        )
+        .unwrap();
+
+    // Discard the participant data sent when the mock participant is discovered
+    let mut buffer = [0; 65535];
+    mock_participant_socket
+        .set_read_timeout(Some(std::time::Duration::from_millis(500)))
+        .unwrap();
+    while mock_participant_socket.recv(&mut buffer).is_ok() {}
+
+    participant.announce().unwrap();
+
+    mock_participant_socket
+        .set_read_timeout(Some(std::time::Duration::from_secs(5)))
+        .unwrap();
+    mock_participant_socket.recv(&mut buffer).unwrap();
+    let received_announcement = RtpsMessageRead::try_from(buffer.as_slice()).unwrap();
+    assert!(received_announcement.submessages().iter().any(|s| matches!(
+        s,
+        RtpsSubmessageReadKind::Data(d) if d.writer_id() == ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER
+    )));
+}
@@ -2,8 +2,9 @@ use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
         qos::{DataWriterQos, QosKind},
-        qos_policy::UserDataQosPolicy,
+        qos_policy::{LifespanQosPolicy, UserDataQosPolicy},
         status::NO_STATUS,
+        time::{Duration, DurationKind},
     },
     topic_definition::type_support::DdsType,
 };
@@ -82,6 +83,44 @@ fn default_data_writer_qos() {
     assert_eq!(&writer.get_qos().unwrap().user_data.value, &user_data);
 }
 
+#[test]
+fn data_writer_created_with_qos_copied_from_updated_topic() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserType>(
+            "data_writer_created_with_qos_copied_from_updated_topic",
+            "UserType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let lifespan = LifespanQosPolicy {
+        duration: DurationKind::Finite(Duration::new(5, 0)),
+    };
+    let mut topic_qos = topic.get_qos().unwrap();
+    topic_qos.lifespan = lifespan.clone();
+    topic.set_qos(QosKind::Specific(topic_qos)).unwrap();
+
+    let mut writer_qos = publisher.get_default_datawriter_qos().unwrap();
+    publisher
+        .copy_from_topic_qos(&mut writer_qos, &topic.get_qos().unwrap())
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(writer.get_qos().unwrap().lifespan, lifespan);
+}
+
 #[test]
 fn different_writers_have_different_instance_handles() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
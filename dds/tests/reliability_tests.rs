@@ -80,8 +80,8 @@ impl<'a> DynamicType<'a> {
         let mut pid = [0, 0];
         let mut length = [0, 0];
         loop {
-            reader.read(&mut pid).unwrap();
-            reader.read(&mut length).unwrap();
+            reader.read_exact(&mut pid).unwrap();
+            reader.read_exact(&mut length).unwrap();
             if i16::from_le_bytes(pid) == PID_METATRAFFIC_UNICAST_LOCATOR {
                 return u32::from_le_bytes([reader[4], reader[5], reader[6], reader[7]]);
             } else {
@@ -96,8 +96,8 @@ impl<'a> DynamicType<'a> {
         let mut pid = [0, 0];
         let mut length = [0, 0];
         loop {
-            reader.read(&mut pid).unwrap();
-            reader.read(&mut length).unwrap();
+            reader.read_exact(&mut pid).unwrap();
+            reader.read_exact(&mut length).unwrap();
             if i16::from_le_bytes(pid) == PID_DEFAULT_UNICAST_LOCATOR {
                 return u32::from_le_bytes([reader[4], reader[5], reader[6], reader[7]]);
             } else {
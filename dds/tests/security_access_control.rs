@@ -0,0 +1,279 @@
+#![cfg(feature = "security")]
+
+use std::time::{Duration as StdDuration, Instant};
+
+use dust_dds::{
+    domain::{
+        domain_participant::DomainParticipant,
+        domain_participant_factory::{DomainId, DomainParticipantFactory},
+    },
+    infrastructure::{
+        error::DdsResult,
+        qos::{DataReaderQos, DomainParticipantQos, QosKind},
+        qos_policy::{Property, PropertyQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
+    },
+    publication::data_writer::DataWriter,
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+const IDENTITY_CA: &str = include_str!("security/identity_ca.pem");
+const PARTICIPANT1_CERTIFICATE: &str = include_str!("security/participant1.pem");
+const PARTICIPANT1_KEY: &str = include_str!("security/participant1_key.pem");
+const PARTICIPANT2_CERTIFICATE: &str = include_str!("security/participant2.pem");
+const PARTICIPANT2_KEY: &str = include_str!("security/participant2_key.pem");
+const GOVERNANCE: &str = include_str!("security/governance.p7s");
+const GOVERNANCE_ENCRYPT: &str = include_str!("security/governance_encrypt.p7s");
+const GOVERNANCE_RTPS_SIGN: &str = include_str!("security/governance_rtps_sign.p7s");
+const PERMISSIONS: &str = include_str!("security/permissions.p7s");
+
+#[derive(Debug, PartialEq, DdsType)]
+struct UserType(#[dust_dds(key)] i32);
+
+fn property(name: &str, value: &str) -> Property {
+    Property {
+        name: name.to_string(),
+        value: format!("data:,{}", value),
+        propagate: false,
+    }
+}
+
+fn create_participant(
+    domain_id: DomainId,
+    properties: Vec<Property>,
+) -> DdsResult<DomainParticipant> {
+    let participant_qos = DomainParticipantQos {
+        property: PropertyQosPolicy {
+            value: properties,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    DomainParticipantFactory::get_instance().create_participant(
+        domain_id,
+        QosKind::Specific(participant_qos),
+        None,
+        NO_STATUS,
+    )
+}
+
+fn secure_participant(
+    domain_id: DomainId,
+    identity_certificate: &str,
+    private_key: &str,
+    governance: &str,
+) -> DomainParticipant {
+    create_participant(
+        domain_id,
+        vec![
+            property("dds.sec.auth.identity_ca", IDENTITY_CA),
+            property("dds.sec.auth.identity_certificate", identity_certificate),
+            property("dds.sec.auth.private_key", private_key),
+            property("dds.sec.access.permissions_ca", IDENTITY_CA),
+            property("dds.sec.access.governance", governance),
+            property("dds.sec.access.permissions", PERMISSIONS),
+        ],
+    )
+    .unwrap()
+}
+
+fn wait_for_publication_matched(data_writer: &DataWriter<UserType>) {
+    let cond = data_writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+}
+
+#[test]
+fn reader_is_only_matched_in_topics_allowed_by_permissions() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant1 = secure_participant(
+        domain_id,
+        PARTICIPANT1_CERTIFICATE,
+        PARTICIPANT1_KEY,
+        GOVERNANCE,
+    );
+    let participant2 = secure_participant(
+        domain_id,
+        PARTICIPANT2_CERTIFICATE,
+        PARTICIPANT2_KEY,
+        GOVERNANCE,
+    );
+
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let mut data_writers = Vec::new();
+    for topic_name in ["Square", "Circle"] {
+        let topic1 = participant1
+            .create_topic::<UserType>(topic_name, "UserType", QosKind::Default, None, NO_STATUS)
+            .unwrap();
+        data_writers.push(
+            publisher
+                .create_datawriter::<UserType>(&topic1, QosKind::Default, None, NO_STATUS)
+                .unwrap(),
+        );
+        let topic2 = participant2
+            .create_topic::<UserType>(topic_name, "UserType", QosKind::Default, None, NO_STATUS)
+            .unwrap();
+        subscriber
+            .create_datareader::<UserType>(&topic2, QosKind::Default, None, NO_STATUS)
+            .unwrap();
+    }
+
+    wait_for_publication_matched(&data_writers[0]);
+    std::thread::sleep(StdDuration::from_millis(500));
+
+    assert_eq!(
+        data_writers[0].get_matched_subscriptions().unwrap().len(),
+        1
+    );
+    // Participant 2 is not allowed to subscribe to Circle
+    assert!(data_writers[1]
+        .get_matched_subscriptions()
+        .unwrap()
+        .is_empty());
+}
+
+// The governance encrypts the discovery and the submessages of the Square topic
+#[test]
+fn samples_are_received_in_protected_topics() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant1 = secure_participant(
+        domain_id,
+        PARTICIPANT1_CERTIFICATE,
+        PARTICIPANT1_KEY,
+        GOVERNANCE_ENCRYPT,
+    );
+    let participant2 = secure_participant(
+        domain_id,
+        PARTICIPANT2_CERTIFICATE,
+        PARTICIPANT2_KEY,
+        GOVERNANCE_ENCRYPT,
+    );
+
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<UserType>("Square", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<UserType>("Square", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic2, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    wait_for_publication_matched(&data_writer);
+    data_writer.write(&UserType(8), None).unwrap();
+
+    let cond = data_reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let samples = data_reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data().unwrap(), UserType(8));
+}
+
+#[test]
+fn participants_with_different_rtps_protection_are_not_discovered() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant1 = secure_participant(
+        domain_id,
+        PARTICIPANT1_CERTIFICATE,
+        PARTICIPANT1_KEY,
+        GOVERNANCE,
+    );
+    let participant2 = secure_participant(
+        domain_id,
+        PARTICIPANT2_CERTIFICATE,
+        PARTICIPANT2_KEY,
+        GOVERNANCE_RTPS_SIGN,
+    );
+
+    let start = Instant::now();
+    while start.elapsed() < StdDuration::from_secs(3) {
+        assert!(!participant1
+            .get_discovered_participants()
+            .unwrap()
+            .contains(&participant2.get_instance_handle()));
+        assert!(!participant2
+            .get_discovered_participants()
+            .unwrap()
+            .contains(&participant1.get_instance_handle()));
+        std::thread::sleep(StdDuration::from_millis(100));
+    }
+}
+
+#[test]
+fn participant_with_modified_permissions_is_not_created() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let modified_permissions = PERMISSIONS.replacen("<default>DENY", "<default>ALLOW", 1);
+
+    let result = create_participant(
+        domain_id,
+        vec![
+            property("dds.sec.auth.identity_ca", IDENTITY_CA),
+            property(
+                "dds.sec.auth.identity_certificate",
+                PARTICIPANT1_CERTIFICATE,
+            ),
+            property("dds.sec.auth.private_key", PARTICIPANT1_KEY),
+            property("dds.sec.access.permissions_ca", IDENTITY_CA),
+            property("dds.sec.access.governance", GOVERNANCE),
+            property("dds.sec.access.permissions", &modified_permissions),
+        ],
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn access_control_without_authentication_is_not_created() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let result = create_participant(
+        domain_id,
+        vec![
+            property("dds.sec.access.permissions_ca", IDENTITY_CA),
+            property("dds.sec.access.governance", GOVERNANCE),
+            property("dds.sec.access.permissions", PERMISSIONS),
+        ],
+    );
+
+    assert!(result.is_err());
+}
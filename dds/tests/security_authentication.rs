@@ -0,0 +1,188 @@
+#![cfg(feature = "security")]
+
+use std::time::{Duration as StdDuration, Instant};
+
+use dust_dds::{
+    domain::{
+        domain_participant::DomainParticipant,
+        domain_participant_factory::{DomainId, DomainParticipantFactory},
+    },
+    infrastructure::{
+        qos::{DomainParticipantQos, QosKind},
+        qos_policy::{Property, PropertyQosPolicy},
+        status::{StatusKind, NO_STATUS},
+        time::Duration,
+        wait_set::{Condition, WaitSet},
+    },
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+const IDENTITY_CA: &str = include_str!("security/identity_ca.pem");
+const PARTICIPANT1_CERTIFICATE: &str = include_str!("security/participant1.pem");
+const PARTICIPANT1_KEY: &str = include_str!("security/participant1_key.pem");
+const PARTICIPANT2_CERTIFICATE: &str = include_str!("security/participant2.pem");
+const PARTICIPANT2_KEY: &str = include_str!("security/participant2_key.pem");
+const INTRUDER_CA: &str = include_str!("security/intruder_ca.pem");
+const INTRUDER_CERTIFICATE: &str = include_str!("security/intruder.pem");
+const INTRUDER_KEY: &str = include_str!("security/intruder_key.pem");
+
+#[derive(DdsType)]
+struct UserType(#[dust_dds(key)] i32);
+
+fn secure_participant(
+    domain_id: DomainId,
+    identity_ca: &str,
+    identity_certificate: &str,
+    private_key: &str,
+) -> DomainParticipant {
+    let property = |name: &str, value: &str| Property {
+        name: name.to_string(),
+        value: format!("data:,{}", value),
+        propagate: false,
+    };
+    let participant_qos = DomainParticipantQos {
+        property: PropertyQosPolicy {
+            value: vec![
+                property("dds.sec.auth.identity_ca", identity_ca),
+                property("dds.sec.auth.identity_certificate", identity_certificate),
+                property("dds.sec.auth.private_key", private_key),
+            ],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    DomainParticipantFactory::get_instance()
+        .create_participant(
+            domain_id,
+            QosKind::Specific(participant_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap()
+}
+
+fn wait_for_discovered_participant(
+    participant: &DomainParticipant,
+    remote_participant: &DomainParticipant,
+    timeout: StdDuration,
+) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        if participant
+            .get_discovered_participants()
+            .unwrap()
+            .contains(&remote_participant.get_instance_handle())
+        {
+            return true;
+        }
+        std::thread::sleep(StdDuration::from_millis(50));
+    }
+    false
+}
+
+#[test]
+fn authenticated_participants_match_endpoints() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant1 = secure_participant(
+        domain_id,
+        IDENTITY_CA,
+        PARTICIPANT1_CERTIFICATE,
+        PARTICIPANT1_KEY,
+    );
+    let participant2 = secure_participant(
+        domain_id,
+        IDENTITY_CA,
+        PARTICIPANT2_CERTIFICATE,
+        PARTICIPANT2_KEY,
+    );
+
+    let topic1 = participant1
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let _data_reader = subscriber
+        .create_datareader::<UserType>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let cond = data_writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    assert_eq!(data_writer.get_matched_subscriptions().unwrap().len(), 1);
+    assert!(participant1
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant2.get_instance_handle()));
+    assert!(participant2
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant1.get_instance_handle()));
+}
+
+#[test]
+fn participant_with_identity_of_other_ca_is_not_discovered() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = secure_participant(
+        domain_id,
+        IDENTITY_CA,
+        PARTICIPANT1_CERTIFICATE,
+        PARTICIPANT1_KEY,
+    );
+    let intruder = secure_participant(domain_id, INTRUDER_CA, INTRUDER_CERTIFICATE, INTRUDER_KEY);
+
+    assert!(!wait_for_discovered_participant(
+        &participant,
+        &intruder,
+        StdDuration::from_secs(3)
+    ));
+    assert!(!wait_for_discovered_participant(
+        &intruder,
+        &participant,
+        StdDuration::from_millis(100)
+    ));
+}
+
+#[test]
+fn participant_without_security_is_not_discovered() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = secure_participant(
+        domain_id,
+        IDENTITY_CA,
+        PARTICIPANT1_CERTIFICATE,
+        PARTICIPANT1_KEY,
+    );
+    let unsecure_participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    // The participant without security accepts the announcement of the secure participant
+    assert!(wait_for_discovered_participant(
+        &unsecure_participant,
+        &participant,
+        StdDuration::from_secs(10)
+    ));
+    std::thread::sleep(StdDuration::from_millis(500));
+    assert!(!participant
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&unsecure_participant.get_instance_handle()));
+}
@@ -0,0 +1,145 @@
+mod utils;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use dust_dds::{
+    dds_async::{
+        domain_participant_factory::DomainParticipantFactoryAsync,
+        runtime::Spawner,
+        wait_set::{ConditionAsync, WaitSetAsync},
+    },
+    infrastructure::{
+        qos::{DataReaderQos, QosKind},
+        qos_policy::{ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsType,
+};
+
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Debug, PartialEq, DdsType)]
+struct UserData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u8,
+}
+
+struct CountingSpawner {
+    runtime: tokio::runtime::Handle,
+    spawned_tasks: Arc<AtomicUsize>,
+}
+
+impl Spawner for CountingSpawner {
+    fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+        self.spawned_tasks.fetch_add(1, Ordering::Relaxed);
+        self.runtime.spawn(task);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dust_dds_should_run_on_custom_spawner() {
+    let spawned_tasks = Arc::new(AtomicUsize::new(0));
+    let participant_factory = DomainParticipantFactoryAsync::new_with_spawner(CountingSpawner {
+        runtime: tokio::runtime::Handle::current(),
+        spawned_tasks: spawned_tasks.clone(),
+    });
+    write_and_read_sample(&participant_factory).await;
+
+    assert!(spawned_tasks.load(Ordering::Relaxed) > 0);
+}
+
+#[cfg(feature = "smol")]
+#[test]
+fn dust_dds_should_run_on_smol_executor() {
+    let executor = Arc::new(smol::Executor::new());
+    let participant_factory = DomainParticipantFactoryAsync::new_with_spawner(executor.clone());
+    smol::block_on(executor.run(write_and_read_sample(&participant_factory)));
+}
+
+#[cfg(feature = "smol")]
+#[test]
+fn dust_dds_should_run_on_smol_global_executor() {
+    let participant_factory =
+        DomainParticipantFactoryAsync::new_with_spawner(dust_dds::dds_async::runtime::SmolSpawner);
+    smol::block_on(write_and_read_sample(&participant_factory));
+}
+
+#[cfg(feature = "async-std")]
+#[test]
+fn dust_dds_should_run_on_async_std_executor() {
+    let participant_factory = DomainParticipantFactoryAsync::new_with_spawner(
+        dust_dds::dds_async::runtime::AsyncStdSpawner,
+    );
+    async_std::task::block_on(write_and_read_sample(&participant_factory));
+}
+
+async fn write_and_read_sample(participant_factory: &DomainParticipantFactoryAsync) {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<UserData>(&topic, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .await
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .await
+        .unwrap();
+    let mut wait_set = WaitSetAsync::new();
+    wait_set
+        .attach_condition(ConditionAsync::StatusCondition(cond))
+        .await
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).await.unwrap();
+
+    let data = UserData { id: 1, value: 10 };
+    writer.write(&data, None).await.unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .await
+        .unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .await
+        .unwrap();
+    assert_eq!(samples[0].data().unwrap(), data);
+}
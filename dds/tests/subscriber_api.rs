@@ -1,9 +1,20 @@
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        qos::{DataReaderQos, QosKind},
-        qos_policy::UserDataQosPolicy,
-        status::NO_STATUS,
+        error::DdsError,
+        qos::{DataReaderQos, DataWriterQos, PublisherQos, QosKind, SubscriberQos, TopicQos},
+        qos_policy::{
+            HistoryQosPolicy, HistoryQosPolicyKind, Length, PresentationQosPolicy,
+            PresentationQosPolicyAccessScopeKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            ResourceLimitsQosPolicy, UserDataQosPolicy,
+        },
+        status::{SampleLostStatus, StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
+    },
+    subscription::{
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+        subscriber::Subscriber,
     },
     topic_definition::type_support::DdsType,
 };
@@ -11,7 +22,7 @@ use dust_dds::{
 mod utils;
 use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
 
-#[derive(DdsType)]
+#[derive(Debug, PartialEq, DdsType)]
 struct UserType(i32);
 
 #[test]
@@ -82,6 +93,118 @@ fn default_data_reader_qos() {
     assert_eq!(&reader.get_qos().unwrap().user_data.value, &user_data);
 }
 
+#[test]
+fn inconsistent_default_data_reader_qos_is_rejected() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let qos = DataReaderQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepLast(10),
+        },
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Unlimited,
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Limited(5),
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        subscriber.set_default_datareader_qos(QosKind::Specific(qos)),
+        Err(DdsError::InconsistentPolicy)
+    );
+    assert_eq!(
+        subscriber.get_default_datareader_qos().unwrap(),
+        DataReaderQos::default()
+    );
+}
+
+#[test]
+fn data_reader_created_with_qos_copied_from_topic() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic_qos = TopicQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepLast(3),
+        },
+        ..Default::default()
+    };
+    let topic = participant
+        .create_topic::<UserType>(
+            "data_reader_created_with_qos_copied_from_topic",
+            "UserType",
+            QosKind::Specific(topic_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let mut reader_qos = subscriber.get_default_datareader_qos().unwrap();
+    Subscriber::copy_from_topic_qos(&mut reader_qos, &topic.get_qos().unwrap()).unwrap();
+    subscriber
+        .set_default_datareader_qos(QosKind::Specific(reader_qos))
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let qos = reader.get_qos().unwrap();
+    assert_eq!(qos.reliability, topic_qos.reliability);
+    assert_eq!(qos.history, topic_qos.history);
+}
+
+#[test]
+fn subscriber_sample_lost_status_without_lost_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserType>(
+            "subscriber_sample_lost_status_without_lost_samples",
+            "UserType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(
+        subscriber.get_sample_lost_status().unwrap(),
+        SampleLostStatus::default()
+    );
+
+    subscriber.delete_datareader(&reader).unwrap();
+    assert_eq!(
+        subscriber.get_sample_lost_status().unwrap(),
+        SampleLostStatus::default()
+    );
+}
+
 #[test]
 fn different_readers_have_different_instance_handles() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -161,3 +284,252 @@ fn data_reader_get_topicdescription_should_return_same_topic_as_used_for_creatio
 
     assert!(reader.get_topicdescription().get_instance_handle() == topic.get_instance_handle());
 }
+
+#[test]
+fn end_access_without_begin_access_fails() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    subscriber.begin_access().unwrap();
+    subscriber.begin_access().unwrap();
+    subscriber.end_access().unwrap();
+    subscriber.end_access().unwrap();
+    assert!(matches!(
+        subscriber.end_access(),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn group_ordered_access_returns_readers_in_write_order() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic_a = participant
+        .create_topic::<UserType>("TopicA", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic_b = participant
+        .create_topic::<UserType>("TopicB", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let presentation = PresentationQosPolicy {
+        access_scope: PresentationQosPolicyAccessScopeKind::Group,
+        coherent_access: false,
+        ordered_access: true,
+    };
+    let publisher = participant
+        .create_publisher(
+            QosKind::Specific(PublisherQos {
+                presentation: presentation.clone(),
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Specific(SubscriberQos {
+                presentation,
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer_a = publisher
+        .create_datawriter(
+            &topic_a,
+            QosKind::Specific(writer_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer_b = publisher
+        .create_datawriter(&topic_b, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    let reader_a = subscriber
+        .create_datareader::<UserType>(
+            &topic_a,
+            QosKind::Specific(reader_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader_b = subscriber
+        .create_datareader::<UserType>(&topic_b, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    for writer_cond in [
+        writer_a.get_statuscondition(),
+        writer_b.get_statuscondition(),
+    ] {
+        writer_cond
+            .set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(writer_cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    for (writer, value) in [(&writer_a, 1), (&writer_b, 2), (&writer_a, 3)] {
+        writer.write(&UserType(value), None).unwrap();
+        writer
+            .wait_for_acknowledgments(Duration::new(10, 0))
+            .unwrap();
+    }
+
+    assert!(matches!(
+        subscriber.get_datareaders::<UserType>(
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE
+        ),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    subscriber.begin_access().unwrap();
+    let reader_list = subscriber
+        .get_datareaders::<UserType>(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        reader_list
+            .iter()
+            .map(|r| r.get_instance_handle())
+            .collect::<Vec<_>>(),
+        vec![
+            reader_a.get_instance_handle(),
+            reader_b.get_instance_handle(),
+            reader_a.get_instance_handle()
+        ]
+    );
+    let values: Vec<_> = reader_list
+        .iter()
+        .map(|r| {
+            r.take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+                .unwrap()[0]
+                .data()
+                .unwrap()
+        })
+        .collect();
+    subscriber.end_access().unwrap();
+
+    assert_eq!(values, vec![UserType(1), UserType(2), UserType(3)]);
+}
+
+#[test]
+fn group_coherent_access_defers_samples_received_during_access() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserType>("MyTopic", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let presentation = PresentationQosPolicy {
+        access_scope: PresentationQosPolicyAccessScopeKind::Group,
+        coherent_access: true,
+        ordered_access: false,
+    };
+    let publisher = participant
+        .create_publisher(
+            QosKind::Specific(PublisherQos {
+                presentation: presentation.clone(),
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Specific(SubscriberQos {
+                presentation,
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(DataWriterQos {
+                reliability: ReliabilityQosPolicy {
+                    kind: ReliabilityQosPolicyKind::Reliable,
+                    max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+                },
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<UserType>(
+            &topic,
+            QosKind::Specific(DataReaderQos {
+                reliability: ReliabilityQosPolicy {
+                    kind: ReliabilityQosPolicyKind::Reliable,
+                    max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+                },
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let writer_cond = writer.get_statuscondition();
+    writer_cond
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(writer_cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    subscriber.begin_access().unwrap();
+    writer.write(&UserType(1), None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    assert_eq!(
+        reader.read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+    subscriber.end_access().unwrap();
+
+    let samples = reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data().unwrap(), UserType(1));
+}
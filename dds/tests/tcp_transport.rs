@@ -0,0 +1,212 @@
+use std::io::Read;
+
+use dust_dds::{
+    configuration::DustDdsConfigurationBuilder,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
+    },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u32,
+}
+
+fn free_tcp_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+#[test]
+fn tcp_peers_are_rejected_without_tcp_port() {
+    assert!(DustDdsConfigurationBuilder::new()
+        .tcp_peers(vec!["127.0.0.1:7400".parse().unwrap()])
+        .build()
+        .is_err());
+}
+
+#[test]
+fn zero_tcp_max_connections_is_rejected() {
+    assert!(DustDdsConfigurationBuilder::new()
+        .tcp_port(Some(0))
+        .tcp_max_connections(0)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn tcp_connections_above_max_connections_are_closed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let port = free_tcp_port();
+    let factory = DomainParticipantFactory::new();
+    factory
+        .set_configuration(
+            DustDdsConfigurationBuilder::new()
+                .tcp_port(Some(port))
+                .tcp_max_connections(1)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    let _participant = factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let mut first_connection = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+    // Wait for the first connection to be accepted before opening the second one
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let mut second_connection = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+
+    let is_timeout = |r: std::io::Result<usize>| {
+        r.is_err_and(|e| {
+            matches!(
+                e.kind(),
+                std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+            )
+        })
+    };
+    let mut buffer = [0; 1];
+    // The second connection is closed by the participant while the first one stays open
+    second_connection
+        .set_read_timeout(Some(std::time::Duration::from_secs(5)))
+        .unwrap();
+    let result = second_connection.read(&mut buffer);
+    assert!(matches!(result, Ok(0)) || (result.is_err() && !is_timeout(result)));
+    first_connection
+        .set_read_timeout(Some(std::time::Duration::from_millis(500)))
+        .unwrap();
+    assert!(is_timeout(first_connection.read(&mut buffer)));
+}
+
+#[test]
+fn tcp_participants_communicate_without_udp_discovery() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let server_port = free_tcp_port();
+
+    // Neither participant joins the multicast groups, so they only discover each other through
+    // the TCP connection opened by the client to the server
+    let server_factory = DomainParticipantFactory::new();
+    server_factory
+        .set_configuration(
+            DustDdsConfigurationBuilder::multicast_free()
+                .tcp_port(Some(server_port))
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    let client_factory = DomainParticipantFactory::new();
+    client_factory
+        .set_configuration(
+            DustDdsConfigurationBuilder::multicast_free()
+                .tcp_port(Some(0))
+                .tcp_peers(vec![([127, 0, 0, 1], server_port).into()])
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+    let server_participant = server_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let client_participant = client_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let reliability = ReliabilityQosPolicy {
+        kind: ReliabilityQosPolicyKind::Reliable,
+        max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+    };
+    let client_topic = client_participant
+        .create_topic::<KeyedData>(
+            "KeyedDataTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer = client_participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter(
+            &client_topic,
+            QosKind::Specific(DataWriterQos {
+                reliability: reliability.clone(),
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let server_topic = server_participant
+        .create_topic::<KeyedData>(
+            "KeyedDataTopic",
+            "KeyedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader = server_participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader::<KeyedData>(
+            &server_topic,
+            QosKind::Specific(DataReaderQos {
+                reliability,
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data = KeyedData { id: 1, value: 7 };
+    writer.write(&data, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data().unwrap(), data);
+    assert_eq!(
+        server_participant
+            .get_discovered_participants()
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(
+        client_participant
+            .get_discovered_participants()
+            .unwrap()
+            .len(),
+        2
+    );
+}
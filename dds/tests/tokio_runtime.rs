@@ -25,9 +25,62 @@ struct UserData {
 
 #[tokio::test]
 async fn dust_dds_should_run_inside_tokio_runtime() {
+    write_and_read_sample(DomainParticipantFactoryAsync::get_instance()).await;
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test(flavor = "multi_thread")]
+async fn dust_dds_should_run_on_injected_tokio_runtime() {
+    let participant_factory =
+        DomainParticipantFactoryAsync::new_with_runtime(tokio::runtime::Handle::current());
+    write_and_read_sample(&participant_factory).await;
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn sync_api_should_run_on_injected_tokio_runtime() {
+    use dust_dds::domain::domain_participant_factory::DomainParticipantFactory;
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .build()
+        .unwrap();
+    let participant_factory = DomainParticipantFactory::new_with_runtime(runtime.handle().clone());
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<UserData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let _reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while writer.get_matched_subscriptions().unwrap().is_empty() {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    participant.delete_contained_entities().unwrap();
+    participant_factory
+        .delete_participant(&participant)
+        .unwrap();
+}
+
+async fn write_and_read_sample(participant_factory: &DomainParticipantFactoryAsync) {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
-    let participant_factory = DomainParticipantFactoryAsync::get_instance();
     let participant = participant_factory
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .await
@@ -1,6 +1,7 @@
 mod utils;
 use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
 use dust_dds::{
+    configuration::{DeserializationLimits, DustDdsConfigurationBuilder},
     dds_async::{
         data_reader_listener::DataReaderListenerAsync,
         data_writer_listener::DataWriterListenerAsync,
@@ -8,8 +9,12 @@ use dust_dds::{
     },
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        qos::{DataReaderQos, DataWriterQos, QosKind},
-        qos_policy::{ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        error::DdsResult,
+        qos::{DataReaderQos, DataWriterQos, DomainParticipantQos, QosKind},
+        qos_policy::{
+            DataRepresentationQosPolicy, EntityFactoryQosPolicy, ReliabilityQosPolicy,
+            ReliabilityQosPolicyKind, XCDR2_DATA_REPRESENTATION,
+        },
         status::{StatusKind, NO_STATUS},
         time::{Duration, DurationKind},
         wait_set::{Condition, WaitSet},
@@ -19,7 +24,7 @@ use dust_dds::{
         data_reader_listener::DataReaderListener,
         sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
     },
-    topic_definition::type_support::DdsType,
+    topic_definition::type_support::{DdsDeserialize, DdsType},
 };
 
 #[derive(DdsType)]
@@ -461,6 +466,550 @@ fn nested_types_should_read_and_write() {
     assert_eq!(samples[0].data().unwrap(), data);
 }
 
+#[test]
+fn xcdr2_representation_types_should_read_and_write() {
+    #[derive(PartialEq, Eq, Debug, DdsType)]
+    #[dust_dds(extensibility = "Appendable")]
+    struct InnerType {
+        a: i32,
+        b: u8,
+    }
+
+    #[derive(PartialEq, Eq, Debug, DdsType)]
+    #[dust_dds(extensibility = "Mutable")]
+    struct OuterType {
+        #[dust_dds(key, id = 1)]
+        id: u8,
+        #[dust_dds(id = 2)]
+        inner: InnerType,
+        #[dust_dds(id = 3)]
+        values: Vec<u16>,
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<OuterType>("MyTopic", "OuterType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let representation = DataRepresentationQosPolicy {
+        value: vec![XCDR2_DATA_REPRESENTATION],
+    };
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        representation: representation.clone(),
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        representation,
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<OuterType>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data = OuterType {
+        id: 1,
+        inner: InnerType { a: 20, b: 5 },
+        values: vec![3, 4],
+    };
+
+    writer.write(&data, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data);
+    assert_eq!(
+        samples[0].sample_info().instance_handle,
+        writer.lookup_instance(&data).unwrap().unwrap()
+    );
+}
+
+#[test]
+fn mutable_type_with_additional_members_should_be_read_by_previous_version() {
+    mod v1 {
+        use super::*;
+        #[derive(PartialEq, Eq, Debug, DdsType)]
+        #[dust_dds(extensibility = "mutable")]
+        pub struct SensorType {
+            #[dust_dds(key, id = 10)]
+            pub id: u8,
+            #[dust_dds(id = 12)]
+            pub value: i32,
+        }
+    }
+    mod v2 {
+        use super::*;
+        #[derive(PartialEq, Eq, Debug, DdsType)]
+        #[dust_dds(extensibility = "mutable")]
+        pub struct SensorType {
+            #[dust_dds(id = 11)]
+            pub unit: String,
+            #[dust_dds(key, id = 10)]
+            pub id: u8,
+            #[dust_dds(id = 13)]
+            pub history: Vec<i32>,
+            #[dust_dds(id = 12)]
+            pub value: i32,
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant_v1 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant_v2 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic_v1 = participant_v1
+        .create_topic::<v1::SensorType>("Sensor", "SensorType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic_v2 = participant_v2
+        .create_topic::<v2::SensorType>("Sensor", "SensorType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = participant_v2
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter(&topic_v2, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = participant_v1
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader::<v1::SensorType>(
+            &topic_v1,
+            QosKind::Specific(reader_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data = v2::SensorType {
+        unit: "mV".to_string(),
+        id: 7,
+        history: vec![1, 2, 3],
+        value: -40,
+    };
+    writer.write(&data, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(
+        samples[0].data().unwrap(),
+        v1::SensorType { id: 7, value: -40 }
+    );
+    assert_eq!(
+        samples[0].sample_info().instance_handle,
+        writer.lookup_instance(&data).unwrap().unwrap()
+    );
+}
+
+#[test]
+fn appendable_type_with_additional_members_should_be_read_by_previous_version() {
+    mod v1 {
+        use super::*;
+        #[derive(PartialEq, Eq, Debug, DdsType)]
+        #[dust_dds(extensibility = "appendable")]
+        pub struct PointType {
+            pub x: i32,
+            pub y: i32,
+        }
+
+        #[derive(PartialEq, Eq, Debug, DdsType)]
+        #[dust_dds(extensibility = "appendable")]
+        pub struct PathType {
+            #[dust_dds(key)]
+            pub name: String,
+            pub start: PointType,
+            pub end: PointType,
+        }
+    }
+    mod v2 {
+        use super::*;
+        #[derive(PartialEq, Eq, Debug, DdsType)]
+        #[dust_dds(extensibility = "appendable")]
+        pub struct PointType {
+            pub x: i32,
+            pub y: i32,
+            pub z: i32,
+        }
+
+        #[derive(PartialEq, Eq, Debug, DdsType)]
+        #[dust_dds(extensibility = "appendable")]
+        pub struct PathType {
+            #[dust_dds(key)]
+            pub name: String,
+            pub start: PointType,
+            pub end: PointType,
+            pub length: u32,
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant_v1 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant_v2 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic_v1 = participant_v1
+        .create_topic::<v1::PathType>("Path", "PathType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic_v2 = participant_v2
+        .create_topic::<v2::PathType>("Path", "PathType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let representation = DataRepresentationQosPolicy {
+        value: vec![XCDR2_DATA_REPRESENTATION],
+    };
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        representation: representation.clone(),
+        ..Default::default()
+    };
+    let writer = participant_v2
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter(&topic_v2, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        representation,
+        ..Default::default()
+    };
+    let reader = participant_v1
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader::<v1::PathType>(
+            &topic_v1,
+            QosKind::Specific(reader_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer
+        .write(
+            &v2::PathType {
+                name: "diagonal".to_string(),
+                start: v2::PointType { x: 1, y: 2, z: 3 },
+                end: v2::PointType { x: 4, y: 5, z: 6 },
+                length: 5,
+            },
+            None,
+        )
+        .unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(
+        samples[0].data().unwrap(),
+        v1::PathType {
+            name: "diagonal".to_string(),
+            start: v1::PointType { x: 1, y: 2 },
+            end: v1::PointType { x: 4, y: 5 },
+        }
+    );
+}
+
+#[test]
+fn optional_members_should_read_and_write() {
+    #[derive(PartialEq, Eq, Debug, DdsType)]
+    struct OptionalType {
+        #[dust_dds(optional)]
+        label: Option<String>,
+        #[dust_dds(key)]
+        id: u8,
+        #[dust_dds(optional)]
+        value: Option<i32>,
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<OptionalType>("MyTopic", "OptionalType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<OptionalType>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = OptionalType {
+        label: Some("first".to_string()),
+        id: 1,
+        value: None,
+    };
+    let data2 = OptionalType {
+        label: None,
+        id: 2,
+        value: Some(-3),
+    };
+    writer.write(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), data1);
+    assert_eq!(samples[1].data().unwrap(), data2);
+    assert_ne!(
+        samples[0].sample_info().instance_handle,
+        samples[1].sample_info().instance_handle
+    );
+}
+
+#[test]
+fn appendable_type_with_optional_member_should_read_previous_version() {
+    mod v1 {
+        use super::*;
+        #[derive(PartialEq, Eq, Debug, DdsType)]
+        #[dust_dds(extensibility = "appendable")]
+        pub struct StatusType {
+            #[dust_dds(key)]
+            pub id: u8,
+            pub code: u16,
+        }
+    }
+    mod v2 {
+        use super::*;
+        #[derive(PartialEq, Eq, Debug, DdsType)]
+        #[dust_dds(extensibility = "appendable")]
+        pub struct StatusType {
+            #[dust_dds(key)]
+            pub id: u8,
+            pub code: u16,
+            #[dust_dds(optional)]
+            pub description: Option<String>,
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant_v1 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant_v2 = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic_v1 = participant_v1
+        .create_topic::<v1::StatusType>("Status", "StatusType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic_v2 = participant_v2
+        .create_topic::<v2::StatusType>("Status", "StatusType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let representation = DataRepresentationQosPolicy {
+        value: vec![XCDR2_DATA_REPRESENTATION],
+    };
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        representation: representation.clone(),
+        ..Default::default()
+    };
+    let writer = participant_v1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter(&topic_v1, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        representation,
+        ..Default::default()
+    };
+    let reader = participant_v2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader::<v2::StatusType>(
+            &topic_v2,
+            QosKind::Specific(reader_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer
+        .write(&v1::StatusType { id: 4, code: 200 }, None)
+        .unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(
+        samples[0].data().unwrap(),
+        v2::StatusType {
+            id: 4,
+            code: 200,
+            description: None,
+        }
+    );
+}
+
 #[test]
 fn foo_xtypes_union_should_read_and_write() {
     #[derive(Clone, Debug, PartialEq, DdsType)]
@@ -537,3 +1086,200 @@ fn foo_xtypes_union_should_read_and_write() {
     assert_eq!(samples.len(), 1);
     assert_eq!(samples[0].data().unwrap(), data);
 }
+
+#[test]
+fn sequence_above_max_sequence_length_is_rejected() {
+    #[derive(Clone, Debug, PartialEq, DdsType)]
+    struct SequenceData {
+        #[dust_dds(key)]
+        id: u8,
+        value: Vec<u16>,
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::new();
+    let configuration = DustDdsConfigurationBuilder::new()
+        .deserialization_limits(DeserializationLimits {
+            max_sequence_length: 4,
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+    domain_participant_factory
+        .set_configuration(configuration)
+        .unwrap();
+
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<SequenceData>("MyTopic", "SequenceData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<SequenceData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data_at_limit = SequenceData {
+        id: 1,
+        value: vec![1, 2, 3, 4],
+    };
+    let data_above_limit = SequenceData {
+        id: 2,
+        value: vec![1, 2, 3, 4, 5],
+    };
+    writer.write(&data_at_limit, None).unwrap();
+    writer.write(&data_above_limit, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), data_at_limit);
+    assert!(samples[1].data().is_err());
+}
+
+#[test]
+fn writer_enabled_by_its_publisher_uses_xcdr2_representation() {
+    #[derive(Clone, Debug, PartialEq, DdsType)]
+    struct KeyedData {
+        #[dust_dds(key)]
+        id: u8,
+        value: u32,
+    }
+
+    struct SerializedData(Vec<u8>);
+    impl<'de> DdsDeserialize<'de> for SerializedData {
+        fn deserialize_data(serialized_data: &'de [u8]) -> DdsResult<Self> {
+            Ok(Self(serialized_data.to_vec()))
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    // The entities are only enabled once their parent is explicitly enabled
+    let participant_qos = DomainParticipantQos {
+        entity_factory: EntityFactoryQosPolicy {
+            autoenable_created_entities: false,
+        },
+        ..Default::default()
+    };
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(
+            domain_id,
+            QosKind::Specific(participant_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    topic.enable().unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<KeyedData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        representation: DataRepresentationQosPolicy {
+            value: vec![XCDR2_DATA_REPRESENTATION],
+        },
+        ..Default::default()
+    };
+    writer.set_qos(QosKind::Specific(writer_qos)).unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        representation: DataRepresentationQosPolicy {
+            value: vec![XCDR2_DATA_REPRESENTATION],
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    publisher.enable().unwrap();
+    subscriber.enable().unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data = KeyedData { id: 1, value: 2 };
+    writer.write(&data, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let serialized_samples = subscriber
+        .lookup_datareader::<SerializedData>("MyTopic")
+        .unwrap()
+        .unwrap()
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    // Representation identifier of XCDR2 final types in little endian
+    assert_eq!(serialized_samples[0].data().unwrap().0[..2], [0x00, 0x07]);
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data().unwrap(), data);
+}
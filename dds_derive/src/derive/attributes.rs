@@ -1,4 +1,4 @@
-use syn::{spanned::Spanned, DeriveInput, Expr, Field, Result};
+use syn::{DeriveInput, Expr, Field, Result};
 
 pub enum Extensibility {
     Final,
@@ -16,22 +16,22 @@ pub fn get_input_extensibility(input: &DeriveInput) -> Result<Extensibility> {
         xtypes_attribute.parse_nested_meta(|meta| {
             if meta.path.is_ident("extensibility") {
                 let format_str: syn::LitStr = meta.value()?.parse()?;
-                match format_str.value().as_ref() {
-                    "Final" => {
+                match format_str.value().to_ascii_lowercase().as_ref() {
+                    "final" => {
                         extensibility = Extensibility::Final;
                         Ok(())
                     }
-                    "Appendable" => {
+                    "appendable" => {
                         extensibility = Extensibility::Appendable;
                         Ok(())
                     }
-                    "Mutable" => {
+                    "mutable" => {
                         extensibility = Extensibility::Mutable;
                         Ok(())
                     }
                     _ => Err(syn::Error::new(
-                        meta.path.span(),
-                        r#"Invalid format specified. Valid options are "Final", "Appendable", "Mutable". "#,
+                        format_str.span(),
+                        r#"Invalid extensibility specified. Valid options are "final", "appendable", "mutable". "#,
                     )),
                 }
             } else {
@@ -45,11 +45,17 @@ pub fn get_input_extensibility(input: &DeriveInput) -> Result<Extensibility> {
 pub struct FieldAttributes {
     pub key: bool,
     pub id: Option<Expr>,
+    pub optional: bool,
+}
+
+pub fn is_field_optional(field: &Field) -> bool {
+    matches!(&field.ty, syn::Type::Path(field_type_path) if field_type_path.path.segments[0].ident == "Option")
 }
 
 pub fn get_field_attributes(field: &Field) -> syn::Result<FieldAttributes> {
     let mut key = false;
     let mut id = None;
+    let mut optional = false;
     if let Some(xtypes_attribute) = field
         .attrs
         .iter()
@@ -60,9 +66,11 @@ pub fn get_field_attributes(field: &Field) -> syn::Result<FieldAttributes> {
                 key = true;
             } else if meta.path.is_ident("id") {
                 id = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("optional") {
+                optional = true;
             }
             Ok(())
         })?;
     }
-    Ok(FieldAttributes { key, id })
+    Ok(FieldAttributes { key, id, optional })
 }
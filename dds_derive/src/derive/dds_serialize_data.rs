@@ -18,6 +18,13 @@ pub fn expand_dds_serialize_data(input: &DeriveInput) -> Result<TokenStream> {
                     fn serialize_data(&self) -> dust_dds::infrastructure::error::DdsResult<Vec<u8>> {
                         #serialize_function
                     }
+
+                    fn serialize_data_w_representation(
+                        &self,
+                        representation: dust_dds::infrastructure::qos_policy::DataRepresentationId,
+                    ) -> dust_dds::infrastructure::error::DdsResult<Vec<u8>> {
+                        dust_dds::topic_definition::type_support::serialize_rtps_xtypes_le(self, representation)
+                    }
                 }
             })
         }
@@ -58,6 +65,13 @@ pub fn expand_dds_deserialize_data(input: &DeriveInput) -> Result<TokenStream> {
                     fn deserialize_data(mut serialized_data: &'__de [u8]) -> dust_dds::infrastructure::error::DdsResult<Self> {
                         #deserialize_function
                     }
+
+                    fn deserialize_data_w_max_sequence_length(
+                        mut serialized_data: &'__de [u8],
+                        max_sequence_length: usize,
+                    ) -> dust_dds::infrastructure::error::DdsResult<Self> {
+                        dust_dds::topic_definition::type_support::deserialize_rtps_encapsulated_data_w_max_sequence_length(&mut serialized_data, max_sequence_length)
+                    }
                 }
             })
         }
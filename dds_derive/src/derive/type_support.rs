@@ -1,13 +1,9 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{spanned::Spanned, DeriveInput, Field, Result, Type};
+use syn::{spanned::Spanned, DeriveInput, Result, Type};
 
 use super::attributes::{get_field_attributes, get_input_extensibility, Extensibility};
 
-fn is_field_optional(field: &Field) -> bool {
-    matches!(&field.ty, syn::Type::Path(field_type_path) if field_type_path.path.segments[0].ident == "Option")
-}
-
 fn get_type_identifier(type_: &Type) -> Result<TokenStream> {
     match type_ {
         syn::Type::Array(field_type_array) => {
@@ -228,9 +224,11 @@ pub fn expand_type_support(input: &DeriveInput) -> Result<TokenStream> {
                     .as_ref()
                     .map(|i| i.to_string())
                     .unwrap_or(field_index.to_string());
-                let is_optional = is_field_optional(field);
+                let is_optional = field_attributes.optional;
                 let member_type_id = get_type_identifier(&field.ty)?;
                 let is_key = field_attributes.key;
+                // Only the key members must be understood by the readers to be assignable
+                let is_must_understand = is_key;
                 member_seq.extend(
                     quote! {dust_dds::xtypes::type_object::CompleteStructMember {
                         common: dust_dds::xtypes::type_object::CommonStructMember {
@@ -240,7 +238,7 @@ pub fn expand_type_support(input: &DeriveInput) -> Result<TokenStream> {
                                     dust_dds::xtypes::dynamic_type::TryConstructKind::Discard,
                                 is_external: false,
                                 is_optional: #is_optional,
-                                is_must_undestand: true,
+                                is_must_undestand: #is_must_understand,
                                 is_key: #is_key,
                             },
                             member_type_id:
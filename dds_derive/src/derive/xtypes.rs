@@ -1,5 +1,5 @@
 use super::{
-    attributes::{get_field_attributes, get_input_extensibility, Extensibility},
+    attributes::{get_field_attributes, get_input_extensibility, is_field_optional, Extensibility},
     enum_support::{
         get_enum_bitbound, is_enum_xtypes_union, read_enum_variant_discriminant_mapping, BitBound,
     },
@@ -36,45 +36,46 @@ pub fn expand_xtypes_serialize(input: &DeriveInput) -> Result<TokenStream> {
             };
 
             for (field_index, field) in data_struct.fields.iter().enumerate() {
-                match &field.ident {
-                    Some(field_name) => {
-                        let field_name_str = field_name.to_string();
-                        match extensibility {
-                            Extensibility::Final => field_serialization.extend(
-                                quote! { dust_dds::xtypes::serializer::SerializeFinalStruct::serialize_field(&mut s, &self.#field_name, #field_name_str)?;},
-                            ),
-                            Extensibility::Appendable => field_serialization.extend(
-                                quote! { dust_dds::xtypes::serializer::SerializeAppendableStruct::serialize_field(&mut s, &self.#field_name, #field_name_str)?;},
-                            ),
-                            Extensibility::Mutable => {
-                                let id = get_field_attributes(field)?.id.ok_or(syn::Error::new(field.span(), "Mutable struct must define id attribute for every field"))?;
-                                field_serialization.extend(
-                                    quote! { dust_dds::xtypes::serializer::SerializeMutableStruct::serialize_field(&mut s, &self.#field_name, #id, #field_name_str)?;},
-                                );
-                            }
-                        }
-                    }
+                let (field_member, field_name_str) = match &field.ident {
+                    Some(field_name) => (quote! {#field_name}, field_name.to_string()),
                     None => {
                         let index = Index::from(field_index);
-                        let index_str = format!("{:?}", field_index);
-                        match extensibility {
-                            Extensibility::Final => field_serialization
-                                .extend(quote! { dust_dds::xtypes::serializer::SerializeFinalStruct::serialize_field(&mut s, &self.#index, #index_str)?;}),
-                            Extensibility::Appendable => field_serialization
-                                .extend(quote! { dust_dds::xtypes::serializer::SerializeAppendableStruct::serialize_field(&mut s, &self.#index, #index_str)?;}),
-                            Extensibility::Mutable => {
-                                let id = get_field_attributes(field)?.id.ok_or(syn::Error::new(field.span(), "Mutable struct must define id attribute for every field"))?;
-                                field_serialization.extend(
-                                    quote! { dust_dds::xtypes::serializer::SerializeMutableStruct::serialize_field(&mut s, &self.#index, #id, #index_str)?;},
-                                );
-                            }
-                        }
+                        (quote! {#index}, format!("{:?}", field_index))
+                    }
+                };
+                let field_attributes = get_field_attributes(field)?;
+                let serialize_field = if field_attributes.optional {
+                    if !is_field_optional(field) {
+                        return Err(syn::Error::new(
+                            field.span(),
+                            "Optional field must be of type Option<T>",
+                        ));
+                    }
+                    quote! {serialize_optional_field}
+                } else {
+                    quote! {serialize_field}
+                };
+                match extensibility {
+                    Extensibility::Final => field_serialization.extend(
+                        quote! { dust_dds::xtypes::serializer::SerializeFinalStruct::#serialize_field(&mut s, &self.#field_member, #field_name_str)?;},
+                    ),
+                    Extensibility::Appendable => field_serialization.extend(
+                        quote! { dust_dds::xtypes::serializer::SerializeAppendableStruct::#serialize_field(&mut s, &self.#field_member, #field_name_str)?;},
+                    ),
+                    Extensibility::Mutable => {
+                        let id = field_attributes.id.ok_or(syn::Error::new(field.span(), "Mutable struct must define id attribute for every field"))?;
+                        field_serialization.extend(
+                            quote! { dust_dds::xtypes::serializer::SerializeMutableStruct::#serialize_field(&mut s, &self.#field_member, #id, #field_name_str)?;},
+                        );
                     }
                 }
             }
 
             match extensibility {
-                Extensibility::Final | Extensibility::Appendable => (),
+                Extensibility::Final => (),
+                Extensibility::Appendable => field_serialization.extend(
+                    quote! { dust_dds::xtypes::serializer::SerializeAppendableStruct::end(s)?;},
+                ),
                 Extensibility::Mutable => field_serialization.extend(
                     quote! { dust_dds::xtypes::serializer::SerializeMutableStruct::end(s)?;},
                 ),
@@ -247,41 +248,48 @@ pub fn expand_xtypes_deserialize(input: &DeriveInput) -> Result<TokenStream> {
                         .expect("Not empty")
                         .ident
                         .is_none();
-                    if is_tuple {
-                        for (index, field) in data_struct.fields.iter().enumerate() {
-                            let index_str = format!("{:?}", index);
-                            match extensibility {
-                                Extensibility::Final => field_deserialization
-                                    .extend(quote! { dust_dds::xtypes::deserializer::DeserializeFinalStruct::deserialize_field(&mut d, #index_str)?,}),
-                                Extensibility::Appendable => field_deserialization
-                                    .extend(quote! { dust_dds::xtypes::deserializer::DeserializeAppendableStruct::deserialize_field(&mut d, #index_str)?,}),
-                                Extensibility::Mutable => {
-                                    let id = get_field_attributes(field)?.id.ok_or(syn::Error::new(field.span(), "Mutable struct must define id attribute for every field"))?;
-                                    field_deserialization
-                                        .extend(quote! { dust_dds::xtypes::deserializer::DeserializeMutableStruct::deserialize_field(&mut d, #id, #index_str)?,});
-                                }
+                    for (index, field) in data_struct.fields.iter().enumerate() {
+                        let field_name_str = match &field.ident {
+                            Some(field_name) => field_name.to_string(),
+                            None => format!("{:?}", index),
+                        };
+                        let field_attributes = get_field_attributes(field)?;
+                        let deserialize_field = if field_attributes.optional {
+                            if !is_field_optional(field) {
+                                return Err(syn::Error::new(
+                                    field.span(),
+                                    "Optional field must be of type Option<T>",
+                                ));
+                            }
+                            quote! {deserialize_optional_field}
+                        } else {
+                            quote! {deserialize_field}
+                        };
+                        let field_value = match extensibility {
+                            Extensibility::Final => {
+                                quote! { dust_dds::xtypes::deserializer::DeserializeFinalStruct::#deserialize_field(&mut d, #field_name_str)?}
+                            }
+                            Extensibility::Appendable => {
+                                quote! { dust_dds::xtypes::deserializer::DeserializeAppendableStruct::#deserialize_field(&mut d, #field_name_str)?}
+                            }
+                            Extensibility::Mutable => {
+                                let id = field_attributes.id.ok_or(syn::Error::new(
+                                    field.span(),
+                                    "Mutable struct must define id attribute for every field",
+                                ))?;
+                                quote! { dust_dds::xtypes::deserializer::DeserializeMutableStruct::#deserialize_field(&mut d, #id, #field_name_str)?}
+                            }
+                        };
+                        match &field.ident {
+                            Some(field_name) => {
+                                field_deserialization.extend(quote! {#field_name: #field_value,})
                             }
+                            None => field_deserialization.extend(quote! {#field_value,}),
                         }
+                    }
+                    if is_tuple {
                         struct_deserialization.extend(quote! {Self(#field_deserialization)})
                     } else {
-                        for field in data_struct.fields.iter() {
-                            let field_name = field.ident.as_ref().expect("Is not a tuple");
-                            let field_name_str = field_name.to_string();
-                            match extensibility {
-                                Extensibility::Final => field_deserialization.extend(
-                                    quote! {#field_name:  dust_dds::xtypes::deserializer::DeserializeFinalStruct::deserialize_field(&mut d, #field_name_str)?,},
-                                ),
-                                Extensibility::Appendable => field_deserialization.extend(
-                                    quote! {#field_name:  dust_dds::xtypes::deserializer::DeserializeAppendableStruct::deserialize_field(&mut d, #field_name_str)?,},
-                                ),
-                                Extensibility::Mutable => {
-                                    let id = get_field_attributes(field)?.id.ok_or(syn::Error::new(field.span(), "Mutable struct must define id attribute for every field"))?;
-                                    field_deserialization.extend(
-                                        quote! {#field_name:  dust_dds::xtypes::deserializer::DeserializeMutableStruct::deserialize_field(&mut d, #id, #field_name_str)?,},
-                                    );
-                                }
-                            }
-                        }
                         struct_deserialization.extend(quote! {Self{
                             #field_deserialization
                         }})
@@ -474,6 +482,7 @@ mod tests {
                     let mut s =  dust_dds::xtypes::serializer::XTypesSerializer::serialize_appendable_struct(serializer)?;
                      dust_dds::xtypes::serializer::SerializeAppendableStruct::serialize_field(&mut s, &self.x, \"x\")?;
                      dust_dds::xtypes::serializer::SerializeAppendableStruct::serialize_field(&mut s, &self.y, \"y\")?;
+                     dust_dds::xtypes::serializer::SerializeAppendableStruct::end(s)?;
                     Ok(())
                 }
             }
@@ -706,6 +715,171 @@ mod tests {
         );
     }
 
+    #[test]
+    fn xtypes_deserialize_lowercase_extensibility() {
+        let input = syn::parse2::<DeriveInput>(
+            "
+            #[dust_dds(extensibility = \"appendable\")]
+            struct MyData {
+                x: u32,
+            }
+        "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        let output_token_stream = expand_xtypes_deserialize(&input).unwrap();
+        let result = syn::parse2::<ItemImpl>(output_token_stream).unwrap();
+        let expected = syn::parse2::<ItemImpl>(
+            "
+            impl<'__de>  dust_dds::xtypes::deserialize::XTypesDeserialize<'__de> for MyData {
+                fn deserialize(deserializer: impl  dust_dds::xtypes::deserializer::XTypesDeserializer<'__de>) -> Result<Self,  dust_dds::xtypes::error::XTypesError> {
+                    let mut d =  dust_dds::xtypes::deserializer::XTypesDeserializer::deserialize_appendable_struct(deserializer)?;
+                    Ok(Self {
+                        x:  dust_dds::xtypes::deserializer::DeserializeAppendableStruct::deserialize_field(&mut d, \"x\")?,
+                    })
+                }
+            }
+            "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            expected,
+            "\n R: {:?} \n \n L: {:?} \n ",
+            result.clone().into_token_stream().to_string(),
+            expected.clone().into_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn xtypes_invalid_extensibility_is_rejected() {
+        let input = syn::parse2::<DeriveInput>(
+            "
+            #[dust_dds(extensibility = \"extendable\")]
+            struct MyData {
+                x: u32,
+            }
+        "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert!(expand_xtypes_serialize(&input).is_err());
+        assert!(expand_xtypes_deserialize(&input).is_err());
+    }
+
+    #[test]
+    fn xtypes_serialize_mutable_struct_with_optional_field() {
+        let input = syn::parse2::<DeriveInput>(
+            "
+            #[dust_dds(extensibility = \"Mutable\")]
+            struct MyData {
+                #[dust_dds(id=1)]
+                x: u32,
+                #[dust_dds(id=2, optional)]
+                y: Option<u32>,
+            }
+        "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        let output_token_stream = expand_xtypes_serialize(&input).unwrap();
+        let result = syn::parse2::<ItemImpl>(output_token_stream).unwrap();
+        let expected = syn::parse2::<ItemImpl>(
+            "
+            impl dust_dds::xtypes::serialize::XTypesSerialize for MyData {
+                fn serialize(&self, serializer: impl dust_dds::xtypes::serialize::XTypesSerializer) -> Result<(), dust_dds::xtypes::error::XTypesError> {
+                    let mut s = dust_dds::xtypes::serializer::XTypesSerializer::serialize_mutable_struct(serializer)?;
+                    dust_dds::xtypes::serializer::SerializeMutableStruct::serialize_field(&mut s, &self.x, 1, \"x\")?;
+                    dust_dds::xtypes::serializer::SerializeMutableStruct::serialize_optional_field(&mut s, &self.y, 2, \"y\")?;
+                    dust_dds::xtypes::serializer::SerializeMutableStruct::end(s)?;
+                    Ok(())
+                }
+            }
+            "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            expected,
+            "\n R: {:?} \n \n L: {:?} \n ",
+            result.clone().into_token_stream().to_string(),
+            expected.clone().into_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn xtypes_deserialize_final_struct_with_optional_field() {
+        let input = syn::parse2::<DeriveInput>(
+            "
+            struct MyData {
+                x: u32,
+                #[dust_dds(optional)]
+                y: Option<u32>,
+            }
+        "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        let output_token_stream = expand_xtypes_deserialize(&input).unwrap();
+        let result = syn::parse2::<ItemImpl>(output_token_stream).unwrap();
+        let expected = syn::parse2::<ItemImpl>(
+            "
+            impl<'__de>  dust_dds::xtypes::deserialize::XTypesDeserialize<'__de> for MyData {
+                fn deserialize(deserializer: impl  dust_dds::xtypes::deserializer::XTypesDeserializer<'__de>) -> Result<Self,  dust_dds::xtypes::error::XTypesError> {
+                    let mut d =  dust_dds::xtypes::deserializer::XTypesDeserializer::deserialize_final_struct(deserializer)?;
+                    Ok(Self {
+                        x:  dust_dds::xtypes::deserializer::DeserializeFinalStruct::deserialize_field(&mut d, \"x\")?,
+                        y:  dust_dds::xtypes::deserializer::DeserializeFinalStruct::deserialize_optional_field(&mut d, \"y\")?,
+                    })
+                }
+            }
+            "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            expected,
+            "\n R: {:?} \n \n L: {:?} \n ",
+            result.clone().into_token_stream().to_string(),
+            expected.clone().into_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn xtypes_optional_field_must_be_option() {
+        let input = syn::parse2::<DeriveInput>(
+            "
+            struct MyData {
+                #[dust_dds(optional)]
+                x: u32,
+            }
+        "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert!(expand_xtypes_serialize(&input).is_err());
+        assert!(expand_xtypes_deserialize(&input).is_err());
+    }
+
     #[test]
     fn xtypes_serialize_enum() {
         let input = syn::parse2::<DeriveInput>(
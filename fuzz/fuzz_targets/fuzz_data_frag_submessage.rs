@@ -1,7 +1,10 @@
 #![no_main]
 
-use dust_dds::rtps::messages::{
-    overall_structure::SubmessageHeaderRead, submessages::data_frag::DataFragSubmessage,
+use dust_dds::{
+    configuration::DeserializationLimits,
+    rtps::messages::{
+        overall_structure::SubmessageHeaderRead, submessages::data_frag::DataFragSubmessage,
+    },
 };
 use libfuzzer_sys::{fuzz_target, Corpus};
 
@@ -9,7 +12,12 @@ fuzz_target!(|data: &[u8]| -> Corpus {
     if data.len() > 4 {
         let mut data = data;
         let header = SubmessageHeaderRead::try_read_from_bytes(&mut data).unwrap();
-        DataFragSubmessage::try_from_bytes(&header, data).ok();
+        DataFragSubmessage::try_from_bytes(
+            &header,
+            data,
+            DeserializationLimits::default().max_parameter_list_length,
+        )
+        .ok();
         Corpus::Keep
     } else {
         Corpus::Reject